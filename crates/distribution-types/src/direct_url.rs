@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use anyhow::{Context, Error, Result};
@@ -131,6 +132,15 @@ impl TryFrom<&LocalFileUrl> for pypi_types::DirectUrl {
     type Error = Error;
 
     fn try_from(value: &LocalFileUrl) -> Result<Self, Self::Error> {
+        // A local wheel or source distribution archive, as opposed to a project directory.
+        if !value.editable && value.url.to_file_path().is_ok_and(|path| path.is_file()) {
+            return Ok(Self::ArchiveUrl {
+                url: value.url.to_string(),
+                archive_info: archive_info(&value.url),
+                subdirectory: None,
+            });
+        }
+
         Ok(Self::LocalDirectory {
             url: value.url.to_string(),
             dir_info: pypi_types::DirInfo {
@@ -146,10 +156,7 @@ impl TryFrom<&DirectArchiveUrl> for pypi_types::DirectUrl {
     fn try_from(value: &DirectArchiveUrl) -> Result<Self, Self::Error> {
         Ok(Self::ArchiveUrl {
             url: value.url.to_string(),
-            archive_info: pypi_types::ArchiveInfo {
-                hash: None,
-                hashes: None,
-            },
+            archive_info: archive_info(&value.url),
             subdirectory: value.subdirectory.clone(),
         })
     }
@@ -171,6 +178,33 @@ impl TryFrom<&DirectGitUrl> for pypi_types::DirectUrl {
     }
 }
 
+/// Extract the [`pypi_types::ArchiveInfo`] for an archive URL, including any hashes embedded in
+/// its fragment (e.g., `#sha256=...`).
+fn archive_info(url: &Url) -> pypi_types::ArchiveInfo {
+    let hashes = url
+        .fragment()
+        .into_iter()
+        .flat_map(|fragment| fragment.split('&'))
+        .filter_map(|pair| pair.split_once('='))
+        .filter(|(algorithm, _)| matches!(*algorithm, "md5" | "sha256" | "sha384" | "sha512"))
+        .map(|(algorithm, digest)| (algorithm.to_string(), digest.to_string()))
+        .collect::<HashMap<_, _>>();
+
+    // The legacy `hash` field holds a single hash, for which we prefer SHA-256.
+    let hash = ["sha256", "sha512", "sha384", "md5"]
+        .into_iter()
+        .find_map(|algorithm| {
+            hashes
+                .get(algorithm)
+                .map(|digest| format!("{algorithm}={digest}"))
+        });
+
+    pypi_types::ArchiveInfo {
+        hash,
+        hashes: (!hashes.is_empty()).then_some(hashes),
+    }
+}
+
 impl From<DirectUrl> for Url {
     fn from(value: DirectUrl) -> Self {
         match value {
@@ -246,4 +280,27 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn archive_info_from_fragment() -> Result<()> {
+        let url = Url::parse("https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl#sha256=1cca6e2b8a1e1bcfb5e2ed3d2e50ee9d1c5c5c4ee47ddbed7dc6b7c8c7e1ef6d")?;
+        let direct_url = pypi_types::DirectUrl::try_from(&DirectUrl::try_from(&url)?)?;
+        let pypi_types::DirectUrl::ArchiveUrl { archive_info, .. } = direct_url else {
+            panic!("Expected an archive URL, found: {direct_url:?}");
+        };
+        assert_eq!(
+            archive_info.hash.as_deref(),
+            Some("sha256=1cca6e2b8a1e1bcfb5e2ed3d2e50ee9d1c5c5c4ee47ddbed7dc6b7c8c7e1ef6d")
+        );
+        assert_eq!(
+            archive_info
+                .hashes
+                .as_ref()
+                .and_then(|hashes| hashes.get("sha256"))
+                .map(String::as_str),
+            Some("1cca6e2b8a1e1bcfb5e2ed3d2e50ee9d1c5c5c4ee47ddbed7dc6b7c8c7e1ef6d")
+        );
+
+        Ok(())
+    }
 }
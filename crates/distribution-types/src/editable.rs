@@ -16,6 +16,10 @@ pub struct LocalEditable {
     pub path: PathBuf,
     /// The extras that should be installed.
     pub extras: Vec<ExtraName>,
+    /// Whether the editable is the local source for a dependency (e.g., on a workspace member),
+    /// in which case it's only installed if that dependency is required, rather than
+    /// unconditionally.
+    pub dependency: bool,
 }
 
 impl LocalEditable {
@@ -42,7 +42,8 @@ impl File {
     /// `TryFrom` instead of `From` to filter out files with invalid requires python version specifiers
     pub fn try_from(file: pypi_types::File, base: &Url) -> Result<Self, FileConversionError> {
         Ok(Self {
-            dist_info_metadata: file.dist_info_metadata,
+            // Prefer the PEP 714 key, falling back to the PEP 658 key used by older indexes.
+            dist_info_metadata: file.core_metadata.or(file.dist_info_metadata),
             filename: file.filename,
             hashes: file.hashes,
             requires_python: file
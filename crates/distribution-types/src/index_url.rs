@@ -6,11 +6,13 @@ use std::str::FromStr;
 
 use itertools::Either;
 use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
 use pep508_rs::{expand_env_vars, split_scheme, Scheme, VerbatimUrl};
 use uv_fs::normalize_url_path;
+use uv_normalize::PackageName;
 
 use crate::Verbatim;
 
@@ -175,11 +177,15 @@ impl Display for FlatIndexLocation {
 /// caches.
 ///
 /// From a pip perspective, this type merges `--index-url`, `--extra-index-url`, and `--find-links`.
+///
+/// Packages can also be pinned to a specific index (e.g., via `tool.uv.sources`), in which case
+/// they're only ever fetched from that index.
 #[derive(Debug, Clone)]
 pub struct IndexLocations {
     index: Option<IndexUrl>,
     extra_index: Vec<IndexUrl>,
     flat_index: Vec<FlatIndexLocation>,
+    pinned: FxHashMap<PackageName, IndexUrl>,
     no_index: bool,
 }
 
@@ -190,6 +196,7 @@ impl Default for IndexLocations {
             index: Some(DEFAULT_INDEX_URL.clone()),
             extra_index: Vec::new(),
             flat_index: Vec::new(),
+            pinned: FxHashMap::default(),
             no_index: false,
         }
     }
@@ -207,6 +214,7 @@ impl IndexLocations {
             index,
             extra_index,
             flat_index,
+            pinned: FxHashMap::default(),
             no_index,
         }
     }
@@ -229,9 +237,26 @@ impl IndexLocations {
             index: self.index.or(index),
             extra_index: self.extra_index.into_iter().chain(extra_index).collect(),
             flat_index: self.flat_index.into_iter().chain(flat_index).collect(),
+            pinned: self.pinned,
             no_index: self.no_index || no_index,
         }
     }
+
+    /// Pin the given packages to specific indexes, such that they're only fetched from the
+    /// pinned index, rather than the primary and extra indexes.
+    ///
+    /// If a package is already pinned, the existing pin will be preserved.
+    #[must_use]
+    pub fn pin(self, pinned: FxHashMap<PackageName, IndexUrl>) -> Self {
+        let mut existing = self.pinned;
+        for (package_name, index) in pinned {
+            existing.entry(package_name).or_insert(index);
+        }
+        Self {
+            pinned: existing,
+            ..self
+        }
+    }
 }
 
 impl<'a> IndexLocations {
@@ -265,6 +290,39 @@ impl<'a> IndexLocations {
         self.index().into_iter().chain(self.extra_index())
     }
 
+    /// Return an iterator over the [`IndexUrl`] entries to search for the given package.
+    ///
+    /// If the package is pinned to a specific index, only that index is returned.
+    pub fn indexes_for(
+        &'a self,
+        package_name: &PackageName,
+    ) -> impl Iterator<Item = &'a IndexUrl> + 'a {
+        if self.no_index {
+            return Either::Left(None.into_iter());
+        }
+        match self.pinned.get(package_name) {
+            Some(index) => Either::Left(Some(index).into_iter()),
+            None => Either::Right(self.indexes()),
+        }
+    }
+
+    /// Return an iterator over the [`IndexUrl`] entries to which packages are pinned.
+    ///
+    /// If `--no-index` is set, return an empty iterator.
+    pub fn pinned_index(&'a self) -> impl Iterator<Item = &'a IndexUrl> + 'a {
+        if self.no_index {
+            Either::Left(std::iter::empty())
+        } else {
+            Either::Right(self.pinned.values())
+        }
+    }
+
+    /// Return an iterator over the packages that are pinned to a specific index, along with the
+    /// index to which each is pinned.
+    pub fn pinned(&'a self) -> impl Iterator<Item = (&'a PackageName, &'a IndexUrl)> + 'a {
+        self.pinned.iter()
+    }
+
     /// Return an iterator over the [`FlatIndexLocation`] entries.
     pub fn flat_index(&'a self) -> impl Iterator<Item = &'a FlatIndexLocation> + 'a {
         self.flat_index.iter()
@@ -275,6 +333,7 @@ impl<'a> IndexLocations {
         IndexUrls {
             index: self.index.clone(),
             extra_index: self.extra_index.clone(),
+            pinned: self.pinned.clone(),
             no_index: self.no_index,
         }
     }
@@ -282,6 +341,7 @@ impl<'a> IndexLocations {
     /// Return an iterator over all [`Url`] entries.
     pub fn urls(&'a self) -> impl Iterator<Item = &'a Url> + 'a {
         self.indexes()
+            .chain(self.pinned_index())
             .map(IndexUrl::url)
             .chain(self.flat_index.iter().filter_map(|index| match index {
                 FlatIndexLocation::Path(_) => None,
@@ -297,6 +357,7 @@ impl<'a> IndexLocations {
 pub struct IndexUrls {
     index: Option<IndexUrl>,
     extra_index: Vec<IndexUrl>,
+    pinned: FxHashMap<PackageName, IndexUrl>,
     no_index: bool,
 }
 
@@ -306,6 +367,7 @@ impl Default for IndexUrls {
         Self {
             index: Some(DEFAULT_INDEX_URL.clone()),
             extra_index: Vec::new(),
+            pinned: FxHashMap::default(),
             no_index: false,
         }
     }
@@ -344,6 +406,23 @@ impl<'a> IndexUrls {
     pub fn indexes(&'a self) -> impl Iterator<Item = &'a IndexUrl> + 'a {
         self.extra_index().chain(self.index())
     }
+
+    /// Return an iterator over the [`IndexUrl`] entries to search for the given package.
+    ///
+    /// If the package is pinned to a specific index, only that index is returned. Otherwise,
+    /// returns all [`IndexUrl`] entries, as in [`IndexUrls::indexes`].
+    pub fn indexes_for(
+        &'a self,
+        package_name: &PackageName,
+    ) -> impl Iterator<Item = &'a IndexUrl> + 'a {
+        if self.no_index {
+            return Either::Left(None.into_iter());
+        }
+        match self.pinned.get(package_name) {
+            Some(index) => Either::Left(Some(index).into_iter()),
+            None => Either::Right(self.indexes()),
+        }
+    }
 }
 
 impl From<IndexLocations> for IndexUrls {
@@ -351,6 +430,7 @@ impl From<IndexLocations> for IndexUrls {
         Self {
             index: locations.index,
             extra_index: locations.extra_index,
+            pinned: locations.pinned,
             no_index: locations.no_index,
         }
     }
@@ -0,0 +1,166 @@
+use std::io;
+use std::path::Path;
+
+use fs_err as fs;
+use fs_err::File;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::record::RecordEntry;
+use crate::wheel::{copy_and_hash, read_record_file};
+use crate::Error;
+
+/// Add the bytecode (`__pycache__/*.pyc`) files compiled from a distribution's Python sources to
+/// the `RECORD` of its `.dist-info` directory, such that they're tracked alongside the sources.
+///
+/// Returns the number of bytecode files added to the `RECORD`. Bytecode files that are already
+/// listed in the `RECORD` are left as-is.
+pub fn record_bytecode(dist_info: impl AsRef<Path>) -> Result<usize, Error> {
+    let dist_info = dist_info.as_ref();
+    let Some(site_packages) = dist_info.parent() else {
+        return Ok(0);
+    };
+
+    let record_path = dist_info.join("RECORD");
+    let mut record = match File::open(&record_path) {
+        Ok(mut record_file) => read_record_file(&mut record_file)?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(Error::MissingRecord(record_path));
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let recorded = record
+        .iter()
+        .map(|entry| entry.path.clone())
+        .collect::<FxHashSet<_>>();
+
+    // The contents of each `__pycache__` directory, read at most once.
+    let mut caches: FxHashMap<String, Vec<String>> = FxHashMap::default();
+
+    let mut bytecode = Vec::new();
+    for entry in &record {
+        let Some(source) = entry.path.strip_suffix(".py") else {
+            continue;
+        };
+        let (parent, stem) = match source.rsplit_once('/') {
+            Some((parent, stem)) => (format!("{parent}/"), stem),
+            None => (String::new(), source),
+        };
+        let cache_dir = format!("{parent}__pycache__");
+
+        let cached = caches.entry(cache_dir.clone()).or_insert_with(|| {
+            fs::read_dir(site_packages.join(&cache_dir))
+                .map(|entries| {
+                    entries
+                        .filter_map(Result::ok)
+                        .filter_map(|entry| entry.file_name().into_string().ok())
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+
+        for file_name in cached.iter() {
+            if !is_bytecode_for(file_name, stem) {
+                continue;
+            }
+            let path = format!("{cache_dir}/{file_name}");
+            if recorded.contains(&path) {
+                continue;
+            }
+            let mut file = File::open(site_packages.join(&path))?;
+            let (size, hash) = copy_and_hash(&mut file, &mut io::sink())?;
+            bytecode.push(RecordEntry {
+                path,
+                hash: Some(hash),
+                size: Some(size),
+            });
+        }
+    }
+
+    if bytecode.is_empty() {
+        return Ok(0);
+    }
+
+    let count = bytecode.len();
+    record.extend(bytecode);
+    record.sort();
+
+    let mut record_writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .escape(b'"')
+        .from_path(&record_path)?;
+    for entry in record {
+        record_writer.serialize(entry)?;
+    }
+    record_writer.flush()?;
+
+    Ok(count)
+}
+
+/// Returns `true` if the file name is that of a bytecode file compiled from a module with the
+/// given stem, i.e., `{stem}.{cache_tag}.pyc` or `{stem}.{cache_tag}.opt-{level}.pyc`.
+fn is_bytecode_for(file_name: &str, stem: &str) -> bool {
+    let Some(suffix) = file_name
+        .strip_prefix(stem)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .and_then(|rest| rest.strip_suffix(".pyc"))
+    else {
+        return false;
+    };
+    match suffix.split_once('.') {
+        None => !suffix.is_empty(),
+        Some((tag, optimization)) => {
+            !tag.is_empty() && optimization.starts_with("opt-") && !optimization.contains('.')
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fs_err as fs;
+
+    use super::record_bytecode;
+
+    #[test]
+    fn record() {
+        let site_packages = tempfile::tempdir().unwrap();
+        let root = site_packages.path();
+        fs::create_dir_all(root.join("foo/__pycache__")).unwrap();
+        fs::create_dir_all(root.join("foo-1.0.0.dist-info")).unwrap();
+        fs::write(root.join("foo/__init__.py"), "").unwrap();
+        fs::write(root.join("foo/__pycache__/__init__.cpython-312.pyc"), "").unwrap();
+        fs::write(
+            root.join("foo/__pycache__/__init__.cpython-312.opt-1.pyc"),
+            "",
+        )
+        .unwrap();
+        // Bytecode for a module that doesn't belong to the distribution.
+        fs::write(root.join("foo/__pycache__/bar.cpython-312.pyc"), "").unwrap();
+        fs::write(
+            root.join("foo-1.0.0.dist-info/RECORD"),
+            "\
+foo/__init__.py,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0
+foo-1.0.0.dist-info/RECORD,,
+",
+        )
+        .unwrap();
+
+        let count = record_bytecode(root.join("foo-1.0.0.dist-info")).unwrap();
+        assert_eq!(count, 2);
+
+        let record = fs::read_to_string(root.join("foo-1.0.0.dist-info/RECORD")).unwrap();
+        assert_eq!(
+            record,
+            "\
+foo-1.0.0.dist-info/RECORD,,
+foo/__init__.py,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0
+foo/__pycache__/__init__.cpython-312.opt-1.pyc,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0
+foo/__pycache__/__init__.cpython-312.pyc,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0
+"
+        );
+
+        // Recording the bytecode again is a no-op.
+        let count = record_bytecode(root.join("foo-1.0.0.dist-info")).unwrap();
+        assert_eq!(count, 0);
+    }
+}
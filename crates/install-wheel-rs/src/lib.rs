@@ -8,18 +8,22 @@ use platform_info::PlatformInfoError;
 use thiserror::Error;
 use zip::result::ZipError;
 
+pub use bytecode::record_bytecode;
 use pep440_rs::Version;
 use platform_tags::{Arch, Os};
 use pypi_types::Scheme;
 pub use uninstall::{uninstall_wheel, Uninstall};
 use uv_fs::Simplified;
 use uv_normalize::PackageName;
+pub use verify::{verify_record, RecordMismatch};
 
+mod bytecode;
 pub mod linker;
 pub mod metadata;
 mod record;
 mod script;
 mod uninstall;
+mod verify;
 mod wheel;
 
 /// The layout of the target environment into which a wheel can be installed.
@@ -33,6 +37,9 @@ pub struct Layout {
     pub os_name: String,
     /// The [`Scheme`] paths for the interpreter.
     pub scheme: Scheme,
+    /// Whether the environment is relocatable, in which case scripts locate the interpreter
+    /// relative to themselves, rather than by its absolute path.
+    pub relocatable: bool,
 }
 
 /// Note: The caller is responsible for adding the path of the wheel we're installing.
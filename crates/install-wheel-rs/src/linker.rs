@@ -1,6 +1,7 @@
 //! Like `wheel.rs`, but for installing wheels that have already been unzipped, rather than
 //! reading from a zip file.
 
+use std::io::Write;
 use std::path::Path;
 use std::str::FromStr;
 use std::time::SystemTime;
@@ -8,13 +9,15 @@ use std::time::SystemTime;
 use fs_err as fs;
 use fs_err::{DirEntry, File};
 use reflink_copy as reflink;
-use tempfile::tempdir_in;
+use tempfile::{tempdir_in, NamedTempFile};
 use tracing::{debug, instrument};
 
 use distribution_filename::WheelFilename;
 use pep440_rs::Version;
 use pypi_types::DirectUrl;
+use uv_fs::normalize_path;
 use uv_normalize::PackageName;
+use uv_warnings::warn_user_once;
 
 use crate::script::{scripts_from_ini, Script};
 use crate::wheel::{
@@ -140,7 +143,7 @@ pub fn install_wheel(
 /// See: <https://github.com/PyO3/python-pkginfo-rs>
 ///
 /// See: <https://github.com/pypa/pip/blob/36823099a9cdd83261fdbc8c1d2a24fa2eea72ca/src/pip/_internal/utils/wheel.py#L38>
-fn find_dist_info(path: impl AsRef<Path>) -> Result<String, Error> {
+pub(crate) fn find_dist_info(path: impl AsRef<Path>) -> Result<String, Error> {
     // Iterate over `path` to find the `.dist-info` directory. It should be at the top-level.
     let Some(dist_info) = fs::read_dir(path.as_ref())?.find_map(|entry| {
         let entry = entry.ok()?;
@@ -201,6 +204,41 @@ fn parse_scripts(
     scripts_from_ini(extras, python_minor, ini)
 }
 
+/// Returns the file names of the executables that an installed distribution placed in the given
+/// scripts directory, as recorded in the `RECORD` of its `.dist-info` directory.
+///
+/// This includes both the generated entry point launchers and any scripts shipped in the wheel's
+/// `.data/scripts` directory.
+pub fn installed_scripts(dist_info: &Path, scripts: &Path) -> Result<Vec<String>, Error> {
+    let Some(site_packages) = dist_info.parent() else {
+        return Err(Error::BrokenVenv(
+            "dist-info directory is not in a site-packages directory".to_string(),
+        ));
+    };
+
+    let record_path = dist_info.join("RECORD");
+    let mut record_file = match File::open(&record_path) {
+        Ok(record_file) => record_file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(Error::MissingRecord(record_path));
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut executables = read_record_file(&mut record_file)?
+        .into_iter()
+        .filter_map(|entry| {
+            let path = normalize_path(site_packages.join(entry.path));
+            if path.parent() != Some(scripts) {
+                return None;
+            }
+            Some(path.file_name()?.to_str()?.to_string())
+        })
+        .collect::<Vec<_>>();
+    executables.sort_unstable();
+    Ok(executables)
+}
+
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum LinkMode {
@@ -210,6 +248,12 @@ pub enum LinkMode {
     Copy,
     /// Hard link packages from the wheel into the site packages.
     Hardlink,
+    /// Symbolically link packages from the wheel into the site packages.
+    ///
+    /// WARNING: The use of symlinks is discouraged, as they create tight coupling between the
+    /// cache and the target environment: removing or pruning the cache will break every
+    /// environment that was installed with symlinks.
+    Symlink,
 }
 
 impl Default for LinkMode {
@@ -223,6 +267,21 @@ impl Default for LinkMode {
 }
 
 impl LinkMode {
+    /// Detect the preferred [`LinkMode`] for installing wheels from the `cache` directory into
+    /// `site_packages`.
+    ///
+    /// Prefers cloning if the filesystem supports copy-on-write (e.g., APFS, Btrfs, XFS, or ReFS),
+    /// which gives copy semantics (i.e., modifying an installed file won't modify the cache) at
+    /// roughly the speed of hard linking. Otherwise, uses the platform default.
+    pub fn detect(cache: &Path, site_packages: &Path) -> Self {
+        if supports_reflink(cache, site_packages) {
+            debug!("Detected copy-on-write support; installing with `clone`");
+            Self::Clone
+        } else {
+            Self::default()
+        }
+    }
+
     /// Extract a wheel by linking all of its files into site packages.
     #[instrument(skip_all)]
     pub fn link_wheel_files(
@@ -234,6 +293,32 @@ impl LinkMode {
             Self::Clone => clone_wheel_files(site_packages, wheel),
             Self::Copy => copy_wheel_files(site_packages, wheel),
             Self::Hardlink => hardlink_wheel_files(site_packages, wheel),
+            Self::Symlink => symlink_wheel_files(site_packages, wheel),
+        }
+    }
+}
+
+/// Returns `true` if files in the `from` directory can be cloned (i.e., reflinked) into the `to`
+/// directory, by attempting to clone a temporary file.
+fn supports_reflink(from: &Path, to: &Path) -> bool {
+    let Ok(mut source) = NamedTempFile::new_in(from) else {
+        return false;
+    };
+    if source.write_all(b"uv").is_err() {
+        return false;
+    }
+    let Ok(target) = tempdir_in(to) else {
+        return false;
+    };
+    match reflink::reflink(source.path(), target.path().join("probe")) {
+        Ok(()) => true,
+        Err(err) => {
+            debug!(
+                "Copy-on-write is not supported from {} to {}: {err}",
+                from.display(),
+                to.display()
+            );
+            false
         }
     }
 }
@@ -242,7 +327,7 @@ impl LinkMode {
 /// via copy-on-write, which is similar to a hard link, but allows the files to be modified
 /// independently (that is, the file is copied upon modification).
 ///
-/// This method uses `clonefile` on macOS, and `reflink` on Linux.
+/// This method uses `clonefile` on macOS, and `FICLONE` (via `reflink`) on Linux.
 fn clone_wheel_files(
     site_packages: impl AsRef<Path>,
     wheel: impl AsRef<Path>,
@@ -315,6 +400,19 @@ fn clone_recursive(
     let from = entry.path();
     let to = site_packages.join(from.strip_prefix(wheel).unwrap());
 
+    // Only `clonefile` (on macOS) can clone an entire directory. Elsewhere, `FICLONE` operates on
+    // individual files, so we recreate the directory structure and clone each file in turn.
+    if !cfg!(any(target_os = "macos", target_os = "ios"))
+        && *attempt != Attempt::UseCopyFallback
+        && entry.file_type()?.is_dir()
+    {
+        fs::create_dir_all(&to)?;
+        for entry in fs::read_dir(from)? {
+            clone_recursive(site_packages, wheel, &entry?, attempt)?;
+        }
+        return Ok(());
+    }
+
     debug!("Cloning {} to {}", from.display(), to.display());
 
     match attempt {
@@ -334,6 +432,7 @@ fn clone_recursive(
                             fs::rename(&tempfile, to)?;
                         } else {
                             debug!("Failed to clone {} to temporary location {} - attempting to copy files as a fallback", from.display(), tempfile.display());
+                            warn_copy_fallback(LinkMode::Clone);
                             *attempt = Attempt::UseCopyFallback;
                             fs::copy(&from, &to)?;
                         }
@@ -344,6 +443,7 @@ fn clone_recursive(
                         from.display(),
                         to.display()
                     );
+                    warn_copy_fallback(LinkMode::Clone);
                     // switch to copy fallback
                     *attempt = Attempt::UseCopyFallback;
                     clone_recursive(site_packages, wheel, entry, attempt)?;
@@ -463,10 +563,22 @@ fn hardlink_wheel_files(
                         if fs::hard_link(path, &tempfile).is_ok() {
                             fs_err::rename(&tempfile, &out_path)?;
                         } else {
+                            debug!(
+                                "Failed to hardlink {} to {} - attempting to copy files as a fallback",
+                                path.display(),
+                                out_path.display()
+                            );
+                            warn_copy_fallback(LinkMode::Hardlink);
                             fs::copy(path, &out_path)?;
                             attempt = Attempt::UseCopyFallback;
                         }
                     } else {
+                        debug!(
+                            "Failed to hardlink {} to {} - attempting to copy files as a fallback",
+                            path.display(),
+                            out_path.display()
+                        );
+                        warn_copy_fallback(LinkMode::Hardlink);
                         fs::copy(path, &out_path)?;
                         attempt = Attempt::UseCopyFallback;
                     }
@@ -500,3 +612,103 @@ fn hardlink_wheel_files(
 
     Ok(count)
 }
+
+/// Extract a wheel by symbolically linking all of its files into site packages.
+fn symlink_wheel_files(
+    site_packages: impl AsRef<Path>,
+    wheel: impl AsRef<Path>,
+) -> Result<usize, Error> {
+    let mut attempt = Attempt::default();
+    let mut count = 0usize;
+
+    // Link to the resolved archive, rather than the (replaceable) cache entry that points to it.
+    let wheel = fs::canonicalize(wheel.as_ref())?;
+
+    // Walk over the directory.
+    for entry in walkdir::WalkDir::new(&wheel) {
+        let entry = entry?;
+        let path = entry.path();
+
+        let relative = path.strip_prefix(&wheel).unwrap();
+        let out_path = site_packages.as_ref().join(relative);
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&out_path)?;
+            continue;
+        }
+
+        // The `RECORD` file is modified during installation, so we copy it instead of linking.
+        if path.ends_with("RECORD") {
+            fs::copy(path, &out_path)?;
+            count += 1;
+            continue;
+        }
+
+        // Fallback to copying if symlinks aren't supported for this installation.
+        match attempt {
+            Attempt::Initial => {
+                attempt = Attempt::Subsequent;
+                if let Err(err) = replace_symlink(path, &out_path, site_packages.as_ref()) {
+                    debug!(
+                        "Failed to symlink {} to {} - attempting to copy files as a fallback: {err}",
+                        path.display(),
+                        out_path.display()
+                    );
+                    warn_copy_fallback(LinkMode::Symlink);
+                    fs::copy(path, &out_path)?;
+                    attempt = Attempt::UseCopyFallback;
+                }
+            }
+            Attempt::Subsequent => {
+                replace_symlink(path, &out_path, site_packages.as_ref())?;
+            }
+            Attempt::UseCopyFallback => {
+                fs::copy(path, &out_path)?;
+            }
+        }
+
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Create a symlink at `to` pointing to the file at `from`, replacing any existing file at `to`.
+fn replace_symlink(from: &Path, to: &Path, site_packages: &Path) -> std::io::Result<()> {
+    match symlink_file(from, to) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+            debug!("File already exists, overwriting: {}", to.display());
+            // Removing and recreating would lead to race conditions.
+            let tempdir = tempdir_in(site_packages)?;
+            let tempfile = tempdir.path().join(to.file_name().unwrap());
+            symlink_file(from, &tempfile)?;
+            fs_err::rename(&tempfile, to)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(unix)]
+fn symlink_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs_err::os::unix::fs::symlink(from, to)
+}
+
+#[cfg(windows)]
+fn symlink_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs_err::os::windows::fs::symlink_file(from, to)
+}
+
+/// Warn the user that the requested [`LinkMode`] isn't supported, and that files will be copied
+/// instead.
+fn warn_copy_fallback(link_mode: LinkMode) {
+    let (operation, gerund) = match link_mode {
+        LinkMode::Clone => ("clone", "cloning"),
+        LinkMode::Copy => return,
+        LinkMode::Hardlink => ("hardlink", "hardlinking"),
+        LinkMode::Symlink => ("symlink", "symlinking"),
+    };
+    warn_user_once!(
+        "Failed to {operation} files; falling back to full copy. This may lead to degraded performance. If the cache and target directories are on different filesystems, {gerund} may not be supported. If this is intentional, set `--link-mode=copy` to suppress this warning."
+    );
+}
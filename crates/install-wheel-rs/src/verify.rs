@@ -0,0 +1,126 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::Path;
+
+use fs_err::File;
+
+use crate::linker::find_dist_info;
+use crate::wheel::{copy_and_hash, read_record_file};
+use crate::Error;
+
+/// A file in an unzipped wheel that doesn't match its entry in the wheel's `RECORD`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordMismatch {
+    /// The file is listed in the `RECORD`, but is missing from the wheel.
+    Missing { path: String },
+    /// The file's hash doesn't match the hash listed in the `RECORD`.
+    Hash {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl Display for RecordMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing { path } => write!(f, "`{path}` is missing"),
+            Self::Hash {
+                path,
+                expected,
+                actual,
+            } => write!(f, "`{path}` has hash `{actual}`, expected `{expected}`"),
+        }
+    }
+}
+
+/// Verify the files in an unzipped wheel against the hashes recorded in its `RECORD` file.
+///
+/// Returns the files that are missing or don't match their recorded hash. Files without a
+/// recorded hash (like the `RECORD` itself) are skipped.
+pub fn verify_record(wheel: impl AsRef<Path>) -> Result<Vec<RecordMismatch>, Error> {
+    let wheel = wheel.as_ref();
+    let dist_info_prefix = find_dist_info(wheel)?;
+
+    let record_path = wheel.join(format!("{dist_info_prefix}.dist-info/RECORD"));
+    let mut record_file = match File::open(&record_path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(Error::MissingRecord(record_path));
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let record = read_record_file(&mut record_file)?;
+
+    let mut mismatches = Vec::new();
+    for entry in record {
+        let Some(expected) = entry.hash else {
+            continue;
+        };
+
+        let mut file = match File::open(wheel.join(&entry.path)) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                mismatches.push(RecordMismatch::Missing { path: entry.path });
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        // Only SHA-256 hashes are supported; skip any other algorithms.
+        if !expected.starts_with("sha256=") {
+            continue;
+        }
+        let (_, actual) = copy_and_hash(&mut file, &mut io::sink())?;
+        if actual != expected {
+            mismatches.push(RecordMismatch::Hash {
+                path: entry.path,
+                expected,
+                actual,
+            });
+        }
+    }
+
+    Ok(mismatches)
+}
+
+#[cfg(test)]
+mod tests {
+    use fs_err as fs;
+
+    use super::{verify_record, RecordMismatch};
+
+    #[test]
+    fn verify() {
+        let wheel = tempfile::tempdir().unwrap();
+        fs::create_dir_all(wheel.path().join("foo")).unwrap();
+        fs::create_dir_all(wheel.path().join("foo-1.0.0.dist-info")).unwrap();
+        fs::write(wheel.path().join("foo/__init__.py"), "").unwrap();
+        fs::write(wheel.path().join("foo/bar.py"), "corrupted").unwrap();
+        fs::write(
+            wheel.path().join("foo-1.0.0.dist-info/RECORD"),
+            "\
+foo/__init__.py,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0
+foo/bar.py,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0
+foo/baz.py,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0
+foo-1.0.0.dist-info/RECORD,,
+",
+        )
+        .unwrap();
+
+        let mismatches = verify_record(wheel.path()).unwrap();
+        assert_eq!(
+            mismatches,
+            vec![
+                RecordMismatch::Hash {
+                    path: "foo/bar.py".to_string(),
+                    expected: "sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU".to_string(),
+                    actual: "sha256=Pbs5Y9EapBjei2H4RsPb1a9DtA0lKEKtuCP5CTb-aSA".to_string(),
+                },
+                RecordMismatch::Missing {
+                    path: "foo/baz.py".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Cursor, Read, Write};
 use std::path::{Path, PathBuf};
@@ -90,7 +91,7 @@ pub(crate) fn read_scripts_from_section(
 /// <https://github.com/richo/hashing-copy/blob/d8dd2fdb63c6faf198de0c9e5713d6249cbb5323/src/lib.rs#L10-L52>
 /// which in turn got it from std
 /// <https://doc.rust-lang.org/1.58.0/src/std/io/copy.rs.html#128-156>
-fn copy_and_hash(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<(u64, String)> {
+pub(crate) fn copy_and_hash(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<(u64, String)> {
     // TODO: Do we need to support anything besides sha256?
     let mut hasher = Sha256::new();
     // Same buf size as std. Note that this number is important for performance
@@ -114,21 +115,24 @@ fn copy_and_hash(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<
     ))
 }
 
-/// Format the shebang for a given Python executable.
+/// Format the shebang for a given Python executable, followed by any interpreter arguments
+/// (e.g., `-u`).
 ///
 /// Like pip, if a shebang is non-simple (too long or contains spaces), we use `/bin/sh` as the
-/// executable.
+/// executable. On Windows, executables with spaces are quoted instead.
 ///
 /// See: <https://github.com/pypa/pip/blob/0ad4c94be74cc24874c6feb5bb3c2152c398a18e/src/pip/_vendor/distlib/scripts.py#L136-L165>
-fn format_shebang(executable: impl AsRef<Path>, os_name: &str) -> String {
-    // Convert the executable to a simplified path.
-    let executable = executable.as_ref().simplified_display().to_string();
+fn format_shebang(executable: impl AsRef<Path>, args: &str, os_name: &str) -> String {
+    let args = format_shebang_args(args);
 
     // Validate the shebang.
     if os_name == "posix" {
+        // Convert the executable to a simplified path.
+        let executable = executable.as_ref().simplified_display().to_string();
+
         // The length of the full line: the shebang, plus the leading `#` and `!`, and a trailing
         // newline.
-        let shebang_length = 2 + executable.len() + 1;
+        let shebang_length = 2 + executable.len() + args.len() + 1;
 
         // If the shebang is too long, or contains spaces, wrap it in `/bin/sh`.
         if shebang_length > 127 || executable.contains(' ') {
@@ -136,11 +140,102 @@ fn format_shebang(executable: impl AsRef<Path>, os_name: &str) -> String {
             // > Use single quotes, and put single quotes into double quotes
             // > The string $'b is then quoted as '$'"'"'b'
             let executable = format!("'{}'", executable.replace('\'', r#"'"'"'"#));
-            return format!("#!/bin/sh\n'''exec' {executable} \"$0\" \"$@\"\n' '''");
+            return format!("#!/bin/sh\n'''exec' {executable}{args} \"$0\" \"$@\"\n' '''");
         }
+
+        return format!("#!{executable}{args}");
+    }
+
+    let executable = windows_executable(executable.as_ref());
+    if os_name == "nt" && executable.contains(' ') {
+        return format!("#!\"{executable}\"{args}");
+    }
+    format!("#!{executable}{args}")
+}
+
+/// Format the interpreter arguments for a shebang, with a leading space if non-empty.
+fn format_shebang_args(args: &str) -> String {
+    if args.is_empty() {
+        String::new()
+    } else {
+        format!(" {args}")
+    }
+}
+
+/// Render a Python executable for use in a Windows shebang or launcher.
+///
+/// Verbatim UNC paths (`\\?\UNC\server\share\...`) are rewritten to the legacy UNC form
+/// (`\\server\share\...`), which both the `py` launcher and the trampoline understand. Verbatim
+/// disk paths are already simplified by [`Simplified::simplified_display`].
+fn windows_executable(executable: &Path) -> String {
+    let executable = executable.simplified_display().to_string();
+    match executable.strip_prefix(r"\\?\UNC\") {
+        Some(unc) => format!(r"\\{unc}"),
+        None => executable,
+    }
+}
+
+/// Format the shebang for a script in the given directory, in a relocatable environment.
+///
+/// On Unix, the script locates the Python executable relative to its own directory at runtime,
+/// via the `/bin/sh` trick used by [`format_shebang`]. The directory is resolved with `pwd -P`
+/// rather than `realpath`, which isn't available on older versions of macOS. Elsewhere, the
+/// absolute path is used as-is.
+fn format_relocatable_shebang(
+    executable: &Path,
+    args: &str,
+    scripts: &Path,
+    os_name: &str,
+) -> String {
+    if os_name != "posix" {
+        return format_shebang(executable, args, os_name);
+    }
+    let Some(relative) = pathdiff::diff_paths(executable, scripts) else {
+        return format_shebang(executable, args, os_name);
+    };
+    let relative = relative.simplified_display().to_string();
+    let relative = format!("'{}'", relative.replace('\'', r#"'"'"'"#));
+    let args = format_shebang_args(args);
+    format!(
+        "#!/bin/sh\n'''exec' \"$(CDPATH= cd -- \"$(dirname -- \"$0\")\" && pwd -P)\"/{relative}{args} \"$0\" \"$@\"\n' '''"
+    )
+}
+
+/// Return the shebang to use for a script installed into the given [`Layout`].
+fn layout_shebang(layout: &Layout, executable: &Path, args: &str) -> String {
+    if layout.relocatable {
+        format_relocatable_shebang(executable, args, &layout.scheme.scripts, &layout.os_name)
+    } else {
+        format_shebang(executable, args, &layout.os_name)
     }
+}
+
+/// Parse the remainder of a `#!python` placeholder line (i.e., everything after `#!python`),
+/// returning whether it's a GUI script (`#!pythonw`) and the interpreter arguments, if any
+/// (e.g., `-u` for `#!python -u`).
+fn parse_placeholder(rest: &[u8]) -> (bool, String) {
+    let rest = String::from_utf8_lossy(rest);
+    let rest = rest.trim_end_matches(['\r', '\n']);
+    let is_gui = rest.starts_with('w');
+    let args = rest
+        .find(|c: char| c.is_ascii_whitespace())
+        .map(|index| rest[index..].trim().to_string())
+        .unwrap_or_default();
+    (is_gui, args)
+}
 
-    format!("#!{executable}")
+/// Return the Python executable with which to run GUI scripts.
+///
+/// On Windows, that's the `pythonw.exe` adjacent to the interpreter (if it exists), which runs
+/// without opening a console window.
+fn gui_executable(executable: &Path, os_name: &str) -> Cow<'_, Path> {
+    if os_name == "nt" {
+        let pythonw = executable.with_file_name("pythonw.exe");
+        if pythonw.is_file() {
+            return Cow::Owned(pythonw);
+        }
+    }
+    Cow::Borrowed(executable)
 }
 
 /// A Windows script is a minimal .exe launcher binary with the python entrypoint script appended as
@@ -200,8 +295,8 @@ pub(crate) fn windows_script_launcher(
         archive.finish().expect(error_msg);
     }
 
-    let python = python_executable.as_ref();
-    let python_path = python.simplified_display().to_string();
+    // The trampoline quotes the path itself when spawning the interpreter, so it's stored as-is.
+    let python_path = windows_executable(python_executable.as_ref());
 
     let mut launcher: Vec<u8> = Vec::with_capacity(launcher_bin.len() + payload.len());
     launcher.extend_from_slice(launcher_bin);
@@ -252,18 +347,22 @@ pub(crate) fn write_script_entrypoints(
                 ))
             })?;
 
+        let executable = if is_gui {
+            gui_executable(&layout.sys_executable, &layout.os_name)
+        } else {
+            Cow::Borrowed(layout.sys_executable.as_path())
+        };
+
         // Generate the launcher script.
-        let launcher_python_script = get_script_launcher(
-            entrypoint,
-            &format_shebang(&layout.sys_executable, &layout.os_name),
-        );
+        let launcher_python_script =
+            get_script_launcher(entrypoint, &layout_shebang(layout, &executable, ""));
 
         // If necessary, wrap the launcher script in a Windows launcher binary.
         if cfg!(windows) {
             write_file_recorded(
                 site_packages,
                 &entrypoint_relative,
-                &windows_script_launcher(&launcher_python_script, is_gui, &layout.sys_executable)?,
+                &windows_script_launcher(&launcher_python_script, is_gui, &executable)?,
                 record,
             )?;
         } else {
@@ -453,7 +552,7 @@ fn install_script(
         })?;
 
     let path = file.path();
-    let mut script = File::open(&path)?;
+    let mut script = BufReader::new(File::open(&path)?);
 
     // https://sphinx-locales.github.io/peps/pep-0427/#recommended-installer-features
     // > In wheel, scripts are packaged in {distribution}-{version}.data/scripts/.
@@ -468,7 +567,18 @@ fn install_script(
     let mut start = vec![0; placeholder_python.len()];
     script.read_exact(&mut start)?;
     let size_and_encoded_hash = if start == placeholder_python {
-        let start = format_shebang(&layout.sys_executable, &layout.os_name)
+        // Replace the entire shebang line, e.g., `#!python` or `#!pythonw`, retaining any
+        // interpreter arguments, e.g., the `-u` in `#!python -u`.
+        let mut placeholder_rest = Vec::new();
+        script.read_until(b'\n', &mut placeholder_rest)?;
+        let (is_gui, args) = parse_placeholder(&placeholder_rest);
+
+        let executable = if is_gui {
+            gui_executable(&layout.sys_executable, &layout.os_name)
+        } else {
+            Cow::Borrowed(layout.sys_executable.as_path())
+        };
+        let start = format!("{}\n", layout_shebang(layout, &executable, &args))
             .as_bytes()
             .to_vec();
         let mut target = File::create(&script_absolute)?;
@@ -734,7 +844,10 @@ mod test {
     use crate::Error;
     use indoc::{formatdoc, indoc};
 
-    use crate::wheel::format_shebang;
+    use crate::wheel::{
+        format_relocatable_shebang, format_shebang, gui_executable, parse_placeholder,
+        windows_executable,
+    };
 
     use super::{parse_key_value_file, parse_wheel_file, read_record_file, relative_to, Script};
 
@@ -868,33 +981,150 @@ mod test {
         // By default, use a simple shebang.
         let executable = Path::new("/usr/bin/python3");
         let os_name = "posix";
-        assert_eq!(format_shebang(executable, os_name), "#!/usr/bin/python3");
+        assert_eq!(
+            format_shebang(executable, "", os_name),
+            "#!/usr/bin/python3"
+        );
 
         // If the path contains spaces, we should use the `exec` trick.
         let executable = Path::new("/usr/bin/path to python3");
         let os_name = "posix";
         assert_eq!(
-            format_shebang(executable, os_name),
+            format_shebang(executable, "", os_name),
             "#!/bin/sh\n'''exec' '/usr/bin/path to python3' \"$0\" \"$@\"\n' '''"
         );
 
-        // Except on Windows...
+        // Except on Windows, where the path is quoted instead...
         let executable = Path::new("/usr/bin/path to python3");
         let os_name = "nt";
         assert_eq!(
-            format_shebang(executable, os_name),
-            "#!/usr/bin/path to python3"
+            format_shebang(executable, "", os_name),
+            "#!\"/usr/bin/path to python3\""
         );
 
         // Quotes, however, are ok.
         let executable = Path::new("/usr/bin/'python3'");
         let os_name = "posix";
-        assert_eq!(format_shebang(executable, os_name), "#!/usr/bin/'python3'");
+        assert_eq!(
+            format_shebang(executable, "", os_name),
+            "#!/usr/bin/'python3'"
+        );
 
         // If the path is too long, we should not use the `exec` trick.
         let executable = Path::new("/usr/bin/path/to/a/very/long/executable/executable/executable/executable/executable/executable/executable/executable/name/python3");
         let os_name = "posix";
-        assert_eq!(format_shebang(executable, os_name), "#!/bin/sh\n'''exec' '/usr/bin/path/to/a/very/long/executable/executable/executable/executable/executable/executable/executable/executable/name/python3' \"$0\" \"$@\"\n' '''");
+        assert_eq!(format_shebang(executable, "", os_name), "#!/bin/sh\n'''exec' '/usr/bin/path/to/a/very/long/executable/executable/executable/executable/executable/executable/executable/executable/name/python3' \"$0\" \"$@\"\n' '''");
+
+        // Interpreter arguments follow the executable.
+        let executable = Path::new("/usr/bin/python3");
+        let os_name = "posix";
+        assert_eq!(
+            format_shebang(executable, "-u", os_name),
+            "#!/usr/bin/python3 -u"
+        );
+
+        // Including when using the `exec` trick.
+        let executable = Path::new("/usr/bin/path to python3");
+        let os_name = "posix";
+        assert_eq!(
+            format_shebang(executable, "-u", os_name),
+            "#!/bin/sh\n'''exec' '/usr/bin/path to python3' -u \"$0\" \"$@\"\n' '''"
+        );
+
+        // And on Windows.
+        let executable = Path::new("C:\\Program Files\\Python\\python.exe");
+        let os_name = "nt";
+        assert_eq!(
+            format_shebang(executable, "-u", os_name),
+            "#!\"C:\\Program Files\\Python\\python.exe\" -u"
+        );
+    }
+
+    #[test]
+    fn test_windows_executable() {
+        // Verbatim UNC paths are rewritten to the legacy UNC form.
+        let executable = Path::new(r"\\?\UNC\server\share\venv\Scripts\python.exe");
+        assert_eq!(
+            windows_executable(executable),
+            r"\\server\share\venv\Scripts\python.exe"
+        );
+        assert_eq!(
+            format_shebang(executable, "", "nt"),
+            r"#!\\server\share\venv\Scripts\python.exe"
+        );
+
+        // Including when they contain spaces, which are quoted in shebangs.
+        let executable = Path::new(r"\\?\UNC\server\my share\venv\Scripts\python.exe");
+        assert_eq!(
+            format_shebang(executable, "", "nt"),
+            r#"#!"\\server\my share\venv\Scripts\python.exe""#
+        );
+
+        // Other paths are left as-is.
+        let executable = Path::new(r"\\server\share\venv\Scripts\python.exe");
+        assert_eq!(
+            windows_executable(executable),
+            r"\\server\share\venv\Scripts\python.exe"
+        );
+    }
+
+    #[test]
+    fn test_parse_placeholder() {
+        assert_eq!(parse_placeholder(b"\n"), (false, String::new()));
+        assert_eq!(parse_placeholder(b"w\r\n"), (true, String::new()));
+        assert_eq!(parse_placeholder(b" -u\n"), (false, "-u".to_string()));
+        assert_eq!(parse_placeholder(b"w -u -E\n"), (true, "-u -E".to_string()));
+        assert_eq!(parse_placeholder(b"3.12\t-O"), (false, "-O".to_string()));
+    }
+
+    #[test]
+    fn test_relocatable_shebang() {
+        // Locate the interpreter relative to the script.
+        let executable = Path::new("/home/ferris/.venv/bin/python");
+        let scripts = Path::new("/home/ferris/.venv/bin");
+        assert_eq!(
+            format_relocatable_shebang(executable, "", scripts, "posix"),
+            "#!/bin/sh\n'''exec' \"$(CDPATH= cd -- \"$(dirname -- \"$0\")\" && pwd -P)\"/'python' \"$0\" \"$@\"\n' '''"
+        );
+
+        // Including when the scripts are installed elsewhere.
+        let scripts = Path::new("/home/ferris/target/bin");
+        assert_eq!(
+            format_relocatable_shebang(executable, "", scripts, "posix"),
+            "#!/bin/sh\n'''exec' \"$(CDPATH= cd -- \"$(dirname -- \"$0\")\" && pwd -P)\"/'../../.venv/bin/python' \"$0\" \"$@\"\n' '''"
+        );
+
+        // Retaining any interpreter arguments.
+        assert_eq!(
+            format_relocatable_shebang(executable, "-u", scripts, "posix"),
+            "#!/bin/sh\n'''exec' \"$(CDPATH= cd -- \"$(dirname -- \"$0\")\" && pwd -P)\"/'../../.venv/bin/python' -u \"$0\" \"$@\"\n' '''"
+        );
+
+        // Except on Windows...
+        let executable = Path::new("C:\\venv\\Scripts\\python.exe");
+        let scripts = Path::new("C:\\venv\\Scripts");
+        assert_eq!(
+            format_relocatable_shebang(executable, "", scripts, "nt"),
+            "#!C:\\venv\\Scripts\\python.exe"
+        );
+    }
+
+    #[test]
+    fn test_gui_executable() {
+        let bin = tempfile::tempdir().unwrap();
+        let python = bin.path().join("python.exe");
+        fs_err::write(&python, "").unwrap();
+
+        // Without a `pythonw.exe`, fall back to the interpreter.
+        assert_eq!(gui_executable(&python, "nt"), python.as_path());
+
+        // On Windows, prefer `pythonw.exe`, which doesn't open a console window.
+        let pythonw = bin.path().join("pythonw.exe");
+        fs_err::write(&pythonw, "").unwrap();
+        assert_eq!(gui_executable(&python, "nt"), pythonw.as_path());
+
+        // Elsewhere, GUI scripts use the interpreter.
+        assert_eq!(gui_executable(&python, "posix"), python.as_path());
     }
 
     #[test]
@@ -74,6 +74,25 @@ impl<K: Eq + Hash, V> OnceMap<K, V> {
     }
 }
 
+impl<K: Eq + Hash + Clone, V> OnceMap<K, V> {
+    /// Return a new map containing the results of all completed jobs.
+    #[must_use]
+    pub fn completed(&self) -> Self {
+        Self {
+            items: self
+                .items
+                .iter()
+                .filter_map(|entry| match entry.value() {
+                    Value::Filled(value) => {
+                        Some((entry.key().clone(), Value::Filled(value.clone())))
+                    }
+                    Value::Waiting(_) => None,
+                })
+                .collect(),
+        }
+    }
+}
+
 impl<K: Eq + Hash + Clone, V> Default for OnceMap<K, V> {
     fn default() -> Self {
         Self {
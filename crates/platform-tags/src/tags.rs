@@ -93,6 +93,7 @@ impl Tags {
         python_version: (u8, u8),
         implementation_name: &str,
         implementation_version: (u8, u8),
+        gil_disabled: bool,
     ) -> Result<Self, TagsError> {
         let implementation = Implementation::from_str(implementation_name)?;
         let platform_tags = compatible_tags(platform)?;
@@ -103,12 +104,22 @@ impl Tags {
         for platform_tag in &platform_tags {
             tags.push((
                 implementation.language_tag(python_version),
-                implementation.abi_tag(python_version, implementation_version),
+                implementation.abi_tag(python_version, implementation_version, gil_disabled),
                 platform_tag.clone(),
             ));
         }
         // 2. abi3 and no abi (e.g. executable binary)
-        if matches!(implementation, Implementation::CPython) {
+        if matches!(implementation, Implementation::CPython) && gil_disabled {
+            // Free-threaded builds don't support the stable ABI.
+            // See: https://peps.python.org/pep-0703/#build-configuration-changes
+            for platform_tag in &platform_tags {
+                tags.push((
+                    implementation.language_tag(python_version),
+                    "none".to_string(),
+                    platform_tag.clone(),
+                ));
+            }
+        } else if matches!(implementation, Implementation::CPython) {
             // For some reason 3.2 is the minimum python for the cp abi
             for minor in (2..=python_version.1).rev() {
                 for platform_tag in &platform_tags {
@@ -129,6 +140,16 @@ impl Tags {
                     }
                 }
             }
+        } else {
+            // Other implementations (e.g., PyPy and GraalPy) don't support the stable ABI, but do
+            // support `none` tags for the exact implementation version
+            for platform_tag in &platform_tags {
+                tags.push((
+                    implementation.language_tag(python_version),
+                    "none".to_string(),
+                    platform_tag.clone(),
+                ));
+            }
         }
         // 3. no abi (e.g. executable binary)
         for minor in (0..=python_version.1).rev() {
@@ -151,13 +172,11 @@ impl Tags {
             }
         }
         // 4. no binary
-        if matches!(implementation, Implementation::CPython) {
-            tags.push((
-                format!("cp{}{}", python_version.0, python_version.1),
-                "none".to_string(),
-                "any".to_string(),
-            ));
-        }
+        tags.push((
+            implementation.language_tag(python_version),
+            "none".to_string(),
+            "any".to_string(),
+        ));
         for minor in (0..=python_version.1).rev() {
             tags.push((
                 format!("py{}{}", python_version.0, minor),
@@ -176,6 +195,28 @@ impl Tags {
         Ok(Self::new(tags))
     }
 
+    /// Returns a new set of tags, restricted to the given ABIs (e.g., `cp310` or `abi3`), along
+    /// with tags that don't depend on an ABI (`none`).
+    ///
+    /// Priorities are retained from the original set of tags.
+    #[must_use]
+    pub fn with_abis(&self, abis: &[String]) -> Self {
+        let map = self
+            .map
+            .iter()
+            .map(|(python_tag, abi_tags)| {
+                let abi_tags = abi_tags
+                    .iter()
+                    .filter(|(abi_tag, _)| *abi_tag == "none" || abis.contains(*abi_tag))
+                    .map(|(abi_tag, platform_tags)| (abi_tag.clone(), platform_tags.clone()))
+                    .collect::<FxHashMap<_, _>>();
+                (python_tag.clone(), abi_tags)
+            })
+            .filter(|(_, abi_tags)| !abi_tags.is_empty())
+            .collect();
+        Self { map: Arc::new(map) }
+    }
+
     /// Returns true when there exists at least one tag for this platform
     /// whose individual components all appear in each of the slices given.
     ///
@@ -294,6 +335,7 @@ enum Implementation {
     CPython,
     PyPy,
     Pyston,
+    GraalPy,
 }
 
 impl Implementation {
@@ -307,14 +349,24 @@ impl Implementation {
             Self::PyPy => format!("pp{}{}", python_version.0, python_version.1),
             // Ex) `pt38``
             Self::Pyston => format!("pt{}{}", python_version.0, python_version.1),
+            // Ex) `graalpy310`
+            Self::GraalPy => format!("graalpy{}{}", python_version.0, python_version.1),
         }
     }
 
-    fn abi_tag(self, python_version: (u8, u8), implementation_version: (u8, u8)) -> String {
+    fn abi_tag(
+        self,
+        python_version: (u8, u8),
+        implementation_version: (u8, u8),
+        gil_disabled: bool,
+    ) -> String {
         match self {
             // Ex) `cp39`
             Self::CPython => {
-                if python_version.1 <= 7 {
+                if gil_disabled {
+                    // Ex) `cp313t`
+                    format!("cp{}{}t", python_version.0, python_version.1)
+                } else if python_version.1 <= 7 {
                     format!("cp{}{}m", python_version.0, python_version.1)
                 } else {
                     format!("cp{}{}", python_version.0, python_version.1)
@@ -336,6 +388,14 @@ impl Implementation {
                 implementation_version.0,
                 implementation_version.1
             ),
+            // Ex) `graalpy240_310_native`
+            Self::GraalPy => format!(
+                "graalpy{}{}_{}{}_native",
+                implementation_version.0,
+                implementation_version.1,
+                python_version.0,
+                python_version.1
+            ),
         }
     }
 }
@@ -349,6 +409,7 @@ impl FromStr for Implementation {
             "cpython" => Ok(Self::CPython),
             "pypy" => Ok(Self::PyPy),
             "pyston" => Ok(Self::Pyston),
+            "graalpy" => Ok(Self::GraalPy),
             // Known but unsupported implementations.
             "python" => Err(TagsError::UnsupportedImplementation(s.to_string())),
             "ironpython" => Err(TagsError::UnsupportedImplementation(s.to_string())),
@@ -924,6 +985,7 @@ mod tests {
             (3, 9),
             "cpython",
             (3, 9),
+            false,
         )
         .unwrap();
         assert_snapshot!(
@@ -1546,6 +1608,7 @@ mod tests {
             (3, 9),
             "cpython",
             (3, 9),
+            false,
         )
         .unwrap();
         assert_snapshot!(
@@ -2007,4 +2070,145 @@ mod tests {
         "###
         );
     }
+
+    #[test]
+    fn test_system_tags_pypy() {
+        let tags = Tags::from_env(
+            &Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::X86_64,
+            ),
+            (3, 10),
+            "pypy",
+            (7, 3),
+            false,
+        )
+        .unwrap();
+
+        let compatible = |tag: &str| {
+            let mut parts = tag.split('-').map(|part| vec![part.to_string()]);
+            let (python, abi, platform) = (
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+            );
+            tags.is_compatible(&python, &abi, &platform)
+        };
+        assert!(compatible("pp310-pypy310_pp73-manylinux_2_17_x86_64"));
+        assert!(compatible("pp310-none-manylinux2014_x86_64"));
+        assert!(compatible("pp310-none-any"));
+        assert!(compatible("py3-none-any"));
+        assert!(!compatible("cp310-cp310-manylinux_2_17_x86_64"));
+        assert!(!compatible("cp310-abi3-manylinux_2_17_x86_64"));
+        assert!(!compatible("pp39-pypy39_pp73-manylinux_2_17_x86_64"));
+    }
+
+    #[test]
+    fn test_system_tags_graalpy() {
+        let tags = Tags::from_env(
+            &Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 28,
+                },
+                Arch::X86_64,
+            ),
+            (3, 10),
+            "graalpy",
+            (24, 0),
+            false,
+        )
+        .unwrap();
+
+        let compatible = |tag: &str| {
+            let mut parts = tag.split('-').map(|part| vec![part.to_string()]);
+            let (python, abi, platform) = (
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+            );
+            tags.is_compatible(&python, &abi, &platform)
+        };
+        assert!(compatible(
+            "graalpy310-graalpy240_310_native-manylinux_2_28_x86_64"
+        ));
+        assert!(compatible("graalpy310-none-any"));
+        assert!(compatible("py310-none-manylinux_2_17_x86_64"));
+        assert!(!compatible(
+            "graalpy311-graalpy240_311_native-manylinux_2_28_x86_64"
+        ));
+        assert!(!compatible("cp310-abi3-manylinux_2_28_x86_64"));
+        assert!(!compatible("pp310-pypy310_pp73-manylinux_2_28_x86_64"));
+    }
+
+    #[test]
+    fn test_system_tags_free_threaded() {
+        let tags = Tags::from_env(
+            &Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::X86_64,
+            ),
+            (3, 13),
+            "cpython",
+            (3, 13),
+            true,
+        )
+        .unwrap();
+
+        let compatible = |tag: &str| {
+            let mut parts = tag.split('-').map(|part| vec![part.to_string()]);
+            let (python, abi, platform) = (
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+            );
+            tags.is_compatible(&python, &abi, &platform)
+        };
+        assert!(compatible("cp313-cp313t-manylinux_2_17_x86_64"));
+        assert!(compatible("cp313-none-manylinux_2_17_x86_64"));
+        assert!(compatible("cp313-none-any"));
+        assert!(compatible("py3-none-any"));
+        assert!(!compatible("cp313-cp313-manylinux_2_17_x86_64"));
+        assert!(!compatible("cp313-abi3-manylinux_2_17_x86_64"));
+        assert!(!compatible("cp312-abi3-manylinux_2_17_x86_64"));
+    }
+
+    #[test]
+    fn test_system_tags_with_abis() {
+        let tags = Tags::from_env(
+            &Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::X86_64,
+            ),
+            (3, 10),
+            "cpython",
+            (3, 10),
+            false,
+        )
+        .unwrap()
+        .with_abis(&["abi3".to_string()]);
+
+        let compatible = |tag: &str| {
+            let mut parts = tag.split('-').map(|part| vec![part.to_string()]);
+            let (python, abi, platform) = (
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+                parts.next().unwrap(),
+            );
+            tags.is_compatible(&python, &abi, &platform)
+        };
+        assert!(compatible("cp38-abi3-manylinux2014_x86_64"));
+        assert!(compatible("cp310-none-any"));
+        assert!(compatible("py3-none-any"));
+        assert!(!compatible("cp310-cp310-manylinux2014_x86_64"));
+    }
 }
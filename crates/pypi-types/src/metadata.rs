@@ -19,7 +19,8 @@ use crate::LenientVersionSpecifiers;
 /// <https://packaging.python.org/specifications/core-metadata/>.
 ///
 /// This is a subset of the full metadata specification, and only includes the
-/// fields that are relevant to dependency resolution.
+/// fields that are relevant to dependency resolution, along with the license
+/// information reported in software bills of materials.
 ///
 /// At present, we support up to version 2.3 of the metadata specification.
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
@@ -33,6 +34,14 @@ pub struct Metadata23 {
     pub requires_dist: Vec<Requirement>,
     pub requires_python: Option<VersionSpecifiers>,
     pub provides_extras: Vec<ExtraName>,
+    /// The SPDX license expression, as introduced by
+    /// [PEP 639](https://peps.python.org/pep-0639/) (e.g., `MIT OR Apache-2.0`).
+    #[serde(default)]
+    pub license_expression: Option<String>,
+    /// The free-form license, from the `License` field or, failing that, the license
+    /// classifiers (e.g., `BSD License`).
+    #[serde(default)]
+    pub license: Option<String>,
 }
 
 /// <https://github.com/PyO3/python-pkginfo-rs/blob/d719988323a0cfea86d4737116d7917f30e819e2/src/error.rs>
@@ -122,6 +131,8 @@ impl Metadata23 {
                 }
             })
             .collect::<Vec<_>>();
+        let license_expression = headers.get_first_value("License-Expression");
+        let license = headers.license();
 
         Ok(Self {
             metadata_version,
@@ -130,6 +141,8 @@ impl Metadata23 {
             requires_dist,
             requires_python,
             provides_extras,
+            license_expression,
+            license,
         })
     }
 
@@ -198,6 +211,8 @@ impl Metadata23 {
                 }
             })
             .collect::<Vec<_>>();
+        let license_expression = headers.get_first_value("License-Expression");
+        let license = headers.license();
 
         Ok(Self {
             metadata_version,
@@ -206,6 +221,8 @@ impl Metadata23 {
             requires_dist,
             requires_python,
             provides_extras,
+            license_expression,
+            license,
         })
     }
 }
@@ -278,6 +295,41 @@ impl<'a> Headers<'a> {
             .into_iter()
             .filter(|value| value != "UNKNOWN")
     }
+
+    /// Return the free-form license, from the `License` field if it's a single line (rather than
+    /// the full license text), or otherwise from the `License ::` classifiers.
+    fn license(&self) -> Option<String> {
+        // Use the raw value, since the parsed value has any line breaks collapsed.
+        if let Some(license) = self
+            .0
+            .get_first_header("License")
+            .map(|header| {
+                String::from_utf8_lossy(header.get_value_raw())
+                    .trim()
+                    .to_string()
+            })
+            .filter(|license| {
+                !license.is_empty() && license != "UNKNOWN" && !license.contains('\n')
+            })
+        {
+            return Some(license);
+        }
+
+        let classifiers = self
+            .get_all_values("Classifier")
+            .filter_map(|classifier| {
+                classifier
+                    .strip_prefix("License ::")
+                    .and_then(|license| license.rsplit("::").next())
+                    .map(|license| license.trim().to_string())
+            })
+            .collect::<Vec<_>>();
+        if classifiers.is_empty() {
+            None
+        } else {
+            Some(classifiers.join(", "))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -355,4 +407,25 @@ mod tests {
         assert_eq!(meta.version, Version::new([1, 0]));
         assert_eq!(meta.requires_dist, vec!["foo".parse().unwrap()]);
     }
+
+    #[test]
+    fn test_parse_license() {
+        let s = "Metadata-Version: 2.4\nName: asdf\nVersion: 1.0\nLicense-Expression: MIT OR Apache-2.0";
+        let meta = Metadata23::parse_metadata(s.as_bytes()).unwrap();
+        assert_eq!(
+            meta.license_expression.as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+        assert_eq!(meta.license, None);
+
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nLicense: BSD-3-Clause";
+        let meta = Metadata23::parse_metadata(s.as_bytes()).unwrap();
+        assert_eq!(meta.license_expression, None);
+        assert_eq!(meta.license.as_deref(), Some("BSD-3-Clause"));
+
+        // Prefer the classifiers to the full text of the license.
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nLicense: Copyright (c) asdf\n        \n        Permission is hereby granted\nClassifier: License :: OSI Approved :: MIT License";
+        let meta = Metadata23::parse_metadata(s.as_bytes()).unwrap();
+        assert_eq!(meta.license.as_deref(), Some("MIT License"));
+    }
 }
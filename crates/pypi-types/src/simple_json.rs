@@ -8,13 +8,40 @@ use pep440_rs::{VersionSpecifiers, VersionSpecifiersParseError};
 use crate::lenient_requirement::LenientVersionSpecifiers;
 
 /// A collection of "files" from `PyPI`'s JSON API for a single package.
+///
+/// <https://peps.python.org/pep-0691/#project-detail>
 #[derive(Debug, Clone, Deserialize)]
 pub struct SimpleJson {
+    /// The metadata for the response, including the version of the Simple API.
+    #[serde(default)]
+    pub meta: Meta,
     /// The list of [`File`]s available for download sorted by filename.
     #[serde(deserialize_with = "sorted_simple_json_files")]
     pub files: Vec<File>,
 }
 
+/// The `meta` key of a JSON Simple API response.
+///
+/// <https://peps.python.org/pep-0691/#json-serialization>
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Meta {
+    /// The version of the Simple API that the response conforms to (e.g., `1.1`).
+    pub api_version: Option<String>,
+}
+
+impl Meta {
+    /// Returns `true` if the response conforms to a supported version of the Simple API.
+    ///
+    /// Per [PEP 629](https://peps.python.org/pep-0629/), clients must reject responses with an
+    /// unknown major version, but should accept those with a newer minor version.
+    pub fn is_supported(&self) -> bool {
+        self.api_version
+            .as_deref()
+            .map_or(true, |version| version.split('.').next() == Some("1"))
+    }
+}
+
 /// Deserializes a sequence of "simple" files from `PyPI` and ensures that they
 /// are sorted in a stable order.
 fn sorted_simple_json_files<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<File>, D::Error> {
@@ -37,6 +64,9 @@ fn sorted_simple_json_files<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<File>
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct File {
+    /// The availability of the file's core metadata, under the key introduced by
+    /// [PEP 714](https://peps.python.org/pep-0714/).
+    pub core_metadata: Option<DistInfoMetadata>,
     // Non-PEP 691-compliant alias used by PyPI.
     #[serde(alias = "data-dist-info-metadata")]
     pub dist_info_metadata: Option<DistInfoMetadata>,
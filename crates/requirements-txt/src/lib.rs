@@ -35,6 +35,7 @@
 //! ```
 
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::io;
 use std::path::{Path, PathBuf};
@@ -51,9 +52,13 @@ use pep508_rs::{
 };
 use uv_client::Connectivity;
 use uv_fs::{normalize_url_path, Simplified};
-use uv_normalize::ExtraName;
+use uv_normalize::{ExtraName, PackageName};
 use uv_warnings::warn_user;
 
+pub use crate::via::{parse_via_annotations, Via};
+
+mod via;
+
 /// We emit one of those for each requirements.txt entry
 enum RequirementsTxtStatement {
     /// `-r` inclusion filename
@@ -153,6 +158,24 @@ pub struct EditableRequirement {
     pub url: VerbatimUrl,
     pub extras: Vec<ExtraName>,
     pub path: PathBuf,
+    /// The Git repository from which the editable is checked out, for editable Git requirements
+    /// (e.g., `-e git+https://github.com/pallets/flask.git#egg=flask`).
+    pub checkout: Option<EditableCheckout>,
+    /// Whether the editable is the local source for a dependency (e.g., on a workspace member),
+    /// rather than a requirement in its own right, in which case it's only installed if that
+    /// dependency is required.
+    pub dependency: bool,
+}
+
+/// A Git repository that's checked out to a local directory to be installed in editable mode.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EditableCheckout {
+    /// The URL of the repository, including any reference (e.g.,
+    /// `git+https://github.com/pallets/flask.git@main`).
+    pub url: Url,
+    /// The directory into which the repository is checked out. (The editable itself may be
+    /// located in a subdirectory of the checkout.)
+    pub path: PathBuf,
 }
 
 impl EditableRequirement {
@@ -174,7 +197,11 @@ impl EditableRequirement {
     /// - `file:../editable/`
     /// - `../editable/`
     ///
-    /// We disallow URLs with schemes other than `file://` (e.g., `https://...`).
+    /// Git URLs (e.g., `git+https://github.com/pallets/flask.git#egg=flask`) are also supported,
+    /// in which case the repository is checked out to `src/<name>` within the working directory,
+    /// as with pip. The package name must be provided via the `#egg=<name>` fragment.
+    ///
+    /// We disallow URLs with other schemes (e.g., `https://...`).
     pub fn parse(
         given: &str,
         working_dir: impl AsRef<Path>,
@@ -227,6 +254,52 @@ impl EditableRequirement {
                     VerbatimUrl::parse_path(path.as_ref(), working_dir.as_ref())
                 }
 
+                // Ex) `git+https://github.com/pallets/flask.git#egg=flask`
+                Some(
+                    Scheme::GitGit
+                    | Scheme::GitHttp
+                    | Scheme::GitFile
+                    | Scheme::GitSsh
+                    | Scheme::GitHttps,
+                ) => {
+                    let git =
+                        Url::parse(&expanded).map_err(|err| RequirementsTxtParserError::Url {
+                            source: err,
+                            url: expanded.to_string(),
+                            start: 0,
+                            end: requirement.len(),
+                        })?;
+                    let fragment = git.fragment().unwrap_or_default();
+                    let Some(name) = fragment
+                        .split('&')
+                        .find_map(|pair| pair.strip_prefix("egg="))
+                        .filter(|name| !name.is_empty())
+                    else {
+                        return Err(RequirementsTxtParserError::MissingEditableGitName(
+                            expanded.to_string(),
+                        ));
+                    };
+                    let checkout = working_dir.as_ref().join("src").join(name);
+                    let path = match fragment
+                        .split('&')
+                        .find_map(|pair| pair.strip_prefix("subdirectory="))
+                    {
+                        Some(subdirectory) => checkout.join(subdirectory),
+                        None => checkout.clone(),
+                    };
+                    return Ok(Self {
+                        url: VerbatimUrl::parse_path(&path, working_dir.as_ref())
+                            .with_given(requirement.to_string()),
+                        extras,
+                        path,
+                        checkout: Some(EditableCheckout {
+                            url: git,
+                            path: checkout,
+                        }),
+                        dependency: false,
+                    });
+                }
+
                 // Ex) `https://download.pytorch.org/whl/torch_stable.html`
                 Some(_) => {
                     return Err(RequirementsTxtParserError::UnsupportedUrl(
@@ -250,7 +323,13 @@ impl EditableRequirement {
         // Add the verbatim representation of the URL to the `VerbatimUrl`.
         let url = url.with_given(requirement.to_string());
 
-        Ok(Self { url, extras, path })
+        Ok(Self {
+            url,
+            extras,
+            path,
+            checkout: None,
+            dependency: false,
+        })
     }
 
     /// Identify the extras in an editable URL (e.g., `../editable[dev]`).
@@ -324,6 +403,11 @@ pub struct RequirementsTxt {
     pub find_links: Vec<FindLink>,
     /// Whether to ignore the index, specified with `--no-index`.
     pub no_index: bool,
+    /// The `-c` file (as written) from which each constraint was read, keyed by package name.
+    pub constraint_sources: BTreeMap<PackageName, Vec<String>>,
+    /// The hashes (e.g., `--hash=sha256:...`) allowed for each constrained package, keyed by
+    /// package name.
+    pub constraint_hashes: BTreeMap<PackageName, Vec<String>>,
 }
 
 impl RequirementsTxt {
@@ -473,6 +557,8 @@ impl RequirementsTxt {
                     for entry in sub_constraints.requirements {
                         match entry.requirement {
                             RequirementsTxtRequirement::Pep508(requirement) => {
+                                data.add_constraint_source(&requirement.name, &filename);
+                                data.add_constraint_hashes(&requirement.name, entry.hashes);
                                 data.constraints.push(requirement);
                             }
                             RequirementsTxtRequirement::Unnamed(_) => {
@@ -484,6 +570,14 @@ impl RequirementsTxt {
                         }
                     }
                     data.constraints.extend(sub_constraints.constraints);
+                    for (name, sources) in sub_constraints.constraint_sources {
+                        for source in sources {
+                            data.add_constraint_source(&name, &source);
+                        }
+                    }
+                    for (name, hashes) in sub_constraints.constraint_hashes {
+                        data.add_constraint_hashes(&name, hashes);
+                    }
                 }
                 RequirementsTxtStatement::RequirementEntry(requirement_entry) => {
                     data.requirements.push(requirement_entry);
@@ -526,6 +620,8 @@ impl RequirementsTxt {
             extra_index_urls,
             find_links,
             no_index,
+            constraint_sources,
+            constraint_hashes,
         } = other;
         self.requirements.extend(requirements);
         self.constraints.extend(constraints);
@@ -536,6 +632,35 @@ impl RequirementsTxt {
         self.extra_index_urls.extend(extra_index_urls);
         self.find_links.extend(find_links);
         self.no_index = self.no_index || no_index;
+        for (name, sources) in constraint_sources {
+            for source in sources {
+                self.add_constraint_source(&name, &source);
+            }
+        }
+        for (name, hashes) in constraint_hashes {
+            self.add_constraint_hashes(&name, hashes);
+        }
+    }
+
+    /// Record that the constraint on the given package was read from the given `-c` file.
+    fn add_constraint_source(&mut self, name: &PackageName, source: &str) {
+        let sources = self.constraint_sources.entry(name.clone()).or_default();
+        if !sources.iter().any(|existing| existing == source) {
+            sources.push(source.to_string());
+        }
+    }
+
+    /// Record the hashes allowed for the given constrained package, if any.
+    fn add_constraint_hashes(&mut self, name: &PackageName, hashes: Vec<String>) {
+        if hashes.is_empty() {
+            return;
+        }
+        let existing = self.constraint_hashes.entry(name.clone()).or_default();
+        for hash in hashes {
+            if !existing.contains(&hash) {
+                existing.push(hash);
+            }
+        }
     }
 }
 
@@ -854,6 +979,7 @@ pub enum RequirementsTxtParserError {
     },
     InvalidEditablePath(String),
     UnsupportedUrl(String),
+    MissingEditableGitName(String),
     MissingRequirementPrefix(String),
     UnnamedConstraint {
         start: usize,
@@ -905,6 +1031,7 @@ impl RequirementsTxtParserError {
                 end: end + offset,
             },
             Self::UnsupportedUrl(url) => Self::UnsupportedUrl(url),
+            Self::MissingEditableGitName(url) => Self::MissingEditableGitName(url),
             Self::MissingRequirementPrefix(given) => Self::MissingRequirementPrefix(given),
             Self::UnnamedConstraint { start, end } => Self::UnnamedConstraint {
                 start: start + offset,
@@ -954,6 +1081,9 @@ impl Display for RequirementsTxtParserError {
             Self::UnsupportedUrl(url) => {
                 write!(f, "Unsupported URL (expected a `file://` scheme): `{url}`")
             }
+            Self::MissingEditableGitName(url) => {
+                write!(f, "Editable Git requirements must specify a package name (e.g., `#egg=<name>`): `{url}`")
+            }
             Self::MissingRequirementPrefix(given) => {
                 write!(f, "Requirement `{given}` looks like a requirements file but was passed as a package name. Did you mean `-r {given}`?")
             }
@@ -998,6 +1128,7 @@ impl std::error::Error for RequirementsTxtParserError {
             Self::Url { source, .. } => Some(source),
             Self::InvalidEditablePath(_) => None,
             Self::UnsupportedUrl(_) => None,
+            Self::MissingEditableGitName(_) => None,
             Self::MissingRequirementPrefix(_) => None,
             Self::UnnamedConstraint { .. } => None,
             Self::UnsupportedRequirement { source, .. } => Some(source),
@@ -1036,6 +1167,13 @@ impl Display for RequirementsTxtFileError {
                     self.file.user_display(),
                 )
             }
+            RequirementsTxtParserError::MissingEditableGitName(url) => {
+                write!(
+                    f,
+                    "Editable Git requirements must specify a package name (e.g., `#egg=<name>`) in `{}`: `{url}`",
+                    self.file.user_display(),
+                )
+            }
             RequirementsTxtParserError::MissingRequirementPrefix(given) => {
                 write!(
                     f,
@@ -1628,6 +1766,8 @@ mod test {
             extra_index_urls: [],
             find_links: [],
             no_index: false,
+            constraint_sources: {},
+            constraint_hashes: {},
         }
         "###);
 
@@ -1687,12 +1827,16 @@ mod test {
                         },
                         extras: [],
                         path: "/foo/bar",
+                        checkout: None,
+                        dependency: false,
                     },
                 ],
                 index_url: None,
                 extra_index_urls: [],
                 find_links: [],
                 no_index: true,
+                constraint_sources: {},
+                constraint_hashes: {},
             }
             "###);
 
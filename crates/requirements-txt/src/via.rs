@@ -0,0 +1,230 @@
+//! Parsing of the `# via` annotations that `pip-compile` (and `uv pip compile`) attach to each
+//! pinned requirement in a compiled requirements file, which record why the requirement was
+//! included.
+//!
+//! Both annotation styles are supported:
+//!
+//! ```text
+//! # --annotation-style=split
+//! anyio==4.3.0
+//!     # via
+//!     #   -r requirements.in
+//!     #   httpx
+//! idna==3.6
+//!     # via anyio
+//!
+//! # --annotation-style=line
+//! anyio==4.3.0              # via -r requirements.in, httpx
+//! idna==3.6                 # via anyio
+//! ```
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use uv_normalize::PackageName;
+
+/// The reason that a pinned requirement was included in a compiled requirements file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Via {
+    /// The requirement is a dependency of another package in the file (e.g., `# via anyio`).
+    Package(PackageName),
+    /// The requirement was requested directly, e.g., by a requirements file
+    /// (`-r requirements.in`), a constraints file (`-c constraints.txt`), or a project
+    /// (`black (pyproject.toml)`).
+    Source(String),
+}
+
+impl Via {
+    /// Parse a single entry in a `# via` annotation.
+    fn parse(entry: &str) -> Self {
+        let entry = entry.trim();
+        if !entry.starts_with('-') && !entry.contains(char::is_whitespace) {
+            // Ex) `requests[socks]`
+            let name = entry.split_once('[').map_or(entry, |(name, _)| name);
+            if let Ok(name) = PackageName::from_str(name) {
+                return Self::Package(name);
+            }
+        }
+        Self::Source(entry.to_string())
+    }
+}
+
+/// Parse the `# via` annotations in the contents of a compiled requirements file, keyed by the
+/// name of the annotated requirement.
+///
+/// Requirements without annotations (e.g., those in a hand-written file, or compiled with
+/// `--no-annotate`) are omitted. Editable requirements and options are ignored.
+pub fn parse_via_annotations(content: &str) -> BTreeMap<PackageName, Vec<Via>> {
+    let mut annotations = BTreeMap::<PackageName, Vec<Via>>::new();
+
+    // The requirement to which any subsequent annotation applies.
+    let mut current: Option<PackageName> = None;
+    // Whether we're within a multi-line `# via` block.
+    let mut in_block = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let indented = trimmed.len() < line.len();
+
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            // A comment at the start of a line (e.g., the header) isn't an annotation.
+            if !indented {
+                current = None;
+                in_block = false;
+                continue;
+            }
+            let Some(name) = current.as_ref() else {
+                continue;
+            };
+
+            let body = comment.trim();
+            if body == "via" {
+                in_block = true;
+            } else if let Some(entries) = body.strip_prefix("via ") {
+                in_block = false;
+                annotations
+                    .entry(name.clone())
+                    .or_default()
+                    .extend(entries.split(", ").map(Via::parse));
+            } else if in_block && comment.starts_with("  ") && !body.is_empty() {
+                // Ex) `    #   httpx`
+                annotations
+                    .entry(name.clone())
+                    .or_default()
+                    .push(Via::parse(body));
+            } else {
+                in_block = false;
+            }
+            continue;
+        }
+
+        in_block = false;
+
+        // Continuation lines (e.g., `    --hash=sha256:...`) belong to the current requirement.
+        if indented && !trimmed.is_empty() {
+            continue;
+        }
+
+        // Blank lines and options (e.g., `-e`, `--index-url`) end the current requirement.
+        if trimmed.is_empty() || trimmed.starts_with('-') {
+            current = None;
+            continue;
+        }
+
+        // Ex) `anyio==4.3.0` or `anyio @ https://...`
+        let (requirement, comment) = trimmed
+            .split_once(" #")
+            .map_or((trimmed, None), |(requirement, comment)| {
+                (requirement, Some(comment))
+            });
+        let name = requirement
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+            .map_or(requirement, |end| &requirement[..end]);
+        current = PackageName::from_str(name).ok();
+
+        // Ex) `anyio==4.3.0  # via httpx` or `anyio==4.3.0  # from https://...; via httpx`
+        if let (Some(name), Some(comment)) = (current.as_ref(), comment) {
+            for segment in comment.trim().split("; ") {
+                if let Some(entries) = segment.strip_prefix("via ") {
+                    annotations
+                        .entry(name.clone())
+                        .or_default()
+                        .extend(entries.split(", ").map(Via::parse));
+                }
+            }
+        }
+    }
+
+    annotations
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use indoc::indoc;
+
+    use uv_normalize::PackageName;
+
+    use super::{parse_via_annotations, Via};
+
+    fn package(name: &str) -> Via {
+        Via::Package(PackageName::from_str(name).unwrap())
+    }
+
+    fn source(source: &str) -> Via {
+        Via::Source(source.to_string())
+    }
+
+    #[test]
+    fn split_style() {
+        let annotations = parse_via_annotations(indoc! {r"
+            #
+            # This file is autogenerated by pip-compile with Python 3.12
+            # by the following command:
+            #
+            #    pip-compile requirements.in
+            #
+            anyio==4.3.0 \
+                --hash=sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8
+                # via
+                #   -r requirements.in
+                #   httpx
+            h11==0.14.0
+                # via httpcore
+            httpx[http2]==0.27.0
+                # via -c constraints.txt
+            -e ./local
+                # via -r requirements.in
+            untracked==1.0.0
+
+            # The following packages are considered to be unsafe in a requirements file:
+            # setuptools
+        "});
+
+        assert_eq!(
+            annotations.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    PackageName::from_str("anyio").unwrap(),
+                    vec![source("-r requirements.in"), package("httpx")]
+                ),
+                (
+                    PackageName::from_str("h11").unwrap(),
+                    vec![package("httpcore")]
+                ),
+                (
+                    PackageName::from_str("httpx").unwrap(),
+                    vec![source("-c constraints.txt")]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn line_style() {
+        let annotations = parse_via_annotations(indoc! {r"
+            anyio==4.3.0              # via -r requirements.in, httpx
+            black==24.3.0             # via my-project (pyproject.toml)
+            idna @ https://example.com/idna-3.6-py3-none-any.whl  # from https://example.com; via anyio, requests[socks]
+        "});
+
+        assert_eq!(
+            annotations.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    PackageName::from_str("anyio").unwrap(),
+                    vec![source("-r requirements.in"), package("httpx")]
+                ),
+                (
+                    PackageName::from_str("black").unwrap(),
+                    vec![source("my-project (pyproject.toml)")]
+                ),
+                (
+                    PackageName::from_str("idna").unwrap(),
+                    vec![package("anyio"), package("requests")]
+                ),
+            ]
+        );
+    }
+}
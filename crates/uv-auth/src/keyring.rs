@@ -17,12 +17,20 @@ pub enum KeyringProvider {
     Disabled,
     /// Will use keyring CLI command for authentication
     Subprocess,
-    // /// Not yet implemented
-    // Auto,
+    /// Will use keyring CLI command for authentication if it's installed, and skip keyring
+    /// authentication otherwise
+    Auto,
     // /// Not implemented yet.  Maybe use <https://docs.rs/keyring/latest/keyring/> for this?
     // Import,
 }
 
+impl KeyringProvider {
+    /// Returns `true` if keyring authentication is enabled.
+    pub fn is_enabled(self) -> bool {
+        !matches!(self, Self::Disabled)
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Url is not valid Keyring target: {0}")]
@@ -35,15 +43,17 @@ pub enum Error {
 
 /// Get credentials from keyring for given url
 ///
+/// Credentials are looked up for the full URL first, falling back to the URL's host, such that
+/// credentials stored for an index's host apply to all of its pages (as in `pip`).
+///
 /// See `pip`'s KeyringCLIProvider
 /// <https://github.com/pypa/pip/blob/ae5fff36b0aad6e5e0037884927eaa29163c0611/src/pip/_internal/network/auth.py#L102>
 pub fn get_keyring_subprocess_auth(url: &Url) -> Result<Option<Credential>, Error> {
-    let host = url.host_str();
-    if host.is_none() {
+    let Some(host) = url.host_str() else {
         return Err(Error::NotKeyringTarget(
             "Should only use keyring for urls with host".to_string(),
         ));
-    }
+    };
     if url.password().is_some() {
         return Err(Error::NotKeyringTarget(
             "Url already contains password - keyring not required".to_string(),
@@ -54,14 +64,23 @@ pub fn get_keyring_subprocess_auth(url: &Url) -> Result<Option<Credential>, Erro
         // this is the username keyring.get_credentials returns as username for GCP registry
         _ => "oauth2accesstoken",
     };
-    debug!(
-        "Running `keyring get` for `{}` with username `{}`",
-        url.to_string(),
-        username
-    );
-    let output = match Command::new("keyring")
+    for service in [url.as_str(), host] {
+        if let Some(password) = keyring_get(service, username)? {
+            return Ok(Some(Credential::Basic(BasicAuthData {
+                username: username.to_string(),
+                password: Some(password),
+            })));
+        }
+    }
+    Ok(None)
+}
+
+/// Run `keyring get` for the given service and username, returning the password (if any).
+fn keyring_get(service: &str, username: &str) -> Result<Option<String>, Error> {
+    debug!("Running `keyring get` for `{service}` with username `{username}`");
+    match Command::new("keyring")
         .arg("get")
-        .arg(url.to_string())
+        .arg(service)
         .arg(username)
         .output()
     {
@@ -73,16 +92,7 @@ pub fn get_keyring_subprocess_auth(url: &Url) -> Result<Option<Credential>, Erro
         )),
         Ok(_) => Ok(None),
         Err(e) => Err(Error::CliFailure(e)),
-    };
-
-    output.map(|password| {
-        password.map(|password| {
-            Credential::Basic(BasicAuthData {
-                username: username.to_string(),
-                password: Some(password),
-            })
-        })
-    })
+    }
 }
 
 #[cfg(test)]
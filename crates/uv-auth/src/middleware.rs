@@ -1,4 +1,7 @@
+use std::env;
+use std::io::ErrorKind;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use netrc::Netrc;
 use reqwest::{header::HeaderValue, Request, Response};
@@ -7,7 +10,7 @@ use task_local_extensions::Extensions;
 use tracing::{debug, warn};
 
 use crate::{
-    keyring::{get_keyring_subprocess_auth, KeyringProvider},
+    keyring::{get_keyring_subprocess_auth, Error as KeyringError, KeyringProvider},
     store::Credential,
     GLOBAL_AUTH_STORE,
 };
@@ -18,13 +21,31 @@ use crate::{
 pub struct AuthMiddleware {
     nrc: Option<Netrc>,
     keyring_provider: KeyringProvider,
+    /// Whether the `keyring` CLI was found to be missing, in which case we avoid retrying it for
+    /// every request.
+    keyring_missing: AtomicBool,
 }
 
 impl AuthMiddleware {
+    /// Create a new [`AuthMiddleware`], reading credentials from the netrc file at `NETRC` if
+    /// set (as in `pip`), or from the default location (e.g., `~/.netrc`) otherwise.
     pub fn new(keyring_provider: KeyringProvider) -> Self {
+        let nrc = if let Some(path) = env::var_os("NETRC") {
+            let path = Path::new(&path);
+            match Netrc::from_file(path) {
+                Ok(nrc) => Some(nrc),
+                Err(err) => {
+                    warn!("Failed to read netrc file at {}: {err}", path.display());
+                    None
+                }
+            }
+        } else {
+            Netrc::new().ok()
+        };
         Self {
-            nrc: Netrc::new().ok(),
+            nrc,
             keyring_provider,
+            keyring_missing: AtomicBool::new(false),
         }
     }
 
@@ -32,6 +53,7 @@ impl AuthMiddleware {
         Self {
             nrc: Netrc::from_file(file).ok(),
             keyring_provider,
+            keyring_missing: AtomicBool::new(false),
         }
     }
 }
@@ -76,7 +98,9 @@ impl Middleware for AuthMiddleware {
                 basic_auth(auth.username(), auth.password()),
             );
             GLOBAL_AUTH_STORE.set(&url, Some(auth));
-        } else if matches!(self.keyring_provider, KeyringProvider::Subprocess) {
+        } else if self.keyring_provider.is_enabled()
+            && !self.keyring_missing.load(Ordering::Relaxed)
+        {
             // If we have keyring support enabled, we check there as well
             match get_keyring_subprocess_auth(&url) {
                 Ok(Some(auth)) => {
@@ -89,6 +113,14 @@ impl Middleware for AuthMiddleware {
                 Ok(None) => {
                     debug!("No keyring credentials found for {url}");
                 }
+                Err(KeyringError::CliFailure(e)) if e.kind() == ErrorKind::NotFound => {
+                    self.keyring_missing.store(true, Ordering::Relaxed);
+                    if matches!(self.keyring_provider, KeyringProvider::Auto) {
+                        debug!("Skipping keyring authentication: `keyring` is not installed");
+                    } else {
+                        warn!("Failed to get keyring credentials for {url}: `keyring` is not installed");
+                    }
+                }
                 Err(e) => {
                     warn!("Failed to get keyring credentials for {url}: {e}");
                 }
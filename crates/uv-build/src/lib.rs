@@ -22,7 +22,7 @@ use serde::{de, Deserialize, Deserializer, Serialize};
 use tempfile::{tempdir_in, TempDir};
 use thiserror::Error;
 use tokio::process::Command;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
 use tracing::{debug, info_span, instrument, Instrument};
 
 use distribution_types::Resolution;
@@ -31,7 +31,8 @@ use pep508_rs::Requirement;
 use uv_fs::{PythonExt, Simplified};
 use uv_interpreter::{Interpreter, PythonEnvironment};
 use uv_traits::{
-    BuildContext, BuildIsolation, BuildKind, ConfigSettings, SetupPyStrategy, SourceBuildTrait,
+    BuildContext, BuildIsolation, BuildKind, Concurrency, ConfigSettings, SetupPyStrategy,
+    SourceBuildTrait,
 };
 
 /// e.g. `pygraphviz/graphviz_wrap.c:3020:10: fatal error: graphviz/cgraph.h: No such file or directory`
@@ -74,8 +75,8 @@ pub enum Error {
     InvalidSourceDist(String),
     #[error("Invalid pyproject.toml")]
     InvalidPyprojectToml(#[from] toml::de::Error),
-    #[error("Editable installs with setup.py legacy builds are unsupported, please specify a build backend in pyproject.toml")]
-    EditableSetupPy,
+    #[error("The build backend `{0}` does not support editable installs (PEP 660); upgrade the build backend, or install without `--editable`")]
+    EditableUnsupported(String),
     #[error("Failed to install requirements from {0}")]
     RequirementsInstall(&'static str, #[source] anyhow::Error),
     #[error("Failed to create temporary virtualenv")]
@@ -328,12 +329,31 @@ impl Pep517Backend {
 }
 
 /// Uses an [`Arc`] internally, clone freely.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct SourceBuildContext {
     /// An in-memory resolution of the default backend's requirements for PEP 517 builds.
     default_resolution: Arc<Mutex<Option<Resolution>>>,
     /// An in-memory resolution of the build requirements for `--legacy-setup-py` builds.
     setup_py_resolution: Arc<Mutex<Option<Resolution>>>,
+    /// A semaphore to limit the number of concurrent builds.
+    concurrent_builds: Arc<Semaphore>,
+}
+
+impl SourceBuildContext {
+    /// Create a [`SourceBuildContext`] that runs at most `concurrent_builds` builds at once.
+    pub fn new(concurrent_builds: usize) -> Self {
+        Self {
+            default_resolution: Arc::default(),
+            setup_py_resolution: Arc::default(),
+            concurrent_builds: Arc::new(Semaphore::new(concurrent_builds)),
+        }
+    }
+}
+
+impl Default for SourceBuildContext {
+    fn default() -> Self {
+        Self::new(Concurrency::default_builds())
+    }
 }
 
 /// Holds the state through a series of PEP 517 frontend to backend calls or a single setup.py
@@ -367,6 +387,8 @@ pub struct SourceBuild {
     modified_path: OsString,
     /// Environment variables to be passed in during metadata or wheel building
     environment_variables: FxHashMap<OsString, OsString>,
+    /// A semaphore to limit the number of concurrent builds, shared across all builds.
+    concurrent_builds: Arc<Semaphore>,
 }
 
 impl SourceBuild {
@@ -397,21 +419,26 @@ impl SourceBuild {
         };
 
         let default_backend: Pep517Backend = DEFAULT_BACKEND.clone();
+        let concurrent_builds = source_build_context.concurrent_builds.clone();
 
         // Check if we have a PEP 517 build backend.
-        let pep517_backend = Self::get_pep517_backend(setup_py, &source_tree, &default_backend)
-            .map_err(|err| *err)?;
+        let pep517_backend =
+            Self::get_pep517_backend(setup_py, build_kind, &source_tree, &default_backend)
+                .map_err(|err| *err)?;
 
         // Create a virtual environment, or install into the shared environment if requested.
         let venv = match build_isolation {
-            BuildIsolation::Isolated => uv_virtualenv::create_venv(
-                &temp_dir.path().join(".venv"),
-                interpreter.clone(),
-                uv_virtualenv::Prompt::None,
-                false,
-                Vec::new(),
-            )?,
             BuildIsolation::Shared(venv) => venv.clone(),
+            BuildIsolation::Isolated | BuildIsolation::SharedPackage(..) => {
+                uv_virtualenv::create_venv(
+                    &temp_dir.path().join(".venv"),
+                    interpreter.clone(),
+                    uv_virtualenv::Prompt::None,
+                    false,
+                    false,
+                    Vec::new(),
+                )?
+            }
         };
 
         // Setup the build environment. If build isolation is disabled, we assume the build
@@ -494,9 +521,22 @@ impl SourceBuild {
             package_id,
             environment_variables,
             modified_path,
+            concurrent_builds,
         })
     }
 
+    /// Wait until a build slot is available, to limit the number of concurrent builds.
+    ///
+    /// The permit should only be held while invoking the build backend, and not while installing
+    /// build requirements, since those may require nested builds.
+    async fn acquire_build_permit(&self) -> OwnedSemaphorePermit {
+        self.concurrent_builds
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("the build semaphore is never closed")
+    }
+
     async fn get_resolved_requirements(
         build_context: &impl BuildContext,
         source_build_context: SourceBuildContext,
@@ -544,6 +584,7 @@ impl SourceBuild {
 
     fn get_pep517_backend(
         setup_py: SetupPyStrategy,
+        build_kind: BuildKind,
         source_tree: &Path,
         default_backend: &Pep517Backend,
     ) -> Result<Option<Pep517Backend>, Box<Error>> {
@@ -586,17 +627,23 @@ impl SourceBuild {
                 // case (which we allow via `SetupPyStrategy::Setuptools`), but plans to make PEP
                 // 517 builds the default in the future.
                 // See: https://github.com/pypa/pip/issues/9175.
-                match setup_py {
-                    SetupPyStrategy::Pep517 => Ok(Some(default_backend.clone())),
-                    SetupPyStrategy::Setuptools => Ok(None),
+                //
+                // Editable installs always use the default backend's PEP 660 hooks, rather than
+                // the legacy `setup.py develop`.
+                match (setup_py, build_kind) {
+                    (SetupPyStrategy::Pep517, _) | (_, BuildKind::Editable) => {
+                        Ok(Some(default_backend.clone()))
+                    }
+                    (SetupPyStrategy::Setuptools, BuildKind::Wheel) => Ok(None),
                 }
             }
             Err(err) => Err(Box::new(err.into())),
         }
     }
 
-    /// Try calling `prepare_metadata_for_build_wheel` to get the metadata without executing the
-    /// actual build.
+    /// Try calling `prepare_metadata_for_build_wheel` (or, for editable builds,
+    /// `prepare_metadata_for_build_editable`) to get the metadata without executing the actual
+    /// build.
     pub async fn get_metadata_without_build(&mut self) -> Result<Option<PathBuf>, Error> {
         let Some(pep517_backend) = &self.pep517_backend else {
             return Ok(None);
@@ -611,23 +658,23 @@ impl SourceBuild {
         fs::create_dir(&metadata_directory)?;
 
         // Write the hook output to a file so that we can read it back reliably.
-        let outfile = self
-            .temp_dir
-            .path()
-            .join("prepare_metadata_for_build_wheel.txt");
+        let outfile = self.temp_dir.path().join(format!(
+            "prepare_metadata_for_build_{}.txt",
+            self.build_kind
+        ));
 
         debug!(
-            "Calling `{}.prepare_metadata_for_build_wheel()`",
-            pep517_backend.backend
+            "Calling `{}.prepare_metadata_for_build_{}()`",
+            pep517_backend.backend, self.build_kind
         );
         let script = formatdoc! {
             r#"
             {}
             import json
 
-            prepare_metadata_for_build_wheel = getattr(backend, "prepare_metadata_for_build_wheel", None)
-            if prepare_metadata_for_build_wheel:
-                dirname = prepare_metadata_for_build_wheel("{}", config_settings={})
+            prepare_metadata_for_build = getattr(backend, "prepare_metadata_for_build_{}", None)
+            if prepare_metadata_for_build:
+                dirname = prepare_metadata_for_build("{}", config_settings={})
             else:
                 dirname = None
 
@@ -635,15 +682,17 @@ impl SourceBuild {
                 fp.write(dirname or "")
             "#,
             pep517_backend.backend_import(),
+            self.build_kind,
             escape_path_for_python(&metadata_directory),
             self.config_settings.escape_for_python(),
             outfile.escape_for_python(),
         };
         let span = info_span!(
             "run_python_script",
-            script="prepare_metadata_for_build_wheel",
+            script=format!("prepare_metadata_for_build_{}", self.build_kind),
             python_version = %self.venv.interpreter().python_version()
         );
+        let permit = self.acquire_build_permit().await;
         let output = run_python_script(
             &self.venv,
             &script,
@@ -653,9 +702,13 @@ impl SourceBuild {
         )
         .instrument(span)
         .await?;
+        drop(permit);
         if !output.status.success() {
             return Err(Error::from_command_output(
-                "Build backend failed to determine metadata through `prepare_metadata_for_build_wheel`".to_string(),
+                format!(
+                    "Build backend failed to determine metadata through `prepare_metadata_for_build_{}`",
+                    self.build_kind
+                ),
                 &output,
                 &self.package_id,
             ));
@@ -691,9 +744,8 @@ impl SourceBuild {
             fs_err::rename(from, to)?;
             Ok(filename)
         } else {
-            if self.build_kind != BuildKind::Wheel {
-                return Err(Error::EditableSetupPy);
-            }
+            // Editable builds always use a PEP 517 backend.
+            debug_assert_eq!(self.build_kind, BuildKind::Wheel);
             // We checked earlier that setup.py exists.
             let python_interpreter = self.venv.python_executable();
             let span = info_span!(
@@ -701,6 +753,7 @@ impl SourceBuild {
                 script="setup.py bdist_wheel",
                 python_version = %self.venv.interpreter().python_version()
             );
+            let _permit = self.acquire_build_permit().await;
             let output = Command::new(python_interpreter)
                 .args(["setup.py", "bdist_wheel"])
                 .current_dir(self.source_tree.simplified())
@@ -761,9 +814,14 @@ impl SourceBuild {
             r#"
             {}
 
-            wheel_filename = backend.build_{}("{}", metadata_directory={}, config_settings={})
+            build = getattr(backend, "build_{}", None)
+            if build:
+                wheel_filename = build("{}", metadata_directory={}, config_settings={})
+            else:
+                wheel_filename = None
+
             with open("{}", "w") as fp:
-                fp.write(wheel_filename)
+                fp.write(wheel_filename or "")
             "#,
             pep517_backend.backend_import(),
             self.build_kind,
@@ -777,6 +835,7 @@ impl SourceBuild {
             script=format!("build_{}", self.build_kind),
             python_version = %self.venv.interpreter().python_version()
         );
+        let _permit = self.acquire_build_permit().await;
         let output = run_python_script(
             &self.venv,
             &script,
@@ -798,6 +857,10 @@ impl SourceBuild {
         }
 
         let distribution_filename = fs::read_to_string(&outfile)?;
+        // The `build_editable` hook is optional (unlike `build_wheel`).
+        if distribution_filename.is_empty() && self.build_kind == BuildKind::Editable {
+            return Err(Error::EditableUnsupported(pep517_backend.backend.clone()));
+        }
         if !wheel_dir.join(&distribution_filename).is_file() {
             return Err(Error::from_command_output(
                 format!(
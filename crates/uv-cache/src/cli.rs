@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use clap::Parser;
 use directories::ProjectDirs;
 
-use crate::Cache;
+use crate::{Cache, CacheLocks, CacheSize};
 
 #[derive(Parser, Debug, Clone)]
 pub struct CacheArgs {
@@ -25,6 +25,32 @@ pub struct CacheArgs {
     /// on Windows.
     #[arg(global = true, long, env = "UV_CACHE_DIR")]
     cache_dir: Option<PathBuf>,
+
+    /// The maximum size of the cache (e.g., `5GB` or `500MiB`).
+    ///
+    /// If set, the least-recently-used wheels and source distribution builds are evicted (at most
+    /// once a day) whenever the cache exceeds this size.
+    #[arg(global = true, long, env = "UV_CACHE_MAX_SIZE")]
+    cache_max_size: Option<CacheSize>,
+
+    /// Whether to use file locks to coordinate access to the cache across concurrent processes.
+    ///
+    /// Disable locking in sandboxed environments, or on filesystems that don't support advisory
+    /// locks. Writes to the cache remain atomic, but concurrent processes may duplicate work.
+    #[arg(global = true, long, value_enum, default_value_t = CacheLocks::On, env = "UV_CACHE_LOCKS")]
+    cache_locks: CacheLocks,
+}
+
+impl CacheArgs {
+    /// Use the given cache directory, unless one was specified via `--cache-dir` or
+    /// `UV_CACHE_DIR` (e.g., to respect a cache directory set in a configuration file).
+    #[must_use]
+    pub fn with_default_cache_dir(self, cache_dir: Option<PathBuf>) -> Self {
+        Self {
+            cache_dir: self.cache_dir.or(cache_dir),
+            ..self
+        }
+    }
 }
 
 impl TryFrom<CacheArgs> for Cache {
@@ -38,7 +64,7 @@ impl TryFrom<CacheArgs> for Cache {
     ///
     /// Returns an absolute cache dir.
     fn try_from(value: CacheArgs) -> Result<Self, Self::Error> {
-        if value.no_cache {
+        let cache = if value.no_cache {
             Self::temp()
         } else if let Some(cache_dir) = value.cache_dir {
             Self::from_path(cache_dir)
@@ -46,6 +72,9 @@ impl TryFrom<CacheArgs> for Cache {
             Self::from_path(project_dirs.cache_dir())
         } else {
             Self::from_path(".uv_cache")
-        }
+        }?;
+        Ok(cache
+            .with_max_size(value.cache_max_size)
+            .with_locks(value.cache_locks))
     }
 }
@@ -16,6 +16,8 @@ use uv_normalize::PackageName;
 pub use crate::by_timestamp::CachedByTimestamp;
 #[cfg(feature = "clap")]
 pub use crate::cli::CacheArgs;
+pub use crate::lock::{CacheLock, CacheLocks};
+pub use crate::prune::{CacheSize, CacheSizeError};
 use crate::removal::{rm_rf, Removal};
 pub use crate::timestamp::Timestamp;
 pub use crate::wheel::WheelCache;
@@ -24,6 +26,8 @@ use crate::wheel::WheelCacheKind;
 mod by_timestamp;
 #[cfg(feature = "clap")]
 mod cli;
+mod lock;
+mod prune;
 mod removal;
 mod timestamp;
 mod wheel;
@@ -106,6 +110,10 @@ pub struct Cache {
     root: PathBuf,
     /// The refresh strategy to use when reading from the cache.
     refresh: Refresh,
+    /// The maximum size of the cache, enforced by evicting the least-recently-used entries.
+    max_size: Option<CacheSize>,
+    /// Whether to lock the cache and its entries to coordinate access across processes.
+    locks: CacheLocks,
     /// A temporary cache directory, if the user requested `--no-cache`.
     ///
     /// Included to ensure that the temporary directory exists for the length of the operation, but
@@ -119,6 +127,8 @@ impl Cache {
         Ok(Self {
             root: Self::init(root)?,
             refresh: Refresh::None,
+            max_size: None,
+            locks: CacheLocks::default(),
             _temp_dir_drop: None,
         })
     }
@@ -129,6 +139,8 @@ impl Cache {
         Ok(Self {
             root: Self::init(temp_dir.path())?,
             refresh: Refresh::None,
+            max_size: None,
+            locks: CacheLocks::default(),
             _temp_dir_drop: Some(Arc::new(temp_dir)),
         })
     }
@@ -139,6 +151,17 @@ impl Cache {
         Self { refresh, ..self }
     }
 
+    /// Set the maximum size of the cache.
+    #[must_use]
+    pub fn with_max_size(self, max_size: Option<CacheSize>) -> Self {
+        Self { max_size, ..self }
+    }
+
+    /// Return the maximum size of the cache, if set.
+    pub fn max_size(&self) -> Option<CacheSize> {
+        self.max_size
+    }
+
     /// Return the root of the cache.
     pub fn root(&self) -> &Path {
         &self.root
@@ -275,6 +298,7 @@ impl Cache {
 
     /// Clear the cache, removing all entries.
     pub fn clear(&self) -> Result<Removal, io::Error> {
+        let _lock = self.lock_exclusive()?;
         rm_rf(&self.root)
     }
 
@@ -282,6 +306,7 @@ impl Cache {
     ///
     /// Returns the number of entries removed from the cache.
     pub fn remove(&self, name: &PackageName) -> Result<Removal, io::Error> {
+        let _lock = self.lock_exclusive()?;
         let mut summary = Removal::default();
         for bucket in [
             CacheBucket::Wheels,
@@ -289,11 +314,41 @@ impl Cache {
             CacheBucket::Git,
             CacheBucket::Interpreter,
             CacheBucket::Simple,
+            CacheBucket::Resolutions,
         ] {
             summary += bucket.remove(self, name)?;
         }
         Ok(summary)
     }
+
+    /// Remove an unzipped archive from the cache, along with any wheel entries that link to it
+    /// (and their HTTP cache policies), such that the wheel is re-downloaded or rebuilt the next
+    /// time it's needed.
+    ///
+    /// The caller is responsible for holding an exclusive lock on the cache.
+    pub fn remove_archive(&self, archive: &Path) -> Result<Removal, io::Error> {
+        let mut summary = Removal::default();
+        let archive = fs::canonicalize(archive)?;
+        for bucket in [CacheBucket::Wheels, CacheBucket::BuiltWheels] {
+            let links = walkdir::WalkDir::new(self.bucket(bucket))
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path_is_symlink())
+                .filter(|entry| {
+                    fs::canonicalize(entry.path()).is_ok_and(|target| target == archive)
+                })
+                .map(walkdir::DirEntry::into_path)
+                .collect::<Vec<_>>();
+            for link in links {
+                let mut http = link.clone().into_os_string();
+                http.push(".http");
+                summary += rm_rf(PathBuf::from(http))?;
+                summary += rm_rf(link)?;
+            }
+        }
+        summary += rm_rf(archive)?;
+        Ok(summary)
+    }
 }
 
 /// The different kinds of data in the cache are stored in different bucket, which in our case
@@ -481,7 +536,7 @@ pub enum CacheBucket {
     /// without the shim itself changing, we only cache when the path equals `sys.executable`, i.e.
     /// the path we're running is the python executable itself and not a shim.
     ///
-    /// Cache structure: `interpreter-v0/<digest(path)>.msgpack`
+    /// Cache structure: `interpreter-v2/<digest(path)>.msgpack`
     ///
     /// # Example
     ///
@@ -528,6 +583,21 @@ pub enum CacheBucket {
     /// that cache entries can be atomically replaced and removed, as storing directories in the
     /// other buckets directly would make atomic operations impossible.
     Archive,
+    /// Ephemeral virtual environments used by `uv run`.
+    ///
+    /// Each environment is keyed by a digest of its requirements and base interpreter, such that
+    /// repeated invocations with the same requirements can reuse the environment.
+    ///
+    /// Cache structure: `environments-v0/<digest(requirements, interpreter)>/`
+    Environments,
+    /// Resolutions from `pip compile` and `pip install`.
+    ///
+    /// Each resolution is keyed by a digest of its inputs (the requirements, the target
+    /// environment, the index locations, and the resolver options), such that repeated
+    /// invocations with unchanged inputs can reuse the previous solution.
+    ///
+    /// Cache structure: `resolutions-v0/<command>/<digest(inputs)>.json`
+    Resolutions,
 }
 
 impl CacheBucket {
@@ -536,10 +606,12 @@ impl CacheBucket {
             Self::BuiltWheels => "built-wheels-v0",
             Self::FlatIndex => "flat-index-v0",
             Self::Git => "git-v0",
-            Self::Interpreter => "interpreter-v0",
+            Self::Interpreter => "interpreter-v2",
             Self::Simple => "simple-v5",
             Self::Wheels => "wheels-v0",
             Self::Archive => "archive-v0",
+            Self::Environments => "environments-v0",
+            Self::Resolutions => "resolutions-v0",
         }
     }
 
@@ -549,58 +621,9 @@ impl CacheBucket {
     fn remove(self, cache: &Cache, name: &PackageName) -> Result<Removal, io::Error> {
         let mut summary = Removal::default();
         match self {
-            Self::Wheels => {
-                // For `pypi` wheels, we expect a directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Pypi);
-                summary += rm_rf(root.join(name.to_string()))?;
-
-                // For alternate indices, we expect a directory for every index, followed by a
-                // directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Index);
-                for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
-                }
-
-                // For direct URLs, we expect a directory for every URL, followed by a
-                // directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Url);
-                for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
-                }
-            }
-            Self::BuiltWheels => {
-                // For `pypi` wheels, we expect a directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Pypi);
-                summary += rm_rf(root.join(name.to_string()))?;
-
-                // For alternate indices, we expect a directory for every index, followed by a
-                // directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Index);
-                for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
-                }
-
-                // For direct URLs, we expect a directory for every index, followed by a
-                // directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Url);
-                for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
-                }
-
-                // For local dependencies, we expect a directory for every path, followed by a
-                // directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Path);
-                for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
-                }
-
-                // For Git dependencies, we expect a directory for every repository, followed by a
-                // directory for every SHA, followed by a directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Git);
-                for directory in directories(root) {
-                    for directory in directories(directory) {
-                        summary += rm_rf(directory.join(name.to_string()))?;
-                    }
+            Self::Wheels | Self::BuiltWheels => {
+                for root in self.package_roots(cache) {
+                    summary += rm_rf(root.join(name.to_string()))?;
                 }
             }
             Self::Simple => {
@@ -630,9 +653,64 @@ impl CacheBucket {
             Self::Archive => {
                 // Nothing to do.
             }
+            Self::Environments => {
+                // Nothing to do.
+            }
+            Self::Resolutions => {
+                // We can't know which resolutions include a package, so we just remove all of
+                // them.
+                let root = cache.bucket(self);
+                summary += rm_rf(root)?;
+            }
         }
         Ok(summary)
     }
+
+    /// Return the directories within the bucket that contain a directory per package (indexed by
+    /// name).
+    ///
+    /// Only applies to the [`CacheBucket::Wheels`] and [`CacheBucket::BuiltWheels`] buckets.
+    fn package_roots(self, cache: &Cache) -> Vec<PathBuf> {
+        let mut roots = Vec::new();
+        match self {
+            Self::Wheels => {
+                // For `pypi` wheels, we expect a directory per package.
+                roots.push(cache.bucket(self).join(WheelCacheKind::Pypi));
+
+                // For alternate indices, we expect a directory for every index, followed by a
+                // directory per package.
+                roots.extend(directories(cache.bucket(self).join(WheelCacheKind::Index)));
+
+                // For direct URLs, we expect a directory for every URL, followed by a
+                // directory per package.
+                roots.extend(directories(cache.bucket(self).join(WheelCacheKind::Url)));
+            }
+            Self::BuiltWheels => {
+                // For `pypi` wheels, we expect a directory per package.
+                roots.push(cache.bucket(self).join(WheelCacheKind::Pypi));
+
+                // For alternate indices, we expect a directory for every index, followed by a
+                // directory per package.
+                roots.extend(directories(cache.bucket(self).join(WheelCacheKind::Index)));
+
+                // For direct URLs, we expect a directory for every index, followed by a
+                // directory per package.
+                roots.extend(directories(cache.bucket(self).join(WheelCacheKind::Url)));
+
+                // For local dependencies, we expect a directory for every path, followed by a
+                // directory per package.
+                roots.extend(directories(cache.bucket(self).join(WheelCacheKind::Path)));
+
+                // For Git dependencies, we expect a directory for every repository, followed by a
+                // directory for every SHA, followed by a directory per package.
+                for directory in directories(cache.bucket(self).join(WheelCacheKind::Git)) {
+                    roots.extend(directories(directory));
+                }
+            }
+            _ => {}
+        }
+        roots
+    }
 }
 
 impl Display for CacheBucket {
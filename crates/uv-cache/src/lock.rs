@@ -0,0 +1,129 @@
+use std::io;
+use std::path::Path;
+
+use uv_fs::{LockedFile, Simplified};
+
+use crate::{Cache, CacheEntry, CacheShard};
+
+/// The lockfile at the root of the cache, which is locked (shared) while entries are written to
+/// the cache, and locked exclusively while entries are removed from the cache (e.g., by
+/// `uv cache clean` or `uv cache prune`).
+const CACHE_LOCK: &str = ".lock";
+
+/// The directory containing the lockfiles for individual cache entries and shards.
+pub(crate) const LOCKS: &str = "locks-v0";
+
+/// Whether to use advisory file locks to coordinate concurrent access to the cache.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum CacheLocks {
+    /// Lock cache entries while they're being written, such that concurrent processes (e.g.,
+    /// parallel CI jobs sharing a cache volume) wait for each other rather than duplicating work,
+    /// and lock the cache as a whole while entries are being removed.
+    #[default]
+    On,
+    /// Disable locking, e.g., in sandboxed environments or on filesystems that don't support
+    /// advisory locks. Writes to the cache remain atomic, but concurrent processes may duplicate
+    /// work, and removing entries may race with other processes.
+    Off,
+}
+
+/// A lock on the cache or an entry within it, which is released when dropped.
+///
+/// Holds no lock if locking is disabled.
+#[derive(Debug)]
+#[must_use]
+pub struct CacheLock {
+    /// The lock on the entry, if any.
+    _entry: Option<LockedFile>,
+    /// The lock on the cache as a whole, released after the lock on the entry.
+    _cache: Option<LockedFile>,
+}
+
+impl CacheLock {
+    /// A lock that holds nothing, used when locking is disabled.
+    fn none() -> Self {
+        Self {
+            _entry: None,
+            _cache: None,
+        }
+    }
+}
+
+impl Cache {
+    /// Set the locking policy for the cache.
+    #[must_use]
+    pub fn with_locks(self, locks: CacheLocks) -> Self {
+        Self { locks, ..self }
+    }
+
+    /// Acquire an exclusive lock on the cache, blocking until any other processes have finished
+    /// writing to it.
+    pub fn lock_exclusive(&self) -> io::Result<CacheLock> {
+        if self.locks == CacheLocks::Off {
+            return Ok(CacheLock::none());
+        }
+        let lock = LockedFile::acquire(self.root.join(CACHE_LOCK), self.root.user_display())?;
+        Ok(CacheLock {
+            _entry: None,
+            _cache: Some(lock),
+        })
+    }
+
+    /// Acquire an exclusive lock on the cache, if no other processes are writing to it.
+    ///
+    /// Returns `None` if the cache is in use.
+    pub fn try_lock_exclusive(&self) -> io::Result<Option<CacheLock>> {
+        if self.locks == CacheLocks::Off {
+            return Ok(Some(CacheLock::none()));
+        }
+        let lock = LockedFile::try_acquire(self.root.join(CACHE_LOCK))?;
+        Ok(lock.map(|lock| CacheLock {
+            _entry: None,
+            _cache: Some(lock),
+        }))
+    }
+
+    /// Acquire an exclusive lock on a [`CacheEntry`] while it's written, waiting until it's
+    /// available.
+    pub async fn lock_entry(&self, entry: &CacheEntry) -> io::Result<CacheLock> {
+        self.lock_path(entry.path()).await
+    }
+
+    /// Acquire an exclusive lock on a [`CacheShard`] while it's written, waiting until it's
+    /// available.
+    pub async fn lock_shard(&self, shard: &CacheShard) -> io::Result<CacheLock> {
+        self.lock_path(shard).await
+    }
+
+    /// Acquire an exclusive lock on a path within the cache, along with a shared lock on the
+    /// cache as a whole, such that the cache isn't cleaned or pruned while the path is written.
+    ///
+    /// The lockfiles are stored in a dedicated directory, keyed by a digest of the path, rather
+    /// than alongside the locked entries, such that they aren't mistaken for cache entries. The
+    /// directory is removed when the cache is pruned.
+    ///
+    /// Since acquiring the locks may block, they're acquired on a separate thread.
+    async fn lock_path(&self, path: &Path) -> io::Result<CacheLock> {
+        if self.locks == CacheLocks::Off {
+            return Ok(CacheLock::none());
+        }
+        let root = self.root.clone();
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let cache = LockedFile::acquire_shared(root.join(CACHE_LOCK), root.user_display())?;
+            let lock_dir = root.join(LOCKS);
+            fs_err::create_dir_all(&lock_dir)?;
+            let key = path.strip_prefix(&root).unwrap_or(&path);
+            let entry =
+                LockedFile::acquire(lock_dir.join(cache_key::digest(&key)), path.user_display())?;
+            Ok(CacheLock {
+                _entry: Some(entry),
+                _cache: Some(cache),
+            })
+        })
+        .await
+        // This just forwards panics from the closure.
+        .unwrap()
+    }
+}
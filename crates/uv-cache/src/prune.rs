@@ -0,0 +1,433 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use walkdir::WalkDir;
+
+use uv_fs::directories;
+
+use crate::lock::LOCKS;
+use crate::removal::{rm_rf, Removal};
+use crate::{Cache, CacheBucket};
+
+/// The minimum interval between automatic prunes of the cache.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The minimum age of an unreferenced archive before it's removed, to avoid racing with a
+/// concurrent process that has persisted an archive but not yet linked it into the cache.
+const ORPHAN_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// The file that records the last time the cache was pruned, via its modification time.
+const LAST_PRUNE: &str = ".last-prune";
+
+/// A maximum size for the cache, like `5GB` or `500MiB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheSize(u64);
+
+impl CacheSize {
+    /// Return the size in bytes.
+    pub fn bytes(self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CacheSizeError {
+    #[error("Expected a size like `5GB` or `500MiB`, but found: `{0}`")]
+    InvalidSize(String),
+}
+
+impl FromStr for CacheSize {
+    type Err = CacheSizeError;
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let (number, unit) = value.split_at(
+            value
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(value.len()),
+        );
+        let number = number
+            .parse::<f64>()
+            .map_err(|_| CacheSizeError::InvalidSize(s.to_string()))?;
+        let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+            "" | "b" => 1,
+            "k" | "kb" => 1000,
+            "kib" => 1024,
+            "m" | "mb" => 1000_u64.pow(2),
+            "mib" => 1024_u64.pow(2),
+            "g" | "gb" => 1000_u64.pow(3),
+            "gib" => 1024_u64.pow(3),
+            "t" | "tb" => 1000_u64.pow(4),
+            "tib" => 1024_u64.pow(4),
+            _ => return Err(CacheSizeError::InvalidSize(s.to_string())),
+        };
+        Ok(Self((number * multiplier as f64) as u64))
+    }
+}
+
+impl Display for CacheSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}B", self.0)
+    }
+}
+
+/// A set of cached artifacts that's evicted as a unit: the wheels and builds for a single package
+/// within a bucket, a single cached index response, or a single Git repository or checkout.
+#[derive(Debug)]
+struct Unit {
+    /// The entry's file or directory.
+    path: PathBuf,
+    /// The unzipped archives linked from the entry.
+    archives: Vec<PathBuf>,
+    /// The total size of the entry and its archives, in bytes.
+    size: u64,
+    /// The last time any file in the entry or its archives was accessed (or, if access times
+    /// aren't available, modified).
+    ///
+    /// Only files are considered, since listing a directory (as when measuring the cache) updates
+    /// its access time, while reading a file's metadata does not.
+    last_used: SystemTime,
+}
+
+impl Unit {
+    /// Measure the file or directory at the given path.
+    fn from_path(path: PathBuf, archive_root: &Path) -> Self {
+        let mut archives = Vec::new();
+        let mut size = 0;
+        let mut last_used = SystemTime::UNIX_EPOCH;
+
+        let mut measure = |entry: &walkdir::DirEntry| {
+            let Ok(metadata) = entry.metadata() else {
+                return;
+            };
+            if !metadata.is_file() {
+                return;
+            }
+            size += metadata.len();
+            if let Ok(time) = metadata.accessed().or_else(|_| metadata.modified()) {
+                last_used = last_used.max(time);
+            }
+        };
+
+        for entry in WalkDir::new(&path).into_iter().filter_map(Result::ok) {
+            if entry.path_is_symlink() {
+                if let Ok(target) = fs_err::canonicalize(entry.path()) {
+                    if target.starts_with(archive_root) {
+                        archives.push(target);
+                    }
+                }
+            }
+            measure(&entry);
+        }
+
+        for archive in &archives {
+            for entry in WalkDir::new(archive).into_iter().filter_map(Result::ok) {
+                measure(&entry);
+            }
+        }
+
+        Self {
+            path,
+            archives,
+            size,
+            last_used,
+        }
+    }
+}
+
+impl Cache {
+    /// Prune the cache, removing any unzipped archives that are no longer referenced, and then
+    /// evicting the least-recently-used entries until the cache is no larger than `max_size`, if
+    /// provided.
+    pub fn prune(&self, max_size: Option<CacheSize>) -> io::Result<Removal> {
+        if !self.root.exists() {
+            return Ok(Removal::default());
+        }
+
+        let _lock = self.lock_exclusive()?;
+        self.prune_locked(max_size)
+    }
+
+    /// Prune the cache to its configured maximum size, if one is set and the cache hasn't been
+    /// pruned within the last day.
+    ///
+    /// Since this runs opportunistically, the cache isn't pruned if it's in use by another
+    /// process.
+    ///
+    /// Returns `None` if the cache wasn't pruned.
+    pub fn prune_if_due(&self) -> io::Result<Option<Removal>> {
+        let Some(max_size) = self.max_size else {
+            return Ok(None);
+        };
+
+        let last_prune = fs_err::metadata(self.root.join(LAST_PRUNE))
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if last_prune
+            .and_then(|last_prune| last_prune.elapsed().ok())
+            .is_some_and(|elapsed| elapsed < PRUNE_INTERVAL)
+        {
+            return Ok(None);
+        }
+
+        let Some(_lock) = self.try_lock_exclusive()? else {
+            return Ok(None);
+        };
+        self.prune_locked(Some(max_size)).map(Some)
+    }
+
+    /// Prune the cache, assuming that the caller holds an exclusive lock on the cache.
+    fn prune_locked(&self, max_size: Option<CacheSize>) -> io::Result<Removal> {
+        let mut summary = Removal::default();
+
+        summary += self.remove_orphans()?;
+        if let Some(max_size) = max_size {
+            summary += self.evict(max_size.bytes())?;
+        }
+
+        // Remove the lockfiles for individual entries, none of which can be held while the cache
+        // is locked exclusively.
+        summary += rm_rf(self.root.join(LOCKS))?;
+
+        // Record the time of the prune.
+        fs_err::write(self.root.join(LAST_PRUNE), b"")?;
+
+        Ok(summary)
+    }
+
+    /// Remove any unzipped archives that aren't linked from a wheel or source distribution
+    /// entry (e.g., after removing a package from the cache).
+    fn remove_orphans(&self) -> io::Result<Removal> {
+        let mut summary = Removal::default();
+
+        let archive_root = self.bucket(CacheBucket::Archive);
+        if !archive_root.is_dir() {
+            return Ok(summary);
+        }
+
+        // Collect the archives referenced from the cache.
+        let mut referenced = HashSet::new();
+        for bucket in [CacheBucket::Wheels, CacheBucket::BuiltWheels] {
+            for entry in WalkDir::new(self.bucket(bucket))
+                .into_iter()
+                .filter_map(Result::ok)
+            {
+                if entry.path_is_symlink() {
+                    if let Ok(target) = fs_err::canonicalize(entry.path()) {
+                        referenced.insert(target);
+                    }
+                }
+            }
+        }
+
+        for archive in directories(&archive_root) {
+            if referenced.contains(&archive) {
+                continue;
+            }
+
+            // Skip recently-created archives, which may not have been linked yet.
+            let is_recent = fs_err::metadata(&archive)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .map_or(true, |age| age < ORPHAN_AGE);
+            if is_recent {
+                continue;
+            }
+
+            summary += rm_rf(&archive)?;
+        }
+
+        Ok(summary)
+    }
+
+    /// Evict the least-recently-used entries until the cache is no larger than `max_size` bytes.
+    ///
+    /// Wheels and source distribution builds are evicted per package, cached index responses and
+    /// resolutions per file, and Git repositories and checkouts per directory. The environments
+    /// created by `uv run` (which may be in use) are never evicted, so the cache can remain larger
+    /// than `max_size` if they alone exceed it.
+    fn evict(&self, max_size: u64) -> io::Result<Removal> {
+        let mut summary = Removal::default();
+
+        let mut total: u64 = WalkDir::new(&self.root)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter_map(|entry| entry.metadata().ok())
+            .filter(std::fs::Metadata::is_file)
+            .map(|metadata| metadata.len())
+            .sum();
+        if total <= max_size {
+            return Ok(summary);
+        }
+
+        let archive_root = self.bucket(CacheBucket::Archive);
+        let mut units = Vec::new();
+        for bucket in [CacheBucket::Wheels, CacheBucket::BuiltWheels] {
+            for root in bucket.package_roots(self) {
+                for directory in directories(root) {
+                    units.push(Unit::from_path(directory, &archive_root));
+                }
+            }
+        }
+        for bucket in [
+            CacheBucket::Simple,
+            CacheBucket::FlatIndex,
+            CacheBucket::Interpreter,
+            CacheBucket::Resolutions,
+        ] {
+            for entry in WalkDir::new(self.bucket(bucket))
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_file())
+            {
+                units.push(Unit::from_path(entry.into_path(), &archive_root));
+            }
+        }
+        let git = self.bucket(CacheBucket::Git);
+        for directory in directories(git.join("db"))
+            .chain(directories(git.join("checkouts")).flat_map(directories))
+        {
+            units.push(Unit::from_path(directory, &archive_root));
+        }
+        units.sort_by_key(|unit| unit.last_used);
+
+        for unit in units {
+            if total <= max_size {
+                break;
+            }
+            total = total.saturating_sub(unit.size);
+            summary += rm_rf(&unit.path)?;
+            for archive in &unit.archives {
+                summary += rm_rf(archive)?;
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::str::FromStr;
+    use std::time::{Duration, SystemTime};
+
+    use filetime::FileTime;
+
+    use crate::{Cache, CacheBucket};
+
+    use super::{CacheSize, Unit, ORPHAN_AGE};
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// Set the access and modification times of the file or directory at the given path.
+    fn set_time(path: &Path, time: SystemTime) {
+        let time = FileTime::from_system_time(time);
+        filetime::set_file_times(path, time, time).unwrap();
+    }
+
+    /// Write a file of the given size, last accessed (and modified) at the given time.
+    fn write(path: &Path, size: usize, time: SystemTime) {
+        fs_err::create_dir_all(path.parent().unwrap()).unwrap();
+        fs_err::write(path, vec![0; size]).unwrap();
+        set_time(path, time);
+    }
+
+    #[test]
+    fn parse_cache_size() {
+        assert_eq!(CacheSize::from_str("1024").unwrap().bytes(), 1024);
+        assert_eq!(CacheSize::from_str("5GB").unwrap().bytes(), 5_000_000_000);
+        assert_eq!(CacheSize::from_str("5 gb").unwrap().bytes(), 5_000_000_000);
+        assert_eq!(
+            CacheSize::from_str("1.5GiB").unwrap().bytes(),
+            1_610_612_736
+        );
+        assert_eq!(CacheSize::from_str("500MiB").unwrap().bytes(), 524_288_000);
+        assert!(CacheSize::from_str("").is_err());
+        assert!(CacheSize::from_str("5 parsecs").is_err());
+    }
+
+    #[test]
+    fn last_used_ignores_directories() {
+        let cache = Cache::temp().unwrap();
+        let now = SystemTime::now();
+
+        let package = cache.bucket(CacheBucket::Wheels).join("pypi").join("foo");
+        write(&package.join("foo.whl"), 10, now - DAY);
+
+        // Listing the directory (e.g., during a previous prune) bumps its access time.
+        set_time(&package, now);
+
+        let unit = Unit::from_path(package, &cache.bucket(CacheBucket::Archive));
+        assert_eq!(unit.size, 10);
+        assert_eq!(
+            FileTime::from_system_time(unit.last_used),
+            FileTime::from_system_time(now - DAY)
+        );
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let cache = Cache::temp().unwrap();
+        let now = SystemTime::now();
+
+        let wheels = cache.bucket(CacheBucket::Wheels).join("pypi");
+        write(&wheels.join("old").join("old.whl"), 1000, now - 2 * DAY);
+        write(&wheels.join("new").join("new.whl"), 1000, now);
+
+        cache.evict(1500).unwrap();
+
+        assert!(!wheels.join("old").exists());
+        assert!(wheels.join("new").exists());
+    }
+
+    #[test]
+    fn evict_beyond_wheels() {
+        let cache = Cache::temp().unwrap();
+        let now = SystemTime::now();
+
+        // Cached index responses are evicted too, such that the cache can reach the limit even
+        // when it isn't dominated by wheels.
+        let simple = cache.bucket(CacheBucket::Simple).join("pypi");
+        write(&simple.join("old.rkyv"), 1000, now - 2 * DAY);
+        write(&simple.join("new.rkyv"), 1000, now - DAY);
+        let wheels = cache.bucket(CacheBucket::Wheels).join("pypi");
+        write(&wheels.join("foo").join("foo.whl"), 1000, now);
+
+        cache.evict(1500).unwrap();
+
+        assert!(!simple.join("old.rkyv").exists());
+        assert!(!simple.join("new.rkyv").exists());
+        assert!(wheels.join("foo").exists());
+    }
+
+    #[test]
+    fn remove_orphans() {
+        let cache = Cache::temp().unwrap();
+        let now = SystemTime::now();
+
+        let archives = cache.bucket(CacheBucket::Archive);
+        let old = archives.join("old");
+        write(&old.join("foo.py"), 10, now - DAY);
+        set_time(&old, now - DAY);
+        let recent = archives.join("recent");
+        write(&recent.join("foo.py"), 10, now);
+        set_time(&recent, now - ORPHAN_AGE / 2);
+
+        cache.remove_orphans().unwrap();
+
+        // Recent archives may not have been linked yet, so they're retained.
+        assert!(!old.exists());
+        assert!(recent.exists());
+    }
+}
@@ -9,6 +9,7 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::path::Path;
 use tracing::debug;
+use url::Url;
 use uv_auth::{AuthMiddleware, KeyringProvider};
 use uv_fs::Simplified;
 use uv_version::version;
@@ -16,15 +17,32 @@ use uv_warnings::warn_user_once;
 
 use crate::linehaul::LineHaul;
 use crate::middleware::OfflineMiddleware;
-use crate::tls::Roots;
-use crate::{tls, Connectivity};
+use crate::retry::{UvRetryPolicy, UvRetryableStrategy};
+use crate::tls::{Roots, TlsError};
+use crate::{tls, Connectivity, TrustedHost};
+
+/// The maximum number of redirects to follow, matching the default `reqwest` policy.
+const MAX_REDIRECTS: usize = 10;
+
+/// An invalid network setting passed to the [`BaseClientBuilder`] (e.g., via `--cert` or
+/// `--proxy`).
+#[derive(thiserror::Error, Debug)]
+pub enum SettingsError {
+    #[error("Failed to load CA bundle")]
+    Cert(#[source] TlsError),
+    #[error("Invalid proxy URL: `{0}`")]
+    Proxy(Url, #[source] reqwest::Error),
+}
 
 /// A builder for an [`RegistryClient`].
 #[derive(Debug, Clone)]
 pub struct BaseClientBuilder<'a> {
     keyring_provider: KeyringProvider,
     native_tls: bool,
+    cert: Option<&'a Path>,
+    proxy: Option<&'a Url>,
     retries: u32,
+    trusted_hosts: Vec<TrustedHost>,
     connectivity: Connectivity,
     client: Option<Client>,
     markers: Option<&'a MarkerEnvironment>,
@@ -36,8 +54,11 @@ impl BaseClientBuilder<'_> {
         Self {
             keyring_provider: KeyringProvider::default(),
             native_tls: false,
+            cert: None,
+            proxy: None,
             connectivity: Connectivity::Online,
             retries: 3,
+            trusted_hosts: Vec::new(),
             client: None,
             markers: None,
             platform: None,
@@ -70,6 +91,28 @@ impl<'a> BaseClientBuilder<'a> {
         self
     }
 
+    /// Use the root certificates in the given PEM-encoded CA bundle, in lieu of the default roots.
+    #[must_use]
+    pub fn cert(mut self, cert: Option<&'a Path>) -> Self {
+        self.cert = cert;
+        self
+    }
+
+    /// Route all requests through the given proxy, in lieu of any proxies configured via the
+    /// environment (e.g., `HTTPS_PROXY`).
+    #[must_use]
+    pub fn proxy(mut self, proxy: Option<&'a Url>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Disable TLS certificate verification for the given hosts, as in `pip`'s `--trusted-host`.
+    #[must_use]
+    pub fn trusted_hosts(mut self, trusted_hosts: Vec<TrustedHost>) -> Self {
+        self.trusted_hosts = trusted_hosts;
+        self
+    }
+
     #[must_use]
     pub fn client(mut self, client: Client) -> Self {
         self.client = Some(client);
@@ -88,6 +131,19 @@ impl<'a> BaseClientBuilder<'a> {
         self
     }
 
+    /// Check that the network settings are usable, such that invalid settings (e.g., an
+    /// unreadable CA bundle) are reported as errors, rather than surfacing when building the client.
+    pub fn check(&self) -> Result<(), SettingsError> {
+        if let Some(cert) = self.cert {
+            tls::load(Roots::File(cert)).map_err(SettingsError::Cert)?;
+        }
+        if let Some(proxy) = self.proxy {
+            reqwest::Proxy::all(proxy.as_str())
+                .map_err(|err| SettingsError::Proxy(proxy.clone(), err))?;
+        }
+        Ok(())
+    }
+
     pub fn build(self) -> BaseClient {
         // Create user agent.
         let mut user_agent_string = format!("uv/{}", version());
@@ -118,7 +174,7 @@ impl<'a> BaseClientBuilder<'a> {
         debug!("Using registry request timeout of {}s", timeout);
 
         // Initialize the base client.
-        let client = self.client.unwrap_or_else(|| {
+        let build_client = |accept_invalid_certs: bool| {
             // Check for the presence of an `SSL_CERT_FILE`.
             let ssl_cert_file_exists = env::var_os("SSL_CERT_FILE").is_some_and(|path| {
                 let path_exists = Path::new(&path).exists();
@@ -130,32 +186,93 @@ impl<'a> BaseClientBuilder<'a> {
                 }
                 path_exists
             });
-            // Load the TLS configuration.
-            let tls = tls::load(if self.native_tls || ssl_cert_file_exists {
+            // Load the TLS configuration, preferring the custom CA bundle, if provided. The bundle
+            // is expected to have been validated upfront, via [`BaseClientBuilder::check`].
+            let tls = tls::load(if let Some(cert) = self.cert {
+                Roots::File(cert)
+            } else if self.native_tls || ssl_cert_file_exists {
                 Roots::Native
             } else {
                 Roots::Webpki
             })
             .expect("Failed to load TLS configuration.");
 
-            let client_core = ClientBuilder::new()
-                .user_agent(user_agent_string)
+            let mut client_core = ClientBuilder::new()
+                .user_agent(user_agent_string.clone())
                 .pool_max_idle_per_host(20)
                 .timeout(std::time::Duration::from_secs(timeout))
-                .use_preconfigured_tls(tls);
+                .use_preconfigured_tls(if accept_invalid_certs {
+                    tls::dangerous(tls)
+                } else {
+                    tls
+                });
+
+            // Route requests through the explicit proxy, if provided. Otherwise, `reqwest` respects
+            // the `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY`, and `NO_PROXY` environment variables.
+            // Credentials embedded in the proxy URL are used for proxy authentication. Hosts listed
+            // in `NO_PROXY` bypass the proxy, as with environment proxies.
+            if let Some(proxy) = self.proxy {
+                let proxy =
+                    reqwest::Proxy::all(proxy.as_str()).expect("Failed to parse proxy URL.");
+                client_core = client_core.proxy(proxy.no_proxy(reqwest::NoProxy::from_env()));
+            }
+
+            // Certificate verification is only skipped for trusted hosts, so check every redirect
+            // target, such that a trusted host can't redirect to an untrusted one.
+            if accept_invalid_certs {
+                let trusted_hosts = self.trusted_hosts.clone();
+                client_core = client_core.redirect(reqwest::redirect::Policy::custom(
+                    move |attempt| {
+                        if attempt.previous().len() >= MAX_REDIRECTS {
+                            attempt.error("too many redirects")
+                        } else if trusted_hosts.iter().any(|host| host.matches(attempt.url())) {
+                            attempt.follow()
+                        } else {
+                            let url = attempt.url().to_string();
+                            attempt.error(format!(
+                                "refusing to follow a redirect from a trusted host to an untrusted host: {url}"
+                            ))
+                        }
+                    },
+                ));
+            }
 
             client_core.build().expect("Failed to build HTTP client.")
-        });
+        };
+        let client = self.client.clone().unwrap_or_else(|| build_client(false));
+
+        // Only initialize a client that skips certificate verification if there are hosts to use
+        // it for.
+        let dangerous_client = if self.trusted_hosts.is_empty() {
+            client.clone()
+        } else {
+            build_client(true)
+        };
+
+        BaseClient {
+            connectivity: self.connectivity,
+            client: self.apply_middleware(client),
+            dangerous_client: self.apply_middleware(dangerous_client),
+            trusted_hosts: self.trusted_hosts,
+            timeout,
+        }
+    }
 
-        // Wrap in any relevant middleware.
-        let client = match self.connectivity {
+    /// Wrap a [`Client`] in any relevant middleware.
+    fn apply_middleware(&self, client: Client) -> ClientWithMiddleware {
+        match self.connectivity {
             Connectivity::Online => {
-                let client = reqwest_middleware::ClientBuilder::new(client.clone());
+                let client = reqwest_middleware::ClientBuilder::new(client);
 
-                // Initialize the retry strategy.
-                let retry_policy =
-                    ExponentialBackoff::builder().build_with_max_retries(self.retries);
-                let retry_strategy = RetryTransientMiddleware::new_with_policy(retry_policy);
+                // Initialize the retry strategy, with a jittered exponential backoff.
+                let retry_policy = UvRetryPolicy::new(
+                    ExponentialBackoff::builder().build_with_max_retries(self.retries),
+                    self.retries,
+                );
+                let retry_strategy = RetryTransientMiddleware::new_with_policy_and_strategy(
+                    retry_policy,
+                    UvRetryableStrategy,
+                );
                 let client = client.with(retry_strategy);
 
                 // Initialize the authentication middleware to set headers.
@@ -163,15 +280,9 @@ impl<'a> BaseClientBuilder<'a> {
 
                 client.build()
             }
-            Connectivity::Offline => reqwest_middleware::ClientBuilder::new(client.clone())
+            Connectivity::Offline => reqwest_middleware::ClientBuilder::new(client)
                 .with(OfflineMiddleware)
                 .build(),
-        };
-
-        BaseClient {
-            connectivity: self.connectivity,
-            client,
-            timeout,
         }
     }
 }
@@ -181,6 +292,10 @@ impl<'a> BaseClientBuilder<'a> {
 pub struct BaseClient {
     /// The underlying HTTP client.
     client: ClientWithMiddleware,
+    /// The HTTP client to use for trusted hosts, which skips TLS certificate verification.
+    dangerous_client: ClientWithMiddleware,
+    /// The hosts for which TLS certificate verification is disabled.
+    trusted_hosts: Vec<TrustedHost>,
     /// The connectivity mode to use.
     connectivity: Connectivity,
     /// Configured client timeout, in seconds.
@@ -193,6 +308,16 @@ impl BaseClient {
         self.client.clone()
     }
 
+    /// The HTTP client to use for requests to the given [`Url`], which skips TLS certificate
+    /// verification if the URL's host is trusted.
+    pub fn for_host(&self, url: &Url) -> &ClientWithMiddleware {
+        if self.trusted_hosts.iter().any(|host| host.matches(url)) {
+            &self.dangerous_client
+        } else {
+            &self.client
+        }
+    }
+
     /// The configured client timeout, in seconds.
     pub fn timeout(&self) -> u64 {
         self.timeout
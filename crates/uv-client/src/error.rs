@@ -23,6 +23,11 @@ impl Error {
         *self.kind
     }
 
+    /// Return the [`ErrorKind`] variant of this error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
     /// Create a new error from a JSON parsing error.
     pub(crate) fn from_json_err(err: serde_json::Error, url: Url) -> Self {
         ErrorKind::BadJson { source: err, url }.into()
@@ -34,7 +39,7 @@ impl Error {
     }
 
     /// Returns `true` if this error corresponds to an offline error.
-    pub(crate) fn is_offline(&self) -> bool {
+    pub fn is_offline(&self) -> bool {
         matches!(&*self.kind, ErrorKind::Offline(_))
     }
 
@@ -210,6 +215,9 @@ pub enum ErrorKind {
     #[error("Unsupported `Content-Type` \"{1}\" for {0}. Expected JSON or HTML.")]
     UnsupportedMediaType(Url, String),
 
+    #[error("Unsupported Simple API version \"{1}\" for {0}. Expected a 1.x version.")]
+    UnsupportedApiVersion(Url, String),
+
     #[error("Reading from cache archive failed: {0}")]
     ArchiveRead(String),
 
@@ -1,12 +1,13 @@
 use std::collections::btree_map::Entry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::path::PathBuf;
 
 use futures::{FutureExt, StreamExt};
 use reqwest::Response;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tracing::{debug, info_span, instrument, warn, Instrument};
 use url::Url;
+use walkdir::WalkDir;
 
 use distribution_filename::DistFilename;
 use distribution_types::{
@@ -24,6 +25,10 @@ use crate::cached_client::{CacheControl, CachedClientError};
 use crate::html::SimpleHtml;
 use crate::{Connectivity, Error, ErrorKind, RegistryClient};
 
+/// The maximum depth to which `--find-links` directories (and the subdirectories linked from
+/// `--find-links` URLs) are searched, relative to the root.
+const MAX_DEPTH: usize = 8;
+
 #[derive(Debug, thiserror::Error)]
 pub enum FlatIndexError {
     #[error("Failed to read `--find-links` directory: {0}")]
@@ -128,7 +133,45 @@ impl<'a> FlatIndexClient<'a> {
     }
 
     /// Read a flat remote index from a `--find-links` URL.
+    ///
+    /// Any links to subdirectories of the URL (as in the directory listings served by, e.g., Nexus
+    /// "raw" repositories) are followed, up to [`MAX_DEPTH`] levels deep, and the distributions
+    /// they contain are included in the index. Each page is read at most once.
     async fn read_from_url(&self, url: &Url) -> Result<FlatIndexEntries, Error> {
+        let index_url = IndexUrl::Url(VerbatimUrl::from_url(url.clone()));
+
+        let mut entries = FlatIndexEntries::default();
+        let mut visited = FxHashSet::default();
+        let mut queue = VecDeque::from([(url.clone(), 0)]);
+        while let Some((url, depth)) = queue.pop_front() {
+            if !visited.insert(url.clone()) {
+                continue;
+            }
+            let (page, subdirectories) = self.read_page(&url, &index_url).await?;
+            entries.extend(page);
+            if depth < MAX_DEPTH {
+                queue.extend(
+                    subdirectories
+                        .into_iter()
+                        .filter(|subdirectory| !visited.contains(subdirectory))
+                        .map(|subdirectory| (subdirectory, depth + 1)),
+                );
+            } else if !subdirectories.is_empty() {
+                warn!(
+                    "Skipping subdirectories beyond a depth of {MAX_DEPTH} in `--find-links` URL: {url}"
+                );
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Read a single HTML page from a flat remote index, returning the distributions it links to,
+    /// along with the URLs of any subdirectories it links to.
+    async fn read_page(
+        &self,
+        url: &Url,
+        index_url: &IndexUrl,
+    ) -> Result<(FlatIndexEntries, Vec<Url>), Error> {
         let cache_entry = self.cache.entry(
             CacheBucket::FlatIndex,
             "html",
@@ -191,41 +234,78 @@ impl<'a> FlatIndexClient<'a> {
             .await;
         match response {
             Ok(files) => {
-                let index_url = IndexUrl::Url(VerbatimUrl::from_url(url.clone()));
-                let files = files
-                    .into_iter()
-                    .filter_map(|file| {
-                        Some((
-                            DistFilename::try_from_normalized_filename(&file.filename)?,
-                            file,
-                            index_url.clone(),
-                        ))
-                    })
-                    .collect();
-                Ok(FlatIndexEntries::from_entries(files))
+                // Only follow links that point within the page's directory, to avoid walking up
+                // (e.g., via `../`) or out of the index.
+                let directory = url.join(".").map_err(ErrorKind::UrlParseError)?;
+
+                let mut dists = Vec::new();
+                let mut subdirectories = Vec::new();
+                for file in files {
+                    // Links to directories have a trailing slash, and thus an empty filename.
+                    if file.filename.is_empty() {
+                        let subdirectory = match &file.url {
+                            FileLocation::RelativeUrl(base, url) => {
+                                pypi_types::base_url_join_relative(base, url)
+                                    .map_err(ErrorKind::JoinRelativeError)?
+                            }
+                            FileLocation::AbsoluteUrl(url) => {
+                                Url::parse(url).map_err(ErrorKind::UrlParseError)?
+                            }
+                            FileLocation::Path(_) => continue,
+                        };
+                        if subdirectory.as_str().len() > directory.as_str().len()
+                            && subdirectory.as_str().starts_with(directory.as_str())
+                        {
+                            subdirectories.push(subdirectory);
+                        }
+                        continue;
+                    }
+
+                    let Some(filename) = DistFilename::try_from_normalized_filename(&file.filename)
+                    else {
+                        continue;
+                    };
+                    dists.push((filename, file, index_url.clone()));
+                }
+                Ok((FlatIndexEntries::from_entries(dists), subdirectories))
             }
             Err(CachedClientError::Client(err)) if err.is_offline() => {
-                Ok(FlatIndexEntries::offline())
+                Ok((FlatIndexEntries::offline(), Vec::new()))
             }
             Err(err) => Err(err.into()),
         }
     }
 
     /// Read a flat remote index from a `--find-links` directory.
+    ///
+    /// The directory is searched recursively, up to [`MAX_DEPTH`] levels deep, such that
+    /// distributions nested in subdirectories (as in, e.g., a wheelhouse with a directory per
+    /// package) are included in the index. Symlinks that loop back to an ancestor directory are
+    /// skipped.
     fn read_from_directory(path: &PathBuf) -> Result<FlatIndexEntries, std::io::Error> {
         // Absolute paths are required for the URL conversion.
         let path = fs_err::canonicalize(path)?;
         let index_url = IndexUrl::Url(VerbatimUrl::from_path(&path));
 
         let mut dists = Vec::new();
-        for entry in fs_err::read_dir(path)? {
-            let entry = entry?;
-            let metadata = entry.metadata()?;
-            if !metadata.is_file() {
+        for entry in WalkDir::new(&path)
+            .follow_links(true)
+            .max_depth(MAX_DEPTH)
+            .sort_by_file_name()
+        {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) if err.loop_ancestor().is_some() => {
+                    warn!("Skipping symlink loop in `--find-links` directory: {err}");
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+            if !entry.file_type().is_file() {
                 continue;
             }
 
-            let Ok(filename) = entry.file_name().into_string() else {
+            let Some(filename) = entry.file_name().to_str() else {
                 warn!(
                     "Skipping non-UTF-8 filename in `--find-links` directory: {}",
                     entry.file_name().to_string_lossy()
@@ -240,11 +320,11 @@ impl<'a> FlatIndexClient<'a> {
                 requires_python: None,
                 size: None,
                 upload_time_utc_ms: None,
-                url: FileLocation::Path(entry.path().clone()),
+                url: FileLocation::Path(entry.path().to_path_buf()),
                 yanked: None,
             };
 
-            let Some(filename) = DistFilename::try_from_normalized_filename(&filename) else {
+            let Some(filename) = DistFilename::try_from_normalized_filename(filename) else {
                 debug!(
                     "Ignoring `--find-links` entry (expected a wheel or source distribution filename): {}",
                     entry.path().display()
@@ -131,6 +131,24 @@ impl SimpleHtml {
         }
     }
 
+    /// Parse a metadata availability attribute (e.g., `data-dist-info-metadata`), which is either
+    /// `true`, `false`, or the hash of the metadata file.
+    fn parse_metadata_attribute(
+        link: &HTMLTag,
+        attribute: &str,
+    ) -> Result<Option<DistInfoMetadata>, Error> {
+        let Some(metadata) = link.attributes().get(attribute).flatten() else {
+            return Ok(None);
+        };
+        let metadata = std::str::from_utf8(metadata.as_bytes())?;
+        let metadata = html_escape::decode_html_entities(metadata);
+        Ok(Some(match metadata.as_ref() {
+            "true" => DistInfoMetadata::Bool(true),
+            "false" => DistInfoMetadata::Bool(false),
+            fragment => DistInfoMetadata::Hashes(Self::parse_hash(fragment)?),
+        }))
+    }
+
     /// Parse a [`File`] from an `<a>` tag.
     fn parse_anchor(link: &HTMLTag) -> Result<File, Error> {
         // Extract the href.
@@ -180,21 +198,13 @@ impl SimpleHtml {
             None
         };
 
+        // Extract the `core-metadata` field, which should be set on the
+        // `data-core-metadata` attribute (PEP 714).
+        let core_metadata = Self::parse_metadata_attribute(link, "data-core-metadata")?;
+
         // Extract the `data-dist-info-metadata` field, which should be set on
-        // the `data-dist-info-metadata` attribute.
-        let dist_info_metadata = if let Some(dist_info_metadata) =
-            link.attributes().get("data-dist-info-metadata").flatten()
-        {
-            let dist_info_metadata = std::str::from_utf8(dist_info_metadata.as_bytes())?;
-            let dist_info_metadata = html_escape::decode_html_entities(dist_info_metadata);
-            match dist_info_metadata.as_ref() {
-                "true" => Some(DistInfoMetadata::Bool(true)),
-                "false" => Some(DistInfoMetadata::Bool(false)),
-                fragment => Some(DistInfoMetadata::Hashes(Self::parse_hash(fragment)?)),
-            }
-        } else {
-            None
-        };
+        // the `data-dist-info-metadata` attribute (PEP 658).
+        let dist_info_metadata = Self::parse_metadata_attribute(link, "data-dist-info-metadata")?;
 
         // Extract the `yanked` field, which should be set on the `data-yanked`
         // attribute.
@@ -207,6 +217,7 @@ impl SimpleHtml {
         };
 
         Ok(File {
+            core_metadata,
             dist_info_metadata,
             yanked,
             requires_python,
@@ -293,6 +304,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -349,6 +361,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -408,6 +421,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -464,6 +478,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2+233fca715f49-py3-none-any.whl",
                     hashes: Hashes {
@@ -520,6 +535,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "torchtext-0.17.0+cpu-cp39-cp39-win_amd64.whl",
                     hashes: Hashes {
@@ -574,6 +590,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -662,6 +679,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -751,6 +769,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "jaxlib-0.1.52+cuda100-cp36-none-manylinux2010_x86_64.whl",
                     hashes: Hashes {
@@ -766,6 +785,7 @@ mod tests {
                     yanked: None,
                 },
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "jaxlib-0.1.52+cuda100-cp37-none-manylinux2010_x86_64.whl",
                     hashes: Hashes {
@@ -830,6 +850,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Flask-0.1.tar.gz",
                     hashes: Hashes {
@@ -847,6 +868,7 @@ mod tests {
                     yanked: None,
                 },
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Flask-0.10.1.tar.gz",
                     hashes: Hashes {
@@ -864,6 +886,7 @@ mod tests {
                     yanked: None,
                 },
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "flask-3.0.1.tar.gz",
                     hashes: Hashes {
@@ -930,6 +953,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -961,4 +985,69 @@ mod tests {
         }
         "###);
     }
+
+    #[test]
+    fn parse_core_metadata() {
+        let text = r#"
+<!DOCTYPE html>
+<html>
+  <body>
+    <h1>Links for jinja2</h1>
+    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl#sha256=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61" data-core-metadata="true" data-dist-info-metadata="true">Jinja2-3.1.2-py3-none-any.whl</a><br/>
+  </body>
+</html>
+<!--TIMESTAMP 1703347410-->
+        "#;
+        let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
+        let result = SimpleHtml::parse(text, &base).unwrap();
+        insta::assert_debug_snapshot!(result, @r###"
+        SimpleHtml {
+            base: BaseUrl(
+                Url {
+                    scheme: "https",
+                    cannot_be_a_base: false,
+                    username: "",
+                    password: None,
+                    host: Some(
+                        Domain(
+                            "download.pytorch.org",
+                        ),
+                    ),
+                    port: None,
+                    path: "/whl/jinja2/",
+                    query: None,
+                    fragment: None,
+                },
+            ),
+            files: [
+                File {
+                    core_metadata: Some(
+                        Bool(
+                            true,
+                        ),
+                    ),
+                    dist_info_metadata: Some(
+                        Bool(
+                            true,
+                        ),
+                    ),
+                    filename: "Jinja2-3.1.2-py3-none-any.whl",
+                    hashes: Hashes {
+                        md5: None,
+                        sha256: Some(
+                            "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
+                        ),
+                        sha384: None,
+                        sha512: None,
+                    },
+                    requires_python: None,
+                    size: None,
+                    upload_time: None,
+                    url: "/whl/Jinja2-3.1.2-py3-none-any.whl#sha256=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
+                    yanked: None,
+                },
+            ],
+        }
+        "###);
+    }
 }
@@ -1,13 +1,14 @@
-pub use base_client::BaseClient;
+pub use base_client::{BaseClient, BaseClientBuilder, SettingsError};
 pub use cached_client::{CacheControl, CachedClient, CachedClientError, DataWithCachePolicy};
 pub use error::{BetterReqwestError, Error, ErrorKind};
 pub use flat_index::{FlatDistributions, FlatIndex, FlatIndexClient, FlatIndexError};
 pub use linehaul::LineHaul;
 pub use registry_client::{
-    Connectivity, RegistryClient, RegistryClientBuilder, SimpleMetadata, SimpleMetadatum,
-    VersionFiles,
+    Connectivity, IndexStrategy, RegistryClient, RegistryClientBuilder, SimpleMetadata,
+    SimpleMetadatum, VersionFiles,
 };
 pub use rkyvutil::OwnedArchive;
+pub use trusted_host::{TrustedHost, TrustedHostError};
 
 mod base_client;
 mod cached_client;
@@ -19,5 +20,7 @@ mod linehaul;
 mod middleware;
 mod registry_client;
 mod remote_metadata;
+mod retry;
 mod rkyvutil;
 mod tls;
+mod trusted_host;
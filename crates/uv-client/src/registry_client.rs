@@ -2,11 +2,13 @@ use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
 use async_http_range_reader::AsyncHttpRangeReader;
 use futures::{FutureExt, TryStreamExt};
 use http::HeaderMap;
 use reqwest::{Client, Response, StatusCode};
+use rustc_hash::FxHashSet;
 use serde::{Deserialize, Serialize};
 use tokio::io::AsyncReadExt;
 use tokio_util::compat::FuturesAsyncReadCompatExt;
@@ -21,7 +23,7 @@ use pep508_rs::MarkerEnvironment;
 use platform_tags::Platform;
 use pypi_types::{Metadata23, SimpleJson};
 use uv_auth::KeyringProvider;
-use uv_cache::{Cache, CacheBucket, WheelCache};
+use uv_cache::{Cache, CacheBucket, CacheEntry, WheelCache};
 use uv_normalize::PackageName;
 
 use crate::base_client::{BaseClient, BaseClientBuilder};
@@ -29,15 +31,19 @@ use crate::cached_client::CacheControl;
 use crate::html::SimpleHtml;
 use crate::remote_metadata::wheel_metadata_from_remote_zip;
 use crate::rkyvutil::OwnedArchive;
-use crate::{CachedClient, CachedClientError, Error, ErrorKind};
+use crate::{CachedClient, CachedClientError, Error, ErrorKind, TrustedHost};
 
 /// A builder for an [`RegistryClient`].
 #[derive(Debug, Clone)]
 pub struct RegistryClientBuilder<'a> {
     index_urls: IndexUrls,
+    index_strategy: IndexStrategy,
     keyring_provider: KeyringProvider,
     native_tls: bool,
+    cert: Option<&'a Path>,
+    proxy: Option<&'a Url>,
     retries: u32,
+    trusted_hosts: Vec<TrustedHost>,
     connectivity: Connectivity,
     cache: Cache,
     client: Option<Client>,
@@ -49,11 +55,15 @@ impl RegistryClientBuilder<'_> {
     pub fn new(cache: Cache) -> Self {
         Self {
             index_urls: IndexUrls::default(),
+            index_strategy: IndexStrategy::default(),
             keyring_provider: KeyringProvider::default(),
             native_tls: false,
+            cert: None,
+            proxy: None,
             cache,
             connectivity: Connectivity::Online,
             retries: 3,
+            trusted_hosts: Vec::new(),
             client: None,
             markers: None,
             platform: None,
@@ -68,6 +78,12 @@ impl<'a> RegistryClientBuilder<'a> {
         self
     }
 
+    #[must_use]
+    pub fn index_strategy(mut self, index_strategy: IndexStrategy) -> Self {
+        self.index_strategy = index_strategy;
+        self
+    }
+
     #[must_use]
     pub fn keyring_provider(mut self, keyring_provider: KeyringProvider) -> Self {
         self.keyring_provider = keyring_provider;
@@ -92,6 +108,28 @@ impl<'a> RegistryClientBuilder<'a> {
         self
     }
 
+    /// Use the root certificates in the given PEM-encoded CA bundle, in lieu of the default roots.
+    #[must_use]
+    pub fn cert(mut self, cert: Option<&'a Path>) -> Self {
+        self.cert = cert;
+        self
+    }
+
+    /// Route all requests through the given proxy, in lieu of any proxies configured via the
+    /// environment (e.g., `HTTPS_PROXY`).
+    #[must_use]
+    pub fn proxy(mut self, proxy: Option<&'a Url>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Disable TLS certificate verification for the given hosts, as in `pip`'s `--trusted-host`.
+    #[must_use]
+    pub fn trusted_hosts(mut self, trusted_hosts: Vec<TrustedHost>) -> Self {
+        self.trusted_hosts = trusted_hosts;
+        self
+    }
+
     #[must_use]
     pub fn cache<T>(mut self, cache: Cache) -> Self {
         self.cache = cache;
@@ -136,6 +174,9 @@ impl<'a> RegistryClientBuilder<'a> {
             .retries(self.retries)
             .connectivity(self.connectivity)
             .native_tls(self.native_tls)
+            .cert(self.cert)
+            .proxy(self.proxy)
+            .trusted_hosts(self.trusted_hosts)
             .keyring_provider(self.keyring_provider)
             .build();
 
@@ -147,10 +188,12 @@ impl<'a> RegistryClientBuilder<'a> {
 
         RegistryClient {
             index_urls: self.index_urls,
+            index_strategy: self.index_strategy,
             cache: self.cache,
             connectivity,
             client,
             timeout,
+            no_range_requests: Arc::default(),
         }
     }
 }
@@ -160,6 +203,8 @@ impl<'a> RegistryClientBuilder<'a> {
 pub struct RegistryClient {
     /// The index URLs to use for fetching packages.
     index_urls: IndexUrls,
+    /// The strategy to use when resolving against multiple index URLs.
+    index_strategy: IndexStrategy,
     /// The underlying HTTP client.
     client: CachedClient,
     /// Used for the remote wheel METADATA cache.
@@ -168,6 +213,9 @@ pub struct RegistryClient {
     connectivity: Connectivity,
     /// Configured client timeout, in seconds.
     timeout: u64,
+    /// The origins (e.g., `https://example.com`) that are known not to support HTTP range
+    /// requests, such that we can skip straight to streaming wheels from those origins.
+    no_range_requests: Arc<Mutex<FxHashSet<String>>>,
 }
 
 impl RegistryClient {
@@ -202,21 +250,34 @@ impl RegistryClient {
     /// "simple" here refers to [PEP 503 – Simple Repository API](https://peps.python.org/pep-0503/)
     /// and [PEP 691 – JSON-based Simple API for Python Package Indexes](https://peps.python.org/pep-0691/),
     /// which the pypi json api approximately implements.
+    ///
+    /// Returns the metadata from each index that contains the package, in priority order. Unless
+    /// the [`IndexStrategy`] permits searching across indexes, this is only the first such index.
+    /// If the package is pinned to a specific index, only that index is searched.
     #[instrument("simple_api", skip_all, fields(package = % package_name))]
     pub async fn simple(
         &self,
         package_name: &PackageName,
-    ) -> Result<(IndexUrl, OwnedArchive<SimpleMetadata>), Error> {
-        let mut it = self.index_urls.indexes().peekable();
+    ) -> Result<Vec<(IndexUrl, OwnedArchive<SimpleMetadata>)>, Error> {
+        let mut it = self.index_urls.indexes_for(package_name).peekable();
         if it.peek().is_none() {
             return Err(ErrorKind::NoIndex(package_name.as_ref().to_string()).into());
         }
 
+        let mut results = Vec::new();
         for index in it {
             let result = self.simple_single_index(package_name, index).await?;
 
-            return match result {
-                Ok(metadata) => Ok((index.clone(), metadata)),
+            match result {
+                Ok(metadata) => {
+                    results.push((index.clone(), metadata));
+
+                    // With the default strategy, stop at the first index that contains the
+                    // package, to avoid dependency confusion attacks.
+                    if self.index_strategy == IndexStrategy::FirstIndex {
+                        break;
+                    }
+                }
                 Err(CachedClientError::Client(err)) => match err.into_kind() {
                     ErrorKind::Offline(_) => continue,
                     ErrorKind::ReqwestError(err) => {
@@ -225,12 +286,16 @@ impl RegistryClient {
                         {
                             continue;
                         }
-                        Err(ErrorKind::from(err).into())
+                        return Err(ErrorKind::from(err).into());
                     }
-                    other => Err(other.into()),
+                    other => return Err(other.into()),
                 },
-                Err(CachedClientError::Callback(err)) => Err(err),
-            };
+                Err(CachedClientError::Callback(err)) => return Err(err),
+            }
+        }
+
+        if !results.is_empty() {
+            return Ok(results);
         }
 
         match self.connectivity {
@@ -308,6 +373,12 @@ impl RegistryClient {
                         let bytes = response.bytes().await.map_err(ErrorKind::from)?;
                         let data: SimpleJson = serde_json::from_slice(bytes.as_ref())
                             .map_err(|err| Error::from_json_err(err, url.clone()))?;
+                        if !data.meta.is_supported() {
+                            return Err(Error::from(ErrorKind::UnsupportedApiVersion(
+                                url.clone(),
+                                data.meta.api_version.unwrap_or_default(),
+                            )));
+                        }
 
                         SimpleMetadata::from_files(data.files, package_name, &url)
                     }
@@ -476,6 +547,72 @@ impl RegistryClient {
             Connectivity::Offline => CacheControl::AllowStale,
         };
 
+        // Avoid a redundant `HEAD` request if we already know that the server doesn't support
+        // range requests.
+        let origin = url.origin().ascii_serialization();
+        if self.no_range_requests.lock().unwrap().contains(&origin) {
+            trace!("Skipping range requests for {filename}, which {origin} doesn't support");
+        } else {
+            match self
+                .wheel_metadata_range_request(filename, url, &cache_entry, cache_control)
+                .await
+            {
+                Ok(metadata) => return Ok(metadata),
+                Err(err) => {
+                    if err.is_http_range_requests_unsupported() {
+                        // The range request version failed. Fall back to streaming the file to
+                        // search for the METADATA file.
+                        warn!("Range requests not supported for {filename}; streaming wheel");
+                        self.no_range_requests.lock().unwrap().insert(origin);
+                    } else {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+
+        // Create a request to stream the file.
+        let req = self
+            .uncached_client()
+            .get(url.clone())
+            .header(
+                // `reqwest` defaults to accepting compressed responses.
+                // Specify identity encoding to get consistent .whl downloading
+                // behavior from servers. ref: https://github.com/pypa/pip/pull/1688
+                "accept-encoding",
+                reqwest::header::HeaderValue::from_static("identity"),
+            )
+            .build()
+            .map_err(ErrorKind::from)?;
+
+        // Stream the file, searching for the METADATA.
+        let read_metadata_stream = |response: Response| {
+            async {
+                let reader = response
+                    .bytes_stream()
+                    .map_err(|err| self.handle_response_errors(err))
+                    .into_async_read();
+
+                read_metadata_async_stream(filename, url.to_string(), reader).await
+            }
+            .instrument(info_span!("read_metadata_stream", wheel = %filename))
+        };
+
+        self.cached_client()
+            .get_serde(req, &cache_entry, cache_control, read_metadata_stream)
+            .await
+            .map_err(crate::Error::from)
+    }
+
+    /// Read the wheel metadata using HTTP range requests, fetching only the zip's central
+    /// directory and the `.dist-info/METADATA` entry, rather than the entire wheel.
+    async fn wheel_metadata_range_request(
+        &self,
+        filename: &WheelFilename,
+        url: &Url,
+        cache_entry: &CacheEntry,
+        cache_control: CacheControl,
+    ) -> Result<Metadata23, Error> {
         let req = self
             .uncached_client()
             .head(url.clone())
@@ -497,7 +634,7 @@ impl RegistryClient {
         let read_metadata_range_request = |response: Response| {
             async {
                 let mut reader = AsyncHttpRangeReader::from_head_response(
-                    self.uncached_client().client(),
+                    self.uncached_client().for_host(url).clone(),
                     response,
                     headers,
                 )
@@ -518,59 +655,8 @@ impl RegistryClient {
             .instrument(info_span!("read_metadata_range_request", wheel = %filename))
         };
 
-        let result = self
-            .cached_client()
-            .get_serde(
-                req,
-                &cache_entry,
-                cache_control,
-                read_metadata_range_request,
-            )
-            .await
-            .map_err(crate::Error::from);
-
-        match result {
-            Ok(metadata) => return Ok(metadata),
-            Err(err) => {
-                if err.is_http_range_requests_unsupported() {
-                    // The range request version failed. Fall back to streaming the file to search
-                    // for the METADATA file.
-                    warn!("Range requests not supported for {filename}; streaming wheel");
-                } else {
-                    return Err(err);
-                }
-            }
-        };
-
-        // Create a request to stream the file.
-        let req = self
-            .uncached_client()
-            .get(url.clone())
-            .header(
-                // `reqwest` defaults to accepting compressed responses.
-                // Specify identity encoding to get consistent .whl downloading
-                // behavior from servers. ref: https://github.com/pypa/pip/pull/1688
-                "accept-encoding",
-                reqwest::header::HeaderValue::from_static("identity"),
-            )
-            .build()
-            .map_err(ErrorKind::from)?;
-
-        // Stream the file, searching for the METADATA.
-        let read_metadata_stream = |response: Response| {
-            async {
-                let reader = response
-                    .bytes_stream()
-                    .map_err(|err| self.handle_response_errors(err))
-                    .into_async_read();
-
-                read_metadata_async_stream(filename, url.to_string(), reader).await
-            }
-            .instrument(info_span!("read_metadata_stream", wheel = %filename))
-        };
-
         self.cached_client()
-            .get_serde(req, &cache_entry, cache_control, read_metadata_stream)
+            .get_serde(req, cache_entry, cache_control, read_metadata_range_request)
             .await
             .map_err(crate::Error::from)
     }
@@ -808,10 +894,17 @@ enum MediaType {
 
 impl MediaType {
     /// Parse a media type from a string, returning `None` if the media type is not supported.
+    ///
+    /// Media types are case-insensitive, and servers may respond with the `latest` version
+    /// alias rather than an explicit version.
     fn from_str(s: &str) -> Option<Self> {
-        match s {
-            "application/vnd.pypi.simple.v1+json" => Some(Self::Json),
-            "application/vnd.pypi.simple.v1+html" | "text/html" => Some(Self::Html),
+        match s.trim().to_ascii_lowercase().as_str() {
+            "application/vnd.pypi.simple.v1+json" | "application/vnd.pypi.simple.latest+json" => {
+                Some(Self::Json)
+            }
+            "application/vnd.pypi.simple.v1+html"
+            | "application/vnd.pypi.simple.latest+html"
+            | "text/html" => Some(Self::Html),
             _ => None,
         }
     }
@@ -824,6 +917,25 @@ impl MediaType {
     }
 }
 
+/// The strategy to use when resolving against multiple index URLs.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum IndexStrategy {
+    /// Only use results from the first index that returns a match for a given package name.
+    ///
+    /// While this differs from pip's behavior, it's the default index strategy as it's the most
+    /// secure, protecting against dependency confusion attacks.
+    #[default]
+    FirstIndex,
+    /// Search for every package name across all indexes, preferring the "best" version found.
+    /// If a package version is in multiple indexes, the first index's distributions are used.
+    ///
+    /// This most closely matches pip's behavior, but exposes the resolver to dependency confusion
+    /// attacks, in which a malicious package is published to a public index under the same name
+    /// as a private package.
+    UnsafeBestMatch,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Connectivity {
     /// Allow access to the network.
@@ -844,6 +956,36 @@ mod tests {
 
     use crate::{html::SimpleHtml, SimpleMetadata, SimpleMetadatum};
 
+    use super::MediaType;
+
+    #[test]
+    fn media_type() {
+        assert!(matches!(
+            MediaType::from_str("application/vnd.pypi.simple.v1+json"),
+            Some(MediaType::Json)
+        ));
+        assert!(matches!(
+            MediaType::from_str("application/vnd.pypi.simple.latest+json"),
+            Some(MediaType::Json)
+        ));
+        assert!(matches!(
+            MediaType::from_str(" Text/HTML"),
+            Some(MediaType::Html)
+        ));
+        assert!(MediaType::from_str("application/json").is_none());
+    }
+
+    #[test]
+    fn api_version() {
+        let response = r#"{"meta": {"api-version": "1.1"}, "name": "flask", "files": []}"#;
+        let data: SimpleJson = serde_json::from_str(response).unwrap();
+        assert!(data.meta.is_supported());
+
+        let response = r#"{"meta": {"api-version": "2.0"}, "name": "flask", "files": []}"#;
+        let data: SimpleJson = serde_json::from_str(response).unwrap();
+        assert!(!data.meta.is_supported());
+    }
+
     #[test]
     fn ignore_failing_files() {
         // 1.7.7 has an invalid requires-python field (double comma), 1.7.8 is valid
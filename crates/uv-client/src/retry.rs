@@ -0,0 +1,99 @@
+use std::error::Error;
+use std::io;
+
+use reqwest::Response;
+use reqwest_retry::{
+    default_on_request_failure, default_on_request_success, Retryable, RetryableStrategy,
+};
+use retry_policies::{RetryDecision, RetryPolicy};
+use tracing::debug;
+
+use uv_warnings::warn_user;
+
+/// A [`RetryPolicy`] that wraps another policy, reporting each retry to the user.
+///
+/// The details of the failure (i.e., the URL and the error) are logged by
+/// [`UvRetryableStrategy`], which is consulted for every failure, including the final attempt.
+pub(crate) struct UvRetryPolicy<P> {
+    policy: P,
+    retries: u32,
+}
+
+impl<P> UvRetryPolicy<P> {
+    pub(crate) fn new(policy: P, retries: u32) -> Self {
+        Self { policy, retries }
+    }
+}
+
+impl<P: RetryPolicy> RetryPolicy for UvRetryPolicy<P> {
+    fn should_retry(&self, n_past_retries: u32) -> RetryDecision {
+        let decision = self.policy.should_retry(n_past_retries);
+        if matches!(decision, RetryDecision::Retry { .. }) {
+            warn_user!(
+                "Retrying request after a transient failure ({}/{})",
+                n_past_retries + 1,
+                self.retries
+            );
+        }
+        decision
+    }
+}
+
+/// A [`RetryableStrategy`] that extends the default strategy to treat dropped connections as
+/// transient, and logs each transient failure.
+///
+/// The default strategy retries timeouts, connection failures, and `408`, `429`, and `5xx`
+/// responses, but treats a connection that's reset mid-request as fatal.
+pub(crate) struct UvRetryableStrategy;
+
+impl RetryableStrategy for UvRetryableStrategy {
+    fn handle(&self, res: &Result<Response, reqwest_middleware::Error>) -> Option<Retryable> {
+        let retryable = match res {
+            Ok(response) => default_on_request_success(response),
+            Err(err) if is_connection_reset(err) => Some(Retryable::Transient),
+            Err(err) => default_on_request_failure(err),
+        };
+
+        if retryable == Some(Retryable::Transient) {
+            match res {
+                Ok(response) => debug!(
+                    "Transient failure for request to {}, with status code: {}",
+                    response.url(),
+                    response.status()
+                ),
+                Err(reqwest_middleware::Error::Reqwest(err)) => {
+                    if let Some(url) = err.url() {
+                        debug!("Transient failure for request to {url}: {err}");
+                    } else {
+                        debug!("Transient failure for request: {err}");
+                    }
+                }
+                Err(err) => debug!("Transient failure for request: {err}"),
+            }
+        }
+
+        retryable
+    }
+}
+
+/// Returns `true` if the error was caused by the connection being reset or aborted by the peer.
+fn is_connection_reset(err: &reqwest_middleware::Error) -> bool {
+    let mut source = match err {
+        reqwest_middleware::Error::Reqwest(err) => err.source(),
+        reqwest_middleware::Error::Middleware(_) => return false,
+    };
+    while let Some(err) = source {
+        if let Some(io_err) = err.downcast_ref::<io::Error>() {
+            if matches!(
+                io_err.kind(),
+                io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+            ) {
+                return true;
+            }
+        }
+        source = err.source();
+    }
+    false
+}
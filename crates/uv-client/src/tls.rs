@@ -1,22 +1,36 @@
-use rustls::ClientConfig;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::{Certificate, ClientConfig, ServerName};
 use tracing::warn;
 
+use uv_fs::Simplified;
+
 #[derive(thiserror::Error, Debug)]
-pub(crate) enum TlsError {
+pub enum TlsError {
     #[error(transparent)]
     Rustls(#[from] rustls::Error),
     #[error("zero valid certificates found in native root store")]
     ZeroCertificates,
     #[error("failed to load native root certificates")]
     NativeCertificates(#[source] std::io::Error),
+    #[error("failed to read certificates from {}: {}", .0.user_display(), .1)]
+    CertificateFile(PathBuf, std::io::Error),
+    #[error("zero valid certificates found in {}", .0.user_display())]
+    ZeroFileCertificates(PathBuf),
 }
 
 #[derive(Debug, Clone, Copy)]
-pub(crate) enum Roots {
+pub(crate) enum Roots<'a> {
     /// Use reqwest's `rustls-tls-webpki-roots` behavior for loading root certificates.
     Webpki,
     /// Use reqwest's `rustls-tls-native-roots` behavior for loading root certificates.
     Native,
+    /// Use the root certificates in the given PEM-encoded CA bundle (e.g., via `--cert`).
+    File(&'a Path),
 }
 
 /// Initialize a TLS configuration for the client.
@@ -35,7 +49,7 @@ pub(crate) enum Roots {
 ///    - `http_version_pref: HttpVersionPref::All`
 ///
 /// See: <https://github.com/seanmonstar/reqwest/blob/e3192638518d577759dd89da489175b8f992b12f/src/async_impl/client.rs#L498>
-pub(crate) fn load(roots: Roots) -> Result<ClientConfig, TlsError> {
+pub(crate) fn load(roots: Roots<'_>) -> Result<ClientConfig, TlsError> {
     // Set root certificates.
     let mut root_cert_store = rustls::RootCertStore::empty();
 
@@ -80,6 +94,22 @@ pub(crate) fn load(roots: Roots) -> Result<ClientConfig, TlsError> {
                 return Err(TlsError::ZeroCertificates);
             }
         }
+        Roots::File(path) => {
+            let file = std::fs::File::open(path)
+                .map_err(|err| TlsError::CertificateFile(path.to_path_buf(), err))?;
+            let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+                .map_err(|err| TlsError::CertificateFile(path.to_path_buf(), err))?;
+            let (valid_count, invalid_count) = root_cert_store.add_parsable_certificates(&certs);
+            if invalid_count > 0 {
+                warn!(
+                    "Ignoring {invalid_count} invalid certificate(s) in: {}",
+                    path.user_display()
+                );
+            }
+            if valid_count == 0 {
+                return Err(TlsError::ZeroFileCertificates(path.to_path_buf()));
+            }
+        }
     }
 
     // Build TLS config
@@ -100,3 +130,28 @@ pub(crate) fn load(roots: Roots) -> Result<ClientConfig, TlsError> {
 
     Ok(tls)
 }
+
+/// Disable certificate verification for the given TLS configuration, for use with trusted hosts
+/// (e.g., a self-signed index).
+pub(crate) fn dangerous(mut tls: ClientConfig) -> ClientConfig {
+    tls.dangerous()
+        .set_certificate_verifier(Arc::new(NoCertificateVerification));
+    tls
+}
+
+/// A [`ServerCertVerifier`] that accepts any server certificate.
+struct NoCertificateVerification;
+
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
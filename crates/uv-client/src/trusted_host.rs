@@ -0,0 +1,154 @@
+use std::fmt::{Display, Formatter};
+use std::net::Ipv6Addr;
+use std::str::FromStr;
+
+use url::Url;
+
+/// A host for which TLS certificate verification is disabled, as in `pip`'s `--trusted-host`.
+///
+/// Either a bare hostname (e.g., `localhost`), in which case all ports are trusted, or a hostname
+/// and port (e.g., `localhost:8080`). IPv6 addresses must be enclosed in brackets (e.g.,
+/// `[::1]:8080`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedHost {
+    /// The hostname, in the form returned by [`Url::host_str`] (i.e., with any IPv6 address
+    /// enclosed in brackets).
+    host: String,
+    port: Option<u16>,
+}
+
+impl TrustedHost {
+    /// Returns `true` if the [`Url`] matches this trusted host.
+    pub fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        if !host.eq_ignore_ascii_case(&self.host) {
+            return false;
+        }
+        self.port
+            .map_or(true, |port| url.port_or_known_default() == Some(port))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TrustedHostError {
+    #[error("Expected a hostname, optionally followed by a port (e.g., `localhost:8080`), but found: `{0}`")]
+    InvalidHost(String),
+}
+
+impl FromStr for TrustedHost {
+    type Err = TrustedHostError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Allow, but ignore, a scheme (e.g., `https://localhost:8080`).
+        let value = s.trim();
+        let value = value
+            .split_once("://")
+            .map_or(value, |(_scheme, value)| value);
+        let value = value.trim_end_matches('/');
+
+        let parse_port = |port: &str| {
+            port.parse::<u16>()
+                .map_err(|_| TrustedHostError::InvalidHost(s.to_string()))
+        };
+
+        // An IPv6 address, which must be enclosed in brackets (e.g., `[::1]:8080`).
+        if let Some(value) = value.strip_prefix('[') {
+            let (address, port) = value
+                .split_once(']')
+                .ok_or_else(|| TrustedHostError::InvalidHost(s.to_string()))?;
+            let address = Ipv6Addr::from_str(address)
+                .map_err(|_| TrustedHostError::InvalidHost(s.to_string()))?;
+            let port = match port.strip_prefix(':') {
+                Some(port) => Some(parse_port(port)?),
+                None if port.is_empty() => None,
+                None => return Err(TrustedHostError::InvalidHost(s.to_string())),
+            };
+            return Ok(Self {
+                host: format!("[{address}]"),
+                port,
+            });
+        }
+
+        let (host, port) = match value.rsplit_once(':') {
+            Some((host, port)) => (host, Some(parse_port(port)?)),
+            None => (value, None),
+        };
+
+        if host.is_empty() || host.contains(['/', ':', '@', '[', ']']) {
+            return Err(TrustedHostError::InvalidHost(s.to_string()));
+        }
+
+        Ok(Self {
+            host: host.to_ascii_lowercase(),
+            port,
+        })
+    }
+}
+
+impl Display for TrustedHost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.port {
+            Some(port) => write!(f, "{}:{port}", self.host),
+            None => write!(f, "{}", self.host),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use url::Url;
+
+    use super::TrustedHost;
+
+    #[test]
+    fn parse() {
+        let host = TrustedHost::from_str("example.com").unwrap();
+        assert_eq!(host.to_string(), "example.com");
+
+        let host = TrustedHost::from_str("Example.com:8080").unwrap();
+        assert_eq!(host.to_string(), "example.com:8080");
+
+        let host = TrustedHost::from_str("https://example.com:8080/").unwrap();
+        assert_eq!(host.to_string(), "example.com:8080");
+
+        assert!(TrustedHost::from_str("").is_err());
+        assert!(TrustedHost::from_str("example.com:port").is_err());
+        assert!(TrustedHost::from_str("example.com/simple").is_err());
+
+        let host = TrustedHost::from_str("[::1]:8080").unwrap();
+        assert_eq!(host.to_string(), "[::1]:8080");
+
+        let host = TrustedHost::from_str("https://[0:0::1]/").unwrap();
+        assert_eq!(host.to_string(), "[::1]");
+
+        assert!(TrustedHost::from_str("::1").is_err());
+        assert!(TrustedHost::from_str("[::1").is_err());
+        assert!(TrustedHost::from_str("[::1]8080").is_err());
+        assert!(TrustedHost::from_str("[localhost]:8080").is_err());
+    }
+
+    #[test]
+    fn matches() {
+        let host = TrustedHost::from_str("example.com").unwrap();
+        assert!(host.matches(&Url::parse("https://example.com/simple").unwrap()));
+        assert!(host.matches(&Url::parse("http://EXAMPLE.com:8080/simple").unwrap()));
+        assert!(!host.matches(&Url::parse("https://pypi.org/simple").unwrap()));
+        assert!(!host.matches(&Url::parse("https://sub.example.com/simple").unwrap()));
+
+        let host = TrustedHost::from_str("example.com:8080").unwrap();
+        assert!(host.matches(&Url::parse("https://example.com:8080/simple").unwrap()));
+        assert!(!host.matches(&Url::parse("https://example.com/simple").unwrap()));
+
+        let host = TrustedHost::from_str("example.com:443").unwrap();
+        assert!(host.matches(&Url::parse("https://example.com/simple").unwrap()));
+
+        let host = TrustedHost::from_str("[::1]:8080").unwrap();
+        assert!(host.matches(&Url::parse("http://[::1]:8080/simple").unwrap()));
+        assert!(!host.matches(&Url::parse("http://[::1]/simple").unwrap()));
+        assert!(!host.matches(&Url::parse("http://[::2]:8080/simple").unwrap()));
+    }
+}
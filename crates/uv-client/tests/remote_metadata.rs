@@ -1,6 +1,14 @@
+use std::path::Path;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use anyhow::Result;
+use futures::future;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use tokio::net::TcpListener;
 
 use distribution_filename::WheelFilename;
 use distribution_types::{BuiltDist, DirectUrlBuiltDist};
@@ -28,3 +36,67 @@ async fn remote_metadata_with_and_without_cache() -> Result<()> {
 
     Ok(())
 }
+
+/// Against a server that doesn't support range requests, fall back to streaming each wheel, and
+/// skip the (redundant) `HEAD` check for subsequent wheels from the same origin.
+#[tokio::test]
+async fn remote_metadata_range_requests_unsupported() -> Result<()> {
+    let wheels = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("scripts")
+        .join("wheels");
+
+    // Set up the TCP listener on a random available port.
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    // Serve the wheels, rejecting any `HEAD` requests.
+    let head_requests = Arc::new(AtomicUsize::new(0));
+    let server_head_requests = head_requests.clone();
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let wheels = wheels.clone();
+            let head_requests = server_head_requests.clone();
+            let svc = service_fn(move |req: Request<Body>| {
+                let response = if req.method() == Method::HEAD {
+                    head_requests.fetch_add(1, Ordering::SeqCst);
+                    Response::builder()
+                        .status(StatusCode::METHOD_NOT_ALLOWED)
+                        .body(Body::empty())
+                } else {
+                    let path = wheels.join(req.uri().path().trim_start_matches('/'));
+                    Response::builder().body(Body::from(fs_err::read(path).unwrap()))
+                };
+                future::ready(response)
+            });
+            tokio::spawn(
+                Http::new()
+                    .http1_keep_alive(false)
+                    .serve_connection(socket, svc),
+            );
+        }
+    });
+
+    let cache = Cache::temp()?;
+    let client = RegistryClientBuilder::new(cache).build();
+
+    for (wheel, version) in [
+        ("tqdm-1000.0.0-py3-none-any.whl", "1000.0.0"),
+        ("maturin-1.4.0-py3-none-any.whl", "1.4.0"),
+    ] {
+        let url = format!("http://{addr}/{wheel}");
+        let dist = BuiltDist::DirectUrl(DirectUrlBuiltDist {
+            filename: WheelFilename::from_str(wheel)?,
+            url: VerbatimUrl::from_str(&url).unwrap(),
+        });
+        let metadata = client.wheel_metadata(&dist).await.unwrap();
+        assert_eq!(metadata.version.to_string(), version);
+    }
+
+    // Only the first wheel should have been checked for range request support.
+    assert_eq!(head_requests.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use futures::future;
+use hyper::header::LOCATION;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response, StatusCode};
+use tokio::net::TcpListener;
+use url::Url;
+
+use uv_client::{BaseClientBuilder, TrustedHost};
+
+#[tokio::test]
+async fn test_trusted_host_redirects() -> Result<()> {
+    // Set up the TCP listener on a random available port
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    // Spawn the server loop in a background task, redirecting to either the same (trusted) host
+    // or to the same server via an untrusted hostname
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let svc = service_fn(move |req: Request<Body>| {
+                let location = match req.uri().path() {
+                    "/trusted" => Some(format!("http://{addr}/ok")),
+                    "/untrusted" => Some(format!("http://localhost:{}/ok", addr.port())),
+                    _ => None,
+                };
+                let response = match location {
+                    Some(location) => Response::builder()
+                        .status(StatusCode::FOUND)
+                        .header(LOCATION, location)
+                        .body(Body::empty())
+                        .unwrap(),
+                    None => Response::new(Body::from("ok")),
+                };
+                future::ok::<_, hyper::Error>(response)
+            });
+            tokio::spawn(
+                Http::new()
+                    .http1_keep_alive(false)
+                    .serve_connection(socket, svc),
+            );
+        }
+    });
+
+    let client = BaseClientBuilder::new()
+        .trusted_hosts(vec![TrustedHost::from_str(&addr.to_string())?])
+        .build();
+
+    // A redirect to a trusted host is followed
+    let url = Url::parse(&format!("http://{addr}/trusted"))?;
+    let response = client.for_host(&url).get(url.clone()).send().await?;
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await?, "ok");
+
+    // A redirect to an untrusted host is refused
+    let url = Url::parse(&format!("http://{addr}/untrusted"))?;
+    let err = client
+        .for_host(&url)
+        .get(url.clone())
+        .send()
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{err:?}").contains("untrusted host"),
+        "unexpected error: {err:?}"
+    );
+
+    Ok(())
+}
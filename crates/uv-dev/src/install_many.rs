@@ -187,7 +187,7 @@ async fn install_chunk(
 
     let wheels: Vec<_> = wheels.into_iter().chain(cached).collect();
     uv_installer::Installer::new(venv)
-        .with_link_mode(LinkMode::default())
+        .with_link_mode(Some(LinkMode::default()))
         .install(&wheels)
         .context("Failed to install")?;
     info!("Installed {} wheels", wheels.len());
@@ -21,7 +21,8 @@ use uv_installer::{Downloader, Installer, NoBinary, Plan, Planner, Reinstall, Si
 use uv_interpreter::{Interpreter, PythonEnvironment};
 use uv_resolver::{InMemoryIndex, Manifest, Options, Resolver};
 use uv_traits::{
-    BuildContext, BuildIsolation, BuildKind, ConfigSettings, InFlight, NoBuild, SetupPyStrategy,
+    BuildContext, BuildIsolation, BuildKind, Concurrency, ConfigSettings, InFlight, NoBuild,
+    PackageConfigSettings, RequiredHashes, SetupPyStrategy,
 };
 
 /// The main implementation of [`BuildContext`], used by the CLI, see [`BuildContext`]
@@ -39,8 +40,12 @@ pub struct BuildDispatch<'a> {
     no_build: &'a NoBuild,
     no_binary: &'a NoBinary,
     config_settings: &'a ConfigSettings,
+    package_config_settings: PackageConfigSettings,
     source_build_context: SourceBuildContext,
+    concurrency: Concurrency,
     options: Options,
+    build_constraints: Vec<Requirement>,
+    required_hashes: RequiredHashes,
     build_extra_env_vars: FxHashMap<OsString, OsString>,
 }
 
@@ -73,8 +78,12 @@ impl<'a> BuildDispatch<'a> {
             build_isolation,
             no_build,
             no_binary,
+            package_config_settings: PackageConfigSettings::default(),
             source_build_context: SourceBuildContext::default(),
+            concurrency: Concurrency::default(),
             options: Options::default(),
+            build_constraints: Vec::new(),
+            required_hashes: RequiredHashes::default(),
             build_extra_env_vars: FxHashMap::default(),
         }
     }
@@ -85,6 +94,40 @@ impl<'a> BuildDispatch<'a> {
         self
     }
 
+    /// Set the limits on the number of concurrent downloads and builds.
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.source_build_context = SourceBuildContext::new(concurrency.builds);
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set the constraints to apply when resolving the build requirements of a source
+    /// distribution.
+    #[must_use]
+    pub fn with_build_constraints(mut self, build_constraints: Vec<Requirement>) -> Self {
+        self.build_constraints = build_constraints;
+        self
+    }
+
+    /// Set the hashes that downloaded distributions must match, in hash-checking mode.
+    #[must_use]
+    pub fn with_required_hashes(mut self, required_hashes: RequiredHashes) -> Self {
+        self.required_hashes = required_hashes;
+        self
+    }
+
+    /// Set the settings to pass to the PEP 517 build backends of specific packages, in addition to
+    /// those that apply to all packages.
+    #[must_use]
+    pub fn with_package_config_settings(
+        mut self,
+        package_config_settings: PackageConfigSettings,
+    ) -> Self {
+        self.package_config_settings = package_config_settings;
+        self
+    }
+
     /// Set the environment variables to be used when building a source distribution.
     #[must_use]
     pub fn with_build_extra_env_vars<I, K, V>(mut self, sdist_build_env_variables: I) -> Self
@@ -128,16 +171,31 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         self.index_locations
     }
 
+    fn required_hashes(&self) -> &RequiredHashes {
+        &self.required_hashes
+    }
+
     fn setup_py_strategy(&self) -> SetupPyStrategy {
         self.setup_py
     }
 
+    fn concurrency(&self) -> Concurrency {
+        self.concurrency
+    }
+
     async fn resolve<'data>(&'data self, requirements: &'data [Requirement]) -> Result<Resolution> {
         let markers = self.interpreter.markers();
         let tags = self.interpreter.tags()?;
         let resolver = Resolver::new(
-            Manifest::simple(requirements.to_vec()),
-            self.options,
+            Manifest::new(
+                requirements.to_vec(),
+                self.build_constraints.clone(),
+                Vec::new(),
+                Vec::new(),
+                None,
+                Vec::new(),
+            ),
+            self.options.clone(),
             markers,
             self.interpreter,
             tags,
@@ -284,11 +342,11 @@ impl<'a> BuildContext for BuildDispatch<'a> {
                 "Only editable builds are exempt from 'no build' checks"
             ),
             NoBuild::None => {}
-            NoBuild::Packages(packages) => {
+            NoBuild::AllExcept(_) | NoBuild::Packages(_) => {
                 if let Some(dist) = dist {
                     // We can only prevent builds by name for packages with names
                     // which is unknown before build of editable source distributions
-                    if packages.contains(dist.name()) {
+                    if self.no_build.no_build_package(dist.name()) {
                         bail!(
                             "Building source distributions for {} is disabled",
                             dist.name()
@@ -303,6 +361,13 @@ impl<'a> BuildContext for BuildDispatch<'a> {
             }
         }
 
+        let config_settings = match dist {
+            Some(dist) => self
+                .package_config_settings
+                .for_package(dist.name(), self.config_settings),
+            None => self.config_settings.clone(),
+        };
+
         let builder = SourceBuild::setup(
             source,
             subdirectory,
@@ -311,8 +376,8 @@ impl<'a> BuildContext for BuildDispatch<'a> {
             self.source_build_context.clone(),
             package_id.to_string(),
             self.setup_py,
-            self.config_settings.clone(),
-            self.build_isolation,
+            config_settings,
+            self.build_isolation.for_package(dist.map(Name::name)),
             build_kind,
             self.build_extra_env_vars.clone(),
         )
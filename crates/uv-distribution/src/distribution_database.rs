@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use futures::{FutureExt, TryStreamExt};
+use sha2::{Digest, Sha256};
 use tokio::io::AsyncSeekExt;
 use tokio_util::compat::FuturesAsyncReadCompatExt;
 use tracing::{info_span, instrument, warn, Instrument};
@@ -17,12 +18,14 @@ use platform_tags::Tags;
 use pypi_types::Metadata23;
 use uv_cache::{ArchiveTarget, ArchiveTimestamp, Cache, CacheBucket, CacheEntry, WheelCache};
 use uv_client::{CacheControl, CachedClientError, Connectivity, RegistryClient};
+use uv_fs::write_atomic;
 use uv_git::GitSource;
-use uv_traits::{BuildContext, NoBinary, NoBuild};
+use uv_traits::BuildContext;
 
 use crate::download::{BuiltWheel, UnzippedWheel};
 use crate::locks::Locks;
 use crate::reporter::Facade;
+use crate::resumable::{DownloadError, Expected, ResumableDownload};
 use crate::{DiskWheel, Error, LocalWheel, Reporter, SourceDistCachedBuilder};
 
 /// A cached high-level interface to convert distributions (a requirement resolved to a location)
@@ -74,9 +77,9 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         }
     }
 
-    /// Handle a specific `reqwest` error, and convert it to [`io::Error`].
-    fn handle_response_errors(&self, err: reqwest::Error) -> io::Error {
-        if err.is_timeout() {
+    /// Handle a specific download error, and convert it to [`io::Error`].
+    fn handle_response_errors(&self, err: DownloadError) -> io::Error {
+        if matches!(&err, DownloadError::Reqwest(err) if err.is_timeout()) {
             io::Error::new(
                 io::ErrorKind::TimedOut,
                 format!(
@@ -94,11 +97,10 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
     /// even if compatible pre-built wheels are available.
     #[instrument(skip(self))]
     pub async fn get_or_build_wheel(&self, dist: Dist) -> Result<LocalWheel, Error> {
-        let no_binary = match self.build_context.no_binary() {
-            NoBinary::None => false,
-            NoBinary::All => true,
-            NoBinary::Packages(packages) => packages.contains(dist.name()),
-        };
+        let no_binary = self
+            .build_context
+            .no_binary()
+            .no_binary_package(dist.name());
         match &dist {
             Dist::Built(BuiltDist::Registry(wheel)) => {
                 if no_binary {
@@ -160,7 +162,14 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
 
                 // Download and unzip.
                 match self
-                    .stream_wheel(url.clone(), &wheel.filename, &wheel_entry, &dist)
+                    .stream_wheel(
+                        url.clone(),
+                        &wheel.filename,
+                        Expected::from(&*wheel.file)
+                            .with_required(self.build_context.required_hashes().get(wheel.name())),
+                        &wheel_entry,
+                        &dist,
+                    )
                     .await
                 {
                     Ok(archive) => Ok(LocalWheel::Unzipped(UnzippedWheel {
@@ -176,7 +185,15 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                         // If the request failed because streaming is unsupported, download the
                         // wheel directly.
                         let archive = self
-                            .download_wheel(url, &wheel.filename, &wheel_entry, &dist)
+                            .download_wheel(
+                                url,
+                                &wheel.filename,
+                                Expected::from(&*wheel.file).with_required(
+                                    self.build_context.required_hashes().get(wheel.name()),
+                                ),
+                                &wheel_entry,
+                                &dist,
+                            )
                             .await?;
                         Ok(LocalWheel::Unzipped(UnzippedWheel {
                             dist: dist.clone(),
@@ -205,6 +222,8 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     .stream_wheel(
                         wheel.url.raw().clone(),
                         &wheel.filename,
+                        Expected::default()
+                            .with_required(self.build_context.required_hashes().get(wheel.name())),
                         &wheel_entry,
                         &dist,
                     )
@@ -226,6 +245,9 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                             .download_wheel(
                                 wheel.url.raw().clone(),
                                 &wheel.filename,
+                                Expected::default().with_required(
+                                    self.build_context.required_hashes().get(wheel.name()),
+                                ),
                                 &wheel_entry,
                                 &dist,
                             )
@@ -335,11 +357,10 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                 }
             }
             Dist::Source(source_dist) => {
-                let no_build = match self.build_context.no_build() {
-                    NoBuild::All => true,
-                    NoBuild::None => false,
-                    NoBuild::Packages(packages) => packages.contains(source_dist.name()),
-                };
+                let no_build = self
+                    .build_context
+                    .no_build()
+                    .no_build_package(source_dist.name());
                 // Optimization: Skip source dist download when we must not build them anyway.
                 if no_build {
                     return Err(Error::NoBuild);
@@ -409,11 +430,14 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         }
 
         // Fetch the precise SHA of the Git reference (which could be a branch, a tag, a partial
-        // commit, etc.).
+        // commit, etc.). The checkout isn't built here, so its history can be omitted.
+        let source = GitSource::new(url, git_dir)
+            .with_offline(self.client.connectivity() == Connectivity::Offline)
+            .with_shallow(true);
         let source = if let Some(reporter) = self.reporter.clone() {
-            GitSource::new(url, git_dir).with_reporter(Facade::from(reporter))
+            source.with_reporter(Facade::from(reporter))
         } else {
-            GitSource::new(url, git_dir)
+            source
         };
         let precise = tokio::task::spawn_blocking(move || source.fetch())
             .await?
@@ -429,23 +453,46 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         &self,
         url: Url,
         filename: &WheelFilename,
+        expected: Expected,
         wheel_entry: &CacheEntry,
         dist: &Dist,
     ) -> Result<PathBuf, Error> {
+        // Avoid races with other processes downloading the same wheel.
+        let _lock = self
+            .cache
+            .lock_entry(wheel_entry)
+            .await
+            .map_err(Error::CacheWrite)?;
+
         // Create an entry for the HTTP cache.
         let http_entry = wheel_entry.with_file(format!("{}.http", filename.stem()));
 
+        // Create an entry for the hash of the downloaded wheel.
+        let hash_entry = wheel_entry.with_file(format!("{}.sha256", filename.stem()));
+
         let download = |response: reqwest::Response| {
             async {
-                let reader = response
-                    .bytes_stream()
-                    .map_err(|err| self.handle_response_errors(err))
-                    .into_async_read();
+                let mut hasher = Sha256::new();
+                let mut reader =
+                    ResumableDownload::new(self.client.uncached_client(), response, expected)
+                        .with_reporter(self.reporter.as_ref(), dist.name())
+                        .into_stream()
+                        .inspect_ok(|chunk| hasher.update(chunk))
+                        .map_err(|err| self.handle_response_errors(err))
+                        .into_async_read()
+                        .compat();
 
                 // Download and unzip the wheel to a temporary directory.
                 let temp_dir =
                     tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
-                uv_extract::stream::unzip(reader.compat(), temp_dir.path()).await?;
+                uv_extract::stream::unzip(&mut reader, temp_dir.path()).await?;
+
+                // Consume the remainder of the response (e.g., the central directory), such that
+                // the download is verified in full.
+                tokio::io::copy(&mut reader, &mut tokio::io::sink())
+                    .await
+                    .map_err(uv_extract::Error::from)?;
+                drop(reader);
 
                 // Persist the temporary directory to the directory store.
                 let archive = self
@@ -453,6 +500,13 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     .persist(temp_dir.into_path(), wheel_entry.path())
                     .await
                     .map_err(Error::CacheRead)?;
+
+                // Record the hash of the wheel, such that it can be verified against the required
+                // hashes when it's reused.
+                write_atomic(hash_entry.path(), format!("sha256:{:x}", hasher.finalize()))
+                    .await
+                    .map_err(Error::CacheWrite)?;
+
                 Ok(archive)
             }
             .instrument(info_span!("wheel", wheel = %dist))
@@ -489,6 +543,9 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                 CachedClientError::Client(err) => Error::Client(err),
             })?;
 
+        // If the wheel was already cached, it wasn't verified as it was downloaded.
+        self.verify_wheel_hash(dist, &hash_entry)?;
+
         Ok(archive)
     }
 
@@ -497,18 +554,33 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         &self,
         url: Url,
         filename: &WheelFilename,
+        expected: Expected,
         wheel_entry: &CacheEntry,
         dist: &Dist,
     ) -> Result<PathBuf, Error> {
+        // Avoid races with other processes downloading the same wheel.
+        let _lock = self
+            .cache
+            .lock_entry(wheel_entry)
+            .await
+            .map_err(Error::CacheWrite)?;
+
         // Create an entry for the HTTP cache.
         let http_entry = wheel_entry.with_file(format!("{}.http", filename.stem()));
 
+        // Create an entry for the hash of the downloaded wheel.
+        let hash_entry = wheel_entry.with_file(format!("{}.sha256", filename.stem()));
+
         let download = |response: reqwest::Response| {
             async {
-                let reader = response
-                    .bytes_stream()
-                    .map_err(|err| self.handle_response_errors(err))
-                    .into_async_read();
+                let mut hasher = Sha256::new();
+                let reader =
+                    ResumableDownload::new(self.client.uncached_client(), response, expected)
+                        .with_reporter(self.reporter.as_ref(), dist.name())
+                        .into_stream()
+                        .inspect_ok(|chunk| hasher.update(chunk))
+                        .map_err(|err| self.handle_response_errors(err))
+                        .into_async_read();
 
                 // Download the wheel to a temporary file.
                 let temp_file =
@@ -534,6 +606,13 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     .persist(temp_dir.into_path(), wheel_entry.path())
                     .await
                     .map_err(Error::CacheRead)?;
+
+                // Record the hash of the wheel, such that it can be verified against the required
+                // hashes when it's reused.
+                write_atomic(hash_entry.path(), format!("sha256:{:x}", hasher.finalize()))
+                    .await
+                    .map_err(Error::CacheWrite)?;
+
                 Ok(archive)
             }
             .instrument(info_span!("wheel", wheel = %dist))
@@ -570,9 +649,41 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                 CachedClientError::Client(err) => Error::Client(err),
             })?;
 
+        // If the wheel was already cached, it wasn't verified as it was downloaded.
+        self.verify_wheel_hash(dist, &hash_entry)?;
+
         Ok(archive)
     }
 
+    /// Verify that a downloaded wheel matches the required hashes, if any, based on the hash
+    /// recorded when it was downloaded.
+    fn verify_wheel_hash(&self, dist: &Dist, hash_entry: &CacheEntry) -> Result<(), Error> {
+        let Some(expected) = self.build_context.required_hashes().get(dist.name()) else {
+            return Ok(());
+        };
+
+        let actual = match fs_err::read_to_string(hash_entry.path()) {
+            Ok(actual) => actual,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(Error::UnverifiedWheel(dist.to_string()));
+            }
+            Err(err) => return Err(Error::CacheRead(err)),
+        };
+        if !expected.contains(&actual) {
+            return Err(Error::HashMismatch {
+                distribution: dist.to_string(),
+                expected: expected
+                    .iter()
+                    .map(|hash| format!("`{hash}`"))
+                    .collect::<Vec<_>>()
+                    .join(" or "),
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Return the [`IndexLocations`] used by this resolver.
     pub fn index_locations(&self) -> &IndexLocations {
         self.build_context.index_locations()
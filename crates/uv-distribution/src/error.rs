@@ -59,16 +59,40 @@ pub enum Error {
     Extract(#[from] uv_extract::Error),
     #[error("Source distribution not found at: {0}")]
     NotFound(PathBuf),
+    #[error(
+        "Source distribution subdirectory must be a relative path within the source tree: `{0}`"
+    )]
+    InvalidSubdirectory(PathBuf),
+    #[error("Source distribution subdirectory not found: `{0}`")]
+    MissingSubdirectory(PathBuf),
     #[error("The source distribution is missing a `PKG-INFO` file")]
     MissingPkgInfo,
     #[error("The source distribution does not support static metadata")]
     DynamicPkgInfo(#[source] pypi_types::Error),
+    #[error("Hash mismatch for `{distribution}`: expected {expected}, found `{actual}`")]
+    HashMismatch {
+        distribution: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("The cached source distribution for `{0}` can't be verified against the provided hashes (hint: re-download it with `--refresh`)")]
+    UnverifiedSourceDist(String),
+    #[error("The cached wheel for `{0}` can't be verified against the provided hashes (hint: re-download it with `--refresh`)")]
+    UnverifiedWheel(String),
 
     /// Should not occur; only seen when another task panicked.
     #[error("The task executor is broken, did some other task panic?")]
     Join(#[from] JoinError),
 }
 
+impl Error {
+    /// Returns `true` if the requested data wasn't found in the cache, and network connectivity is
+    /// disabled.
+    pub fn is_offline(&self) -> bool {
+        matches!(self, Self::Client(err) if err.is_offline())
+    }
+}
+
 impl From<reqwest::Error> for Error {
     fn from(error: reqwest::Error) -> Self {
         Self::Reqwest(BetterReqwestError::from(error))
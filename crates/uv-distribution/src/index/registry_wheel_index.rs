@@ -90,7 +90,10 @@ impl<'a> RegistryWheelIndex<'a> {
             })
             .collect();
 
-        for index_url in index_locations.indexes().chain(flat_index_urls.iter()) {
+        for index_url in index_locations
+            .indexes_for(package)
+            .chain(flat_index_urls.iter())
+        {
             // Index all the wheels that were downloaded directly from the registry.
             let wheel_dir = cache.shard(
                 CacheBucket::Wheels,
@@ -12,5 +12,6 @@ mod error;
 mod index;
 mod locks;
 mod reporter;
+mod resumable;
 mod source;
 mod unzip;
@@ -0,0 +1,595 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::Stream;
+use reqwest::header::{
+    HeaderValue, ACCEPT_ENCODING, ACCEPT_RANGES, CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED,
+    RANGE,
+};
+use reqwest::{Response, StatusCode};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+use url::Url;
+
+use distribution_types::File;
+use uv_client::BaseClient;
+use uv_normalize::PackageName;
+
+use crate::Reporter;
+
+/// The maximum number of times to resume a single interrupted download.
+const MAX_RESUMES: usize = 5;
+
+/// The minimum number of bytes to download between progress reports.
+const PROGRESS_INTERVAL: u64 = 256 * 1024;
+
+/// The expected size and hash of a download, as reported by the index, along with the hashes
+/// that the download must match in hash-checking mode.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Expected {
+    size: Option<u64>,
+    sha256: Option<String>,
+    required: Option<Vec<String>>,
+}
+
+impl From<&File> for Expected {
+    fn from(file: &File) -> Self {
+        Self {
+            size: file.size,
+            sha256: file.hashes.sha256.as_deref().map(str::to_ascii_lowercase),
+            required: None,
+        }
+    }
+}
+
+impl Expected {
+    /// Require the download to match one of the given `{algorithm}:{digest}` hashes, if provided.
+    ///
+    /// Downloads are only hashed with SHA-256, so a download can never match a required hash that
+    /// uses any other algorithm: if none of the required hashes are SHA-256 hashes, the download
+    /// fails verification, rather than passing unchecked.
+    #[must_use]
+    pub(crate) fn with_required(self, hashes: Option<&[String]>) -> Self {
+        let required = hashes.map(|hashes| {
+            hashes
+                .iter()
+                .map(|hash| hash.to_ascii_lowercase())
+                .collect()
+        });
+        Self { required, ..self }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum DownloadError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+    #[error("Download of `{url}` is incomplete: expected {expected} bytes, but received {actual}")]
+    SizeMismatch {
+        url: Url,
+        expected: u64,
+        actual: u64,
+    },
+    #[error("Hash mismatch for `{url}`: expected `sha256:{expected}`, found `sha256:{actual}`")]
+    HashMismatch {
+        url: Url,
+        expected: String,
+        actual: String,
+    },
+    #[error("Hash mismatch for `{url}`: expected {}, found `sha256:{actual}`", format_hashes(.expected))]
+    RequiredHashMismatch {
+        url: Url,
+        expected: Vec<String>,
+        actual: String,
+    },
+}
+
+/// Format a list of hashes for display, like `` `sha256:...` or `sha256:...` ``.
+fn format_hashes(hashes: &[String]) -> String {
+    hashes
+        .iter()
+        .map(|hash| format!("`{hash}`"))
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+/// A download that can be resumed via HTTP range requests if the connection drops mid-transfer,
+/// rather than restarting from zero, and that's verified against its expected size and hash once
+/// complete.
+///
+/// Downloads are only resumed if the server advertises support for range requests, and if we can
+/// guarantee that the remaining bytes belong to the same file: either via a strong validator
+/// (`ETag` or `Last-Modified`) in an `If-Range` header, or by verifying the hash on completion.
+pub(crate) struct ResumableDownload {
+    client: BaseClient,
+    response: Response,
+    url: Url,
+    /// The validator to send in the `If-Range` header when resuming.
+    validator: Option<HeaderValue>,
+    /// Whether the download can be resumed.
+    resumable: bool,
+    /// The number of bytes received so far.
+    offset: u64,
+    /// The number of times the download has been resumed.
+    resumes: usize,
+    size: Option<u64>,
+    sha256: Option<String>,
+    /// The hashes that the download must match, in hash-checking mode.
+    required: Option<Vec<String>>,
+    hasher: Sha256,
+    /// The reporter to notify of progress, along with the package name and download ID.
+    reporter: Option<(Arc<dyn Reporter>, PackageName, usize)>,
+    /// The number of bytes received as of the last progress report.
+    reported: u64,
+}
+
+impl ResumableDownload {
+    /// Initialize a download from the response to the initial request.
+    pub(crate) fn new(client: BaseClient, response: Response, expected: Expected) -> Self {
+        let url = response.url().clone();
+        let headers = response.headers();
+        let accepts_ranges = headers
+            .get(ACCEPT_RANGES)
+            .is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"bytes"));
+        // Weak `ETag` values can't be used with `If-Range`.
+        let validator = headers
+            .get(ETAG)
+            .filter(|etag| !etag.as_bytes().starts_with(b"W/"))
+            .or_else(|| headers.get(LAST_MODIFIED))
+            .cloned();
+        let resumable = accepts_ranges
+            && (validator.is_some() || expected.sha256.is_some() || expected.required.is_some());
+        let size = expected.size.or_else(|| response.content_length());
+
+        Self {
+            client,
+            response,
+            url,
+            validator,
+            resumable,
+            offset: 0,
+            resumes: 0,
+            size,
+            sha256: expected.sha256,
+            required: expected.required,
+            hasher: Sha256::new(),
+            reporter: None,
+            reported: 0,
+        }
+    }
+
+    /// Report the progress of the download, if a [`Reporter`] is provided.
+    #[must_use]
+    pub(crate) fn with_reporter(
+        self,
+        reporter: Option<&Arc<dyn Reporter>>,
+        name: &PackageName,
+    ) -> Self {
+        let reporter = reporter.map(|reporter| {
+            let id = reporter.on_download_start(name, self.size);
+            (reporter.clone(), name.clone(), id)
+        });
+        Self { reporter, ..self }
+    }
+
+    /// Convert the download into a stream of bytes, which ends with an error if the download
+    /// couldn't be completed or failed verification.
+    pub(crate) fn into_stream(self) -> impl Stream<Item = Result<Bytes, DownloadError>> + Unpin {
+        Box::pin(futures::stream::unfold(Some(self), |download| async move {
+            let mut download = download?;
+            match download.next_chunk().await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(download))),
+                Ok(None) => None,
+                Err(err) => Some((Err(err), None)),
+            }
+        }))
+    }
+
+    /// Read the next chunk of the response body, resuming the download if it was interrupted.
+    async fn next_chunk(&mut self) -> Result<Option<Bytes>, DownloadError> {
+        loop {
+            let err = match self.response.chunk().await {
+                Ok(Some(chunk)) => {
+                    self.offset += chunk.len() as u64;
+                    self.hasher.update(&chunk);
+                    if let Some((reporter, _, id)) = &self.reporter {
+                        if self.offset - self.reported >= PROGRESS_INTERVAL {
+                            reporter.on_download_progress(*id, self.offset);
+                            self.reported = self.offset;
+                        }
+                    }
+                    return Ok(Some(chunk));
+                }
+                Ok(None) => {
+                    self.verify()?;
+                    if let Some((reporter, name, id)) = &self.reporter {
+                        reporter.on_download_progress(*id, self.offset);
+                        reporter.on_download_complete(name, *id);
+                    }
+                    return Ok(None);
+                }
+                Err(err) => err,
+            };
+
+            if !self.resumable || self.resumes >= MAX_RESUMES {
+                return Err(err.into());
+            }
+            if !(err.is_body() || err.is_timeout()) {
+                return Err(err.into());
+            }
+
+            self.resumes += 1;
+            warn!(
+                "Download of {} was interrupted after {} bytes; resuming ({err})",
+                self.url, self.offset
+            );
+            match self.resume().await {
+                Some(response) => self.response = response,
+                None => return Err(err.into()),
+            }
+        }
+    }
+
+    /// Request the remainder of the file, starting at the current offset.
+    ///
+    /// Returns `None` if the server didn't respond with the expected range.
+    async fn resume(&self) -> Option<Response> {
+        let mut request = self
+            .client
+            .for_host(&self.url)
+            .get(self.url.clone())
+            .header(ACCEPT_ENCODING, HeaderValue::from_static("identity"))
+            .header(RANGE, format!("bytes={}-", self.offset));
+        if let Some(validator) = &self.validator {
+            request = request.header(IF_RANGE, validator.clone());
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(err) => {
+                debug!("Failed to resume download of {}: {err}", self.url);
+                return None;
+            }
+        };
+
+        // If the file changed, or the server ignored the range, it will respond with the full
+        // file, which we can't splice onto the bytes we've already consumed.
+        let expected = format!("bytes {}-", self.offset);
+        let is_continuation = response.status() == StatusCode::PARTIAL_CONTENT
+            && response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with(&expected));
+        if !is_continuation {
+            debug!(
+                "Unable to resume download of {}: server responded with {}",
+                self.url,
+                response.status()
+            );
+            return None;
+        }
+
+        Some(response)
+    }
+
+    /// Verify the completed download against its expected size and hash.
+    fn verify(&self) -> Result<(), DownloadError> {
+        if let Some(expected) = self.size {
+            if self.offset != expected {
+                return Err(DownloadError::SizeMismatch {
+                    url: self.url.clone(),
+                    expected,
+                    actual: self.offset,
+                });
+            }
+        }
+
+        if self.sha256.is_none() && self.required.is_none() {
+            return Ok(());
+        }
+
+        let actual = format!("{:x}", self.hasher.clone().finalize());
+        if let Some(expected) = self.sha256.as_deref() {
+            if actual != expected {
+                return Err(DownloadError::HashMismatch {
+                    url: self.url.clone(),
+                    expected: expected.to_string(),
+                    actual,
+                });
+            }
+        }
+        if let Some(required) = self.required.as_ref() {
+            if !required.contains(&format!("sha256:{actual}")) {
+                return Err(DownloadError::RequiredHashMismatch {
+                    url: self.url.clone(),
+                    expected: required.clone(),
+                    actual,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use futures::TryStreamExt;
+    use sha2::{Digest, Sha256};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use url::Url;
+
+    use uv_client::{BaseClient, BaseClientBuilder};
+
+    use super::{DownloadError, Expected, ResumableDownload};
+
+    const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    /// How the test server responds to requests.
+    #[derive(Debug, Clone, Copy)]
+    struct Server {
+        /// Whether to advertise support for range requests.
+        accept_ranges: bool,
+        /// Whether to close the connection halfway through the body of the initial response.
+        interrupt: bool,
+        /// Whether to ignore the `Range` header, and respond with the full body.
+        ignore_range: bool,
+    }
+
+    impl Default for Server {
+        fn default() -> Self {
+            Self {
+                accept_ranges: true,
+                interrupt: false,
+                ignore_range: false,
+            }
+        }
+    }
+
+    impl Server {
+        /// Serve [`BODY`], returning the URL along with the headers of each request received.
+        async fn start(self) -> (Url, Arc<Mutex<Vec<String>>>) {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url =
+                Url::parse(&format!("http://{}/file", listener.local_addr().unwrap())).unwrap();
+            let requests = Arc::new(Mutex::new(Vec::new()));
+
+            let received = requests.clone();
+            tokio::spawn(async move {
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+
+                    let mut request = Vec::new();
+                    let mut buffer = [0; 1024];
+                    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        let n = stream.read(&mut buffer).await.unwrap();
+                        if n == 0 {
+                            break;
+                        }
+                        request.extend_from_slice(&buffer[..n]);
+                    }
+                    let request = String::from_utf8(request).unwrap().to_ascii_lowercase();
+                    let offset = request
+                        .lines()
+                        .find_map(|line| line.strip_prefix("range: bytes="))
+                        .and_then(|range| range.trim_end_matches('-').parse::<usize>().ok())
+                        .filter(|_| !self.ignore_range);
+                    let initial = received.lock().unwrap().is_empty();
+                    received.lock().unwrap().push(request);
+
+                    let mut headers = String::new();
+                    if self.accept_ranges {
+                        headers.push_str("Accept-Ranges: bytes\r\n");
+                    }
+                    headers.push_str("ETag: \"v1\"\r\nConnection: close\r\n");
+                    let response = if let Some(offset) = offset {
+                        let mut response = format!(
+                            "HTTP/1.1 206 Partial Content\r\n{headers}Content-Range: bytes {offset}-{}/{}\r\nContent-Length: {}\r\n\r\n",
+                            BODY.len() - 1,
+                            BODY.len(),
+                            BODY.len() - offset
+                        )
+                        .into_bytes();
+                        response.extend_from_slice(&BODY[offset..]);
+                        response
+                    } else {
+                        let mut response = format!(
+                            "HTTP/1.1 200 OK\r\n{headers}Content-Length: {}\r\n\r\n",
+                            BODY.len()
+                        )
+                        .into_bytes();
+                        if self.interrupt && initial {
+                            response.extend_from_slice(&BODY[..BODY.len() / 2]);
+                        } else {
+                            response.extend_from_slice(BODY);
+                        }
+                        response
+                    };
+                    stream.write_all(&response).await.unwrap();
+                    stream.shutdown().await.unwrap();
+                }
+            });
+
+            (url, requests)
+        }
+    }
+
+    fn client() -> BaseClient {
+        BaseClientBuilder::new().retries(0).build()
+    }
+
+    fn sha256(bytes: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(bytes))
+    }
+
+    async fn download(url: &Url, expected: Expected) -> Result<Vec<u8>, DownloadError> {
+        let client = client();
+        let response = client.get(url.clone()).send().await.unwrap();
+        let chunks = ResumableDownload::new(client, response, expected)
+            .into_stream()
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(chunks.concat())
+    }
+
+    #[test]
+    fn with_required() {
+        let expected = Expected::default().with_required(Some(&[
+            "sha256:ABCDEF".to_string(),
+            "md5:123456".to_string(),
+        ]));
+        assert_eq!(
+            expected.required,
+            Some(vec!["sha256:abcdef".to_string(), "md5:123456".to_string()])
+        );
+
+        let expected = Expected::default().with_required(None);
+        assert_eq!(expected.required, None);
+    }
+
+    #[tokio::test]
+    async fn complete() {
+        let (url, requests) = Server::default().start().await;
+
+        let expected = Expected {
+            size: Some(BODY.len() as u64),
+            sha256: Some(sha256(BODY)),
+            required: None,
+        };
+        let body = download(&url, expected).await.unwrap();
+
+        assert_eq!(body, BODY);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn resume_interrupted() {
+        let (url, requests) = Server {
+            interrupt: true,
+            ..Server::default()
+        }
+        .start()
+        .await;
+
+        let expected = Expected {
+            size: Some(BODY.len() as u64),
+            sha256: Some(sha256(BODY)),
+            required: None,
+        };
+        let body = download(&url, expected).await.unwrap();
+
+        assert_eq!(body, BODY);
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].contains(&format!("range: bytes={}-", BODY.len() / 2)));
+        assert!(requests[1].contains("if-range: \"v1\""));
+    }
+
+    #[tokio::test]
+    async fn no_resume_without_range_support() {
+        let (url, requests) = Server {
+            accept_ranges: false,
+            interrupt: true,
+            ..Server::default()
+        }
+        .start()
+        .await;
+
+        let expected = Expected {
+            size: Some(BODY.len() as u64),
+            sha256: Some(sha256(BODY)),
+            required: None,
+        };
+        let err = download(&url, expected).await.unwrap_err();
+
+        assert!(matches!(err, DownloadError::Reqwest(_)), "{err}");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn no_resume_if_range_ignored() {
+        let (url, requests) = Server {
+            interrupt: true,
+            ignore_range: true,
+            ..Server::default()
+        }
+        .start()
+        .await;
+
+        let expected = Expected {
+            size: Some(BODY.len() as u64),
+            sha256: Some(sha256(BODY)),
+            required: None,
+        };
+        let err = download(&url, expected).await.unwrap_err();
+
+        // The server responded to the range request with the full file, which can't be spliced
+        // onto the bytes already received.
+        assert!(matches!(err, DownloadError::Reqwest(_)), "{err}");
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn size_mismatch() {
+        let (url, _) = Server::default().start().await;
+
+        let expected = Expected {
+            size: Some(BODY.len() as u64 + 1),
+            sha256: None,
+            required: None,
+        };
+        let err = download(&url, expected).await.unwrap_err();
+
+        assert!(
+            matches!(err, DownloadError::SizeMismatch { actual, .. } if actual == BODY.len() as u64),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn hash_mismatch() {
+        let (url, _) = Server::default().start().await;
+
+        let expected = Expected {
+            size: None,
+            sha256: Some(sha256(b"other")),
+            required: None,
+        };
+        let err = download(&url, expected).await.unwrap_err();
+
+        assert!(matches!(err, DownloadError::HashMismatch { .. }), "{err}");
+    }
+
+    #[tokio::test]
+    async fn required_hashes() {
+        let (url, _) = Server::default().start().await;
+
+        // The download must match one of the required hashes.
+        let expected = Expected::default().with_required(Some(&[
+            format!("sha256:{}", sha256(b"other")),
+            format!("sha256:{}", sha256(BODY)),
+        ]));
+        let body = download(&url, expected).await.unwrap();
+        assert_eq!(body, BODY);
+
+        let expected =
+            Expected::default().with_required(Some(&[format!("sha256:{}", sha256(b"other"))]));
+        let err = download(&url, expected).await.unwrap_err();
+        assert!(
+            matches!(err, DownloadError::RequiredHashMismatch { .. }),
+            "{err}"
+        );
+
+        // If none of the required hashes can be compared against the download, it must fail.
+        let expected = Expected::default().with_required(Some(&["sha512:abcdef".to_string()]));
+        let err = download(&url, expected).await.unwrap_err();
+        assert!(
+            matches!(err, DownloadError::RequiredHashMismatch { .. }),
+            "{err}"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! Fetch and build source distributions from remote sources.
 
-use std::path::{Path, PathBuf};
+use std::borrow::Cow;
+use std::path::{Component, Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -8,6 +9,7 @@ use anyhow::Result;
 use fs_err::tokio as fs;
 use futures::{FutureExt, TryStreamExt};
 use reqwest::Response;
+use sha2::{Digest, Sha256};
 use tempfile::TempDir;
 use tokio_util::compat::FuturesAsyncReadCompatExt;
 use tracing::{debug, info_span, instrument, Instrument};
@@ -29,12 +31,13 @@ use uv_cache::{
 use uv_client::{
     CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, RegistryClient,
 };
-use uv_fs::{write_atomic, LockedFile};
+use uv_fs::{rename_with_retry, write_atomic, LockedFile};
 use uv_git::{Fetch, GitSource};
-use uv_traits::{BuildContext, BuildKind, NoBuild, SourceBuildTrait};
+use uv_traits::{BuildContext, BuildKind, SourceBuildTrait};
 
 use crate::error::Error;
 use crate::reporter::Facade;
+use crate::resumable::{Expected, ResumableDownload};
 use crate::source::built_wheel_metadata::BuiltWheelMetadata;
 use crate::source::manifest::Manifest;
 use crate::Reporter;
@@ -53,6 +56,10 @@ pub struct SourceDistCachedBuilder<'a, T: BuildContext> {
 /// The name of the file that contains the cached manifest, encoded via `MsgPack`.
 pub(crate) const MANIFEST: &str = "manifest.msgpack";
 
+/// The name of the file that records the hash of a downloaded source distribution, as
+/// `sha256:{digest}`.
+pub(crate) const SOURCE_HASH: &str = "source.sha256";
+
 /// The name of the file that contains the cached distribution metadata, encoded via `MsgPack`.
 pub(crate) const METADATA: &str = "metadata.msgpack";
 
@@ -330,11 +337,27 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         // shard.
         let cache_shard = cache_shard.shard(manifest.id());
 
+        // Verify that any cached builds were produced from a source distribution that matches the
+        // required hashes.
+        self.verify_source_hash(source_dist, &cache_shard)?;
+
         // If the cache contains a compatible wheel, return it.
         if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &cache_shard) {
             return Ok(built_wheel);
         }
 
+        // Avoid races with other processes building the same source distribution. Once we hold
+        // the lock, check the cache again, in case another process built the wheel in the interim.
+        let _lock = self
+            .build_context
+            .cache()
+            .lock_shard(&cache_shard)
+            .await
+            .map_err(Error::CacheWrite)?;
+        if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &cache_shard) {
+            return Ok(built_wheel);
+        }
+
         let task = self
             .reporter
             .as_ref()
@@ -439,6 +462,10 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         // shard.
         let cache_shard = cache_shard.shard(manifest.id());
 
+        // Verify that any cached builds were produced from a source distribution that matches the
+        // required hashes.
+        self.verify_source_hash(source_dist, &cache_shard)?;
+
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
         if let Some(metadata) = read_cached_metadata(&metadata_entry).await? {
@@ -538,6 +565,18 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             return Ok(built_wheel);
         }
 
+        // Avoid races with other processes building the same source distribution. Once we hold
+        // the lock, check the cache again, in case another process built the wheel in the interim.
+        let _lock = self
+            .build_context
+            .cache()
+            .lock_shard(&cache_shard)
+            .await
+            .map_err(Error::CacheWrite)?;
+        if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &cache_shard) {
+            return Ok(built_wheel);
+        }
+
         // Otherwise, we need to build a wheel.
         let task = self
             .reporter
@@ -683,6 +722,18 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             return Ok(built_wheel);
         }
 
+        // Avoid races with other processes building the same source distribution. Once we hold
+        // the lock, check the cache again, in case another process built the wheel in the interim.
+        let _lock = self
+            .build_context
+            .cache()
+            .lock_shard(&cache_shard)
+            .await
+            .map_err(Error::CacheWrite)?;
+        if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &cache_shard) {
+            return Ok(built_wheel);
+        }
+
         let task = self
             .reporter
             .as_ref()
@@ -816,11 +867,27 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         let temp_dir =
             tempfile::tempdir_in(self.build_context.cache().bucket(CacheBucket::BuiltWheels))
                 .map_err(Error::CacheWrite)?;
-        let reader = response
-            .bytes_stream()
+        let expected = match source_dist {
+            SourceDist::Registry(dist) => Expected::from(&*dist.file),
+            _ => Expected::default(),
+        }
+        .with_required(self.build_context.required_hashes().get(source_dist.name()));
+        let mut hasher = Sha256::new();
+        let mut reader = ResumableDownload::new(self.client.uncached_client(), response, expected)
+            .with_reporter(self.reporter.as_ref(), source_dist.name())
+            .into_stream()
+            .inspect_ok(|chunk| hasher.update(chunk))
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
-            .into_async_read();
-        uv_extract::stream::archive(reader.compat(), filename, temp_dir.path()).await?;
+            .into_async_read()
+            .compat();
+        uv_extract::stream::archive(&mut reader, filename, temp_dir.path()).await?;
+
+        // Consume the remainder of the response (e.g., any trailing padding), such that the
+        // download is verified in full.
+        tokio::io::copy(&mut reader, &mut tokio::io::sink())
+            .await
+            .map_err(uv_extract::Error::from)?;
+        drop(reader);
         drop(span);
 
         // Extract the top-level directory.
@@ -838,9 +905,51 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             .await
             .map_err(Error::CacheWrite)?;
 
+        // Record the hash of the source distribution, such that any builds can be verified against
+        // the required hashes when they're reused.
+        write_atomic(
+            cache_entry.with_file(SOURCE_HASH).path(),
+            format!("sha256:{:x}", hasher.finalize()),
+        )
+        .await
+        .map_err(Error::CacheWrite)?;
+
         Ok(cache_path)
     }
 
+    /// Verify that the source distribution for a build matches the required hashes, if any, based
+    /// on the hash recorded when it was downloaded.
+    fn verify_source_hash(
+        &self,
+        source_dist: &SourceDist,
+        cache_shard: &CacheShard,
+    ) -> Result<(), Error> {
+        let Some(expected) = self.build_context.required_hashes().get(source_dist.name()) else {
+            return Ok(());
+        };
+
+        let actual = match fs_err::read_to_string(cache_shard.join(SOURCE_HASH)) {
+            Ok(actual) => actual,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::UnverifiedSourceDist(source_dist.to_string()));
+            }
+            Err(err) => return Err(Error::CacheRead(err)),
+        };
+        if !expected.contains(&actual) {
+            return Err(Error::HashMismatch {
+                distribution: source_dist.to_string(),
+                expected: expected
+                    .iter()
+                    .map(|hash| format!("`{hash}`"))
+                    .collect::<Vec<_>>()
+                    .join(" or "),
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Download a source distribution from a Git repository.
     async fn download_source_dist_git(&self, url: &Url) -> Result<(Fetch, Option<PathBuf>), Error> {
         debug!("Fetching source distribution from Git: {url}");
@@ -860,10 +969,12 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
 
         let DirectGitUrl { url, subdirectory } = DirectGitUrl::try_from(url).map_err(Error::Git)?;
 
+        let source = GitSource::new(url, git_dir)
+            .with_offline(self.client.connectivity() == Connectivity::Offline);
         let source = if let Some(reporter) = &self.reporter {
-            GitSource::new(url, git_dir).with_reporter(Facade::from(reporter.clone()))
+            source.with_reporter(Facade::from(reporter.clone()))
         } else {
-            GitSource::new(url, git_dir)
+            source
         };
         let fetch = tokio::task::spawn_blocking(move || source.fetch())
             .await?
@@ -931,19 +1042,16 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         debug!("Building: {dist}");
 
         // Guard against build of source distributions when disabled
-        let no_build = match self.build_context.no_build() {
-            NoBuild::All => true,
-            NoBuild::None => false,
-            NoBuild::Packages(packages) => packages.contains(dist.name()),
-        };
-        if no_build {
+        if self.build_context.no_build().no_build_package(dist.name()) {
             return Err(Error::NoBuild);
         }
 
-        // Build the wheel.
-        fs::create_dir_all(&cache_shard)
-            .await
-            .map_err(Error::CacheWrite)?;
+        validate_subdirectory(source_dist, subdirectory)?;
+
+        // Build the wheel into a temporary directory, such that other processes never observe a
+        // partially-written wheel in the cache.
+        let temp_dir =
+            tempfile::tempdir_in(self.build_context.cache().root()).map_err(Error::CacheWrite)?;
         let disk_filename = self
             .build_context
             .setup_build(
@@ -955,10 +1063,21 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             )
             .await
             .map_err(|err| Error::Build(dist.to_string(), err))?
-            .wheel(cache_shard)
+            .wheel(temp_dir.path())
             .await
             .map_err(|err| Error::Build(dist.to_string(), err))?;
 
+        // Move the wheel into the cache.
+        fs::create_dir_all(&cache_shard)
+            .await
+            .map_err(Error::CacheWrite)?;
+        rename_with_retry(
+            temp_dir.path().join(&disk_filename),
+            cache_shard.join(&disk_filename),
+        )
+        .await
+        .map_err(Error::CacheWrite)?;
+
         // Read the metadata from the wheel.
         let filename = WheelFilename::from_str(&disk_filename)?;
         let metadata = read_wheel_metadata(&filename, cache_shard.join(&disk_filename))?;
@@ -985,8 +1104,14 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
     ) -> Result<Option<Metadata23>, Error> {
         debug!("Preparing metadata for: {dist}");
 
+        validate_subdirectory(source_tree, subdirectory)?;
+
         // Attempt to read static metadata from the source distribution.
-        match read_pkg_info(source_tree).await {
+        let package_root = subdirectory.map_or_else(
+            || Cow::Borrowed(source_tree),
+            |subdirectory| Cow::Owned(source_tree.join(subdirectory)),
+        );
+        match read_pkg_info(&package_root).await {
             Ok(metadata) => {
                 debug!("Found static metadata for: {dist}");
 
@@ -1109,6 +1234,24 @@ impl ExtractedSource<'_> {
     }
 }
 
+/// Validate that the subdirectory of a source distribution (e.g., from a `#subdirectory=` URL
+/// fragment) is a directory within its source tree.
+fn validate_subdirectory(source_tree: &Path, subdirectory: Option<&Path>) -> Result<(), Error> {
+    let Some(subdirectory) = subdirectory else {
+        return Ok(());
+    };
+    if !subdirectory
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(Error::InvalidSubdirectory(subdirectory.to_path_buf()));
+    }
+    if !source_tree.join(subdirectory).is_dir() {
+        return Err(Error::MissingSubdirectory(subdirectory.to_path_buf()));
+    }
+    Ok(())
+}
+
 /// Read the [`Metadata23`] from a source distribution's `PKG-INFO` file, if it uses Metadata 2.2
 /// or later _and_ none of the required fields (`Requires-Python`, `Requires-Dist`, and
 /// `Provides-Extra`) are marked as dynamic.
@@ -165,7 +165,22 @@ pub async fn untar<R: tokio::io::AsyncBufRead + Unpin>(
     Ok(untar_in(&mut archive, target.as_ref()).await?)
 }
 
-/// Unzip a `.zip` or `.tar.gz` archive into the target directory, without requiring `Seek`.
+/// Unpack a `.tar.zst` archive into the target directory, without requiring `Seek`.
+///
+/// This is useful for unpacking files as they're being downloaded.
+pub async fn untar_zst<R: tokio::io::AsyncBufRead + Unpin>(
+    reader: R,
+    target: impl AsRef<Path>,
+) -> Result<(), Error> {
+    let decompressed_bytes = async_compression::tokio::bufread::ZstdDecoder::new(reader);
+    let mut archive = tokio_tar::ArchiveBuilder::new(decompressed_bytes)
+        .set_preserve_mtime(false)
+        .build();
+    Ok(untar_in(&mut archive, target.as_ref()).await?)
+}
+
+/// Unzip a `.zip`, `.tar.gz`, or `.tar.zst` archive into the target directory, without requiring
+/// `Seek`.
 pub async fn archive<R: tokio::io::AsyncBufRead + Unpin>(
     reader: R,
     source: impl AsRef<Path>,
@@ -196,5 +211,20 @@ pub async fn archive<R: tokio::io::AsyncBufRead + Unpin>(
         return Ok(());
     }
 
+    // `.tar.zst`
+    if source
+        .as_ref()
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zst"))
+        && source.as_ref().file_stem().is_some_and(|stem| {
+            Path::new(stem)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("tar"))
+        })
+    {
+        untar_zst(reader, target).await?;
+        return Ok(());
+    }
+
     Err(Error::UnsupportedArchive(source.as_ref().to_path_buf()))
 }
@@ -280,6 +280,7 @@ pub fn files(path: impl AsRef<Path>) -> impl Iterator<Item = PathBuf> {
 pub struct LockedFile(fs_err::File);
 
 impl LockedFile {
+    /// Acquire an exclusive lock on the file at `path`, blocking until it's available.
     pub fn acquire(path: impl AsRef<Path>, resource: impl Display) -> Result<Self, std::io::Error> {
         let file = fs_err::File::create(path.as_ref())?;
         match file.file().try_lock_exclusive() {
@@ -296,6 +297,39 @@ impl LockedFile {
             Err(err) => Err(err),
         }
     }
+
+    /// Acquire a shared lock on the file at `path`, blocking until any exclusive lock is released.
+    pub fn acquire_shared(
+        path: impl AsRef<Path>,
+        resource: impl Display,
+    ) -> Result<Self, std::io::Error> {
+        let file = fs_err::File::create(path.as_ref())?;
+        match file.file().try_lock_shared() {
+            Ok(()) => Ok(Self(file)),
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                warn_user!(
+                    "Waiting to acquire shared lock for {} (lockfile: {})",
+                    resource,
+                    path.user_display(),
+                );
+                file.file().lock_shared()?;
+                Ok(Self(file))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Acquire an exclusive lock on the file at `path`, if it's immediately available.
+    ///
+    /// Returns `None` if the lock is held by another process.
+    pub fn try_acquire(path: impl AsRef<Path>) -> Result<Option<Self>, std::io::Error> {
+        let file = fs_err::File::create(path.as_ref())?;
+        match file.file().try_lock_exclusive() {
+            Ok(()) => Ok(Some(Self(file))),
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
 }
 
 impl Drop for LockedFile {
@@ -20,7 +20,7 @@ use crate::FetchStrategy;
 
 /// A file indicates that if present, `git reset` has been done and a repo
 /// checkout is ready to go. See [`GitCheckout::reset`] for why we need this.
-const CHECKOUT_READY_LOCK: &str = ".ok";
+pub(crate) const CHECKOUT_READY_LOCK: &str = ".ok";
 
 /// A reference to commit or commit-ish.
 #[derive(Debug, Clone)]
@@ -164,13 +164,21 @@ impl GitRemote {
         reference: &GitReference,
         locked_rev: Option<git2::Oid>,
         strategy: FetchStrategy,
+        shallow: bool,
         client: &Client,
     ) -> Result<(GitDatabase, git2::Oid)> {
         let locked_ref = locked_rev.map(|oid| GitReference::FullCommit(oid.to_string()));
         let reference = locked_ref.as_ref().unwrap_or(reference);
         if let Some(mut db) = db {
-            fetch(&mut db.repo, self.url.as_str(), reference, strategy, client)
-                .with_context(|| format!("failed to fetch into: {}", into.user_display()))?;
+            fetch(
+                &mut db.repo,
+                self.url.as_str(),
+                reference,
+                strategy,
+                shallow,
+                client,
+            )
+            .with_context(|| format!("failed to fetch into: {}", into.user_display()))?;
 
             let resolved_commit_hash = match locked_rev {
                 Some(rev) => db.contains(rev).then_some(rev),
@@ -189,8 +197,15 @@ impl GitRemote {
         }
         paths::create_dir_all(into)?;
         let mut repo = init(into, true)?;
-        fetch(&mut repo, self.url.as_str(), reference, strategy, client)
-            .with_context(|| format!("failed to clone into: {}", into.user_display()))?;
+        fetch(
+            &mut repo,
+            self.url.as_str(),
+            reference,
+            strategy,
+            shallow,
+            client,
+        )
+        .with_context(|| format!("failed to clone into: {}", into.user_display()))?;
         let rev = match locked_rev {
             Some(rev) => rev,
             None => reference.resolve(&repo)?,
@@ -248,10 +263,20 @@ impl GitDatabase {
         Ok(GitShortID(obj.short_id()?))
     }
 
+    /// Resolves a reference to a revision, using only the objects that the database already has.
+    pub(crate) fn resolve(&self, reference: &GitReference) -> Result<git2::Oid> {
+        reference.resolve(&self.repo)
+    }
+
     /// Checks if the database contains the object of this `oid`.
     pub(crate) fn contains(&self, oid: git2::Oid) -> bool {
         self.repo.revparse_single(&oid.to_string()).is_ok()
     }
+
+    /// Checks if the database was fetched shallowly, and so lacks the history of its revisions.
+    pub(crate) fn is_shallow(&self) -> bool {
+        self.repo.is_shallow()
+    }
 }
 
 impl GitReference {
@@ -404,7 +429,12 @@ impl<'a> GitCheckout<'a> {
     }
 
     /// Checks if the `HEAD` of this checkout points to the expected revision.
+    ///
+    /// A shallow checkout is stale if the database has since fetched the full history.
     fn is_fresh(&self) -> bool {
+        if self.repo.is_shallow() && !self.database.is_shallow() {
+            return false;
+        }
         match self.repo.revparse_single("HEAD") {
             Ok(ref head) if head.id() == self.revision => {
                 // See comments in reset() for why we check this
@@ -525,18 +555,25 @@ impl<'a> GitCheckout<'a> {
                     init(&path, false)?
                 };
 
-                // Fetch data from origin and reset to the head commit
+                // Fetch data from origin and reset to the head commit. Submodules are fetched in
+                // full, since they're only checked out as part of building the parent repository.
                 debug!("Updating Git submodule: {}", child_remote_url);
                 let reference = GitReference::FullCommit(head.to_string());
-                fetch(&mut repo, &child_remote_url, &reference, strategy, client).with_context(
-                    || {
-                        format!(
-                            "failed to fetch submodule `{}` from {}",
-                            child.name().unwrap_or(""),
-                            child_remote_url
-                        )
-                    },
-                )?;
+                fetch(
+                    &mut repo,
+                    &child_remote_url,
+                    &reference,
+                    strategy,
+                    false,
+                    client,
+                )
+                .with_context(|| {
+                    format!(
+                        "failed to fetch submodule `{}` from {}",
+                        child.name().unwrap_or(""),
+                        child_remote_url
+                    )
+                })?;
 
                 let obj = repo.find_object(head, None)?;
                 reset(&repo, &obj)?;
@@ -904,15 +941,21 @@ pub(crate) fn with_fetch_options(
 /// * Dispatches `git fetch` using libgit2 or git CLI.
 ///
 /// The `remote_url` argument is the git remote URL where we want to fetch from.
+///
+/// If `shallow` is set, the history of the fetched revision is omitted where possible. Otherwise,
+/// the full history is fetched, including for a repository that was previously fetched shallowly.
 pub(crate) fn fetch(
     repo: &mut git2::Repository,
     remote_url: &str,
     reference: &GitReference,
     strategy: FetchStrategy,
+    shallow: bool,
     client: &Client,
 ) -> Result<()> {
     let oid_to_fetch = match github_fast_path(repo, remote_url, reference, client) {
-        Ok(FastPathRev::UpToDate) => return Ok(()),
+        // A shallow repository isn't up-to-date if its full history is required.
+        Ok(FastPathRev::UpToDate) if shallow || !repo.is_shallow() => return Ok(()),
+        Ok(FastPathRev::UpToDate) => None,
         Ok(FastPathRev::NeedsFetch(rev)) => Some(rev),
         Ok(FastPathRev::Indeterminate) => None,
         Err(e) => {
@@ -930,6 +973,9 @@ pub(crate) fn fetch(
     let mut refspecs = Vec::new();
     let mut tags = false;
     let mut refspec_strategy = RefspecStrategy::All;
+    // Whether to omit the history of the fetched references (i.e., perform a shallow fetch), which
+    // requires that they identify the commits we need.
+    let mut shallow = shallow;
     // The `+` symbol on the refspec means to allow a forced (fast-forward)
     // update which is needed if there is ever a force push that requires a
     // fast-forward.
@@ -986,6 +1032,7 @@ pub(crate) fn fetch(
                 refspecs.push(String::from("+refs/heads/*:refs/remotes/origin/*"));
                 refspecs.push(String::from("+HEAD:refs/remotes/origin/HEAD"));
                 tags = true;
+                shallow = false;
             }
         }
     }
@@ -994,14 +1041,16 @@ pub(crate) fn fetch(
     match strategy {
         FetchStrategy::Cli => {
             let result = match refspec_strategy {
-                RefspecStrategy::All => fetch_with_cli(repo, remote_url, refspecs.as_slice(), tags),
+                RefspecStrategy::All => {
+                    fetch_with_cli(repo, remote_url, refspecs.as_slice(), tags, shallow)
+                }
                 RefspecStrategy::First => {
                     // Try each refspec
                     let mut errors = refspecs
                         .iter()
                         .map_while(|refspec| {
                             let fetch_result =
-                                fetch_with_cli(repo, remote_url, &[refspec.clone()], tags);
+                                fetch_with_cli(repo, remote_url, &[refspec.clone()], tags, shallow);
 
                             // Stop after the first success and log failures
                             match fetch_result {
@@ -1048,6 +1097,14 @@ pub(crate) fn fetch(
                 if tags {
                     opts.download_tags(git2::AutotagOption::All);
                 }
+                // As with the CLI, omit the history of the fetched commits where possible, and
+                // otherwise fetch the full history (`i32::MAX` is libgit2's sentinel for
+                // unshallowing a repository).
+                if shallow {
+                    opts.depth(1);
+                } else if repo.is_shallow() {
+                    opts.depth(i32::MAX);
+                }
 
                 // The `fetch` operation here may fail spuriously due to a corrupt
                 // repository. It could also fail, however, for a whole slew of other
@@ -1102,18 +1159,28 @@ pub(crate) fn fetch(
 /// flavors of authentication possible while also still giving us all the
 /// speed and portability of using `libgit2`.
 ///
+/// If `shallow` is set, only the commits that the refspecs point to are fetched, omitting their
+/// history. Otherwise, the full history is fetched, including that of any commits that were
+/// previously fetched shallowly (e.g., to find a truncated revision in the repository's history).
+///
 /// [1]: https://doc.rust-lang.org/nightly/cargo/reference/config.html#netgit-fetch-with-cli
 fn fetch_with_cli(
     repo: &mut git2::Repository,
     url: &str,
     refspecs: &[String],
     tags: bool,
+    shallow: bool,
 ) -> Result<()> {
     let mut cmd = ProcessBuilder::new("git");
     cmd.arg("fetch");
     if tags {
         cmd.arg("--tags");
     }
+    if shallow {
+        cmd.arg("--depth=1");
+    } else if repo.is_shallow() {
+        cmd.arg("--unshallow");
+    }
     cmd.arg("--force") // handle force pushes
         .arg("--update-head-ok") // see discussion in #2078
         .arg(url)
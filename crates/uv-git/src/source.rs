@@ -10,7 +10,7 @@ use url::Url;
 
 use cache_key::{digest, RepositoryUrl};
 
-use crate::git::GitRemote;
+use crate::git::{GitRemote, CHECKOUT_READY_LOCK};
 use crate::{FetchStrategy, GitSha, GitUrl};
 
 /// A remote Git source that can be checked out locally.
@@ -23,6 +23,10 @@ pub struct GitSource {
     strategy: FetchStrategy,
     /// The path to the Git source database.
     cache: PathBuf,
+    /// Whether to resolve the source from the Git database alone, without accessing the network.
+    offline: bool,
+    /// Whether to fetch the requested revision alone, omitting its history.
+    shallow: bool,
     /// The reporter to use for this source.
     reporter: Option<Box<dyn Reporter>>,
 }
@@ -35,6 +39,8 @@ impl GitSource {
             client: Client::new(),
             strategy: FetchStrategy::Cli,
             cache: cache.into(),
+            offline: false,
+            shallow: false,
             reporter: None,
         }
     }
@@ -48,9 +54,58 @@ impl GitSource {
         }
     }
 
+    /// Resolve the source from the existing Git database alone, failing if the requested revision
+    /// isn't available locally.
+    #[must_use]
+    pub fn with_offline(self, offline: bool) -> Self {
+        Self { offline, ..self }
+    }
+
+    /// Fetch the requested revision alone, omitting its history (i.e., perform a shallow fetch).
+    ///
+    /// A shallow checkout lacks the tags and history that build backends rely on to determine the
+    /// version of the package (e.g., `setuptools-scm` or `hatch-vcs`), so this should only be used
+    /// when the checkout won't be built (e.g., to resolve a reference to a commit). If a shallow
+    /// database is later fetched without this option, its full history is fetched.
+    #[must_use]
+    pub fn with_shallow(self, shallow: bool) -> Self {
+        Self { shallow, ..self }
+    }
+
     /// Fetch the underlying Git repository at the given revision.
     #[instrument(skip(self))]
     pub fn fetch(self) -> Result<Fetch> {
+        self.fetch_to(None)
+    }
+
+    /// Fetch the underlying Git repository at the given revision, and check it out to the given
+    /// directory rather than to the cache (e.g., for an editable install).
+    ///
+    /// Unlike the checkouts in the cache, the checkout's `origin` remote points to the repository
+    /// itself, such that changes can be pulled and pushed as usual. The directory must not exist.
+    #[instrument(skip(self))]
+    pub fn fetch_into(self, path: &Path) -> Result<Fetch> {
+        if path.exists() {
+            return Err(anyhow::anyhow!(
+                "Checkout directory already exists: {}",
+                path.display()
+            ));
+        }
+        let repository = self.git.repository.clone();
+        let fetch = self.fetch_to(Some(path.to_path_buf()))?;
+
+        // Point the checkout at the repository, rather than at the database from which it was
+        // cloned, and remove the marker that indicates a complete checkout within the cache.
+        let repo = git2::Repository::open(path)?;
+        repo.remote_set_url("origin", repository.as_str())?;
+        fs_err::remove_file(path.join(CHECKOUT_READY_LOCK))?;
+
+        Ok(fetch)
+    }
+
+    /// Fetch the underlying Git repository at the given revision, and check it out to the given
+    /// directory, or to the cache if no directory is provided.
+    fn fetch_to(self, checkout_path: Option<PathBuf>) -> Result<Fetch> {
         // The path to the repo, within the Git database.
         let ident = digest(&RepositoryUrl::new(&self.git.repository));
         let db_path = self.cache.join("db").join(&ident);
@@ -58,8 +113,25 @@ impl GitSource {
         let remote = GitRemote::new(&self.git.repository);
         let (db, actual_rev, task) = match (self.git.precise, remote.db_at(&db_path).ok()) {
             // If we have a locked revision, and we have a preexisting database
-            // which has that revision, then no update needs to happen.
-            (Some(rev), Some(db)) if db.contains(rev.into()) => (db, rev, None),
+            // which has that revision (along with its history, unless a shallow
+            // fetch was requested), then no update needs to happen.
+            (Some(rev), Some(db))
+                if db.contains(rev.into()) && (self.shallow || !db.is_shallow()) =>
+            {
+                (db, rev, None)
+            }
+
+            // If we're offline, use the locked revision as-is, even if its history wasn't
+            // fetched.
+            (Some(rev), Some(db)) if self.offline && db.contains(rev.into()) => (db, rev, None),
+
+            // If we're offline, resolve the reference against the existing database, without
+            // fetching any updates.
+            (None, Some(db)) if self.offline => match db.resolve(&self.git.reference) {
+                Ok(rev) => (db, GitSha::from(rev), None),
+                Err(_) => return Err(self.offline_error()),
+            },
+            (_, _) if self.offline => return Err(self.offline_error()),
 
             // ... otherwise we use this state to update the git database. Note
             // that we still check for being offline here, for example in the
@@ -79,6 +151,7 @@ impl GitSource {
                     &self.git.reference,
                     locked_rev.map(git2::Oid::from),
                     self.strategy,
+                    self.shallow,
                     &self.client,
                 )?;
 
@@ -93,11 +166,12 @@ impl GitSource {
         // Check out `actual_rev` from the database to a scoped location on the
         // filesystem. This will use hard links and such to ideally make the
         // checkout operation here pretty fast.
-        let checkout_path = self
-            .cache
-            .join("checkouts")
-            .join(&ident)
-            .join(short_id.as_str());
+        let checkout_path = checkout_path.unwrap_or_else(|| {
+            self.cache
+                .join("checkouts")
+                .join(&ident)
+                .join(short_id.as_str())
+        });
         db.copy_to(
             actual_rev.into(),
             &checkout_path,
@@ -117,6 +191,15 @@ impl GitSource {
             path: checkout_path,
         })
     }
+
+    /// Return the error to raise when the requested revision isn't available offline.
+    fn offline_error(&self) -> anyhow::Error {
+        anyhow::anyhow!(
+            "Network connectivity is disabled, but the requested Git reference wasn't found in the cache for: `{}@{}`",
+            self.git.repository,
+            self.git.reference.as_str()
+        )
+    }
 }
 
 pub struct Fetch {
@@ -56,7 +56,9 @@ pub enum CompileError {
 /// All compilation errors are muted (like pip). There is a 60s timeout for each file to handle
 /// a broken `python`.
 ///
-/// We only compile all files, but we don't update the RECORD, relying on PEP 491:
+/// We only compile the files here; the caller is responsible for adding the compiled files to the
+/// RECORD of the owning distributions (see [`install_wheel_rs::record_bytecode`]). Either way,
+/// per PEP 491:
 /// > Uninstallers should be smart enough to remove .pyc even if it is not mentioned in RECORD.
 ///
 /// We've confirmed that both `uv` and `pip` (as of 24.0.0) remove the `__pycache__` directory.
@@ -1,18 +1,22 @@
 use std::cmp::Reverse;
 use std::path::Path;
+use std::pin::pin;
 use std::sync::Arc;
 
-use futures::{FutureExt, Stream, StreamExt, TryFutureExt, TryStreamExt};
+use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
 use tempfile::TempDir;
 use tokio::task::JoinError;
 use tracing::instrument;
 use url::Url;
 
-use distribution_types::{CachedDist, Dist, Identifier, LocalEditable, RemoteSource, SourceDist};
+use distribution_types::{
+    CachedDist, Dist, Identifier, LocalEditable, Name, RemoteSource, SourceDist,
+};
 use platform_tags::Tags;
 use uv_cache::Cache;
 use uv_client::RegistryClient;
 use uv_distribution::{DistributionDatabase, LocalWheel, Unzip};
+use uv_normalize::PackageName;
 use uv_traits::{BuildContext, InFlight};
 
 use crate::editable::BuiltEditable;
@@ -32,6 +36,8 @@ pub enum Error {
     CacheWrite(#[source] std::io::Error),
     #[error("Unzip failed in another thread: {0}")]
     Thread(String),
+    #[error("Network connectivity is disabled, but the following distributions weren't found in the cache: {}", .0.iter().map(|dist| format!("`{dist}`")).collect::<Vec<_>>().join(", "))]
+    Offline(Vec<Dist>),
 }
 
 /// Download, build, and unzip a set of distributions.
@@ -39,6 +45,8 @@ pub struct Downloader<'a, Context: BuildContext + Send + Sync> {
     database: DistributionDatabase<'a, Context>,
     cache: &'a Cache,
     reporter: Option<Arc<dyn Reporter>>,
+    /// The maximum number of concurrent downloads.
+    concurrent_downloads: usize,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
@@ -52,6 +60,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             database: DistributionDatabase::new(cache, tags, client, build_context),
             reporter: None,
             cache,
+            concurrent_downloads: build_context.concurrency().downloads,
         }
     }
 
@@ -63,6 +72,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             reporter: Some(reporter.clone()),
             database: self.database.with_reporter(Facade::from(reporter.clone())),
             cache: self.cache,
+            concurrent_downloads: self.concurrent_downloads,
         }
     }
 
@@ -80,26 +90,51 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 }
                 Ok::<CachedDist, Error>(wheel)
             })
-            // TODO(charlie): The number of concurrent fetches, such that we limit the number of
-            // concurrent builds to the number of cores, while allowing more concurrent downloads.
-            .buffer_unordered(50)
+            // The number of concurrent builds is limited separately, by the build context.
+            .buffer_unordered(self.concurrent_downloads)
     }
 
     /// Download, build, and unzip a set of downloaded wheels.
-    #[instrument(skip_all, fields(total = distributions.len()))]
     pub async fn download(
+        &self,
+        distributions: Vec<Dist>,
+        in_flight: &InFlight,
+    ) -> Result<Vec<CachedDist>, Error> {
+        self.download_with(distributions, in_flight, |_| {}).await
+    }
+
+    /// Download, build, and unzip a set of downloaded wheels, invoking `on_wheel` with each wheel
+    /// as soon as it's available (e.g., to install it while the remaining wheels are downloaded).
+    #[instrument(skip_all, fields(total = distributions.len()))]
+    pub async fn download_with(
         &self,
         mut distributions: Vec<Dist>,
         in_flight: &InFlight,
+        mut on_wheel: impl FnMut(&CachedDist),
     ) -> Result<Vec<CachedDist>, Error> {
         // Sort the distributions by size.
         distributions
             .sort_unstable_by_key(|distribution| Reverse(distribution.size().unwrap_or(u64::MAX)));
 
-        let wheels = self
-            .download_stream(distributions, in_flight)
-            .try_collect()
-            .await?;
+        // When offline, report every distribution that's missing from the cache, rather than just
+        // the first.
+        let mut wheels = Vec::with_capacity(distributions.len());
+        let mut missing = Vec::new();
+        let mut results = pin!(self.download_stream(distributions, in_flight));
+        while let Some(result) = results.next().await {
+            match result {
+                Ok(wheel) => {
+                    on_wheel(&wheel);
+                    wheels.push(wheel);
+                }
+                Err(Error::Fetch(dist, err)) if err.is_offline() => missing.push(dist),
+                Err(err) => return Err(err),
+            }
+        }
+        if !missing.is_empty() {
+            missing.sort_unstable_by(|a, b| a.name().cmp(b.name()));
+            return Err(Error::Offline(missing));
+        }
 
         if let Some(reporter) = self.reporter.as_ref() {
             reporter.on_complete();
@@ -249,6 +284,15 @@ pub trait Reporter: Send + Sync {
 
     /// Callback to invoke when a repository checkout completes.
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize);
+    /// Callback to invoke when a download is kicked off.
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize;
+
+    /// Callback to invoke when a download makes progress, with the total number of bytes
+    /// downloaded so far.
+    fn on_download_progress(&self, id: usize, bytes: u64);
+
+    /// Callback to invoke when a download is complete.
+    fn on_download_complete(&self, name: &PackageName, id: usize);
 }
 
 /// A facade for converting from [`Reporter`] to [`uv_git::Reporter`].
@@ -278,4 +322,15 @@ impl uv_distribution::Reporter for Facade {
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize) {
         self.reporter.on_checkout_complete(url, rev, index);
     }
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize {
+        self.reporter.on_download_start(name, size)
+    }
+
+    fn on_download_progress(&self, id: usize, bytes: u64) {
+        self.reporter.on_download_progress(id, bytes);
+    }
+
+    fn on_download_complete(&self, name: &PackageName, id: usize) {
+        self.reporter.on_download_complete(name, id);
+    }
 }
@@ -1,14 +1,22 @@
+use std::pin::pin;
+use std::sync::Arc;
+
 use anyhow::{Context, Error, Result};
+use futures::channel::oneshot;
+use futures::stream::FuturesUnordered;
+use futures::{Stream, StreamExt};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use tracing::instrument;
 
 use distribution_types::CachedDist;
+use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::Layout;
 use uv_interpreter::PythonEnvironment;
 
 pub struct Installer<'a> {
     venv: &'a PythonEnvironment,
-    link_mode: install_wheel_rs::linker::LinkMode,
-    reporter: Option<Box<dyn Reporter>>,
+    link_mode: Option<LinkMode>,
+    reporter: Option<Arc<dyn Reporter>>,
     installer_name: Option<String>,
 }
 
@@ -17,15 +25,17 @@ impl<'a> Installer<'a> {
     pub fn new(venv: &'a PythonEnvironment) -> Self {
         Self {
             venv,
-            link_mode: install_wheel_rs::linker::LinkMode::default(),
+            link_mode: None,
             reporter: None,
             installer_name: Some("uv".to_string()),
         }
     }
 
     /// Set the [`LinkMode`][`install_wheel_rs::linker::LinkMode`] to use for this installer.
+    ///
+    /// If `None`, the link mode is detected based on the capabilities of the filesystem.
     #[must_use]
-    pub fn with_link_mode(self, link_mode: install_wheel_rs::linker::LinkMode) -> Self {
+    pub fn with_link_mode(self, link_mode: Option<LinkMode>) -> Self {
         Self { link_mode, ..self }
     }
 
@@ -33,7 +43,7 @@ impl<'a> Installer<'a> {
     #[must_use]
     pub fn with_reporter(self, reporter: impl Reporter + 'static) -> Self {
         Self {
-            reporter: Some(Box::new(reporter)),
+            reporter: Some(Arc::new(reporter)),
             ..self
         }
     }
@@ -47,26 +57,26 @@ impl<'a> Installer<'a> {
         }
     }
 
+    /// Return the [`Layout`] with which to install wheels into the environment.
+    fn layout(&self) -> Layout {
+        Layout {
+            relocatable: self.venv.relocatable(),
+            ..self.venv.interpreter().layout()
+        }
+    }
+
     /// Install a set of wheels into a Python virtual environment.
     #[instrument(skip_all, fields(num_wheels = %wheels.len()))]
     pub fn install(self, wheels: &[CachedDist]) -> Result<()> {
-        let layout = self.venv.interpreter().layout();
+        let layout = self.layout();
+        let link_mode = self.link_mode.unwrap_or_else(|| {
+            wheels
+                .first()
+                .map_or_else(LinkMode::default, |wheel| detect_link_mode(wheel, &layout))
+        });
         tokio::task::block_in_place(|| {
             wheels.par_iter().try_for_each(|wheel| {
-                install_wheel_rs::linker::install_wheel(
-                    &layout,
-                    wheel.path(),
-                    wheel.filename(),
-                    wheel
-                        .direct_url()?
-                        .as_ref()
-                        .map(pypi_types::DirectUrl::try_from)
-                        .transpose()?
-                        .as_ref(),
-                    self.installer_name.as_deref(),
-                    self.link_mode,
-                )
-                .with_context(|| format!("Failed to install: {} ({wheel})", wheel.filename()))?;
+                install_wheel(&layout, wheel, self.installer_name.as_deref(), link_mode)?;
 
                 if let Some(reporter) = self.reporter.as_ref() {
                     reporter.on_install_progress(wheel);
@@ -76,6 +86,90 @@ impl<'a> Installer<'a> {
             })
         })
     }
+
+    /// Install wheels into a Python virtual environment as they're produced by the given stream
+    /// (e.g., as they're downloaded), such that installation overlaps with the production of the
+    /// remaining wheels.
+    ///
+    /// Returns once the stream is exhausted and every wheel has been installed.
+    #[instrument(skip_all)]
+    pub async fn install_stream(self, wheels: impl Stream<Item = CachedDist>) -> Result<()> {
+        let layout = Arc::new(self.layout());
+        let installer_name: Option<Arc<str>> = self.installer_name.map(Arc::from);
+        let mut link_mode = self.link_mode;
+
+        let mut installed = false;
+        let mut tasks = FuturesUnordered::new();
+        let mut wheels = pin!(wheels);
+        while let Some(wheel) = wheels.next().await {
+            installed = true;
+            let link_mode = *link_mode.get_or_insert_with(|| detect_link_mode(&wheel, &layout));
+            let layout = layout.clone();
+            let installer_name = installer_name.clone();
+            let reporter = self.reporter.clone();
+
+            // Install on the Rayon thread pool, like `install`, rather than blocking the runtime.
+            let (sender, receiver) = oneshot::channel();
+            rayon::spawn(move || {
+                let result = install_wheel(&layout, &wheel, installer_name.as_deref(), link_mode);
+                if result.is_ok() {
+                    if let Some(reporter) = reporter.as_ref() {
+                        reporter.on_install_progress(&wheel);
+                    }
+                }
+                // If the receiver was dropped, the installation was abandoned.
+                sender.send(result).ok();
+            });
+            tasks.push(receiver);
+        }
+
+        while let Some(result) = tasks.next().await {
+            result.context("Installation task was cancelled")??;
+        }
+
+        if installed {
+            if let Some(reporter) = self.reporter.as_ref() {
+                reporter.on_install_complete();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Detect the link mode based on the capabilities of the filesystem, i.e., whether the wheel can
+/// be linked from the cache into the environment.
+fn detect_link_mode(wheel: &CachedDist, layout: &Layout) -> LinkMode {
+    wheel
+        .path()
+        .parent()
+        .map_or_else(LinkMode::default, |cache| {
+            LinkMode::detect(cache, &layout.scheme.purelib)
+        })
+}
+
+/// Install a single wheel into the environment described by the [`Layout`].
+fn install_wheel(
+    layout: &Layout,
+    wheel: &CachedDist,
+    installer_name: Option<&str>,
+    link_mode: LinkMode,
+) -> Result<()> {
+    install_wheel_rs::linker::install_wheel(
+        layout,
+        wheel.path(),
+        wheel.filename(),
+        wheel
+            .direct_url()?
+            .as_ref()
+            .map(pypi_types::DirectUrl::try_from)
+            .transpose()?
+            .as_ref(),
+        installer_name,
+        link_mode,
+    )
+    .with_context(|| format!("Failed to install: {} ({wheel})", wheel.filename()))?;
+    Ok(())
 }
 
 pub trait Reporter: Send + Sync {
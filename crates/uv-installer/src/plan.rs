@@ -17,7 +17,7 @@ use uv_distribution::{BuiltWheelIndex, RegistryWheelIndex};
 use uv_fs::Simplified;
 use uv_interpreter::PythonEnvironment;
 use uv_normalize::PackageName;
-use uv_traits::NoBinary;
+use uv_traits::{NoBinary, RequiredHashes};
 
 use crate::{ResolvedEditable, SitePackages};
 
@@ -26,6 +26,9 @@ use crate::{ResolvedEditable, SitePackages};
 pub struct Planner<'a> {
     requirements: &'a [Requirement],
     editable_requirements: &'a [ResolvedEditable],
+    keep: &'a [PackageName],
+    exact: bool,
+    required_hashes: Option<&'a RequiredHashes>,
 }
 
 impl<'a> Planner<'a> {
@@ -35,6 +38,9 @@ impl<'a> Planner<'a> {
         Self {
             requirements,
             editable_requirements: &[],
+            keep: &[],
+            exact: false,
+            required_hashes: None,
         }
     }
 
@@ -47,6 +53,32 @@ impl<'a> Planner<'a> {
         }
     }
 
+    /// Set the packages that should never be removed, even if they're not required.
+    #[must_use]
+    pub fn with_keep(self, keep: &'a [PackageName]) -> Self {
+        Self { keep, ..self }
+    }
+
+    /// Remove any packages that aren't required, including the seed packages (`pip`,
+    /// `setuptools`, `wheel`, and `uv`) that are otherwise preserved.
+    #[must_use]
+    pub fn with_exact(self, exact: bool) -> Self {
+        Self { exact, ..self }
+    }
+
+    /// Set the hashes that the distributions must match.
+    ///
+    /// Cached distributions are never linked directly for packages with required hashes; instead,
+    /// they're treated as remote, such that they're verified against the hash that was recorded
+    /// when they were downloaded.
+    #[must_use]
+    pub fn with_required_hashes(self, required_hashes: &'a RequiredHashes) -> Self {
+        Self {
+            required_hashes: Some(required_hashes),
+            ..self
+        }
+    }
+
     /// Partition a set of requirements into those that should be linked from the cache, those that
     /// need to be downloaded, and those that should be removed.
     ///
@@ -159,11 +191,7 @@ impl<'a> Planner<'a> {
             };
 
             // Check if installation of a binary version of the package should be allowed.
-            let no_binary = match no_binary {
-                NoBinary::None => false,
-                NoBinary::All => true,
-                NoBinary::Packages(packages) => packages.contains(&requirement.name),
-            };
+            let no_binary = no_binary.no_binary_package(&requirement.name);
 
             if reinstall {
                 let installed = site_packages.remove_packages(&requirement.name);
@@ -222,6 +250,15 @@ impl<'a> Planner<'a> {
                 continue;
             }
 
+            if self
+                .required_hashes
+                .is_some_and(|required_hashes| required_hashes.get(&requirement.name).is_some())
+            {
+                debug!("Must verify requirement against the required hashes: {requirement}");
+                remote.push(requirement.clone());
+                continue;
+            }
+
             // Identify any locally-available distributions that satisfy the requirement.
             match requirement.version_or_url.as_ref() {
                 None => {
@@ -389,12 +426,35 @@ impl<'a> Planner<'a> {
             remote.push(requirement.clone());
         }
 
+        // Distributions in read-only `site-packages` directories (e.g., the system `site-packages`
+        // when installing into the user site) are shadowed by the new installation, rather than
+        // removed.
+        reinstalls.retain(|dist_info| {
+            if venv.is_base_site_packages(dist_info.path()) {
+                debug!("Shadowing read-only package: {dist_info}");
+                false
+            } else {
+                true
+            }
+        });
+
         // Remove any unnecessary packages.
         if site_packages.any() {
-            // If uv created the virtual environment, then remove all packages, regardless of
-            // whether they're considered "seed" packages.
-            let seed_packages = !venv.cfg().is_ok_and(|cfg| cfg.is_uv());
+            // If uv created the virtual environment without seeding it, then remove all packages,
+            // regardless of whether they're considered "seed" packages.
+            let seed_packages =
+                !self.exact && !venv.cfg().is_ok_and(|cfg| cfg.is_uv() && !cfg.is_seeded());
             for dist_info in site_packages {
+                if venv.is_base_site_packages(dist_info.path()) {
+                    debug!("Preserving read-only package: {dist_info}");
+                    continue;
+                }
+
+                if self.keep.contains(dist_info.name()) {
+                    debug!("Preserving kept package: {dist_info}");
+                    continue;
+                }
+
                 if seed_packages
                     && matches!(
                         dist_info.name().as_ref(),
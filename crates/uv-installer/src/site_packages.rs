@@ -42,17 +42,14 @@ impl<'a> SitePackages<'a> {
         let mut by_name = FxHashMap::default();
         let mut by_url = FxHashMap::default();
 
-        for site_packages in venv.site_packages() {
+        // Index the read-only `site-packages` directories too (e.g., the system `site-packages` when
+        // installing into the user site), such that requirements they satisfy aren't reinstalled.
+        for site_packages in venv.site_packages().chain(venv.base_site_packages()) {
             // Read the site-packages directory.
             let site_packages = match fs::read_dir(site_packages) {
                 Ok(site_packages) => site_packages,
                 Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-                    return Ok(Self {
-                        venv,
-                        distributions,
-                        by_name,
-                        by_url,
-                    });
+                    continue;
                 }
                 Err(err) => return Err(err).context("Failed to read site-packages directory"),
             };
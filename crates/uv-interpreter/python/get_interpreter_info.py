@@ -407,6 +407,44 @@ def get_scheme():
         return get_distutils_scheme()
 
 
+def get_user_scheme():
+    """Return the user Scheme for the current interpreter (i.e., the scheme used by
+    `pip install --user`), or `None` if user site-packages are unavailable.
+
+    The paths returned should be absolute.
+
+    This is based on pip's user scheme discovery logic:
+        https://github.com/pypa/pip/blob/ae5fff36b0aad6e5e0037884927eaa29163c0611/src/pip/_internal/locations/_sysconfig.py#L124
+    """
+    # User site-packages are not visible from within a virtual environment. Note that we can't
+    # rely on `site.ENABLE_USER_SITE`, since the interpreter is queried in isolated mode.
+    if running_under_virtualenv() or os.environ.get("PYTHONNOUSERSITE"):
+        return None
+
+    scheme_names = sysconfig.get_scheme_names()
+
+    preferred_scheme_api = getattr(sysconfig, "get_preferred_scheme", None)
+    if preferred_scheme_api:
+        scheme_name = preferred_scheme_api("user")
+    elif sys.platform == "darwin" and sysconfig.get_config_var("PYTHONFRAMEWORK"):
+        scheme_name = "osx_framework_user"
+    else:
+        scheme_name = f"{os.name}_user"
+
+    if scheme_name not in scheme_names:
+        return None
+
+    paths = sysconfig.get_paths(scheme=scheme_name)
+
+    return {
+        "platlib": paths["platlib"],
+        "purelib": paths["purelib"],
+        "include": paths["include"],
+        "scripts": paths["scripts"],
+        "data": paths["data"],
+    }
+
+
 def get_operating_system_and_architecture():
     """Determine the Python interpreter architecture and operating system.
 
@@ -522,8 +560,12 @@ def main() -> None:
         "sys_executable": sys.executable,
         "stdlib": sysconfig.get_path("stdlib"),
         "scheme": get_scheme(),
+        "user_scheme": get_user_scheme(),
         "virtualenv": get_virtualenv(),
         "platform": get_operating_system_and_architecture(),
+        # Free-threaded builds (e.g., `python3.13t`) set `Py_GIL_DISABLED`.
+        # See: https://peps.python.org/pep-0703/#build-configuration-changes
+        "gil_disabled": bool(sysconfig.get_config_var("Py_GIL_DISABLED")),
     }
     print(json.dumps(interpreter_info))
 
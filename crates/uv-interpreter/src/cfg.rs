@@ -10,6 +10,10 @@ pub struct PyVenvConfiguration {
     pub(crate) virtualenv: bool,
     /// The version of the `uv` package used to create the virtual environment, if any.
     pub(crate) uv: bool,
+    /// Whether the virtual environment is relocatable.
+    pub(crate) relocatable: bool,
+    /// Whether the virtual environment was seeded with `pip`, `setuptools`, and `wheel`.
+    pub(crate) seed: bool,
 }
 
 impl PyVenvConfiguration {
@@ -17,13 +21,15 @@ impl PyVenvConfiguration {
     pub fn parse(cfg: impl AsRef<Path>) -> Result<Self, Error> {
         let mut virtualenv = false;
         let mut uv = false;
+        let mut relocatable = false;
+        let mut seed = false;
 
         // Per https://snarky.ca/how-virtual-environments-work/, the `pyvenv.cfg` file is not a
         // valid INI file, and is instead expected to be parsed by partitioning each line on the
         // first equals sign.
         let content = fs::read_to_string(&cfg)?;
         for line in content.lines() {
-            let Some((key, _value)) = line.split_once('=') else {
+            let Some((key, value)) = line.split_once('=') else {
                 continue;
             };
             match key.trim() {
@@ -33,11 +39,22 @@ impl PyVenvConfiguration {
                 "uv" => {
                     uv = true;
                 }
+                "relocatable" => {
+                    relocatable = value.trim().eq_ignore_ascii_case("true");
+                }
+                "seed" => {
+                    seed = value.trim().eq_ignore_ascii_case("true");
+                }
                 _ => {}
             }
         }
 
-        Ok(Self { virtualenv, uv })
+        Ok(Self {
+            virtualenv,
+            uv,
+            relocatable,
+            seed,
+        })
     }
 
     /// Returns true if the virtual environment was created with the `virtualenv` package.
@@ -49,6 +66,16 @@ impl PyVenvConfiguration {
     pub fn is_uv(&self) -> bool {
         self.uv
     }
+
+    /// Returns true if the virtual environment is relocatable.
+    pub fn is_relocatable(&self) -> bool {
+        self.relocatable
+    }
+
+    /// Returns true if the virtual environment was seeded with `pip`, `setuptools`, and `wheel`.
+    pub fn is_seeded(&self) -> bool {
+        self.seed
+    }
 }
 
 #[derive(Debug, Error)]
@@ -1,14 +1,20 @@
 use std::borrow::Cow;
 use std::env;
 use std::ffi::{OsStr, OsString};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
+use once_cell::sync::Lazy;
+use regex::Regex;
 use tracing::{debug, instrument};
 
+use pep440_rs::VersionSpecifiers;
 use uv_cache::Cache;
 use uv_fs::normalize_path;
 
 use crate::interpreter::InterpreterInfoError;
+use crate::managed::{InstalledToolchains, Toolchain};
+use crate::pyenv::{PyenvRoot, PyenvVersion};
 use crate::python_environment::{detect_python_executable, detect_virtual_env};
 use crate::{Error, Interpreter, PythonVersion};
 
@@ -16,7 +22,10 @@ use crate::{Error, Interpreter, PythonVersion};
 ///
 /// Supported formats:
 /// * `-p 3.10` searches for an installed Python 3.10 (`py --list-paths` on Windows, `python3.10` on
-///   Linux/Mac). Specifying a patch version is not supported.
+///   Linux/Mac).
+/// * `-p 3.10.4` searches for an installed Python with the exact patch version.
+/// * `-p '>=3.10,<3.12'` searches for an installed Python that satisfies the version specifiers,
+///   preferring the newest versioned binary (e.g., `python3.11`) in each `PATH` entry.
 /// * `-p python3.10` or `-p python.exe` looks for a binary in `PATH`.
 /// * `-p /home/ferris/.local/bin/python3.10` uses this exact Python.
 ///
@@ -27,22 +36,9 @@ use crate::{Error, Interpreter, PythonVersion};
 #[instrument(skip_all, fields(%request))]
 pub fn find_requested_python(request: &str, cache: &Cache) -> Result<Option<Interpreter>, Error> {
     debug!("Starting interpreter discovery for Python @ `{request}`");
-    let versions = request
-        .splitn(3, '.')
-        .map(str::parse::<u8>)
-        .collect::<Result<Vec<_>, _>>();
-    if let Ok(versions) = versions {
-        // `-p 3.10` or `-p 3.10.1`
-        let selector = match versions.as_slice() {
-            [requested_major] => PythonVersionSelector::Major(*requested_major),
-            [major, minor] => PythonVersionSelector::MajorMinor(*major, *minor),
-            [major, minor, requested_patch] => {
-                PythonVersionSelector::MajorMinorPatch(*major, *minor, *requested_patch)
-            }
-            // SAFETY: Guaranteed by the Ok(versions) guard
-            _ => unreachable!(),
-        };
-        find_python(selector, cache)
+    if let Some(selector) = PythonVersionSelector::from_request(request)? {
+        // `-p 3.10`, `-p 3.10.1`, or `-p '>=3.10,<3.12'`
+        find_python(&selector, cache)
     } else if !request.contains(std::path::MAIN_SEPARATOR) {
         // `-p python3.10`; Generally not used on windows because all Python are `python.exe`.
         let Some(executable) = find_executable(request)? else {
@@ -57,6 +53,18 @@ pub fn find_requested_python(request: &str, cache: &Cache) -> Result<Option<Inte
     }
 }
 
+/// Returns `true` if the Python request is a version request (e.g., `-p 3.10` or
+/// `-p '>=3.10'`), rather than an executable name or path.
+pub(crate) fn is_version_request(request: &str) -> Result<bool, Error> {
+    Ok(PythonVersionSelector::from_request(request)?.is_some())
+}
+
+/// Returns `true` if the interpreter satisfies the given Python version request.
+pub(crate) fn satisfies_request(request: &str, interpreter: &Interpreter) -> Result<bool, Error> {
+    Ok(PythonVersionSelector::from_request(request)?
+        .is_some_and(|selector| selector.matches(interpreter)))
+}
+
 /// Pick a sensible default for the Python a user wants when they didn't specify a version.
 ///
 /// We prefer the test overwrite `UV_TEST_PYTHON_PATH` if it is set, otherwise `python3`/`python` or
@@ -75,7 +83,7 @@ pub fn find_default_python(cache: &Cache) -> Result<Interpreter, Error> {
 
 /// Same as [`find_default_python`] but returns `None` if no python is found instead of returning an `Err`.
 pub(crate) fn try_find_default_python(cache: &Cache) -> Result<Option<Interpreter>, Error> {
-    find_python(PythonVersionSelector::Default, cache)
+    find_python(&PythonVersionSelector::Default, cache)
 }
 
 /// Find a Python version matching `selector`.
@@ -88,11 +96,14 @@ pub(crate) fn try_find_default_python(cache: &Cache) -> Result<Option<Interprete
 ///   * Major: `pythonx`, `python`
 ///   * Default: `python3`, `python`
 ///   * (windows): For each of the above, test for the existence of `python.bat` shim (pyenv-windows) last.
-/// * (windows): Discover installations using `py --list-paths` (PEP514). Continue if `py` is not installed.
+/// * (windows): Discover installations registered in the Windows registry (PEP 514), then using
+///   `py --list-paths`. Continue if `py` is not installed.
+/// * Versions installed via `pyenv` (`$PYENV_ROOT/versions`), newest first.
+/// * Managed toolchains installed via `uv python install`, newest first.
 ///
 /// (Windows): Filter out the Windows store shim (Enabled in Settings/Apps/Advanced app settings/App execution aliases).
 fn find_python(
-    selector: PythonVersionSelector,
+    selector: &PythonVersionSelector,
     cache: &Cache,
 ) -> Result<Option<Interpreter>, Error> {
     #[allow(non_snake_case)]
@@ -110,38 +121,48 @@ fn find_python(
     // binary is executable and exists. It also has some extra logic that handles inconsistent casing on Windows
     // and expands `~`.
     for path in env::split_paths(&PATH) {
+        let mut executables = Vec::new();
+
+        // Version specifiers can match any minor version, so look for versioned binaries (e.g.,
+        // `python3.11`) in addition to `python3` and `python`.
+        if matches!(selector, PythonVersionSelector::Specifiers(_)) {
+            executables.extend(versioned_executables(&path));
+        }
+
         for name in possible_names.iter().flatten() {
             if let Ok(paths) = which::which_in_global(&**name, Some(&path)) {
-                for path in paths {
-                    #[cfg(windows)]
-                    if windows::is_windows_store_shim(&path) {
-                        continue;
-                    }
-
-                    let interpreter = match Interpreter::query(&path, cache) {
-                        Ok(interpreter) => interpreter,
-                        Err(
-                            err @ Error::QueryScript {
-                                err: InterpreterInfoError::UnsupportedPythonVersion,
-                                ..
-                            },
-                        ) => {
-                            if selector.major() <= Some(2) {
-                                return Err(err);
-                            }
-                            // Skip over Python 2 or older installation when querying for a recent python installation.
-                            debug!("Found a Python 2 installation that isn't supported by uv, skipping.");
-                            continue;
-                        }
-                        Err(error) => return Err(error),
-                    };
+                executables.extend(paths);
+            }
+        }
 
-                    let installation = PythonInstallation::Interpreter(interpreter);
+        for path in executables {
+            #[cfg(windows)]
+            if windows::is_windows_store_shim(&path) {
+                continue;
+            }
 
-                    if let Some(interpreter) = installation.select(selector, cache)? {
-                        return Ok(Some(interpreter));
+            let interpreter = match Interpreter::query(&path, cache) {
+                Ok(interpreter) => interpreter,
+                Err(
+                    err @ Error::QueryScript {
+                        err: InterpreterInfoError::UnsupportedPythonVersion,
+                        ..
+                    },
+                ) => {
+                    if selector.major() <= Some(2) {
+                        return Err(err);
                     }
+                    // Skip over Python 2 or older installation when querying for a recent python installation.
+                    debug!("Found a Python 2 installation that isn't supported by uv, skipping.");
+                    continue;
                 }
+                Err(error) => return Err(error),
+            };
+
+            let installation = PythonInstallation::Interpreter(interpreter);
+
+            if let Some(interpreter) = installation.select(selector, cache)? {
+                return Ok(Some(interpreter));
             }
         }
 
@@ -171,6 +192,18 @@ fn find_python(
         }
     }
 
+    #[cfg(windows)]
+    if !use_override {
+        // Read the installations registered in the Windows registry, which includes those that
+        // weren't added to `PATH`.
+        for entry in windows::registry_paths() {
+            let installation = PythonInstallation::PyListPath(entry);
+            if let Some(interpreter) = installation.select(selector, cache)? {
+                return Ok(Some(interpreter));
+            }
+        }
+    }
+
     if cfg!(windows) && !use_override {
         // Use `py` to find the python installation on the system.
         match windows::py_list_paths() {
@@ -191,9 +224,59 @@ fn find_python(
         }
     }
 
+    if !use_override {
+        // Look for versions installed via `pyenv`, which aren't on `PATH` unless selected.
+        if let Some(pyenv) = PyenvRoot::from_env() {
+            for version in pyenv.find_all() {
+                let installation = PythonInstallation::Pyenv(version);
+                if let Some(interpreter) = installation.select(selector, cache)? {
+                    return Ok(Some(interpreter));
+                }
+            }
+        }
+
+        // Fall back to the managed toolchains, if any match.
+        if let Ok(toolchains) = InstalledToolchains::from_settings() {
+            for toolchain in toolchains.find_all() {
+                let installation = PythonInstallation::Toolchain(toolchain);
+                if let Some(interpreter) = installation.select(selector, cache)? {
+                    return Ok(Some(interpreter));
+                }
+            }
+        }
+    }
+
     Ok(None)
 }
 
+/// Return the versioned Python 3 binaries (e.g., `python3.12`) in the given directory, newest
+/// first.
+fn versioned_executables(directory: &Path) -> Vec<PathBuf> {
+    static VERSIONED_PYTHON: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"^python(\d+)\.(\d+)(?:\.exe)?$").unwrap());
+
+    let Ok(entries) = fs_err::read_dir(directory) else {
+        return Vec::new();
+    };
+    let mut executables = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let captures = VERSIONED_PYTHON.captures(file_name.to_str()?)?;
+            let major = captures[1].parse::<u8>().ok()?;
+            let minor = captures[2].parse::<u8>().ok()?;
+            // Python 2 isn't supported by uv.
+            if major < 3 {
+                return None;
+            }
+            let path = entry.path();
+            path.is_file().then_some(((major, minor), path))
+        })
+        .collect::<Vec<_>>();
+    executables.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+    executables.into_iter().map(|(_, path)| path).collect()
+}
+
 /// Find the Python interpreter in `PATH` matching the given name (e.g., `python3`, respecting
 /// `UV_PYTHON_PATH`.
 ///
@@ -274,6 +357,8 @@ struct PyListPath {
 #[derive(Debug, Clone)]
 enum PythonInstallation {
     PyListPath(PyListPath),
+    Pyenv(PyenvVersion),
+    Toolchain(Toolchain),
     Interpreter(Interpreter),
 }
 
@@ -281,6 +366,8 @@ impl PythonInstallation {
     fn major(&self) -> u8 {
         match self {
             Self::PyListPath(PyListPath { major, .. }) => *major,
+            Self::Pyenv(version) => version.version().0,
+            Self::Toolchain(toolchain) => toolchain.version().0,
             Self::Interpreter(interpreter) => interpreter.python_major(),
         }
     }
@@ -288,6 +375,8 @@ impl PythonInstallation {
     fn minor(&self) -> u8 {
         match self {
             Self::PyListPath(PyListPath { minor, .. }) => *minor,
+            Self::Pyenv(version) => version.version().1,
+            Self::Toolchain(toolchain) => toolchain.version().1,
             Self::Interpreter(interpreter) => interpreter.python_minor(),
         }
     }
@@ -295,30 +384,21 @@ impl PythonInstallation {
     /// Selects the interpreter if it matches the selector (version specification).
     fn select(
         self,
-        selector: PythonVersionSelector,
+        selector: &PythonVersionSelector,
         cache: &Cache,
     ) -> Result<Option<Interpreter>, Error> {
         let selected = match selector {
             PythonVersionSelector::Default => true,
 
-            PythonVersionSelector::Major(major) => self.major() == major,
+            PythonVersionSelector::Major(major) => self.major() == *major,
 
             PythonVersionSelector::MajorMinor(major, minor) => {
-                self.major() == major && self.minor() == minor
+                self.major() == *major && self.minor() == *minor
             }
 
-            PythonVersionSelector::MajorMinorPatch(major, minor, requested_patch) => {
+            PythonVersionSelector::MajorMinorPatch(..) | PythonVersionSelector::Specifiers(_) => {
                 let interpreter = self.into_interpreter(cache)?;
-                return Ok(
-                    if major == interpreter.python_major()
-                        && minor == interpreter.python_minor()
-                        && requested_patch == interpreter.python_patch()
-                    {
-                        Some(interpreter)
-                    } else {
-                        None
-                    },
-                );
+                return Ok(selector.matches(&interpreter).then_some(interpreter));
             }
         };
 
@@ -334,21 +414,64 @@ impl PythonInstallation {
             Self::PyListPath(PyListPath {
                 executable_path, ..
             }) => Interpreter::query(executable_path, cache),
+            Self::Pyenv(version) => Interpreter::query(version.executable(), cache),
+            Self::Toolchain(toolchain) => Interpreter::query(toolchain.executable(), cache),
             Self::Interpreter(interpreter) => Ok(interpreter),
         }
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 enum PythonVersionSelector {
     Default,
     Major(u8),
     MajorMinor(u8, u8),
     MajorMinorPatch(u8, u8, u8),
+    Specifiers(VersionSpecifiers),
 }
 
 impl PythonVersionSelector {
-    fn possible_names(self) -> [Option<Cow<'static, str>>; 4] {
+    /// Parse a version request (e.g., `3.10`, `3.10.1`, or `>=3.10,<3.12`), returning `None` if
+    /// the request isn't a version (e.g., `python3.10` or a path).
+    fn from_request(request: &str) -> Result<Option<Self>, Error> {
+        let versions = request
+            .splitn(3, '.')
+            .map(str::parse::<u8>)
+            .collect::<Result<Vec<_>, _>>();
+        if let Ok(versions) = versions {
+            Ok(match versions.as_slice() {
+                [requested_major] => Some(Self::Major(*requested_major)),
+                [major, minor] => Some(Self::MajorMinor(*major, *minor)),
+                [major, minor, requested_patch] => {
+                    Some(Self::MajorMinorPatch(*major, *minor, *requested_patch))
+                }
+                // SAFETY: Guaranteed by the Ok(versions) guard
+                _ => unreachable!(),
+            })
+        } else if request.starts_with(['<', '>', '=', '!', '~']) {
+            let specifiers = VersionSpecifiers::from_str(request)
+                .map_err(|err| Error::InvalidVersionSpecifiers(request.to_string(), err))?;
+            Ok(Some(Self::Specifiers(specifiers)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns `true` if the interpreter satisfies the selector.
+    fn matches(&self, interpreter: &Interpreter) -> bool {
+        match self {
+            Self::Default => true,
+            Self::Major(major) => interpreter.python_major() == *major,
+            Self::MajorMinor(major, minor) => interpreter.python_tuple() == (*major, *minor),
+            Self::MajorMinorPatch(major, minor, patch) => {
+                interpreter.python_tuple() == (*major, *minor)
+                    && interpreter.python_patch() == *patch
+            }
+            Self::Specifiers(specifiers) => specifiers.contains(interpreter.python_version()),
+        }
+    }
+
+    fn possible_names(&self) -> [Option<Cow<'static, str>>; 4] {
         let (python, python3, extension) = if cfg!(windows) {
             (
                 Cow::Borrowed("python.exe"),
@@ -360,7 +483,7 @@ impl PythonVersionSelector {
         };
 
         match self {
-            Self::Default => [Some(python3), Some(python), None, None],
+            Self::Default | Self::Specifiers(_) => [Some(python3), Some(python), None, None],
             Self::Major(major) => [
                 Some(Cow::Owned(format!("python{major}{extension}"))),
                 Some(python),
@@ -384,12 +507,12 @@ impl PythonVersionSelector {
         }
     }
 
-    fn major(self) -> Option<u8> {
+    fn major(&self) -> Option<u8> {
         match self {
-            Self::Default => None,
-            Self::Major(major) => Some(major),
-            Self::MajorMinor(major, _) => Some(major),
-            Self::MajorMinorPatch(major, _, _) => Some(major),
+            Self::Default | Self::Specifiers(_) => None,
+            Self::Major(major) => Some(*major),
+            Self::MajorMinor(major, _) => Some(*major),
+            Self::MajorMinorPatch(major, _, _) => Some(*major),
         }
     }
 }
@@ -472,7 +595,7 @@ fn find_version(
     };
 
     // Check if the venv Python matches.
-    if let Some(venv) = detect_virtual_env()? {
+    if let Some((venv, _)) = detect_virtual_env()? {
         let executable = detect_python_executable(venv);
         let interpreter = Interpreter::query(executable, cache)?;
 
@@ -520,7 +643,6 @@ mod windows {
     /// Run `py --list-paths` to find the installed pythons.
     ///
     /// The command takes 8ms on my machine.
-    /// TODO(konstin): Implement <https://peps.python.org/pep-0514/> to read python installations from the registry instead.
     pub(super) fn py_list_paths() -> Result<Vec<PyListPath>, Error> {
         let output = info_span!("py_list_paths")
             .in_scope(|| Command::new("py").arg("--list-paths").output())
@@ -567,6 +689,84 @@ mod windows {
             .collect())
     }
 
+    /// Read the Python installations registered in the Windows registry, per
+    /// [PEP 514](https://peps.python.org/pep-0514/).
+    ///
+    /// Installations are registered under `Software\Python\{Company}\{Tag}` in both
+    /// `HKEY_CURRENT_USER` and `HKEY_LOCAL_MACHINE` (the latter in both its 64-bit and 32-bit
+    /// views). Each tag contains an `InstallPath` key, which provides the `ExecutablePath` or,
+    /// failing that, the installation directory containing `python.exe`.
+    #[cfg(windows)]
+    pub(super) fn registry_paths() -> Vec<PyListPath> {
+        use winreg::enums::{
+            HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_READ, KEY_WOW64_32KEY, KEY_WOW64_64KEY,
+        };
+        use winreg::RegKey;
+
+        let hives = [
+            (HKEY_CURRENT_USER, KEY_READ),
+            (HKEY_LOCAL_MACHINE, KEY_READ | KEY_WOW64_64KEY),
+            (HKEY_LOCAL_MACHINE, KEY_READ | KEY_WOW64_32KEY),
+        ];
+
+        let mut paths = Vec::new();
+        for (hive, flags) in hives {
+            let Ok(python) = RegKey::predef(hive).open_subkey_with_flags(r"Software\Python", flags)
+            else {
+                continue;
+            };
+            for company in python.enum_keys().filter_map(Result::ok) {
+                // The `py` launcher registers itself alongside the installations.
+                if company == "PyLauncher" {
+                    continue;
+                }
+                let Ok(company_key) = python.open_subkey_with_flags(&company, flags) else {
+                    continue;
+                };
+                for tag in company_key.enum_keys().filter_map(Result::ok) {
+                    let Ok(tag_key) = company_key.open_subkey_with_flags(&tag, flags) else {
+                        continue;
+                    };
+
+                    // Ex) `3.12`, or a tag like `3.12-32` or `3.12-arm64`.
+                    let version = tag_key
+                        .get_value::<String, _>("SysVersion")
+                        .unwrap_or_else(|_| tag.clone());
+                    let mut parts = version
+                        .split(|c: char| !c.is_ascii_digit())
+                        .map(str::parse::<u8>);
+                    let (Some(Ok(major)), Some(Ok(minor))) = (parts.next(), parts.next()) else {
+                        continue;
+                    };
+
+                    let Ok(install_path) = tag_key.open_subkey_with_flags("InstallPath", flags)
+                    else {
+                        continue;
+                    };
+                    let executable_path = if let Ok(executable) =
+                        install_path.get_value::<String, _>("ExecutablePath")
+                    {
+                        PathBuf::from(executable)
+                    } else if let Ok(directory) = install_path.get_value::<String, _>("") {
+                        PathBuf::from(directory).join("python.exe")
+                    } else {
+                        continue;
+                    };
+                    if !executable_path.is_file() {
+                        continue;
+                    }
+
+                    paths.push(PyListPath {
+                        major,
+                        minor,
+                        executable_path,
+                    });
+                }
+            }
+        }
+        paths
+    }
+
     /// On Windows we might encounter the Windows Store proxy shim (enabled in:
     /// Settings/Apps/Advanced app settings/App execution aliases). When Python is _not_ installed
     /// via the Windows Store, but the proxy shim is enabled, then executing `python.exe` or
@@ -760,6 +960,22 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(not(unix), ignore)]
+    fn no_such_python_specifiers() {
+        let request = ">=3.1000";
+        let result = find_requested_python(request, &Cache::temp().unwrap())
+            .unwrap()
+            .ok_or(Error::NoSuchPython(request.to_string()));
+        assert_snapshot!(
+            format_err(result),
+            @"No Python >=3.1000 in `PATH`. Is Python >=3.1000 installed?"
+        );
+
+        let result = find_requested_python(">=3.x", &Cache::temp().unwrap());
+        assert!(matches!(result, Err(Error::InvalidVersionSpecifiers(..))));
+    }
+
     #[test]
     #[cfg_attr(not(unix), ignore)]
     fn no_such_python_binary() {
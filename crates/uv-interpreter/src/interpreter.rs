@@ -26,6 +26,7 @@ pub struct Interpreter {
     platform: Platform,
     markers: Box<MarkerEnvironment>,
     scheme: Scheme,
+    user_scheme: Option<Scheme>,
     virtualenv: Scheme,
     prefix: PathBuf,
     base_exec_prefix: PathBuf,
@@ -33,6 +34,7 @@ pub struct Interpreter {
     base_executable: Option<PathBuf>,
     sys_executable: PathBuf,
     stdlib: PathBuf,
+    gil_disabled: bool,
     tags: OnceCell<Tags>,
 }
 
@@ -51,6 +53,7 @@ impl Interpreter {
             platform: info.platform,
             markers: Box::new(info.markers),
             scheme: info.scheme,
+            user_scheme: info.user_scheme,
             virtualenv: info.virtualenv,
             prefix: info.prefix,
             base_exec_prefix: info.base_exec_prefix,
@@ -58,6 +61,7 @@ impl Interpreter {
             base_executable: info.base_executable,
             sys_executable: info.sys_executable,
             stdlib: info.stdlib,
+            gil_disabled: info.gil_disabled,
             tags: OnceCell::new(),
         })
     }
@@ -74,6 +78,7 @@ impl Interpreter {
                 scripts: PathBuf::from("/dev/null"),
                 data: PathBuf::from("/dev/null"),
             },
+            user_scheme: None,
             virtualenv: Scheme {
                 purelib: PathBuf::from("/dev/null"),
                 platlib: PathBuf::from("/dev/null"),
@@ -87,6 +92,7 @@ impl Interpreter {
             base_executable: None,
             sys_executable: PathBuf::from("/dev/null"),
             stdlib: PathBuf::from("/dev/null"),
+            gil_disabled: false,
             tags: OnceCell::new(),
         }
     }
@@ -102,6 +108,28 @@ impl Interpreter {
         }
     }
 
+    /// Return a new [`Interpreter`] that installs into the user site-packages (as in
+    /// `pip install --user`), or `None` if the user site-packages are unavailable.
+    #[must_use]
+    pub fn with_user_scheme(self) -> Option<Self> {
+        let scheme = self.user_scheme.clone()?;
+        Some(Self { scheme, ..self })
+    }
+
+    /// Return a new [`Interpreter`] that installs into the given directory (as in
+    /// `pip install --target`), rather than its own `site-packages`.
+    #[must_use]
+    pub fn with_target_scheme(self, target: &Path) -> Self {
+        let scheme = Scheme {
+            purelib: target.to_path_buf(),
+            platlib: target.to_path_buf(),
+            scripts: target.join("bin"),
+            data: target.to_path_buf(),
+            include: target.join("include"),
+        };
+        Self { scheme, ..self }
+    }
+
     /// Returns the path to the Python virtual environment.
     #[inline]
     pub fn platform(&self) -> &Platform {
@@ -122,6 +150,7 @@ impl Interpreter {
                 self.python_tuple(),
                 self.implementation_name(),
                 self.implementation_tuple(),
+                self.gil_disabled(),
             )
         })
     }
@@ -223,7 +252,7 @@ impl Interpreter {
         (self.implementation_major(), self.implementation_minor())
     }
 
-    /// Returns the implementation name (e.g., `CPython` or `PyPy`).
+    /// Returns the implementation name (e.g., `cpython`, `pypy`, or `graalpy`).
     pub fn implementation_name(&self) -> &str {
         &self.markers.implementation_name
     }
@@ -259,6 +288,12 @@ impl Interpreter {
         &self.stdlib
     }
 
+    /// Returns `true` if this is a free-threaded build of CPython (i.e., built with
+    /// `Py_GIL_DISABLED`), which uses a distinct ABI (e.g., `cp313t`).
+    pub fn gil_disabled(&self) -> bool {
+        self.gil_disabled
+    }
+
     /// Return the `purelib` path for this Python interpreter, as returned by `sysconfig.get_paths()`.
     pub fn purelib(&self) -> &Path {
         &self.scheme.purelib
@@ -289,6 +324,12 @@ impl Interpreter {
         &self.virtualenv
     }
 
+    /// Return the user [`Scheme`] for this Python interpreter (i.e., the scheme used by
+    /// `pip install --user`), if user site-packages are available.
+    pub fn user_scheme(&self) -> Option<&Scheme> {
+        self.user_scheme.as_ref()
+    }
+
     /// Return the [`Layout`] environment used to install wheels into this interpreter.
     pub fn layout(&self) -> Layout {
         Layout {
@@ -312,6 +353,7 @@ impl Interpreter {
                     self.include().to_path_buf()
                 },
             },
+            relocatable: false,
         }
     }
 }
@@ -354,6 +396,7 @@ struct InterpreterInfo {
     platform: Platform,
     markers: MarkerEnvironment,
     scheme: Scheme,
+    user_scheme: Option<Scheme>,
     virtualenv: Scheme,
     prefix: PathBuf,
     base_exec_prefix: PathBuf,
@@ -361,6 +404,7 @@ struct InterpreterInfo {
     base_executable: Option<PathBuf>,
     sys_executable: PathBuf,
     stdlib: PathBuf,
+    gil_disabled: bool,
 }
 
 impl InterpreterInfo {
@@ -592,7 +636,8 @@ mod tests {
                     "platlib": "lib/python3.12/site-packages",
                     "purelib": "lib/python3.12/site-packages",
                     "scripts": "bin"
-                }
+                },
+                "gil_disabled": false
             }
         "##};
 
@@ -18,14 +18,21 @@ pub use crate::cfg::PyVenvConfiguration;
 pub use crate::find_python::{find_best_python, find_default_python, find_requested_python};
 pub use crate::interpreter::Interpreter;
 use crate::interpreter::InterpreterInfoError;
-pub use crate::python_environment::PythonEnvironment;
+pub use crate::managed::{InstalledToolchains, PythonDownload, Toolchain, ToolchainRequest};
+pub use crate::python_environment::{PythonEnvironment, VirtualEnvSource};
+pub use crate::python_implementation::PythonImplementation;
+pub use crate::python_platform::PythonPlatform;
 pub use crate::python_version::PythonVersion;
 pub use crate::virtualenv::Virtualenv;
 
 mod cfg;
 mod find_python;
 mod interpreter;
+mod managed;
+mod pyenv;
 mod python_environment;
+mod python_implementation;
+mod python_platform;
 mod python_version;
 mod virtualenv;
 
@@ -39,6 +46,12 @@ pub enum Error {
     VenvNotFound,
     #[error("Failed to locate Python interpreter at `{0}`")]
     RequestedPythonNotFound(String),
+    #[error("The Conda environment at `{}` doesn't contain a Python interpreter. Install one with `conda install python`.", _0.display())]
+    CondaMissingPython(PathBuf),
+    #[error("Invalid Python version specifiers: `{0}`")]
+    InvalidVersionSpecifiers(String, #[source] pep440_rs::VersionSpecifiersParseError),
+    #[error("User site-packages are not available for the Python interpreter at `{}` (user installs are not supported within virtual environments)", _0.display())]
+    UserSiteUnavailable(PathBuf),
     #[error(transparent)]
     Io(#[from] io::Error),
     #[error("Failed to query Python interpreter at `{interpreter}`")]
@@ -76,6 +89,8 @@ pub enum Error {
     Cfg(#[from] cfg::Error),
     #[error("Error finding `{}` in PATH", _0.to_string_lossy())]
     WhichError(OsString, #[source] which::Error),
+    #[error("Could not determine the toolchain directory; set `UV_TOOLCHAIN_DIR`")]
+    ToolchainDirectory,
     #[error("Can't use Python at `{interpreter}`")]
     QueryScript {
         #[source]
@@ -0,0 +1,309 @@
+//! Managed Python toolchains, i.e., standalone CPython builds downloaded and installed by uv.
+//!
+//! Toolchains are sourced from [`python-build-standalone`](https://github.com/indygreg/python-build-standalone),
+//! and installed into a directory per build (e.g., `cpython-3.12.1-linux-x86_64-gnu`) within the
+//! toolchain directory.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use directories::ProjectDirs;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::Error;
+
+/// The metadata for the available Python downloads, as generated by
+/// `scripts/bootstrap/fetch-version-metadata.py`.
+static DOWNLOADS: Lazy<BTreeMap<String, PythonDownload>> = Lazy::new(|| {
+    serde_json::from_str(include_str!("../../../scripts/bootstrap/versions.json"))
+        .expect("Python download metadata to be valid JSON")
+});
+
+/// A downloadable standalone Python build.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PythonDownload {
+    pub name: String,
+    pub arch: String,
+    pub os: String,
+    pub libc: String,
+    pub major: u8,
+    pub minor: u8,
+    pub patch: u8,
+    pub url: Option<String>,
+    pub sha256: Option<String>,
+}
+
+impl PythonDownload {
+    /// Return the newest download for the current platform that matches the given version
+    /// request.
+    pub fn find(request: &ToolchainRequest) -> Option<&'static Self> {
+        Self::iter_current_platform()
+            .filter(|download| download.url.is_some())
+            .filter(|download| request.matches(download.major, download.minor, download.patch))
+            .max_by_key(|download| (download.major, download.minor, download.patch))
+    }
+
+    /// Iterate over all downloads available for the current platform.
+    pub fn iter_current_platform() -> impl Iterator<Item = &'static Self> {
+        let (os, arch, libc) = current_platform();
+        DOWNLOADS.values().filter(move |download| {
+            download.os == os && download.arch == arch && download.libc == libc
+        })
+    }
+
+    /// The key used to identify the build, which doubles as the name of its installation
+    /// directory (e.g., `cpython-3.12.1-linux-x86_64-gnu`).
+    pub fn key(&self) -> String {
+        format!(
+            "{}-{}.{}.{}-{}-{}-{}",
+            self.name, self.major, self.minor, self.patch, self.os, self.arch, self.libc
+        )
+    }
+}
+
+/// A request for a Python toolchain version, e.g., `3`, `3.12`, or `3.12.1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolchainRequest {
+    major: u8,
+    minor: Option<u8>,
+    patch: Option<u8>,
+}
+
+impl ToolchainRequest {
+    /// Returns `true` if the given version satisfies the request.
+    pub fn matches(&self, major: u8, minor: u8, patch: u8) -> bool {
+        self.major == major
+            && self.minor.map_or(true, |request| request == minor)
+            && self.patch.map_or(true, |request| request == patch)
+    }
+}
+
+impl FromStr for ToolchainRequest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let versions = s
+            .splitn(3, '.')
+            .map(str::parse::<u8>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("Invalid Python version: `{s}`"))?;
+        match versions.as_slice() {
+            [major] => Ok(Self {
+                major: *major,
+                minor: None,
+                patch: None,
+            }),
+            [major, minor] => Ok(Self {
+                major: *major,
+                minor: Some(*minor),
+                patch: None,
+            }),
+            [major, minor, patch] => Ok(Self {
+                major: *major,
+                minor: Some(*minor),
+                patch: Some(*patch),
+            }),
+            _ => Err(format!("Invalid Python version: `{s}`")),
+        }
+    }
+}
+
+impl Display for ToolchainRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.major)?;
+        if let Some(minor) = self.minor {
+            write!(f, ".{minor}")?;
+        }
+        if let Some(patch) = self.patch {
+            write!(f, ".{patch}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A Python toolchain installed into the toolchain directory.
+#[derive(Debug, Clone)]
+pub struct Toolchain {
+    path: PathBuf,
+    key: String,
+    major: u8,
+    minor: u8,
+    patch: u8,
+}
+
+impl Toolchain {
+    /// Parse a toolchain from its installation directory, returning `None` if the directory name
+    /// isn't a valid toolchain key for the current platform.
+    fn from_path(path: PathBuf) -> Option<Self> {
+        let key = path.file_name()?.to_str()?.to_string();
+
+        // Ex) `cpython-3.12.1-linux-x86_64-gnu`
+        let mut parts = key.splitn(3, '-');
+        let _name = parts.next()?;
+        let mut version = parts.next()?.splitn(3, '.').map(str::parse::<u8>);
+        let major = version.next()?.ok()?;
+        let minor = version.next()?.ok()?;
+        let patch = version.next()?.ok()?;
+
+        let (os, arch, libc) = current_platform();
+        if parts.next()? != format!("{os}-{arch}-{libc}") {
+            return None;
+        }
+
+        Some(Self {
+            path,
+            key,
+            major,
+            minor,
+            patch,
+        })
+    }
+
+    /// The key identifying the build (e.g., `cpython-3.12.1-linux-x86_64-gnu`).
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The installation directory of the toolchain.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The Python version of the toolchain, as a `(major, minor, patch)` tuple.
+    pub fn version(&self) -> (u8, u8, u8) {
+        (self.major, self.minor, self.patch)
+    }
+
+    /// The path to the toolchain's Python executable.
+    pub fn executable(&self) -> PathBuf {
+        if cfg!(windows) {
+            self.path.join("install").join("python.exe")
+        } else {
+            self.path.join("install").join("bin").join("python3")
+        }
+    }
+}
+
+/// The directory into which managed Python toolchains are installed.
+#[derive(Debug, Clone)]
+pub struct InstalledToolchains {
+    root: PathBuf,
+}
+
+impl InstalledToolchains {
+    /// Determine the toolchain directory from the environment.
+    ///
+    /// Defaults to the `toolchains` directory within the user's data directory, and can be
+    /// overridden via `UV_TOOLCHAIN_DIR`.
+    pub fn from_settings() -> Result<Self, Error> {
+        if let Some(root) = std::env::var_os("UV_TOOLCHAIN_DIR") {
+            return Ok(Self::from_path(root));
+        }
+        ProjectDirs::from("", "", "uv")
+            .map(|dirs| Self::from_path(dirs.data_dir().join("toolchains")))
+            .ok_or(Error::ToolchainDirectory)
+    }
+
+    /// Use the given directory as the toolchain directory.
+    pub fn from_path(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The root of the toolchain directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Return the installed toolchains for the current platform, newest first.
+    pub fn find_all(&self) -> Vec<Toolchain> {
+        let mut toolchains = uv_fs::directories(&self.root)
+            .filter_map(Toolchain::from_path)
+            .filter(|toolchain| toolchain.executable().is_file())
+            .collect::<Vec<_>>();
+        toolchains.sort_unstable_by(|a, b| b.version().cmp(&a.version()));
+        debug!(
+            "Found {} managed toolchain(s) in: {}",
+            toolchains.len(),
+            self.root.display()
+        );
+        toolchains
+    }
+
+    /// Return the newest installed toolchain that matches the given request, if any.
+    pub fn find(&self, request: &ToolchainRequest) -> Option<Toolchain> {
+        self.find_all().into_iter().find(|toolchain| {
+            let (major, minor, patch) = toolchain.version();
+            request.matches(major, minor, patch)
+        })
+    }
+}
+
+/// Return the `(os, arch, libc)` triple for the current platform, using the names from the
+/// download metadata.
+fn current_platform() -> (&'static str, &'static str, &'static str) {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        os => os,
+    };
+    let arch = match std::env::consts::ARCH {
+        "aarch64" => "arm64",
+        "x86" => "i686",
+        "powerpc64" => "ppc64le",
+        arch => arch,
+    };
+    let libc = if cfg!(target_os = "linux") {
+        if cfg!(target_env = "musl") {
+            "musl"
+        } else {
+            "gnu"
+        }
+    } else {
+        "none"
+    };
+    (os, arch, libc)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{PythonDownload, ToolchainRequest};
+
+    #[test]
+    fn parse_request() {
+        let request = ToolchainRequest::from_str("3.12").unwrap();
+        assert!(request.matches(3, 12, 1));
+        assert!(!request.matches(3, 11, 1));
+        assert_eq!(request.to_string(), "3.12");
+
+        let request = ToolchainRequest::from_str("3.12.1").unwrap();
+        assert!(request.matches(3, 12, 1));
+        assert!(!request.matches(3, 12, 0));
+
+        assert!(ToolchainRequest::from_str("python3.12").is_err());
+        assert!(ToolchainRequest::from_str("3.12.1.1").is_err());
+    }
+
+    #[test]
+    fn find_download() {
+        // Not every platform is covered by the metadata, so only check the platforms we test on.
+        if !cfg!(all(
+            target_os = "linux",
+            target_arch = "x86_64",
+            target_env = "gnu"
+        )) {
+            return;
+        }
+        let download = PythonDownload::find(&ToolchainRequest::from_str("3.12").unwrap()).unwrap();
+        assert_eq!((download.major, download.minor), (3, 12));
+        assert_eq!(
+            download.key(),
+            format!("cpython-3.12.{}-linux-x86_64-gnu", download.patch)
+        );
+        assert!(download.sha256.is_some());
+    }
+}
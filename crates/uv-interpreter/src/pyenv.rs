@@ -0,0 +1,137 @@
+//! Python versions installed by [`pyenv`](https://github.com/pyenv/pyenv) (or
+//! [`pyenv-win`](https://github.com/pyenv-win/pyenv-win) on Windows).
+//!
+//! Each version is installed into its own directory within `$PYENV_ROOT/versions` (e.g.,
+//! `~/.pyenv/versions/3.12.1`), and is only on `PATH` if it's been selected via `pyenv global` or
+//! `pyenv local`.
+
+use std::path::PathBuf;
+
+use directories::BaseDirs;
+use tracing::debug;
+
+/// A Python version installed by `pyenv`.
+#[derive(Debug, Clone)]
+pub(crate) struct PyenvVersion {
+    path: PathBuf,
+    major: u8,
+    minor: u8,
+}
+
+impl PyenvVersion {
+    /// Parse a version from its installation directory, returning `None` if the directory name
+    /// isn't a CPython version (e.g., `pypy3.10-7.3.15` or `miniconda3-latest`).
+    fn from_path(path: PathBuf) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+
+        // Ex) `3.12.1` or `3.13-dev`
+        let version = name.split_once('-').map_or(name, |(version, _)| version);
+        let mut parts = version.splitn(3, '.').map(str::parse::<u8>);
+        let major = parts.next()?.ok()?;
+        let minor = parts.next()?.ok()?;
+        if let Some(patch) = parts.next() {
+            patch.ok()?;
+        }
+
+        Some(Self { path, major, minor })
+    }
+
+    /// The Python version, as a `(major, minor)` tuple.
+    pub(crate) fn version(&self) -> (u8, u8) {
+        (self.major, self.minor)
+    }
+
+    /// The path to the version's Python executable.
+    pub(crate) fn executable(&self) -> PathBuf {
+        if cfg!(windows) {
+            self.path.join("python.exe")
+        } else {
+            self.path.join("bin").join("python3")
+        }
+    }
+}
+
+/// The root of a `pyenv` installation.
+#[derive(Debug, Clone)]
+pub(crate) struct PyenvRoot {
+    root: PathBuf,
+}
+
+impl PyenvRoot {
+    /// Determine the `pyenv` root from the environment.
+    ///
+    /// Respects `PYENV_ROOT`, and otherwise defaults to `~/.pyenv` (or `~/.pyenv/pyenv-win` on
+    /// Windows).
+    pub(crate) fn from_env() -> Option<Self> {
+        if let Some(root) = std::env::var_os("PYENV_ROOT") {
+            return Some(Self::from_path(root));
+        }
+        let home = BaseDirs::new()?.home_dir().join(".pyenv");
+        if cfg!(windows) {
+            Some(Self::from_path(home.join("pyenv-win")))
+        } else {
+            Some(Self::from_path(home))
+        }
+    }
+
+    /// Use the given directory as the `pyenv` root.
+    pub(crate) fn from_path(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The directory containing the installed versions.
+    fn versions(&self) -> PathBuf {
+        self.root.join("versions")
+    }
+
+    /// Return the installed versions, newest first.
+    pub(crate) fn find_all(&self) -> Vec<PyenvVersion> {
+        let versions = self.versions();
+        let mut installed = uv_fs::directories(&versions)
+            .filter_map(PyenvVersion::from_path)
+            .filter(|version| version.executable().is_file())
+            .collect::<Vec<_>>();
+        installed.sort_unstable_by(|a, b| b.version().cmp(&a.version()));
+        debug!(
+            "Found {} pyenv version(s) in: {}",
+            installed.len(),
+            versions.display()
+        );
+        installed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fs_err as fs;
+
+    use super::{PyenvRoot, PyenvVersion};
+
+    #[test]
+    fn find_all() {
+        let root = tempfile::tempdir().unwrap();
+        for name in ["3.8.18", "3.12.1", "3.13-dev", "pypy3.10-7.3.15", "3.11.7"] {
+            let bin = if cfg!(windows) {
+                root.path().join("versions").join(name)
+            } else {
+                root.path().join("versions").join(name).join("bin")
+            };
+            fs::create_dir_all(&bin).unwrap();
+            let executable = if cfg!(windows) {
+                bin.join("python.exe")
+            } else {
+                bin.join("python3")
+            };
+            fs::write(executable, "").unwrap();
+        }
+        // A version that was never fully installed.
+        fs::create_dir_all(root.path().join("versions").join("3.10.13")).unwrap();
+
+        let versions = PyenvRoot::from_path(root.path())
+            .find_all()
+            .iter()
+            .map(PyenvVersion::version)
+            .collect::<Vec<_>>();
+        assert_eq!(versions, vec![(3, 13), (3, 12), (3, 11), (3, 8)]);
+    }
+}
@@ -7,23 +7,46 @@ use uv_cache::Cache;
 use uv_fs::{LockedFile, Simplified};
 
 use crate::cfg::PyVenvConfiguration;
+use crate::find_python::{is_version_request, satisfies_request};
 use crate::{find_default_python, find_requested_python, Error, Interpreter};
 
+/// How a virtual environment was located.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VirtualEnvSource {
+    /// The `VIRTUAL_ENV` environment variable, as set when a virtual environment is activated.
+    VirtualEnv,
+    /// The `CONDA_PREFIX` environment variable, as set when a Conda environment is activated.
+    CondaPrefix,
+    /// A `.venv` directory in the current directory or one of its parents.
+    DotVenv,
+}
+
 /// A Python environment, consisting of a Python [`Interpreter`] and its associated paths.
 #[derive(Debug, Clone)]
 pub struct PythonEnvironment {
     root: PathBuf,
     interpreter: Interpreter,
+    /// The `site-packages` directories that are visible to the interpreter, but should not be
+    /// modified (e.g., the system `site-packages` when installing into the user site).
+    base_site_packages: Vec<PathBuf>,
+    /// How the environment was located, if it was discovered as a virtual environment.
+    source: Option<VirtualEnvSource>,
 }
 
 impl PythonEnvironment {
     /// Create a [`PythonEnvironment`] for an existing virtual environment.
     pub fn from_virtualenv(cache: &Cache) -> Result<Self, Error> {
-        let Some(venv) = detect_virtual_env()? else {
+        let Some((venv, source)) = detect_virtual_env()? else {
             return Err(Error::VenvNotFound);
         };
         let venv = fs_err::canonicalize(venv)?;
         let executable = detect_python_executable(&venv);
+
+        // Conda environments aren't required to include Python.
+        if source == VirtualEnvSource::CondaPrefix && !executable.is_file() {
+            return Err(Error::CondaMissingPython(venv));
+        }
+
         let interpreter = Interpreter::query(&executable, cache)?;
 
         debug_assert!(
@@ -36,6 +59,22 @@ impl PythonEnvironment {
         Ok(Self {
             root: venv,
             interpreter,
+            base_site_packages: Vec::new(),
+            source: Some(source),
+        })
+    }
+
+    /// Create a [`PythonEnvironment`] for the virtual environment at the given root.
+    pub fn from_root(root: &Path, cache: &Cache) -> Result<Self, Error> {
+        let venv = fs_err::canonicalize(root)?;
+        let executable = detect_python_executable(&venv);
+        let interpreter = Interpreter::query(&executable, cache)?;
+
+        Ok(Self {
+            root: venv,
+            interpreter,
+            base_site_packages: Vec::new(),
+            source: None,
         })
     }
 
@@ -47,15 +86,44 @@ impl PythonEnvironment {
         Ok(Self {
             root: interpreter.prefix().to_path_buf(),
             interpreter,
+            base_site_packages: Vec::new(),
+            source: None,
         })
     }
 
+    /// Create a [`PythonEnvironment`] for a Python interpreter request, preferring the active
+    /// virtual environment.
+    ///
+    /// If the request is a Python version (e.g., `3.12` or `>=3.10`) and the virtual environment's
+    /// interpreter satisfies it, the virtual environment is used. Otherwise (or if the request is
+    /// an executable name or path), the request is resolved as in
+    /// [`PythonEnvironment::from_requested_python`].
+    pub fn from_requested_python_or_virtualenv(python: &str, cache: &Cache) -> Result<Self, Error> {
+        if is_version_request(python)? {
+            match Self::from_virtualenv(cache) {
+                Ok(venv) if satisfies_request(python, venv.interpreter())? => return Ok(venv),
+                Ok(venv) => {
+                    debug!(
+                        "Ignoring virtual environment at `{}` with Python {}, which doesn't satisfy `{python}`",
+                        venv.root().user_display(),
+                        venv.interpreter().python_version()
+                    );
+                }
+                Err(Error::VenvNotFound) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Self::from_requested_python(python, cache)
+    }
+
     /// Create a [`PythonEnvironment`] for the default Python interpreter.
     pub fn from_default_python(cache: &Cache) -> Result<Self, Error> {
         let interpreter = find_default_python(cache)?;
         Ok(Self {
             root: interpreter.prefix().to_path_buf(),
             interpreter,
+            base_site_packages: Vec::new(),
+            source: None,
         })
     }
 
@@ -64,14 +132,62 @@ impl PythonEnvironment {
         Self {
             root: interpreter.prefix().to_path_buf(),
             interpreter,
+            base_site_packages: Vec::new(),
+            source: None,
         }
     }
 
+    /// Return a new [`PythonEnvironment`] that installs into the user site-packages of the
+    /// underlying interpreter (as in `pip install --user`).
+    ///
+    /// The interpreter's existing `site-packages` directories remain visible (e.g., to determine
+    /// whether a requirement is already satisfied), but are treated as read-only.
+    pub fn with_user_site(self) -> Result<Self, Error> {
+        let base_site_packages = self.site_packages().map(Path::to_path_buf).collect();
+        let executable = self.interpreter.sys_executable().to_path_buf();
+        let Some(interpreter) = self.interpreter.with_user_scheme() else {
+            return Err(Error::UserSiteUnavailable(executable));
+        };
+        debug!(
+            "Using user site-packages at: {}",
+            interpreter.purelib().display()
+        );
+        Ok(Self {
+            root: self.root,
+            interpreter,
+            base_site_packages,
+            source: self.source,
+        })
+    }
+
+    /// Return a new [`PythonEnvironment`] that installs into the given directory (as in
+    /// `pip install --target`), creating it if necessary.
+    ///
+    /// Packages installed in the interpreter's own `site-packages` are ignored, such that every
+    /// requirement is installed into the target directory.
+    pub fn with_target(self, target: &Path) -> Result<Self, Error> {
+        fs_err::create_dir_all(target)?;
+        let target = fs_err::canonicalize(target)?;
+        debug!("Using target directory at: {}", target.display());
+        Ok(Self {
+            root: self.root,
+            interpreter: self.interpreter.with_target_scheme(&target),
+            base_site_packages: Vec::new(),
+            source: self.source,
+        })
+    }
+
     /// Returns the location of the Python interpreter.
     pub fn root(&self) -> &Path {
         &self.root
     }
 
+    /// Returns how the environment was located, if it was discovered as a virtual environment
+    /// (e.g., via `VIRTUAL_ENV` or a `.venv` directory).
+    pub fn source(&self) -> Option<VirtualEnvSource> {
+        self.source
+    }
+
     /// Return the [`Interpreter`] for this virtual environment.
     pub fn interpreter(&self) -> &Interpreter {
         &self.interpreter
@@ -83,6 +199,12 @@ impl PythonEnvironment {
         Ok(PyVenvConfiguration::parse(self.root.join("pyvenv.cfg"))?)
     }
 
+    /// Returns `true` if the environment is a relocatable virtual environment (i.e., created with
+    /// `uv venv --relocatable`).
+    pub fn relocatable(&self) -> bool {
+        self.cfg().is_ok_and(|cfg| cfg.is_relocatable())
+    }
+
     /// Returns the location of the Python executable.
     pub fn python_executable(&self) -> &Path {
         self.interpreter.sys_executable()
@@ -102,6 +224,21 @@ impl PythonEnvironment {
         )
     }
 
+    /// Returns an iterator over the `site-packages` directories that are visible to the
+    /// interpreter, but should not be modified (e.g., the system `site-packages` when installing
+    /// into the user site).
+    pub fn base_site_packages(&self) -> impl Iterator<Item = &Path> {
+        self.base_site_packages.iter().map(PathBuf::as_path)
+    }
+
+    /// Returns `true` if the given path is contained in one of the read-only `site-packages`
+    /// directories (see: [`PythonEnvironment::base_site_packages`]).
+    pub fn is_base_site_packages(&self, path: &Path) -> bool {
+        self.base_site_packages
+            .iter()
+            .any(|site_packages| path.starts_with(site_packages))
+    }
+
     /// Returns the path to the `bin` directory inside a virtual environment.
     pub fn scripts(&self) -> &Path {
         self.interpreter.scripts()
@@ -123,20 +260,24 @@ impl PythonEnvironment {
 }
 
 /// Locate the current virtual environment.
-pub(crate) fn detect_virtual_env() -> Result<Option<PathBuf>, Error> {
+pub(crate) fn detect_virtual_env() -> Result<Option<(PathBuf, VirtualEnvSource)>, Error> {
     if let Some(dir) = env::var_os("VIRTUAL_ENV").filter(|value| !value.is_empty()) {
         info!(
             "Found a virtualenv through VIRTUAL_ENV at: {}",
             Path::new(&dir).display()
         );
-        return Ok(Some(PathBuf::from(dir)));
+        return Ok(Some((PathBuf::from(dir), VirtualEnvSource::VirtualEnv)));
     }
-    if let Some(dir) = env::var_os("CONDA_PREFIX").filter(|value| !value.is_empty()) {
+
+    // The base Conda environment is often activated automatically in every shell, so prefer a
+    // `.venv` over it.
+    let conda_prefix = env::var_os("CONDA_PREFIX").filter(|value| !value.is_empty());
+    if let Some(dir) = conda_prefix.as_ref().filter(|_| !is_conda_base()) {
         info!(
-            "Found a virtualenv through CONDA_PREFIX at: {}",
-            Path::new(&dir).display()
+            "Found a Conda environment through CONDA_PREFIX at: {}",
+            Path::new(dir).display()
         );
-        return Ok(Some(PathBuf::from(dir)));
+        return Ok(Some((PathBuf::from(dir), VirtualEnvSource::CondaPrefix)));
     }
 
     // Search for a `.venv` directory in the current or any parent directory.
@@ -148,13 +289,27 @@ pub(crate) fn detect_virtual_env() -> Result<Option<PathBuf>, Error> {
                 return Err(Error::MissingPyVenvCfg(dot_venv));
             }
             debug!("Found a virtualenv named .venv at: {}", dot_venv.display());
-            return Ok(Some(dot_venv));
+            return Ok(Some((dot_venv, VirtualEnvSource::DotVenv)));
         }
     }
 
+    if let Some(dir) = conda_prefix {
+        info!(
+            "Found the base Conda environment through CONDA_PREFIX at: {}",
+            Path::new(&dir).display()
+        );
+        return Ok(Some((PathBuf::from(dir), VirtualEnvSource::CondaPrefix)));
+    }
+
     Ok(None)
 }
 
+/// Returns `true` if the active Conda environment is the base environment, as indicated by
+/// `CONDA_DEFAULT_ENV`.
+fn is_conda_base() -> bool {
+    env::var_os("CONDA_DEFAULT_ENV").is_some_and(|name| name == "base")
+}
+
 /// Returns the path to the `python` executable inside a virtual environment.
 pub(crate) fn detect_python_executable(venv: impl AsRef<Path>) -> PathBuf {
     let venv = venv.as_ref();
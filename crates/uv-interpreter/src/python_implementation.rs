@@ -0,0 +1,80 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use pep508_rs::MarkerEnvironment;
+
+/// A target Python implementation for resolution, used to select wheels for an interpreter other
+/// than the current one (e.g., to fetch PyPy wheels with CPython).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PythonImplementation {
+    /// CPython, identified by the `cp` tag.
+    CPython,
+    /// PyPy, identified by the `pp` tag.
+    PyPy,
+}
+
+impl FromStr for PythonImplementation {
+    type Err = String;
+
+    /// Parse an implementation from its wheel tag abbreviation (e.g., `cp` or `pp`), as in
+    /// `pip download --implementation`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cp" | "cpython" => Ok(Self::CPython),
+            "pp" | "pypy" => Ok(Self::PyPy),
+            _ => Err(format!(
+                "Unsupported implementation `{s}`; expected one of `cp` or `pp`"
+            )),
+        }
+    }
+}
+
+impl Display for PythonImplementation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CPython => f.write_str("cp"),
+            Self::PyPy => f.write_str("pp"),
+        }
+    }
+}
+
+impl PythonImplementation {
+    /// Return the implementation name, as in `sys.implementation.name` (e.g., `cpython`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CPython => "cpython",
+            Self::PyPy => "pypy",
+        }
+    }
+
+    /// Return the implementation version to assume for the given Python version.
+    ///
+    /// CPython's implementation version matches its Python version, while every supported PyPy
+    /// release shares the `pp73` ABI.
+    pub fn version(self, python_version: (u8, u8)) -> (u8, u8) {
+        match self {
+            Self::CPython => python_version,
+            Self::PyPy => (7, 3),
+        }
+    }
+
+    /// Return a [`MarkerEnvironment`] compatible with the given [`PythonImplementation`], based
+    /// on a base [`MarkerEnvironment`].
+    ///
+    /// The implementation version of the target is unknown, so the base environment's
+    /// `implementation_version` is preserved.
+    pub fn markers(self, base: &MarkerEnvironment) -> MarkerEnvironment {
+        let mut markers = base.clone();
+        match self {
+            Self::CPython => {
+                markers.implementation_name = "cpython".to_string();
+                markers.platform_python_implementation = "CPython".to_string();
+            }
+            Self::PyPy => {
+                markers.implementation_name = "pypy".to_string();
+                markers.platform_python_implementation = "PyPy".to_string();
+            }
+        }
+        markers
+    }
+}
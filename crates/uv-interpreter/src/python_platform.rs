@@ -0,0 +1,202 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use pep508_rs::MarkerEnvironment;
+use platform_tags::{Arch, Os, Platform};
+
+/// A target platform for resolution, used to resolve for an environment other than the current
+/// one (e.g., to resolve for a Linux container from macOS).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonPlatform {
+    /// The verbatim representation, as provided by the user.
+    name: String,
+    /// The operating system and architecture of the target platform.
+    platform: Platform,
+}
+
+impl FromStr for PythonPlatform {
+    type Err = String;
+
+    /// Parse a platform from either a shorthand (`linux`, `macos`, or `windows`), which targets
+    /// the most common architecture for that operating system, or a wheel platform tag (e.g.,
+    /// `manylinux_2_17_x86_64`, `macosx_11_0_arm64`, or `win_amd64`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let platform = match s {
+            "linux" => Platform::new(Os::Manylinux { major: 2, minor: 17 }, Arch::X86_64),
+            "macos" => Platform::new(Os::Macos { major: 12, minor: 0 }, Arch::Aarch64),
+            "windows" => Platform::new(Os::Windows, Arch::X86_64),
+            tag => parse_platform_tag(tag).ok_or_else(|| {
+                format!(
+                    "Unsupported platform `{tag}`; expected one of `linux`, `macos`, `windows`, or a wheel platform tag (e.g., `manylinux_2_17_x86_64`)"
+                )
+            })?,
+        };
+        Ok(Self {
+            name: s.to_string(),
+            platform,
+        })
+    }
+}
+
+impl Display for PythonPlatform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl PythonPlatform {
+    /// Return the [`Platform`] to use when determining compatible wheel tags.
+    pub fn platform(&self) -> &Platform {
+        &self.platform
+    }
+
+    /// Return a [`MarkerEnvironment`] compatible with the given [`PythonPlatform`], based on
+    /// a base [`MarkerEnvironment`].
+    ///
+    /// The returned [`MarkerEnvironment`] will preserve the base environment's Python version
+    /// markers, but override its platform markers.
+    pub fn markers(&self, base: &MarkerEnvironment) -> MarkerEnvironment {
+        let mut markers = base.clone();
+
+        let arch = self.platform.arch();
+        match self.platform.os() {
+            Os::Manylinux { .. } | Os::Musllinux { .. } => {
+                markers.os_name = "posix".to_string();
+                markers.platform_system = "Linux".to_string();
+                markers.sys_platform = "linux".to_string();
+                markers.platform_machine = arch.to_string();
+            }
+            Os::Macos { .. } => {
+                markers.os_name = "posix".to_string();
+                markers.platform_system = "Darwin".to_string();
+                markers.sys_platform = "darwin".to_string();
+                markers.platform_machine = match arch {
+                    Arch::Aarch64 => "arm64".to_string(),
+                    arch => arch.to_string(),
+                };
+            }
+            Os::Windows => {
+                markers.os_name = "nt".to_string();
+                markers.platform_system = "Windows".to_string();
+                markers.sys_platform = "win32".to_string();
+                markers.platform_machine = match arch {
+                    Arch::X86_64 => "AMD64".to_string(),
+                    Arch::Aarch64 => "ARM64".to_string(),
+                    arch => arch.to_string(),
+                };
+            }
+            // Only the platforms above can be parsed from user input.
+            _ => {}
+        }
+
+        // The release and version of the target operating system are unknown.
+        markers.platform_release = String::new();
+        markers.platform_version = String::new();
+
+        markers
+    }
+}
+
+/// Parse a wheel platform tag (e.g., `manylinux_2_17_x86_64`) into a [`Platform`].
+fn parse_platform_tag(tag: &str) -> Option<Platform> {
+    match tag {
+        "win32" => return Some(Platform::new(Os::Windows, Arch::X86)),
+        "win_amd64" => return Some(Platform::new(Os::Windows, Arch::X86_64)),
+        "win_arm64" => return Some(Platform::new(Os::Windows, Arch::Aarch64)),
+        _ => {}
+    }
+
+    // Ex) `manylinux2014_x86_64`
+    let legacy = [
+        ("manylinux1_", (2, 5)),
+        ("manylinux2010_", (2, 12)),
+        ("manylinux2014_", (2, 17)),
+    ];
+    for (prefix, (major, minor)) in legacy {
+        if let Some(arch) = tag.strip_prefix(prefix) {
+            return Some(Platform::new(
+                Os::Manylinux { major, minor },
+                parse_arch(arch)?,
+            ));
+        }
+    }
+
+    // Ex) `manylinux_2_17_x86_64`, `musllinux_1_2_aarch64`, or `macosx_11_0_arm64`
+    let (os, rest) = tag.split_once('_')?;
+    let (major, rest) = rest.split_once('_')?;
+    let (minor, arch) = rest.split_once('_')?;
+    let major = major.parse::<u16>().ok()?;
+    let minor = minor.parse::<u16>().ok()?;
+    let os = match os {
+        "manylinux" => Os::Manylinux { major, minor },
+        "musllinux" => Os::Musllinux { major, minor },
+        "macosx" => Os::Macos { major, minor },
+        _ => return None,
+    };
+    Some(Platform::new(os, parse_arch(arch)?))
+}
+
+/// Parse the architecture component of a wheel platform tag.
+fn parse_arch(arch: &str) -> Option<Arch> {
+    match arch {
+        "x86_64" => Some(Arch::X86_64),
+        "aarch64" | "arm64" => Some(Arch::Aarch64),
+        "i686" => Some(Arch::X86),
+        "armv7l" => Some(Arch::Armv7L),
+        "ppc64le" => Some(Arch::Powerpc64Le),
+        "ppc64" => Some(Arch::Powerpc64),
+        "s390x" => Some(Arch::S390X),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use platform_tags::{Arch, Os};
+
+    use crate::PythonPlatform;
+
+    #[test]
+    fn shorthand() {
+        let platform = PythonPlatform::from_str("linux").unwrap();
+        assert_eq!(
+            platform.platform().os(),
+            &Os::Manylinux {
+                major: 2,
+                minor: 17
+            }
+        );
+        assert_eq!(platform.platform().arch(), Arch::X86_64);
+    }
+
+    #[test]
+    fn tags() {
+        let platform = PythonPlatform::from_str("manylinux2014_aarch64").unwrap();
+        assert_eq!(
+            platform.platform().os(),
+            &Os::Manylinux {
+                major: 2,
+                minor: 17
+            }
+        );
+        assert_eq!(platform.platform().arch(), Arch::Aarch64);
+
+        let platform = PythonPlatform::from_str("macosx_11_0_arm64").unwrap();
+        assert_eq!(
+            platform.platform().os(),
+            &Os::Macos {
+                major: 11,
+                minor: 0
+            }
+        );
+        assert_eq!(platform.platform().arch(), Arch::Aarch64);
+
+        let platform = PythonPlatform::from_str("win_amd64").unwrap();
+        assert_eq!(platform.platform().os(), &Os::Windows);
+        assert_eq!(platform.platform().arch(), Arch::X86_64);
+
+        assert!(PythonPlatform::from_str("macosx_arm64").is_err());
+    }
+}
@@ -5,6 +5,7 @@ use std::ops::Deref;
 
 use dashmap::{DashMap, DashSet};
 use indexmap::IndexMap;
+use itertools::Itertools;
 use pubgrub::range::Range;
 use pubgrub::report::{DefaultStringReporter, DerivationTree, Reporter};
 use rustc_hash::FxHashMap;
@@ -13,10 +14,13 @@ use distribution_types::{BuiltDist, IndexLocations, PathBuiltDist, PathSourceDis
 use once_map::OnceMap;
 use pep440_rs::Version;
 use pep508_rs::Requirement;
+use platform_tags::IncompatibleTag;
 use uv_normalize::PackageName;
 
 use crate::candidate_selector::CandidateSelector;
-use crate::pubgrub::{PubGrubPackage, PubGrubPython, PubGrubReportFormatter};
+use crate::pubgrub::{
+    DisplayDerivationTree, PubGrubPackage, PubGrubPython, PubGrubReportFormatter,
+};
 use crate::python_requirement::PythonRequirement;
 use crate::resolver::{UnavailablePackage, VersionsResponse};
 
@@ -25,6 +29,9 @@ pub enum ResolveError {
     #[error("Failed to find a version of {0} that satisfies the requirement")]
     NotFound(Requirement),
 
+    #[error("Network connectivity is disabled, but the following packages weren't found in the cache: {}", .0.iter().map(|name| format!("`{name}`")).join(", "))]
+    Offline(Vec<PackageName>),
+
     #[error(transparent)]
     Client(#[from] uv_client::Error),
 
@@ -55,6 +62,9 @@ pub enum ResolveError {
     #[error("Package `{0}` attempted to resolve via URL: {1}. URL dependencies must be expressed as direct requirements or constraints. Consider adding `{0} @ {1}` to your dependencies or constraints file.")]
     DisallowedUrl(PackageName, String),
 
+    #[error("Package `{0}` is required at conflicting versions under different markers, which requires forking the resolution")]
+    ConflictingMarkers(PackageName),
+
     #[error("There are conflicting editable requirements for package `{0}`:\n- {1}\n- {2}")]
     ConflictingEditables(PackageName, String, String),
 
@@ -123,6 +133,7 @@ impl From<pubgrub::error::PubGrubError<PubGrubPackage, Range<Version>, Infallibl
                     python_requirement: None,
                     index_locations: None,
                     unavailable_packages: FxHashMap::default(),
+                    incompatible_wheels: FxHashMap::default(),
                 })
             }
             pubgrub::error::PubGrubError::SelfDependency { package, version } => {
@@ -144,6 +155,7 @@ pub struct NoSolutionError {
     python_requirement: Option<PythonRequirement>,
     index_locations: Option<IndexLocations>,
     unavailable_packages: FxHashMap<PackageName, UnavailablePackage>,
+    incompatible_wheels: FxHashMap<(PackageName, Version), IncompatibleTag>,
 }
 
 impl std::error::Error for NoSolutionError {}
@@ -165,6 +177,7 @@ impl std::fmt::Display for NoSolutionError {
             &self.selector,
             &self.index_locations,
             &self.unavailable_packages,
+            &self.incompatible_wheels,
         ) {
             write!(f, "\n\n{hint}")?;
         }
@@ -174,6 +187,19 @@ impl std::fmt::Display for NoSolutionError {
 }
 
 impl NoSolutionError {
+    /// Render the derivation tree as an indented tree of incompatibilities.
+    pub fn derivation_tree(&self) -> String {
+        let formatter = PubGrubReportFormatter {
+            available_versions: &self.available_versions,
+            python_requirement: self.python_requirement.as_ref(),
+        };
+        DisplayDerivationTree {
+            tree: &self.derivation_tree,
+            formatter: &formatter,
+        }
+        .to_string()
+    }
+
     /// Update the available versions attached to the error using the given package version index.
     ///
     /// Only packages used in the error's derivation tree will be retrieved.
@@ -258,6 +284,29 @@ impl NoSolutionError {
         self
     }
 
+    /// Update the incompatible wheels attached to the error.
+    ///
+    /// Only package versions used in the error's derivation tree will be retained.
+    #[must_use]
+    pub(crate) fn with_incompatible_wheels(
+        mut self,
+        incompatible_wheels: &DashMap<(PackageName, Version), IncompatibleTag>,
+    ) -> Self {
+        let mut new = FxHashMap::default();
+        for package in self.derivation_tree.packages() {
+            if let PubGrubPackage::Package(name, ..) = package {
+                for entry in incompatible_wheels.iter() {
+                    let ((package_name, version), tag) = entry.pair();
+                    if package_name == name {
+                        new.insert((package_name.clone(), version.clone()), tag.clone());
+                    }
+                }
+            }
+        }
+        self.incompatible_wheels = new;
+        self
+    }
+
     /// Update the Python requirements attached to the error.
     #[must_use]
     pub(crate) fn with_python_requirement(
@@ -2,8 +2,10 @@
 //!
 //! This is similar to running `pip install` with the `--no-deps` flag.
 
+use std::pin::pin;
+
 use anyhow::Result;
-use futures::{stream, Stream, StreamExt, TryStreamExt};
+use futures::{stream, Stream, StreamExt};
 use rustc_hash::FxHashMap;
 use uv_traits::{NoBinary, NoBuild};
 
@@ -70,11 +72,19 @@ impl<'a> DistFinder<'a> {
         match requirement.version_or_url.as_ref() {
             None | Some(VersionOrUrl::VersionSpecifier(_)) => {
                 // Query the index(es) (cached) to get the URLs for the available files.
-                let (index, raw_metadata) = self.client.simple(&requirement.name).await?;
-                let metadata = OwnedArchive::deserialize(&raw_metadata);
+                let results = match self.client.simple(&requirement.name).await {
+                    Ok(results) => results,
+                    Err(err) if err.is_offline() => {
+                        return Err(ResolveError::Offline(vec![requirement.name.clone()]));
+                    }
+                    Err(err) => return Err(err.into()),
+                };
 
-                // Pick a version that satisfies the requirement.
-                let Some(dist) = self.select(requirement, metadata, &index, flat_index) else {
+                // Pick a version that satisfies the requirement, from the first index that has one.
+                let Some(dist) = results.iter().find_map(|(index, raw_metadata)| {
+                    let metadata = OwnedArchive::deserialize(raw_metadata);
+                    self.select(requirement, metadata, index, flat_index)
+                }) else {
                     return Err(ResolveError::NotFound(requirement.clone()));
                 };
 
@@ -112,8 +122,24 @@ impl<'a> DistFinder<'a> {
             return Ok(Resolution::default());
         }
 
-        let resolution: FxHashMap<PackageName, Dist> =
-            self.resolve_stream(requirements).try_collect().await?;
+        // When offline, report every package that's missing from the cache, rather than just the
+        // first.
+        let mut resolution: FxHashMap<PackageName, Dist> = FxHashMap::default();
+        let mut missing = Vec::new();
+        let mut results = pin!(self.resolve_stream(requirements));
+        while let Some(result) = results.next().await {
+            match result {
+                Ok((package_name, dist)) => {
+                    resolution.insert(package_name, dist);
+                }
+                Err(ResolveError::Offline(package_names)) => missing.extend(package_names),
+                Err(err) => return Err(err),
+            }
+        }
+        if !missing.is_empty() {
+            missing.sort_unstable();
+            return Err(ResolveError::Offline(missing));
+        }
 
         if let Some(reporter) = self.reporter.as_ref() {
             reporter.on_complete();
@@ -133,16 +159,8 @@ impl<'a> DistFinder<'a> {
         index: &IndexUrl,
         flat_index: Option<&FlatDistributions>,
     ) -> Option<Dist> {
-        let no_binary = match self.no_binary {
-            NoBinary::None => false,
-            NoBinary::All => true,
-            NoBinary::Packages(packages) => packages.contains(&requirement.name),
-        };
-        let no_build = match self.no_build {
-            NoBuild::None => false,
-            NoBuild::All => true,
-            NoBuild::Packages(packages) => packages.contains(&requirement.name),
-        };
+        let no_binary = self.no_binary.no_binary_package(&requirement.name);
+        let no_build = self.no_build.no_build_package(&requirement.name);
 
         // Prioritize the flat index by initializing the "best" matches with its entries.
         let matching_override = if let Some(flat_index) = flat_index {
@@ -0,0 +1,393 @@
+//! Forking a universal resolution on requirements that conflict under disjoint markers.
+//!
+//! A universal resolution can't satisfy, e.g., both `foo==1 ; sys_platform == 'linux'` and
+//! `foo==2 ; sys_platform == 'win32'` with a single pin. Instead, the resolver forks: it solves
+//! once with `sys_platform == 'linux'`, once with `sys_platform == 'win32'`, and once for the
+//! remaining platforms, ignoring any requirements whose markers can't hold within each fork.
+
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use pubgrub::range::Range;
+
+use pep440_rs::Version;
+use pep508_rs::{
+    MarkerEnvironment, MarkerExpression, MarkerOperator, MarkerTree, MarkerValue, Requirement,
+    VersionOrUrl,
+};
+use uv_normalize::{ExtraName, PackageName};
+
+use crate::overrides::Overrides;
+use crate::pubgrub::{is_relevant, PubGrubSpecifier};
+use crate::resolution::strip_extras;
+
+/// The maximum number of environments to evaluate when determining whether a set of markers can
+/// hold at once. Beyond this, the markers are assumed to be satisfiable.
+const MAX_ENVIRONMENTS: usize = 4096;
+
+/// The markers under which a fork of a universal resolution applies, as a conjunction.
+///
+/// The root of a resolution has no markers, and applies to every environment.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct ForkMarkers(Vec<MarkerTree>);
+
+impl ForkMarkers {
+    /// Returns `true` if the given marker can hold within the fork.
+    pub(crate) fn is_compatible(
+        &self,
+        marker: Option<&MarkerTree>,
+        env: &MarkerEnvironment,
+        extras: &[ExtraName],
+    ) -> bool {
+        let Some(marker) = marker else {
+            return true;
+        };
+        if self.0.is_empty() {
+            return true;
+        }
+        is_satisfiable(
+            &self
+                .0
+                .iter()
+                .chain(std::iter::once(marker))
+                .collect::<Vec<_>>(),
+            env,
+            extras,
+        )
+    }
+
+    /// Returns `true` if the given marker is one of the markers that define the fork, such that it
+    /// holds in every environment to which the fork applies.
+    pub(crate) fn contains(&self, marker: &MarkerTree) -> bool {
+        self.0.contains(marker)
+    }
+
+    /// Split the fork into one fork per marker, plus a fork for the environments in which none of
+    /// the markers hold. Forks that can't apply to any environment are omitted.
+    ///
+    /// Returns `None` if the remaining environments can't be expressed as a marker (e.g., one of
+    /// the markers uses `~=`).
+    pub(crate) fn split(
+        &self,
+        markers: &[MarkerTree],
+        env: &MarkerEnvironment,
+    ) -> Option<Vec<Self>> {
+        let complement = markers.iter().map(negate).collect::<Option<Vec<_>>>()?;
+        let forks = markers
+            .iter()
+            .cloned()
+            .map(|marker| self.and(marker))
+            .chain(std::iter::once(self.and(MarkerTree::And(complement))))
+            .filter(|fork| is_satisfiable(&fork.0.iter().collect::<Vec<_>>(), env, &[]))
+            .collect();
+        Some(forks)
+    }
+
+    /// Return the markers that define the fork as a single [`MarkerTree`], or `None` for the root
+    /// of the resolution.
+    pub(crate) fn to_marker(&self) -> Option<MarkerTree> {
+        match self.0.as_slice() {
+            [] => None,
+            [marker] => Some(marker.clone()),
+            markers => Some(MarkerTree::And(markers.to_vec())),
+        }
+    }
+
+    /// Narrow the fork to the environments in which the given marker also holds.
+    fn and(&self, marker: MarkerTree) -> Self {
+        let mut markers = self.0.clone();
+        let additional = match marker {
+            MarkerTree::And(markers) => markers,
+            marker => vec![marker],
+        };
+        for marker in additional {
+            if !markers.contains(&marker) {
+                markers.push(marker);
+            }
+        }
+        Self(markers)
+    }
+}
+
+/// Find a package that's required with disjoint specifiers (or distinct URLs) under markers that
+/// can't hold at once, returning the markers on which to fork the resolution.
+pub(crate) fn find_conflict(
+    requirements: &[Requirement],
+    overrides: &Overrides,
+    extra: Option<&ExtraName>,
+    env: &MarkerEnvironment,
+    fork: &ForkMarkers,
+) -> Option<(PackageName, Vec<MarkerTree>)> {
+    let requirements = overrides
+        .apply(requirements)
+        .filter(|requirement| is_relevant(requirement, extra, env, Some(fork)))
+        .collect::<Vec<_>>();
+
+    for (left, right) in requirements.iter().tuple_combinations() {
+        if left.name != right.name || !is_disjoint(left, right) {
+            continue;
+        }
+
+        // Requirements that apply in every environment conflict outright; leave it to the solver
+        // to report them.
+        let (Some(left_marker), Some(right_marker)) = (
+            left.marker.as_ref().and_then(strip_extras),
+            right.marker.as_ref().and_then(strip_extras),
+        ) else {
+            continue;
+        };
+        if fork.contains(&left_marker) || fork.contains(&right_marker) {
+            continue;
+        }
+
+        if !is_satisfiable(
+            &fork
+                .0
+                .iter()
+                .chain([&left_marker, &right_marker])
+                .collect::<Vec<_>>(),
+            env,
+            &[],
+        ) {
+            return Some((left.name.clone(), vec![left_marker, right_marker]));
+        }
+    }
+
+    None
+}
+
+/// Returns `true` if no single distribution could satisfy both requirements.
+fn is_disjoint(left: &Requirement, right: &Requirement) -> bool {
+    match (&left.version_or_url, &right.version_or_url) {
+        (
+            Some(VersionOrUrl::VersionSpecifier(left)),
+            Some(VersionOrUrl::VersionSpecifier(right)),
+        ) => {
+            let to_range = |specifiers: &pep440_rs::VersionSpecifiers| {
+                specifiers
+                    .iter()
+                    .map(PubGrubSpecifier::try_from)
+                    .fold_ok(Range::<Version>::full(), |range, specifier| {
+                        range.intersection(&specifier.into())
+                    })
+                    .ok()
+            };
+            let (Some(left), Some(right)) = (to_range(left), to_range(right)) else {
+                return false;
+            };
+            left.intersection(&right) == Range::empty()
+        }
+        (Some(VersionOrUrl::Url(left)), Some(VersionOrUrl::Url(right))) => left != right,
+        _ => false,
+    }
+}
+
+/// Returns `true` if there's an environment in which all of the given markers hold.
+///
+/// As for any universal resolution, the Python version and extras are taken from the target
+/// environment. The string markers (e.g., `sys_platform`) are varied over every value that the
+/// markers compare them against with `==` or `!=`, plus one value that matches none of them. Any
+/// other comparison on a string marker (e.g., `'linux' in sys_platform`) is assumed to be
+/// satisfiable.
+fn is_satisfiable(markers: &[&MarkerTree], env: &MarkerEnvironment, extras: &[ExtraName]) -> bool {
+    let mut values: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for marker in markers {
+        if !collect_values(marker, &mut values) {
+            return true;
+        }
+    }
+
+    let combinations = values
+        .values()
+        .try_fold(1usize, |count, values| count.checked_mul(values.len() + 1));
+    if combinations.map_or(true, |count| count > MAX_ENVIRONMENTS) {
+        return true;
+    }
+
+    values
+        .iter()
+        .map(|(key, values)| {
+            values
+                .iter()
+                .map(|value| Some(value.as_str()))
+                .chain(std::iter::once(None))
+                .map(move |value| (key.as_str(), value))
+        })
+        .multi_cartesian_product()
+        .any(|assignment| {
+            let mut env = env.clone();
+            for (key, value) in assignment {
+                // A value that doesn't match any of the values in the markers.
+                let value = value.map_or_else(|| "\0".to_string(), ToString::to_string);
+                set_string(&mut env, key, value);
+            }
+            markers.iter().all(|marker| marker.evaluate(&env, extras))
+        })
+        // With no string markers to vary, evaluate against the target environment as-is.
+        || (values.is_empty() && markers.iter().all(|marker| marker.evaluate(env, extras)))
+}
+
+/// Collect the values that each string marker is compared against with `==` or `!=`.
+///
+/// Returns `false` if a string marker is compared in any other way.
+fn collect_values(marker: &MarkerTree, values: &mut BTreeMap<String, Vec<String>>) -> bool {
+    match marker {
+        MarkerTree::Expression(MarkerExpression {
+            l_value,
+            operator,
+            r_value,
+        }) => match (l_value, r_value) {
+            (MarkerValue::MarkerEnvString(key), MarkerValue::QuotedString(value))
+            | (MarkerValue::QuotedString(value), MarkerValue::MarkerEnvString(key)) => {
+                if !matches!(operator, MarkerOperator::Equal | MarkerOperator::NotEqual) {
+                    return false;
+                }
+                let values = values.entry(key.to_string()).or_default();
+                if !values.contains(value) {
+                    values.push(value.clone());
+                }
+                true
+            }
+            (MarkerValue::MarkerEnvString(_), _) | (_, MarkerValue::MarkerEnvString(_)) => false,
+            _ => true,
+        },
+        MarkerTree::And(markers) | MarkerTree::Or(markers) => {
+            markers.iter().all(|marker| collect_values(marker, values))
+        }
+    }
+}
+
+/// Set the value of the string marker with the given (normalized) name.
+fn set_string(env: &mut MarkerEnvironment, key: &str, value: String) {
+    match key {
+        "implementation_name" => env.implementation_name = value,
+        "os_name" => env.os_name = value,
+        "platform_machine" => env.platform_machine = value,
+        "platform_python_implementation" => env.platform_python_implementation = value,
+        "platform_release" => env.platform_release = value,
+        "platform_system" => env.platform_system = value,
+        "platform_version" => env.platform_version = value,
+        "sys_platform" => env.sys_platform = value,
+        _ => {}
+    }
+}
+
+/// Negate a marker, such that it holds in exactly the environments in which the given marker
+/// doesn't.
+///
+/// Returns `None` if the negation can't be expressed as a marker (i.e., for `~=`).
+fn negate(marker: &MarkerTree) -> Option<MarkerTree> {
+    match marker {
+        MarkerTree::Expression(expression) => {
+            let operator = match expression.operator {
+                MarkerOperator::Equal => MarkerOperator::NotEqual,
+                MarkerOperator::NotEqual => MarkerOperator::Equal,
+                MarkerOperator::GreaterThan => MarkerOperator::LessEqual,
+                MarkerOperator::GreaterEqual => MarkerOperator::LessThan,
+                MarkerOperator::LessThan => MarkerOperator::GreaterEqual,
+                MarkerOperator::LessEqual => MarkerOperator::GreaterThan,
+                MarkerOperator::In => MarkerOperator::NotIn,
+                MarkerOperator::NotIn => MarkerOperator::In,
+                MarkerOperator::TildeEqual => return None,
+            };
+            Some(MarkerTree::Expression(MarkerExpression {
+                l_value: expression.l_value.clone(),
+                operator,
+                r_value: expression.r_value.clone(),
+            }))
+        }
+        MarkerTree::And(markers) => markers
+            .iter()
+            .map(negate)
+            .collect::<Option<Vec<_>>>()
+            .map(MarkerTree::Or),
+        MarkerTree::Or(markers) => markers
+            .iter()
+            .map(negate)
+            .collect::<Option<Vec<_>>>()
+            .map(MarkerTree::And),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep508_rs::{MarkerEnvironment, MarkerTree, StringVersion};
+
+    use super::{negate, ForkMarkers};
+
+    fn env() -> MarkerEnvironment {
+        MarkerEnvironment {
+            implementation_name: "cpython".to_string(),
+            implementation_version: StringVersion::from_str("3.12.0").unwrap(),
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            platform_python_implementation: "CPython".to_string(),
+            platform_release: String::new(),
+            platform_system: "Linux".to_string(),
+            platform_version: String::new(),
+            python_full_version: StringVersion::from_str("3.12.0").unwrap(),
+            python_version: StringVersion::from_str("3.12").unwrap(),
+            sys_platform: "linux".to_string(),
+        }
+    }
+
+    fn marker(marker: &str) -> MarkerTree {
+        MarkerTree::from_str(marker).unwrap()
+    }
+
+    #[test]
+    fn split() {
+        let forks = ForkMarkers::default()
+            .split(
+                &[
+                    marker("sys_platform == 'linux'"),
+                    marker("sys_platform == 'win32'"),
+                ],
+                &env(),
+            )
+            .unwrap()
+            .iter()
+            .map(|fork| fork.to_marker().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            forks,
+            [
+                "sys_platform == 'linux'",
+                "sys_platform == 'win32'",
+                "sys_platform != 'linux' and sys_platform != 'win32'",
+            ]
+        );
+    }
+
+    #[test]
+    fn compatible() {
+        let linux = ForkMarkers::default().and(marker("sys_platform == 'linux'"));
+        assert!(linux.is_compatible(Some(&marker("os_name == 'posix'")), &env(), &[]));
+        assert!(linux.is_compatible(
+            Some(&marker(
+                "sys_platform == 'linux' or sys_platform == 'darwin'"
+            )),
+            &env(),
+            &[]
+        ));
+        assert!(!linux.is_compatible(Some(&marker("sys_platform == 'win32'")), &env(), &[]));
+
+        // The Python version is fixed by the target environment.
+        assert!(!linux.is_compatible(Some(&marker("python_version < '3.8'")), &env(), &[]));
+    }
+
+    #[test]
+    fn negate_marker() {
+        let negated = negate(&marker(
+            "sys_platform == 'linux' and (python_version < '3.9' or 'arm' in platform_machine)",
+        ))
+        .unwrap();
+        assert_eq!(
+            negated.to_string(),
+            "sys_platform != 'linux' or (python_version >= '3.9' and 'arm' not in platform_machine)"
+        );
+        assert!(negate(&marker("python_version ~= '3.8'")).is_none());
+    }
+}
@@ -0,0 +1,153 @@
+//! Machine-readable representations of a [`ResolutionGraph`], for consumption by other tools.
+
+use petgraph::Direction;
+use serde::Serialize;
+use url::Url;
+
+use distribution_types::{Dist, FileLocation, Name, SourceDist, VersionOrUrl};
+use pep440_rs::Version;
+use pypi_types::{base_url_join_relative, Hashes, JoinRelativeError};
+use uv_normalize::{ExtraName, PackageName};
+
+use crate::ResolutionGraph;
+
+/// The version of the exported resolution format.
+const EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GraphExportError {
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Toml(#[from] toml::ser::Error),
+
+    #[error(transparent)]
+    JoinRelative(#[from] JoinRelativeError),
+
+    #[error("Failed to parse URL: {0}")]
+    Url(String, #[source] url::ParseError),
+
+    #[error("Invalid file path: {0}")]
+    Path(String),
+}
+
+/// A resolved dependency graph, in a form that can be serialized to JSON or TOML.
+#[derive(Debug, Serialize)]
+pub struct GraphExport {
+    version: u32,
+    packages: Vec<GraphExportPackage>,
+}
+
+/// A single pinned package in the resolution, alongside its direct dependencies.
+#[derive(Debug, Serialize)]
+struct GraphExportPackage {
+    name: PackageName,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<Version>,
+    /// The URL from which the distribution is fetched (a `file://` URL for local distributions).
+    source: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    editable: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    extras: Vec<ExtraName>,
+    /// The environment markers under which the package is required, if it's not required in
+    /// every environment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    marker: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<String>,
+    /// The names of the packages that this package depends on.
+    dependencies: Vec<PackageName>,
+}
+
+impl GraphExport {
+    /// Create a [`GraphExport`] from a [`ResolutionGraph`], omitting any of the given packages.
+    pub fn from_resolution(
+        resolution: &ResolutionGraph,
+        no_emit_packages: &[PackageName],
+        include_hashes: bool,
+        include_extras: bool,
+    ) -> Result<Self, GraphExportError> {
+        let petgraph = resolution.petgraph();
+        let mut packages = petgraph
+            .node_indices()
+            .filter(|index| !no_emit_packages.contains(petgraph[*index].name()))
+            .map(|index| {
+                let dist = &petgraph[index];
+                let mut dependencies = petgraph
+                    .neighbors_directed(index, Direction::Outgoing)
+                    .map(|dependency| petgraph[dependency].name().clone())
+                    .filter(|name| !no_emit_packages.contains(name))
+                    .collect::<Vec<_>>();
+                dependencies.sort_unstable();
+                dependencies.dedup();
+
+                let mut extras = if include_extras {
+                    resolution.extras(dist.name()).to_vec()
+                } else {
+                    Vec::new()
+                };
+                extras.sort_unstable();
+                extras.dedup();
+
+                let hashes = if include_hashes {
+                    resolution
+                        .hashes(dist.name())
+                        .iter()
+                        .filter_map(Hashes::to_string)
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+
+                Ok(GraphExportPackage {
+                    name: dist.name().clone(),
+                    version: dist.version().cloned(),
+                    source: source(dist)?,
+                    editable: matches!(dist, Dist::Source(SourceDist::Path(sdist)) if sdist.editable),
+                    extras,
+                    marker: resolution.marker(dist.name()).map(ToString::to_string),
+                    hashes,
+                    dependencies,
+                })
+            })
+            .collect::<Result<Vec<_>, GraphExportError>>()?;
+        packages.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(Self {
+            version: EXPORT_VERSION,
+            packages,
+        })
+    }
+
+    /// Serialize the [`GraphExport`] to JSON.
+    pub fn to_json(&self) -> Result<String, GraphExportError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Serialize the [`GraphExport`] to TOML.
+    pub fn to_toml(&self) -> Result<String, GraphExportError> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+}
+
+/// Return the URL from which a distribution is fetched.
+fn source(dist: &Dist) -> Result<String, GraphExportError> {
+    if let Some(file) = dist.file() {
+        let url = match &file.url {
+            FileLocation::RelativeUrl(base, url) => base_url_join_relative(base, url)?,
+            FileLocation::AbsoluteUrl(url) => {
+                Url::parse(url).map_err(|err| GraphExportError::Url(url.clone(), err))?
+            }
+            FileLocation::Path(path) => Url::from_file_path(path)
+                .map_err(|()| GraphExportError::Path(path.to_string_lossy().to_string()))?,
+        };
+        return Ok(url.to_string());
+    }
+
+    let VersionOrUrl::Url(url) = dist.version_or_url() else {
+        unreachable!("Registry distributions should have a file");
+    };
+    Ok(url.to_url().to_string())
+}
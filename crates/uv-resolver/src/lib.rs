@@ -1,12 +1,17 @@
 pub use dependency_mode::DependencyMode;
-pub use error::ResolveError;
+pub use error::{NoSolutionError, ResolveError};
 pub use finder::{DistFinder, Reporter as FinderReporter};
+pub use graph_export::{GraphExport, GraphExportError};
+pub use lock::{Lock, LockError, LockedArtifact, LockedDistribution, LockedRequirements};
 pub use manifest::Manifest;
 pub use options::{Options, OptionsBuilder};
 pub use preferences::{Preference, PreferenceError};
 pub use prerelease_mode::PreReleaseMode;
+pub use pylock::{is_pylock_path, PyLock, PyLockError};
 pub use python_requirement::PythonRequirement;
-pub use resolution::{AnnotationStyle, Diagnostic, DisplayResolutionGraph, ResolutionGraph};
+pub use resolution::{
+    AnnotationStyle, Diagnostic, DisplayResolutionGraph, DisplayResolutionMatrix, ResolutionGraph,
+};
 pub use resolution_mode::ResolutionMode;
 pub use resolver::{
     BuildId, DefaultResolverProvider, InMemoryIndex, PackageVersionsResult,
@@ -14,6 +19,7 @@ pub use resolver::{
     WheelMetadataResult,
 };
 pub use version_map::VersionMap;
+pub use yanks::AllowYanked;
 
 mod bare;
 mod candidate_selector;
@@ -22,6 +28,9 @@ mod dependency_mode;
 mod editables;
 mod error;
 mod finder;
+mod fork;
+mod graph_export;
+mod lock;
 mod manifest;
 mod options;
 mod overrides;
@@ -29,6 +38,7 @@ mod pins;
 mod preferences;
 mod prerelease_mode;
 mod pubgrub;
+mod pylock;
 mod python_requirement;
 mod redirect;
 mod resolution;
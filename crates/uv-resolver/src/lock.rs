@@ -0,0 +1,674 @@
+//! Support for the `uv.lock` project lockfile.
+//!
+//! Unlike a `requirements.txt` file, the lockfile captures a universal resolution: every
+//! distribution is recorded alongside the environment markers under which it's required, such
+//! that a single lockfile can be installed on any supported platform.
+
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+
+use itertools::Itertools;
+use petgraph::Direction;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use distribution_types::{BuiltDist, Dist, File, FileLocation, Name, SourceDist, VersionOrUrl};
+use pep440_rs::Version;
+use pep508_rs::{MarkerEnvironment, MarkerTree, Requirement};
+use pypi_types::{base_url_join_relative, HashError, Hashes, JoinRelativeError};
+use uv_normalize::PackageName;
+
+use crate::preferences::Preference;
+use crate::ResolutionGraph;
+
+/// The version of the `uv.lock` format written by uv.
+const LOCK_VERSION: u32 = 1;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LockError {
+    #[error(transparent)]
+    Serialize(#[from] toml::ser::Error),
+
+    #[error(transparent)]
+    Deserialize(#[from] toml::de::Error),
+
+    #[error("Unsupported `uv.lock` version: {0} (expected {LOCK_VERSION})")]
+    UnsupportedVersion(u32),
+
+    #[error("Invalid locked requirement for `{0}`")]
+    Requirement(PackageName, #[source] Box<pep508_rs::Pep508Error>),
+
+    #[error("Invalid marker for `{0}`")]
+    Marker(PackageName, #[source] Box<pep508_rs::Pep508Error>),
+
+    #[error("Locked distribution `{0}` is missing a version")]
+    MissingVersion(PackageName),
+
+    #[error("Multiple locked distributions for `{0}` apply to the current environment")]
+    Ambiguous(PackageName),
+
+    #[error(transparent)]
+    Hash(#[from] HashError),
+
+    #[error(transparent)]
+    JoinRelative(#[from] JoinRelativeError),
+}
+
+/// A `uv.lock` lockfile.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Lock {
+    version: u32,
+    #[serde(rename = "distribution", default)]
+    distributions: Vec<LockedDistribution>,
+}
+
+/// A distribution pinned in the lockfile.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LockedDistribution {
+    name: PackageName,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<Version>,
+    source: Source,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    marker: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    artifact: Option<LockedArtifact>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dependencies: Vec<PackageName>,
+}
+
+/// The wheel or source distribution that was selected for a registry distribution.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LockedArtifact {
+    filename: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<String>,
+}
+
+/// The source of a locked distribution, serialized as `<kind>+<location>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Source {
+    /// A distribution from a registry, e.g., `registry+https://pypi.org/simple`.
+    Registry(String),
+    /// A distribution at a direct URL, including Git and local file URLs, e.g.,
+    /// `direct+git+https://github.com/pallets/flask.git@<commit>`.
+    Direct(String),
+    /// An editable distribution in a local directory, e.g., `editable+/home/ferris/flask`.
+    Editable(String),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Registry(index) => write!(f, "registry+{index}"),
+            Self::Direct(url) => write!(f, "direct+{url}"),
+            Self::Editable(path) => write!(f, "editable+{path}"),
+        }
+    }
+}
+
+impl FromStr for Source {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('+') {
+            Some(("registry", index)) => Ok(Self::Registry(index.to_string())),
+            Some(("direct", url)) => Ok(Self::Direct(url.to_string())),
+            Some(("editable", path)) => Ok(Self::Editable(path.to_string())),
+            _ => Err(format!("Invalid source: `{s}`")),
+        }
+    }
+}
+
+impl Serialize for Source {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Source {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// The requirements to install from a [`Lock`] into a given environment.
+#[derive(Debug, Default)]
+pub struct LockedRequirements {
+    /// The pinned, non-editable requirements, along with the markers under which they're
+    /// required (e.g., `colorama==0.4.6 ; sys_platform == 'win32'`).
+    pub requirements: Vec<Requirement>,
+    /// The locked hashes for each pinned requirement, as `{algorithm}:{digest}`.
+    pub hashes: FxHashMap<PackageName, Vec<String>>,
+    /// The paths to any editable requirements.
+    pub editables: Vec<String>,
+}
+
+impl Lock {
+    /// Create a [`Lock`] from a universal [`ResolutionGraph`].
+    pub fn from_resolution(resolution: &ResolutionGraph) -> Result<Self, LockError> {
+        let graph = resolution.petgraph();
+        let mut distributions = graph
+            .node_indices()
+            .map(|index| -> Result<_, LockError> {
+                let dist = &graph[index];
+                let mut dependencies = graph
+                    .neighbors_directed(index, Direction::Outgoing)
+                    .map(|dependency| graph[dependency].name().clone())
+                    .collect::<Vec<_>>();
+                dependencies.sort_unstable();
+                dependencies.dedup();
+
+                let mut hashes = resolution
+                    .hashes(dist.name())
+                    .iter()
+                    .filter_map(Hashes::to_string)
+                    .collect::<Vec<_>>();
+                hashes.sort_unstable();
+                hashes.dedup();
+
+                let artifact = match dist {
+                    Dist::Built(BuiltDist::Registry(wheel)) => {
+                        Some(LockedArtifact::from_file(&wheel.file)?)
+                    }
+                    Dist::Source(SourceDist::Registry(sdist)) => {
+                        Some(LockedArtifact::from_file(&sdist.file)?)
+                    }
+                    _ => None,
+                };
+
+                Ok(LockedDistribution {
+                    name: dist.name().clone(),
+                    version: dist.version().cloned(),
+                    source: Source::from_dist(dist),
+                    marker: resolution.marker(dist.name()).map(ToString::to_string),
+                    hashes,
+                    artifact,
+                    dependencies,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        distributions.sort_unstable_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+
+        Ok(Self {
+            version: LOCK_VERSION,
+            distributions,
+        })
+    }
+
+    /// Create a [`Lock`] from the forks of a universal resolution, along with the markers under
+    /// which each fork applies.
+    ///
+    /// Distributions that are locked identically by every fork are recorded as-is, while any others
+    /// are qualified with the markers of the forks that include them, such that, e.g., a package
+    /// can be locked at one version on Linux and another on Windows.
+    pub fn from_resolutions(
+        resolutions: &[(Option<MarkerTree>, ResolutionGraph)],
+    ) -> Result<Self, LockError> {
+        // For each distribution, its markers within each fork that includes it, along with the
+        // markers of the fork itself.
+        let mut merged: Vec<(LockedDistribution, Vec<(Option<String>, Option<String>)>)> =
+            Vec::new();
+        for (fork, resolution) in resolutions {
+            for mut distribution in Self::from_resolution(resolution)?.distributions {
+                let markers = (
+                    distribution.marker.take(),
+                    fork.as_ref().map(ToString::to_string),
+                );
+                if let Some((existing, existing_markers)) =
+                    merged.iter_mut().find(|(existing, _)| {
+                        existing.name == distribution.name
+                            && existing.version == distribution.version
+                            && existing.source == distribution.source
+                    })
+                {
+                    existing.dependencies.extend(distribution.dependencies);
+                    existing.dependencies.sort_unstable();
+                    existing.dependencies.dedup();
+                    existing_markers.push(markers);
+                } else {
+                    merged.push((distribution, vec![markers]));
+                }
+            }
+        }
+
+        let mut distributions = merged
+            .into_iter()
+            .map(|(mut distribution, markers)| {
+                distribution.marker = if markers.len() == resolutions.len()
+                    && markers.iter().all(|(marker, _)| *marker == markers[0].0)
+                {
+                    markers.into_iter().next().and_then(|(marker, _)| marker)
+                } else {
+                    let disjunction = markers
+                        .into_iter()
+                        .map(|markers| match markers {
+                            (Some(marker), Some(fork)) => {
+                                Some(format!("{} and {}", group(&marker), group(&fork)))
+                            }
+                            (Some(marker), None) | (None, Some(marker)) => Some(marker),
+                            (None, None) => None,
+                        })
+                        .collect::<Option<Vec<_>>>();
+                    disjunction.map(|disjunction| match disjunction.as_slice() {
+                        [marker] => marker.clone(),
+                        _ => disjunction.iter().map(|marker| group(marker)).join(" or "),
+                    })
+                };
+                distribution
+            })
+            .collect::<Vec<_>>();
+        distributions.sort_unstable_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+
+        Ok(Self {
+            version: LOCK_VERSION,
+            distributions,
+        })
+    }
+
+    /// Parse a [`Lock`] from the contents of a `uv.lock` file.
+    pub fn from_toml(contents: &str) -> Result<Self, LockError> {
+        let lock: Self = toml::from_str(contents)?;
+        if lock.version != LOCK_VERSION {
+            return Err(LockError::UnsupportedVersion(lock.version));
+        }
+        Ok(lock)
+    }
+
+    /// Serialize the [`Lock`] to TOML.
+    pub fn to_toml(&self) -> Result<String, LockError> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    /// Return the number of distributions in the lockfile.
+    pub fn len(&self) -> usize {
+        self.distributions.len()
+    }
+
+    /// Return `true` if the lockfile doesn't contain any distributions.
+    pub fn is_empty(&self) -> bool {
+        self.distributions.is_empty()
+    }
+
+    /// Return the distributions in the lockfile, sorted by name.
+    pub fn distributions(&self) -> &[LockedDistribution] {
+        &self.distributions
+    }
+
+    /// Return the pinned registry distributions in the lockfile as resolver [`Preference`]s, such
+    /// that re-locking retains the existing versions where possible.
+    pub fn to_preferences(&self) -> Result<Vec<Preference>, LockError> {
+        self.distributions
+            .iter()
+            .filter(|distribution| matches!(distribution.source, Source::Registry(_)))
+            .filter_map(|distribution| Some((distribution, distribution.version.as_ref()?)))
+            .map(|(distribution, version)| {
+                let requirement = match &distribution.marker {
+                    Some(marker) => format!("{}=={version} ; {marker}", distribution.name),
+                    None => format!("{}=={version}", distribution.name),
+                };
+                let requirement = Requirement::from_str(&requirement).map_err(|err| {
+                    LockError::Requirement(distribution.name.clone(), Box::new(err))
+                })?;
+                let hashes = distribution
+                    .hashes
+                    .iter()
+                    .map(String::as_str)
+                    .map(Hashes::from_str)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Preference::from_lock(requirement, hashes))
+            })
+            .collect()
+    }
+
+    /// Return the requirements that should be installed into an environment with the given
+    /// markers, i.e., every distribution whose markers are satisfied by the environment.
+    ///
+    /// A package may be locked more than once under disjoint markers (e.g., one version for
+    /// Windows and another for Linux), but at most one of its distributions may apply to any
+    /// given environment.
+    pub fn to_requirements(
+        &self,
+        markers: &MarkerEnvironment,
+    ) -> Result<LockedRequirements, LockError> {
+        let mut locked = LockedRequirements::default();
+        let mut seen = FxHashSet::default();
+        for distribution in &self.distributions {
+            if let Some(marker) = &distribution.marker {
+                let marker = MarkerTree::from_str(marker)
+                    .map_err(|err| LockError::Marker(distribution.name.clone(), Box::new(err)))?;
+                if !marker.evaluate(markers, &[]) {
+                    continue;
+                }
+            }
+
+            if !seen.insert(&distribution.name) {
+                return Err(LockError::Ambiguous(distribution.name.clone()));
+            }
+
+            let requirement = match &distribution.source {
+                Source::Registry(_) => {
+                    let Some(version) = &distribution.version else {
+                        return Err(LockError::MissingVersion(distribution.name.clone()));
+                    };
+                    format!("{}=={version}", distribution.name)
+                }
+                Source::Direct(url) => format!("{} @ {url}", distribution.name),
+                Source::Editable(path) => {
+                    locked.editables.push(path.clone());
+                    continue;
+                }
+            };
+            let requirement = match &distribution.marker {
+                Some(marker) => format!("{requirement} ; {marker}"),
+                None => requirement,
+            };
+            if !distribution.hashes.is_empty() {
+                locked
+                    .hashes
+                    .insert(distribution.name.clone(), distribution.hashes.clone());
+            }
+            locked.requirements.push(
+                Requirement::from_str(&requirement).map_err(|err| {
+                    LockError::Requirement(distribution.name.clone(), Box::new(err))
+                })?,
+            );
+        }
+        Ok(locked)
+    }
+}
+
+impl LockedDistribution {
+    /// Return the name of the distribution.
+    pub fn name(&self) -> &PackageName {
+        &self.name
+    }
+
+    /// Return the pinned version of the distribution, if known.
+    pub fn version(&self) -> Option<&Version> {
+        self.version.as_ref()
+    }
+
+    /// Return the environment markers under which the distribution is required, if any.
+    pub fn marker(&self) -> Option<&str> {
+        self.marker.as_deref()
+    }
+
+    /// Return the hashes of the distribution's artifacts, as `{algorithm}:{digest}`.
+    pub fn hashes(&self) -> &[String] {
+        &self.hashes
+    }
+
+    /// Return the artifact selected for the distribution, if it's from a registry.
+    ///
+    /// Lockfiles written by older versions of uv omit the artifact.
+    pub fn artifact(&self) -> Option<&LockedArtifact> {
+        self.artifact.as_ref()
+    }
+
+    /// Return the names of the distribution's direct dependencies.
+    pub fn dependencies(&self) -> &[PackageName] {
+        &self.dependencies
+    }
+
+    /// Return `true` if the distribution is installed as editable from a local directory.
+    pub fn is_editable(&self) -> bool {
+        matches!(self.source, Source::Editable(_))
+    }
+
+    /// Return the URL of the index from which the distribution is installed, if any.
+    pub fn index(&self) -> Option<&str> {
+        match &self.source {
+            Source::Registry(index) => Some(index),
+            Source::Direct(_) | Source::Editable(_) => None,
+        }
+    }
+
+    /// Return the direct URL (or, for editables, the path) from which the distribution is
+    /// installed, if any.
+    pub fn url(&self) -> Option<&str> {
+        match &self.source {
+            Source::Registry(_) => None,
+            Source::Direct(url) => Some(url),
+            Source::Editable(path) => Some(path),
+        }
+    }
+}
+
+impl LockedArtifact {
+    fn from_file(file: &File) -> Result<Self, LockError> {
+        let (url, path) = match &file.url {
+            FileLocation::RelativeUrl(base, url) => {
+                (Some(base_url_join_relative(base, url)?.to_string()), None)
+            }
+            FileLocation::AbsoluteUrl(url) => (Some(url.clone()), None),
+            FileLocation::Path(path) => (None, Some(path_to_string(path))),
+        };
+        Ok(Self {
+            filename: file.filename.clone(),
+            url,
+            path,
+            size: file.size,
+            hashes: file.hashes.to_string().into_iter().collect(),
+        })
+    }
+
+    /// Return the filename of the artifact (e.g., `anyio-4.3.0-py3-none-any.whl`).
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// Return the URL of the artifact, if it's hosted remotely.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// Return the path to the artifact, if it's in a local directory (e.g., a `--find-links`
+    /// directory).
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Return the size of the artifact in bytes, if known.
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    /// Return the hashes of the artifact, as `{algorithm}:{digest}`.
+    pub fn hashes(&self) -> &[String] {
+        &self.hashes
+    }
+}
+
+impl Source {
+    fn from_dist(dist: &Dist) -> Self {
+        match dist {
+            Dist::Built(BuiltDist::Registry(wheel)) => Self::Registry(wheel.index.to_string()),
+            Dist::Source(SourceDist::Registry(sdist)) => Self::Registry(sdist.index.to_string()),
+            Dist::Source(SourceDist::Path(sdist)) if sdist.editable => {
+                Self::Editable(path_to_string(&sdist.path))
+            }
+            dist => {
+                let VersionOrUrl::Url(url) = dist.version_or_url() else {
+                    unreachable!("Non-registry distributions should have a URL");
+                };
+                Self::Direct(url.to_url().to_string())
+            }
+        }
+    }
+}
+
+/// Wrap a compound marker (e.g., `os_name == 'nt' or sys_platform == 'win32'`) in parentheses,
+/// for use within a larger marker.
+fn group(marker: &str) -> Cow<'_, str> {
+    if marker.contains(" and ") || marker.contains(" or ") {
+        Cow::Owned(format!("({marker})"))
+    } else {
+        Cow::Borrowed(marker)
+    }
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep508_rs::MarkerEnvironment;
+    use uv_normalize::PackageName;
+
+    use super::Lock;
+
+    fn markers(sys_platform: &str) -> MarkerEnvironment {
+        MarkerEnvironment {
+            implementation_name: "cpython".to_string(),
+            implementation_version: pep508_rs::StringVersion::from_str("3.12.1").unwrap(),
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            platform_python_implementation: "CPython".to_string(),
+            platform_release: String::new(),
+            platform_system: String::new(),
+            platform_version: String::new(),
+            python_full_version: pep508_rs::StringVersion::from_str("3.12.1").unwrap(),
+            python_version: pep508_rs::StringVersion::from_str("3.12").unwrap(),
+            sys_platform: sys_platform.to_string(),
+        }
+    }
+
+    const LOCK: &str = r#"
+version = 1
+
+[[distribution]]
+name = "anyio"
+version = "4.3.0"
+source = "registry+https://pypi.org/simple"
+hashes = ["sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"]
+dependencies = ["idna"]
+
+[[distribution]]
+name = "colorama"
+version = "0.4.6"
+source = "registry+https://pypi.org/simple"
+marker = "sys_platform == 'win32'"
+
+[[distribution]]
+name = "colorama"
+version = "0.4.5"
+source = "registry+https://pypi.org/simple"
+marker = "sys_platform == 'darwin'"
+
+[[distribution]]
+name = "flask"
+source = "editable+/home/ferris/flask"
+
+[[distribution]]
+name = "idna"
+version = "3.6"
+source = "direct+https://files.pythonhosted.org/packages/idna-3.6-py3-none-any.whl"
+"#;
+
+    #[test]
+    fn requirements() {
+        let lock = Lock::from_toml(LOCK).unwrap();
+
+        let linux = lock.to_requirements(&markers("linux")).unwrap();
+        assert_eq!(
+            linux
+                .requirements
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "anyio==4.3.0",
+                "idna @ https://files.pythonhosted.org/packages/idna-3.6-py3-none-any.whl"
+            ]
+        );
+        assert_eq!(linux.editables, vec!["/home/ferris/flask"]);
+        assert_eq!(
+            linux.hashes[&PackageName::from_str("anyio").unwrap()],
+            vec!["sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"]
+        );
+        assert_eq!(linux.hashes.len(), 1);
+
+        let windows = lock.to_requirements(&markers("win32")).unwrap();
+        assert_eq!(windows.requirements.len(), 3);
+        assert_eq!(
+            windows.requirements[1].to_string(),
+            "colorama==0.4.6 ; sys_platform == 'win32'"
+        );
+
+        let macos = lock.to_requirements(&markers("darwin")).unwrap();
+        assert_eq!(
+            macos.requirements[1].to_string(),
+            "colorama==0.4.5 ; sys_platform == 'darwin'"
+        );
+    }
+
+    #[test]
+    fn ambiguous() {
+        let lock = Lock::from_toml(
+            r#"
+version = 1
+
+[[distribution]]
+name = "colorama"
+version = "0.4.6"
+source = "registry+https://pypi.org/simple"
+
+[[distribution]]
+name = "colorama"
+version = "0.4.5"
+source = "registry+https://pypi.org/simple"
+marker = "sys_platform == 'win32'"
+"#,
+        )
+        .unwrap();
+        assert!(lock.to_requirements(&markers("linux")).is_ok());
+        let err = lock.to_requirements(&markers("win32")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Multiple locked distributions for `colorama` apply to the current environment"
+        );
+    }
+
+    #[test]
+    fn preferences() {
+        let lock = Lock::from_toml(LOCK).unwrap();
+        let preferences = lock.to_preferences().unwrap();
+        assert_eq!(preferences.len(), 3);
+        assert_eq!(preferences[0].requirement().to_string(), "anyio==4.3.0");
+        assert_eq!(
+            preferences[1].requirement().to_string(),
+            "colorama==0.4.6 ; sys_platform == 'win32'"
+        );
+        assert_eq!(
+            preferences[2].requirement().to_string(),
+            "colorama==0.4.5 ; sys_platform == 'darwin'"
+        );
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let err = Lock::from_toml("version = 2").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unsupported `uv.lock` version: 2 (expected 1)"
+        );
+    }
+}
@@ -1,14 +1,21 @@
 use chrono::{DateTime, Utc};
+use rustc_hash::FxHashMap;
 
-use crate::{DependencyMode, PreReleaseMode, ResolutionMode};
+use uv_normalize::PackageName;
+
+use crate::{AllowYanked, DependencyMode, PreReleaseMode, ResolutionMode};
 
 /// Options for resolving a manifest.
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct Options {
     pub resolution_mode: ResolutionMode,
     pub prerelease_mode: PreReleaseMode,
     pub dependency_mode: DependencyMode,
     pub exclude_newer: Option<DateTime<Utc>>,
+    pub exclude_newer_package: FxHashMap<PackageName, DateTime<Utc>>,
+    pub allow_yanked: AllowYanked,
+    pub universal: bool,
+    pub preserve_markers: bool,
 }
 
 /// Builder for [`Options`].
@@ -18,6 +25,10 @@ pub struct OptionsBuilder {
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
     exclude_newer: Option<DateTime<Utc>>,
+    exclude_newer_package: FxHashMap<PackageName, DateTime<Utc>>,
+    allow_yanked: AllowYanked,
+    universal: bool,
+    preserve_markers: bool,
 }
 
 impl OptionsBuilder {
@@ -54,6 +65,39 @@ impl OptionsBuilder {
         self
     }
 
+    /// Sets per-package exclusion dates, which take precedence over the global exclusion date.
+    #[must_use]
+    pub fn exclude_newer_package(
+        mut self,
+        exclude_newer_package: FxHashMap<PackageName, DateTime<Utc>>,
+    ) -> Self {
+        self.exclude_newer_package = exclude_newer_package;
+        self
+    }
+
+    /// Sets the packages for which yanked distributions may be selected.
+    #[must_use]
+    pub fn allow_yanked(mut self, allow_yanked: AllowYanked) -> Self {
+        self.allow_yanked = allow_yanked;
+        self
+    }
+
+    /// Sets whether to include the requirements for all platforms, forking the resolution on any
+    /// requirements that conflict under different markers.
+    #[must_use]
+    pub fn universal(mut self, universal: bool) -> Self {
+        self.universal = universal;
+        self
+    }
+
+    /// Sets whether to retain the environment markers under which each package is required, even
+    /// when resolving for the current environment only.
+    #[must_use]
+    pub fn preserve_markers(mut self, preserve_markers: bool) -> Self {
+        self.preserve_markers = preserve_markers;
+        self
+    }
+
     /// Builds the options.
     pub fn build(self) -> Options {
         Options {
@@ -61,6 +105,10 @@ impl OptionsBuilder {
             prerelease_mode: self.prerelease_mode,
             dependency_mode: self.dependency_mode,
             exclude_newer: self.exclude_newer,
+            exclude_newer_package: self.exclude_newer_package,
+            allow_yanked: self.allow_yanked,
+            universal: self.universal,
+            preserve_markers: self.preserve_markers,
         }
     }
 }
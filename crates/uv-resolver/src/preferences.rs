@@ -19,7 +19,7 @@ pub enum PreferenceError {
 }
 
 /// A pinned requirement, as extracted from a `requirements.txt` file.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Preference {
     requirement: Requirement,
     hashes: Vec<Hashes>,
@@ -52,6 +52,14 @@ impl Preference {
         }
     }
 
+    /// Create a [`Preference`] from a [`Requirement`] pinned in a lockfile, along with its hashes.
+    pub(crate) fn from_lock(requirement: Requirement, hashes: Vec<Hashes>) -> Self {
+        Self {
+            requirement,
+            hashes,
+        }
+    }
+
     /// Return the name of the package for this preference.
     pub fn name(&self) -> &PackageName {
         &self.requirement.name
@@ -61,6 +69,11 @@ impl Preference {
     pub fn requirement(&self) -> &Requirement {
         &self.requirement
     }
+
+    /// Return the hashes for this preference, if any.
+    pub fn hashes(&self) -> &[Hashes] {
+        &self.hashes
+    }
 }
 
 /// A set of pinned packages that should be preserved during resolution, if possible.
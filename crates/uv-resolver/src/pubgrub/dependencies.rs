@@ -8,6 +8,7 @@ use pep508_rs::{MarkerEnvironment, Requirement, VersionOrUrl};
 use uv_normalize::{ExtraName, PackageName};
 
 use crate::constraints::Constraints;
+use crate::fork::ForkMarkers;
 use crate::overrides::Overrides;
 use crate::pubgrub::specifier::PubGrubSpecifier;
 use crate::pubgrub::PubGrubPackage;
@@ -29,17 +30,14 @@ impl PubGrubDependencies {
         urls: &Urls,
         locals: &Locals,
         env: &MarkerEnvironment,
+        fork: Option<&ForkMarkers>,
     ) -> Result<Self, ResolveError> {
         let mut dependencies = Vec::default();
 
         // Iterate over all declared requirements.
         for requirement in overrides.apply(requirements) {
             // If the requirement isn't relevant for the current platform, skip it.
-            if let Some(extra) = source_extra {
-                if !requirement.evaluate_markers(env, std::slice::from_ref(extra)) {
-                    continue;
-                }
-            } else if !requirement.evaluate_markers(env, &[]) {
+            if !is_relevant(requirement, source_extra, env, fork) {
                 continue;
             }
 
@@ -69,11 +67,7 @@ impl PubGrubDependencies {
                 // If the requirement was constrained, add those constraints.
                 for constraint in constraints.get(&requirement.name).into_iter().flatten() {
                     // If the requirement isn't relevant for the current platform, skip it.
-                    if let Some(extra) = source_extra {
-                        if !constraint.evaluate_markers(env, std::slice::from_ref(extra)) {
-                            continue;
-                        }
-                    } else if !constraint.evaluate_markers(env, &[]) {
+                    if !is_relevant(constraint, source_extra, env, fork) {
                         continue;
                     }
 
@@ -125,6 +119,31 @@ impl From<PubGrubDependencies> for Vec<(PubGrubPackage, Range<Version>)> {
     }
 }
 
+/// Returns `true` if the [`Requirement`] applies to the given environment, with the given extra
+/// enabled.
+///
+/// In universal mode (i.e., given the markers of a fork), only the extras and the Python version
+/// are considered, such that the requirements for every platform within the fork are included.
+pub(crate) fn is_relevant(
+    requirement: &Requirement,
+    extra: Option<&ExtraName>,
+    env: &MarkerEnvironment,
+    fork: Option<&ForkMarkers>,
+) -> bool {
+    if let Some(fork) = fork {
+        requirement.evaluate_extras_and_python_version(
+            &extra.into_iter().cloned().collect(),
+            std::slice::from_ref(&env.python_version.version),
+        ) && fork.is_compatible(
+            requirement.marker.as_ref(),
+            env,
+            extra.map_or(&[], std::slice::from_ref),
+        )
+    } else {
+        requirement.evaluate_markers(env, extra.map_or(&[], std::slice::from_ref))
+    }
+}
+
 /// Convert a [`Requirement`] to a `PubGrub`-compatible package and range.
 fn to_pubgrub(
     requirement: &Requirement,
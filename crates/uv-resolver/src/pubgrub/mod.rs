@@ -1,8 +1,8 @@
-pub(crate) use crate::pubgrub::dependencies::PubGrubDependencies;
+pub(crate) use crate::pubgrub::dependencies::{is_relevant, PubGrubDependencies};
 pub(crate) use crate::pubgrub::distribution::PubGrubDistribution;
 pub(crate) use crate::pubgrub::package::{PubGrubPackage, PubGrubPython};
 pub(crate) use crate::pubgrub::priority::{PubGrubPriorities, PubGrubPriority};
-pub(crate) use crate::pubgrub::report::PubGrubReportFormatter;
+pub(crate) use crate::pubgrub::report::{DisplayDerivationTree, PubGrubReportFormatter};
 pub(crate) use crate::pubgrub::specifier::PubGrubSpecifier;
 
 mod dependencies;
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::ops::Bound;
@@ -6,13 +7,15 @@ use std::ops::Bound;
 use derivative::Derivative;
 use distribution_types::IndexLocations;
 use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 use pep440_rs::Version;
+use platform_tags::IncompatibleTag;
 use pubgrub::range::Range;
 use pubgrub::report::{DerivationTree, Derived, External, ReportFormatter};
 use pubgrub::term::Term;
 use pubgrub::type_aliases::Map;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use uv_normalize::PackageName;
 
 use crate::candidate_selector::CandidateSelector;
@@ -342,6 +345,7 @@ impl PubGrubReportFormatter<'_> {
         selector: &Option<CandidateSelector>,
         index_locations: &Option<IndexLocations>,
         unavailable_packages: &FxHashMap<PackageName, UnavailablePackage>,
+        incompatible_wheels: &FxHashMap<(PackageName, Version), IncompatibleTag>,
     ) -> IndexSet<PubGrubHint> {
         /// Returns `true` if pre-releases were allowed for a package.
         fn allowed_prerelease(package: &PubGrubPackage, selector: &CandidateSelector) -> bool {
@@ -418,8 +422,27 @@ impl PubGrubReportFormatter<'_> {
                         }
                     }
                 }
+                External::Unavailable(package, set, _) => {
+                    // Check for versions that only publish wheels for other environments.
+                    if let (PubGrubPackage::Package(name, ..), Some(version)) =
+                        (package, set.as_singleton())
+                    {
+                        if let Some(tag) = incompatible_wheels.get(&(name.clone(), version.clone()))
+                        {
+                            if *tag != IncompatibleTag::Invalid {
+                                hints.insert(PubGrubHint::IncompatibleWheels {
+                                    package: package.clone(),
+                                    version: version.clone(),
+                                    tag: tag.clone(),
+                                    python: self.python_requirement.map(|python| {
+                                        python.installed().release().iter().take(2).join(".")
+                                    }),
+                                });
+                            }
+                        }
+                    }
+                }
                 External::NotRoot(..) => {}
-                External::Unavailable(..) => {}
                 External::FromDependencyOf(..) => {}
             },
             DerivationTree::Derived(derived) => {
@@ -428,12 +451,14 @@ impl PubGrubReportFormatter<'_> {
                     selector,
                     index_locations,
                     unavailable_packages,
+                    incompatible_wheels,
                 ));
                 hints.extend(self.hints(
                     &derived.cause2,
                     selector,
                     index_locations,
                     unavailable_packages,
+                    incompatible_wheels,
                 ));
             }
         }
@@ -459,6 +484,16 @@ pub(crate) enum PubGrubHint {
         #[derivative(PartialEq = "ignore", Hash = "ignore")]
         range: Range<Version>,
     },
+    /// A package version has no source distribution, and none of its wheels are compatible with
+    /// the current environment.
+    IncompatibleWheels {
+        package: PubGrubPackage,
+        version: Version,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        tag: IncompatibleTag,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        python: Option<String>,
+    },
     /// Requirements were unavailable due to lookups in the index being disabled and no extra
     /// index was provided via `--find-links`
     NoIndex,
@@ -489,6 +524,30 @@ impl std::fmt::Display for PubGrubHint {
                     PackageRange::compatibility(package, range).bold()
                 )
             }
+            Self::IncompatibleWheels {
+                package,
+                version,
+                tag,
+                python,
+            } => {
+                let environment = match (tag, python) {
+                    (IncompatibleTag::Abi, Some(python)) => format!("Python {python}"),
+                    (IncompatibleTag::Abi, None) => "the current Python version".to_string(),
+                    (IncompatibleTag::Python | IncompatibleTag::Invalid, _) => {
+                        "the current Python implementation".to_string()
+                    }
+                    (IncompatibleTag::Platform, _) => "the current platform".to_string(),
+                };
+                write!(
+                    f,
+                    "{}{} {} has no source distribution, and none of its wheels support {} (try: pinning {} to a version that publishes compatible wheels)",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    format!("{package}=={version}").bold(),
+                    environment,
+                    package.bold(),
+                )
+            }
             Self::NoIndex => {
                 write!(
                     f,
@@ -509,6 +568,62 @@ impl std::fmt::Display for PubGrubHint {
     }
 }
 
+/// A [`std::fmt::Display`] implementation that renders a derivation tree as an indented tree of
+/// incompatibilities, with the final conclusion at the root and the facts it was derived from as
+/// its descendants.
+pub(crate) struct DisplayDerivationTree<'a> {
+    pub(crate) tree: &'a DerivationTree<PubGrubPackage, Range<Version>>,
+    pub(crate) formatter: &'a PubGrubReportFormatter<'a>,
+}
+
+impl DisplayDerivationTree<'_> {
+    /// Write a single node of the tree, followed by its causes.
+    fn write_node(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        tree: &DerivationTree<PubGrubPackage, Range<Version>>,
+        prefix: &str,
+        connector: &str,
+        visited: &RefCell<FxHashSet<usize>>,
+    ) -> std::fmt::Result {
+        match tree {
+            DerivationTree::External(external) => {
+                writeln!(
+                    f,
+                    "{prefix}{connector}{}",
+                    self.formatter.format_external(external)
+                )
+            }
+            DerivationTree::Derived(derived) => {
+                let conclusion = self.formatter.format_terms(&derived.terms);
+
+                // Derived incompatibilities may be shared across branches; only expand them once.
+                if let Some(id) = derived.shared_id {
+                    if !visited.borrow_mut().insert(id) {
+                        return writeln!(f, "{prefix}{connector}{conclusion} (see above)");
+                    }
+                }
+                writeln!(f, "{prefix}{connector}{conclusion}")?;
+
+                let prefix = match connector {
+                    "" => prefix.to_string(),
+                    "└── " => format!("{prefix}    "),
+                    _ => format!("{prefix}│   "),
+                };
+                self.write_node(f, &derived.cause1, &prefix, "├── ", visited)?;
+                self.write_node(f, &derived.cause2, &prefix, "└── ", visited)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DisplayDerivationTree<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let visited = RefCell::new(FxHashSet::default());
+        self.write_node(f, self.tree, "", "", &visited)
+    }
+}
+
 /// A [`Term`] and [`PubGrubPackage`] combination for display.
 struct PackageTerm<'a> {
     package: &'a PubGrubPackage,
@@ -0,0 +1,645 @@
+//! Support for the standardized `pylock.toml` lockfile format.
+//!
+//! See: <https://peps.python.org/pep-0751/>
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use distribution_types::{
+    BuiltDist, DirectUrl, Dist, File, FileLocation, Name, SourceDist, VersionOrUrl,
+};
+use pep440_rs::Version;
+use pep508_rs::Requirement;
+use pypi_types::{base_url_join_relative, HashError, Hashes, JoinRelativeError};
+use uv_normalize::PackageName;
+
+use crate::lock::{Lock, LockedArtifact, LockedDistribution};
+use crate::preferences::Preference;
+use crate::ResolutionGraph;
+
+/// The version of the `pylock.toml` format written by uv.
+const LOCK_VERSION: &str = "1.0";
+
+#[derive(Debug, thiserror::Error)]
+pub enum PyLockError {
+    #[error(transparent)]
+    Serialize(#[from] toml::ser::Error),
+
+    #[error(transparent)]
+    Deserialize(#[from] toml::de::Error),
+
+    #[error("Unsupported `pylock.toml` version: {0} (expected {LOCK_VERSION})")]
+    UnsupportedVersion(String),
+
+    #[error(transparent)]
+    JoinRelative(#[from] JoinRelativeError),
+
+    #[error("Failed to parse URL: {0}")]
+    Url(String, #[source] url::ParseError),
+
+    #[error("Invalid locked requirement for `{0}`")]
+    Requirement(PackageName, #[source] Box<pep508_rs::Pep508Error>),
+
+    #[error(transparent)]
+    Hash(#[from] HashError),
+
+    #[error(transparent)]
+    DirectUrl(#[from] anyhow::Error),
+
+    #[error("Locked distribution `{0}` is missing an artifact; re-run `uv lock` to update it")]
+    MissingArtifact(PackageName),
+
+    #[error("Locked distribution `{0}` is missing a hash")]
+    MissingHash(PackageName),
+}
+
+/// A `pylock.toml` lockfile.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PyLock {
+    lock_version: String,
+    created_by: String,
+    #[serde(default)]
+    packages: Vec<PyLockPackage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PyLockPackage {
+    name: PackageName,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<Version>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    marker: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    index: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    vcs: Option<PyLockVcs>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    directory: Option<PyLockDirectory>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    archive: Option<PyLockArchive>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sdist: Option<PyLockFile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    wheels: Vec<PyLockFile>,
+}
+
+/// A source distribution or wheel from an index.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PyLockFile {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    #[serde(default)]
+    hashes: BTreeMap<String, String>,
+}
+
+/// A distribution at a direct URL or path to an archive.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PyLockArchive {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(default)]
+    hashes: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    subdirectory: Option<PathBuf>,
+}
+
+/// A distribution in a version control repository.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PyLockVcs {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    requested_revision: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    commit_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    subdirectory: Option<PathBuf>,
+}
+
+/// A distribution in a local directory.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PyLockDirectory {
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    editable: Option<bool>,
+}
+
+impl PyLock {
+    /// Create a [`PyLock`] from a [`ResolutionGraph`], omitting any of the given packages.
+    pub fn from_resolution(
+        resolution: &ResolutionGraph,
+        no_emit_packages: &[PackageName],
+    ) -> Result<Self, PyLockError> {
+        let mut packages = resolution
+            .petgraph()
+            .node_weights()
+            .filter(|dist| !no_emit_packages.contains(dist.name()))
+            .map(|dist| PyLockPackage::from_dist(dist, resolution))
+            .collect::<Result<Vec<_>, _>>()?;
+        packages.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(Self {
+            lock_version: LOCK_VERSION.to_string(),
+            created_by: "uv".to_string(),
+            packages,
+        })
+    }
+
+    /// Create a [`PyLock`] from a `uv.lock` [`Lock`].
+    ///
+    /// Registry packages are exported with the artifact that was selected during resolution. If
+    /// `require_hashes` is set, every exported artifact must have a hash.
+    pub fn from_lock(lock: &Lock, require_hashes: bool) -> Result<Self, PyLockError> {
+        let packages = lock
+            .distributions()
+            .iter()
+            .map(PyLockPackage::from_locked)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if require_hashes {
+            if let Some(package) = packages.iter().find(|package| package.is_missing_hashes()) {
+                return Err(PyLockError::MissingHash(package.name.clone()));
+            }
+        }
+
+        Ok(Self {
+            lock_version: LOCK_VERSION.to_string(),
+            created_by: "uv".to_string(),
+            packages,
+        })
+    }
+
+    /// Parse a [`PyLock`] from the contents of a `pylock.toml` file.
+    pub fn from_toml(contents: &str) -> Result<Self, PyLockError> {
+        let lock: Self = toml::from_str(contents)?;
+        if lock.lock_version.split('.').next() != LOCK_VERSION.split('.').next() {
+            return Err(PyLockError::UnsupportedVersion(lock.lock_version));
+        }
+        Ok(lock)
+    }
+
+    /// Serialize the [`PyLock`] to TOML.
+    pub fn to_toml(&self) -> Result<String, PyLockError> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    /// Return the pinned packages in the lockfile as resolver [`Preference`]s.
+    ///
+    /// Only packages pinned to a version from an index are returned, since direct URL and path
+    /// requirements are already fixed by the input requirements.
+    pub fn to_preferences(&self) -> Result<Vec<Preference>, PyLockError> {
+        self.packages
+            .iter()
+            .filter(|package| package.index.is_some())
+            .filter_map(|package| Some((package, package.version.as_ref()?)))
+            .map(|(package, version)| {
+                let requirement = match &package.marker {
+                    Some(marker) => format!("{}=={version} ; {marker}", package.name),
+                    None => format!("{}=={version}", package.name),
+                };
+                let requirement = Requirement::from_str(&requirement)
+                    .map_err(|err| PyLockError::Requirement(package.name.clone(), Box::new(err)))?;
+                let hashes = package
+                    .sdist
+                    .iter()
+                    .chain(&package.wheels)
+                    .flat_map(|file| &file.hashes)
+                    .map(|(algorithm, digest)| Hashes::from_str(&format!("{algorithm}:{digest}")))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Preference::from_lock(requirement, hashes))
+            })
+            .collect()
+    }
+}
+
+impl PyLockPackage {
+    fn from_dist(dist: &Dist, resolution: &ResolutionGraph) -> Result<Self, PyLockError> {
+        let mut package = Self {
+            name: dist.name().clone(),
+            version: dist.version().cloned(),
+            marker: resolution.marker(dist.name()).map(ToString::to_string),
+            index: None,
+            vcs: None,
+            directory: None,
+            archive: None,
+            sdist: None,
+            wheels: Vec::new(),
+        };
+
+        match dist {
+            Dist::Built(BuiltDist::Registry(wheel)) => {
+                package.index = Some(wheel.index.to_string());
+                package.wheels = vec![PyLockFile::from_file(&wheel.file)?];
+            }
+            Dist::Source(SourceDist::Registry(sdist)) => {
+                package.index = Some(sdist.index.to_string());
+                package.sdist = Some(PyLockFile::from_file(&sdist.file)?);
+            }
+            Dist::Built(BuiltDist::DirectUrl(_)) | Dist::Source(SourceDist::DirectUrl(_)) => {
+                let VersionOrUrl::Url(url) = dist.version_or_url() else {
+                    unreachable!("Direct URL distributions should have a URL");
+                };
+                let subdirectory = match DirectUrl::try_from(url.raw())? {
+                    DirectUrl::Archive(archive) => archive.subdirectory,
+                    _ => None,
+                };
+                let mut url = url.to_url();
+                url.set_fragment(None);
+                package.archive = Some(PyLockArchive {
+                    url: Some(url.to_string()),
+                    path: None,
+                    hashes: hashes_to_table(resolution.hashes(dist.name())),
+                    subdirectory,
+                });
+            }
+            Dist::Built(BuiltDist::Path(wheel)) => {
+                package.archive = Some(PyLockArchive {
+                    url: None,
+                    path: Some(path_to_string(&wheel.path)),
+                    hashes: hashes_to_table(resolution.hashes(dist.name())),
+                    subdirectory: None,
+                });
+            }
+            Dist::Source(SourceDist::Path(sdist)) => {
+                if sdist.path.is_dir() {
+                    package.directory = Some(PyLockDirectory {
+                        path: path_to_string(&sdist.path),
+                        editable: sdist.editable.then_some(true),
+                    });
+                } else {
+                    package.archive = Some(PyLockArchive {
+                        url: None,
+                        path: Some(path_to_string(&sdist.path)),
+                        hashes: hashes_to_table(resolution.hashes(dist.name())),
+                        subdirectory: None,
+                    });
+                }
+            }
+            Dist::Source(SourceDist::Git(git)) => {
+                let DirectUrl::Git(git_url) = DirectUrl::try_from(git.url.raw())? else {
+                    unreachable!("Git distributions should have a Git URL");
+                };
+                package.vcs = Some(PyLockVcs {
+                    kind: "git".to_string(),
+                    url: git_url.url.repository().to_string(),
+                    requested_revision: git_url.url.reference().map(ToString::to_string),
+                    commit_id: git_url.url.precise().map(|sha| sha.to_string()),
+                    subdirectory: git_url.subdirectory,
+                });
+            }
+        }
+
+        Ok(package)
+    }
+
+    fn from_locked(distribution: &LockedDistribution) -> Result<Self, PyLockError> {
+        let mut package = Self {
+            name: distribution.name().clone(),
+            version: distribution.version().cloned(),
+            marker: distribution.marker().map(ToString::to_string),
+            index: distribution.index().map(ToString::to_string),
+            vcs: None,
+            directory: None,
+            archive: None,
+            sdist: None,
+            wheels: Vec::new(),
+        };
+
+        if distribution.index().is_some() {
+            let Some(artifact) = distribution.artifact() else {
+                return Err(PyLockError::MissingArtifact(distribution.name().clone()));
+            };
+            let file = PyLockFile::from_artifact(artifact);
+            if artifact.filename().ends_with(".whl") {
+                package.wheels = vec![file];
+            } else {
+                package.sdist = Some(file);
+            }
+            return Ok(package);
+        }
+
+        let Some(url) = distribution.url() else {
+            return Ok(package);
+        };
+
+        if distribution.is_editable() {
+            package.directory = Some(PyLockDirectory {
+                path: url.to_string(),
+                editable: Some(true),
+            });
+            return Ok(package);
+        }
+
+        let url = Url::parse(url).map_err(|err| PyLockError::Url(url.to_string(), err))?;
+        match DirectUrl::try_from(&url)? {
+            DirectUrl::Git(git_url) => {
+                package.vcs = Some(PyLockVcs {
+                    kind: "git".to_string(),
+                    url: git_url.url.repository().to_string(),
+                    requested_revision: git_url.url.reference().map(ToString::to_string),
+                    commit_id: git_url.url.precise().map(|sha| sha.to_string()),
+                    subdirectory: git_url.subdirectory,
+                });
+            }
+            DirectUrl::LocalFile(file) => {
+                let path = file
+                    .url
+                    .to_file_path()
+                    .map_err(|()| anyhow::anyhow!("Invalid file URL: {}", file.url))?;
+                if path.is_dir() {
+                    package.directory = Some(PyLockDirectory {
+                        path: path_to_string(&path),
+                        editable: None,
+                    });
+                } else {
+                    package.archive = Some(PyLockArchive {
+                        url: None,
+                        path: Some(path_to_string(&path)),
+                        hashes: locked_hashes_to_table(distribution.hashes()),
+                        subdirectory: None,
+                    });
+                }
+            }
+            DirectUrl::Archive(archive) => {
+                let mut url = archive.url;
+                url.set_fragment(None);
+                package.archive = Some(PyLockArchive {
+                    url: Some(url.to_string()),
+                    path: None,
+                    hashes: locked_hashes_to_table(distribution.hashes()),
+                    subdirectory: archive.subdirectory,
+                });
+            }
+        }
+
+        Ok(package)
+    }
+
+    /// Returns `true` if any of the package's artifacts lack a hash.
+    fn is_missing_hashes(&self) -> bool {
+        self.sdist
+            .iter()
+            .chain(&self.wheels)
+            .any(|file| file.hashes.is_empty())
+            || self
+                .archive
+                .as_ref()
+                .is_some_and(|archive| archive.hashes.is_empty())
+    }
+}
+
+impl PyLockFile {
+    fn from_file(file: &File) -> Result<Self, PyLockError> {
+        let (url, path) = match &file.url {
+            FileLocation::RelativeUrl(base, url) => {
+                (Some(base_url_join_relative(base, url)?.to_string()), None)
+            }
+            FileLocation::AbsoluteUrl(url) => (
+                Some(
+                    Url::parse(url)
+                        .map_err(|err| PyLockError::Url(url.clone(), err))?
+                        .to_string(),
+                ),
+                None,
+            ),
+            FileLocation::Path(path) => (None, Some(path_to_string(path))),
+        };
+        Ok(Self {
+            name: file.filename.clone(),
+            url,
+            path,
+            size: file.size,
+            hashes: hashes_to_table(std::slice::from_ref(&file.hashes)),
+        })
+    }
+
+    fn from_artifact(artifact: &LockedArtifact) -> Self {
+        Self {
+            name: artifact.filename().to_string(),
+            url: artifact.url().map(ToString::to_string),
+            path: artifact.path().map(ToString::to_string),
+            size: artifact.size(),
+            hashes: locked_hashes_to_table(artifact.hashes()),
+        }
+    }
+}
+
+/// Returns `true` if the given path names a `pylock.toml` file (e.g., `pylock.toml` or
+/// `pylock.dev.toml`).
+pub fn is_pylock_path(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("pylock.") && name.ends_with(".toml"))
+}
+
+/// Convert a set of [`Hashes`] to a table from algorithm to digest.
+fn hashes_to_table(hashes: &[Hashes]) -> BTreeMap<String, String> {
+    hashes
+        .iter()
+        .flat_map(|hashes| {
+            [
+                ("md5", &hashes.md5),
+                ("sha256", &hashes.sha256),
+                ("sha384", &hashes.sha384),
+                ("sha512", &hashes.sha512),
+            ]
+        })
+        .filter_map(|(algorithm, digest)| {
+            Some((algorithm.to_string(), digest.as_deref()?.to_string()))
+        })
+        .collect()
+}
+
+/// Convert a set of `{algorithm}:{digest}` hashes, as recorded in a `uv.lock`, to a table from
+/// algorithm to digest.
+fn locked_hashes_to_table(hashes: &[String]) -> BTreeMap<String, String> {
+    hashes
+        .iter()
+        .filter_map(|hash| hash.split_once(':'))
+        .map(|(algorithm, digest)| (algorithm.to_string(), digest.to_string()))
+        .collect()
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_pylock_path, PyLock};
+    use crate::Lock;
+    use std::path::Path;
+
+    #[test]
+    fn pylock_path() {
+        assert!(is_pylock_path(Path::new("pylock.toml")));
+        assert!(is_pylock_path(Path::new("dir/pylock.dev.toml")));
+        assert!(!is_pylock_path(Path::new("requirements.txt")));
+        assert!(!is_pylock_path(Path::new("pyproject.toml")));
+    }
+
+    #[test]
+    fn preferences() {
+        let lock = PyLock::from_toml(
+            r#"
+lock-version = "1.0"
+created-by = "uv"
+
+[[packages]]
+name = "anyio"
+version = "4.3.0"
+index = "https://pypi.org/simple"
+
+[[packages.wheels]]
+name = "anyio-4.3.0-py3-none-any.whl"
+url = "https://files.pythonhosted.org/packages/anyio-4.3.0-py3-none-any.whl"
+hashes = { sha256 = "048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8" }
+
+[[packages]]
+name = "flask"
+directory = { path = "/home/ferris/flask", editable = true }
+"#,
+        )
+        .unwrap();
+
+        let preferences = lock.to_preferences().unwrap();
+        assert_eq!(preferences.len(), 1);
+        assert_eq!(preferences[0].name().as_ref(), "anyio");
+        assert_eq!(preferences[0].requirement().to_string(), "anyio==4.3.0");
+    }
+
+    #[test]
+    fn from_lock() {
+        let lock = Lock::from_toml(
+            r#"
+version = 1
+
+[[distribution]]
+name = "anyio"
+version = "4.3.0"
+source = "registry+https://pypi.org/simple"
+marker = "sys_platform == 'win32'"
+hashes = ["sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"]
+
+[distribution.artifact]
+filename = "anyio-4.3.0-py3-none-any.whl"
+url = "https://files.pythonhosted.org/packages/anyio-4.3.0-py3-none-any.whl"
+size = 85584
+hashes = ["sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"]
+
+[[distribution]]
+name = "flask"
+source = "editable+/home/ferris/flask"
+
+[[distribution]]
+name = "idna"
+version = "3.6"
+source = "direct+https://files.pythonhosted.org/packages/idna-3.6-py3-none-any.whl"
+hashes = ["sha256:c05567e9c24a6b9faaa835c4821bad0590fbb9d5779e7caa6e1cc4978e7eb24f"]
+"#,
+        )
+        .unwrap();
+
+        let pylock = PyLock::from_lock(&lock, true).unwrap();
+        insta::assert_snapshot!(pylock.to_toml().unwrap(), @r###"
+        lock-version = "1.0"
+        created-by = "uv"
+
+        [[packages]]
+        name = "anyio"
+        version = "4.3.0"
+        marker = "sys_platform == 'win32'"
+        index = "https://pypi.org/simple"
+
+        [[packages.wheels]]
+        name = "anyio-4.3.0-py3-none-any.whl"
+        url = "https://files.pythonhosted.org/packages/anyio-4.3.0-py3-none-any.whl"
+        size = 85584
+
+        [packages.wheels.hashes]
+        sha256 = "048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"
+
+        [[packages]]
+        name = "flask"
+
+        [packages.directory]
+        path = "/home/ferris/flask"
+        editable = true
+
+        [[packages]]
+        name = "idna"
+        version = "3.6"
+
+        [packages.archive]
+        url = "https://files.pythonhosted.org/packages/idna-3.6-py3-none-any.whl"
+
+        [packages.archive.hashes]
+        sha256 = "c05567e9c24a6b9faaa835c4821bad0590fbb9d5779e7caa6e1cc4978e7eb24f"
+        "###);
+
+        // The exported lockfile can be read back as preferences.
+        let preferences = PyLock::from_toml(&pylock.to_toml().unwrap())
+            .unwrap()
+            .to_preferences()
+            .unwrap();
+        assert_eq!(
+            preferences[0].requirement().to_string(),
+            "anyio==4.3.0 ; sys_platform == 'win32'"
+        );
+    }
+
+    #[test]
+    fn from_lock_missing_hash() {
+        let lock = Lock::from_toml(
+            r#"
+version = 1
+
+[[distribution]]
+name = "idna"
+version = "3.6"
+source = "direct+https://files.pythonhosted.org/packages/idna-3.6-py3-none-any.whl"
+"#,
+        )
+        .unwrap();
+
+        assert!(PyLock::from_lock(&lock, false).is_ok());
+        let err = PyLock::from_lock(&lock, true).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Locked distribution `idna` is missing a hash"
+        );
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let err = PyLock::from_toml(
+            r#"
+lock-version = "2.0"
+created-by = "uv"
+"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unsupported `pylock.toml` version: 2.0 (expected 1.0)"
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::hash::BuildHasherDefault;
+use std::str::FromStr;
 
 use anyhow::Result;
 use dashmap::DashMap;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use petgraph::graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use pubgrub::range::Range;
@@ -16,13 +19,15 @@ use url::Url;
 use distribution_types::{Dist, DistributionMetadata, LocalEditable, Name, PackageId, Verbatim};
 use once_map::OnceMap;
 use pep440_rs::Version;
-use pypi_types::{Hashes, Metadata23};
+use pep508_rs::{MarkerEnvironment, MarkerTree, MarkerValue, Requirement};
+use pypi_types::{Hashes, Metadata23, Yanked};
 use uv_normalize::{ExtraName, PackageName};
 
 use crate::editables::Editables;
+use crate::fork::ForkMarkers;
 use crate::pins::FilePins;
 use crate::preferences::Preferences;
-use crate::pubgrub::{PubGrubDistribution, PubGrubPackage, PubGrubPriority};
+use crate::pubgrub::{is_relevant, PubGrubDistribution, PubGrubPackage, PubGrubPriority};
 use crate::redirect::apply_redirect;
 use crate::resolver::VersionsResponse;
 use crate::ResolveError;
@@ -49,8 +54,14 @@ pub struct ResolutionGraph {
     hashes: FxHashMap<PackageName, Vec<Hashes>>,
     /// The enabled extras for every distribution in this resolution.
     extras: FxHashMap<PackageName, Vec<ExtraName>>,
+    /// The environment markers under which each distribution is required, for universal
+    /// resolutions (or when markers are preserved). Distributions that are required in every
+    /// environment are omitted.
+    markers: FxHashMap<PackageName, MarkerTree>,
     /// The set of editable requirements in this resolution.
     editables: Editables,
+    /// The yanked distributions that were selected in this resolution.
+    yanks: FxHashMap<PackageName, YankedPin>,
     /// Any diagnostics that were encountered while building the graph.
     diagnostics: Vec<Diagnostic>,
 }
@@ -67,6 +78,10 @@ impl ResolutionGraph {
         state: &State<PubGrubPackage, Range<Version>, PubGrubPriority>,
         preferences: &Preferences,
         editables: Editables,
+        requirements: &[Requirement],
+        env: &MarkerEnvironment,
+        fork: &ForkMarkers,
+        include_markers: bool,
     ) -> Result<Self, ResolveError> {
         // TODO(charlie): petgraph is a really heavy and unnecessary dependency here. We should
         // write our own graph, given that our requirements are so simple.
@@ -74,6 +89,7 @@ impl ResolutionGraph {
         let mut hashes =
             FxHashMap::with_capacity_and_hasher(selection.len(), BuildHasherDefault::default());
         let mut extras = FxHashMap::default();
+        let mut yanks = FxHashMap::default();
         let mut diagnostics = Vec::new();
 
         // Add every package to the graph.
@@ -91,6 +107,29 @@ impl ResolutionGraph {
                             .clone()
                     };
 
+                    // Track any yanked distributions, which are only selected when pinned
+                    // explicitly, or when retained from an existing lockfile.
+                    if let Some(yanked) = pinned_package
+                        .file()
+                        .and_then(|file| file.yanked.as_ref())
+                        .filter(|yanked| yanked.is_yanked())
+                    {
+                        let pin = YankedPin {
+                            reason: match yanked {
+                                Yanked::Reason(reason) => Some(reason.clone()),
+                                Yanked::Bool(_) => None,
+                            },
+                            preference: preferences.version(package_name) == Some(version),
+                        };
+                        if pin.preference {
+                            diagnostics.push(Diagnostic::YankedPreference {
+                                dist: pinned_package.clone(),
+                                reason: pin.reason.clone(),
+                            });
+                        }
+                        yanks.insert(package_name.clone(), pin);
+                    }
+
                     // Add its hashes to the index, preserving those that were already present in
                     // the lockfile if necessary.
                     if let Some(hash) = preferences.match_hashes(package_name, version) {
@@ -122,9 +161,12 @@ impl ResolutionGraph {
                     };
 
                     // Add its hashes to the index, preserving those that were already present in
-                    // the lockfile if necessary.
+                    // the lockfile if necessary. Otherwise, fall back to any hash embedded in the
+                    // URL fragment (e.g., `#sha256=...`).
                     if let Some(hash) = preferences.match_hashes(package_name, version) {
                         hashes.insert(package_name.clone(), hash.to_vec());
+                    } else if let Some(hash) = url_hash(url) {
+                        hashes.insert(package_name.clone(), vec![hash]);
                     } else if let Some(versions_response) = packages.get(package_name) {
                         if let VersionsResponse::Found(ref version_map) = *versions_response {
                             hashes.insert(package_name.clone(), {
@@ -280,11 +322,38 @@ impl ResolutionGraph {
             }
         }
 
+        // In universal mode (or if markers should be preserved), determine the markers under
+        // which each package is required.
+        let markers = if include_markers {
+            let mut requires_dist = FxHashMap::default();
+            for (package, version) in selection {
+                let PubGrubPackage::Package(package_name, None, url) = package else {
+                    continue;
+                };
+                if let Some((_, metadata)) = editables.get(package_name) {
+                    requires_dist.insert(package_name.clone(), metadata.requires_dist.clone());
+                } else {
+                    let dist = match url {
+                        Some(url) => PubGrubDistribution::from_url(package_name, url),
+                        None => PubGrubDistribution::from_registry(package_name, version),
+                    };
+                    if let Some(metadata) = distributions.get(&dist.package_id()) {
+                        requires_dist.insert(package_name.clone(), metadata.requires_dist.clone());
+                    }
+                }
+            }
+            universal_markers(requirements, &editables, &requires_dist, &extras, env, fork)
+        } else {
+            FxHashMap::default()
+        };
+
         Ok(Self {
             petgraph,
             hashes,
             extras,
+            markers,
             editables,
+            yanks,
             diagnostics,
         })
     }
@@ -311,10 +380,268 @@ impl ResolutionGraph {
         &self.diagnostics
     }
 
+    /// Return the hashes for the given package, if any.
+    pub fn hashes(&self, name: &PackageName) -> &[Hashes] {
+        self.hashes.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Return the enabled extras for the given package, if any.
+    pub fn extras(&self, name: &PackageName) -> &[ExtraName] {
+        self.extras.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Return the environment markers under which the given package is required, for universal
+    /// resolutions (or when markers are preserved).
+    pub fn marker(&self, name: &PackageName) -> Option<&MarkerTree> {
+        self.markers.get(name)
+    }
+
     /// Return the underlying graph.
     pub fn petgraph(&self) -> &petgraph::graph::Graph<Dist, Range<Version>, petgraph::Directed> {
         &self.petgraph
     }
+
+    /// Return the nodes to display, omitting any excluded packages.
+    fn nodes(
+        &self,
+        no_emit_packages: &[PackageName],
+        include_extras: bool,
+    ) -> Vec<(NodeIndex, Node<'_>)> {
+        self.petgraph
+            .node_indices()
+            .filter_map(|index| {
+                let dist = &self.petgraph[index];
+                let name = dist.name();
+                if no_emit_packages.contains(name) {
+                    return None;
+                }
+
+                let node = if let Some((editable, _)) = self.editables.get(name) {
+                    Node::Editable(name, editable)
+                } else if include_extras {
+                    Node::Distribution(
+                        name,
+                        dist,
+                        self.extras
+                            .get(name)
+                            .map_or(&[], |extras| extras.as_slice()),
+                    )
+                } else {
+                    Node::Distribution(name, dist, &[])
+                };
+                Some((index, node))
+            })
+            .collect()
+    }
+
+    /// Return the names of the packages that depend on the given node.
+    fn dependents(&self, index: NodeIndex) -> Vec<&PackageName> {
+        self.petgraph
+            .edges_directed(index, Direction::Incoming)
+            .map(|edge| self.petgraph[edge.source()].name())
+            .collect()
+    }
+}
+
+/// Extract the hash embedded in the fragment of a direct URL (e.g., `#sha256=...`), if any.
+fn url_hash(url: &Url) -> Option<Hashes> {
+    url.fragment()?.split('&').find_map(|pair| {
+        let (algorithm, digest) = pair.split_once('=')?;
+        Hashes::from_str(&format!("{algorithm}:{digest}")).ok()
+    })
+}
+
+/// Determine the environment markers under which each package in a resolution is required, by
+/// propagating the markers on each requirement from the root requirements down through the graph.
+///
+/// Packages that are required in every environment are omitted from the returned map. For a fork
+/// of a universal resolution, requirements that can't apply within the fork are ignored, and the
+/// markers that define the fork are omitted, since they hold throughout.
+fn universal_markers(
+    requirements: &[Requirement],
+    editables: &Editables,
+    requires_dist: &FxHashMap<PackageName, Vec<Requirement>>,
+    extras: &FxHashMap<PackageName, Vec<ExtraName>>,
+    env: &MarkerEnvironment,
+    fork: &ForkMarkers,
+) -> FxHashMap<PackageName, MarkerTree> {
+    // For each package, the markers under which it's required, in disjunctive normal form (i.e.,
+    // a disjunction of conjunctions). An empty conjunction is satisfied in every environment.
+    let mut reachable: FxHashMap<PackageName, Vec<Vec<MarkerTree>>> = FxHashMap::default();
+
+    // Seed the markers with the root requirements. Editables are required unconditionally, unless
+    // they're the local source for a dependency.
+    for name in requires_dist.keys() {
+        if editables
+            .get(name)
+            .is_some_and(|(editable, _)| !editable.dependency)
+        {
+            add_conjunction(&mut reachable, name, Vec::new());
+        }
+    }
+    for requirement in requirements {
+        if requires_dist.contains_key(&requirement.name)
+            && is_relevant(requirement, None, env, Some(fork))
+        {
+            let conjunction = requirement
+                .marker
+                .as_ref()
+                .and_then(strip_extras)
+                .filter(|marker| !fork.contains(marker))
+                .into_iter()
+                .collect();
+            add_conjunction(&mut reachable, &requirement.name, conjunction);
+        }
+    }
+
+    // Propagate the markers along every dependency edge, until we reach a fixed point.
+    loop {
+        let mut changed = false;
+        for (name, dependencies) in requires_dist {
+            let Some(conjunctions) = reachable.get(name).cloned() else {
+                continue;
+            };
+            let enabled = extras.get(name).map(Vec::as_slice).unwrap_or_default();
+            for requirement in dependencies {
+                if requirement.name == *name || !requires_dist.contains_key(&requirement.name) {
+                    continue;
+                }
+                if !is_relevant(requirement, None, env, Some(fork))
+                    && !enabled
+                        .iter()
+                        .any(|extra| is_relevant(requirement, Some(extra), env, Some(fork)))
+                {
+                    continue;
+                }
+                let marker = requirement
+                    .marker
+                    .as_ref()
+                    .and_then(strip_extras)
+                    .filter(|marker| !fork.contains(marker));
+                for conjunction in &conjunctions {
+                    let mut conjunction = conjunction.clone();
+                    if let Some(marker) = &marker {
+                        if !conjunction.contains(marker) {
+                            conjunction.push(marker.clone());
+                        }
+                    }
+                    changed |= add_conjunction(&mut reachable, &requirement.name, conjunction);
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    reachable
+        .into_iter()
+        .filter_map(|(name, conjunctions)| {
+            // If the package is required unconditionally along any path, omit its markers.
+            if conjunctions.iter().any(Vec::is_empty) {
+                return None;
+            }
+            let mut disjunction = conjunctions
+                .into_iter()
+                .map(|mut conjunction| {
+                    if conjunction.len() == 1 {
+                        conjunction.remove(0)
+                    } else {
+                        MarkerTree::And(conjunction)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let marker = if disjunction.len() == 1 {
+                disjunction.remove(0)
+            } else {
+                MarkerTree::Or(disjunction)
+            };
+            Some((name, marker))
+        })
+        .collect()
+}
+
+/// Add a conjunction of markers to the disjunction for the given package, returning `true` if the
+/// disjunction changed.
+fn add_conjunction(
+    reachable: &mut FxHashMap<PackageName, Vec<Vec<MarkerTree>>>,
+    name: &PackageName,
+    conjunction: Vec<MarkerTree>,
+) -> bool {
+    let conjunctions = reachable.entry(name.clone()).or_default();
+
+    // If an existing conjunction is less restrictive, the new conjunction is redundant.
+    if conjunctions
+        .iter()
+        .any(|existing| existing.iter().all(|marker| conjunction.contains(marker)))
+    {
+        return false;
+    }
+
+    // Otherwise, drop any existing conjunctions that are more restrictive than the new one.
+    conjunctions.retain(|existing| !conjunction.iter().all(|marker| existing.contains(marker)));
+    conjunctions.push(conjunction);
+    true
+}
+
+/// Remove any `extra` expressions from a marker, since extras are resolved ahead of time.
+///
+/// Returns `None` if the remaining marker is satisfied in every environment.
+pub(crate) fn strip_extras(marker: &MarkerTree) -> Option<MarkerTree> {
+    match marker {
+        MarkerTree::Expression(expression) => {
+            if matches!(expression.l_value, MarkerValue::Extra)
+                || matches!(expression.r_value, MarkerValue::Extra)
+            {
+                None
+            } else {
+                Some(marker.clone())
+            }
+        }
+        MarkerTree::And(markers) => {
+            let mut markers = markers.iter().filter_map(strip_extras).collect::<Vec<_>>();
+            match markers.len() {
+                0 => None,
+                1 => markers.pop(),
+                _ => Some(MarkerTree::And(markers)),
+            }
+        }
+        MarkerTree::Or(markers) => {
+            let mut markers = markers
+                .iter()
+                .map(strip_extras)
+                .collect::<Option<Vec<_>>>()?;
+            match markers.len() {
+                0 => None,
+                1 => markers.pop(),
+                _ => Some(MarkerTree::Or(markers)),
+            }
+        }
+    }
+}
+
+/// A yanked distribution that was selected during resolution.
+#[derive(Debug, Clone)]
+struct YankedPin {
+    /// The reason the distribution was yanked, if provided by the index.
+    reason: Option<String>,
+    /// Whether the version was retained from an existing pin (e.g., in the output file).
+    preference: bool,
+}
+
+impl YankedPin {
+    /// Return an annotation explaining why the yanked distribution was selected.
+    fn annotation(&self) -> String {
+        let source = if self.preference {
+            "kept from the existing pin"
+        } else {
+            "allowed explicitly"
+        };
+        match &self.reason {
+            Some(reason) => format!("yanked (reason: \"{reason}\"), {source}"),
+            None => format!("yanked, {source}"),
+        }
+    }
 }
 
 /// A [`std::fmt::Display`] implementation for the resolution graph.
@@ -334,6 +661,9 @@ pub struct DisplayResolutionGraph<'a> {
     /// The style of annotation comments, used to indicate the dependencies that requested each
     /// package.
     annotation_style: AnnotationStyle,
+    /// The sources of the constraints on each package (e.g., `-c constraints.txt`), to include in
+    /// the annotations.
+    constraint_sources: Option<&'a FxHashMap<PackageName, Vec<String>>>,
 }
 
 impl<'a> From<&'a ResolutionGraph> for DisplayResolutionGraph<'a> {
@@ -366,8 +696,19 @@ impl<'a> DisplayResolutionGraph<'a> {
             include_extras,
             include_annotations,
             annotation_style,
+            constraint_sources: None,
         }
     }
+
+    /// Annotate each package with the sources of its constraints (e.g., `-c constraints.txt`).
+    #[must_use]
+    pub fn with_constraint_sources(
+        mut self,
+        constraint_sources: &'a FxHashMap<PackageName, Vec<String>>,
+    ) -> Self {
+        self.constraint_sources = Some(constraint_sources);
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -430,32 +771,7 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
         // Collect all packages.
         let mut nodes = self
             .resolution
-            .petgraph
-            .node_indices()
-            .filter_map(|index| {
-                let dist = &self.resolution.petgraph[index];
-                let name = dist.name();
-                if self.no_emit_packages.contains(name) {
-                    return None;
-                }
-
-                let node = if let Some((editable, _)) = self.resolution.editables.get(name) {
-                    Node::Editable(name, editable)
-                } else if self.include_extras {
-                    Node::Distribution(
-                        name,
-                        dist,
-                        self.resolution
-                            .extras
-                            .get(name)
-                            .map_or(&[], |extras| extras.as_slice()),
-                    )
-                } else {
-                    Node::Distribution(name, dist, &[])
-                };
-                Some((index, node))
-            })
-            .collect::<Vec<_>>();
+            .nodes(self.no_emit_packages, self.include_extras);
 
         // Sort the nodes by name, but with editable packages first.
         nodes.sort_unstable_by_key(|(index, node)| (node.key(), *index));
@@ -465,89 +781,322 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
             // Display the node itself.
             let mut line = node.verbatim().to_string();
 
+            // Display the environment markers, if any (e.g., for a universal resolution).
+            if let Some(marker) = self.resolution.markers.get(node.name()) {
+                line.push_str(" ; ");
+                line.push_str(&marker.to_string());
+            }
+
             // Display the distribution hashes, if any.
             let mut has_hashes = false;
             if self.show_hashes {
-                if let Some(hashes) = self
-                    .resolution
-                    .hashes
-                    .get(node.name())
-                    .filter(|hashes| !hashes.is_empty())
-                {
-                    for hash in hashes {
-                        if let Some(hash) = hash.to_string() {
-                            has_hashes = true;
-                            line.push_str(" \\\n");
-                            line.push_str("    --hash=");
-                            line.push_str(&hash);
-                        }
+                for hash in self.resolution.hashes(node.name()) {
+                    if let Some(hash) = hash.to_string() {
+                        has_hashes = true;
+                        line.push_str(" \\\n");
+                        line.push_str("    --hash=");
+                        line.push_str(&hash);
                     }
                 }
             }
 
-            // Determine the annotation comment and separator (between comment and requirement).
-            let mut annotation = None;
-
+            // Determine the annotation comments and the packages (or constraints) that requested
+            // the node.
+            let mut comments = Vec::new();
+            let mut sources: &[String] = &[];
+            let mut dependents = Vec::new();
             if self.include_annotations {
+                // Explain why a yanked distribution was selected, if necessary.
+                comments.extend(
+                    self.resolution
+                        .yanks
+                        .get(node.name())
+                        .map(YankedPin::annotation),
+                );
+
+                // Display any constraints that apply to the package.
+                sources = self
+                    .constraint_sources
+                    .and_then(|constraint_sources| constraint_sources.get(node.name()))
+                    .map_or(&[][..], Vec::as_slice);
+
                 // Display all dependencies.
-                let mut edges = self
-                    .resolution
-                    .petgraph
-                    .edges_directed(index, Direction::Incoming)
-                    .map(|edge| &self.resolution.petgraph[edge.source()])
-                    .collect::<Vec<_>>();
-                edges.sort_unstable_by_key(|package| package.name());
-
-                match self.annotation_style {
-                    AnnotationStyle::Line => {
-                        if !edges.is_empty() {
-                            let separator = if has_hashes { "\n    " } else { "  " };
-                            let deps = edges
-                                .into_iter()
-                                .map(|dependency| dependency.name().to_string())
-                                .collect::<Vec<_>>()
-                                .join(", ");
-                            let comment = format!("# via {deps}").green().to_string();
-                            annotation = Some((separator, comment));
+                dependents = self.resolution.dependents(index);
+                dependents.sort_unstable();
+            }
+
+            write_requirement(
+                f,
+                &line,
+                has_hashes,
+                &comments,
+                sources,
+                &dependents,
+                self.annotation_style,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`std::fmt::Display`] implementation for a set of resolution graphs, each of which targets a
+/// different Python version (or fork of a universal resolution), folded into a single
+/// requirements.txt.
+///
+/// Pins that are shared by every resolution are written as-is, while pins that are only present in
+/// some resolutions are qualified with the markers of those resolutions.
+#[derive(Debug)]
+pub struct DisplayResolutionMatrix<'a> {
+    /// The underlying graphs, along with the markers under which each applies (e.g.,
+    /// `python_version == '3.12'`).
+    resolutions: &'a [(String, ResolutionGraph)],
+    /// The packages to exclude from the output.
+    no_emit_packages: &'a [PackageName],
+    /// Whether to include hashes in the output.
+    show_hashes: bool,
+    /// Whether to include extras in the output (e.g., `black[colorama]`).
+    include_extras: bool,
+    /// Whether to include annotations in the output, to indicate which dependency or dependencies
+    /// requested each package.
+    include_annotations: bool,
+    /// The style of annotation comments, used to indicate the dependencies that requested each
+    /// package.
+    annotation_style: AnnotationStyle,
+    /// The sources of the constraints on each package (e.g., `-c constraints.txt`), to include in
+    /// the annotations.
+    constraint_sources: Option<&'a FxHashMap<PackageName, Vec<String>>>,
+}
+
+impl<'a> DisplayResolutionMatrix<'a> {
+    /// Create a new [`DisplayResolutionMatrix`] for the given graphs.
+    pub fn new(
+        resolutions: &'a [(String, ResolutionGraph)],
+        no_emit_packages: &'a [PackageName],
+        show_hashes: bool,
+        include_extras: bool,
+        include_annotations: bool,
+        annotation_style: AnnotationStyle,
+    ) -> DisplayResolutionMatrix<'a> {
+        Self {
+            resolutions,
+            no_emit_packages,
+            show_hashes,
+            include_extras,
+            include_annotations,
+            annotation_style,
+            constraint_sources: None,
+        }
+    }
+
+    /// Annotate each package with the sources of its constraints (e.g., `-c constraints.txt`).
+    #[must_use]
+    pub fn with_constraint_sources(
+        mut self,
+        constraint_sources: &'a FxHashMap<PackageName, Vec<String>>,
+    ) -> Self {
+        self.constraint_sources = Some(constraint_sources);
+        self
+    }
+}
+
+/// A pin that's present in one or more of the resolutions in a [`DisplayResolutionMatrix`].
+#[derive(Debug, Default)]
+struct MatrixEntry<'a> {
+    /// The name of the pinned package.
+    name: Option<&'a PackageName>,
+    /// The markers of each resolution that includes the pin.
+    markers: Vec<&'a str>,
+    /// The distribution hashes, across all resolutions.
+    hashes: Vec<String>,
+    /// The packages that requested the pin, across all resolutions.
+    dependents: BTreeSet<&'a PackageName>,
+    /// The annotation explaining why a yanked distribution was selected, if necessary.
+    yanked: Option<String>,
+}
+
+impl std::fmt::Display for DisplayResolutionMatrix<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Collect the pins across all resolutions, keyed by the node and its requirement (e.g.,
+        // `anyio==4.0.0`) and existing markers.
+        let mut entries: BTreeMap<(NodeKey<'_>, String, Option<String>), MatrixEntry<'_>> =
+            BTreeMap::new();
+        for (markers, resolution) in self.resolutions {
+            for (index, node) in resolution.nodes(self.no_emit_packages, self.include_extras) {
+                let requirement = node.verbatim().to_string();
+                let marker = resolution.markers.get(node.name()).map(ToString::to_string);
+                let entry = entries
+                    .entry((node.key(), requirement, marker))
+                    .or_default();
+                entry.name = Some(node.name());
+                entry.markers.push(markers);
+                if self.show_hashes {
+                    for hash in resolution.hashes(node.name()) {
+                        if let Some(hash) = hash.to_string() {
+                            if !entry.hashes.contains(&hash) {
+                                entry.hashes.push(hash);
+                            }
                         }
                     }
-                    AnnotationStyle::Split => match edges.as_slice() {
-                        [] => {}
-                        [edge] => {
-                            let separator = "\n";
-                            let comment = format!("    # via {}", edge.name()).green().to_string();
-                            annotation = Some((separator, comment));
-                        }
-                        edges => {
-                            let separator = "\n";
-                            let deps = edges
-                                .iter()
-                                .map(|dependency| format!("    #   {}", dependency.name()))
-                                .collect::<Vec<_>>()
-                                .join("\n");
-                            let comment = format!("    # via\n{deps}").green().to_string();
-                            annotation = Some((separator, comment));
-                        }
-                    },
+                }
+                if self.include_annotations {
+                    entry.dependents.extend(resolution.dependents(index));
+                    if entry.yanked.is_none() {
+                        entry.yanked = resolution.yanks.get(node.name()).map(YankedPin::annotation);
+                    }
                 }
             }
+        }
 
-            if let Some((separator, comment)) = annotation {
-                // Assemble the line with the annotations and remove trailing whitespaces.
-                for line in format!("{line:24}{separator}{comment}").lines() {
-                    let line = line.trim_end();
-                    writeln!(f, "{line}")?;
-                }
+        for ((_, requirement, marker), entry) in entries {
+            let mut line = requirement;
+
+            // Qualify the pin with the markers of the resolutions that require it, unless it's
+            // required by every resolution.
+            let resolution_marker = if entry.markers.len() == self.resolutions.len() {
+                None
+            } else if let [markers] = entry.markers.as_slice() {
+                Some((*markers).to_string())
             } else {
-                // Write the line as is.
-                writeln!(f, "{line}")?;
+                Some(
+                    entry
+                        .markers
+                        .iter()
+                        .map(|markers| {
+                            if markers.contains(" and ") || markers.contains(" or ") {
+                                format!("({markers})")
+                            } else {
+                                (*markers).to_string()
+                            }
+                        })
+                        .join(" or "),
+                )
+            };
+            match (marker, resolution_marker) {
+                (None, None) => {}
+                (Some(marker), None) | (None, Some(marker)) => {
+                    line.push_str(" ; ");
+                    line.push_str(&marker);
+                }
+                (Some(marker), Some(resolution_marker)) => {
+                    line.push_str(" ; (");
+                    line.push_str(&marker);
+                    line.push_str(") and (");
+                    line.push_str(&resolution_marker);
+                    line.push(')');
+                }
             }
+
+            // Display the distribution hashes, if any.
+            for hash in &entry.hashes {
+                line.push_str(" \\\n");
+                line.push_str("    --hash=");
+                line.push_str(hash);
+            }
+
+            let comments = entry.yanked.into_iter().collect::<Vec<_>>();
+            let sources = self
+                .constraint_sources
+                .filter(|_| self.include_annotations)
+                .zip(entry.name)
+                .and_then(|(constraint_sources, name)| constraint_sources.get(name))
+                .map_or(&[][..], Vec::as_slice);
+            let dependents = entry.dependents.into_iter().collect::<Vec<_>>();
+            write_requirement(
+                f,
+                &line,
+                !entry.hashes.is_empty(),
+                &comments,
+                sources,
+                &dependents,
+                self.annotation_style,
+            )?;
         }
 
         Ok(())
     }
 }
 
+/// Write a requirement line, along with any annotation comments, the constraints that apply to it
+/// (e.g., `-c constraints.txt`), and the packages that requested it (which are expected to be
+/// sorted).
+fn write_requirement(
+    f: &mut std::fmt::Formatter<'_>,
+    line: &str,
+    has_hashes: bool,
+    comments: &[String],
+    sources: &[String],
+    dependents: &[&PackageName],
+    annotation_style: AnnotationStyle,
+) -> std::fmt::Result {
+    // Determine the annotation comment and separator (between comment and requirement).
+    let mut annotation = None;
+
+    // List the constraints ahead of the dependents, as in `pip-compile`.
+    let dependents = sources
+        .iter()
+        .map(String::as_str)
+        .chain(dependents.iter().map(|dependent| dependent.as_ref()))
+        .collect::<Vec<_>>();
+
+    match annotation_style {
+        AnnotationStyle::Line => {
+            let mut comments = comments.to_vec();
+            if !dependents.is_empty() {
+                let deps = dependents
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                comments.push(format!("via {deps}"));
+            }
+            if !comments.is_empty() {
+                let separator = if has_hashes { "\n    " } else { "  " };
+                let comment = format!("# {}", comments.join("; ")).green().to_string();
+                annotation = Some((separator, comment));
+            }
+        }
+        AnnotationStyle::Split => {
+            let mut comments = comments
+                .iter()
+                .map(|comment| format!("    # {comment}"))
+                .collect::<Vec<_>>();
+            match dependents.as_slice() {
+                [] => {}
+                [dependent] => {
+                    comments.push(format!("    # via {dependent}"));
+                }
+                dependents => {
+                    comments.push("    # via".to_string());
+                    comments.extend(
+                        dependents
+                            .iter()
+                            .map(|dependent| format!("    #   {dependent}")),
+                    );
+                }
+            }
+            if !comments.is_empty() {
+                let separator = "\n";
+                let comment = comments.join("\n").green().to_string();
+                annotation = Some((separator, comment));
+            }
+        }
+    }
+
+    if let Some((separator, comment)) = annotation {
+        // Assemble the line with the annotations and remove trailing whitespaces.
+        for line in format!("{line:24}{separator}{comment}").lines() {
+            let line = line.trim_end();
+            writeln!(f, "{line}")?;
+        }
+    } else {
+        // Write the line as is.
+        writeln!(f, "{line}")?;
+    }
+
+    Ok(())
+}
+
 impl From<ResolutionGraph> for distribution_types::Resolution {
     fn from(graph: ResolutionGraph) -> Self {
         Self::new(
@@ -574,6 +1123,13 @@ pub enum Diagnostic {
         /// The extra that was requested. For example, `colorama` in `black[colorama]`.
         extra: ExtraName,
     },
+    YankedPreference {
+        /// The yanked distribution that was retained from an existing pin. For example,
+        /// `click==8.1.0`.
+        dist: Dist,
+        /// The reason the distribution was yanked, if provided by the index.
+        reason: Option<String>,
+    },
 }
 
 impl Diagnostic {
@@ -583,6 +1139,16 @@ impl Diagnostic {
             Self::MissingExtra { dist, extra } => {
                 format!("The package `{dist}` does not have an extra named `{extra}`.")
             }
+            Self::YankedPreference { dist, reason } => {
+                let reason = reason
+                    .as_ref()
+                    .map(|reason| format!(" (reason: \"{reason}\")"))
+                    .unwrap_or_default();
+                format!(
+                    "The existing pin `{dist}` is yanked{reason}. Pass `--upgrade-package {}` to select a different version.",
+                    dist.name()
+                )
+            }
         }
     }
 
@@ -590,6 +1156,7 @@ impl Diagnostic {
     pub fn includes(&self, name: &PackageName) -> bool {
         match self {
             Self::MissingExtra { dist, .. } => name == dist.name(),
+            Self::YankedPreference { dist, .. } => name == dist.name(),
         }
     }
 }
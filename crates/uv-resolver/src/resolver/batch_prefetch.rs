@@ -0,0 +1,187 @@
+use std::cmp::min;
+
+use pubgrub::range::Range;
+use rustc_hash::FxHashMap;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, trace};
+
+use distribution_types::{CompatibleDist, DistributionMetadata};
+use pep440_rs::Version;
+use uv_normalize::PackageName;
+
+use crate::candidate_selector::CandidateSelector;
+use crate::error::ResolveError;
+use crate::preferences::Preferences;
+use crate::pubgrub::PubGrubPackage;
+use crate::resolver::{InMemoryIndex, Request, VersionsResponse};
+
+/// The number of times a package must be tried before we start prefetching its older versions.
+const BATCH_THRESHOLD: usize = 5;
+
+/// The initial number of versions to prefetch once a package crosses the threshold. Each
+/// subsequent batch is twice as large, up to [`MAX_BATCH_SIZE`].
+const INITIAL_BATCH_SIZE: usize = 10;
+
+/// The maximum number of versions to prefetch in a single batch.
+const MAX_BATCH_SIZE: usize = 50;
+
+/// Speculatively prefetch the metadata for the candidate versions that the resolver is likely to
+/// try next.
+///
+/// By default, the resolver only fetches the metadata for the best candidate of each package it
+/// visits. When a package conflicts with the rest of the resolution, the resolver instead walks
+/// through its versions one at a time, blocking on a network request for each. Once a package has
+/// been tried [`BATCH_THRESHOLD`] times, we instead fetch the metadata for the next batch of
+/// candidates in parallel, such that the network overlaps with the solver's backtracking.
+///
+/// Only wheels are prefetched, since building source distributions speculatively is expensive.
+#[derive(Debug, Default)]
+pub(crate) struct BatchPrefetcher {
+    /// The number of versions tried for each package.
+    tried_versions: FxHashMap<PackageName, usize>,
+    /// The number of versions tried at the time of the last prefetch, and the size of that batch.
+    last_prefetch: FxHashMap<PackageName, (usize, usize)>,
+}
+
+impl BatchPrefetcher {
+    /// Record that a version of the given package was tried.
+    pub(crate) fn version_tried(&mut self, package: &PubGrubPackage) {
+        // Only registry packages have multiple versions to walk through.
+        let PubGrubPackage::Package(package_name, _extra, None) = package else {
+            return;
+        };
+        *self.tried_versions.entry(package_name.clone()).or_default() += 1;
+    }
+
+    /// Prefetch the metadata for the next batch of candidates below the chosen `version`, if the
+    /// package has been tried enough times to suggest that the resolver is backtracking over it.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn prefetch_batches(
+        &mut self,
+        package: &PubGrubPackage,
+        version: &Version,
+        range: &Range<Version>,
+        request_sink: &Sender<Request>,
+        index: &InMemoryIndex,
+        selector: &CandidateSelector,
+        preferences: &Preferences,
+    ) -> Result<(), ResolveError> {
+        let PubGrubPackage::Package(package_name, _extra, None) = package else {
+            return Ok(());
+        };
+
+        let Some(batch_size) = self.batch_size(package_name) else {
+            return Ok(());
+        };
+
+        // The package was already chosen, so its versions must be available.
+        let Some(versions_response) = index.packages.get(package_name) else {
+            return Ok(());
+        };
+        let VersionsResponse::Found(ref version_map) = *versions_response else {
+            return Ok(());
+        };
+
+        // Walk the candidates in the order in which the selector would visit them, excluding the
+        // version that was just chosen.
+        let mut remaining = range.intersection(&Range::singleton(version.clone()).complement());
+        let mut prefetched = 0;
+        for _ in 0..batch_size {
+            let Some(candidate) =
+                selector.select(package_name, &remaining, version_map, preferences)
+            else {
+                break;
+            };
+            remaining =
+                remaining.intersection(&Range::singleton(candidate.version().clone()).complement());
+
+            let Some(CompatibleDist::CompatibleWheel(dist, _)) = candidate.compatible() else {
+                continue;
+            };
+
+            // Emit a request to fetch the metadata for this version, unless it's already known.
+            if index.distributions.register(candidate.package_id()) {
+                trace!("Prefetching metadata for: {dist}");
+                request_sink.send(Request::Dist((*dist).clone())).await?;
+                prefetched += 1;
+            }
+        }
+
+        debug!("Prefetching {prefetched} versions of {package_name} (batch of {batch_size})");
+
+        let tried = self.tried_versions.get(package_name).copied().unwrap_or(0);
+        self.last_prefetch
+            .insert(package_name.clone(), (tried, batch_size));
+
+        Ok(())
+    }
+
+    /// Return the size of the next batch to prefetch for the given package, or `None` if we
+    /// shouldn't prefetch yet.
+    fn batch_size(&self, package_name: &PackageName) -> Option<usize> {
+        let tried = self.tried_versions.get(package_name).copied().unwrap_or(0);
+        if tried < BATCH_THRESHOLD {
+            return None;
+        }
+
+        match self.last_prefetch.get(package_name) {
+            // This is the first batch.
+            None => Some(INITIAL_BATCH_SIZE),
+            // Wait until the resolver has worked through most of the previous batch before
+            // prefetching the next one, doubling its size each time.
+            Some(&(last_tried, last_batch_size)) => {
+                if tried - last_tried >= last_batch_size / 2 {
+                    Some(min(last_batch_size * 2, MAX_BATCH_SIZE))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use uv_normalize::PackageName;
+
+    use crate::pubgrub::PubGrubPackage;
+
+    use super::BatchPrefetcher;
+
+    #[test]
+    fn batch_size() {
+        let package_name = PackageName::from_str("boto3").unwrap();
+        let package = PubGrubPackage::Package(package_name.clone(), None, None);
+        let mut prefetcher = BatchPrefetcher::default();
+
+        // Don't prefetch until the package has been tried enough times.
+        for _ in 0..4 {
+            prefetcher.version_tried(&package);
+        }
+        assert_eq!(prefetcher.batch_size(&package_name), None);
+        prefetcher.version_tried(&package);
+        assert_eq!(prefetcher.batch_size(&package_name), Some(10));
+
+        // Wait for half of the batch to be consumed before doubling it.
+        prefetcher
+            .last_prefetch
+            .insert(package_name.clone(), (5, 10));
+        for _ in 0..4 {
+            prefetcher.version_tried(&package);
+        }
+        assert_eq!(prefetcher.batch_size(&package_name), None);
+        prefetcher.version_tried(&package);
+        assert_eq!(prefetcher.batch_size(&package_name), Some(20));
+
+        // Cap the batch size.
+        prefetcher
+            .last_prefetch
+            .insert(package_name.clone(), (10, 40));
+        for _ in 0..20 {
+            prefetcher.version_tried(&package);
+        }
+        assert_eq!(prefetcher.batch_size(&package_name), Some(50));
+    }
+}
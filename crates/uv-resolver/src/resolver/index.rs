@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use dashmap::DashMap;
 use url::Url;
 
@@ -23,3 +25,24 @@ pub struct InMemoryIndex {
     /// `git+https://github.com/pallets/flask.git@c2f65dd1cfff0672b902fd5b30815f0b4137214c`.
     pub(crate) redirects: DashMap<Url, Url>,
 }
+
+impl InMemoryIndex {
+    /// Return the metadata for the given package, if it was fetched during resolution.
+    pub fn get_metadata(&self, package_id: &PackageId) -> Option<Arc<Metadata23>> {
+        self.distributions.get(package_id)
+    }
+
+    /// Create a new index that reuses the distribution metadata fetched so far, e.g., to resolve
+    /// the same requirements for another Python version.
+    ///
+    /// The available versions of each package are omitted, since their compatibility depends on
+    /// the target Python version and platform.
+    #[must_use]
+    pub fn fork(&self) -> Self {
+        Self {
+            packages: OnceMap::default(),
+            distributions: self.distributions.completed(),
+            redirects: self.redirects.clone(),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Given a set of requirements, find a set of compatible packages.
 
 use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::sync::Arc;
@@ -23,20 +24,21 @@ use distribution_types::{
 };
 pub(crate) use locals::Locals;
 use pep440_rs::{Version, MIN_VERSION};
-use pep508_rs::{MarkerEnvironment, Requirement};
-use platform_tags::Tags;
+use pep508_rs::{MarkerEnvironment, MarkerTree, Requirement};
+use platform_tags::{IncompatibleTag, Tags};
 use pypi_types::Metadata23;
 pub(crate) use urls::Urls;
 use uv_client::{FlatIndex, RegistryClient};
 use uv_distribution::DistributionDatabase;
 use uv_interpreter::Interpreter;
-use uv_normalize::PackageName;
+use uv_normalize::{ExtraName, PackageName};
 use uv_traits::BuildContext;
 
 use crate::candidate_selector::{CandidateDist, CandidateSelector};
 use crate::constraints::Constraints;
 use crate::editables::Editables;
 use crate::error::ResolveError;
+use crate::fork::{find_conflict, ForkMarkers};
 use crate::manifest::Manifest;
 use crate::overrides::Overrides;
 use crate::pins::FilePins;
@@ -47,6 +49,7 @@ use crate::pubgrub::{
 };
 use crate::python_requirement::PythonRequirement;
 use crate::resolution::ResolutionGraph;
+use crate::resolver::batch_prefetch::BatchPrefetcher;
 pub use crate::resolver::index::InMemoryIndex;
 pub use crate::resolver::provider::{
     DefaultResolverProvider, PackageVersionsResult, ResolverProvider, VersionsResponse,
@@ -57,6 +60,7 @@ pub use crate::resolver::reporter::{BuildId, Reporter};
 use crate::yanks::AllowedYanks;
 use crate::{DependencyMode, Options};
 
+mod batch_prefetch;
 mod index;
 mod locals;
 mod provider;
@@ -100,11 +104,15 @@ pub struct Resolver<'a, Provider: ResolverProvider> {
     locals: Locals,
     dependency_mode: DependencyMode,
     markers: &'a MarkerEnvironment,
+    universal: bool,
+    preserve_markers: bool,
     python_requirement: PythonRequirement,
     selector: CandidateSelector,
     index: &'a InMemoryIndex,
     /// Incompatibilities for packages that are entirely unavailable
     unavailable_packages: DashMap<PackageName, UnavailablePackage>,
+    /// Package versions that were unusable because none of their wheels matched the environment
+    incompatible_wheels: DashMap<(PackageName, Version), IncompatibleTag>,
     /// The set of all registry-based packages visited during resolution.
     visited: DashSet<PackageName>,
     reporter: Option<Arc<dyn Reporter>>,
@@ -133,8 +141,9 @@ impl<'a, Context: BuildContext + Send + Sync> Resolver<'a, DefaultResolverProvid
             flat_index,
             tags,
             PythonRequirement::new(interpreter, markers),
-            AllowedYanks::from_manifest(&manifest, markers),
+            AllowedYanks::from_manifest(&manifest, markers, options.allow_yanked.clone()),
             options.exclude_newer,
+            options.exclude_newer_package.clone(),
             build_context.no_binary(),
             build_context.no_build(),
         );
@@ -162,9 +171,12 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         Ok(Self {
             index,
             unavailable_packages: DashMap::default(),
+            incompatible_wheels: DashMap::default(),
             visited: DashSet::default(),
-            selector: CandidateSelector::for_resolution(options, &manifest, markers),
             dependency_mode: options.dependency_mode,
+            universal: options.universal,
+            preserve_markers: options.preserve_markers,
+            selector: CandidateSelector::for_resolution(options, &manifest, markers),
             urls: Urls::from_manifest(&manifest, markers)?,
             locals: Locals::from_manifest(&manifest, markers),
             project: manifest.project,
@@ -192,7 +204,37 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
     }
 
     /// Resolve a set of requirements into a set of pinned versions.
+    ///
+    /// Fails with [`ResolveError::ConflictingMarkers`] if a universal resolution would need to
+    /// fork; see [`Resolver::resolve_forks`].
     pub async fn resolve(self) -> Result<ResolutionGraph, ResolveError> {
+        let mut resolutions = self.run(false).await?;
+        Ok(resolutions.remove(0).1)
+    }
+
+    /// Resolve a set of requirements into one set of pinned versions for each fork of a universal
+    /// resolution, along with the markers under which each fork applies.
+    ///
+    /// A universal resolution forks when a package is required with conflicting versions under
+    /// markers that can't hold at once (e.g., `foo==1 ; sys_platform == 'linux'` and
+    /// `foo==2 ; sys_platform == 'win32'`). If the resolution doesn't fork, a single resolution is
+    /// returned, without markers.
+    pub async fn resolve_forks(
+        self,
+    ) -> Result<Vec<(Option<MarkerTree>, ResolutionGraph)>, ResolveError> {
+        Ok(self
+            .run(true)
+            .await?
+            .into_iter()
+            .map(|(fork, resolution)| (fork.to_marker(), resolution))
+            .collect())
+    }
+
+    /// Run the resolver, solving each fork in turn (if forking is allowed).
+    async fn run(
+        &self,
+        forking: bool,
+    ) -> Result<Vec<(ForkMarkers, ResolutionGraph)>, ResolveError> {
         // A channel to fetch package metadata (e.g., given `flask`, fetch all versions) and version
         // metadata (e.g., given `flask==1.0.0`, fetch the metadata for that version).
         // Channel size is set to the same size as the task buffer for simplicity.
@@ -201,20 +243,44 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         // Run the fetcher.
         let requests_fut = self.fetch(request_stream).fuse();
 
-        // Run the solver.
-        let resolve_fut = self.solve(request_sink).fuse();
+        // Run the solver, once for each fork. The request channel closes once every fork is
+        // solved, at which point the fetcher completes.
+        let resolve_fut = async move {
+            let mut resolutions = Vec::new();
+            let mut forks = VecDeque::from([ForkMarkers::default()]);
+            while let Some(fork) = forks.pop_front() {
+                match self.solve(&fork, &request_sink).await? {
+                    Solution::Resolved(resolution) => resolutions.push((fork, resolution)),
+                    Solution::Fork(package_name, markers) => {
+                        if !forking {
+                            return Err(ResolveError::ConflictingMarkers(package_name));
+                        }
+                        let Some(children) = fork.split(&markers, self.markers) else {
+                            return Err(ResolveError::ConflictingMarkers(package_name));
+                        };
+                        debug!(
+                            "Forking the resolution on {package_name} ({})",
+                            markers.iter().join(", ")
+                        );
+                        forks.extend(children);
+                    }
+                }
+            }
+            Ok(resolutions)
+        }
+        .fuse();
 
         // Wait for both to complete.
         match tokio::try_join!(requests_fut, resolve_fut) {
-            Ok(((), resolution)) => {
+            Ok(((), resolutions)) => {
                 self.on_complete();
-                Ok(resolution)
+                Ok(resolutions)
             }
             Err(err) => {
                 // Add version information to improve unsat error messages.
                 Err(if let ResolveError::NoSolution(err) = err {
-                    ResolveError::NoSolution(
-                        err.with_available_versions(
+                    let err = err
+                        .with_available_versions(
                             &self.python_requirement,
                             &self.visited,
                             &self.index.packages,
@@ -222,8 +288,9 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                         .with_selector(self.selector.clone())
                         .with_python_requirement(&self.python_requirement)
                         .with_index_locations(self.provider.index_locations())
-                        .with_unavailable_packages(&self.unavailable_packages),
-                    )
+                        .with_unavailable_packages(&self.unavailable_packages)
+                        .with_incompatible_wheels(&self.incompatible_wheels);
+                    ResolveError::NoSolution(err)
                 } else {
                     err
                 })
@@ -231,17 +298,19 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         }
     }
 
-    /// Run the `PubGrub` solver.
+    /// Run the `PubGrub` solver for the given fork.
     #[instrument(skip_all)]
     async fn solve(
         &self,
-        request_sink: tokio::sync::mpsc::Sender<Request>,
-    ) -> Result<ResolutionGraph, ResolveError> {
+        fork: &ForkMarkers,
+        request_sink: &tokio::sync::mpsc::Sender<Request>,
+    ) -> Result<Solution, ResolveError> {
         let root = PubGrubPackage::Root(self.project.clone());
 
         // Keep track of the packages for which we've requested metadata.
         let mut pins = FilePins::default();
         let mut priorities = PubGrubPriorities::default();
+        let mut prefetcher = BatchPrefetcher::default();
 
         // Start the solve.
         let mut state = State::init(root.clone(), MIN_VERSION.clone());
@@ -249,10 +318,17 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
             FxHashMap::default();
         let mut next = root;
 
-        debug!(
-            "Solving with target Python version {}",
-            self.python_requirement.target()
-        );
+        if let Some(marker) = fork.to_marker() {
+            debug!(
+                "Solving with target Python version {} for: {marker}",
+                self.python_requirement.target()
+            );
+        } else {
+            debug!(
+                "Solving with target Python version {}",
+                self.python_requirement.target()
+            );
+        }
 
         loop {
             // Run unit propagation.
@@ -261,7 +337,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
             // Pre-visit all candidate packages, to allow metadata to be fetched in parallel. If
             // the dependency mode is direct, we only need to visit the root package.
             if self.dependency_mode.is_transitive() {
-                Self::pre_visit(state.partial_solution.prioritized_packages(), &request_sink)
+                Self::pre_visit(state.partial_solution.prioritized_packages(), request_sink)
                     .await?;
             }
 
@@ -274,7 +350,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     })
             else {
                 let selection = state.partial_solution.extract_solution();
-                return ResolutionGraph::from_state(
+                let resolution = ResolutionGraph::from_state(
                     &selection,
                     &pins,
                     &self.index.packages,
@@ -283,7 +359,12 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     &state,
                     &self.preferences,
                     self.editables.clone(),
-                );
+                    &self.requirements,
+                    self.markers,
+                    fork,
+                    self.universal || self.preserve_markers,
+                )?;
+                return Ok(Solution::Resolved(resolution));
             };
             next = highest_priority_pkg;
 
@@ -298,7 +379,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     &next,
                     term_intersection.unwrap_positive(),
                     &mut pins,
-                    &request_sink,
+                    request_sink,
                 )
                 .await?;
 
@@ -376,6 +457,16 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                             continue;
                         }
                         UnavailableVersion::IncompatibleDist(incompatibility) => {
+                            // Track versions that only publish incompatible wheels, to provide a
+                            // hint if resolution fails.
+                            if let (
+                                IncompatibleDist::Wheel(IncompatibleWheel::Tag(tag)),
+                                PubGrubPackage::Package(package_name, ..),
+                            ) = (&incompatibility, &next)
+                            {
+                                self.incompatible_wheels
+                                    .insert((package_name.clone(), version.clone()), tag.clone());
+                            }
                             incompatibility.to_string()
                         }
                     };
@@ -388,6 +479,25 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                 }
             };
 
+            // If the resolver keeps revisiting this package, prefetch the versions it's likely to
+            // try next.
+            prefetcher.version_tried(&next);
+            if let Some(term_intersection) =
+                state.partial_solution.term_intersection_for_package(&next)
+            {
+                prefetcher
+                    .prefetch_batches(
+                        &next,
+                        &version,
+                        term_intersection.unwrap_positive(),
+                        request_sink,
+                        self.index,
+                        &self.selector,
+                        &self.preferences,
+                    )
+                    .await?;
+            }
+
             self.on_progress(&next, &version);
 
             if added_dependencies
@@ -398,9 +508,12 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                 // Retrieve that package dependencies.
                 let package = &next;
                 let dependencies = match self
-                    .get_dependencies(package, &version, &mut priorities, &request_sink)
+                    .get_dependencies(package, &version, fork, &mut priorities, request_sink)
                     .await?
                 {
+                    Dependencies::Fork(package_name, markers) => {
+                        return Ok(Solution::Fork(package_name, markers));
+                    }
                     Dependencies::Unavailable(reason) => {
                         let message = {
                             if matches!(package, PubGrubPackage::Root(_)) {
@@ -464,9 +577,10 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
             PubGrubPackage::Root(_) => {}
             PubGrubPackage::Python(_) => {}
             PubGrubPackage::Package(package_name, _extra, None) => {
-                // Emit a request to fetch the metadata for this package.
+                // Emit a request to fetch the metadata for this package. The metadata may have been
+                // requested by an earlier fork, but the priorities are specific to each fork.
+                priorities.add(package_name.clone());
                 if self.index.packages.register(package_name.clone()) {
-                    priorities.add(package_name.clone());
                     request_sink
                         .send(Request::Package(package_name.clone()))
                         .await?;
@@ -475,8 +589,8 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
             PubGrubPackage::Package(package_name, _extra, Some(url)) => {
                 // Emit a request to fetch the metadata for this distribution.
                 let dist = Dist::from_url(package_name.clone(), url.clone())?;
+                priorities.add(dist.name().clone());
                 if self.index.distributions.register(dist.package_id()) {
-                    priorities.add(dist.name().clone());
                     request_sink.send(Request::Dist(dist)).await?;
                 }
             }
@@ -691,8 +805,12 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
 
                 let version = candidate.version().clone();
 
-                // Emit a request to fetch the metadata for this version.
-                if self.index.distributions.register(candidate.package_id()) {
+                // Emit a request to fetch the metadata for this version. When excluding transitive
+                // dependencies, the metadata is only needed to validate any requested extras, so
+                // avoid fetching (and, for source distributions, building) it otherwise.
+                if (self.dependency_mode.is_transitive() || extra.is_some())
+                    && self.index.distributions.register(candidate.package_id())
+                {
                     let dist = dist.for_resolution().clone();
                     request_sink.send(Request::Dist(dist)).await?;
                 }
@@ -708,11 +826,16 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         &self,
         package: &PubGrubPackage,
         version: &Version,
+        fork: &ForkMarkers,
         priorities: &mut PubGrubPriorities,
         request_sink: &tokio::sync::mpsc::Sender<Request>,
     ) -> Result<Dependencies, ResolveError> {
         match package {
             PubGrubPackage::Root(_) => {
+                if let Some(dependencies) = self.fork_on_conflict(&self.requirements, None, fork) {
+                    return Ok(dependencies);
+                }
+
                 // Add the root requirements.
                 let constraints = PubGrubDependencies::from_requirements(
                     &self.requirements,
@@ -723,6 +846,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     &self.urls,
                     &self.locals,
                     self.markers,
+                    self.universal.then_some(fork),
                 );
 
                 let mut constraints = match constraints {
@@ -740,8 +864,13 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                         .await?;
                 }
 
-                // Add a dependency on each editable.
+                // Add a dependency on each editable. Editables that are the local source for a
+                // dependency (e.g., on a workspace member) are only reached through that
+                // dependency, along with its markers and version specifiers.
                 for (editable, metadata) in self.editables.iter() {
+                    if editable.dependency {
+                        continue;
+                    }
                     constraints.push(
                         PubGrubPackage::from_package(metadata.name.clone(), None, &self.urls),
                         Range::singleton(metadata.version.clone()),
@@ -790,6 +919,12 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
 
                 // Determine if the distribution is editable.
                 if let Some((_local, metadata)) = self.editables.get(package_name) {
+                    if let Some(dependencies) =
+                        self.fork_on_conflict(&metadata.requires_dist, extra.as_ref(), fork)
+                    {
+                        return Ok(dependencies);
+                    }
+
                     let mut constraints = PubGrubDependencies::from_requirements(
                         &metadata.requires_dist,
                         &self.constraints,
@@ -799,6 +934,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                         &self.urls,
                         &self.locals,
                         self.markers,
+                        self.universal.then_some(fork),
                     )?;
 
                     for (dep_package, dep_version) in constraints.iter() {
@@ -847,6 +983,12 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     .await
                     .ok_or(ResolveError::Unregistered)?;
 
+                if let Some(dependencies) =
+                    self.fork_on_conflict(&metadata.requires_dist, extra.as_ref(), fork)
+                {
+                    return Ok(dependencies);
+                }
+
                 let mut constraints = PubGrubDependencies::from_requirements(
                     &metadata.requires_dist,
                     &self.constraints,
@@ -856,6 +998,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     &self.urls,
                     &self.locals,
                     self.markers,
+                    self.universal.then_some(fork),
                 )?;
 
                 for (package, version) in constraints.iter() {
@@ -879,6 +1022,22 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         }
     }
 
+    /// In universal mode, return [`Dependencies::Fork`] if the given requirements include
+    /// conflicting versions of a package under markers that can't hold at once.
+    fn fork_on_conflict(
+        &self,
+        requirements: &[Requirement],
+        extra: Option<&ExtraName>,
+        fork: &ForkMarkers,
+    ) -> Option<Dependencies> {
+        if !self.universal {
+            return None;
+        }
+        let (package_name, markers) =
+            find_conflict(requirements, &self.overrides, extra, self.markers, fork)?;
+        Some(Dependencies::Fork(package_name, markers))
+    }
+
     /// Fetch the metadata for a stream of packages and versions.
     async fn fetch(
         &self,
@@ -1128,6 +1287,18 @@ enum Dependencies {
     Unavailable(String),
     /// Container for all available package versions.
     Available(Vec<(PubGrubPackage, Range<Version>)>),
+    /// The dependencies conflict under the given markers, such that the resolution must fork.
+    Fork(PackageName, Vec<MarkerTree>),
+}
+
+/// The outcome of solving a single fork of the resolution.
+#[allow(clippy::large_enum_variant)]
+enum Solution {
+    /// The fork was resolved.
+    Resolved(ResolutionGraph),
+    /// The given package is required with conflicting versions under the given markers, such that
+    /// the fork must be split.
+    Fork(PackageName, Vec<MarkerTree>),
 }
 
 fn uncapitalize<T: AsRef<str>>(string: T) -> String {
@@ -2,6 +2,7 @@ use std::future::Future;
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use rustc_hash::FxHashMap;
 use url::Url;
 
 use distribution_types::{Dist, IndexLocations};
@@ -69,6 +70,7 @@ pub struct DefaultResolverProvider<'a, Context: BuildContext + Send + Sync> {
     python_requirement: PythonRequirement,
     allowed_yanks: AllowedYanks,
     exclude_newer: Option<DateTime<Utc>>,
+    exclude_newer_package: FxHashMap<PackageName, DateTime<Utc>>,
     no_binary: NoBinary,
     no_build: NoBuild,
 }
@@ -84,6 +86,7 @@ impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Contex
         python_requirement: PythonRequirement,
         allowed_yanks: AllowedYanks,
         exclude_newer: Option<DateTime<Utc>>,
+        exclude_newer_package: FxHashMap<PackageName, DateTime<Utc>>,
         no_binary: &'a NoBinary,
         no_build: &'a NoBuild,
     ) -> Self {
@@ -95,6 +98,7 @@ impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Contex
             python_requirement,
             allowed_yanks,
             exclude_newer,
+            exclude_newer_package,
             no_binary: no_binary.clone(),
             no_build: no_build.clone(),
         }
@@ -114,17 +118,26 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
         // If the "Simple API" request was successful, convert to `VersionMap` on the Tokio
         // threadpool, since it can be slow.
         match result {
-            Ok((index, metadata)) => Ok(VersionsResponse::Found(VersionMap::from_metadata(
-                metadata,
-                package_name,
-                &index,
-                &self.tags,
-                &self.python_requirement,
-                &self.allowed_yanks,
-                self.exclude_newer.as_ref(),
-                self.flat_index.get(package_name).cloned(),
-                &self.no_binary,
-                &self.no_build,
+            Ok(results) => Ok(VersionsResponse::Found(VersionMap::merge(
+                results
+                    .into_iter()
+                    .map(|(index, metadata)| {
+                        VersionMap::from_metadata(
+                            metadata,
+                            package_name,
+                            &index,
+                            &self.tags,
+                            &self.python_requirement,
+                            &self.allowed_yanks,
+                            self.exclude_newer_package
+                                .get(package_name)
+                                .or(self.exclude_newer.as_ref()),
+                            self.flat_index.get(package_name).cloned(),
+                            &self.no_binary,
+                            &self.no_build,
+                        )
+                    })
+                    .collect(),
             ))),
             Err(err) => match err.into_kind() {
                 uv_client::ErrorKind::PackageNotFound(_) => {
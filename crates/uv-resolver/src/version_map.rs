@@ -94,21 +94,14 @@ impl VersionMap {
             }
         }
         // Check if binaries are allowed for this package.
-        let no_binary = match no_binary {
-            NoBinary::None => false,
-            NoBinary::All => true,
-            NoBinary::Packages(packages) => packages.contains(package_name),
-        };
+        let no_binary = no_binary.no_binary_package(package_name);
         // Check if source distributions are allowed for this package.
-        let no_build = match no_build {
-            NoBuild::None => false,
-            NoBuild::All => true,
-            NoBuild::Packages(packages) => packages.contains(package_name),
-        };
+        let no_build = no_build.no_build_package(package_name);
         let allowed_yanks = allowed_yanks
             .allowed_versions(package_name)
             .cloned()
             .unwrap_or_default();
+        let allow_yanked = allowed_yanks.allows_package(package_name);
         Self {
             inner: VersionMapInner::Lazy(VersionMapLazy {
                 map,
@@ -120,10 +113,35 @@ impl VersionMap {
                 python_requirement: python_requirement.clone(),
                 exclude_newer: exclude_newer.copied(),
                 allowed_yanks,
+                allow_yanked,
             }),
         }
     }
 
+    /// Merge the [`VersionMap`]s for a package from multiple indexes, in priority order.
+    ///
+    /// If a version is available from multiple indexes, the distributions from the first such
+    /// index are used. Unlike a [`VersionMap`] for a single index, the merged map is eagerly
+    /// materialized.
+    pub(crate) fn merge(mut maps: Vec<Self>) -> Self {
+        if maps.len() == 1 {
+            return maps.remove(0);
+        }
+
+        let mut merged = BTreeMap::new();
+        for map in &maps {
+            for (version, handle) in map.iter() {
+                if merged.contains_key(version) {
+                    continue;
+                }
+                if let Some(prioritized_dist) = handle.prioritized_dist() {
+                    merged.insert(version.clone(), prioritized_dist.clone());
+                }
+            }
+        }
+        Self::from(merged)
+    }
+
     /// Return the [`DistFile`] for the given version, if any.
     pub(crate) fn get(&self, version: &Version) -> Option<&PrioritizedDist> {
         self.get_with_version(version).map(|(_version, dist)| dist)
@@ -297,6 +315,8 @@ struct VersionMapLazy {
     exclude_newer: Option<DateTime<Utc>>,
     /// Which yanked versions are allowed
     allowed_yanks: FxHashSet<Version>,
+    /// Whether any yanked version is allowed (e.g., via `--allow-yanked`).
+    allow_yanked: bool,
 }
 
 impl VersionMapLazy {
@@ -439,7 +459,7 @@ impl VersionMapLazy {
 
         // Check if yanked
         if let Some(yanked) = yanked {
-            if yanked.is_yanked() && !self.allowed_yanks.contains(version) {
+            if yanked.is_yanked() && !self.allow_yanked && !self.allowed_yanks.contains(version) {
                 return SourceDistCompatibility::Incompatible(IncompatibleSource::Yanked(yanked));
             }
         }
@@ -481,7 +501,7 @@ impl VersionMapLazy {
 
         // Check if yanked
         if let Some(yanked) = yanked {
-            if yanked.is_yanked() && !self.allowed_yanks.contains(version) {
+            if yanked.is_yanked() && !self.allow_yanked && !self.allowed_yanks.contains(version) {
                 return WheelCompatibility::Incompatible(IncompatibleWheel::Yanked(yanked));
             }
         }
@@ -7,13 +7,58 @@ use uv_normalize::PackageName;
 use crate::preferences::Preference;
 use crate::Manifest;
 
+/// The packages for which yanked distributions may be selected during resolution.
+///
+/// By default, yanked distributions are only selected when they're pinned exactly (e.g., via
+/// `flask==1.0.0` or an existing lockfile), per PEP 592.
+#[derive(Debug, Default, Clone)]
+pub enum AllowYanked {
+    /// Only select yanked distributions when they're pinned exactly.
+    #[default]
+    None,
+    /// Allow yanked distributions for all packages.
+    All,
+    /// Allow yanked distributions for a specific set of packages.
+    Packages(FxHashSet<PackageName>),
+}
+
+impl AllowYanked {
+    /// Determine the yanked policy from the command-line arguments, where an empty list of
+    /// packages indicates that yanked distributions are allowed for all packages.
+    pub fn from_args(allow_yanked: Option<Vec<PackageName>>) -> Self {
+        match allow_yanked {
+            None => Self::None,
+            Some(packages) if packages.is_empty() => Self::All,
+            Some(packages) => Self::Packages(packages.into_iter().collect()),
+        }
+    }
+
+    /// Returns `true` if yanked distributions are allowed for the given package.
+    pub fn contains(&self, package_name: &PackageName) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Packages(packages) => packages.contains(package_name),
+        }
+    }
+}
+
 /// A set of package versions that are permitted, even if they're marked as yanked by the
 /// relevant index.
 #[derive(Debug, Default, Clone)]
-pub struct AllowedYanks(FxHashMap<PackageName, FxHashSet<Version>>);
+pub struct AllowedYanks {
+    /// The versions that were pinned exactly, and so are allowed even if yanked.
+    versions: FxHashMap<PackageName, FxHashSet<Version>>,
+    /// The packages for which any yanked version is allowed.
+    packages: AllowYanked,
+}
 
 impl AllowedYanks {
-    pub(crate) fn from_manifest(manifest: &Manifest, markers: &MarkerEnvironment) -> Self {
+    pub(crate) fn from_manifest(
+        manifest: &Manifest,
+        markers: &MarkerEnvironment,
+        allow_yanked: AllowYanked,
+    ) -> Self {
         let mut allowed_yanks = FxHashMap::<PackageName, FxHashSet<Version>>::default();
         for requirement in manifest
             .requirements
@@ -47,7 +92,10 @@ impl AllowedYanks {
                     .insert(specifier.version().clone());
             }
         }
-        Self(allowed_yanks)
+        Self {
+            versions: allowed_yanks,
+            packages: allow_yanked,
+        }
     }
 
     /// Returns versions for the given package which are allowed even if marked as yanked by the
@@ -56,6 +104,11 @@ impl AllowedYanks {
         &self,
         package_name: &PackageName,
     ) -> Option<&FxHashSet<Version>> {
-        self.0.get(package_name)
+        self.versions.get(package_name)
+    }
+
+    /// Returns `true` if any yanked version of the given package is allowed.
+    pub(crate) fn allows_package(&self, package_name: &PackageName) -> bool {
+        self.packages.contains(package_name)
     }
 }
@@ -21,7 +21,8 @@ use uv_resolver::{
     Preference, ResolutionGraph, ResolutionMode, Resolver,
 };
 use uv_traits::{
-    BuildContext, BuildIsolation, BuildKind, NoBinary, NoBuild, SetupPyStrategy, SourceBuildTrait,
+    BuildContext, BuildIsolation, BuildKind, Concurrency, NoBinary, NoBuild, RequiredHashes,
+    SetupPyStrategy, SourceBuildTrait,
 };
 
 // Exclude any packages uploaded after this date.
@@ -35,6 +36,7 @@ struct DummyContext {
     cache: Cache,
     interpreter: Interpreter,
     index_locations: IndexLocations,
+    required_hashes: RequiredHashes,
 }
 
 impl DummyContext {
@@ -43,6 +45,7 @@ impl DummyContext {
             cache,
             interpreter,
             index_locations: IndexLocations::default(),
+            required_hashes: RequiredHashes::default(),
         }
     }
 }
@@ -74,10 +77,18 @@ impl BuildContext for DummyContext {
         SetupPyStrategy::default()
     }
 
+    fn concurrency(&self) -> Concurrency {
+        Concurrency::default()
+    }
+
     fn index_locations(&self) -> &IndexLocations {
         &self.index_locations
     }
 
+    fn required_hashes(&self) -> &RequiredHashes {
+        &self.required_hashes
+    }
+
     async fn resolve<'a>(&'a self, _: &'a [Requirement]) -> Result<Resolution> {
         panic!("The test should not need to build source distributions")
     }
@@ -680,6 +691,7 @@ static TAGS_311: Lazy<Tags> = Lazy::new(|| {
         (3, 11),
         "cpython",
         (3, 11),
+        false,
     )
     .unwrap()
 });
@@ -712,6 +724,7 @@ static TAGS_310: Lazy<Tags> = Lazy::new(|| {
         (3, 10),
         "cpython",
         (3, 10),
+        false,
     )
     .unwrap()
 });
@@ -4,6 +4,7 @@ use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::future::Future;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
@@ -12,6 +13,7 @@ use anyhow::Result;
 use distribution_types::{CachedDist, DistributionId, IndexLocations, Resolution, SourceDist};
 use once_map::OnceMap;
 use pep508_rs::Requirement;
+use rustc_hash::FxHashMap;
 use uv_cache::Cache;
 use uv_interpreter::{Interpreter, PythonEnvironment};
 use uv_normalize::PackageName;
@@ -78,9 +80,15 @@ pub trait BuildContext: Sync {
     /// The index locations being searched.
     fn index_locations(&self) -> &IndexLocations;
 
+    /// The hashes that downloaded distributions must match, in hash-checking mode.
+    fn required_hashes(&self) -> &RequiredHashes;
+
     /// The strategy to use when building source distributions that lack a `pyproject.toml`.
     fn setup_py_strategy(&self) -> SetupPyStrategy;
 
+    /// The limits on the number of concurrent downloads and builds.
+    fn concurrency(&self) -> Concurrency;
+
     /// Resolve the given requirements into a ready-to-install set of package versions.
     fn resolve<'a>(
         &'a self,
@@ -140,17 +148,66 @@ pub struct InFlight {
     pub downloads: OnceMap<DistributionId, Result<CachedDist, String>>,
 }
 
+/// The limits on the number of concurrent operations.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Concurrency {
+    /// The maximum number of concurrent downloads.
+    pub downloads: usize,
+    /// The maximum number of concurrent source distribution builds.
+    pub builds: usize,
+}
+
+impl Concurrency {
+    /// The default maximum number of concurrent downloads.
+    pub const DEFAULT_DOWNLOADS: usize = 50;
+
+    /// The default maximum number of concurrent builds, i.e., the number of available cores.
+    pub fn default_builds() -> usize {
+        std::thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1)
+    }
+}
+
+impl Default for Concurrency {
+    fn default() -> Self {
+        Self {
+            downloads: Self::DEFAULT_DOWNLOADS,
+            builds: Self::default_builds(),
+        }
+    }
+}
+
 /// Whether to enforce build isolation when building source distributions.
 #[derive(Debug, Copy, Clone)]
 pub enum BuildIsolation<'a> {
     Isolated,
     Shared(&'a PythonEnvironment),
+    /// Build the given packages in the shared environment, and all other packages in isolation.
+    SharedPackage(&'a PythonEnvironment, &'a [PackageName]),
 }
 
 impl<'a> BuildIsolation<'a> {
     /// Returns `true` if build isolation is enforced.
     pub fn is_isolated(&self) -> bool {
-        matches!(self, Self::Isolated)
+        !matches!(self, Self::Shared(_))
+    }
+
+    /// Resolve the build isolation to use for the given package, if known.
+    ///
+    /// Packages whose names aren't known ahead of the build (e.g., editables) are built in
+    /// isolation, unless isolation is disabled globally.
+    #[must_use]
+    pub fn for_package(self, package_name: Option<&PackageName>) -> Self {
+        match self {
+            Self::SharedPackage(venv, packages)
+                if package_name.is_some_and(|package_name| packages.contains(package_name)) =>
+            {
+                Self::Shared(venv)
+            }
+            Self::SharedPackage(..) => Self::Isolated,
+            isolation => isolation,
+        }
     }
 }
 
@@ -238,7 +295,7 @@ impl PackageNameSpecifiers {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum NoBinary {
     /// Allow installation of any wheel.
     None,
@@ -246,6 +303,9 @@ pub enum NoBinary {
     /// Do not allow installation from any wheels.
     All,
 
+    /// Do not allow installation from any wheels, except for those of the given packages.
+    AllExcept(Vec<PackageName>),
+
     /// Do not allow installation from the specific wheels.
     Packages(Vec<PackageName>),
 }
@@ -260,6 +320,24 @@ impl NoBinary {
             PackageNameSpecifiers::Packages(packages) => Self::Packages(packages),
         }
     }
+
+    /// Determine the binary installation strategy to use, given both the `--no-binary` and
+    /// `--only-binary` arguments.
+    ///
+    /// Like pip, packages that are named explicitly in `--only-binary` are exempt from
+    /// `--no-binary :all:`.
+    pub fn from_pip_args(
+        no_binary: Vec<PackageNameSpecifier>,
+        only_binary: &[PackageNameSpecifier],
+    ) -> Self {
+        match Self::from_args(no_binary) {
+            Self::All => match PackageNameSpecifiers::from_iter(only_binary.iter().cloned()) {
+                PackageNameSpecifiers::Packages(packages) => Self::AllExcept(packages),
+                PackageNameSpecifiers::All | PackageNameSpecifiers::None => Self::All,
+            },
+            no_binary => no_binary,
+        }
+    }
 }
 
 impl NoBinary {
@@ -267,6 +345,16 @@ impl NoBinary {
     pub fn is_none(&self) -> bool {
         matches!(self, Self::None)
     }
+
+    /// Returns `true` if installation from wheels is disabled for the given package.
+    pub fn no_binary_package(&self, package_name: &PackageName) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::AllExcept(packages) => !packages.contains(package_name),
+            Self::Packages(packages) => packages.contains(package_name),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -277,6 +365,10 @@ pub enum NoBuild {
     /// Do not allow building wheels from any source distribution.
     All,
 
+    /// Do not allow building wheels from any source distribution, except for those of the given
+    /// packages.
+    AllExcept(Vec<PackageName>),
+
     /// Do not allow building wheels from the given package's source distributions.
     Packages(Vec<PackageName>),
 }
@@ -295,6 +387,40 @@ impl NoBuild {
             }
         }
     }
+
+    /// Determine the build strategy to use, given the `--only-binary`, `--no-build`, and
+    /// `--no-binary` arguments.
+    ///
+    /// Like pip, packages that are named explicitly in `--no-binary` are exempt from
+    /// `--only-binary :all:` (but not from `--no-build`).
+    pub fn from_pip_args(
+        only_binary: Vec<PackageNameSpecifier>,
+        no_build: bool,
+        no_binary: &[PackageNameSpecifier],
+    ) -> Self {
+        if no_build {
+            return Self::All;
+        }
+        match Self::from_args(only_binary, false) {
+            Self::All => match PackageNameSpecifiers::from_iter(no_binary.iter().cloned()) {
+                PackageNameSpecifiers::Packages(packages) => Self::AllExcept(packages),
+                PackageNameSpecifiers::All | PackageNameSpecifiers::None => Self::All,
+            },
+            no_build => no_build,
+        }
+    }
+
+    /// Determine the build strategy to use, given the `--no-sdist` and `--allow-sdist` arguments.
+    ///
+    /// Source distributions are forbidden for all packages, except for those that are allowed
+    /// explicitly. As with any other strategy, editables are still built.
+    pub fn from_allowed_sdists(allowed: Vec<PackageName>) -> Self {
+        if allowed.is_empty() {
+            Self::All
+        } else {
+            Self::AllExcept(allowed)
+        }
+    }
 }
 
 impl NoBuild {
@@ -302,6 +428,42 @@ impl NoBuild {
     pub fn is_none(&self) -> bool {
         matches!(self, Self::None)
     }
+
+    /// Returns `true` if building wheels from source distributions is disabled for the given
+    /// package.
+    pub fn no_build_package(&self, package_name: &PackageName) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::AllExcept(packages) => !packages.contains(package_name),
+            Self::Packages(packages) => packages.contains(package_name),
+        }
+    }
+}
+
+/// The hashes that distributions must match in hash-checking mode (e.g., if any requirement
+/// includes a `--hash`, or `--require-hashes` is provided), keyed by package name.
+///
+/// Hashes are stored as `{algorithm}:{digest}`, like `sha256:0123abcd...`. If empty, hash-checking
+/// mode is disabled, and downloads are only verified against the hashes reported by the index.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RequiredHashes(FxHashMap<PackageName, Vec<String>>);
+
+impl RequiredHashes {
+    /// Require the given hashes for each package.
+    pub fn new(hashes: FxHashMap<PackageName, Vec<String>>) -> Self {
+        Self(hashes)
+    }
+
+    /// Returns `true` if hash-checking mode is disabled.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Return the hashes that the distributions of the given package must match, if any.
+    pub fn get(&self, package_name: &PackageName) -> Option<&[String]> {
+        self.0.get(package_name).map(Vec::as_slice)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -366,6 +528,98 @@ impl FromIterator<ConfigSettingEntry> for ConfigSettings {
     }
 }
 
+impl ConfigSettings {
+    /// Merge two sets of config settings, such that values for the same key are combined into a
+    /// list, with the values in `self` first.
+    #[must_use]
+    pub fn merge(self, other: &ConfigSettings) -> ConfigSettings {
+        let mut config = self.0;
+        for (key, value) in &other.0 {
+            match config.entry(key.clone()) {
+                Entry::Vacant(vacant) => {
+                    vacant.insert(value.clone());
+                }
+                Entry::Occupied(mut occupied) => {
+                    let mut values = match occupied.get() {
+                        ConfigSettingValue::String(existing) => vec![existing.clone()],
+                        ConfigSettingValue::List(existing) => existing.clone(),
+                    };
+                    match value {
+                        ConfigSettingValue::String(value) => values.push(value.clone()),
+                        ConfigSettingValue::List(value) => values.extend(value.iter().cloned()),
+                    }
+                    occupied.insert(ConfigSettingValue::List(values));
+                }
+            }
+        }
+        Self(config)
+    }
+}
+
+/// A [`ConfigSettingEntry`] that only applies to a specific package, like `numpy:KEY=VALUE`.
+#[derive(Debug, Clone)]
+pub struct ConfigSettingPackageEntry {
+    /// The package to which the setting applies.
+    package: PackageName,
+    /// The setting itself.
+    setting: ConfigSettingEntry,
+}
+
+impl FromStr for ConfigSettingPackageEntry {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((package, setting)) = s.split_once(':') else {
+            return Err(anyhow::anyhow!(
+                "Invalid config setting: {s} (expected `PACKAGE:KEY=VALUE`)"
+            ));
+        };
+        Ok(Self {
+            package: PackageName::from_str(package.trim())?,
+            setting: ConfigSettingEntry::from_str(setting)?,
+        })
+    }
+}
+
+/// Settings to pass to the PEP 517 build backends of specific packages, in addition to any
+/// [`ConfigSettings`] that apply to all packages.
+#[derive(Debug, Default, Clone)]
+pub struct PackageConfigSettings(BTreeMap<PackageName, ConfigSettings>);
+
+impl FromIterator<ConfigSettingPackageEntry> for PackageConfigSettings {
+    fn from_iter<T: IntoIterator<Item = ConfigSettingPackageEntry>>(iter: T) -> Self {
+        let mut entries: BTreeMap<PackageName, Vec<ConfigSettingEntry>> = BTreeMap::default();
+        for entry in iter {
+            entries
+                .entry(entry.package)
+                .or_default()
+                .push(entry.setting);
+        }
+        Self(
+            entries
+                .into_iter()
+                .map(|(package, settings)| (package, settings.into_iter().collect()))
+                .collect(),
+        )
+    }
+}
+
+impl PackageConfigSettings {
+    /// Returns the settings to pass to the build backend of the given package, combining the
+    /// settings for all packages with those for the given package.
+    pub fn for_package(&self, package: &PackageName, global: &ConfigSettings) -> ConfigSettings {
+        match self.0.get(package) {
+            Some(settings) => global.clone().merge(settings),
+            None => global.clone(),
+        }
+    }
+
+    /// Returns `true` if no package-specific settings were provided.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
 #[cfg(feature = "serde")]
 impl ConfigSettings {
     /// Convert the settings to a string that can be passed directly to a PEP 517 build backend.
@@ -456,6 +710,60 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn from_pip_args() -> Result<(), Error> {
+        let foo = PackageName::from_str("foo")?;
+        let bar = PackageName::from_str("bar")?;
+
+        // `--no-binary :all: --only-binary foo`
+        let no_binary = NoBinary::from_pip_args(
+            vec![PackageNameSpecifier::All],
+            &[PackageNameSpecifier::from_str("foo")?],
+        );
+        let no_build = NoBuild::from_pip_args(
+            vec![PackageNameSpecifier::from_str("foo")?],
+            false,
+            &[PackageNameSpecifier::All],
+        );
+        assert_eq!(no_binary, NoBinary::AllExcept(vec![foo.clone()]));
+        assert_eq!(no_build, NoBuild::Packages(vec![foo.clone()]));
+        assert!(!no_binary.no_binary_package(&foo));
+        assert!(no_build.no_build_package(&foo));
+        assert!(no_binary.no_binary_package(&bar));
+        assert!(!no_build.no_build_package(&bar));
+
+        // `--only-binary :all: --no-binary foo`
+        let no_binary = NoBinary::from_pip_args(
+            vec![PackageNameSpecifier::from_str("foo")?],
+            &[PackageNameSpecifier::All],
+        );
+        let no_build = NoBuild::from_pip_args(
+            vec![PackageNameSpecifier::All],
+            false,
+            &[PackageNameSpecifier::from_str("foo")?],
+        );
+        assert_eq!(no_binary, NoBinary::Packages(vec![foo.clone()]));
+        assert_eq!(no_build, NoBuild::AllExcept(vec![foo.clone()]));
+        assert!(no_binary.no_binary_package(&foo));
+        assert!(!no_build.no_build_package(&foo));
+        assert!(!no_binary.no_binary_package(&bar));
+        assert!(no_build.no_build_package(&bar));
+
+        // `--no-build` isn't overridden by `--no-binary`.
+        assert_eq!(
+            NoBuild::from_pip_args(vec![], true, &[PackageNameSpecifier::from_str("foo")?]),
+            NoBuild::All,
+        );
+
+        // `--no-sdist --allow-sdist foo`
+        let no_build = NoBuild::from_allowed_sdists(vec![foo.clone()]);
+        assert!(!no_build.no_build_package(&foo));
+        assert!(no_build.no_build_package(&bar));
+        assert_eq!(NoBuild::from_allowed_sdists(vec![]), NoBuild::All);
+
+        Ok(())
+    }
+
     #[test]
     fn collect_config_settings() {
         let settings: ConfigSettings = vec![
@@ -494,6 +802,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn package_config_settings() -> Result<(), Error> {
+        let global: ConfigSettings = vec![ConfigSettingEntry::from_str("key=value")?]
+            .into_iter()
+            .collect();
+        let packages: PackageConfigSettings = vec![
+            ConfigSettingPackageEntry::from_str("foo:key=value2")?,
+            ConfigSettingPackageEntry::from_str("foo:other=value3")?,
+        ]
+        .into_iter()
+        .collect();
+
+        let foo = packages.for_package(&PackageName::from_str("foo")?, &global);
+        assert_eq!(
+            foo.0.get("key"),
+            Some(&ConfigSettingValue::List(vec![
+                "value".to_string(),
+                "value2".to_string()
+            ]))
+        );
+        assert_eq!(
+            foo.0.get("other"),
+            Some(&ConfigSettingValue::String("value3".to_string()))
+        );
+
+        let bar = packages.for_package(&PackageName::from_str("bar")?, &global);
+        assert_eq!(
+            bar.0.get("key"),
+            Some(&ConfigSettingValue::String("value".to_string()))
+        );
+        assert_eq!(bar.0.get("other"), None);
+
+        assert!(ConfigSettingPackageEntry::from_str("key=value").is_err());
+
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn escape_for_python() {
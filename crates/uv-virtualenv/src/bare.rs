@@ -33,6 +33,26 @@ const ACTIVATE_TEMPLATES: &[(&str, &str)] = &[
 ];
 const VIRTUALENV_PATCH: &str = include_str!("_virtualenv.py");
 
+/// Return an expression that locates the virtual environment relative to the given activation
+/// script at activation time, for use in place of its absolute path in relocatable environments.
+///
+/// Returns `None` for scripts that can't determine their own location (e.g., `activate.csh`), or
+/// that always determine it (e.g., `activate.ps1` and `activate_this.py`).
+fn relocatable_virtual_env_dir(name: &str) -> Option<&'static str> {
+    match name {
+        // Ex) `VIRTUAL_ENV=''"$(dirname -- ...)"''`
+        "activate" => Some(
+            r#"'"$(dirname -- "$(CDPATH= cd -- "$(dirname -- "${BASH_SOURCE[0]:-${(%):-%x}}")" > /dev/null && echo "$PWD")")"'"#,
+        ),
+        // Ex) `set -gx VIRTUAL_ENV ''(dirname ...)''`
+        "activate.fish" => Some("'(dirname (dirname (realpath (status filename))))'"),
+        // Ex) `let virtual_env = '' + ($env.CURRENT_FILE | ...) + ''`
+        "activate.nu" => Some("' + ($env.CURRENT_FILE | path dirname | path dirname) + '"),
+        "activate.bat" => Some("%~dp0.."),
+        _ => None,
+    }
+}
+
 /// Very basic `.cfg` file format writer.
 fn write_cfg(f: &mut impl Write, data: &[(String, String)]) -> io::Result<()> {
     for (key, value) in data {
@@ -47,6 +67,7 @@ pub fn create_bare_venv(
     interpreter: &Interpreter,
     prompt: Prompt,
     system_site_packages: bool,
+    relocatable: bool,
     extra_cfg: Vec<(String, String)>,
 ) -> Result<Virtualenv, Error> {
     // Determine the base Python executable; that is, the Python executable that should be
@@ -160,6 +181,30 @@ pub fn create_bare_venv(
                 interpreter.python_minor(),
             )),
         )?;
+
+        // PyPy and GraalPy also expose their interpreters under the implementation name (e.g.,
+        // `pypy3.10` or `graalpy`).
+        match interpreter.implementation_name() {
+            "pypy" => {
+                symlink("python", scripts.join("pypy"))?;
+                symlink(
+                    "python",
+                    scripts.join(format!("pypy{}", interpreter.python_major())),
+                )?;
+                symlink(
+                    "python",
+                    scripts.join(format!(
+                        "pypy{}.{}",
+                        interpreter.python_major(),
+                        interpreter.python_minor(),
+                    )),
+                )?;
+            }
+            "graalpy" => {
+                symlink("python", scripts.join("graalpy"))?;
+            }
+            _ => {}
+        }
     }
 
     // No symlinking on Windows, at least not on a regular non-dev non-admin Windows install.
@@ -224,13 +269,15 @@ pub fn create_bare_venv(
             &interpreter.virtualenv().scripts,
         )
         .expect("Failed to calculate relative path to site-packages");
+        let virtual_env_dir = relocatable
+            .then(|| relocatable_virtual_env_dir(name))
+            .flatten()
+            // SAFETY: `unwrap` is guaranteed to succeed because `location` is an `Utf8PathBuf`.
+            .unwrap_or_else(|| location.simplified().to_str().unwrap());
         let activator = template
-            .replace(
-                "{{ VIRTUAL_ENV_DIR }}",
-                // SAFETY: `unwrap` is guaranteed to succeed because `location` is an `Utf8PathBuf`.
-                location.simplified().to_str().unwrap(),
-            )
+            .replace("{{ VIRTUAL_ENV_DIR }}", virtual_env_dir)
             .replace("{{ BIN_NAME }}", bin_name)
+            .replace("{{ PATH_SEP }}", if cfg!(windows) { ";" } else { ":" })
             .replace(
                 "{{ VIRTUAL_PROMPT }}",
                 prompt.as_deref().unwrap_or_default(),
@@ -264,6 +311,7 @@ pub fn create_bare_venv(
         "base-exec-prefix",
         "base-executable",
         "prompt",
+        "relocatable",
     ];
     for (key, _) in &extra_cfg {
         if reserved_keys.contains(&key.as_str()) {
@@ -298,6 +346,10 @@ pub fn create_bare_venv(
         pyvenv_cfg_data.push(("prompt".to_string(), prompt));
     }
 
+    if relocatable {
+        pyvenv_cfg_data.push(("relocatable".to_string(), "true".to_string()));
+    }
+
     let mut pyvenv_cfg = BufWriter::new(File::create(location.join("pyvenv.cfg"))?);
     write_cfg(&mut pyvenv_cfg, &pyvenv_cfg_data)?;
     drop(pyvenv_cfg);
@@ -1,5 +1,5 @@
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use platform_tags::PlatformError;
 use thiserror::Error;
@@ -20,6 +20,8 @@ pub enum Error {
     Platform(#[from] PlatformError),
     #[error("Reserved key used for pyvenv.cfg: {0}")]
     ReservedConfigKey(String),
+    #[error("Expected `{}` to be a virtual environment, but `pyvenv.cfg` is missing", _0.display())]
+    MissingPyVenvCfg(PathBuf),
 }
 
 /// The value to use for the shell prompt when inside a virtual environment.
@@ -51,6 +53,7 @@ pub fn create_venv(
     interpreter: Interpreter,
     prompt: Prompt,
     system_site_packages: bool,
+    relocatable: bool,
     extra_cfg: Vec<(String, String)>,
 ) -> Result<PythonEnvironment, Error> {
     // Create the virtualenv at the given location.
@@ -59,6 +62,7 @@ pub fn create_venv(
         &interpreter,
         prompt,
         system_site_packages,
+        relocatable,
         extra_cfg,
     )?;
 
@@ -66,3 +70,42 @@ pub fn create_venv(
     let interpreter = interpreter.with_virtualenv(virtualenv);
     Ok(PythonEnvironment::from_interpreter(interpreter))
 }
+
+/// Enable or disable access to the system `site-packages` for an existing virtual environment, by
+/// updating the `include-system-site-packages` key in its `pyvenv.cfg`.
+///
+/// All other keys in the `pyvenv.cfg` are preserved as-is.
+pub fn set_system_site_packages(location: &Path, system_site_packages: bool) -> Result<(), Error> {
+    let cfg = location.join("pyvenv.cfg");
+    let contents = match fs_err::read_to_string(&cfg) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(Error::MissingPyVenvCfg(location.to_path_buf()));
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let value = if system_site_packages {
+        "true"
+    } else {
+        "false"
+    };
+
+    let mut found = false;
+    let mut lines = contents
+        .lines()
+        .map(|line| match line.split_once('=') {
+            Some((key, _)) if key.trim() == "include-system-site-packages" => {
+                found = true;
+                format!("include-system-site-packages = {value}")
+            }
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>();
+    if !found {
+        lines.push(format!("include-system-site-packages = {value}"));
+    }
+
+    fs_err::write(&cfg, lines.join("\n") + "\n")?;
+    Ok(())
+}
@@ -24,6 +24,8 @@ struct Cli {
     prompt: Option<String>,
     #[clap(long)]
     system_site_packages: bool,
+    #[clap(long)]
+    relocatable: bool,
 }
 
 fn run() -> Result<(), uv_virtualenv::Error> {
@@ -46,6 +48,7 @@ fn run() -> Result<(), uv_virtualenv::Error> {
         &interpreter,
         Prompt::from_args(cli.prompt),
         cli.system_site_packages,
+        cli.relocatable,
         Vec::new(),
     )?;
     Ok(())
@@ -1,33 +1,63 @@
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 
-// macro hygiene: The user might not have direct dependencies on those crates
-#[doc(hidden)]
-pub use anstream;
+use anstream::eprintln;
 use once_cell::sync::Lazy;
-#[doc(hidden)]
-pub use owo_colors;
+use owo_colors::OwoColorize;
 use rustc_hash::FxHashSet;
 
 /// Whether user-facing warnings are enabled.
 pub static ENABLED: AtomicBool = AtomicBool::new(false);
 
+/// Whether user-facing warnings are collected (to be retrieved via [`take`]), rather than
+/// printed to stderr.
+static COLLECT: AtomicBool = AtomicBool::new(false);
+
+/// The user-facing warnings collected so far.
+static COLLECTED: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+
 /// Enable user-facing warnings.
 pub fn enable() {
-    ENABLED.store(true, std::sync::atomic::Ordering::SeqCst);
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+/// Collect user-facing warnings, rather than printing them to stderr (e.g., to include them in a
+/// structured error report).
+pub fn collect() {
+    COLLECT.store(true, Ordering::SeqCst);
+}
+
+/// Take the user-facing warnings collected so far.
+pub fn take() -> Vec<String> {
+    COLLECTED
+        .lock()
+        .map(|mut warnings| std::mem::take(&mut *warnings))
+        .unwrap_or_default()
+}
+
+/// Print a user-facing warning to stderr, or collect it, if warnings are being collected.
+#[doc(hidden)]
+pub fn emit(message: String) {
+    if COLLECT.load(Ordering::SeqCst) {
+        if let Ok(mut warnings) = COLLECTED.lock() {
+            warnings.push(message);
+        }
+    } else {
+        eprintln!(
+            "{}{} {}",
+            "warning".yellow().bold(),
+            ":".bold(),
+            message.bold()
+        );
+    }
 }
 
 /// Warn a user, if warnings are enabled.
 #[macro_export]
 macro_rules! warn_user {
     ($($arg:tt)*) => {
-        use $crate::anstream::eprintln;
-        use $crate::owo_colors::OwoColorize;
-
         if $crate::ENABLED.load(std::sync::atomic::Ordering::SeqCst) {
-            let message = format!("{}", format_args!($($arg)*));
-            let formatted = message.bold();
-            eprintln!("{}{} {formatted}", "warning".yellow().bold(), ":".bold());
+            $crate::emit(format!("{}", format_args!($($arg)*)));
         }
     };
 }
@@ -39,14 +69,11 @@ pub static WARNINGS: Lazy<Mutex<FxHashSet<String>>> = Lazy::new(Mutex::default);
 #[macro_export]
 macro_rules! warn_user_once {
     ($($arg:tt)*) => {
-        use $crate::anstream::eprintln;
-        use $crate::owo_colors::OwoColorize;
-
         if $crate::ENABLED.load(std::sync::atomic::Ordering::SeqCst) {
             if let Ok(mut states) = $crate::WARNINGS.lock() {
                 let message = format!("{}", format_args!($($arg)*));
                 if states.insert(message.clone()) {
-                    eprintln!("{}{} {}", "warning".yellow().bold(), ":".bold(), message.bold());
+                    $crate::emit(message);
                 }
             }
         }
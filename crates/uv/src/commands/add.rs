@@ -0,0 +1,242 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Instant;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::{DistributionMetadata, IndexLocations, Resolution, VersionOrUrl};
+use pep440_rs::{Operator, VersionSpecifier, VersionSpecifiers};
+use pep508_rs::Requirement;
+use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
+use uv_cache::Cache;
+use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder, TrustedHost};
+use uv_dispatch::BuildDispatch;
+use uv_installer::NoBinary;
+use uv_interpreter::Interpreter;
+use uv_resolver::{InMemoryIndex, OptionsBuilder, ResolutionMode};
+use uv_traits::{
+    BuildContext, BuildIsolation, Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy,
+};
+
+use crate::commands::lock::find_interpreter;
+use crate::commands::{elapsed, lock, ExitStatus, Upgrade};
+use crate::printer::Printer;
+use crate::pyproject_mut::{DependencyTarget, PyProjectTomlMut};
+
+/// Add one or more requirements to the project's `pyproject.toml`, optionally re-locking the
+/// project afterwards.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn add(
+    requirements: Vec<String>,
+    target: DependencyTarget,
+    relock: bool,
+    python: Option<&str>,
+    index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    exclude_newer: Option<DateTime<Utc>>,
+    native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let path = std::env::current_dir()?.join("pyproject.toml");
+    let mut pyproject = PyProjectTomlMut::from_toml(&read_pyproject(&path)?)?;
+
+    let mut requirements = requirements
+        .iter()
+        .map(|requirement| {
+            Requirement::from_str(requirement)
+                .with_context(|| format!("Failed to parse `{requirement}`"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // For any requirements without a version specifier, resolve the latest compatible version
+    // and use it as a lower bound.
+    if requirements
+        .iter()
+        .any(|requirement| requirement.version_or_url.is_none())
+    {
+        let start = Instant::now();
+
+        let interpreter = find_interpreter(python, cache)?;
+        debug!(
+            "Resolving requirements with: {}",
+            interpreter.sys_executable().display()
+        );
+
+        let resolution = resolve(
+            &requirements,
+            &interpreter,
+            &index_locations,
+            keyring_provider,
+            trusted_hosts.clone(),
+            connectivity,
+            exclude_newer,
+            native_tls,
+            cert,
+            proxy,
+            retries,
+            concurrency,
+            cache,
+        )
+        .await?;
+
+        let s = if resolution.len() == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Resolved {} in {}",
+                format!("{} package{}", resolution.len(), s).bold(),
+                elapsed(start.elapsed())
+            )
+            .dimmed()
+        )?;
+
+        for requirement in &mut requirements {
+            if requirement.version_or_url.is_some() {
+                continue;
+            }
+            let Some(dist) = resolution.get(&requirement.name) else {
+                continue;
+            };
+            let VersionOrUrl::Version(version) = dist.version_or_url() else {
+                continue;
+            };
+            if let Ok(specifier) =
+                VersionSpecifier::from_version(Operator::GreaterThanEqual, version.clone())
+            {
+                requirement.version_or_url = Some(pep508_rs::VersionOrUrl::VersionSpecifier(
+                    VersionSpecifiers::from(specifier),
+                ));
+            }
+        }
+    }
+
+    for requirement in &requirements {
+        pyproject.add_dependency(requirement, &target)?;
+    }
+    uv_fs::write_atomic_sync(&path, pyproject.to_string())?;
+
+    for requirement in &requirements {
+        writeln!(
+            printer.stderr(),
+            "Added `{}` to {target}",
+            requirement.to_string().cyan()
+        )?;
+    }
+
+    if relock {
+        return lock(
+            Upgrade::None,
+            ResolutionMode::default(),
+            python,
+            index_locations,
+            keyring_provider,
+            trusted_hosts,
+            connectivity,
+            exclude_newer,
+            FxHashMap::default(),
+            native_tls,
+            cert,
+            proxy,
+            retries,
+            concurrency,
+            cache,
+            printer,
+        )
+        .await;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Read the `pyproject.toml` at the given path.
+pub(crate) fn read_pyproject(path: &Path) -> Result<String> {
+    match fs_err::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Err(anyhow!(
+            "No `pyproject.toml` found in the current directory"
+        )),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Resolve the given requirements against the interpreter.
+#[allow(clippy::too_many_arguments)]
+async fn resolve(
+    requirements: &[Requirement],
+    interpreter: &Interpreter,
+    index_locations: &IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    exclude_newer: Option<DateTime<Utc>>,
+    native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
+    cache: &Cache,
+) -> Result<Resolution> {
+    // Add all authenticated sources to the store.
+    for url in index_locations.urls() {
+        GLOBAL_AUTH_STORE.save_from_url(url);
+    }
+
+    // Instantiate a client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .native_tls(native_tls)
+        .cert(cert)
+        .proxy(proxy)
+        .retries(retries)
+        .index_urls(index_locations.index_urls())
+        .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_hosts)
+        .connectivity(connectivity)
+        .markers(interpreter.markers())
+        .platform(interpreter.platform())
+        .build();
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let tags = interpreter.tags()?;
+        let client = FlatIndexClient::new(&client, cache);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, tags)
+    };
+
+    let index = InMemoryIndex::default();
+    let in_flight = InFlight::default();
+    let config_settings = ConfigSettings::default();
+
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        cache,
+        interpreter,
+        index_locations,
+        &flat_index,
+        &index,
+        &in_flight,
+        SetupPyStrategy::default(),
+        &config_settings,
+        BuildIsolation::Isolated,
+        &NoBuild::None,
+        &NoBinary::None,
+    )
+    .with_concurrency(concurrency)
+    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+
+    build_dispatch.resolve(requirements).await
+}
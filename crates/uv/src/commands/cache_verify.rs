@@ -0,0 +1,122 @@
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use install_wheel_rs::verify_record;
+use uv_cache::{Cache, CacheBucket};
+use uv_fs::{directories, Simplified};
+
+use crate::commands::{human_readable_bytes, ExitStatus};
+use crate::printer::Printer;
+
+/// Verify the unzipped wheels in the cache against the hashes recorded in their `RECORD` files,
+/// optionally removing any corrupted entries.
+pub(crate) fn cache_verify(repair: bool, cache: &Cache, printer: Printer) -> Result<ExitStatus> {
+    if !cache.root().exists() {
+        writeln!(
+            printer.stderr(),
+            "No cache found at: {}",
+            cache.root().user_display().cyan()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    writeln!(
+        printer.stderr(),
+        "Verifying cache at: {}",
+        cache.root().user_display().cyan()
+    )?;
+
+    // Avoid racing with other processes if we're going to modify the cache.
+    let _lock = repair
+        .then(|| cache.lock_exclusive())
+        .transpose()
+        .with_context(|| format!("Failed to lock cache at: {}", cache.root().user_display()))?;
+
+    let mut num_verified = 0;
+    let mut corrupted = Vec::new();
+    for archive in directories(cache.bucket(CacheBucket::Archive)) {
+        num_verified += 1;
+        match verify_record(&archive) {
+            Ok(mismatches) if mismatches.is_empty() => {}
+            Ok(mismatches) => {
+                writeln!(
+                    printer.stderr(),
+                    "{}: {}",
+                    "Corrupted".red().bold(),
+                    archive.user_display()
+                )?;
+                for mismatch in mismatches {
+                    writeln!(printer.stderr(), "  - {mismatch}")?;
+                }
+                corrupted.push(archive);
+            }
+            Err(err) => {
+                writeln!(
+                    printer.stderr(),
+                    "{}: {}",
+                    "Corrupted".red().bold(),
+                    archive.user_display()
+                )?;
+                writeln!(printer.stderr(), "  - {err}")?;
+                corrupted.push(archive);
+            }
+        }
+    }
+
+    if corrupted.is_empty() {
+        let s = if num_verified == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "Verified {num_verified} wheel{s} with no corruption"
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let num_corrupted = corrupted.len();
+    writeln!(
+        printer.stderr(),
+        "Found {} (out of {num_verified} verified)",
+        format!(
+            "{num_corrupted} corrupted wheel{}",
+            if num_corrupted == 1 { "" } else { "s" }
+        )
+        .bold()
+    )?;
+
+    if !repair {
+        writeln!(
+            printer.stderr(),
+            "Run `{}` to remove the corrupted entries, such that they're re-downloaded (or rebuilt) the next time they're needed",
+            "uv cache verify --repair".green()
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    let mut total_bytes = 0;
+    for archive in &corrupted {
+        let summary = cache
+            .remove_archive(archive)
+            .with_context(|| format!("Failed to remove {}", archive.user_display()))?;
+        total_bytes += summary.total_bytes;
+    }
+
+    write!(
+        printer.stderr(),
+        "Removed {num_corrupted} corrupted wheel{}",
+        if num_corrupted == 1 { "" } else { "s" }
+    )?;
+    if total_bytes > 0 {
+        let bytes = if total_bytes < 1024 {
+            format!("{total_bytes}B")
+        } else {
+            let (bytes, unit) = human_readable_bytes(total_bytes);
+            format!("{bytes:.1}{unit}")
+        };
+        write!(printer.stderr(), " ({})", bytes.green())?;
+    }
+    writeln!(printer.stderr())?;
+
+    Ok(ExitStatus::Success)
+}
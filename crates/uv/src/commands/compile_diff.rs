@@ -0,0 +1,131 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+
+use pep508_rs::VersionOrUrl;
+use requirements_txt::{RequirementsTxt, RequirementsTxtRequirement};
+use uv_client::Connectivity;
+use uv_normalize::PackageName;
+
+/// The changes to the pinned packages in a compiled `requirements.txt` file, as reported by
+/// `uv pip compile --diff`.
+#[derive(Debug, Default)]
+pub(crate) struct PinDiff {
+    /// The packages that would be pinned, but aren't pinned in the existing file.
+    added: Vec<(PackageName, BTreeSet<String>)>,
+    /// The packages that are pinned in the existing file, but would no longer be pinned.
+    removed: Vec<(PackageName, BTreeSet<String>)>,
+    /// The packages whose pins would change, as `(name, existing, updated)`.
+    changed: Vec<(PackageName, BTreeSet<String>, BTreeSet<String>)>,
+}
+
+impl PinDiff {
+    /// Compare the pins in the contents of an existing `requirements.txt` file against those in
+    /// an updated compilation.
+    ///
+    /// Only the pinned requirements are compared; the header, annotations, and editables are
+    /// ignored.
+    pub(crate) async fn from_contents(existing: &str, updated: &str) -> Result<Self> {
+        let mut existing = pins(existing).await?;
+        let updated = pins(updated).await?;
+
+        let mut diff = Self::default();
+        for (name, updated) in updated {
+            match existing.remove(&name) {
+                None => diff.added.push((name, updated)),
+                Some(existing) if existing != updated => {
+                    diff.changed.push((name, existing, updated));
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed.extend(existing);
+
+        Ok(diff)
+    }
+
+    /// Returns `true` if the pins are unchanged.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Summarize the number of added, removed, and changed packages (e.g.,
+    /// `1 added, 0 removed, 2 changed`).
+    pub(crate) fn summary(&self) -> String {
+        format!(
+            "{} added, {} removed, {} changed",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        )
+    }
+}
+
+impl Display for PinDiff {
+    /// Render each change on its own line, ordered by package name.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let lines = self
+            .added
+            .iter()
+            .map(|(name, pins)| (name, format!(" {} {}", "+".green(), display(name, pins))))
+            .chain(
+                self.removed
+                    .iter()
+                    .map(|(name, pins)| (name, format!(" {} {}", "-".red(), display(name, pins)))),
+            )
+            .chain(self.changed.iter().map(|(name, existing, updated)| {
+                (
+                    name,
+                    format!(
+                        " {} {} -> {}",
+                        "~".yellow(),
+                        display(name, existing),
+                        display(name, updated)
+                    ),
+                )
+            }))
+            .sorted_by(|(a, _), (b, _)| a.cmp(b));
+        for (_, line) in lines {
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Format the pins for a package (e.g., `anyio==4.3.0`).
+fn display(name: &PackageName, pins: &BTreeSet<String>) -> String {
+    pins.iter().map(|pin| format!("{name}{pin}")).join(", ")
+}
+
+/// Read the pins from the contents of a compiled `requirements.txt` file, keyed by package name.
+///
+/// A package can be pinned more than once (e.g., under different markers, when compiling for
+/// multiple Python versions), so each package maps to a set of pins, like `==4.3.0` or
+/// `==4.3.0 ; python_version >= '3.8'`.
+async fn pins(contents: &str) -> Result<BTreeMap<PackageName, BTreeSet<String>>> {
+    let working_dir = std::env::current_dir()?;
+    let requirements_txt =
+        RequirementsTxt::parse_inner(contents, &working_dir, &working_dir, Connectivity::Offline)
+            .await?;
+
+    let mut pins = BTreeMap::<PackageName, BTreeSet<String>>::new();
+    for entry in requirements_txt.requirements {
+        let RequirementsTxtRequirement::Pep508(requirement) = entry.requirement else {
+            continue;
+        };
+        let pin = match &requirement.version_or_url {
+            Some(VersionOrUrl::VersionSpecifier(specifiers)) => specifiers.to_string(),
+            Some(VersionOrUrl::Url(url)) => format!(" @ {url}"),
+            None => String::new(),
+        };
+        let pin = match &requirement.marker {
+            Some(marker) => format!("{pin} ; {marker}"),
+            None => pin,
+        };
+        pins.entry(requirement.name).or_default().insert(pin);
+    }
+    Ok(pins)
+}
@@ -0,0 +1,192 @@
+use std::env;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use rustc_hash::FxHashMap;
+
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+use uv_resolver::{is_pylock_path, Lock, LockedDistribution, PyLock};
+
+use crate::commands::sbom::{write_sbom, SbomComponent, SbomFormat};
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+use crate::workspace::Workspace;
+
+/// The format to which `uv export` writes the project's `uv.lock` file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum LockExportFormat {
+    /// A `requirements.txt` file, with each package pinned to its locked version (or URL), and
+    /// gated by the markers under which it's required.
+    #[value(name = "requirements.txt")]
+    RequirementsTxt,
+    /// A `requirements.txt` file in the style of `pip-compile`, with `# via` annotations that
+    /// record why each package was included.
+    PipTools,
+    /// A standardized `pylock.toml` lockfile (PEP 751).
+    #[value(name = "pylock.toml")]
+    PylockToml,
+    /// A CycloneDX 1.5 software bill of materials, in JSON.
+    #[value(name = "cyclonedx")]
+    CycloneDx,
+    /// An SPDX 2.3 software bill of materials, in JSON.
+    Spdx,
+}
+
+impl LockExportFormat {
+    /// Infer the format from the name of the output file, if any: `pylock.toml` for files named
+    /// `pylock.toml` (or `pylock.*.toml`), `requirements.txt` for `.txt` files, and a CycloneDX
+    /// software bill of materials otherwise.
+    pub(crate) fn from_output_file(output_file: Option<&Path>) -> Self {
+        match output_file {
+            Some(path) if is_pylock_path(path) => Self::PylockToml,
+            Some(path) if path.extension().is_some_and(|ext| ext == "txt") => Self::RequirementsTxt,
+            _ => Self::CycloneDx,
+        }
+    }
+}
+
+/// Export the project's `uv.lock` file to an alternate format.
+pub(crate) fn export(
+    format: LockExportFormat,
+    generate_hashes: bool,
+    output_file: Option<&Path>,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let lockfile = Workspace::project_root(&env::current_dir()?)?.join("uv.lock");
+    let lock = match fs_err::read_to_string(&lockfile) {
+        Ok(contents) => Lock::from_toml(&contents)?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(anyhow!(
+                "No `uv.lock` found in the current directory; run `uv lock` to create one"
+            ));
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let output_file = output_file.map_or_else(|| PathBuf::from("-"), Path::to_path_buf);
+
+    let contents = match format {
+        LockExportFormat::CycloneDx | LockExportFormat::Spdx => {
+            let components = lock
+                .distributions()
+                .iter()
+                .map(SbomComponent::from_locked)
+                .collect::<Vec<_>>();
+            let format = if format == LockExportFormat::Spdx {
+                SbomFormat::Spdx
+            } else {
+                SbomFormat::CycloneDx
+            };
+            write_sbom(&components, format, &output_file, printer)?;
+            return Ok(ExitStatus::Success);
+        }
+        LockExportFormat::PylockToml => PyLock::from_lock(&lock, generate_hashes)?.to_toml()?,
+        LockExportFormat::RequirementsTxt => requirements_txt(&lock, generate_hashes, false)?,
+        LockExportFormat::PipTools => requirements_txt(&lock, generate_hashes, true)?,
+    };
+
+    if output_file == Path::new("-") {
+        write!(printer.stdout(), "{contents}")?;
+    } else {
+        uv_fs::write_atomic_sync(&output_file, contents)?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Render the locked distributions as a `requirements.txt` file.
+///
+/// If `annotate` is set, the file follows the layout of `pip-compile`'s output, with a header and
+/// a `# via` annotation listing the locked packages that depend on each package.
+fn requirements_txt(lock: &Lock, generate_hashes: bool, annotate: bool) -> Result<String> {
+    let mut dependents = FxHashMap::<&PackageName, Vec<&PackageName>>::default();
+    for distribution in lock.distributions() {
+        for dependency in distribution.dependencies() {
+            dependents
+                .entry(dependency)
+                .or_default()
+                .push(distribution.name());
+        }
+    }
+
+    let mut output = String::new();
+    if annotate {
+        writeln!(output, "#")?;
+        writeln!(
+            output,
+            "# This file is autogenerated by uv via the following command:"
+        )?;
+        writeln!(output, "#")?;
+        writeln!(output, "#    {}", cmd())?;
+        writeln!(output, "#")?;
+    } else {
+        writeln!(
+            output,
+            "# This file was autogenerated by uv via the following command:"
+        )?;
+        writeln!(output, "#    {}", cmd())?;
+    }
+
+    for distribution in lock.distributions() {
+        write!(output, "{}", requirement(distribution)?)?;
+        if generate_hashes && !distribution.is_editable() {
+            for hash in distribution.hashes() {
+                write!(output, " \\\n    --hash={hash}")?;
+            }
+        }
+        writeln!(output)?;
+
+        if annotate {
+            match dependents.get(distribution.name()).map(Vec::as_slice) {
+                None | Some([]) => {}
+                Some([dependent]) => writeln!(output, "    # via {dependent}")?,
+                Some(dependents) => {
+                    writeln!(output, "    # via")?;
+                    for dependent in dependents {
+                        writeln!(output, "    #   {dependent}")?;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Format a locked distribution as a line in a `requirements.txt` file (e.g.,
+/// `anyio==4.3.0 ; sys_platform == 'win32'`), excluding any hashes.
+fn requirement(distribution: &LockedDistribution) -> Result<String> {
+    let name = distribution.name();
+
+    // Editables can't be gated by markers in a `requirements.txt` file.
+    if distribution.is_editable() {
+        let path = distribution
+            .url()
+            .ok_or_else(|| anyhow!("Locked editable `{name}` is missing a path"))?;
+        return Ok(format!("-e {path}"));
+    }
+
+    let requirement = if let Some(url) = distribution.url() {
+        format!("{name} @ {url}")
+    } else if let Some(version) = distribution.version() {
+        format!("{name}=={version}")
+    } else {
+        return Err(anyhow!("Locked distribution `{name}` is missing a version"));
+    };
+    Ok(match distribution.marker() {
+        Some(marker) => format!("{requirement} ; {marker}"),
+        None => requirement,
+    })
+}
+
+/// Format the `uv` command used to generate the output file.
+fn cmd() -> String {
+    let args = env::args_os()
+        .skip(1)
+        .map(|arg| arg.user_display().to_string())
+        .join(" ");
+    format!("uv {args}")
+}
@@ -0,0 +1,384 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use tempfile::tempdir_in;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::{IndexLocations, LocalEditable, Name};
+use pep508_rs::Requirement;
+use requirements_txt::EditableRequirement;
+use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
+use uv_cache::Cache;
+use uv_client::{
+    BaseClientBuilder, Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder, TrustedHost,
+};
+use uv_dispatch::BuildDispatch;
+use uv_fs::Simplified;
+use uv_installer::{Downloader, NoBinary};
+use uv_interpreter::{
+    find_default_python, find_requested_python, Error, Interpreter, PythonEnvironment,
+};
+use uv_normalize::PackageName;
+use uv_resolver::{
+    InMemoryIndex, Lock, Manifest, OptionsBuilder, PythonRequirement, ResolutionMode, Resolver,
+};
+use uv_traits::{BuildIsolation, Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+
+use crate::commands::reporters::{DownloadReporter, ResolverReporter};
+use crate::commands::{elapsed, ExitStatus, Upgrade};
+use crate::diagnostics;
+use crate::printer::Printer;
+use crate::requirements::{
+    read_lockfile, ExtrasSpecification, NamedRequirements, RequirementsSource,
+    RequirementsSpecification,
+};
+use crate::workspace::Workspace;
+
+/// Resolve the project's dependencies into a `uv.lock` file.
+///
+/// The resolution is universal, such that the lockfile can be installed on any platform, and
+/// includes the dependencies for all of the project's extras.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn lock(
+    upgrade: Upgrade,
+    resolution_mode: ResolutionMode,
+    python: Option<&str>,
+    index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    exclude_newer: Option<DateTime<Utc>>,
+    exclude_newer_package: FxHashMap<PackageName, DateTime<Utc>>,
+    native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    let root = std::env::current_dir()?;
+    let pyproject_toml = root.join("pyproject.toml");
+    if !pyproject_toml.is_file() {
+        return Err(anyhow!(
+            "No `pyproject.toml` found in the current directory"
+        ));
+    }
+
+    // If the project is part of a workspace, lock the entire workspace into a single lockfile at
+    // the workspace root.
+    let workspace = Workspace::discover(&root)?;
+    let (lockfile, sources) = match &workspace {
+        Some(workspace) => {
+            let mut sources = vec![];
+            let pyproject_toml = workspace.root().join("pyproject.toml");
+            sources.push(RequirementsSource::pyproject_toml(pyproject_toml));
+            for member in workspace.members().values() {
+                if member.root() != workspace.root() {
+                    sources.push(RequirementsSource::pyproject_toml(
+                        member.root().join("pyproject.toml"),
+                    ));
+                }
+            }
+            (workspace.root().join("uv.lock"), sources)
+        }
+        None => (
+            root.join("uv.lock"),
+            vec![RequirementsSource::pyproject_toml(pyproject_toml)],
+        ),
+    };
+
+    // Read the project's requirements, including those for all extras.
+    let spec = RequirementsSpecification::from_sources(
+        &sources,
+        &[],
+        &[],
+        &[],
+        &ExtrasSpecification::All,
+        connectivity,
+    )
+    .await?;
+
+    // Convert from unnamed to named requirements, downloading any archives that can't be named
+    // from their URLs alone.
+    let client_builder = BaseClientBuilder::new()
+        .native_tls(native_tls)
+        .cert(cert)
+        .proxy(proxy)
+        .retries(retries)
+        .connectivity(connectivity)
+        .trusted_hosts(trusted_hosts.clone())
+        .keyring_provider(keyring_provider);
+    let NamedRequirements {
+        project,
+        mut requirements,
+        hashes: _hashes,
+        constraints,
+        constraint_sources: _constraint_sources,
+        constraint_hashes: _constraint_hashes,
+        overrides,
+        build_constraints: _build_constraints,
+        mut editables,
+        index_url,
+        extra_index_urls,
+        no_index,
+        find_links,
+        pinned_indexes,
+        unnamed,
+    } = NamedRequirements::from_spec(spec, &client_builder, cache).await?;
+
+    // Lock every workspace member, with all of its extras, as an editable, such that the members
+    // (and the dependencies between them) are recorded in the lockfile.
+    if let Some(workspace) = &workspace {
+        let members = workspace
+            .members()
+            .iter()
+            .map(|(name, member)| Requirement {
+                name: name.clone(),
+                extras: member.extras().cloned().collect(),
+                version_or_url: None,
+                marker: None,
+            })
+            .collect::<Vec<_>>();
+        for editable in workspace.editables(&members)? {
+            if !editables
+                .iter()
+                .any(|existing| existing.path == editable.path)
+            {
+                editables.push(editable);
+            }
+        }
+        requirements.extend(members);
+    }
+
+    // Apply any version bounds attached to the packages to upgrade.
+    let constraints = constraints
+        .into_iter()
+        .chain(upgrade.constraints().cloned())
+        .collect::<Vec<_>>();
+
+    // Prefer the versions pinned in the existing lockfile, if any.
+    let preferences = read_lockfile(Some(lockfile.as_path()), upgrade).await?;
+
+    let interpreter = find_interpreter(python, cache)?;
+    debug!(
+        "Using Python {} interpreter at {} for builds",
+        interpreter.python_version(),
+        interpreter.sys_executable().user_display().cyan()
+    );
+    let tags = interpreter.tags()?;
+    let markers = interpreter.markers();
+
+    // Incorporate any index locations from the `pyproject.toml`.
+    let index_locations = index_locations
+        .combine(index_url, extra_index_urls, find_links, no_index)
+        .pin(pinned_indexes);
+
+    // Add all authenticated sources to the store.
+    for url in index_locations.urls() {
+        GLOBAL_AUTH_STORE.save_from_url(url);
+    }
+
+    // Initialize the registry client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .native_tls(native_tls)
+        .cert(cert)
+        .proxy(proxy)
+        .retries(retries)
+        .connectivity(connectivity)
+        .index_urls(index_locations.index_urls())
+        .trusted_hosts(trusted_hosts)
+        .keyring_provider(keyring_provider)
+        .markers(markers)
+        .platform(interpreter.platform())
+        .build();
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let client = FlatIndexClient::new(&client, cache);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, tags)
+    };
+
+    let index = InMemoryIndex::default();
+    let in_flight = InFlight::default();
+    let config_settings = ConfigSettings::default();
+    let options = OptionsBuilder::new()
+        .resolution_mode(resolution_mode)
+        .exclude_newer(exclude_newer)
+        .exclude_newer_package(exclude_newer_package.clone())
+        .universal(true)
+        .build();
+
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        cache,
+        &interpreter,
+        &index_locations,
+        &flat_index,
+        &index,
+        &in_flight,
+        SetupPyStrategy::default(),
+        &config_settings,
+        BuildIsolation::Isolated,
+        &NoBuild::None,
+        &NoBinary::None,
+    )
+    .with_concurrency(concurrency)
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer)
+            .exclude_newer_package(exclude_newer_package)
+            .build(),
+    );
+
+    // Infer the names of any local projects that lack static metadata, which requires a build
+    // environment.
+    requirements.extend(NamedRequirements::name_with_build(unnamed, &build_dispatch).await?);
+
+    // Build the editables (e.g., the project itself), such that they're locked alongside their
+    // dependencies.
+    let editable_metadata = if editables.is_empty() {
+        Vec::new()
+    } else {
+        let start = std::time::Instant::now();
+
+        let editables: Vec<LocalEditable> = editables
+            .into_iter()
+            .map(|editable| {
+                let EditableRequirement {
+                    url,
+                    extras,
+                    path,
+                    dependency,
+                    ..
+                } = editable;
+                LocalEditable {
+                    url,
+                    path,
+                    extras,
+                    dependency,
+                }
+            })
+            .collect();
+
+        let downloader = Downloader::new(cache, tags, &client, &build_dispatch)
+            .with_reporter(DownloadReporter::from(printer).with_length(editables.len() as u64));
+
+        let editable_wheel_dir = tempdir_in(cache.root())?;
+        let editables: Vec<_> = downloader
+            .build_editables(editables, editable_wheel_dir.path())
+            .await
+            .context("Failed to build editables")?
+            .into_iter()
+            .map(|built_editable| (built_editable.editable, built_editable.metadata))
+            .collect();
+
+        // Validate that the editables are compatible with the target Python version.
+        let requirement = PythonRequirement::new(&interpreter, markers);
+        for (.., metadata) in &editables {
+            if let Some(python_requires) = metadata.requires_python.as_ref() {
+                if !python_requires.contains(requirement.target()) {
+                    return Err(anyhow!(
+                        "Editable `{}` requires Python {}, but resolution targets Python {}",
+                        metadata.name,
+                        python_requires,
+                        requirement.target()
+                    ));
+                }
+            }
+        }
+
+        let s = if editables.len() == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Built {} in {}",
+                format!("{} editable{}", editables.len(), s).bold(),
+                elapsed(start.elapsed())
+            )
+            .dimmed()
+        )?;
+        editables
+    };
+
+    // Resolve the requirements.
+    let manifest = Manifest::new(
+        requirements,
+        constraints,
+        overrides,
+        preferences,
+        project,
+        editable_metadata,
+    );
+    let resolver = Resolver::new(
+        manifest,
+        options,
+        markers,
+        &interpreter,
+        tags,
+        &client,
+        &flat_index,
+        &index,
+        &build_dispatch,
+    )?
+    .with_reporter(ResolverReporter::from(printer));
+
+    let resolutions = match resolver.resolve_forks().await {
+        Err(uv_resolver::ResolveError::NoSolution(err)) => {
+            diagnostics::report_no_solution(
+                &err,
+                "No solution found when resolving dependencies:",
+                printer,
+            );
+            return Ok(ExitStatus::Failure);
+        }
+        result => result,
+    }?;
+
+    let packages = resolutions
+        .iter()
+        .flat_map(|(_, resolution)| resolution.petgraph().node_weights())
+        .map(Name::name)
+        .unique()
+        .count();
+    let s = if packages == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Resolved {} in {}",
+            format!("{packages} package{s}").bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    // Write the lockfile.
+    let lock = Lock::from_resolutions(&resolutions)?;
+    uv_fs::write_atomic_sync(&lockfile, lock.to_toml()?)?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Find the interpreter to use for project operations: the requested Python, if any, or the
+/// active virtual environment, falling back to the default Python.
+pub(crate) fn find_interpreter(python: Option<&str>, cache: &Cache) -> Result<Interpreter> {
+    Ok(if let Some(python) = python {
+        find_requested_python(python, cache)?
+            .ok_or_else(|| Error::RequestedPythonNotFound(python.to_string()))?
+    } else if let Ok(venv) = PythonEnvironment::from_virtualenv(cache) {
+        venv.interpreter().clone()
+    } else {
+        find_default_python(cache)?
+    })
+}
@@ -1,12 +1,26 @@
+use std::path::Path;
 use std::time::Duration;
 use std::{fmt::Display, fmt::Write, process::ExitCode};
 
 use anyhow::Context;
+use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tracing::debug;
 
+pub(crate) use add::add;
 pub(crate) use cache_clean::cache_clean;
 pub(crate) use cache_dir::cache_dir;
-use distribution_types::InstalledMetadata;
+pub(crate) use cache_prune::cache_prune;
+pub(crate) use cache_verify::cache_verify;
+use distribution_types::{
+    BuiltDist, CachedDist, DirectGitUrl, Dist, DistributionMetadata, InstalledDist,
+    InstalledMetadata, InstalledVersion, Name, SourceDist, VersionOrUrl,
+};
+pub(crate) use export::{export, LockExportFormat};
+pub(crate) use lock::lock;
 pub(crate) use pip_check::pip_check;
 pub(crate) use pip_compile::{extra_name_with_clap_error, pip_compile, Upgrade};
 pub(crate) use pip_freeze::pip_freeze;
@@ -15,19 +29,39 @@ pub(crate) use pip_list::pip_list;
 pub(crate) use pip_show::pip_show;
 pub(crate) use pip_sync::pip_sync;
 pub(crate) use pip_uninstall::pip_uninstall;
+pub(crate) use python_install::python_install;
+pub(crate) use python_list::python_list;
+pub(crate) use remove::remove;
+use requirements_txt::EditableRequirement;
+pub(crate) use run::run;
+pub(crate) use sbom::SbomFormat;
 pub(crate) use self_update::self_update;
-use uv_cache::Cache;
-use uv_fs::Simplified;
+pub(crate) use sync::sync;
+pub(crate) use tool_install::tool_install;
+pub(crate) use tool_list::tool_list;
+pub(crate) use tool_uninstall::tool_uninstall;
+pub(crate) use tool_upgrade::tool_upgrade;
+use uv_cache::{Cache, CacheBucket};
+use uv_client::Connectivity;
+use uv_fs::{directories, LockedFile, Simplified};
+use uv_git::GitSource;
 use uv_installer::compile_tree;
-use uv_interpreter::PythonEnvironment;
+use uv_interpreter::{PythonEnvironment, VirtualEnvSource};
 use uv_normalize::PackageName;
-pub(crate) use venv::venv;
+use uv_traits::RequiredHashes;
+pub(crate) use venv::{project_prompt, venv, venv_system_site_packages};
 pub(crate) use version::version;
 
 use crate::printer::Printer;
 
+mod add;
 mod cache_clean;
 mod cache_dir;
+mod cache_prune;
+mod cache_verify;
+mod compile_diff;
+mod export;
+mod lock;
 mod pip_check;
 mod pip_compile;
 mod pip_freeze;
@@ -36,8 +70,20 @@ mod pip_list;
 mod pip_show;
 mod pip_sync;
 mod pip_uninstall;
+mod python_install;
+mod python_list;
+mod remove;
 mod reporters;
+mod resolution_cache;
+mod run;
+mod sbom;
 mod self_update;
+mod sync;
+mod tool;
+mod tool_install;
+mod tool_list;
+mod tool_uninstall;
+mod tool_upgrade;
 mod venv;
 mod version;
 
@@ -54,6 +100,9 @@ pub(crate) enum ExitStatus {
     /// The command failed with an unexpected error.
     #[allow(unused)]
     Error,
+
+    /// The command's exit status is propagated from an external command.
+    External(u8),
 }
 
 impl From<ExitStatus> for ExitCode {
@@ -62,6 +111,7 @@ impl From<ExitStatus> for ExitCode {
             ExitStatus::Success => Self::from(0),
             ExitStatus::Failure => Self::from(1),
             ExitStatus::Error => Self::from(2),
+            ExitStatus::External(code) => Self::from(code),
         }
     }
 }
@@ -82,6 +132,22 @@ pub(super) fn elapsed(duration: Duration) -> String {
     }
 }
 
+/// Formats a number of bytes into a human readable SI-prefixed size.
+///
+/// Returns a tuple of `(quantity, units)`.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub(super) fn human_readable_bytes(bytes: u64) -> (f32, &'static str) {
+    static UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+    let bytes = bytes as f32;
+    let i = ((bytes.log2() / 10.0) as usize).min(UNITS.len() - 1);
+    (bytes / 1024_f32.powi(i as i32), UNITS[i])
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub(super) enum ChangeEventKind {
     /// The package was removed from the environment.
@@ -103,12 +169,246 @@ pub(super) struct DryRunEvent<T: Display> {
     kind: ChangeEventKind,
 }
 
+/// A planned change to the environment, as reported by `--dry-run --json`.
+#[derive(Debug, Serialize)]
+struct DryRunEntry {
+    name: String,
+    action: DryRunAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    installed_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum DryRunAction {
+    /// The package is not installed, and would be installed.
+    Install,
+    /// The package is installed, and would be replaced by a different version or source.
+    Upgrade,
+    /// The package is installed, and would be removed.
+    Remove,
+}
+
+impl DryRunEntry {
+    /// Summarize a set of planned changes, sorted by package name.
+    fn from_changes(
+        remote: &[Dist],
+        local: &[CachedDist],
+        removals: impl Iterator<Item = &InstalledDist>,
+    ) -> Vec<Self> {
+        let mut removed = removals
+            .map(|dist| (dist.name(), base_version(&dist.installed_version())))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut entries = remote
+            .iter()
+            .map(|dist| {
+                let (version, url) = match dist.version_or_url() {
+                    VersionOrUrl::Version(version) => (Some(version.to_string()), None),
+                    VersionOrUrl::Url(url) => (None, Some(url.to_string())),
+                };
+                (
+                    dist.name(),
+                    version,
+                    url,
+                    dist.file().and_then(|file| file.size),
+                )
+            })
+            .chain(local.iter().map(|dist| {
+                let (version, url) = match dist.installed_version() {
+                    InstalledVersion::Version(version) => (Some(version.to_string()), None),
+                    InstalledVersion::Url(url, version) => {
+                        (Some(version.to_string()), Some(url.to_string()))
+                    }
+                };
+                (dist.name(), version, url, None)
+            }))
+            .map(|(name, version, url, size)| {
+                let installed_version = removed.remove(name);
+                Self {
+                    name: name.to_string(),
+                    action: if installed_version.is_some() {
+                        DryRunAction::Upgrade
+                    } else {
+                        DryRunAction::Install
+                    },
+                    version,
+                    url,
+                    installed_version,
+                    size,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Any remaining distributions would be removed without replacement.
+        entries.extend(removed.into_iter().map(|(name, installed_version)| Self {
+            name: name.to_string(),
+            action: DryRunAction::Remove,
+            version: None,
+            url: None,
+            installed_version: Some(installed_version),
+            size: None,
+        }));
+
+        entries.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+}
+
+/// Return the version of an installed distribution, without any URL.
+fn base_version(version: &InstalledVersion) -> String {
+    match version {
+        InstalledVersion::Version(version) | InstalledVersion::Url(_, version) => {
+            version.to_string()
+        }
+    }
+}
+
+/// Report the changes that would be made to the environment in a dry run.
+///
+/// If `json` is set, the changes are additionally written to `stdout` in a machine-readable format.
+pub(super) fn report_planned_changes(
+    remote: &[Dist],
+    local: &[CachedDist],
+    reinstalls: &[InstalledDist],
+    extraneous: &[InstalledDist],
+    json: bool,
+    printer: Printer,
+) -> anyhow::Result<()> {
+    // Download, build, and unzip any missing distributions.
+    if !remote.is_empty() {
+        let s = if remote.len() == 1 { "" } else { "s" };
+
+        // Include the total download size, if known.
+        let size = remote
+            .iter()
+            .filter_map(|dist| dist.file().and_then(|file| file.size))
+            .sum::<u64>();
+        let size = if size == 0 {
+            String::new()
+        } else if size < 1024 {
+            format!(" ({size}B)")
+        } else {
+            let (bytes, unit) = human_readable_bytes(size);
+            format!(" ({bytes:.1}{unit})")
+        };
+
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Would download {}{}",
+                format!("{} package{}", remote.len(), s).bold(),
+                size
+            )
+            .dimmed()
+        )?;
+    }
+
+    // Remove any existing installations.
+    let uninstalls = reinstalls.len() + extraneous.len();
+    if uninstalls > 0 {
+        let s = if uninstalls == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Would uninstall {}",
+                format!("{uninstalls} package{s}").bold()
+            )
+            .dimmed()
+        )?;
+    }
+
+    // Install the resolved distributions.
+    let installs = remote.len() + local.len();
+    if installs > 0 {
+        let s = if installs == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!("Would install {}", format!("{installs} package{s}").bold()).dimmed()
+        )?;
+    }
+
+    // If requested, write the plan to `stdout` in a machine-readable format.
+    if json {
+        let entries = DryRunEntry::from_changes(remote, local, reinstalls.iter().chain(extraneous));
+        writeln!(printer.stdout(), "{}", serde_json::to_string(&entries)?)?;
+    }
+
+    for event in reinstalls
+        .iter()
+        .chain(extraneous)
+        .map(|distribution| DryRunEvent {
+            name: distribution.name().clone(),
+            version: distribution.installed_version().to_string(),
+            kind: ChangeEventKind::Removed,
+        })
+        .chain(remote.iter().map(|distribution| DryRunEvent {
+            name: distribution.name().clone(),
+            version: distribution.version_or_url().to_string(),
+            kind: ChangeEventKind::Added,
+        }))
+        .chain(local.iter().map(|distribution| DryRunEvent {
+            name: distribution.name().clone(),
+            version: distribution.installed_version().to_string(),
+            kind: ChangeEventKind::Added,
+        }))
+        .sorted_unstable_by(|a, b| a.name.cmp(&b.name).then_with(|| a.kind.cmp(&b.kind)))
+    {
+        match event.kind {
+            ChangeEventKind::Added => {
+                writeln!(
+                    printer.stderr(),
+                    " {} {}{}",
+                    "+".green(),
+                    event.name.as_ref().bold(),
+                    event.version.dimmed()
+                )?;
+            }
+            ChangeEventKind::Removed => {
+                writeln!(
+                    printer.stderr(),
+                    " {} {}{}",
+                    "-".red(),
+                    event.name.as_ref().bold(),
+                    event.version.dimmed()
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone, Copy, clap::ValueEnum)]
 pub(crate) enum VersionFormat {
     Text,
     Json,
 }
 
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum ExportFormat {
+    /// Export in `requirements.txt` format.
+    #[default]
+    #[value(name = "requirements.txt")]
+    RequirementsTxt,
+    /// Export in the standardized `pylock.toml` lockfile format (PEP 751).
+    #[value(name = "pylock.toml")]
+    PylockToml,
+    /// Export the resolved dependency graph as JSON.
+    Json,
+    /// Export the resolved dependency graph as TOML.
+    Toml,
+}
+
 #[derive(Debug, Default, Clone, clap::ValueEnum)]
 pub(crate) enum ListFormat {
     /// Display the list of packages in a human-readable table.
@@ -121,12 +421,194 @@ pub(crate) enum ListFormat {
     Json,
 }
 
+/// An error encountered when verifying the hashes of the distributions to install.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum HashVerificationError {
+    #[error("In hash-checking mode, all requirements must have a hash, but none were provided for: `{0}`")]
+    Missing(PackageName),
+
+    #[error("Git and directory requirements can't be hashed, and are not supported in hash-checking mode (found: `{0}`)")]
+    Unhashable(String),
+
+    #[error("Hash mismatch for `{dist}`: expected {expected}, found `{actual}`")]
+    Mismatch {
+        dist: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+/// Verify that the distributions to install satisfy the required hashes.
+///
+/// Any hash reported by the index must be among the required hashes for the distribution, and
+/// local archives are hashed directly. Remote archives are always verified as they're downloaded
+/// (failing if none of the required hashes can be compared against the download), and cached
+/// archives are verified against the hash recorded when they were downloaded. In hash-checking
+/// mode (`require_hashes`), every distribution must have at least one required hash; otherwise,
+/// distributions without any required hashes are skipped.
+pub(super) fn verify_hashes<'a>(
+    dists: impl IntoIterator<Item = &'a Dist>,
+    required_hashes: &RequiredHashes,
+    require_hashes: bool,
+) -> anyhow::Result<()> {
+    if required_hashes.is_empty() && !require_hashes {
+        return Ok(());
+    }
+
+    for dist in dists {
+        let Some(expected) = required_hashes.get(dist.name()) else {
+            if !require_hashes {
+                continue;
+            }
+            return Err(HashVerificationError::Missing(dist.name().clone()).into());
+        };
+
+        let actual = match dist {
+            Dist::Built(BuiltDist::Path(wheel)) => Some(hash_file(&wheel.path)?),
+            Dist::Source(SourceDist::Path(sdist)) if sdist.path.is_file() => {
+                Some(hash_file(&sdist.path)?)
+            }
+            Dist::Source(SourceDist::Git(_) | SourceDist::Path(_)) => {
+                return Err(HashVerificationError::Unhashable(dist.to_string()).into());
+            }
+            _ => dist
+                .file()
+                .and_then(|file| file.hashes.sha256.as_deref())
+                .map(|sha256| format!("sha256:{}", sha256.to_ascii_lowercase())),
+        };
+
+        // Otherwise, the archive is verified by the distribution database, as it's downloaded or
+        // read from the cache.
+        if let Some(actual) = actual {
+            if !expected.contains(&actual) {
+                return Err(HashVerificationError::Mismatch {
+                    dist: dist.to_string(),
+                    expected: expected.iter().map(|hash| format!("`{hash}`")).join(" or "),
+                    actual,
+                }
+                .into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Report the environment, if it was discovered (e.g., as a `.venv` in a parent directory or via
+/// `CONDA_PREFIX`) rather than explicitly activated via `VIRTUAL_ENV`.
+pub(super) fn report_environment(venv: &PythonEnvironment, printer: Printer) -> std::fmt::Result {
+    let kind = match venv.source() {
+        Some(VirtualEnvSource::DotVenv) => "virtual environment",
+        Some(VirtualEnvSource::CondaPrefix) => "Conda environment",
+        Some(VirtualEnvSource::VirtualEnv) | None => return Ok(()),
+    };
+    writeln!(
+        printer.stderr(),
+        "Using {kind} at: {}",
+        venv.root().user_display().cyan()
+    )
+}
+
+/// Report the number of installed distributions that were verified against the required hashes.
+///
+/// Cached distributions with required hashes are always read through the distribution database
+/// (see [`uv_installer::Planner::with_required_hashes`]), which verifies them, so any installed
+/// wheel with a required hash was verified, with the exception of editables, which can't be hashed.
+pub(super) fn report_verified_hashes(
+    wheels: &[CachedDist],
+    required_hashes: &RequiredHashes,
+    printer: Printer,
+) -> std::fmt::Result {
+    let verified = wheels
+        .iter()
+        .filter(|wheel| !wheel.editable() && required_hashes.get(wheel.name()).is_some())
+        .count();
+    if verified == 0 {
+        return Ok(());
+    }
+
+    let s = if verified == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Verified {} against the provided hashes",
+            format!("{verified} package{s}").bold(),
+        )
+        .dimmed()
+    )
+}
+
+/// Compute the `sha256:{digest}` hash of a local file.
+fn hash_file(path: &Path) -> anyhow::Result<String> {
+    let mut file = fs_err::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
+
+/// Check out the Git repositories of any editable Git requirements (e.g.,
+/// `-e git+https://github.com/pallets/flask.git#egg=flask`) to their source directories, such that
+/// they can be built and installed like any other editable.
+///
+/// Existing checkouts are reused as-is, such that any local changes are preserved.
+pub(super) async fn checkout_editables(
+    editables: &[EditableRequirement],
+    cache: &Cache,
+    connectivity: Connectivity,
+) -> anyhow::Result<()> {
+    for checkout in editables
+        .iter()
+        .filter_map(|editable| editable.checkout.as_ref())
+    {
+        if checkout.path.exists() {
+            debug!(
+                "Using existing checkout of {} at: {}",
+                checkout.url,
+                checkout.path.user_display()
+            );
+            continue;
+        }
+
+        debug!(
+            "Checking out {} to: {}",
+            checkout.url,
+            checkout.path.user_display()
+        );
+        let DirectGitUrl { url, .. } = DirectGitUrl::try_from(&checkout.url)?;
+        let git_dir = cache.bucket(CacheBucket::Git);
+        let canonical_url = cache_key::CanonicalUrl::new(&checkout.url);
+        let path = checkout.path.clone();
+        tokio::task::spawn_blocking(move || {
+            // Avoid races with other processes fetching the same repository.
+            let lock_dir = git_dir.join("locks");
+            fs_err::create_dir_all(&lock_dir)?;
+            let _lock = LockedFile::acquire(
+                lock_dir.join(cache_key::digest(&canonical_url)),
+                &canonical_url,
+            )?;
+
+            GitSource::new(url, git_dir)
+                .with_offline(connectivity == Connectivity::Offline)
+                .fetch_into(&path)
+        })
+        .await?
+        .with_context(|| format!("Failed to check out: {}", checkout.url))?;
+    }
+    Ok(())
+}
+
 /// Compile all Python source files in site-packages to bytecode, to speed up the
 /// initial run of any subsequent executions.
 ///
-/// See the `--compile` option on `pip sync` and `pip install`.
+/// The compiled bytecode files are added to the `RECORD` of each of the `installed` distributions,
+/// such that they're removed along with it. The `RECORD` files of any other distributions in the
+/// environment are left untouched.
+///
+/// See the `--compile-bytecode` option on `pip sync`, `pip install`, and `sync`.
 pub(super) async fn compile_bytecode(
     venv: &PythonEnvironment,
+    installed: &[CachedDist],
     cache: &Cache,
     printer: Printer,
 ) -> anyhow::Result<()> {
@@ -141,6 +623,32 @@ pub(super) async fn compile_bytecode(
                     site_packages.user_display()
                 )
             })?;
+
+        for dist_info in directories(site_packages) {
+            let Ok(Some(dist)) = InstalledDist::try_from_path(&dist_info) else {
+                continue;
+            };
+            if !installed
+                .iter()
+                .any(|installed| installed.name() == dist.name())
+            {
+                continue;
+            }
+            match install_wheel_rs::record_bytecode(&dist_info) {
+                Ok(_) => {}
+                // Distributions without a `RECORD` (e.g., those installed by a distro package
+                // manager) can't be updated.
+                Err(install_wheel_rs::Error::MissingRecord(_)) => {}
+                Err(err) => {
+                    return Err(err).with_context(|| {
+                        format!(
+                            "Failed to record bytecode in: {}",
+                            dist_info.join("RECORD").user_display()
+                        )
+                    });
+                }
+            }
+        }
     }
     let s = if files == 1 { "" } else { "s" };
     writeln!(
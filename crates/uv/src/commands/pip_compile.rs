@@ -6,35 +6,51 @@ use std::ops::Deref;
 use std::path::Path;
 use std::str::FromStr;
 
-use anstream::{eprint, AutoStream, StripStream};
+use anstream::{AutoStream, StripStream};
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
-use rustc_hash::FxHashSet;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
 use tempfile::tempdir_in;
 use tracing::debug;
+use url::Url;
 
-use distribution_types::{IndexLocations, LocalEditable, Verbatim};
+use distribution_types::{IndexLocations, LocalEditable, Name, Verbatim};
+use pep508_rs::{MarkerEnvironment, MarkerTree, Requirement, VersionOrUrl};
 use platform_tags::Tags;
+use pypi_types::Hashes;
 use requirements_txt::EditableRequirement;
 use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder};
+use uv_client::{
+    BaseClientBuilder, Connectivity, FlatIndex, FlatIndexClient, IndexStrategy,
+    RegistryClientBuilder, TrustedHost,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::{Downloader, NoBinary};
-use uv_interpreter::{find_best_python, PythonEnvironment, PythonVersion};
+use uv_interpreter::{
+    find_best_python, Interpreter, PythonEnvironment, PythonPlatform, PythonVersion,
+};
 use uv_normalize::{ExtraName, PackageName};
 use uv_resolver::{
-    AnnotationStyle, DependencyMode, DisplayResolutionGraph, InMemoryIndex, Manifest,
-    OptionsBuilder, PreReleaseMode, PythonRequirement, ResolutionMode, Resolver,
+    AllowYanked, AnnotationStyle, DependencyMode, Diagnostic, DisplayResolutionGraph,
+    DisplayResolutionMatrix, GraphExport, InMemoryIndex, Manifest, OptionsBuilder, PreReleaseMode,
+    PyLock, PythonRequirement, ResolutionMode, Resolver,
+};
+use uv_traits::{
+    BuildIsolation, Concurrency, ConfigSettings, InFlight, NoBuild, PackageConfigSettings,
+    SetupPyStrategy,
 };
-use uv_traits::{BuildIsolation, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
 use uv_warnings::warn_user;
 
+use crate::commands::compile_diff::PinDiff;
 use crate::commands::reporters::{DownloadReporter, ResolverReporter};
-use crate::commands::{elapsed, ExitStatus};
+use crate::commands::resolution_cache::{ResolutionCache, ResolutionKey};
+use crate::commands::{checkout_editables, elapsed, ExitStatus, ExportFormat};
+use crate::diagnostics;
 use crate::printer::Printer;
 use crate::requirements::{
     read_lockfile, ExtrasSpecification, NamedRequirements, RequirementsSource,
@@ -47,31 +63,50 @@ pub(crate) async fn pip_compile(
     requirements: &[RequirementsSource],
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
+    build_constraints: &[RequirementsSource],
     extras: ExtrasSpecification<'_>,
     output_file: Option<&Path>,
+    format: ExportFormat,
+    diff: bool,
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
     generate_hashes: bool,
     no_emit_packages: Vec<PackageName>,
+    allow_unsafe: bool,
     include_extras: bool,
+    include_markers: bool,
     include_annotations: bool,
     include_header: bool,
     custom_compile_command: Option<String>,
     include_index_url: bool,
     include_find_links: bool,
     index_locations: IndexLocations,
+    index_strategy: IndexStrategy,
     keyring_provider: KeyringProvider,
+    trusted_host: Vec<TrustedHost>,
     setup_py: SetupPyStrategy,
     config_settings: ConfigSettings,
+    package_config_settings: PackageConfigSettings,
     connectivity: Connectivity,
     no_build_isolation: bool,
+    no_build_isolation_package: &[PackageName],
     no_build: &NoBuild,
+    no_binary: &NoBinary,
     python_version: Option<PythonVersion>,
+    python_versions: Vec<PythonVersion>,
+    python_platform: Option<PythonPlatform>,
+    universal: bool,
     exclude_newer: Option<DateTime<Utc>>,
+    exclude_newer_package: FxHashMap<PackageName, DateTime<Utc>>,
+    allow_yanked: AllowYanked,
     annotation_style: AnnotationStyle,
     native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
     quiet: bool,
     cache: Cache,
     printer: Printer,
@@ -82,23 +117,41 @@ pub(crate) async fn pip_compile(
     if !matches!(extras, ExtrasSpecification::None)
         && !requirements
             .iter()
-            .any(|source| matches!(source, RequirementsSource::PyprojectToml(_)))
+            .any(RequirementsSource::is_pyproject_toml)
     {
         return Err(anyhow!(
             "Requesting extras requires a pyproject.toml input file."
         ));
     }
 
+    // A `pylock.toml` (or an exported graph) can only represent a single resolution.
+    if !python_versions.is_empty() && format != ExportFormat::RequirementsTxt {
+        return Err(anyhow!(
+            "`--python-versions` is only supported when exporting to `requirements.txt`"
+        ));
+    }
+
+    // Only the pins in a `requirements.txt` can be compared.
+    if diff && format != ExportFormat::RequirementsTxt {
+        return Err(anyhow!(
+            "`--diff` is only supported when exporting to `requirements.txt`"
+        ));
+    }
+
     // Read all requirements from the provided sources.
     let spec = RequirementsSpecification::from_sources(
         requirements,
         constraints,
         overrides,
+        build_constraints,
         &extras,
         connectivity,
     )
     .await?;
 
+    // Check out any editable Git requirements.
+    checkout_editables(&spec.editables, &cache, connectivity).await?;
+
     // Check that all provided extras are used.
     if let ExtrasSpecification::Some(extras) = extras {
         let mut unused_extras = extras
@@ -116,20 +169,42 @@ pub(crate) async fn pip_compile(
         }
     }
 
-    // Convert from unnamed to named requirements.
+    // Convert from unnamed to named requirements, downloading any archives that can't be named
+    // from their URLs alone.
+    let client_builder = BaseClientBuilder::new()
+        .native_tls(native_tls)
+        .cert(cert)
+        .proxy(proxy)
+        .retries(retries)
+        .connectivity(connectivity)
+        .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_host.clone());
     let NamedRequirements {
         project,
-        requirements,
+        mut requirements,
+        hashes: _hashes,
         constraints,
+        constraint_sources,
+        constraint_hashes: _constraint_hashes,
         overrides,
+        build_constraints,
         editables,
         index_url,
         extra_index_urls,
         no_index,
         find_links,
-    } = NamedRequirements::from_spec(spec)?;
+        pinned_indexes,
+        unnamed,
+    } = NamedRequirements::from_spec(spec, &client_builder, &cache).await?;
+
+    // Apply any version bounds attached to the packages to upgrade.
+    let constraints = constraints
+        .into_iter()
+        .chain(upgrade.constraints().cloned())
+        .collect::<Vec<_>>();
 
     // Read the lockfile, if present.
+    let upgrade_requested = !upgrade.is_none();
     let preferences = read_lockfile(output_file, upgrade).await?;
 
     // Find an interpreter to use for building distributions
@@ -163,34 +238,132 @@ pub(crate) async fn pip_compile(
     // Create a shared in-memory index.
     let source_index = InMemoryIndex::default();
 
-    // If we're resolving against a different Python version, use a separate index. Source
-    // distributions will be built against the installed version, and so the index may contain
-    // different package priorities than in the top-level resolution.
-    let top_level_index = if python_version.is_some() {
-        InMemoryIndexRef::Owned(InMemoryIndex::default())
-    } else {
-        InMemoryIndexRef::Borrowed(&source_index)
-    };
+    // If we're resolving against a different Python version or platform, use a separate index.
+    // Source distributions will be built against the installed version, and so the index may
+    // contain different package priorities than in the top-level resolution.
+    let top_level_index =
+        if python_version.is_some() || python_platform.is_some() || !python_versions.is_empty() {
+            InMemoryIndexRef::Owned(InMemoryIndex::default())
+        } else {
+            InMemoryIndexRef::Borrowed(&source_index)
+        };
 
     // Determine the tags, markers, and interpreter to use for resolution.
-    let tags = if let Some(python_version) = python_version.as_ref() {
-        Cow::Owned(Tags::from_env(
-            interpreter.platform(),
-            (python_version.major(), python_version.minor()),
-            interpreter.implementation_name(),
-            interpreter.implementation_tuple(),
+    let (tags, markers) = target_environment(
+        &interpreter,
+        python_version.as_ref(),
+        python_platform.as_ref(),
+    )?;
+
+    // Incorporate any index locations from the provided sources.
+    let index_locations = index_locations
+        .combine(index_url, extra_index_urls, find_links, no_index)
+        .pin(pinned_indexes);
+
+    let options = OptionsBuilder::new()
+        .resolution_mode(resolution_mode)
+        .prerelease_mode(prerelease_mode)
+        .dependency_mode(dependency_mode)
+        .exclude_newer(exclude_newer)
+        .exclude_newer_package(exclude_newer_package.clone())
+        .allow_yanked(allow_yanked)
+        .universal(universal)
+        .preserve_markers(include_markers)
+        .build();
+
+    // If none of the inputs have changed since a previous invocation, reuse its output (unless
+    // upgrades were requested). Editables, direct URLs, and `--find-links` are excluded, since
+    // their contents can change in-place.
+    let cacheable = editables.is_empty()
+        && unnamed.is_empty()
+        && index_locations.flat_index().next().is_none()
+        && requirements
+            .iter()
+            .chain(&constraints)
+            .chain(&overrides)
+            .all(|requirement| !matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_))));
+    let resolution_cache = if cacheable {
+        let key = ResolutionKey::new()
+            .set("requirements", &requirements)
+            .set("constraints", &constraints)
+            .set(
+                "constraint-sources",
+                constraint_sources
+                    .iter()
+                    .map(|(name, sources)| format!("{name}: {}", sources.iter().join(", "))),
+            )
+            .set("overrides", &overrides)
+            .set("build-constraints", &build_constraints)
+            .set(
+                "preferences",
+                preferences.iter().map(|preference| {
+                    let hashes = preference.hashes().iter().filter_map(Hashes::to_string);
+                    format!("{} {}", preference.requirement(), hashes.sorted().join(" "))
+                }),
+            )
+            .list("project", project.as_ref())
+            .value("markers", format!("{:?}", &*markers))
+            .value("platform", format!("{:?}", interpreter.platform()))
+            .list("python-platform", python_platform.as_ref())
+            .list("python-versions", &python_versions)
+            .value("interpreter", interpreter.sys_executable().user_display())
+            .index_locations(&index_locations, index_strategy)
+            .options(&options)
+            .value("setup-py", format!("{setup_py:?}"))
+            .value("config-settings", format!("{config_settings:?}"))
+            .value(
+                "package-config-settings",
+                format!("{package_config_settings:?}"),
+            )
+            .value("no-build", format!("{no_build:?}"))
+            .value("no-binary", format!("{no_binary:?}"))
+            .value("no-build-isolation", no_build_isolation)
+            .set("no-build-isolation-package", no_build_isolation_package)
+            .value("format", format!("{format:?}"))
+            .set("no-emit-package", &no_emit_packages)
+            .value("annotation-style", format!("{annotation_style:?}"))
+            .list(
+                "output-options",
+                [
+                    generate_hashes,
+                    allow_unsafe,
+                    include_extras,
+                    include_annotations,
+                    include_index_url,
+                    include_find_links,
+                ],
+            );
+        Some(ResolutionCache::new(
+            &cache,
+            "pip-compile",
+            &key,
+            exclude_newer.is_some(),
         )?)
     } else {
-        Cow::Borrowed(interpreter.tags()?)
+        None
     };
-    let markers = python_version.map_or_else(
-        || Cow::Borrowed(interpreter.markers()),
-        |python_version| Cow::Owned(python_version.markers(interpreter.markers())),
-    );
-
-    // Incorporate any index locations from the provided sources.
-    let index_locations =
-        index_locations.combine(index_url, extra_index_urls, find_links, no_index);
+    if let Some(compilation) = resolution_cache
+        .as_ref()
+        .filter(|_| !upgrade_requested)
+        .and_then(ResolutionCache::read::<Compilation>)
+    {
+        if diff {
+            return compilation.diff(output_file, start, printer).await;
+        }
+        compilation.write(
+            output_file,
+            quiet,
+            include_header,
+            &cmd(
+                include_index_url,
+                include_find_links,
+                custom_compile_command,
+            ),
+            start,
+            printer,
+        )?;
+        return Ok(ExitStatus::Success);
+    }
 
     // Add all authenticated sources to the store.
     for url in index_locations.urls() {
@@ -200,9 +373,14 @@ pub(crate) async fn pip_compile(
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
         .native_tls(native_tls)
+        .cert(cert)
+        .proxy(proxy)
+        .retries(retries)
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
+        .index_strategy(index_strategy)
         .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_host)
         .markers(&markers)
         .platform(interpreter.platform())
         .build();
@@ -222,8 +400,11 @@ pub(crate) async fn pip_compile(
     let build_isolation = if no_build_isolation {
         venv = PythonEnvironment::from_interpreter(interpreter.clone());
         BuildIsolation::Shared(&venv)
-    } else {
+    } else if no_build_isolation_package.is_empty() {
         BuildIsolation::Isolated
+    } else {
+        venv = PythonEnvironment::from_interpreter(interpreter.clone());
+        BuildIsolation::SharedPackage(&venv, no_build_isolation_package)
     };
 
     let build_dispatch = BuildDispatch::new(
@@ -238,9 +419,21 @@ pub(crate) async fn pip_compile(
         &config_settings,
         build_isolation,
         no_build,
-        &NoBinary::None,
+        no_binary,
+    )
+    .with_package_config_settings(package_config_settings)
+    .with_concurrency(concurrency)
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer)
+            .exclude_newer_package(exclude_newer_package.clone())
+            .build(),
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_build_constraints(build_constraints);
+
+    // Infer the names of any local projects that lack static metadata, which requires a build
+    // environment.
+    requirements.extend(NamedRequirements::name_with_build(unnamed, &build_dispatch).await?);
 
     // Build the editables and add their requirements
     let editable_metadata = if editables.is_empty() {
@@ -251,8 +444,19 @@ pub(crate) async fn pip_compile(
         let editables: Vec<LocalEditable> = editables
             .into_iter()
             .map(|editable| {
-                let EditableRequirement { url, extras, path } = editable;
-                Ok(LocalEditable { url, path, extras })
+                let EditableRequirement {
+                    url,
+                    extras,
+                    path,
+                    dependency,
+                    ..
+                } = editable;
+                Ok(LocalEditable {
+                    url,
+                    path,
+                    extras,
+                    dependency,
+                })
             })
             .collect::<Result<_>>()?;
 
@@ -308,143 +512,383 @@ pub(crate) async fn pip_compile(
         editable_metadata,
     );
 
-    let options = OptionsBuilder::new()
-        .resolution_mode(resolution_mode)
-        .prerelease_mode(prerelease_mode)
-        .dependency_mode(dependency_mode)
-        .exclude_newer(exclude_newer)
-        .build();
+    // Resolve the dependencies, once for each Python version in the matrix (if any). A universal
+    // resolution may also fork into a resolution for each set of conflicting markers.
+    let targets: Vec<Option<&PythonVersion>> = if python_versions.is_empty() {
+        vec![None]
+    } else {
+        python_versions.iter().map(Some).collect()
+    };
+    let mut index = top_level_index;
+    let mut resolutions = Vec::with_capacity(targets.len());
+    for target in targets {
+        let (tags, markers) = match target {
+            Some(python_version) => {
+                target_environment(&interpreter, Some(python_version), python_platform.as_ref())?
+            }
+            None => (Cow::Borrowed(&*tags), Cow::Borrowed(&*markers)),
+        };
 
-    // Resolve the dependencies.
-    let resolver = Resolver::new(
-        manifest,
-        options,
-        &markers,
-        &interpreter,
-        &tags,
-        &client,
-        &flat_index,
-        &top_level_index,
-        &build_dispatch,
-    )?
-    .with_reporter(ResolverReporter::from(printer));
-
-    let resolution = match resolver.resolve().await {
-        Err(uv_resolver::ResolveError::NoSolution(err)) => {
-            let report = miette::Report::msg(format!("{err}"))
-                .context("No solution found when resolving dependencies:");
-            eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+        // Reuse the distribution metadata fetched while resolving for the previous version.
+        if !resolutions.is_empty() {
+            index = InMemoryIndexRef::Owned(index.fork());
         }
-        result => result,
-    }?;
-
-    let s = if resolution.len() == 1 { "" } else { "s" };
-    writeln!(
-        printer.stderr(),
-        "{}",
-        format!(
-            "Resolved {} in {}",
-            format!("{} package{}", resolution.len(), s).bold(),
-            elapsed(start.elapsed())
-        )
-        .dimmed()
-    )?;
 
-    // Notify the user of any diagnostics.
-    for diagnostic in resolution.diagnostics() {
-        writeln!(
-            printer.stderr(),
-            "{}{} {}",
-            "warning".yellow().bold(),
-            ":".bold(),
-            diagnostic.message().bold()
-        )?;
+        let resolver = Resolver::new(
+            manifest.clone(),
+            options.clone(),
+            &markers,
+            &interpreter,
+            &tags,
+            &client,
+            &flat_index,
+            &index,
+            &build_dispatch,
+        )?
+        .with_reporter(ResolverReporter::from(printer));
+
+        let forks = match resolver.resolve_forks().await {
+            Err(uv_resolver::ResolveError::NoSolution(err)) => {
+                let context = match target {
+                    Some(python_version) => format!(
+                        "No solution found when resolving dependencies for Python {python_version}:"
+                    ),
+                    None => "No solution found when resolving dependencies:".to_string(),
+                };
+                diagnostics::report_no_solution(&err, &context, printer);
+                return Ok(ExitStatus::Failure);
+            }
+            result => result,
+        }?;
+
+        // Label each resolution with the markers under which it applies.
+        let python_marker =
+            target.map(|_| format!("python_version == '{}'", markers.python_version));
+        for (fork, resolution) in forks {
+            let label = match (&python_marker, fork) {
+                (Some(python_marker), Some(MarkerTree::Or(fork))) => {
+                    format!("{python_marker} and ({})", MarkerTree::Or(fork))
+                }
+                (Some(python_marker), Some(fork)) => format!("{python_marker} and {fork}"),
+                (Some(python_marker), None) => python_marker.clone(),
+                (None, Some(fork)) => fork.to_string(),
+                (None, None) => String::new(),
+            };
+            resolutions.push((label, resolution));
+        }
     }
 
-    // Write the resolved dependencies to the output channel.
-    let mut writer = OutputWriter::new(!quiet || output_file.is_none(), output_file)?;
+    // A `pylock.toml` (or an exported graph) can only represent a single resolution.
+    if resolutions.len() > 1 && format != ExportFormat::RequirementsTxt {
+        return Err(anyhow!(
+            "The resolution forked on conflicting markers, which is only supported when exporting to `requirements.txt`"
+        ));
+    }
 
-    if include_header {
-        writeln!(
-            writer,
-            "{}",
-            "# This file was autogenerated by uv via the following command:".green()
-        )?;
-        writeln!(
-            writer,
-            "{}",
-            format!(
-                "#    {}",
-                cmd(
-                    include_index_url,
-                    include_find_links,
-                    custom_compile_command
-                )
-            )
-            .green()
+    // Render the resolved dependencies.
+    let mut output = String::new();
+
+    if format == ExportFormat::PylockToml {
+        // If requested, write a `pylock.toml` lockfile in lieu of a `requirements.txt` file.
+        let lock = PyLock::from_resolution(&resolutions[0].1, &no_emit_packages)?;
+        write!(output, "{}", lock.to_toml()?)?;
+    } else if matches!(format, ExportFormat::Json | ExportFormat::Toml) {
+        // If requested, write the resolved graph in a machine-readable format.
+        let export = GraphExport::from_resolution(
+            &resolutions[0].1,
+            &no_emit_packages,
+            generate_hashes,
+            include_extras,
         )?;
-    }
+        if format == ExportFormat::Json {
+            writeln!(output, "{}", export.to_json()?)?;
+        } else {
+            write!(output, "{}", export.to_toml()?)?;
+        }
+    } else {
+        // Write the index locations to the output channel.
+        let mut wrote_index = false;
+
+        // If necessary, include the `--index-url` and `--extra-index-url` locations.
+        if include_index_url {
+            if let Some(index) = index_locations.index() {
+                writeln!(output, "--index-url {}", index.verbatim())?;
+                wrote_index = true;
+            }
+            for extra_index in index_locations.extra_index() {
+                writeln!(output, "--extra-index-url {}", extra_index.verbatim())?;
+                wrote_index = true;
+            }
+        }
 
-    // Write the index locations to the output channel.
-    let mut wrote_index = false;
+        // If necessary, include the `--find-links` locations.
+        if include_find_links {
+            for flat_index in index_locations.flat_index() {
+                writeln!(output, "--find-links {flat_index}")?;
+                wrote_index = true;
+            }
+        }
 
-    // If necessary, include the `--index-url` and `--extra-index-url` locations.
-    if include_index_url {
-        if let Some(index) = index_locations.index() {
-            writeln!(writer, "--index-url {}", index.verbatim())?;
-            wrote_index = true;
+        // If we wrote an index, add a newline to separate it from the requirements
+        if wrote_index {
+            writeln!(output)?;
         }
-        for extra_index in index_locations.extra_index() {
-            writeln!(writer, "--extra-index-url {}", extra_index.verbatim())?;
-            wrote_index = true;
+
+        // Render the resolution, omitting the given packages. A matrix of resolutions (across
+        // Python versions, or across the forks of a universal resolution) is folded into a single
+        // output, with markers on any pins that differ.
+        let display = |omit: &[PackageName]| {
+            if python_versions.is_empty() && resolutions.len() == 1 {
+                DisplayResolutionGraph::new(
+                    &resolutions[0].1,
+                    omit,
+                    generate_hashes,
+                    include_extras,
+                    include_annotations,
+                    annotation_style,
+                )
+                .with_constraint_sources(&constraint_sources)
+                .to_string()
+            } else {
+                DisplayResolutionMatrix::new(
+                    &resolutions,
+                    omit,
+                    generate_hashes,
+                    include_extras,
+                    include_annotations,
+                    annotation_style,
+                )
+                .with_constraint_sources(&constraint_sources)
+                .to_string()
+            }
+        };
+
+        write!(output, "{}", display(&no_emit_packages))?;
+
+        // If any "unsafe" packages were excluded, notify the user.
+        let excluded = no_emit_packages
+            .into_iter()
+            .filter(|name| {
+                resolutions
+                    .iter()
+                    .any(|(_, resolution)| resolution.contains(name))
+            })
+            .collect::<Vec<_>>();
+        if !excluded.is_empty() && allow_unsafe {
+            // Pin the "unsafe" packages in a dedicated section, as in pip-compile.
+            let others = resolutions
+                .iter()
+                .flat_map(|(_, resolution)| resolution.petgraph().node_weights())
+                .map(|dist| dist.name().clone())
+                .filter(|name| !excluded.contains(name))
+                .unique()
+                .collect::<Vec<_>>();
+            writeln!(output)?;
+            writeln!(
+                output,
+                "{}",
+                "# The following packages are considered to be unsafe in a requirements file:"
+                    .green()
+            )?;
+            write!(output, "{}", display(&others))?;
+        } else if !excluded.is_empty() {
+            writeln!(output)?;
+            writeln!(
+                output,
+                "{}",
+                "# The following packages were excluded from the output:".green()
+            )?;
+            for package in excluded {
+                writeln!(output, "# {package}")?;
+            }
         }
     }
 
-    // If necessary, include the `--find-links` locations.
-    if include_find_links {
-        for flat_index in index_locations.flat_index() {
-            writeln!(writer, "--find-links {flat_index}")?;
-            wrote_index = true;
-        }
+    let packages = resolutions
+        .iter()
+        .flat_map(|(_, resolution)| resolution.petgraph().node_weights())
+        .map(Name::name)
+        .unique()
+        .cloned()
+        .collect::<Vec<_>>();
+    let compilation = Compilation {
+        packages: packages.len(),
+        diagnostics: resolutions
+            .iter()
+            .flat_map(|(_, resolution)| resolution.diagnostics())
+            .map(Diagnostic::message)
+            .unique()
+            .collect(),
+        output,
+    };
+
+    // Cache the output, for reuse by subsequent invocations.
+    if let Some(resolution_cache) = resolution_cache.as_ref() {
+        resolution_cache.write(packages, &compilation)?;
     }
 
-    // If we wrote an index, add a newline to separate it from the requirements
-    if wrote_index {
-        writeln!(writer)?;
+    if diff {
+        return compilation.diff(output_file, start, printer).await;
     }
 
-    write!(
-        writer,
-        "{}",
-        DisplayResolutionGraph::new(
-            &resolution,
-            &no_emit_packages,
-            generate_hashes,
-            include_extras,
-            include_annotations,
-            annotation_style,
-        )
+    compilation.write(
+        output_file,
+        quiet,
+        // JSON doesn't support comments, so omit the header.
+        include_header && format != ExportFormat::Json,
+        cmd(
+            include_index_url,
+            include_find_links,
+            custom_compile_command,
+        ),
+        start,
+        printer,
     )?;
 
-    // If any "unsafe" packages were excluded, notify the user.
-    let excluded = no_emit_packages
-        .into_iter()
-        .filter(|name| resolution.contains(name))
-        .collect::<Vec<_>>();
-    if !excluded.is_empty() {
-        writeln!(writer)?;
+    Ok(ExitStatus::Success)
+}
+
+/// Determine the tags and markers to use for resolution, accounting for any requested target
+/// Python version or platform.
+fn target_environment<'a>(
+    interpreter: &'a Interpreter,
+    python_version: Option<&PythonVersion>,
+    python_platform: Option<&PythonPlatform>,
+) -> Result<(Cow<'a, Tags>, Cow<'a, MarkerEnvironment>)> {
+    let tags = if python_version.is_some() || python_platform.is_some() {
+        Cow::Owned(Tags::from_env(
+            python_platform.map_or(interpreter.platform(), PythonPlatform::platform),
+            python_version.map_or(
+                (interpreter.python_major(), interpreter.python_minor()),
+                |python_version| (python_version.major(), python_version.minor()),
+            ),
+            interpreter.implementation_name(),
+            interpreter.implementation_tuple(),
+            interpreter.gil_disabled(),
+        )?)
+    } else {
+        Cow::Borrowed(interpreter.tags()?)
+    };
+    let markers = python_version.map_or_else(
+        || Cow::Borrowed(interpreter.markers()),
+        |python_version| Cow::Owned(python_version.clone().markers(interpreter.markers())),
+    );
+    let markers = match python_platform {
+        Some(python_platform) => Cow::Owned(python_platform.markers(&markers)),
+        None => markers,
+    };
+    Ok((tags, markers))
+}
+
+/// The output of a `pip compile` invocation, exclusive of the header.
+#[derive(Debug, Serialize, Deserialize)]
+struct Compilation {
+    /// The number of packages in the resolution.
+    packages: usize,
+    /// Any diagnostics that were encountered during resolution.
+    diagnostics: Vec<String>,
+    /// The rendered requirements.
+    output: String,
+}
+
+impl Compilation {
+    /// Write the compilation to the output channels, alongside a summary of the resolution.
+    fn write(
+        &self,
+        output_file: Option<&Path>,
+        quiet: bool,
+        include_header: bool,
+        cmd: &str,
+        start: std::time::Instant,
+        printer: Printer,
+    ) -> Result<()> {
+        self.report(start, printer)?;
+
+        // Write the resolved dependencies to the output channel.
+        let mut writer = OutputWriter::new(!quiet || output_file.is_none(), output_file)?;
+
+        if include_header {
+            writeln!(
+                writer,
+                "{}",
+                "# This file was autogenerated by uv via the following command:".green()
+            )?;
+            writeln!(writer, "{}", format!("#    {cmd}").green())?;
+        }
+
+        write!(writer, "{}", self.output)?;
+
+        Ok(())
+    }
+
+    /// Compare the compilation against the existing output file, rather than writing it, and
+    /// report the packages that would be added, removed, or changed.
+    ///
+    /// Returns a failure status if the output file is missing or out of date.
+    async fn diff(
+        &self,
+        output_file: Option<&Path>,
+        start: std::time::Instant,
+        printer: Printer,
+    ) -> Result<ExitStatus> {
+        let output_file = output_file.context("`--diff` requires an output file")?;
+
+        self.report(start, printer)?;
+
+        let existing = match fs_err::read_to_string(output_file) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err.into()),
+        };
+        let updated = anstream::adapter::strip_str(&self.output).to_string();
+        let diff = PinDiff::from_contents(&existing, &updated).await?;
+
+        if diff.is_empty() {
+            writeln!(
+                printer.stderr(),
+                "{}",
+                format!("`{}` is up to date", output_file.user_display()).dimmed()
+            )?;
+            return Ok(ExitStatus::Success);
+        }
+
+        write!(printer.stdout(), "{diff}")?;
         writeln!(
-            writer,
+            printer.stderr(),
+            "`{}` is out of date ({})",
+            output_file.user_display(),
+            diff.summary()
+        )?;
+        Ok(ExitStatus::Failure)
+    }
+
+    /// Report a summary of the resolution, along with any diagnostics.
+    fn report(&self, start: std::time::Instant, printer: Printer) -> Result<()> {
+        let s = if self.packages == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
             "{}",
-            "# The following packages were excluded from the output:".green()
+            format!(
+                "Resolved {} in {}",
+                format!("{} package{}", self.packages, s).bold(),
+                elapsed(start.elapsed())
+            )
+            .dimmed()
         )?;
-        for package in excluded {
-            writeln!(writer, "# {package}")?;
+
+        // Notify the user of any diagnostics.
+        for diagnostic in &self.diagnostics {
+            writeln!(
+                printer.stderr(),
+                "{}{} {}",
+                "warning".yellow().bold(),
+                ":".bold(),
+                diagnostic.bold()
+            )?;
         }
-    }
 
-    Ok(ExitStatus::Success)
+        Ok(())
+    }
 }
 
 /// Format the `uv` command used to generate the output file.
@@ -469,14 +913,17 @@ fn cmd(
 
             // Skip any index URLs, unless requested.
             if !include_index_url {
-                if arg.starts_with("--extra-index-url=") || arg.starts_with("--index-url=") {
+                if arg.starts_with("--extra-index-url=")
+                    || arg.starts_with("--index-url=")
+                    || (arg.starts_with("-i") && arg.len() > 2)
+                {
                     // Reset state; skip this iteration.
                     *skip_next = None;
                     return Some(None);
                 }
 
                 // Mark the next item as (to be) skipped.
-                if arg == "--index-url" || arg == "--extra-index-url" {
+                if arg == "--index-url" || arg == "--extra-index-url" || arg == "-i" {
                     *skip_next = Some(true);
                     return Some(None);
                 }
@@ -484,7 +931,7 @@ fn cmd(
 
             // Skip any `--find-links` URLs, unless requested.
             if !include_find_links {
-                if arg.starts_with("--find-links=") || arg.starts_with("-f=") {
+                if arg.starts_with("--find-links=") || (arg.starts_with("-f") && arg.len() > 2) {
                     // Reset state; skip this iteration.
                     *skip_next = None;
                     return Some(None);
@@ -572,22 +1019,44 @@ pub(crate) enum Upgrade {
     /// Allow package upgrades for all packages, ignoring the existing lockfile.
     All,
 
-    /// Allow package upgrades, but only for the specified packages.
-    Packages(FxHashSet<PackageName>),
+    /// Allow package upgrades, but only for the specified packages, optionally within the given
+    /// version bounds (e.g., `django<5`).
+    Packages(FxHashMap<PackageName, Vec<Requirement>>),
 }
 
 impl Upgrade {
     /// Determine the upgrade strategy from the command-line arguments.
-    pub(crate) fn from_args(upgrade: bool, upgrade_package: Vec<PackageName>) -> Self {
+    pub(crate) fn from_args(upgrade: bool, upgrade_package: Vec<Requirement>) -> Self {
         if upgrade {
             Self::All
         } else if !upgrade_package.is_empty() {
-            Self::Packages(upgrade_package.into_iter().collect())
+            let mut packages = FxHashMap::<PackageName, Vec<Requirement>>::default();
+            for requirement in upgrade_package {
+                packages
+                    .entry(requirement.name.clone())
+                    .or_default()
+                    .push(requirement);
+            }
+            Self::Packages(packages)
         } else {
             Self::None
         }
     }
 
+    /// Returns the version bounds for the packages to upgrade, to be applied as constraints
+    /// (e.g., `django<5` for `--upgrade-package 'django<5'`).
+    pub(crate) fn constraints(&self) -> impl Iterator<Item = &Requirement> {
+        let packages = match self {
+            Self::Packages(packages) => Some(packages),
+            Self::None | Self::All => None,
+        };
+        packages
+            .into_iter()
+            .flat_map(FxHashMap::values)
+            .flatten()
+            .filter(|requirement| requirement.version_or_url.is_some())
+    }
+
     /// Returns `true` if no packages should be upgraded.
     pub(crate) fn is_none(&self) -> bool {
         matches!(self, Self::None)
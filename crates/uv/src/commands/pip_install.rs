@@ -1,50 +1,70 @@
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Instant;
 
-use anstream::eprint;
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
+use futures::StreamExt;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
 use tempfile::tempdir_in;
 use tracing::debug;
+use url::Url;
 
 use distribution_types::{
-    DistributionMetadata, IndexLocations, InstalledMetadata, LocalDist, LocalEditable, Name,
-    Resolution,
+    Dist, DistributionMetadata, FileLocation, IndexLocations, InstalledMetadata, LocalDist,
+    LocalEditable, LocalFileUrl, Name, PathSourceDist, Resolution, SourceDist, VersionOrUrl,
 };
 use install_wheel_rs::linker::LinkMode;
-use pep508_rs::{MarkerEnvironment, Requirement};
+use pep508_rs::{MarkerEnvironment, Requirement, RequirementsTxtRequirement};
 use platform_tags::Tags;
-use pypi_types::Yanked;
-use requirements_txt::EditableRequirement;
+use pypi_types::{base_url_join_relative, ArchiveInfo, DirectUrl, Hashes, Metadata23, Yanked};
+use requirements_txt::{EditableRequirement, RequirementEntry};
 use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
+use uv_client::{
+    BaseClientBuilder, Connectivity, FlatIndex, FlatIndexClient, IndexStrategy, RegistryClient,
+    RegistryClientBuilder, TrustedHost,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::{
     BuiltEditable, Downloader, NoBinary, Plan, Planner, Reinstall, ResolvedEditable, SitePackages,
 };
-use uv_interpreter::{Interpreter, PythonEnvironment};
+use uv_interpreter::{
+    Interpreter, PythonEnvironment, PythonImplementation, PythonPlatform, PythonVersion,
+};
 use uv_normalize::PackageName;
 use uv_resolver::{
-    DependencyMode, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode, Preference,
-    ResolutionGraph, ResolutionMode, Resolver,
+    AllowYanked, DependencyMode, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
+    Preference, ResolutionGraph, ResolutionMode, Resolver,
+};
+use uv_traits::{
+    BuildContext, BuildIsolation, Concurrency, ConfigSettings, InFlight, NoBuild,
+    PackageConfigSettings, SetupPyStrategy,
 };
-use uv_traits::{BuildIsolation, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
 use uv_warnings::warn_user;
 
 use crate::commands::reporters::{DownloadReporter, InstallReporter, ResolverReporter};
-use crate::commands::{compile_bytecode, elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::resolution_cache::{ResolutionCache, ResolutionKey};
+use crate::commands::sbom::{write_sbom, SbomComponent, SbomFormat};
+use crate::commands::{
+    checkout_editables, compile_bytecode, elapsed, report_environment, report_planned_changes,
+    report_verified_hashes, verify_hashes, ChangeEvent, ChangeEventKind, ExitStatus,
+};
+use crate::diagnostics;
 use crate::printer::Printer;
 use crate::requirements::{
     ExtrasSpecification, NamedRequirements, RequirementsSource, RequirementsSpecification,
 };
 
-use super::{DryRunEvent, Upgrade};
+use super::Upgrade;
 
 /// Install packages into the current environment.
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
@@ -52,54 +72,115 @@ pub(crate) async fn pip_install(
     requirements: &[RequirementsSource],
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
+    build_constraints: &[RequirementsSource],
     extras: &ExtrasSpecification<'_>,
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
     index_locations: IndexLocations,
+    index_strategy: IndexStrategy,
     keyring_provider: KeyringProvider,
+    trusted_host: Vec<TrustedHost>,
     reinstall: &Reinstall,
-    link_mode: LinkMode,
+    link_mode: Option<LinkMode>,
     compile: bool,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
     config_settings: &ConfigSettings,
+    package_config_settings: &PackageConfigSettings,
     no_build_isolation: bool,
+    no_build_isolation_package: &[PackageName],
     no_build: &NoBuild,
     no_binary: &NoBinary,
+    require_hashes: bool,
     strict: bool,
     exclude_newer: Option<DateTime<Utc>>,
+    exclude_newer_package: FxHashMap<PackageName, DateTime<Utc>>,
+    allow_yanked: AllowYanked,
+    python_version: Option<PythonVersion>,
+    python_platform: Option<PythonPlatform>,
+    python_implementation: Option<PythonImplementation>,
+    abi: &[String],
     python: Option<String>,
     system: bool,
     break_system_packages: bool,
+    user: bool,
+    target: Option<&Path>,
     native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
     cache: Cache,
     dry_run: bool,
+    json: bool,
+    report: Option<&Path>,
+    sbom: Option<&Path>,
+    sbom_format: SbomFormat,
     printer: Printer,
 ) -> Result<ExitStatus> {
     let start = Instant::now();
 
     // Read all requirements from the provided sources.
-    let spec =
-        read_requirements(requirements, constraints, overrides, extras, connectivity).await?;
+    let spec = read_requirements(
+        requirements,
+        constraints,
+        overrides,
+        build_constraints,
+        extras,
+        connectivity,
+    )
+    .await?;
+
+    // Check out any editable Git requirements.
+    checkout_editables(&spec.editables, &cache, connectivity).await?;
 
     // Detect the current Python interpreter.
     let venv = if let Some(python) = python.as_ref() {
-        PythonEnvironment::from_requested_python(python, &cache)?
-    } else if system {
+        if user {
+            PythonEnvironment::from_requested_python(python, &cache)?
+        } else {
+            PythonEnvironment::from_requested_python_or_virtualenv(python, &cache)?
+        }
+    } else if system || user {
         PythonEnvironment::from_default_python(&cache)?
+    } else if target.is_some() {
+        // Installing into a target directory doesn't require a virtual environment.
+        match PythonEnvironment::from_virtualenv(&cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(&cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
     } else {
         PythonEnvironment::from_virtualenv(&cache)?
     };
+
+    // If requested, install into the user site-packages or a target directory instead.
+    let venv = if user {
+        venv.with_user_site()?
+    } else if let Some(target) = target {
+        venv.with_target(target)?
+    } else {
+        venv
+    };
     debug!(
         "Using Python {} environment at {}",
         venv.interpreter().python_version(),
         venv.python_executable().user_display().cyan()
     );
 
-    // If the environment is externally managed, abort.
-    if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
+    report_environment(&venv, printer)?;
+
+    // If the environment is externally managed, abort (unless we're installing elsewhere).
+    let externally_managed = if target.is_some() {
+        None
+    } else {
+        venv.interpreter().is_externally_managed()
+    };
+    if let Some(externally_managed) = externally_managed {
         if break_system_packages {
             debug!("Ignoring externally managed environment due to `--break-system-packages`");
         } else {
@@ -125,9 +206,12 @@ pub(crate) async fn pip_install(
 
     // If the requirements are already satisfied, we're done. Ideally, the resolver would be fast
     // enough to let us remove this check. But right now, for large environments, it's an order of
-    // magnitude faster to validate the environment than to resolve the requirements.
-    if reinstall.is_none()
+    // magnitude faster to validate the environment than to resolve the requirements. (If a report
+    // was requested, we need the full resolution regardless.)
+    if report.is_none()
+        && reinstall.is_none()
         && upgrade.is_none()
+        && spec.unnamed_constraints.is_empty()
         && site_packages.satisfies(&spec.requirements, &spec.editables, &spec.constraints)?
     {
         let num_requirements = spec.requirements.len() + spec.editables.len();
@@ -144,31 +228,202 @@ pub(crate) async fn pip_install(
         )?;
         if dry_run {
             writeln!(printer.stderr(), "Would make no changes")?;
+            if json {
+                writeln!(printer.stdout(), "[]")?;
+            }
+        } else if let Some(sbom) = sbom {
+            write_environment_sbom(&venv, None, sbom_format, sbom, printer)?;
         }
         return Ok(ExitStatus::Success);
     }
 
-    // Convert from unnamed to named requirements.
+    // Convert from unnamed to named requirements, downloading any archives that can't be named
+    // from their URLs alone.
+    let client_builder = BaseClientBuilder::new()
+        .native_tls(native_tls)
+        .cert(cert)
+        .proxy(proxy)
+        .retries(retries)
+        .connectivity(connectivity)
+        .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_host.clone());
+    let named = NamedRequirements::from_spec(spec, &client_builder, &cache).await?;
+
+    // Determine the hashes that the distributions must match, in hash-checking mode.
+    let required_hashes = named.required_hashes(require_hashes)?;
+    let require_hashes = named.hash_checking_mode(require_hashes);
+
     let NamedRequirements {
         project,
-        requirements,
+        mut requirements,
+        hashes: _hashes,
         constraints,
+        constraint_sources: _constraint_sources,
+        constraint_hashes: _constraint_hashes,
         overrides,
+        build_constraints,
         editables,
         index_url,
         extra_index_urls,
         no_index,
         find_links,
-    } = NamedRequirements::from_spec(spec)?;
+        pinned_indexes,
+        unnamed,
+    } = named;
+
+    // Source distributions can only be built with the interpreter itself, so only allow wheels
+    // when targeting another Python version, platform, implementation, or ABI.
+    let no_build = if python_version.as_ref().is_some_and(|python_version| {
+        (python_version.major(), python_version.minor()) != venv.interpreter().python_tuple()
+    }) || python_platform.is_some()
+        || python_implementation.is_some()
+        || !abi.is_empty()
+    {
+        debug!("Disabling source distribution builds for the target environment");
+        &NoBuild::All
+    } else {
+        no_build
+    };
 
-    // Determine the tags, markers, and interpreter to use for resolution.
+    // Determine the tags, markers, and interpreter to use for resolution, accounting for any
+    // requested target Python version, platform, implementation, or ABI.
     let interpreter = venv.interpreter().clone();
-    let tags = venv.interpreter().tags()?;
-    let markers = venv.interpreter().markers();
+    let target_tags =
+        if python_version.is_some() || python_platform.is_some() || python_implementation.is_some()
+        {
+            let python_tuple = python_version.as_ref().map_or(
+                (interpreter.python_major(), interpreter.python_minor()),
+                |python_version| (python_version.major(), python_version.minor()),
+            );
+            let (implementation_name, implementation_tuple, gil_disabled) =
+                match python_implementation {
+                    Some(python_implementation) => (
+                        python_implementation.name(),
+                        python_implementation.version(python_tuple),
+                        false,
+                    ),
+                    None => (
+                        interpreter.implementation_name(),
+                        interpreter.implementation_tuple(),
+                        interpreter.gil_disabled(),
+                    ),
+                };
+            Cow::Owned(Tags::from_env(
+                python_platform
+                    .as_ref()
+                    .map_or(interpreter.platform(), PythonPlatform::platform),
+                python_tuple,
+                implementation_name,
+                implementation_tuple,
+                gil_disabled,
+            )?)
+        } else {
+            Cow::Borrowed(venv.interpreter().tags()?)
+        };
+    let target_tags = if abi.is_empty() {
+        target_tags
+    } else {
+        Cow::Owned(target_tags.with_abis(abi))
+    };
+    let target_markers = match (
+        python_version,
+        python_platform.as_ref(),
+        python_implementation,
+    ) {
+        (None, None, None) => Cow::Borrowed(venv.interpreter().markers()),
+        (python_version, python_platform, python_implementation) => {
+            let markers = python_version.map_or_else(
+                || venv.interpreter().markers().clone(),
+                |python_version| python_version.markers(venv.interpreter().markers()),
+            );
+            let markers = match python_platform {
+                Some(python_platform) => python_platform.markers(&markers),
+                None => markers,
+            };
+            Cow::Owned(match python_implementation {
+                Some(python_implementation) => python_implementation.markers(&markers),
+                None => markers,
+            })
+        }
+    };
+    let tags = target_tags.as_ref();
+    let markers = target_markers.as_ref();
 
     // Incorporate any index locations from the provided sources.
-    let index_locations =
-        index_locations.combine(index_url, extra_index_urls, find_links, no_index);
+    let index_locations = index_locations
+        .combine(index_url, extra_index_urls, find_links, no_index)
+        .pin(pinned_indexes);
+
+    let options = OptionsBuilder::new()
+        .resolution_mode(resolution_mode)
+        .prerelease_mode(prerelease_mode)
+        .dependency_mode(dependency_mode)
+        .exclude_newer(exclude_newer)
+        .exclude_newer_package(exclude_newer_package.clone())
+        .allow_yanked(allow_yanked)
+        .build();
+
+    // If none of the inputs have changed since a previous invocation, reuse its resolution (unless
+    // upgrades were requested), such that we only need to fetch the pinned distributions.
+    // Editables, direct URLs, and `--find-links` are excluded, since their contents can change
+    // in-place.
+    //
+    // The installed packages are excluded from the key: they're only used as preferences, and a
+    // cached resolution remains a valid solution for the requirements regardless.
+    let cacheable = editables.is_empty()
+        && unnamed.is_empty()
+        && index_locations.flat_index().next().is_none()
+        && requirements
+            .iter()
+            .chain(&constraints)
+            .chain(&overrides)
+            .all(|requirement| {
+                !matches!(
+                    requirement.version_or_url,
+                    Some(pep508_rs::VersionOrUrl::Url(_))
+                )
+            });
+    let resolution_cache = if cacheable {
+        let key = ResolutionKey::new()
+            .set("requirements", &requirements)
+            .set("constraints", &constraints)
+            .set("overrides", &overrides)
+            .set("build-constraints", &build_constraints)
+            .list("project", project.as_ref())
+            .value("markers", format!("{markers:?}"))
+            .value("reinstall", format!("{reinstall:?}"))
+            .value("platform", format!("{:?}", interpreter.platform()))
+            .value(
+                "target",
+                format!("{python_platform:?} {python_implementation:?} {abi:?}"),
+            )
+            .value("interpreter", interpreter.sys_executable().user_display())
+            .index_locations(&index_locations, index_strategy)
+            .options(&options)
+            .value("setup-py", format!("{setup_py:?}"))
+            .value("config-settings", format!("{config_settings:?}"))
+            .value(
+                "package-config-settings",
+                format!("{package_config_settings:?}"),
+            )
+            .value("no-build", format!("{no_build:?}"))
+            .value("no-binary", format!("{no_binary:?}"))
+            .value("no-build-isolation", no_build_isolation)
+            .set("no-build-isolation-package", no_build_isolation_package);
+        Some(ResolutionCache::new(
+            &cache,
+            "pip-install",
+            &key,
+            exclude_newer.is_some(),
+        )?)
+    } else {
+        None
+    };
+    let cached_pins = resolution_cache
+        .as_ref()
+        .filter(|_| upgrade.is_none())
+        .and_then(ResolutionCache::read::<Vec<CachedPin>>)
+        .and_then(|pins| CachedPin::into_requirements(pins).ok());
 
     // Add all authenticated sources to the store.
     for url in index_locations.urls() {
@@ -178,9 +433,14 @@ pub(crate) async fn pip_install(
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
         .native_tls(native_tls)
+        .cert(cert)
+        .proxy(proxy)
+        .retries(retries)
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
+        .index_strategy(index_strategy)
         .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_host)
         .markers(markers)
         .platform(interpreter.platform())
         .build();
@@ -195,8 +455,10 @@ pub(crate) async fn pip_install(
     // Determine whether to enable build isolation.
     let build_isolation = if no_build_isolation {
         BuildIsolation::Shared(&venv)
-    } else {
+    } else if no_build_isolation_package.is_empty() {
         BuildIsolation::Isolated
+    } else {
+        BuildIsolation::SharedPackage(&venv, no_build_isolation_package)
     };
 
     // Create a shared in-memory index.
@@ -219,7 +481,20 @@ pub(crate) async fn pip_install(
         no_build,
         no_binary,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_package_config_settings(package_config_settings.clone())
+    .with_concurrency(concurrency)
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer)
+            .exclude_newer_package(exclude_newer_package.clone())
+            .build(),
+    )
+    .with_build_constraints(build_constraints.clone())
+    .with_required_hashes(required_hashes.clone());
+
+    // Infer the names of any local projects that lack static metadata, which requires a build
+    // environment.
+    requirements.extend(NamedRequirements::name_with_build(unnamed, &resolve_dispatch).await?);
 
     // Build all editable distributions. The editables are shared between resolution and
     // installation, and should live for the duration of the command. If an editable is already
@@ -242,12 +517,27 @@ pub(crate) async fn pip_install(
         .await?
     };
 
-    let options = OptionsBuilder::new()
-        .resolution_mode(resolution_mode)
-        .prerelease_mode(prerelease_mode)
-        .dependency_mode(dependency_mode)
-        .exclude_newer(exclude_newer)
-        .build();
+    // Track the packages that were requested directly, for the installation report.
+    let requested = requirements
+        .iter()
+        .map(|requirement| requirement.name.clone())
+        .chain(editables.iter().map(|editable| editable.name().clone()))
+        .collect::<FxHashSet<_>>();
+
+    // If we're reusing a cached resolution, install the pinned packages directly, without
+    // resolving their dependencies. The cached hashes are passed along as preferences, as they
+    // would be for a lockfile.
+    let from_cache = cached_pins.is_some();
+    let (requirements, constraints, overrides, locked, options) =
+        if let Some((pins, locked)) = cached_pins {
+            let options = Options {
+                dependency_mode: DependencyMode::Direct,
+                ..options
+            };
+            (pins, vec![], vec![], locked, options)
+        } else {
+            (requirements, constraints, overrides, vec![], options)
+        };
 
     // Resolve the requirements.
     let resolution = match resolve(
@@ -255,6 +545,7 @@ pub(crate) async fn pip_install(
         constraints,
         overrides,
         project,
+        locked,
         &editables,
         &site_packages,
         reinstall,
@@ -271,16 +562,66 @@ pub(crate) async fn pip_install(
     )
     .await
     {
-        Ok(resolution) => Resolution::from(resolution),
+        Ok(graph) => {
+            let hashes = graph
+                .petgraph()
+                .node_weights()
+                .map(|dist| {
+                    let hashes = graph
+                        .hashes(dist.name())
+                        .iter()
+                        .filter_map(Hashes::to_string)
+                        .collect::<Vec<_>>();
+                    (dist.name().clone(), hashes)
+                })
+                .collect::<FxHashMap<_, _>>();
+            let resolution = Resolution::from(graph);
+
+            // Cache the resolution, for reuse by subsequent invocations.
+            if let Some(resolution_cache) = resolution_cache.filter(|_| !from_cache) {
+                let pins = resolution
+                    .requirements()
+                    .into_iter()
+                    .map(|requirement| CachedPin {
+                        hashes: hashes.get(&requirement.name).cloned().unwrap_or_default(),
+                        requirement: requirement.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                resolution_cache.write(
+                    resolution.requirements().into_iter().map(|pin| pin.name),
+                    &pins,
+                )?;
+            }
+
+            resolution
+        }
         Err(Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
-            let report = miette::Report::msg(format!("{err}"))
-                .context("No solution found when resolving dependencies:");
-            eprint!("{report:?}");
+            diagnostics::report_no_solution(
+                &err,
+                "No solution found when resolving dependencies:",
+                printer,
+            );
             return Ok(ExitStatus::Failure);
         }
         Err(err) => return Err(err.into()),
     };
 
+    // Verify the resolved distributions against any required hashes.
+    verify_hashes(resolution.distributions(), &required_hashes, require_hashes)?;
+
+    // Write the installation report, if requested.
+    if let Some(report) = report {
+        write_report(
+            report,
+            &resolution,
+            &requested,
+            &editables,
+            &index,
+            markers,
+            printer,
+        )?;
+    }
+
     // Re-initialize the in-flight map.
     let in_flight = InFlight::default();
 
@@ -303,7 +644,16 @@ pub(crate) async fn pip_install(
             no_build,
             no_binary,
         )
-        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+        .with_package_config_settings(package_config_settings.clone())
+        .with_concurrency(concurrency)
+        .with_options(
+            OptionsBuilder::new()
+                .exclude_newer(exclude_newer)
+                .exclude_newer_package(exclude_newer_package.clone())
+                .build(),
+        )
+        .with_build_constraints(build_constraints)
+        .with_required_hashes(required_hashes.clone())
     };
 
     // Sync the environment.
@@ -323,6 +673,7 @@ pub(crate) async fn pip_install(
         &cache,
         &venv,
         dry_run,
+        json,
         printer,
     )
     .await?;
@@ -332,6 +683,13 @@ pub(crate) async fn pip_install(
         validate(&resolution, &venv, printer)?;
     }
 
+    // Write the software bill of materials, if requested.
+    if let Some(sbom) = sbom {
+        if !dry_run {
+            write_environment_sbom(&venv, Some(&resolution), sbom_format, sbom, printer)?;
+        }
+    }
+
     Ok(ExitStatus::Success)
 }
 
@@ -340,6 +698,7 @@ async fn read_requirements(
     requirements: &[RequirementsSource],
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
+    build_constraints: &[RequirementsSource],
     extras: &ExtrasSpecification<'_>,
     connectivity: Connectivity,
 ) -> Result<RequirementsSpecification, Error> {
@@ -347,7 +706,7 @@ async fn read_requirements(
     if !matches!(extras, ExtrasSpecification::None)
         && !requirements
             .iter()
-            .any(|source| matches!(source, RequirementsSource::PyprojectToml(_)))
+            .any(RequirementsSource::is_pyproject_toml)
     {
         return Err(anyhow!("Requesting extras requires a pyproject.toml input file.").into());
     }
@@ -357,6 +716,7 @@ async fn read_requirements(
         requirements,
         constraints,
         overrides,
+        build_constraints,
         extras,
         connectivity,
     )
@@ -403,11 +763,18 @@ async fn build_editables(
     let editables: Vec<LocalEditable> = editables
         .iter()
         .map(|editable| {
-            let EditableRequirement { url, extras, path } = editable;
+            let EditableRequirement {
+                url,
+                extras,
+                path,
+                dependency,
+                ..
+            } = editable;
             Ok(LocalEditable {
                 url: url.clone(),
                 extras: extras.clone(),
                 path: path.clone(),
+                dependency: *dependency,
             })
         })
         .collect::<Result<_>>()?;
@@ -456,6 +823,7 @@ async fn resolve(
     constraints: Vec<Requirement>,
     overrides: Vec<Requirement>,
     project: Option<PackageName>,
+    locked: Vec<Preference>,
     editables: &[BuiltEditable],
     site_packages: &SitePackages<'_>,
     reinstall: &Reinstall,
@@ -473,7 +841,7 @@ async fn resolve(
     let start = std::time::Instant::now();
 
     let preferences = if upgrade.is_all() || reinstall.is_all() {
-        vec![]
+        locked
     } else {
         // Combine upgrade and reinstall lists
         let mut exclusions: HashSet<&PackageName> = if let Reinstall::Packages(packages) = reinstall
@@ -483,7 +851,7 @@ async fn resolve(
             HashSet::default()
         };
         if let Upgrade::Packages(packages) = upgrade {
-            exclusions.extend(packages);
+            exclusions.extend(packages.keys());
         };
 
         // Prefer current site packages, unless in the upgrade or reinstall lists
@@ -491,9 +859,16 @@ async fn resolve(
             .requirements()
             .map(Preference::from_requirement)
             .filter(|preference| !exclusions.contains(preference.name()))
+            .chain(locked)
             .collect()
     };
 
+    // Apply any version bounds attached to the packages to upgrade.
+    let constraints = constraints
+        .into_iter()
+        .chain(upgrade.constraints().cloned())
+        .collect::<Vec<_>>();
+
     // Map the editables to their metadata.
     let editables = editables
         .iter()
@@ -553,7 +928,7 @@ async fn install(
     site_packages: SitePackages<'_>,
     reinstall: &Reinstall,
     no_binary: &NoBinary,
-    link_mode: LinkMode,
+    link_mode: Option<LinkMode>,
     compile: bool,
     index_urls: &IndexLocations,
     tags: &Tags,
@@ -563,15 +938,18 @@ async fn install(
     cache: &Cache,
     venv: &PythonEnvironment,
     dry_run: bool,
+    json: bool,
     printer: Printer,
 ) -> Result<(), Error> {
     let start = std::time::Instant::now();
 
     let requirements = resolution.requirements();
 
-    // Map the built editables to their resolved form.
+    // Map the built editables to their resolved form, skipping any that are the local source for
+    // a dependency that isn't required (e.g., a workspace member that's gated by a marker).
     let editables = built_editables
         .into_iter()
+        .filter(|built| resolution.get(built.name()).is_some())
         .map(ResolvedEditable::Built)
         .collect::<Vec<_>>();
 
@@ -579,6 +957,7 @@ async fn install(
     // downloaded (`remote`), and those that should be removed (`extraneous`).
     let plan = Planner::with_requirements(&requirements)
         .with_editable_requirements(&editables)
+        .with_required_hashes(build_dispatch.required_hashes())
         .build(
             site_packages,
             reinstall,
@@ -591,7 +970,7 @@ async fn install(
         .context("Failed to determine installation plan")?;
 
     if dry_run {
-        return report_dry_run(resolution, plan, start, printer);
+        return report_dry_run(resolution, plan, start, json, printer);
     }
 
     let Plan {
@@ -628,7 +1007,11 @@ async fn install(
         })
         .collect::<Vec<_>>();
 
-    // Download, build, and unzip any missing distributions.
+    // Download, build, and unzip any missing distributions. If no existing installations need to be
+    // removed first, install each distribution as soon as it's available, such that installation
+    // overlaps with the remaining downloads.
+    let install_start = Instant::now();
+    let pipeline = !remote.is_empty() && reinstalls.is_empty();
     let wheels = if remote.is_empty() {
         vec![]
     } else {
@@ -637,10 +1020,27 @@ async fn install(
         let downloader = Downloader::new(cache, tags, client, build_dispatch)
             .with_reporter(DownloadReporter::from(printer).with_length(remote.len() as u64));
 
-        let wheels = downloader
-            .download(remote.clone(), in_flight)
-            .await
-            .context("Failed to download distributions")?;
+        let (sender, receiver) = futures::channel::mpsc::unbounded();
+        let download = async {
+            downloader
+                .download_with(remote.clone(), in_flight, move |wheel| {
+                    if pipeline {
+                        // If installation fails, the receiver is dropped, and the error is
+                        // reported below.
+                        sender.unbounded_send(wheel.clone()).ok();
+                    }
+                })
+                .await
+                .context("Failed to download distributions")
+        };
+        let install = uv_installer::Installer::new(venv)
+            .with_link_mode(link_mode)
+            .with_reporter(InstallReporter::from(printer).hidden())
+            .install_stream(
+                futures::stream::iter(if pipeline { local.clone() } else { Vec::new() })
+                    .chain(receiver),
+            );
+        let (wheels, ()) = tokio::try_join!(download, install)?;
 
         let s = if wheels.len() == 1 { "" } else { "s" };
         writeln!(
@@ -684,14 +1084,19 @@ async fn install(
         }
     }
 
-    // Install the resolved distributions.
+    // Install the resolved distributions, unless they were installed as they were downloaded.
     let wheels = wheels.into_iter().chain(local).collect::<Vec<_>>();
     if !wheels.is_empty() {
-        let start = std::time::Instant::now();
-        uv_installer::Installer::new(venv)
-            .with_link_mode(link_mode)
-            .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
-            .install(&wheels)?;
+        let start = if pipeline {
+            install_start
+        } else {
+            let start = std::time::Instant::now();
+            uv_installer::Installer::new(venv)
+                .with_link_mode(link_mode)
+                .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
+                .install(&wheels)?;
+            start
+        };
 
         let s = if wheels.len() == 1 { "" } else { "s" };
         writeln!(
@@ -704,10 +1109,12 @@ async fn install(
             )
             .dimmed()
         )?;
+
+        report_verified_hashes(&wheels, build_dispatch.required_hashes(), printer)?;
     }
 
     if compile {
-        compile_bytecode(venv, cache, printer).await?;
+        compile_bytecode(venv, &wheels, cache, printer).await?;
     }
 
     for event in reinstalls
@@ -755,6 +1162,7 @@ async fn install(
         resolution: &Resolution,
         plan: Plan,
         start: Instant,
+        json: bool,
         printer: Printer,
     ) -> Result<(), Error> {
         let Plan {
@@ -778,6 +1186,9 @@ async fn install(
                 .dimmed()
             )?;
             writeln!(printer.stderr(), "Would make no changes")?;
+            if json {
+                writeln!(printer.stdout(), "[]")?;
+            }
             return Ok(());
         }
 
@@ -792,89 +1203,7 @@ async fn install(
             })
             .collect::<Vec<_>>();
 
-        // Download, build, and unzip any missing distributions.
-        let wheels = if remote.is_empty() {
-            vec![]
-        } else {
-            let s = if remote.len() == 1 { "" } else { "s" };
-            writeln!(
-                printer.stderr(),
-                "{}",
-                format!(
-                    "Would download {}",
-                    format!("{} package{}", remote.len(), s).bold(),
-                )
-                .dimmed()
-            )?;
-            remote
-        };
-
-        // Remove any existing installations.
-        if !reinstalls.is_empty() {
-            let s = if reinstalls.len() == 1 { "" } else { "s" };
-            writeln!(
-                printer.stderr(),
-                "{}",
-                format!(
-                    "Would uninstall {}",
-                    format!("{} package{}", reinstalls.len(), s).bold(),
-                )
-                .dimmed()
-            )?;
-        }
-
-        // Install the resolved distributions.
-        let installs = wheels.len() + local.len();
-
-        if installs > 0 {
-            let s = if installs == 1 { "" } else { "s" };
-            writeln!(
-                printer.stderr(),
-                "{}",
-                format!("Would install {}", format!("{installs} package{s}").bold()).dimmed()
-            )?;
-        }
-
-        for event in reinstalls
-            .into_iter()
-            .map(|distribution| DryRunEvent {
-                name: distribution.name().clone(),
-                version: distribution.installed_version().to_string(),
-                kind: ChangeEventKind::Removed,
-            })
-            .chain(wheels.into_iter().map(|distribution| DryRunEvent {
-                name: distribution.name().clone(),
-                version: distribution.version_or_url().to_string(),
-                kind: ChangeEventKind::Added,
-            }))
-            .chain(local.into_iter().map(|distribution| DryRunEvent {
-                name: distribution.name().clone(),
-                version: distribution.installed_version().to_string(),
-                kind: ChangeEventKind::Added,
-            }))
-            .sorted_unstable_by(|a, b| a.name.cmp(&b.name).then_with(|| a.kind.cmp(&b.kind)))
-        {
-            match event.kind {
-                ChangeEventKind::Added => {
-                    writeln!(
-                        printer.stderr(),
-                        " {} {}{}",
-                        "+".green(),
-                        event.name.as_ref().bold(),
-                        event.version.dimmed()
-                    )?;
-                }
-                ChangeEventKind::Removed => {
-                    writeln!(
-                        printer.stderr(),
-                        " {} {}{}",
-                        "-".red(),
-                        event.name.as_ref().bold(),
-                        event.version.dimmed()
-                    )?;
-                }
-            }
-        }
+        report_planned_changes(&remote, &local, &reinstalls, &[], json, printer)?;
 
         Ok(())
     }
@@ -909,6 +1238,203 @@ async fn install(
     Ok(())
 }
 
+/// Write an installation report for the given resolution to a file (or to `stdout`, for `-`).
+fn write_report(
+    path: &Path,
+    resolution: &Resolution,
+    requested: &FxHashSet<PackageName>,
+    editables: &[BuiltEditable],
+    index: &InMemoryIndex,
+    markers: &MarkerEnvironment,
+    printer: Printer,
+) -> Result<()> {
+    let install = resolution
+        .distributions()
+        .sorted_unstable_by(|a, b| a.name().cmp(b.name()))
+        .map(|dist| {
+            let metadata = editables
+                .iter()
+                .find(|editable| editable.name() == dist.name())
+                .map(|editable| Arc::new(editable.metadata.clone()))
+                .or_else(|| index.get_metadata(&dist.package_id()));
+            InstallationReportItem::from_dist(dist, metadata.as_deref(), requested)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let report = InstallationReport {
+        version: "1",
+        install,
+        environment: markers,
+    };
+
+    let report = serde_json::to_string_pretty(&report)?;
+    if path == Path::new("-") {
+        writeln!(printer.stdout(), "{report}")?;
+    } else {
+        fs_err::write(path, report)?;
+    }
+
+    Ok(())
+}
+
+/// An installation report, in the format of pip's `--report` output.
+///
+/// See: <https://pip.pypa.io/en/stable/reference/installation-report/>
+#[derive(Debug, Serialize)]
+struct InstallationReport<'a> {
+    version: &'static str,
+    install: Vec<InstallationReportItem>,
+    environment: &'a MarkerEnvironment,
+}
+
+#[derive(Debug, Serialize)]
+struct InstallationReportItem {
+    /// The source of the distribution, in the format of `direct_url.json`.
+    download_info: DirectUrl,
+    /// Whether the distribution was requested via a direct URL.
+    is_direct: bool,
+    /// Whether the distribution is yanked from the registry.
+    is_yanked: bool,
+    /// Whether the distribution was requested directly, rather than as a dependency.
+    requested: bool,
+    /// The core metadata of the distribution, as JSON.
+    metadata: InstallationReportMetadata,
+}
+
+impl InstallationReportItem {
+    fn from_dist(
+        dist: &Dist,
+        metadata: Option<&Metadata23>,
+        requested: &FxHashSet<PackageName>,
+    ) -> Result<Self> {
+        let (download_info, is_direct) = if let Some(file) = dist.file() {
+            let url = match &file.url {
+                FileLocation::RelativeUrl(base, url) => base_url_join_relative(base, url)?,
+                FileLocation::AbsoluteUrl(url) => Url::parse(url)?,
+                FileLocation::Path(path) => Url::from_file_path(path)
+                    .map_err(|()| anyhow!("Invalid file path: {}", path.user_display()))?,
+            };
+            let hashes = [
+                ("md5", &file.hashes.md5),
+                ("sha256", &file.hashes.sha256),
+                ("sha384", &file.hashes.sha384),
+                ("sha512", &file.hashes.sha512),
+            ]
+            .into_iter()
+            .filter_map(|(algorithm, digest)| {
+                Some((algorithm.to_string(), digest.as_deref()?.to_string()))
+            })
+            .collect::<HashMap<_, _>>();
+            let download_info = DirectUrl::ArchiveUrl {
+                url: url.to_string(),
+                archive_info: ArchiveInfo {
+                    hash: file
+                        .hashes
+                        .to_string()
+                        .map(|hash| hash.replacen(':', "=", 1)),
+                    hashes: (!hashes.is_empty()).then_some(hashes),
+                },
+                subdirectory: None,
+            };
+            (download_info, false)
+        } else {
+            let VersionOrUrl::Url(url) = dist.version_or_url() else {
+                unreachable!("Registry distributions should have a file");
+            };
+            let download_info = match distribution_types::DirectUrl::try_from(url.raw())? {
+                distribution_types::DirectUrl::LocalFile(local) => {
+                    let editable = matches!(
+                        dist,
+                        Dist::Source(SourceDist::Path(PathSourceDist { editable: true, .. }))
+                    );
+                    DirectUrl::try_from(&distribution_types::DirectUrl::LocalFile(LocalFileUrl {
+                        editable,
+                        ..local
+                    }))?
+                }
+                direct_url => DirectUrl::try_from(&direct_url)?,
+            };
+            (download_info, true)
+        };
+
+        let metadata = match metadata {
+            Some(metadata) => InstallationReportMetadata {
+                metadata_version: metadata.metadata_version.clone(),
+                name: metadata.name.to_string(),
+                version: metadata.version.to_string(),
+                requires_dist: metadata
+                    .requires_dist
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+                requires_python: metadata.requires_python.as_ref().map(ToString::to_string),
+                provides_extra: metadata
+                    .provides_extras
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            },
+            None => InstallationReportMetadata {
+                metadata_version: "2.3".to_string(),
+                name: dist.name().to_string(),
+                version: dist.version().map(ToString::to_string).unwrap_or_default(),
+                requires_dist: Vec::new(),
+                requires_python: None,
+                provides_extra: Vec::new(),
+            },
+        };
+
+        Ok(Self {
+            download_info,
+            is_direct,
+            is_yanked: dist
+                .file()
+                .and_then(|file| file.yanked.as_ref())
+                .is_some_and(Yanked::is_yanked),
+            requested: requested.contains(dist.name()),
+            metadata,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct InstallationReportMetadata {
+    metadata_version: String,
+    name: String,
+    version: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    requires_dist: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requires_python: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    provides_extra: Vec<String>,
+}
+
+/// Write a software bill of materials for the distributions installed in the environment.
+///
+/// If the environment was synced to a resolution, the resolution provides the hashes and URLs of
+/// the artifacts from which the distributions were installed.
+fn write_environment_sbom(
+    venv: &PythonEnvironment,
+    resolution: Option<&Resolution>,
+    format: SbomFormat,
+    path: &Path,
+    printer: Printer,
+) -> Result<()> {
+    let site_packages = SitePackages::from_executable(venv)?;
+    let components = site_packages
+        .iter()
+        .sorted_unstable_by(|a, b| a.name().cmp(b.name()))
+        .map(|installed| {
+            SbomComponent::from_installed(
+                installed,
+                resolution.and_then(|resolution| resolution.get(installed.name())),
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+    write_sbom(&components, format, path, printer)
+}
+
 /// Validate the installed packages in the virtual environment.
 fn validate(
     resolution: &Resolution,
@@ -958,3 +1484,28 @@ enum Error {
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 }
+
+/// A pinned requirement in a cached resolution, along with the hashes of its distributions.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedPin {
+    requirement: String,
+    hashes: Vec<String>,
+}
+
+impl CachedPin {
+    /// Convert the cached pins to requirements, along with preferences that retain their hashes.
+    fn into_requirements(pins: Vec<Self>) -> Result<(Vec<Requirement>, Vec<Preference>)> {
+        let mut requirements = Vec::with_capacity(pins.len());
+        let mut preferences = Vec::with_capacity(pins.len());
+        for pin in pins {
+            let requirement = Requirement::from_str(&pin.requirement)?;
+            preferences.push(Preference::from_entry(RequirementEntry {
+                requirement: RequirementsTxtRequirement::Pep508(requirement.clone()),
+                hashes: pin.hashes,
+                editable: false,
+            })?);
+            requirements.push(requirement);
+        }
+        Ok((requirements, preferences))
+    }
+}
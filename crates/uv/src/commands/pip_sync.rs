@@ -1,60 +1,108 @@
 use std::fmt::Write;
+use std::path::Path;
 
 use anyhow::{anyhow, Context, Result};
+use futures::StreamExt;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
 use tracing::debug;
+use url::Url;
 
-use distribution_types::{IndexLocations, InstalledMetadata, LocalDist, LocalEditable, Name};
+use distribution_types::{
+    IndexLocations, InstalledMetadata, LocalDist, LocalEditable, Name, Resolution,
+};
 use install_wheel_rs::linker::LinkMode;
+use pep508_rs::Requirement;
 use platform_tags::Tags;
 use pypi_types::Yanked;
 use requirements_txt::EditableRequirement;
 use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
 use uv_cache::{ArchiveTarget, ArchiveTimestamp, Cache};
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
+use uv_client::{
+    BaseClientBuilder, Connectivity, FlatIndex, FlatIndexClient, IndexStrategy, RegistryClient,
+    RegistryClientBuilder, TrustedHost,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::{
     is_dynamic, Downloader, NoBinary, Plan, Planner, Reinstall, ResolvedEditable, SitePackages,
 };
 use uv_interpreter::{Interpreter, PythonEnvironment};
-use uv_resolver::InMemoryIndex;
-use uv_traits::{BuildIsolation, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_normalize::PackageName;
+use uv_resolver::{InMemoryIndex, Manifest, Options, Preference, Resolver};
+use uv_traits::{
+    BuildContext, BuildIsolation, Concurrency, ConfigSettings, InFlight, NoBuild,
+    PackageConfigSettings, SetupPyStrategy,
+};
 use uv_warnings::warn_user;
 
-use crate::commands::reporters::{DownloadReporter, FinderReporter, InstallReporter};
-use crate::commands::{compile_bytecode, elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::reporters::{
+    DownloadReporter, FinderReporter, InstallReporter, ResolverReporter,
+};
+use crate::commands::{
+    checkout_editables, compile_bytecode, elapsed, report_environment, report_planned_changes,
+    report_verified_hashes, verify_hashes, ChangeEvent, ChangeEventKind, ExitStatus,
+};
 use crate::printer::Printer;
-use crate::requirements::{NamedRequirements, RequirementsSource, RequirementsSpecification};
+use crate::requirements::{
+    ExtrasSpecification, NamedRequirements, RequirementsSource, RequirementsSpecification,
+};
 
 /// Install a set of locked requirements into the current Python environment.
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 pub(crate) async fn pip_sync(
     sources: &[RequirementsSource],
+    extras: &ExtrasSpecification<'_>,
     reinstall: &Reinstall,
-    link_mode: LinkMode,
+    keep: &[PackageName],
+    exact: bool,
+    link_mode: Option<LinkMode>,
     compile: bool,
     index_locations: IndexLocations,
+    index_strategy: IndexStrategy,
     keyring_provider: KeyringProvider,
+    trusted_host: Vec<TrustedHost>,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
     config_settings: &ConfigSettings,
+    package_config_settings: &PackageConfigSettings,
     no_build_isolation: bool,
+    no_build_isolation_package: &[PackageName],
     no_build: &NoBuild,
     no_binary: &NoBinary,
+    require_hashes: bool,
     strict: bool,
     python: Option<String>,
     system: bool,
     break_system_packages: bool,
     native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
     cache: Cache,
+    dry_run: bool,
+    json: bool,
     printer: Printer,
 ) -> Result<ExitStatus> {
     let start = std::time::Instant::now();
 
+    // If the user requests `extras` but does not provide a pyproject toml source
+    if !matches!(extras, ExtrasSpecification::None)
+        && !sources.iter().any(RequirementsSource::is_pyproject_toml)
+    {
+        return Err(anyhow!(
+            "Requesting extras requires a pyproject.toml input file."
+        ));
+    }
+
     // Read all requirements from the provided sources.
-    let spec = RequirementsSpecification::from_simple_sources(sources, connectivity).await?;
+    let spec =
+        RequirementsSpecification::from_sources(sources, &[], &[], &[], extras, connectivity)
+            .await?;
+
+    // Check out any editable Git requirements.
+    checkout_editables(&spec.editables, &cache, connectivity).await?;
 
     // Validate that the requirements are non-empty.
     let num_requirements = spec.requirements.len() + spec.editables.len();
@@ -65,7 +113,7 @@ pub(crate) async fn pip_sync(
 
     // Detect the current Python interpreter.
     let venv = if let Some(python) = python.as_ref() {
-        PythonEnvironment::from_requested_python(python, &cache)?
+        PythonEnvironment::from_requested_python_or_virtualenv(python, &cache)?
     } else if system {
         PythonEnvironment::from_default_python(&cache)?
     } else {
@@ -77,6 +125,8 @@ pub(crate) async fn pip_sync(
         venv.python_executable().user_display().cyan()
     );
 
+    report_environment(&venv, printer)?;
+
     // If the environment is externally managed, abort.
     if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
         if break_system_packages {
@@ -97,18 +147,39 @@ pub(crate) async fn pip_sync(
         }
     }
 
-    // Convert from unnamed to named requirements.
+    // Convert from unnamed to named requirements, downloading any archives that can't be named
+    // from their URLs alone.
+    let client_builder = BaseClientBuilder::new()
+        .native_tls(native_tls)
+        .cert(cert)
+        .proxy(proxy)
+        .retries(retries)
+        .connectivity(connectivity)
+        .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_host.clone());
+    let named = NamedRequirements::from_spec(spec, &client_builder, &cache).await?;
+
+    // Determine the hashes that the distributions must match, in hash-checking mode.
+    let required_hashes = named.required_hashes(require_hashes)?;
+    let require_hashes = named.hash_checking_mode(require_hashes);
+
     let NamedRequirements {
         project: _project,
-        requirements,
+        mut requirements,
+        hashes: _hashes,
         constraints: _constraints,
+        constraint_sources: _constraint_sources,
+        constraint_hashes: _constraint_hashes,
         overrides: _overrides,
+        build_constraints: _build_constraints,
         editables,
         index_url,
         extra_index_urls,
         no_index,
         find_links,
-    } = NamedRequirements::from_spec(spec)?;
+        pinned_indexes,
+        unnamed,
+    } = named;
 
     let _lock = venv.lock()?;
 
@@ -116,8 +187,9 @@ pub(crate) async fn pip_sync(
     let tags = venv.interpreter().tags()?;
 
     // Incorporate any index locations from the provided sources.
-    let index_locations =
-        index_locations.combine(index_url, extra_index_urls, find_links, no_index);
+    let index_locations = index_locations
+        .combine(index_url, extra_index_urls, find_links, no_index)
+        .pin(pinned_indexes);
 
     // Add all authenticated sources to the store.
     for url in index_locations.urls() {
@@ -127,9 +199,14 @@ pub(crate) async fn pip_sync(
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
         .native_tls(native_tls)
+        .cert(cert)
+        .proxy(proxy)
+        .retries(retries)
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
+        .index_strategy(index_strategy)
         .keyring_provider(keyring_provider)
+        .trusted_hosts(trusted_host)
         .markers(venv.interpreter().markers())
         .platform(venv.interpreter().platform())
         .build();
@@ -150,8 +227,10 @@ pub(crate) async fn pip_sync(
     // Determine whether to enable build isolation.
     let build_isolation = if no_build_isolation {
         BuildIsolation::Shared(&venv)
-    } else {
+    } else if no_build_isolation_package.is_empty() {
         BuildIsolation::Isolated
+    } else {
+        BuildIsolation::SharedPackage(&venv, no_build_isolation_package)
     };
 
     // Prep the build context.
@@ -168,13 +247,21 @@ pub(crate) async fn pip_sync(
         build_isolation,
         no_build,
         no_binary,
-    );
+    )
+    .with_package_config_settings(package_config_settings.clone())
+    .with_concurrency(concurrency)
+    .with_required_hashes(required_hashes.clone());
+
+    // Infer the names of any local projects that lack static metadata, which requires a build
+    // environment.
+    requirements.extend(NamedRequirements::name_with_build(unnamed, &build_dispatch).await?);
 
     // Determine the set of installed packages.
     let site_packages = SitePackages::from_executable(&venv)?;
 
     // Resolve any editables.
-    let resolved_editables = resolve_editables(
+    let editable_requirements = editables.clone();
+    let mut resolved_editables = resolve_editables(
         editables,
         &site_packages,
         reinstall,
@@ -187,6 +274,42 @@ pub(crate) async fn pip_sync(
     )
     .await?;
 
+    // A `pyproject.toml` file declares the project's direct dependencies, rather than a complete
+    // set of pinned packages, so resolve them (along with the dependencies of any editables) before
+    // syncing.
+    let (requirements, resolution) = if sources.iter().any(RequirementsSource::is_pyproject_toml) {
+        let resolution = resolve(
+            requirements,
+            &editable_requirements,
+            &resolved_editables.editables,
+            &site_packages,
+            reinstall,
+            venv.interpreter(),
+            tags,
+            &client,
+            &flat_index,
+            &index,
+            &build_dispatch,
+            printer,
+        )
+        .await?;
+
+        // Skip any editables that are the local source for a dependency that isn't required
+        // (e.g., a workspace member that's gated by a marker).
+        resolved_editables
+            .editables
+            .retain(|editable| resolution.get(editable.name()).is_some());
+
+        (resolution.requirements(), Some(resolution))
+    } else {
+        (requirements, None)
+    };
+    let num_requirements = if resolution.is_some() {
+        requirements.len() + resolved_editables.editables.len()
+    } else {
+        num_requirements
+    };
+
     // Partition into those that should be linked from the cache (`local`), those that need to be
     // downloaded (`remote`), and those that should be removed (`extraneous`).
     let Plan {
@@ -196,6 +319,9 @@ pub(crate) async fn pip_sync(
         extraneous,
     } = Planner::with_requirements(&requirements)
         .with_editable_requirements(&resolved_editables.editables)
+        .with_keep(keep)
+        .with_exact(exact)
+        .with_required_hashes(build_dispatch.required_hashes())
         .build(
             site_packages,
             reinstall,
@@ -220,6 +346,12 @@ pub(crate) async fn pip_sync(
             )
             .dimmed()
         )?;
+        if dry_run {
+            writeln!(printer.stderr(), "Would make no changes")?;
+            if json {
+                writeln!(printer.stdout(), "[]")?;
+            }
+        }
 
         return Ok(ExitStatus::Success);
     }
@@ -227,6 +359,21 @@ pub(crate) async fn pip_sync(
     // Resolve any registry-based requirements.
     let remote = if remote.is_empty() {
         Vec::new()
+    } else if let Some(resolution) = resolution.as_ref() {
+        // The requirements were already resolved to distributions.
+        let remote = remote
+            .iter()
+            .map(|requirement| {
+                resolution.get(&requirement.name).cloned().ok_or_else(|| {
+                    anyhow!("Missing resolved distribution for: {}", requirement.name)
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Verify the distributions against any required hashes.
+        verify_hashes(&remote, &required_hashes, require_hashes)?;
+
+        remote
     } else {
         let start = std::time::Instant::now();
 
@@ -253,10 +400,23 @@ pub(crate) async fn pip_sync(
             .dimmed()
         )?;
 
+        // Verify the distributions against any required hashes.
+        verify_hashes(resolution.distributions(), &required_hashes, require_hashes)?;
+
         resolution.into_distributions().collect::<Vec<_>>()
     };
 
-    // Download, build, and unzip any missing distributions.
+    // If this is a dry run, report the planned changes without modifying the environment.
+    if dry_run {
+        report_planned_changes(&remote, &local, &reinstalls, &extraneous, json, printer)?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // Download, build, and unzip any missing distributions. If no existing installations need to be
+    // removed first, install each distribution as soon as it's available, such that installation
+    // overlaps with the remaining downloads.
+    let install_start = std::time::Instant::now();
+    let pipeline = !remote.is_empty() && extraneous.is_empty() && reinstalls.is_empty();
     let wheels = if remote.is_empty() {
         Vec::new()
     } else {
@@ -265,10 +425,27 @@ pub(crate) async fn pip_sync(
         let downloader = Downloader::new(&cache, tags, &client, &build_dispatch)
             .with_reporter(DownloadReporter::from(printer).with_length(remote.len() as u64));
 
-        let wheels = downloader
-            .download(remote.clone(), &in_flight)
-            .await
-            .context("Failed to download distributions")?;
+        let (sender, receiver) = futures::channel::mpsc::unbounded();
+        let download = async {
+            downloader
+                .download_with(remote.clone(), &in_flight, move |wheel| {
+                    if pipeline {
+                        // If installation fails, the receiver is dropped, and the error is
+                        // reported below.
+                        sender.unbounded_send(wheel.clone()).ok();
+                    }
+                })
+                .await
+                .context("Failed to download distributions")
+        };
+        let install = uv_installer::Installer::new(&venv)
+            .with_link_mode(link_mode)
+            .with_reporter(InstallReporter::from(printer).hidden())
+            .install_stream(
+                futures::stream::iter(if pipeline { local.clone() } else { Vec::new() })
+                    .chain(receiver),
+            );
+        let (wheels, ()) = tokio::try_join!(download, install)?;
 
         let s = if wheels.len() == 1 { "" } else { "s" };
         writeln!(
@@ -330,14 +507,19 @@ pub(crate) async fn pip_sync(
         )?;
     }
 
-    // Install the resolved distributions.
+    // Install the resolved distributions, unless they were installed as they were downloaded.
     let wheels = wheels.into_iter().chain(local).collect::<Vec<_>>();
     if !wheels.is_empty() {
-        let start = std::time::Instant::now();
-        uv_installer::Installer::new(&venv)
-            .with_link_mode(link_mode)
-            .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
-            .install(&wheels)?;
+        let start = if pipeline {
+            install_start
+        } else {
+            let start = std::time::Instant::now();
+            uv_installer::Installer::new(&venv)
+                .with_link_mode(link_mode)
+                .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
+                .install(&wheels)?;
+            start
+        };
 
         let s = if wheels.len() == 1 { "" } else { "s" };
         writeln!(
@@ -350,10 +532,12 @@ pub(crate) async fn pip_sync(
             )
             .dimmed()
         )?;
+
+        report_verified_hashes(&wheels, build_dispatch.required_hashes(), printer)?;
     }
 
     if compile {
-        compile_bytecode(&venv, &cache, printer).await?;
+        compile_bytecode(&venv, &wheels, &cache, printer).await?;
     }
 
     // Report on any changes in the environment.
@@ -452,6 +636,92 @@ struct ResolvedEditables {
     temp_dir: Option<tempfile::TempDir>,
 }
 
+/// Resolve the requirements declared in a `pyproject.toml` file, along with the dependencies of
+/// any editables, to a complete set of pinned packages, preferring the installed versions.
+#[allow(clippy::too_many_arguments)]
+async fn resolve(
+    requirements: Vec<Requirement>,
+    editable_requirements: &[EditableRequirement],
+    editables: &[ResolvedEditable],
+    site_packages: &SitePackages<'_>,
+    reinstall: &Reinstall,
+    interpreter: &Interpreter,
+    tags: &Tags,
+    client: &RegistryClient,
+    flat_index: &FlatIndex,
+    index: &InMemoryIndex,
+    build_dispatch: &BuildDispatch<'_>,
+    printer: Printer,
+) -> Result<Resolution> {
+    let start = std::time::Instant::now();
+
+    // Map the editables to their metadata, reading it from the environment for those that are
+    // already installed.
+    let editables = editables
+        .iter()
+        .map(|editable| match editable {
+            ResolvedEditable::Built(built) => Ok((built.editable.clone(), built.metadata.clone())),
+            ResolvedEditable::Installed(installed) => {
+                let requirement = editable_requirements
+                    .iter()
+                    .find(|requirement| installed.as_editable() == Some(requirement.raw()))
+                    .ok_or_else(|| anyhow!("Missing editable requirement for: {installed}"))?;
+                let editable = LocalEditable {
+                    url: requirement.url.clone(),
+                    path: requirement.path.clone(),
+                    extras: requirement.extras.clone(),
+                    dependency: requirement.dependency,
+                };
+                Ok((editable, installed.metadata()?))
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Prefer the installed versions, unless they're to be reinstalled.
+    let preferences = if reinstall.is_all() {
+        Vec::new()
+    } else {
+        site_packages
+            .requirements()
+            .map(Preference::from_requirement)
+            .filter(|preference| match reinstall {
+                Reinstall::Packages(packages) => !packages.contains(preference.name()),
+                Reinstall::None | Reinstall::All => true,
+            })
+            .collect()
+    };
+
+    let manifest = Manifest::new(requirements, vec![], vec![], preferences, None, editables);
+    let resolution = Resolver::new(
+        manifest,
+        Options::default(),
+        interpreter.markers(),
+        interpreter,
+        tags,
+        client,
+        flat_index,
+        index,
+        build_dispatch,
+    )?
+    .with_reporter(ResolverReporter::from(printer))
+    .resolve()
+    .await?;
+
+    let s = if resolution.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Resolved {} in {}",
+            format!("{} package{}", resolution.len(), s).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    Ok(Resolution::from(resolution))
+}
+
 /// Resolve the set of editables that need to be installed.
 #[allow(clippy::too_many_arguments)]
 async fn resolve_editables(
@@ -532,11 +802,18 @@ async fn resolve_editables(
         let local_editables: Vec<LocalEditable> = uninstalled
             .iter()
             .map(|editable| {
-                let EditableRequirement { url, path, extras } = editable;
+                let EditableRequirement {
+                    url,
+                    path,
+                    extras,
+                    dependency,
+                    ..
+                } = editable;
                 Ok(LocalEditable {
                     url: url.clone(),
                     path: path.clone(),
                     extras: extras.clone(),
+                    dependency: *dependency,
                 })
             })
             .collect::<Result<_>>()?;
@@ -12,7 +12,7 @@ use uv_client::Connectivity;
 use uv_fs::Simplified;
 use uv_interpreter::PythonEnvironment;
 
-use crate::commands::{elapsed, ExitStatus};
+use crate::commands::{elapsed, report_environment, ExitStatus};
 use crate::printer::Printer;
 use crate::requirements::{RequirementsSource, RequirementsSpecification};
 
@@ -22,6 +22,7 @@ pub(crate) async fn pip_uninstall(
     python: Option<String>,
     system: bool,
     break_system_packages: bool,
+    user: bool,
     cache: Cache,
     connectivity: Connectivity,
     printer: Printer,
@@ -33,18 +34,27 @@ pub(crate) async fn pip_uninstall(
 
     // Detect the current Python interpreter.
     let venv = if let Some(python) = python.as_ref() {
-        PythonEnvironment::from_requested_python(python, &cache)?
-    } else if system {
+        if user {
+            PythonEnvironment::from_requested_python(python, &cache)?
+        } else {
+            PythonEnvironment::from_requested_python_or_virtualenv(python, &cache)?
+        }
+    } else if system || user {
         PythonEnvironment::from_default_python(&cache)?
     } else {
         PythonEnvironment::from_virtualenv(&cache)?
     };
+
+    // If requested, uninstall from the user site-packages instead.
+    let venv = if user { venv.with_user_site()? } else { venv };
     debug!(
         "Using Python {} environment at {}",
         venv.interpreter().python_version(),
         venv.python_executable().user_display().cyan(),
     );
 
+    report_environment(&venv, printer)?;
+
     // If the environment is externally managed, abort.
     if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
         if break_system_packages {
@@ -108,8 +118,13 @@ pub(crate) async fn pip_uninstall(
 
         // Identify all packages that are installed.
         for package in &names {
-            let installed = site_packages.get_packages(package);
-            if installed.is_empty() {
+            let (installed, read_only): (Vec<_>, Vec<_>) = site_packages
+                .get_packages(package)
+                .into_iter()
+                .partition(|dist| !venv.is_base_site_packages(dist.path()));
+            if !installed.is_empty() {
+                distributions.extend(installed);
+            } else if read_only.is_empty() {
                 writeln!(
                     printer.stderr(),
                     "{}{} Skipping {} as it is not installed.",
@@ -118,14 +133,25 @@ pub(crate) async fn pip_uninstall(
                     package.as_ref().bold()
                 )?;
             } else {
-                distributions.extend(installed);
+                writeln!(
+                    printer.stderr(),
+                    "{}{} Skipping {} as it is not installed in the user site.",
+                    "warning".yellow().bold(),
+                    ":".bold(),
+                    package.as_ref().bold()
+                )?;
             }
         }
 
         // Identify all unnamed distributions that are installed.
         for url in &urls {
-            let installed = site_packages.get_urls(url);
-            if installed.is_empty() {
+            let (installed, read_only): (Vec<_>, Vec<_>) = site_packages
+                .get_urls(url)
+                .into_iter()
+                .partition(|dist| !venv.is_base_site_packages(dist.path()));
+            if !installed.is_empty() {
+                distributions.extend(installed);
+            } else if read_only.is_empty() {
                 writeln!(
                     printer.stderr(),
                     "{}{} Skipping {} as it is not installed.",
@@ -134,7 +160,13 @@ pub(crate) async fn pip_uninstall(
                     url.as_ref().bold()
                 )?;
             } else {
-                distributions.extend(installed);
+                writeln!(
+                    printer.stderr(),
+                    "{}{} Skipping {} as it is not installed in the user site.",
+                    "warning".yellow().bold(),
+                    ":".bold(),
+                    url.as_ref().bold()
+                )?;
             }
         }
 
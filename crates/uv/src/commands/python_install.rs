@@ -0,0 +1,164 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Instant;
+
+use anyhow::{anyhow, Context, Result};
+use futures::TryStreamExt;
+use owo_colors::OwoColorize;
+use sha2::{Digest, Sha256};
+use tokio_util::compat::FuturesAsyncReadCompatExt;
+use tracing::debug;
+use url::Url;
+
+use uv_client::{BaseClient, BaseClientBuilder, Connectivity};
+use uv_fs::Simplified;
+use uv_interpreter::{InstalledToolchains, PythonDownload, ToolchainRequest};
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+
+/// Download and install managed Python toolchains.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn python_install(
+    targets: Vec<ToolchainRequest>,
+    force: bool,
+    connectivity: Connectivity,
+    native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let start = Instant::now();
+
+    let toolchains = InstalledToolchains::from_settings()?;
+    let installed = toolchains.find_all();
+
+    let client = BaseClientBuilder::new()
+        .connectivity(connectivity)
+        .native_tls(native_tls)
+        .cert(cert)
+        .proxy(proxy)
+        .retries(retries)
+        .build();
+
+    let mut downloads = Vec::with_capacity(targets.len());
+    for request in targets {
+        if !force {
+            if let Some(toolchain) = installed.iter().find(|toolchain| {
+                let (major, minor, patch) = toolchain.version();
+                request.matches(major, minor, patch)
+            }) {
+                writeln!(
+                    printer.stderr(),
+                    "Found existing installation for Python {request}: {}",
+                    toolchain.key().bold()
+                )?;
+                continue;
+            }
+        }
+
+        let Some(download) = PythonDownload::find(&request) else {
+            return Err(anyhow!(
+                "No download is available for Python {request} on this platform"
+            ));
+        };
+        if !downloads
+            .iter()
+            .any(|existing: &&PythonDownload| existing.key() == download.key())
+        {
+            downloads.push(download);
+        }
+    }
+
+    if downloads.is_empty() {
+        return Ok(ExitStatus::Success);
+    }
+
+    fs_err::create_dir_all(toolchains.root())?;
+    for download in &downloads {
+        install_download(&client, download, &toolchains).await?;
+    }
+
+    let s = if downloads.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Installed {} in {}",
+            format!("{} toolchain{s}", downloads.len()).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+    for download in &downloads {
+        writeln!(
+            printer.stderr(),
+            " {} {}",
+            "+".green(),
+            download.key().bold()
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Download, verify, and unpack a Python build into the toolchain directory.
+async fn install_download(
+    client: &BaseClient,
+    download: &PythonDownload,
+    toolchains: &InstalledToolchains,
+) -> Result<()> {
+    let key = download.key();
+    let url = download
+        .url
+        .as_deref()
+        .ok_or_else(|| anyhow!("No download URL for `{key}`"))?;
+    let filename = url
+        .rsplit_once('/')
+        .map_or(url, |(_, filename)| filename)
+        .replace("%2B", "+");
+
+    debug!("Downloading {url}");
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to download `{key}`"))?
+        .error_for_status()
+        .with_context(|| format!("Failed to download `{key}`"))?;
+
+    // Unpack the archive as it's downloaded, hashing the contents along the way.
+    let temp_dir = tempfile::tempdir_in(toolchains.root())?;
+    let mut hasher = Sha256::new();
+    let reader = response
+        .bytes_stream()
+        .inspect_ok(|chunk| hasher.update(chunk))
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
+        .into_async_read();
+    uv_extract::stream::archive(reader.compat(), &filename, temp_dir.path())
+        .await
+        .with_context(|| format!("Failed to extract `{filename}`"))?;
+
+    if let Some(expected) = download.sha256.as_deref() {
+        let actual = format!("{:x}", hasher.finalize());
+        if actual != expected {
+            return Err(anyhow!(
+                "Hash mismatch for `{key}`: expected `{expected}`, found `{actual}`"
+            ));
+        }
+    } else {
+        debug!("No checksum available for `{key}`; skipping verification");
+    }
+
+    // The builds contain a single top-level `python` directory.
+    let extracted = uv_extract::strip_component(temp_dir.path())?;
+    let target = toolchains.root().join(&key);
+    if target.exists() {
+        debug!("Removing existing toolchain at: {}", target.user_display());
+        fs_err::remove_dir_all(&target)?;
+    }
+    fs_err::rename(extracted, &target)?;
+
+    Ok(())
+}
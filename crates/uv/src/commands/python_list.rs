@@ -0,0 +1,56 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use uv_fs::Simplified;
+use uv_interpreter::{InstalledToolchains, PythonDownload};
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// List the installed Python toolchains and, optionally, those available for download.
+pub(crate) fn python_list(available: bool, printer: Printer) -> Result<ExitStatus> {
+    let installed = InstalledToolchains::from_settings()?.find_all();
+
+    if available {
+        let mut downloads = PythonDownload::iter_current_platform()
+            .filter(|download| download.url.is_some())
+            .collect::<Vec<_>>();
+        downloads
+            .sort_unstable_by(|a, b| (b.major, b.minor, b.patch).cmp(&(a.major, a.minor, a.patch)));
+        for download in downloads {
+            let key = download.key();
+            if let Some(toolchain) = installed.iter().find(|toolchain| toolchain.key() == key) {
+                writeln!(
+                    printer.stdout(),
+                    "{key}    {}",
+                    toolchain.executable().user_display()
+                )?;
+            } else {
+                writeln!(
+                    printer.stdout(),
+                    "{key}    {}",
+                    "<download available>".dimmed()
+                )?;
+            }
+        }
+        return Ok(ExitStatus::Success);
+    }
+
+    if installed.is_empty() {
+        writeln!(printer.stderr(), "No Python toolchains installed")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    for toolchain in &installed {
+        writeln!(
+            printer.stdout(),
+            "{}    {}",
+            toolchain.key(),
+            toolchain.executable().user_display()
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,88 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use url::Url;
+
+use distribution_types::IndexLocations;
+use uv_auth::KeyringProvider;
+use uv_cache::Cache;
+use uv_client::{Connectivity, TrustedHost};
+use uv_normalize::PackageName;
+use uv_resolver::ResolutionMode;
+use uv_traits::Concurrency;
+
+use crate::commands::add::read_pyproject;
+use crate::commands::{lock, ExitStatus, Upgrade};
+use crate::printer::Printer;
+use crate::pyproject_mut::{DependencyTarget, PyProjectTomlMut};
+
+/// Remove one or more packages from the project's `pyproject.toml`, optionally re-locking the
+/// project afterwards.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn remove(
+    packages: Vec<PackageName>,
+    target: DependencyTarget,
+    relock: bool,
+    python: Option<&str>,
+    index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    exclude_newer: Option<DateTime<Utc>>,
+    native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let path = std::env::current_dir()?.join("pyproject.toml");
+    let mut pyproject = PyProjectTomlMut::from_toml(&read_pyproject(&path)?)?;
+
+    let mut removed = Vec::new();
+    for package in &packages {
+        let requirements = pyproject.remove_dependency(package, &target)?;
+        if requirements.is_empty() {
+            return Err(anyhow!("`{package}` is not a dependency in {target}"));
+        }
+        removed.extend(requirements);
+    }
+    uv_fs::write_atomic_sync(&path, pyproject.to_string())?;
+
+    for requirement in &removed {
+        writeln!(
+            printer.stderr(),
+            "Removed `{}` from {target}",
+            requirement.to_string().cyan()
+        )?;
+    }
+
+    if relock {
+        return lock(
+            Upgrade::None,
+            ResolutionMode::default(),
+            python,
+            index_locations,
+            keyring_provider,
+            trusted_hosts,
+            connectivity,
+            exclude_newer,
+            FxHashMap::default(),
+            native_tls,
+            cert,
+            proxy,
+            retries,
+            concurrency,
+            cache,
+            printer,
+        )
+        .await;
+    }
+
+    Ok(ExitStatus::Success)
+}
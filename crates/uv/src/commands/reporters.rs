@@ -1,8 +1,9 @@
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use owo_colors::OwoColorize;
+use serde::Serialize;
 use url::Url;
 
 use distribution_types::{
@@ -10,10 +11,11 @@ use distribution_types::{
 };
 use uv_normalize::PackageName;
 
-use crate::printer::Printer;
+use crate::printer::{Events, Printer};
 
 #[derive(Debug)]
 pub(crate) struct FinderReporter {
+    events: Events,
     progress: ProgressBar,
 }
 
@@ -24,7 +26,11 @@ impl From<Printer> for FinderReporter {
             ProgressStyle::with_template("{bar:20} [{pos}/{len}] {wide_msg:.dim}").unwrap(),
         );
         progress.set_message("Resolving dependencies...");
-        Self { progress }
+
+        let events = printer.events();
+        events.emit(&Event::FindStarted);
+
+        Self { events, progress }
     }
 }
 
@@ -38,11 +44,16 @@ impl FinderReporter {
 
 impl uv_resolver::FinderReporter for FinderReporter {
     fn on_progress(&self, dist: &Dist) {
+        self.events.emit(&Event::FindProgress {
+            package: dist.name().to_string(),
+            version: event_version(&dist.version_or_url()),
+        });
         self.progress.set_message(format!("{dist}"));
         self.progress.inc(1);
     }
 
     fn on_complete(&self) {
+        self.events.emit(&Event::FindFinished);
         self.progress.finish_and_clear();
     }
 }
@@ -50,6 +61,7 @@ impl uv_resolver::FinderReporter for FinderReporter {
 #[derive(Debug)]
 pub(crate) struct DownloadReporter {
     printer: Printer,
+    events: Events,
     multi_progress: MultiProgress,
     progress: ProgressBar,
     bars: Arc<Mutex<Vec<ProgressBar>>>,
@@ -67,6 +79,7 @@ impl From<Printer> for DownloadReporter {
 
         Self {
             printer,
+            events: printer.events(),
             multi_progress,
             progress,
             bars: Arc::new(Mutex::new(Vec::new())),
@@ -83,7 +96,7 @@ impl DownloadReporter {
 }
 
 impl DownloadReporter {
-    fn on_any_build_start(&self, color_string: &str) -> usize {
+    fn on_any_build_start(&self, package: String, color_string: &str) -> usize {
         let progress = self.multi_progress.insert_before(
             &self.progress,
             ProgressBar::with_draw_target(None, self.printer.target()),
@@ -94,13 +107,16 @@ impl DownloadReporter {
 
         let mut bars = self.bars.lock().unwrap();
         bars.push(progress);
-        bars.len() - 1
+        let id = bars.len() - 1;
+        self.events.emit(&Event::BuildStarted { id, package });
+        id
     }
 
-    fn on_any_build_complete(&self, color_string: &str, id: usize) {
+    fn on_any_build_complete(&self, package: String, color_string: &str, id: usize) {
         let bars = self.bars.lock().unwrap();
         let progress = &bars[id];
         progress.finish_with_message(format!("   {} {}", "Built".bold().green(), color_string));
+        self.events.emit(&Event::BuildFinished { id, package });
     }
 }
 
@@ -115,19 +131,19 @@ impl uv_installer::DownloadReporter for DownloadReporter {
     }
 
     fn on_build_start(&self, dist: &SourceDist) -> usize {
-        self.on_any_build_start(&dist.to_color_string())
+        self.on_any_build_start(dist.name().to_string(), &dist.to_color_string())
     }
 
     fn on_build_complete(&self, dist: &SourceDist, index: usize) {
-        self.on_any_build_complete(&dist.to_color_string(), index);
+        self.on_any_build_complete(dist.name().to_string(), &dist.to_color_string(), index);
     }
 
     fn on_editable_build_start(&self, dist: &LocalEditable) -> usize {
-        self.on_any_build_start(&dist.to_color_string())
+        self.on_any_build_start(dist.to_string(), &dist.to_color_string())
     }
 
     fn on_editable_build_complete(&self, dist: &LocalEditable, id: usize) {
-        self.on_any_build_complete(&dist.to_color_string(), id);
+        self.on_any_build_complete(dist.to_string(), &dist.to_color_string(), id);
     }
 
     fn on_checkout_start(&self, url: &Url, rev: &str) -> usize {
@@ -160,10 +176,51 @@ impl uv_installer::DownloadReporter for DownloadReporter {
             rev.dimmed()
         ));
     }
+
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize {
+        let progress = self.multi_progress.insert_before(
+            &self.progress,
+            ProgressBar::with_draw_target(size, self.printer.target()),
+        );
+
+        progress.set_style(
+            ProgressStyle::with_template(
+                "{msg:10.dim} {bar:30.green/dim} {binary_bytes:>7}/{binary_total_bytes:7}",
+            )
+            .unwrap(),
+        );
+        progress.set_message(name.to_string());
+
+        let mut bars = self.bars.lock().unwrap();
+        bars.push(progress);
+        let id = bars.len() - 1;
+        self.events.emit(&Event::DownloadStarted {
+            id,
+            package: name.to_string(),
+            size,
+        });
+        id
+    }
+
+    fn on_download_progress(&self, id: usize, bytes: u64) {
+        let bars = self.bars.lock().unwrap();
+        bars[id].set_position(bytes);
+        self.events.emit(&Event::DownloadProgress { id, bytes });
+    }
+
+    fn on_download_complete(&self, name: &PackageName, id: usize) {
+        let bars = self.bars.lock().unwrap();
+        bars[id].finish_and_clear();
+        self.events.emit(&Event::DownloadFinished {
+            id,
+            package: name.to_string(),
+        });
+    }
 }
 
 #[derive(Debug)]
 pub(crate) struct InstallReporter {
+    events: Events,
     progress: ProgressBar,
 }
 
@@ -174,7 +231,10 @@ impl From<Printer> for InstallReporter {
             ProgressStyle::with_template("{bar:20} [{pos}/{len}] {wide_msg:.dim}").unwrap(),
         );
         progress.set_message("Installing wheels...");
-        Self { progress }
+        Self {
+            events: printer.events(),
+            progress,
+        }
     }
 }
 
@@ -184,15 +244,28 @@ impl InstallReporter {
         self.progress.set_length(length);
         self
     }
+
+    /// Hide the progress bar (e.g., when installing alongside the download progress bars), while
+    /// continuing to emit events.
+    #[must_use]
+    pub(crate) fn hidden(self) -> Self {
+        self.progress.set_draw_target(ProgressDrawTarget::hidden());
+        self
+    }
 }
 
 impl uv_installer::InstallReporter for InstallReporter {
     fn on_install_progress(&self, wheel: &CachedDist) {
+        self.events.emit(&Event::InstallProgress {
+            package: wheel.name().to_string(),
+            version: event_version(&wheel.version_or_url()),
+        });
         self.progress.set_message(format!("{wheel}"));
         self.progress.inc(1);
     }
 
     fn on_install_complete(&self) {
+        self.events.emit(&Event::InstallFinished);
         self.progress.finish_and_clear();
     }
 }
@@ -200,6 +273,7 @@ impl uv_installer::InstallReporter for InstallReporter {
 #[derive(Debug)]
 pub(crate) struct ResolverReporter {
     printer: Printer,
+    events: Events,
     multi_progress: MultiProgress,
     progress: ProgressBar,
     bars: Arc<Mutex<Vec<ProgressBar>>>,
@@ -218,8 +292,12 @@ impl From<Printer> for ResolverReporter {
         );
         progress.set_message("Resolving dependencies...");
 
+        let events = printer.events();
+        events.emit(&Event::ResolveStarted);
+
         Self {
             printer,
+            events,
             multi_progress,
             progress,
             bars: Arc::new(Mutex::new(Vec::new())),
@@ -229,6 +307,10 @@ impl From<Printer> for ResolverReporter {
 
 impl uv_resolver::ResolverReporter for ResolverReporter {
     fn on_progress(&self, name: &PackageName, version_or_url: VersionOrUrl) {
+        self.events.emit(&Event::ResolveProgress {
+            package: name.to_string(),
+            version: event_version(&version_or_url),
+        });
         match version_or_url {
             VersionOrUrl::Version(version) => {
                 self.progress.set_message(format!("{name}=={version}"));
@@ -240,6 +322,7 @@ impl uv_resolver::ResolverReporter for ResolverReporter {
     }
 
     fn on_complete(&self) {
+        self.events.emit(&Event::ResolveFinished);
         self.progress.finish_and_clear();
     }
 
@@ -258,7 +341,12 @@ impl uv_resolver::ResolverReporter for ResolverReporter {
 
         let mut bars = self.bars.lock().unwrap();
         bars.push(progress);
-        bars.len() - 1
+        let id = bars.len() - 1;
+        self.events.emit(&Event::BuildStarted {
+            id,
+            package: dist.name().to_string(),
+        });
+        id
     }
 
     fn on_build_complete(&self, dist: &SourceDist, index: usize) {
@@ -269,6 +357,10 @@ impl uv_resolver::ResolverReporter for ResolverReporter {
             "Built".bold().green(),
             dist.to_color_string(),
         ));
+        self.events.emit(&Event::BuildFinished {
+            id: index,
+            package: dist.name().to_string(),
+        });
     }
 
     fn on_checkout_start(&self, url: &Url, rev: &str) -> usize {
@@ -301,6 +393,91 @@ impl uv_resolver::ResolverReporter for ResolverReporter {
             rev.dimmed()
         ));
     }
+
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize {
+        let progress = self.multi_progress.insert_before(
+            &self.progress,
+            ProgressBar::with_draw_target(size, self.printer.target()),
+        );
+
+        progress.set_style(
+            ProgressStyle::with_template(
+                "{msg:10.dim} {bar:30.green/dim} {binary_bytes:>7}/{binary_total_bytes:7}",
+            )
+            .unwrap(),
+        );
+        progress.set_message(name.to_string());
+
+        let mut bars = self.bars.lock().unwrap();
+        bars.push(progress);
+        let id = bars.len() - 1;
+        self.events.emit(&Event::DownloadStarted {
+            id,
+            package: name.to_string(),
+            size,
+        });
+        id
+    }
+
+    fn on_download_progress(&self, id: usize, bytes: u64) {
+        let bars = self.bars.lock().unwrap();
+        bars[id].set_position(bytes);
+        self.events.emit(&Event::DownloadProgress { id, bytes });
+    }
+
+    fn on_download_complete(&self, name: &PackageName, id: usize) {
+        let bars = self.bars.lock().unwrap();
+        bars[id].finish_and_clear();
+        self.events.emit(&Event::DownloadFinished {
+            id,
+            package: name.to_string(),
+        });
+    }
+}
+
+/// Format the version (e.g., `2.0.0`) or URL of a distribution for a progress event.
+fn event_version(version_or_url: &VersionOrUrl) -> String {
+    match version_or_url {
+        VersionOrUrl::Version(version) => version.to_string(),
+        VersionOrUrl::Url(url) => url.to_string(),
+    }
+}
+
+/// A machine-readable progress event, emitted with `--progress json`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Event {
+    /// Resolution has started.
+    ResolveStarted,
+    /// A package was added to the resolution.
+    ResolveProgress { package: String, version: String },
+    /// Resolution is complete.
+    ResolveFinished,
+    /// Finding distributions for pinned requirements (e.g., in `uv pip sync`), without resolving
+    /// their dependencies, has started.
+    FindStarted,
+    /// A distribution was found for a pinned requirement.
+    FindProgress { package: String, version: String },
+    /// Finding distributions is complete.
+    FindFinished,
+    /// A download has started. The `size` is in bytes, if known.
+    DownloadStarted {
+        id: usize,
+        package: String,
+        size: Option<u64>,
+    },
+    /// A download has made progress, with the total number of bytes downloaded so far.
+    DownloadProgress { id: usize, bytes: u64 },
+    /// A download is complete.
+    DownloadFinished { id: usize, package: String },
+    /// A build has started.
+    BuildStarted { id: usize, package: String },
+    /// A build is complete.
+    BuildFinished { id: usize, package: String },
+    /// A wheel was installed.
+    InstallProgress { package: String, version: String },
+    /// Installation is complete.
+    InstallFinished,
 }
 
 /// Like [`std::fmt::Display`], but with colors.
@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use distribution_types::IndexLocations;
+use uv_cache::{Cache, CacheBucket, CacheEntry};
+use uv_client::IndexStrategy;
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+use uv_resolver::{AllowYanked, Options};
+
+/// The maximum age of a cached resolution, when resolving without `--exclude-newer`.
+///
+/// Matches the `max-age` that PyPI sets on its Simple API responses, such that a cached resolution
+/// is never staler than the index responses that a fresh resolution would use.
+const MAX_AGE: Duration = Duration::from_secs(600);
+
+/// The inputs to a resolution (the requirements, the target environment, the index locations, and
+/// the resolver options), from which the cache key for the resolution is derived.
+///
+/// Each input is recorded by name as a list of strings and serialized to JSON, such that the key
+/// doesn't depend on the in-memory representation (or iteration order) of the inputs. Inputs
+/// whose order is irrelevant (e.g., the set of requirements) are sorted.
+#[derive(Debug, Serialize)]
+pub(crate) struct ResolutionKey(BTreeMap<&'static str, Vec<String>>);
+
+impl ResolutionKey {
+    /// Create a key for the current version of uv.
+    pub(crate) fn new() -> Self {
+        Self(BTreeMap::from([(
+            "version",
+            vec![env!("CARGO_PKG_VERSION").to_string()],
+        )]))
+    }
+
+    /// Record a single input.
+    #[must_use]
+    pub(crate) fn value(mut self, name: &'static str, value: impl Display) -> Self {
+        self.0.insert(name, vec![value.to_string()]);
+        self
+    }
+
+    /// Record an ordered list of inputs.
+    #[must_use]
+    pub(crate) fn list<T: Display>(
+        mut self,
+        name: &'static str,
+        values: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.0.insert(
+            name,
+            values.into_iter().map(|value| value.to_string()).collect(),
+        );
+        self
+    }
+
+    /// Record an unordered set of inputs.
+    #[must_use]
+    pub(crate) fn set<T: Display>(
+        mut self,
+        name: &'static str,
+        values: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let mut values = values
+            .into_iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        values.dedup();
+        self.0.insert(name, values);
+        self
+    }
+
+    /// Record the index locations, in priority order, along with any pinned indexes.
+    #[must_use]
+    pub(crate) fn index_locations(
+        self,
+        index_locations: &IndexLocations,
+        index_strategy: IndexStrategy,
+    ) -> Self {
+        self.list("indexes", index_locations.indexes())
+            .set(
+                "pinned-indexes",
+                index_locations
+                    .pinned()
+                    .map(|(name, index)| format!("{name}={index}")),
+            )
+            .list("find-links", index_locations.flat_index())
+            .value("index-strategy", format!("{index_strategy:?}"))
+    }
+
+    /// Record the resolver options.
+    #[must_use]
+    pub(crate) fn options(self, options: &Options) -> Self {
+        let allow_yanked = match &options.allow_yanked {
+            AllowYanked::None => vec![],
+            AllowYanked::All => vec![":all:".to_string()],
+            AllowYanked::Packages(packages) => packages.iter().map(ToString::to_string).collect(),
+        };
+        self.value("resolution-mode", format!("{:?}", options.resolution_mode))
+            .value("prerelease-mode", format!("{:?}", options.prerelease_mode))
+            .value("dependency-mode", format!("{:?}", options.dependency_mode))
+            .list(
+                "exclude-newer",
+                options.exclude_newer.map(|date| date.to_rfc3339()),
+            )
+            .set(
+                "exclude-newer-package",
+                options
+                    .exclude_newer_package
+                    .iter()
+                    .map(|(name, date)| format!("{name}={}", date.to_rfc3339())),
+            )
+            .set("allow-yanked", allow_yanked)
+            .value("universal", options.universal)
+            .value("preserve-markers", options.preserve_markers)
+    }
+}
+
+/// A cached resolution, along with the names of the packages it contains.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResolution<T> {
+    packages: Vec<PackageName>,
+    resolution: T,
+}
+
+/// A persistent cache of resolutions, keyed by a digest of their inputs (see [`ResolutionKey`]),
+/// such that repeated invocations with unchanged inputs can skip straight to the previous
+/// solution.
+///
+/// Cached resolutions are ignored when running with `--refresh`, or with `--refresh-package` for
+/// any of the packages in the resolution.
+#[derive(Debug)]
+pub(crate) struct ResolutionCache<'a> {
+    cache: &'a Cache,
+    entry: CacheEntry,
+    /// Whether the index state is pinned via `--exclude-newer`, in which case the cached
+    /// resolution never expires.
+    exclude_newer: bool,
+}
+
+impl<'a> ResolutionCache<'a> {
+    /// Initialize the cache entry for the resolution with the given inputs.
+    pub(crate) fn new(
+        cache: &'a Cache,
+        command: &str,
+        key: &ResolutionKey,
+        exclude_newer: bool,
+    ) -> Result<Self> {
+        let key = serde_json::to_string(key)?;
+        let entry = cache.entry(
+            CacheBucket::Resolutions,
+            command,
+            format!("{}.json", cache_key::digest(&key)),
+        );
+        Ok(Self {
+            cache,
+            entry,
+            exclude_newer,
+        })
+    }
+
+    /// Read the cached resolution, if it exists and is fresh.
+    pub(crate) fn read<T: DeserializeOwned>(&self) -> Option<T> {
+        // Without `--exclude-newer`, the index may have changed since the resolution was cached.
+        if !self.exclude_newer {
+            let modified = fs_err::metadata(self.entry.path()).ok()?.modified().ok()?;
+            let age = SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default();
+            if age > MAX_AGE {
+                debug!(
+                    "Ignoring expired resolution at: {}",
+                    self.entry.path().user_display()
+                );
+                return None;
+            }
+        }
+
+        let contents = fs_err::read(self.entry.path()).ok()?;
+        let cached = match serde_json::from_slice::<CachedResolution<T>>(&contents) {
+            Ok(cached) => cached,
+            Err(err) => {
+                debug!(
+                    "Ignoring invalid resolution at {}: {err}",
+                    self.entry.path().user_display()
+                );
+                return None;
+            }
+        };
+
+        // If any of the packages must be refreshed, the resolution may change.
+        if let Some(package) = cached
+            .packages
+            .iter()
+            .find(|package| self.cache.must_revalidate(package))
+        {
+            debug!(
+                "Ignoring cached resolution at {} to refresh: {package}",
+                self.entry.path().user_display()
+            );
+            return None;
+        }
+
+        debug!(
+            "Reusing cached resolution at: {}",
+            self.entry.path().user_display()
+        );
+        Some(cached.resolution)
+    }
+
+    /// Write the resolution, which contains the given packages, to the cache.
+    pub(crate) fn write<T: Serialize>(
+        &self,
+        packages: impl IntoIterator<Item = PackageName>,
+        resolution: &T,
+    ) -> Result<()> {
+        let cached = CachedResolution {
+            packages: packages.into_iter().collect(),
+            resolution,
+        };
+        fs_err::create_dir_all(self.entry.dir())?;
+        uv_fs::write_atomic_sync(self.entry.path(), serde_json::to_vec(&cached)?)?;
+        Ok(())
+    }
+}
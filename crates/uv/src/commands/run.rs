@@ -0,0 +1,185 @@
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::IndexLocations;
+use uv_auth::KeyringProvider;
+use uv_cache::{Cache, CacheBucket};
+use uv_client::{Connectivity, IndexStrategy, TrustedHost};
+use uv_fs::Simplified;
+use uv_installer::{NoBinary, Reinstall};
+use uv_interpreter::{find_default_python, find_requested_python, Error, PythonEnvironment};
+use uv_resolver::{AllowYanked, DependencyMode, PreReleaseMode, ResolutionMode};
+use uv_traits::{Concurrency, ConfigSettings, NoBuild, PackageConfigSettings, SetupPyStrategy};
+
+use crate::commands::{pip_install, ExitStatus, SbomFormat, Upgrade};
+use crate::printer::Printer;
+use crate::requirements::{ExtrasSpecification, RequirementsSource};
+
+/// Run a command in an ephemeral environment containing the given requirements.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run(
+    command: Vec<OsString>,
+    with: Vec<String>,
+    python: Option<&str>,
+    no_project: bool,
+    index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    exclude_newer: Option<DateTime<Utc>>,
+    native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
+    cache: Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let Some((program, args)) = command.split_first() else {
+        return Err(anyhow::anyhow!("No command provided"));
+    };
+
+    // Collect the requirements: any packages requested via `--with`, along with the project in
+    // the current directory, if any.
+    let mut requirements = with
+        .into_iter()
+        .map(RequirementsSource::from_package)
+        .collect::<Result<Vec<_>>>()?;
+    if !no_project {
+        let project = std::env::current_dir()?;
+        if project.join("pyproject.toml").is_file() {
+            debug!("Including project at: {}", project.user_display());
+            requirements.push(RequirementsSource::Editable(
+                project.simplified_display().to_string(),
+            ));
+        }
+    }
+
+    // Locate the base interpreter.
+    let interpreter = if let Some(python) = python {
+        find_requested_python(python, &cache)?
+            .ok_or_else(|| Error::RequestedPythonNotFound(python.to_string()))?
+    } else {
+        find_default_python(&cache)?
+    };
+
+    // Environments are keyed by their requirements and base interpreter, such that repeated
+    // invocations can reuse the same environment.
+    let key = {
+        let mut sources = requirements
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        sources.sort_unstable();
+        cache_key::digest(&(sources, interpreter.sys_executable()))
+    };
+    let root = cache.bucket(CacheBucket::Environments).join(key);
+
+    // Create the environment, if it doesn't already exist.
+    let venv = if root.join("pyvenv.cfg").is_file() {
+        debug!("Reusing environment at: {}", root.user_display());
+        PythonEnvironment::from_root(&root, &cache)?
+    } else {
+        debug!("Creating environment at: {}", root.user_display());
+        let extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
+        uv_virtualenv::create_venv(
+            &root,
+            interpreter,
+            uv_virtualenv::Prompt::None,
+            false,
+            false,
+            extra_cfg,
+        )
+        .context("Failed to create environment")?
+    };
+
+    // Install the requirements into the environment. If the environment already satisfies the
+    // requirements, this is a no-op.
+    if !requirements.is_empty() {
+        let status = pip_install(
+            &requirements,
+            &[],
+            &[],
+            &[],
+            &ExtrasSpecification::None,
+            ResolutionMode::default(),
+            PreReleaseMode::default(),
+            DependencyMode::Transitive,
+            Upgrade::None,
+            index_locations,
+            IndexStrategy::default(),
+            keyring_provider,
+            trusted_hosts,
+            &Reinstall::None,
+            None,
+            false,
+            SetupPyStrategy::default(),
+            connectivity,
+            &ConfigSettings::default(),
+            &PackageConfigSettings::default(),
+            false,
+            &[],
+            &NoBuild::None,
+            &NoBinary::None,
+            false,
+            false,
+            exclude_newer,
+            FxHashMap::default(),
+            AllowYanked::default(),
+            None,
+            None,
+            None,
+            &[],
+            Some(venv.python_executable().simplified_display().to_string()),
+            false,
+            false,
+            false,
+            None,
+            native_tls,
+            cert,
+            proxy,
+            retries,
+            concurrency,
+            cache.clone(),
+            false,
+            false,
+            None,
+            None,
+            SbomFormat::default(),
+            printer,
+        )
+        .await?;
+        if !matches!(status, ExitStatus::Success) {
+            return Ok(status);
+        }
+    }
+
+    // Run the command, with the environment's scripts directory at the front of the `PATH`.
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let path = std::env::join_paths(
+        std::iter::once(venv.scripts().to_path_buf()).chain(std::env::split_paths(&path)),
+    )?;
+
+    debug!("Running `{}`", program.to_string_lossy());
+    let status = Command::new(program)
+        .args(args)
+        .env("PATH", path)
+        .env("VIRTUAL_ENV", venv.root())
+        .status()
+        .with_context(|| format!("Failed to run `{}`", program.to_string_lossy().cyan()))?;
+
+    Ok(match status.code() {
+        Some(0) => ExitStatus::Success,
+        Some(code) => ExitStatus::External(u8::try_from(code).unwrap_or(1)),
+        // The process was terminated by a signal.
+        None => ExitStatus::Failure,
+    })
+}
@@ -0,0 +1,455 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use chrono::{SecondsFormat, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use distribution_types::{
+    Dist, DistributionMetadata, FileLocation, InstalledDist, Name, VersionOrUrl,
+};
+use pypi_types::base_url_join_relative;
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+use uv_resolver::LockedDistribution;
+
+use crate::printer::Printer;
+
+/// The format of a software bill of materials (SBOM).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum SbomFormat {
+    /// A CycloneDX 1.5 document, in JSON.
+    #[default]
+    #[value(name = "cyclonedx")]
+    CycloneDx,
+    /// An SPDX 2.3 document, in JSON.
+    Spdx,
+}
+
+/// A package listed in a software bill of materials.
+#[derive(Debug)]
+pub(crate) struct SbomComponent {
+    name: PackageName,
+    version: Option<String>,
+    /// The hashes of the package's artifact, as `(algorithm, digest)` pairs (e.g., `sha256`).
+    hashes: Vec<(String, String)>,
+    /// The SPDX license expression for the package, if known.
+    license_expression: Option<String>,
+    /// The free-form license for the package, if known.
+    license: Option<String>,
+    /// The URL from which the package is (or was) installed.
+    url: Option<String>,
+}
+
+impl SbomComponent {
+    /// Create a component from a distribution installed in the environment.
+    ///
+    /// The version and license are read from the installed distribution's metadata. If the
+    /// distribution was installed from the given resolved distribution, the hashes and URL of the
+    /// artifact are taken from the resolution; otherwise, the component only includes the URL of
+    /// direct URL installations, as recorded in `direct_url.json`.
+    pub(crate) fn from_installed(
+        installed: &InstalledDist,
+        resolved: Option<&Dist>,
+    ) -> Result<Self> {
+        let metadata = installed.metadata()?;
+
+        // Only use the resolved distribution if it's the one that's installed.
+        let resolved = resolved.filter(|dist| match dist.version_or_url() {
+            VersionOrUrl::Version(version) => version == installed.version(),
+            VersionOrUrl::Url(url) => match installed {
+                InstalledDist::Registry(_) => false,
+                InstalledDist::Url(installed) => installed.url == *url.raw(),
+            },
+        });
+
+        let url = match resolved {
+            Some(dist) => dist_url(dist)?,
+            None => match installed {
+                InstalledDist::Registry(_) => None,
+                InstalledDist::Url(installed) => Some(installed.url.to_string()),
+            },
+        };
+        let hashes = resolved.map(dist_hashes).unwrap_or_default();
+
+        Ok(Self {
+            name: installed.name().clone(),
+            version: Some(installed.version().to_string()),
+            hashes,
+            license_expression: metadata.license_expression,
+            license: metadata.license,
+            url,
+        })
+    }
+
+    /// Create a component from a distribution in the `uv.lock` file.
+    ///
+    /// Since the lockfile doesn't record package metadata, the component omits any license
+    /// information.
+    pub(crate) fn from_locked(distribution: &LockedDistribution) -> Self {
+        Self {
+            name: distribution.name().clone(),
+            version: distribution.version().map(ToString::to_string),
+            hashes: distribution
+                .hashes()
+                .iter()
+                .filter_map(|hash| {
+                    let (algorithm, digest) = hash.split_once(':')?;
+                    Some((algorithm.to_string(), digest.to_string()))
+                })
+                .collect(),
+            license_expression: None,
+            license: None,
+            url: distribution.url().map(ToString::to_string),
+        }
+    }
+
+    /// Return the package URL (purl) of the component, e.g., `pkg:pypi/flask@3.0.0`.
+    ///
+    /// See: <https://github.com/package-url/purl-spec/blob/master/PURL-TYPES.rst#pypi>
+    fn purl(&self) -> String {
+        match &self.version {
+            Some(version) => format!("pkg:pypi/{}@{version}", self.name),
+            None => format!("pkg:pypi/{}", self.name),
+        }
+    }
+}
+
+/// Return the URL from which a distribution is (or was) installed, if known.
+fn dist_url(dist: &Dist) -> Result<Option<String>> {
+    if let Some(file) = dist.file() {
+        Ok(Some(match &file.url {
+            FileLocation::RelativeUrl(base, url) => base_url_join_relative(base, url)?.to_string(),
+            FileLocation::AbsoluteUrl(url) => url.clone(),
+            FileLocation::Path(path) => Url::from_file_path(path)
+                .map_err(|()| anyhow!("Invalid file path: {}", path.user_display()))?
+                .to_string(),
+        }))
+    } else if let VersionOrUrl::Url(url) = dist.version_or_url() {
+        Ok(Some(url.to_url().to_string()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Return the hashes of a distribution's artifact, as reported by the index, as
+/// `(algorithm, digest)` pairs.
+fn dist_hashes(dist: &Dist) -> Vec<(String, String)> {
+    dist.file()
+        .map(|file| {
+            [
+                ("md5", &file.hashes.md5),
+                ("sha256", &file.hashes.sha256),
+                ("sha384", &file.hashes.sha384),
+                ("sha512", &file.hashes.sha512),
+            ]
+            .into_iter()
+            .filter_map(|(algorithm, digest)| {
+                Some((algorithm.to_string(), digest.as_deref()?.to_string()))
+            })
+            .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Write a software bill of materials for the given components to a file, or to `stdout` if the
+/// path is `-`.
+pub(crate) fn write_sbom(
+    components: &[SbomComponent],
+    format: SbomFormat,
+    path: &Path,
+    printer: Printer,
+) -> Result<()> {
+    let sbom = match format {
+        SbomFormat::CycloneDx => serde_json::to_string_pretty(&CycloneDx::new(components))?,
+        SbomFormat::Spdx => serde_json::to_string_pretty(&Spdx::new(components))?,
+    };
+    if path == Path::new("-") {
+        writeln!(printer.stdout(), "{sbom}")?;
+    } else {
+        fs_err::write(path, sbom)?;
+    }
+    Ok(())
+}
+
+/// A CycloneDX 1.5 document.
+///
+/// See: <https://cyclonedx.org/docs/1.5/json/>
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDx<'a> {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    version: u32,
+    metadata: CycloneDxMetadata,
+    components: Vec<CycloneDxComponent<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxMetadata {
+    tools: CycloneDxTools,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxTools {
+    components: Vec<CycloneDxTool>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxTool {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: &'static str,
+    version: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDxComponent<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    #[serde(rename = "bom-ref")]
+    bom_ref: String,
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'a str>,
+    purl: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<CycloneDxHash<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    licenses: Vec<CycloneDxLicense<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_references: Vec<CycloneDxReference<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxHash<'a> {
+    alg: &'static str,
+    content: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum CycloneDxLicense<'a> {
+    Expression { expression: &'a str },
+    License { license: CycloneDxLicenseName<'a> },
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxLicenseName<'a> {
+    name: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxReference<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    url: &'a str,
+}
+
+impl<'a> CycloneDx<'a> {
+    fn new(components: &'a [SbomComponent]) -> Self {
+        Self {
+            bom_format: "CycloneDX",
+            spec_version: "1.5",
+            version: 1,
+            metadata: CycloneDxMetadata {
+                tools: CycloneDxTools {
+                    components: vec![CycloneDxTool {
+                        kind: "application",
+                        name: "uv",
+                        version: env!("CARGO_PKG_VERSION"),
+                    }],
+                },
+            },
+            components: components
+                .iter()
+                .map(|component| CycloneDxComponent {
+                    kind: "library",
+                    bom_ref: component.purl(),
+                    name: component.name.as_ref(),
+                    version: component.version.as_deref(),
+                    purl: component.purl(),
+                    hashes: component
+                        .hashes
+                        .iter()
+                        .filter_map(|(algorithm, digest)| {
+                            let alg = match algorithm.as_str() {
+                                "md5" => "MD5",
+                                "sha256" => "SHA-256",
+                                "sha384" => "SHA-384",
+                                "sha512" => "SHA-512",
+                                _ => return None,
+                            };
+                            Some(CycloneDxHash {
+                                alg,
+                                content: digest,
+                            })
+                        })
+                        .collect(),
+                    licenses: if let Some(expression) = &component.license_expression {
+                        vec![CycloneDxLicense::Expression { expression }]
+                    } else if let Some(name) = &component.license {
+                        vec![CycloneDxLicense::License {
+                            license: CycloneDxLicenseName { name },
+                        }]
+                    } else {
+                        vec![]
+                    },
+                    external_references: component
+                        .url
+                        .iter()
+                        .map(|url| CycloneDxReference {
+                            kind: "distribution",
+                            url,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// An SPDX 2.3 document.
+///
+/// See: <https://spdx.github.io/spdx-spec/v2.3/>
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Spdx<'a> {
+    spdx_version: &'static str,
+    data_license: &'static str,
+    #[serde(rename = "SPDXID")]
+    spdx_id: &'static str,
+    name: &'static str,
+    document_namespace: String,
+    creation_info: SpdxCreationInfo,
+    packages: Vec<SpdxPackage<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct SpdxCreationInfo {
+    created: String,
+    creators: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxPackage<'a> {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_info: Option<&'a str>,
+    download_location: &'a str,
+    files_analyzed: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    checksums: Vec<SpdxChecksum<'a>>,
+    license_concluded: &'static str,
+    license_declared: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    license_comments: Option<&'a str>,
+    external_refs: Vec<SpdxExternalRef>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxChecksum<'a> {
+    algorithm: &'static str,
+    checksum_value: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxExternalRef {
+    reference_category: &'static str,
+    reference_type: &'static str,
+    reference_locator: String,
+}
+
+impl<'a> Spdx<'a> {
+    fn new(components: &'a [SbomComponent]) -> Self {
+        // Derive a unique namespace for the document from its contents.
+        let mut hasher = Sha256::new();
+        for component in components {
+            hasher.update(component.purl().as_bytes());
+            hasher.update(b"\n");
+        }
+        let document_namespace = format!("https://spdx.org/spdxdocs/uv-{:x}", hasher.finalize());
+
+        Self {
+            spdx_version: "SPDX-2.3",
+            data_license: "CC0-1.0",
+            spdx_id: "SPDXRef-DOCUMENT",
+            name: "uv",
+            document_namespace,
+            creation_info: SpdxCreationInfo {
+                created: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+                creators: vec![format!("Tool: uv-{}", env!("CARGO_PKG_VERSION"))],
+            },
+            packages: components
+                .iter()
+                .map(|component| SpdxPackage {
+                    spdx_id: spdx_id(component),
+                    name: component.name.as_ref(),
+                    version_info: component.version.as_deref(),
+                    download_location: component.url.as_deref().unwrap_or("NOASSERTION"),
+                    files_analyzed: false,
+                    checksums: component
+                        .hashes
+                        .iter()
+                        .filter_map(|(algorithm, digest)| {
+                            let algorithm = match algorithm.as_str() {
+                                "md5" => "MD5",
+                                "sha256" => "SHA256",
+                                "sha384" => "SHA384",
+                                "sha512" => "SHA512",
+                                _ => return None,
+                            };
+                            Some(SpdxChecksum {
+                                algorithm,
+                                checksum_value: digest,
+                            })
+                        })
+                        .collect(),
+                    license_concluded: "NOASSERTION",
+                    // Only SPDX license expressions are valid here; any free-form license is
+                    // recorded as a comment instead.
+                    license_declared: component
+                        .license_expression
+                        .as_deref()
+                        .unwrap_or("NOASSERTION"),
+                    license_comments: component
+                        .license_expression
+                        .is_none()
+                        .then_some(component.license.as_deref())
+                        .flatten(),
+                    external_refs: vec![SpdxExternalRef {
+                        reference_category: "PACKAGE-MANAGER",
+                        reference_type: "purl",
+                        reference_locator: component.purl(),
+                    }],
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Return the SPDX identifier for a component, which may only contain letters, numbers, `.`, and
+/// `-`.
+fn spdx_id(component: &SbomComponent) -> String {
+    let id = match &component.version {
+        Some(version) => format!("SPDXRef-Package-{}-{version}", component.name),
+        None => format!("SPDXRef-Package-{}", component.name),
+    };
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use url::Url;
+
+use distribution_types::IndexLocations;
+use install_wheel_rs::linker::LinkMode;
+use uv_auth::KeyringProvider;
+use uv_cache::Cache;
+use uv_client::{Connectivity, IndexStrategy, TrustedHost};
+use uv_installer::{NoBinary, Reinstall};
+use uv_interpreter::PythonEnvironment;
+use uv_traits::{Concurrency, ConfigSettings, NoBuild, PackageConfigSettings, SetupPyStrategy};
+
+use crate::commands::{pip_sync, ExitStatus};
+use crate::printer::Printer;
+use crate::requirements::{ExtrasSpecification, RequirementsSource};
+use crate::workspace::Workspace;
+
+/// Install the project's locked dependencies from `uv.lock` into the active virtual environment.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn sync(
+    reinstall: &Reinstall,
+    link_mode: Option<LinkMode>,
+    compile_bytecode: bool,
+    index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
+    cache: Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let lockfile = Workspace::project_root(&std::env::current_dir()?)?.join("uv.lock");
+    if !lockfile.is_file() {
+        return Err(anyhow!(
+            "No `uv.lock` found in the current directory; run `uv lock` to create one"
+        ));
+    }
+
+    // Select the locked distributions (and their hashes) that apply to the target environment.
+    let venv = PythonEnvironment::from_virtualenv(&cache)?;
+    let sources = [RequirementsSource::Lockfile(
+        lockfile,
+        Box::new(venv.interpreter().markers().clone()),
+    )];
+
+    // Since every distribution is pinned, install the environment as-is, without resolving.
+    pip_sync(
+        &sources,
+        &ExtrasSpecification::None,
+        reinstall,
+        &[],
+        false,
+        link_mode,
+        compile_bytecode,
+        index_locations,
+        IndexStrategy::default(),
+        keyring_provider,
+        trusted_hosts,
+        SetupPyStrategy::default(),
+        connectivity,
+        &ConfigSettings::default(),
+        &PackageConfigSettings::default(),
+        false,
+        &[],
+        &NoBuild::None,
+        &NoBinary::None,
+        false,
+        false,
+        None,
+        false,
+        false,
+        native_tls,
+        cert,
+        proxy,
+        retries,
+        concurrency,
+        cache,
+        false,
+        false,
+        printer,
+    )
+    .await
+}
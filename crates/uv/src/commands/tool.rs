@@ -0,0 +1,214 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use directories::{BaseDirs, ProjectDirs};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use url::Url;
+
+use distribution_types::IndexLocations;
+use install_wheel_rs::linker::installed_scripts;
+use uv_auth::KeyringProvider;
+use uv_cache::Cache;
+use uv_client::{Connectivity, IndexStrategy, TrustedHost};
+use uv_fs::Simplified;
+use uv_installer::{NoBinary, Reinstall, SitePackages};
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+use uv_resolver::{AllowYanked, DependencyMode, PreReleaseMode, ResolutionMode};
+use uv_traits::{Concurrency, ConfigSettings, NoBuild, PackageConfigSettings, SetupPyStrategy};
+
+use crate::commands::{pip_install, ExitStatus, SbomFormat, Upgrade};
+use crate::printer::Printer;
+use crate::requirements::{ExtrasSpecification, RequirementsSource};
+
+/// The name of the receipt written to the root of each tool environment.
+const RECEIPT: &str = "uv-receipt.toml";
+
+/// A record of an installed tool, persisted alongside its environment.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct ToolReceipt {
+    /// The requirement from which the tool was installed (e.g., `black>=24`).
+    pub(crate) requirement: String,
+    /// The file names of the executables linked into the tool executable directory.
+    pub(crate) entrypoints: Vec<String>,
+}
+
+impl ToolReceipt {
+    /// Read the receipt from the given tool environment, if it exists.
+    pub(crate) fn read(root: &Path) -> Result<Option<Self>> {
+        let path = root.join(RECEIPT);
+        let contents = match fs_err::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let receipt = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?;
+        Ok(Some(receipt))
+    }
+
+    /// Write the receipt to the given tool environment.
+    pub(crate) fn write(&self, root: &Path) -> Result<()> {
+        uv_fs::write_atomic_sync(root.join(RECEIPT), toml::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+/// Return the directory in which tool environments are stored.
+///
+/// Defaults to the `tools` directory within the user's data directory, and can be overridden via
+/// `UV_TOOL_DIR`.
+pub(crate) fn tool_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os("UV_TOOL_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    ProjectDirs::from("", "", "uv")
+        .map(|dirs| dirs.data_dir().join("tools"))
+        .ok_or_else(|| anyhow!("Could not determine the tool directory; set `UV_TOOL_DIR`"))
+}
+
+/// Return the directory into which tool executables are linked.
+///
+/// Defaults to `~/.local/bin`, and can be overridden via `UV_TOOL_BIN_DIR`.
+pub(crate) fn tool_bin_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os("UV_TOOL_BIN_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    BaseDirs::new()
+        .map(|dirs| dirs.home_dir().join(".local").join("bin"))
+        .ok_or_else(|| {
+            anyhow!("Could not determine the tool executable directory; set `UV_TOOL_BIN_DIR`")
+        })
+}
+
+/// Install (or upgrade) the tool requirement into its environment.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn install_tool_requirement(
+    venv: &PythonEnvironment,
+    requirement: &str,
+    upgrade: Upgrade,
+    index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    exclude_newer: Option<DateTime<Utc>>,
+    native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
+    cache: Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    pip_install(
+        &[RequirementsSource::Package(requirement.to_string())],
+        &[],
+        &[],
+        &[],
+        &ExtrasSpecification::None,
+        ResolutionMode::default(),
+        PreReleaseMode::default(),
+        DependencyMode::Transitive,
+        upgrade,
+        index_locations,
+        IndexStrategy::default(),
+        keyring_provider,
+        trusted_hosts,
+        &Reinstall::None,
+        None,
+        false,
+        SetupPyStrategy::default(),
+        connectivity,
+        &ConfigSettings::default(),
+        &PackageConfigSettings::default(),
+        false,
+        &[],
+        &NoBuild::None,
+        &NoBinary::None,
+        false,
+        false,
+        exclude_newer,
+        FxHashMap::default(),
+        AllowYanked::default(),
+        None,
+        None,
+        None,
+        &[],
+        Some(venv.python_executable().simplified_display().to_string()),
+        false,
+        false,
+        false,
+        None,
+        native_tls,
+        cert,
+        proxy,
+        retries,
+        concurrency,
+        cache,
+        false,
+        false,
+        None,
+        None,
+        SbomFormat::default(),
+        printer,
+    )
+    .await
+}
+
+/// Return the names of the executables provided by the tool package in the given environment.
+pub(crate) fn tool_entrypoints(
+    venv: &PythonEnvironment,
+    name: &PackageName,
+) -> Result<Vec<String>> {
+    let site_packages = SitePackages::from_executable(venv)?;
+    let Some(dist) = site_packages.get_packages(name).into_iter().next() else {
+        return Err(anyhow!(
+            "`{name}` is not installed in: `{}`",
+            venv.root().user_display()
+        ));
+    };
+    Ok(installed_scripts(dist.path(), venv.scripts())?)
+}
+
+/// Link the given executables from the tool environment into the executable directory.
+pub(crate) fn link_entrypoints(
+    venv: &PythonEnvironment,
+    entrypoints: &[String],
+    bin_dir: &Path,
+) -> Result<()> {
+    fs_err::create_dir_all(bin_dir)?;
+    for entrypoint in entrypoints {
+        let source = venv.scripts().join(entrypoint);
+        let target = bin_dir.join(entrypoint);
+        debug!(
+            "Linking `{}` to `{}`",
+            source.user_display(),
+            target.user_display()
+        );
+
+        // On Windows, the launchers embed the path to the environment's interpreter, so they can
+        // be copied; elsewhere, we symlink to the script in the environment.
+        #[cfg(unix)]
+        uv_fs::replace_symlink(&source, &target)?;
+        #[cfg(windows)]
+        fs_err::copy(&source, &target)?;
+    }
+    Ok(())
+}
+
+/// Remove the given executables from the executable directory.
+pub(crate) fn unlink_entrypoints(entrypoints: &[String], bin_dir: &Path) -> Result<()> {
+    for entrypoint in entrypoints {
+        let target = bin_dir.join(entrypoint);
+        match fs_err::remove_file(&target) {
+            Ok(()) => debug!("Removed `{}`", target.user_display()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::IndexLocations;
+use pep508_rs::Requirement;
+use uv_auth::KeyringProvider;
+use uv_cache::Cache;
+use uv_client::{Connectivity, TrustedHost};
+use uv_fs::Simplified;
+use uv_interpreter::{find_default_python, find_requested_python, Error};
+use uv_traits::Concurrency;
+use uv_warnings::warn_user;
+
+use crate::commands::tool::{
+    install_tool_requirement, link_entrypoints, tool_bin_dir, tool_dir, tool_entrypoints,
+    ToolReceipt,
+};
+use crate::commands::{ExitStatus, Upgrade};
+use crate::printer::Printer;
+
+/// Install a tool into an isolated environment, linking its executables onto the `PATH`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn tool_install(
+    package: String,
+    python: Option<&str>,
+    force: bool,
+    index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    exclude_newer: Option<DateTime<Utc>>,
+    native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
+    cache: Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let requirement =
+        Requirement::from_str(&package).with_context(|| format!("Failed to parse `{package}`"))?;
+    let name = requirement.name;
+
+    let root = tool_dir()?.join(name.as_ref());
+    if let Some(receipt) = ToolReceipt::read(&root)? {
+        if !force {
+            writeln!(
+                printer.stderr(),
+                "`{}` is already installed (from `{}`); use `--force` to reinstall",
+                name.cyan(),
+                receipt.requirement
+            )?;
+            return Ok(ExitStatus::Success);
+        }
+    }
+    if root.exists() {
+        debug!("Removing existing environment at: {}", root.user_display());
+        fs_err::remove_dir_all(&root)?;
+    }
+
+    // Locate the base interpreter.
+    let interpreter = if let Some(python) = python {
+        find_requested_python(python, &cache)?
+            .ok_or_else(|| Error::RequestedPythonNotFound(python.to_string()))?
+    } else {
+        find_default_python(&cache)?
+    };
+
+    debug!("Creating environment at: {}", root.user_display());
+    let extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
+    let venv = uv_virtualenv::create_venv(
+        &root,
+        interpreter,
+        uv_virtualenv::Prompt::None,
+        false,
+        false,
+        extra_cfg,
+    )
+    .context("Failed to create environment")?;
+
+    let status = install_tool_requirement(
+        &venv,
+        &package,
+        Upgrade::None,
+        index_locations,
+        keyring_provider,
+        trusted_hosts,
+        connectivity,
+        exclude_newer,
+        native_tls,
+        cert,
+        proxy,
+        retries,
+        concurrency,
+        cache,
+        printer,
+    )
+    .await;
+    if !matches!(status, Ok(ExitStatus::Success)) {
+        // Don't leave a partially-installed tool behind.
+        fs_err::remove_dir_all(&root)?;
+        return status;
+    }
+
+    let entrypoints = tool_entrypoints(&venv, &name)?;
+    if entrypoints.is_empty() {
+        fs_err::remove_dir_all(&root)?;
+        writeln!(
+            printer.stderr(),
+            "`{}` does not provide any executables",
+            name.cyan()
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    let bin_dir = tool_bin_dir()?;
+    link_entrypoints(&venv, &entrypoints, &bin_dir)?;
+
+    ToolReceipt {
+        requirement: package,
+        entrypoints: entrypoints.clone(),
+    }
+    .write(&root)?;
+
+    writeln!(
+        printer.stderr(),
+        "Installed {} for `{}`: {}",
+        if entrypoints.len() == 1 {
+            "1 executable".to_string()
+        } else {
+            format!("{} executables", entrypoints.len())
+        },
+        name.cyan(),
+        entrypoints
+            .iter()
+            .map(|entrypoint| entrypoint.bold())
+            .join(", ")
+    )?;
+
+    // Warn if the executables won't be discoverable.
+    let on_path = std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir == bin_dir));
+    if !on_path {
+        warn_user!(
+            "`{}` is not on your `PATH`; add it to run the installed executables",
+            bin_dir.user_display()
+        );
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,63 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+use uv_warnings::warn_user;
+
+use crate::commands::tool::{tool_dir, ToolReceipt};
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// List the installed tools, along with their executables.
+pub(crate) fn tool_list(cache: &Cache, printer: Printer) -> Result<ExitStatus> {
+    let mut tools = uv_fs::directories(tool_dir()?)
+        .filter_map(|root| {
+            let name = PackageName::from_str(root.file_name()?.to_str()?).ok()?;
+            Some((name, root))
+        })
+        .collect::<Vec<_>>();
+    tools.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut found = false;
+    for (name, root) in tools {
+        let Some(receipt) = ToolReceipt::read(&root)? else {
+            continue;
+        };
+
+        // Determine the installed version from the tool's environment.
+        let venv = match PythonEnvironment::from_root(&root, cache) {
+            Ok(venv) => venv,
+            Err(err) => {
+                warn_user!(
+                    "Ignoring malformed tool environment at `{}`: {err}",
+                    root.user_display()
+                );
+                continue;
+            }
+        };
+        let site_packages = SitePackages::from_executable(&venv)?;
+        let Some(dist) = site_packages.get_packages(&name).into_iter().next() else {
+            warn_user!("`{name}` is missing from its environment; reinstall it with `--force`");
+            continue;
+        };
+
+        found = true;
+        writeln!(printer.stdout(), "{} v{}", name.bold(), dist.version())?;
+        for entrypoint in &receipt.entrypoints {
+            writeln!(printer.stdout(), "- {entrypoint}")?;
+        }
+    }
+
+    if !found {
+        writeln!(printer.stderr(), "No tools installed")?;
+    }
+
+    Ok(ExitStatus::Success)
+}
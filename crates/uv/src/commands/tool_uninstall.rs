@@ -0,0 +1,30 @@
+use std::fmt::Write;
+
+use anyhow::{anyhow, Result};
+use owo_colors::OwoColorize;
+
+use uv_normalize::PackageName;
+
+use crate::commands::tool::{tool_bin_dir, tool_dir, unlink_entrypoints, ToolReceipt};
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Uninstall a tool, removing its environment and executables.
+pub(crate) fn tool_uninstall(name: &PackageName, printer: Printer) -> Result<ExitStatus> {
+    let root = tool_dir()?.join(name.as_ref());
+    let Some(receipt) = ToolReceipt::read(&root)? else {
+        return Err(anyhow!("`{name}` is not installed"));
+    };
+
+    unlink_entrypoints(&receipt.entrypoints, &tool_bin_dir()?)?;
+    fs_err::remove_dir_all(&root)?;
+
+    writeln!(
+        printer.stderr(),
+        "Uninstalled `{}` ({})",
+        name.cyan(),
+        receipt.entrypoints.join(", ")
+    )?;
+
+    Ok(ExitStatus::Success)
+}
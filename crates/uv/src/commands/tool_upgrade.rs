@@ -0,0 +1,91 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use owo_colors::OwoColorize;
+use url::Url;
+
+use distribution_types::IndexLocations;
+use uv_auth::KeyringProvider;
+use uv_cache::Cache;
+use uv_client::{Connectivity, TrustedHost};
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+use uv_traits::Concurrency;
+
+use crate::commands::tool::{
+    install_tool_requirement, link_entrypoints, tool_bin_dir, tool_dir, tool_entrypoints,
+    unlink_entrypoints, ToolReceipt,
+};
+use crate::commands::{ExitStatus, Upgrade};
+use crate::printer::Printer;
+
+/// Upgrade an installed tool to the latest version compatible with its requirement.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn tool_upgrade(
+    name: &PackageName,
+    index_locations: IndexLocations,
+    keyring_provider: KeyringProvider,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    exclude_newer: Option<DateTime<Utc>>,
+    native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
+    cache: Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let root = tool_dir()?.join(name.as_ref());
+    let Some(receipt) = ToolReceipt::read(&root)? else {
+        return Err(anyhow!("`{name}` is not installed"));
+    };
+    let venv = PythonEnvironment::from_root(&root, &cache)?;
+
+    let status = install_tool_requirement(
+        &venv,
+        &receipt.requirement,
+        Upgrade::All,
+        index_locations,
+        keyring_provider,
+        trusted_hosts,
+        connectivity,
+        exclude_newer,
+        native_tls,
+        cert,
+        proxy,
+        retries,
+        concurrency,
+        cache,
+        printer,
+    )
+    .await?;
+    if !matches!(status, ExitStatus::Success) {
+        return Ok(status);
+    }
+
+    // The set of executables may have changed across versions, so re-link them, removing any
+    // that are no longer provided.
+    let bin_dir = tool_bin_dir()?;
+    let entrypoints = tool_entrypoints(&venv, name)?;
+    let removed = receipt
+        .entrypoints
+        .iter()
+        .filter(|entrypoint| !entrypoints.contains(entrypoint))
+        .cloned()
+        .collect::<Vec<_>>();
+    unlink_entrypoints(&removed, &bin_dir)?;
+    link_entrypoints(&venv, &entrypoints, &bin_dir)?;
+
+    ToolReceipt {
+        entrypoints,
+        ..receipt
+    }
+    .write(&root)?;
+
+    writeln!(printer.stderr(), "Upgraded `{}`", name.cyan())?;
+
+    Ok(ExitStatus::Success)
+}
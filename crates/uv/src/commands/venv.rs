@@ -10,6 +10,8 @@ use itertools::Itertools;
 use miette::{Diagnostic, IntoDiagnostic};
 use owo_colors::OwoColorize;
 use thiserror::Error;
+use tracing::debug;
+use url::Url;
 
 use distribution_types::{DistributionMetadata, IndexLocations, Name};
 use pep508_rs::Requirement;
@@ -21,7 +23,9 @@ use uv_fs::Simplified;
 use uv_installer::NoBinary;
 use uv_interpreter::{find_default_python, find_requested_python, Error};
 use uv_resolver::{InMemoryIndex, OptionsBuilder};
-use uv_traits::{BuildContext, BuildIsolation, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{
+    BuildContext, BuildIsolation, Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy,
+};
 
 use crate::commands::ExitStatus;
 use crate::printer::Printer;
@@ -36,10 +40,15 @@ pub(crate) async fn venv(
     keyring_provider: KeyringProvider,
     prompt: uv_virtualenv::Prompt,
     system_site_packages: bool,
+    relocatable: bool,
     connectivity: Connectivity,
     seed: bool,
     exclude_newer: Option<DateTime<Utc>>,
     native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -50,10 +59,15 @@ pub(crate) async fn venv(
         keyring_provider,
         prompt,
         system_site_packages,
+        relocatable,
         connectivity,
         seed,
         exclude_newer,
         native_tls,
+        cert,
+        proxy,
+        retries,
+        concurrency,
         cache,
         printer,
     )
@@ -86,6 +100,41 @@ enum VenvError {
     FlatIndex(#[source] uv_client::FlatIndexError),
 }
 
+/// Enable or disable access to the system `site-packages` for an existing virtual environment.
+pub(crate) fn venv_system_site_packages(
+    path: &Path,
+    system_site_packages: bool,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    uv_virtualenv::set_system_site_packages(path, system_site_packages)?;
+
+    writeln!(
+        printer.stderr(),
+        "{} system site packages for: {}",
+        if system_site_packages {
+            "Enabled"
+        } else {
+            "Disabled"
+        },
+        path.user_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Return the project name declared in the `pyproject.toml` in the current directory, if any, for
+/// use as the virtual environment's prompt.
+pub(crate) fn project_prompt() -> Option<String> {
+    let contents = fs_err::read_to_string("pyproject.toml").ok()?;
+    let pyproject_toml = toml::from_str::<pyproject_toml::PyProjectToml>(&contents).ok()?;
+    let project = pyproject_toml.project?;
+    debug!(
+        "Using project name from `pyproject.toml` as prompt: {}",
+        project.name
+    );
+    Some(project.name)
+}
+
 /// Create a virtual environment.
 #[allow(clippy::too_many_arguments)]
 async fn venv_impl(
@@ -95,10 +144,15 @@ async fn venv_impl(
     keyring_provider: KeyringProvider,
     prompt: uv_virtualenv::Prompt,
     system_site_packages: bool,
+    relocatable: bool,
     connectivity: Connectivity,
     seed: bool,
     exclude_newer: Option<DateTime<Utc>>,
     native_tls: bool,
+    cert: Option<&Path>,
+    proxy: Option<&Url>,
+    retries: u32,
+    concurrency: Concurrency,
     cache: &Cache,
     printer: Printer,
 ) -> miette::Result<ExitStatus> {
@@ -127,13 +181,23 @@ async fn venv_impl(
     )
     .into_diagnostic()?;
 
-    // Extra cfg for pyvenv.cfg to specify uv version
-    let extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
+    // Extra cfg for pyvenv.cfg to specify uv version, and whether seed packages were installed
+    // (so that `pip sync` knows to preserve them).
+    let mut extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
+    if seed {
+        extra_cfg.push(("seed".to_string(), "true".to_string()));
+    }
 
     // Create the virtual environment.
-    let venv =
-        uv_virtualenv::create_venv(path, interpreter, prompt, system_site_packages, extra_cfg)
-            .map_err(VenvError::Creation)?;
+    let venv = uv_virtualenv::create_venv(
+        path,
+        interpreter,
+        prompt,
+        system_site_packages,
+        relocatable,
+        extra_cfg,
+    )
+    .map_err(VenvError::Creation)?;
 
     // Install seed packages.
     if seed {
@@ -148,6 +212,9 @@ async fn venv_impl(
         // Instantiate a client.
         let client = RegistryClientBuilder::new(cache.clone())
             .native_tls(native_tls)
+            .cert(cert)
+            .proxy(proxy)
+            .retries(retries)
             .index_urls(index_locations.index_urls())
             .keyring_provider(keyring_provider)
             .connectivity(connectivity)
@@ -190,6 +257,7 @@ async fn venv_impl(
             &NoBuild::All,
             &NoBinary::None,
         )
+        .with_concurrency(concurrency)
         .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
 
         // Resolve the seed packages.
@@ -245,10 +313,15 @@ async fn venv_impl(
             "source {}",
             shlex_posix(path.join("bin").join("activate.csh"))
         )),
-        Some(Shell::Powershell) => Some(shlex_windows(
+        Some(Shell::Powershell) if cfg!(windows) => Some(shlex_windows(
             path.join("Scripts").join("activate"),
             Shell::Powershell,
         )),
+        // PowerShell Core on Unix doesn't resolve the `.ps1` extension implicitly.
+        Some(Shell::Powershell) => Some(shlex_windows(
+            path.join("bin").join("activate.ps1"),
+            Shell::Powershell,
+        )),
         Some(Shell::Cmd) => Some(shlex_windows(
             path.join("Scripts").join("activate"),
             Shell::Cmd,
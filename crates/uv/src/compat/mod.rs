@@ -10,14 +10,11 @@ pub(crate) trait CompatArgs {
 /// Arguments for `pip-compile` compatibility.
 ///
 /// These represent a subset of the `pip-compile` interface that uv supports by default.
-/// For example, users often pass `--allow-unsafe`, which is unnecessary with uv. But it's a
-/// nice user experience to warn, rather than fail, when users pass `--allow-unsafe`.
+/// For example, users often pass `--no-allow-unsafe`, which is unnecessary with uv. But it's a
+/// nice user experience to warn, rather than fail, when users pass `--no-allow-unsafe`.
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct PipCompileCompatArgs {
-    #[clap(long, hide = true)]
-    allow_unsafe: bool,
-
     #[clap(long, hide = true)]
     no_allow_unsafe: bool,
 
@@ -83,12 +80,6 @@ impl CompatArgs for PipCompileCompatArgs {
     /// behavior. If an argument is passed that does _not_ match uv's behavior (e.g.,
     /// `--no-build-isolation`), this method will return an error.
     fn validate(&self) -> Result<()> {
-        if self.allow_unsafe {
-            warn_user!(
-                "pip-compile's `--allow-unsafe` has no effect (uv can safely pin `pip` and other packages)."
-            );
-        }
-
         if self.no_allow_unsafe {
             warn_user!("pip-compile's `--no-allow-unsafe` has no effect (uv can safely pin `pip` and other packages).");
         }
@@ -0,0 +1,400 @@
+//! Persistent configuration, as read from `uv.toml` files.
+//!
+//! Configuration is layered: a system-level file (e.g., `/etc/uv/uv.toml`), a user-level file
+//! (e.g., `~/.config/uv/uv.toml`), and a project-level file (the nearest `uv.toml` in the current
+//! directory or its ancestors), with later layers taking precedence over earlier ones.
+//!
+//! Each setting acts as a default for the corresponding command-line argument, such that
+//! command-line arguments (and their `UV_*` environment variables) continue to take precedence
+//! over any configuration file.
+
+use std::env;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use configparser::ini::Ini;
+use directories::BaseDirs;
+use serde::{Deserialize, Deserializer};
+use url::Url;
+
+use distribution_types::{FlatIndexLocation, IndexLocations, IndexUrl};
+use install_wheel_rs::linker::LinkMode;
+use uv_auth::KeyringProvider;
+use uv_client::{IndexStrategy, TrustedHost};
+use uv_fs::Simplified;
+use uv_resolver::{PreReleaseMode, ResolutionMode};
+
+use crate::Maybe;
+
+/// The name of the configuration file.
+const CONFIG_FILE: &str = "uv.toml";
+
+/// The settings that can be provided in a `uv.toml` file.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct Options {
+    /// The URL of the Python package index, as in `--index-url`.
+    index_url: Option<String>,
+    /// Extra URLs of package indexes to use, as in `--extra-index-url`.
+    extra_index_url: Option<Vec<String>>,
+    /// Locations to search for candidate distributions, as in `--find-links`.
+    find_links: Option<Vec<String>>,
+    /// Ignore the registry index, as in `--no-index`.
+    no_index: Option<bool>,
+    /// The strategy to use when resolving against multiple indexes, as in `--index-strategy`.
+    #[serde(default, deserialize_with = "value_enum")]
+    index_strategy: Option<IndexStrategy>,
+    /// Attempt to use `keyring` for authentication, as in `--keyring-provider`.
+    #[serde(default, deserialize_with = "value_enum")]
+    keyring_provider: Option<KeyringProvider>,
+    /// Hosts to trust over plain HTTP, or with invalid certificates, as in `--trusted-host`.
+    trusted_host: Option<Vec<String>>,
+    /// The path to the cache directory, as in `--cache-dir`.
+    cache_dir: Option<PathBuf>,
+    /// The strategy to use when selecting between package versions, as in `--resolution`.
+    #[serde(default, deserialize_with = "value_enum")]
+    resolution: Option<ResolutionMode>,
+    /// The strategy to use when considering pre-release versions, as in `--prerelease`.
+    #[serde(default, deserialize_with = "value_enum")]
+    prerelease: Option<PreReleaseMode>,
+    /// The method to use when installing packages from the cache, as in `--link-mode`.
+    #[serde(default, deserialize_with = "value_enum")]
+    link_mode: Option<LinkMode>,
+    /// Compile Python files to bytecode after installation, as in `--compile-bytecode`.
+    compile_bytecode: Option<bool>,
+    /// Load TLS certificates from the platform's native certificate store, as in `--native-tls`.
+    native_tls: Option<bool>,
+    /// Read index settings from `pip.conf` (or `pip.ini` on Windows), for migrating from `pip`.
+    read_pip_config: Option<bool>,
+}
+
+impl Options {
+    /// Read the options from a `uv.toml` file.
+    fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs_err::read_to_string(path)?;
+        let options = toml::from_str::<Self>(&contents)
+            .with_context(|| format!("Failed to parse `{}`", path.user_display()))?;
+
+        // Resolve any relative paths against the directory containing the file.
+        let root = path.parent().unwrap_or(path);
+        Ok(options.relative_to(root))
+    }
+
+    /// Resolve any relative paths in the options against the given directory.
+    fn relative_to(self, root: &Path) -> Self {
+        Self {
+            find_links: self.find_links.map(|find_links| {
+                find_links
+                    .into_iter()
+                    .map(|location| {
+                        if Path::new(&location).is_absolute() || Url::parse(&location).is_ok() {
+                            location
+                        } else {
+                            root.join(location).to_string_lossy().to_string()
+                        }
+                    })
+                    .collect()
+            }),
+            cache_dir: self.cache_dir.map(|cache_dir| root.join(cache_dir)),
+            ..self
+        }
+    }
+
+    /// Combine two sets of options, preferring the values in `self`.
+    fn combine(self, other: Self) -> Self {
+        Self {
+            index_url: self.index_url.or(other.index_url),
+            extra_index_url: self.extra_index_url.or(other.extra_index_url),
+            find_links: self.find_links.or(other.find_links),
+            no_index: self.no_index.or(other.no_index),
+            index_strategy: self.index_strategy.or(other.index_strategy),
+            keyring_provider: self.keyring_provider.or(other.keyring_provider),
+            trusted_host: self.trusted_host.or(other.trusted_host),
+            cache_dir: self.cache_dir.or(other.cache_dir),
+            resolution: self.resolution.or(other.resolution),
+            prerelease: self.prerelease.or(other.prerelease),
+            link_mode: self.link_mode.or(other.link_mode),
+            compile_bytecode: self.compile_bytecode.or(other.compile_bytecode),
+            native_tls: self.native_tls.or(other.native_tls),
+            read_pip_config: self.read_pip_config.or(other.read_pip_config),
+        }
+    }
+
+    /// Load the options from the system, user, and project configuration files, in increasing
+    /// order of precedence, falling back to `pip.conf` if enabled.
+    pub(crate) fn load(cwd: &Path) -> Result<Self> {
+        let mut options = Self::default();
+        for path in [
+            system_config_file(),
+            user_config_file(),
+            project_config_file(cwd),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            options = Self::from_file(&path)?.combine(options);
+        }
+
+        if options.read_pip_config == Some(true) {
+            options = options.combine(read_pip_config()?);
+        }
+
+        Ok(options)
+    }
+
+    /// Resolve the index locations, preferring any provided on the command line.
+    ///
+    /// An index URL provided on the command line (or via the environment) overrides a `no-index`
+    /// setting in the configuration files.
+    pub(crate) fn index_locations(
+        &self,
+        index_url: Option<Maybe<IndexUrl>>,
+        extra_index_url: Vec<Maybe<IndexUrl>>,
+        find_links: Vec<FlatIndexLocation>,
+        no_index: bool,
+    ) -> Result<IndexLocations> {
+        let no_index = no_index
+            || (index_url.is_none()
+                && extra_index_url.is_empty()
+                && self.no_index.unwrap_or(false));
+        let index_url = match index_url {
+            Some(index_url) => index_url.into_option(),
+            None => self
+                .index_url
+                .as_deref()
+                .map(|index_url| parse("index-url", index_url))
+                .transpose()?,
+        };
+        let extra_index_url = if extra_index_url.is_empty() {
+            parse_all("extra-index-url", self.extra_index_url.as_deref())?
+        } else {
+            extra_index_url
+                .into_iter()
+                .filter_map(Maybe::into_option)
+                .collect()
+        };
+        let find_links = if find_links.is_empty() {
+            parse_all("find-links", self.find_links.as_deref())?
+        } else {
+            find_links
+        };
+        Ok(IndexLocations::new(
+            index_url,
+            extra_index_url,
+            find_links,
+            no_index,
+        ))
+    }
+
+    /// Resolve the hosts to trust, preferring any provided on the command line.
+    pub(crate) fn trusted_host(&self, trusted_host: Vec<TrustedHost>) -> Result<Vec<TrustedHost>> {
+        if trusted_host.is_empty() {
+            parse_all("trusted-host", self.trusted_host.as_deref())
+        } else {
+            Ok(trusted_host)
+        }
+    }
+
+    /// Resolve the index strategy, preferring that provided on the command line.
+    pub(crate) fn index_strategy(&self, index_strategy: Option<IndexStrategy>) -> IndexStrategy {
+        index_strategy.or(self.index_strategy).unwrap_or_default()
+    }
+
+    /// Resolve the keyring provider, preferring that provided on the command line.
+    pub(crate) fn keyring_provider(
+        &self,
+        keyring_provider: Option<KeyringProvider>,
+    ) -> KeyringProvider {
+        keyring_provider
+            .or(self.keyring_provider)
+            .unwrap_or_default()
+    }
+
+    /// Resolve the resolution strategy, preferring that provided on the command line.
+    pub(crate) fn resolution(&self, resolution: Option<ResolutionMode>) -> ResolutionMode {
+        resolution.or(self.resolution).unwrap_or_default()
+    }
+
+    /// Resolve the pre-release strategy, preferring that provided on the command line.
+    pub(crate) fn prerelease(&self, prerelease: Option<PreReleaseMode>) -> PreReleaseMode {
+        prerelease.or(self.prerelease).unwrap_or_default()
+    }
+
+    /// Resolve the link mode, preferring that provided on the command line.
+    pub(crate) fn link_mode(&self, link_mode: Option<LinkMode>) -> Option<LinkMode> {
+        link_mode.or(self.link_mode)
+    }
+
+    /// Whether to compile Python files to bytecode, if enabled on the command line or in the
+    /// configuration files.
+    pub(crate) fn compile_bytecode(&self, compile_bytecode: bool) -> bool {
+        compile_bytecode || self.compile_bytecode.unwrap_or(false)
+    }
+
+    /// Whether to use the platform's native certificate store, if enabled on the command line or
+    /// in the configuration files.
+    pub(crate) fn native_tls(&self, native_tls: bool) -> bool {
+        native_tls || self.native_tls.unwrap_or(false)
+    }
+
+    /// The configured cache directory, if any.
+    pub(crate) fn cache_dir(&self) -> Option<PathBuf> {
+        self.cache_dir.clone()
+    }
+}
+
+/// Parse a configured value (e.g., `index-url`), as it would be parsed on the command line.
+fn parse<T>(setting: &str, value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    T::from_str(value).map_err(|err| anyhow!("Invalid `{setting}` setting `{value}`: {err}"))
+}
+
+/// Parse a list of configured values (e.g., `find-links`), as they would be parsed on the command
+/// line.
+fn parse_all<T>(setting: &str, values: Option<&[String]>) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    values
+        .unwrap_or_default()
+        .iter()
+        .map(|value| parse(setting, value))
+        .collect()
+}
+
+/// Deserialize a [`ValueEnum`] from its command-line name (e.g., `lowest-direct`).
+fn value_enum<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: ValueEnum,
+{
+    let value = String::deserialize(deserializer)?;
+    T::from_str(&value, false)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+/// The system-level `uv.toml` (e.g., `/etc/uv/uv.toml`).
+fn system_config_file() -> Option<PathBuf> {
+    let path = if cfg!(windows) {
+        PathBuf::from(env::var_os("PROGRAMDATA")?)
+            .join("uv")
+            .join(CONFIG_FILE)
+    } else {
+        PathBuf::from("/etc/uv").join(CONFIG_FILE)
+    };
+    path.is_file().then_some(path)
+}
+
+/// The user-level `uv.toml`: `$XDG_CONFIG_HOME/uv/uv.toml` (or `~/.config/uv/uv.toml`) on all
+/// platforms but Windows, where it's `%APPDATA%\uv\uv.toml`.
+fn user_config_file() -> Option<PathBuf> {
+    let config_dir = if cfg!(windows) {
+        PathBuf::from(env::var_os("APPDATA")?)
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|config_dir| !config_dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| BaseDirs::new().map(|base_dirs| base_dirs.home_dir().join(".config")))?
+    };
+    let path = config_dir.join("uv").join(CONFIG_FILE);
+    path.is_file().then_some(path)
+}
+
+/// The project-level `uv.toml`: the nearest `uv.toml` in the given directory or its ancestors.
+fn project_config_file(cwd: &Path) -> Option<PathBuf> {
+    cwd.ancestors()
+        .map(|ancestor| ancestor.join(CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+/// The `pip` configuration files, in increasing order of precedence.
+///
+/// See: <https://pip.pypa.io/en/stable/topics/configuration/#location>
+fn pip_config_files() -> Vec<PathBuf> {
+    let name = if cfg!(windows) { "pip.ini" } else { "pip.conf" };
+    let mut paths = Vec::new();
+
+    // Global.
+    if cfg!(windows) {
+        if let Some(program_data) = env::var_os("PROGRAMDATA") {
+            paths.push(PathBuf::from(program_data).join("pip").join(name));
+        }
+    } else {
+        paths.push(PathBuf::from("/etc/xdg/pip").join(name));
+        paths.push(PathBuf::from("/etc").join(name));
+    }
+
+    // User, including the legacy per-user location.
+    if let Some(base_dirs) = BaseDirs::new() {
+        if !cfg!(windows) {
+            paths.push(base_dirs.home_dir().join(".pip").join(name));
+        }
+        paths.push(base_dirs.config_dir().join("pip").join(name));
+    }
+
+    // Site, i.e., the active virtual environment.
+    if let Some(venv) = env::var_os("VIRTUAL_ENV") {
+        paths.push(PathBuf::from(venv).join(name));
+    }
+
+    // An explicit configuration file.
+    if let Some(path) = env::var_os("PIP_CONFIG_FILE") {
+        paths.push(PathBuf::from(path));
+    }
+
+    paths
+}
+
+/// Read the index settings from any `pip` configuration files.
+///
+/// Only the settings that have a `uv` equivalent are respected; within each file, the `[install]`
+/// section takes precedence over `[global]`.
+fn read_pip_config() -> Result<Options> {
+    let mut options = Options::default();
+    for path in pip_config_files() {
+        if !path.is_file() {
+            continue;
+        }
+
+        let contents = fs_err::read_to_string(&path)?;
+        let mut ini = Ini::new();
+        ini.set_multiline(true);
+        ini.read(contents)
+            .map_err(anyhow::Error::msg)
+            .with_context(|| format!("Failed to parse `{}`", path.user_display()))?;
+
+        for section in ["global", "install"] {
+            let get = |key: &str| ini.get(section, key);
+            let list = |key: &str| {
+                get(key).map(|value| value.split_whitespace().map(ToString::to_string).collect())
+            };
+            options = Options {
+                index_url: get("index-url"),
+                extra_index_url: list("extra-index-url"),
+                find_links: list("find-links"),
+                no_index: get("no-index").map(|value| is_truthy(&value)),
+                trusted_host: list("trusted-host"),
+                cache_dir: get("cache-dir").map(PathBuf::from),
+                ..Options::default()
+            }
+            .combine(options);
+        }
+    }
+    Ok(options)
+}
+
+/// Returns `true` if a `pip` configuration value represents a boolean `true`.
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
@@ -1,6 +1,37 @@
+use std::sync::OnceLock;
+
 use anyhow::Result;
 use console::{style, Key, Term};
 
+/// How to resolve prompts when interactive input is disabled, if it is.
+static NO_INPUT: OnceLock<NoInput> = OnceLock::new();
+
+/// How to resolve prompts when interactive input is disabled (e.g., via `--no-input`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum NoInput {
+    /// Exit with an error that includes the suggested correction.
+    #[default]
+    #[value(alias = "1", alias = "true")]
+    Error,
+    /// Apply the suggested correction automatically.
+    Accept,
+    /// Prompt as usual, if the terminal is interactive (e.g., to unset `UV_NO_INPUT`).
+    #[value(alias = "0", alias = "false")]
+    Interactive,
+}
+
+impl NoInput {
+    /// Disable interactive input for all subsequent prompts.
+    pub(crate) fn set_global(self) {
+        NO_INPUT.set(self).ok();
+    }
+
+    /// Returns the configured behavior if interactive input is disabled.
+    pub(crate) fn get() -> Option<Self> {
+        NO_INPUT.get().copied()
+    }
+}
+
 /// Prompt the user for confirmation in the given [`Term`].
 ///
 /// This is a slimmed-down version of `dialoguer::Confirm`, with the post-confirmation report
@@ -0,0 +1,289 @@
+//! Reporting of fatal errors, either as human-readable text or as structured JSON diagnostics.
+//!
+//! Each diagnostic carries a stable [`ErrorCode`], such that wrapping tools can branch on the
+//! cause of a failure without parsing error messages.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anstream::{eprint, eprintln};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use uv_client::ErrorKind;
+use uv_resolver::{NoSolutionError, ResolveError};
+
+use crate::commands::HashVerificationError;
+use crate::printer::Printer;
+
+/// Whether fatal errors should be reported as JSON diagnostics.
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// The format in which to report fatal errors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum ErrorFormat {
+    /// Report errors as human-readable text.
+    #[default]
+    Text,
+    /// Report errors as a single JSON object on stderr, including a stable error code.
+    Json,
+}
+
+impl ErrorFormat {
+    /// Use this format for all subsequent error reports.
+    pub(crate) fn set_global(self) {
+        JSON.store(self == Self::Json, Ordering::SeqCst);
+    }
+}
+
+/// A stable code identifying the cause of a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(crate) enum ErrorCode {
+    /// The requirements are unsatisfiable.
+    #[serde(rename = "UV_RESOLVE_CONFLICT")]
+    ResolveConflict,
+    /// No distribution matches a requirement.
+    #[serde(rename = "UV_NO_MATCHING_DISTRIBUTION")]
+    NoMatchingDistribution,
+    /// A package doesn't exist in the registry.
+    #[serde(rename = "UV_PACKAGE_NOT_FOUND")]
+    PackageNotFound,
+    /// A package is required, but isn't available without network access.
+    #[serde(rename = "UV_OFFLINE")]
+    Offline,
+    /// The requirements contain conflicting or disallowed URLs.
+    #[serde(rename = "UV_CONFLICTING_URLS")]
+    ConflictingUrls,
+    /// A distribution failed to download.
+    #[serde(rename = "UV_DOWNLOAD_FAILED")]
+    DownloadFailed,
+    /// A source distribution failed to build.
+    #[serde(rename = "UV_BUILD_FAILED")]
+    BuildFailed,
+    /// A distribution must be built from source (or installed from a wheel), but that's disabled
+    /// (e.g., via `--no-build` or `--no-binary`).
+    #[serde(rename = "UV_BUILD_DISABLED")]
+    BuildDisabled,
+    /// A distribution (or its metadata) is invalid.
+    #[serde(rename = "UV_INVALID_DISTRIBUTION")]
+    InvalidDistribution,
+    /// A distribution failed to install.
+    #[serde(rename = "UV_INSTALL_FAILED")]
+    InstallFailed,
+    /// A distribution doesn't match the expected hash.
+    #[serde(rename = "UV_HASH_MISMATCH")]
+    HashMismatch,
+    /// A distribution is missing a hash in hash-checking mode.
+    #[serde(rename = "UV_HASH_REQUIRED")]
+    HashRequired,
+    /// A network request failed.
+    #[serde(rename = "UV_NETWORK")]
+    Network,
+    /// A requirement or requirements file couldn't be parsed.
+    #[serde(rename = "UV_INVALID_REQUIREMENT")]
+    InvalidRequirement,
+    /// A `pyproject.toml` or `uv.toml` file couldn't be parsed.
+    #[serde(rename = "UV_INVALID_CONFIGURATION")]
+    InvalidConfiguration,
+    /// A `uv.lock` file couldn't be parsed, or doesn't apply to the current environment.
+    #[serde(rename = "UV_INVALID_LOCKFILE")]
+    InvalidLockfile,
+    /// No suitable Python interpreter or virtual environment was found.
+    #[serde(rename = "UV_PYTHON_NOT_FOUND")]
+    PythonNotFound,
+    /// A filesystem operation failed.
+    #[serde(rename = "UV_IO")]
+    Io,
+    /// Any other failure.
+    #[serde(rename = "UV_UNKNOWN")]
+    Unknown,
+}
+
+impl ErrorCode {
+    /// Classify an error by its most specific known cause.
+    ///
+    /// Errors that merely wrap a failed download or build (e.g., "Failed to download and build")
+    /// are only used if none of their causes is of a known type.
+    pub(crate) fn from_error(err: &anyhow::Error) -> Self {
+        err.chain()
+            .find_map(Self::from_cause)
+            .or_else(|| err.chain().find_map(Self::from_wrapper))
+            .unwrap_or(Self::Unknown)
+    }
+
+    /// Classify a single error in a chain, if it wraps a failed download or build.
+    fn from_wrapper(err: &(dyn std::error::Error + 'static)) -> Option<Self> {
+        match err.downcast_ref::<ResolveError>()? {
+            ResolveError::Fetch(..) | ResolveError::Read(..) => Some(Self::DownloadFailed),
+            ResolveError::FetchAndBuild(..) | ResolveError::Build(..) => Some(Self::BuildFailed),
+            _ => None,
+        }
+    }
+
+    /// Classify a single error in a chain, if it's of a known type.
+    fn from_cause(err: &(dyn std::error::Error + 'static)) -> Option<Self> {
+        if let Some(err) = err.downcast_ref::<ResolveError>() {
+            return match err {
+                ResolveError::NoSolution(_) => Some(Self::ResolveConflict),
+                ResolveError::NotFound(_) => Some(Self::NoMatchingDistribution),
+                ResolveError::Offline(_) => Some(Self::Offline),
+                ResolveError::ConflictingUrlsDirect(..)
+                | ResolveError::ConflictingUrlsTransitive(..)
+                | ResolveError::DisallowedUrl(..)
+                | ResolveError::ConflictingEditables(..) => Some(Self::ConflictingUrls),
+                // Defer to the underlying cause, if any (see `from_wrapper`).
+                _ => None,
+            };
+        }
+        if err.is::<NoSolutionError>() {
+            return Some(Self::ResolveConflict);
+        }
+        if let Some(err) = err.downcast_ref::<HashVerificationError>() {
+            return Some(match err {
+                HashVerificationError::Mismatch { .. } => Self::HashMismatch,
+                HashVerificationError::Missing(_) | HashVerificationError::Unhashable(_) => {
+                    Self::HashRequired
+                }
+            });
+        }
+        if let Some(err) = err.downcast_ref::<uv_distribution::Error>() {
+            return match err {
+                uv_distribution::Error::HashMismatch { .. } => Some(Self::HashMismatch),
+                uv_distribution::Error::UnverifiedSourceDist(_)
+                | uv_distribution::Error::UnverifiedWheel(_) => Some(Self::HashRequired),
+                uv_distribution::Error::Build(..) | uv_distribution::Error::BuildEditable(..) => {
+                    Some(Self::BuildFailed)
+                }
+                uv_distribution::Error::NoBuild | uv_distribution::Error::NoBinary => {
+                    Some(Self::BuildDisabled)
+                }
+                uv_distribution::Error::Git(_) | uv_distribution::Error::Reqwest(_) => {
+                    Some(Self::DownloadFailed)
+                }
+                uv_distribution::Error::WheelFilename(_)
+                | uv_distribution::Error::NameMismatch { .. }
+                | uv_distribution::Error::Metadata(_)
+                | uv_distribution::Error::DistInfo(_)
+                | uv_distribution::Error::Zip(_)
+                | uv_distribution::Error::Extract(_)
+                | uv_distribution::Error::DirWithoutEntrypoint
+                | uv_distribution::Error::MissingPkgInfo
+                | uv_distribution::Error::DynamicPkgInfo(_) => Some(Self::InvalidDistribution),
+                _ => None,
+            };
+        }
+        if let Some(err) = err.downcast_ref::<uv_client::Error>() {
+            return match err.kind() {
+                ErrorKind::PackageNotFound(_) => Some(Self::PackageNotFound),
+                ErrorKind::NoIndex(_) => Some(Self::Offline),
+                ErrorKind::FileNotFound(..)
+                | ErrorKind::ReqwestError(_)
+                | ErrorKind::ReqwestMiddlewareError(_)
+                | ErrorKind::AsyncHttpRangeReader(_)
+                | ErrorKind::BadJson { .. }
+                | ErrorKind::BadHtml { .. } => Some(Self::Network),
+                ErrorKind::MetadataParseError(..)
+                | ErrorKind::MetadataNotFound(..)
+                | ErrorKind::InvalidDistInfo(..)
+                | ErrorKind::WheelFilename(_)
+                | ErrorKind::NameMismatch { .. }
+                | ErrorKind::Zip(..) => Some(Self::InvalidDistribution),
+                _ => None,
+            };
+        }
+        if err.is::<install_wheel_rs::Error>() {
+            return Some(Self::InstallFailed);
+        }
+        if err.is::<pep508_rs::Pep508Error>()
+            || err.is::<requirements_txt::RequirementsTxtFileError>()
+            || err.is::<requirements_txt::RequirementsTxtParserError>()
+        {
+            return Some(Self::InvalidRequirement);
+        }
+        if err.is::<uv_resolver::LockError>() {
+            return Some(Self::InvalidLockfile);
+        }
+        if err.is::<toml::de::Error>() {
+            return Some(Self::InvalidConfiguration);
+        }
+        if let Some(err) = err.downcast_ref::<uv_interpreter::Error>() {
+            return match err {
+                uv_interpreter::Error::PythonNotFound
+                | uv_interpreter::Error::VenvNotFound
+                | uv_interpreter::Error::RequestedPythonNotFound(_)
+                | uv_interpreter::Error::NoSuchPython(_) => Some(Self::PythonNotFound),
+                _ => None,
+            };
+        }
+        if err.is::<std::io::Error>() {
+            return Some(Self::Io);
+        }
+        None
+    }
+}
+
+/// A fatal error, as reported with `--error-format json`.
+#[derive(Debug, Serialize)]
+struct Diagnostic<'a> {
+    code: ErrorCode,
+    message: String,
+    causes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<&'a str>,
+    /// The warnings that were raised before the failure, which are otherwise suppressed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
+}
+
+impl Diagnostic<'_> {
+    fn report(&self) {
+        // Serialization can't fail, as the diagnostic only contains strings.
+        let json = serde_json::to_string(self).unwrap_or_default();
+        eprintln!("{json}");
+    }
+}
+
+/// Report a fatal error that aborted the command.
+pub(crate) fn report_error(err: &anyhow::Error) {
+    if JSON.load(Ordering::SeqCst) {
+        let mut causes = err.chain().map(ToString::to_string);
+        Diagnostic {
+            code: ErrorCode::from_error(err),
+            message: causes.next().unwrap_or_default(),
+            causes: causes.collect(),
+            context: None,
+            warnings: uv_warnings::take(),
+        }
+        .report();
+    } else {
+        let mut causes = err.chain();
+        eprintln!("{}: {}", "error".red().bold(), causes.next().unwrap());
+        for err in causes {
+            eprintln!("  {}: {}", "Caused by".red().bold(), err);
+        }
+    }
+}
+
+/// Report that no solution was found when resolving dependencies.
+///
+/// With `--verbose`, the report is followed by the full derivation tree, showing every
+/// incompatibility from which the conclusion was derived.
+///
+/// Unlike [`report_error`], the command continues and exits with a failure status.
+pub(crate) fn report_no_solution(err: &NoSolutionError, context: &str, printer: Printer) {
+    if JSON.load(Ordering::SeqCst) {
+        Diagnostic {
+            code: ErrorCode::ResolveConflict,
+            message: err.to_string(),
+            causes: vec![],
+            context: Some(context),
+            warnings: uv_warnings::take(),
+        }
+        .report();
+    } else {
+        let report = miette::Report::msg(format!("{err}")).context(context.to_string());
+        eprint!("{report:?}");
+        if printer == Printer::Verbose {
+            eprint!("\nDerivation tree:\n{}", err.derivation_tree());
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use anstream::ColorChoice;
 use std::fmt;
+use std::path::Path;
 use std::str::FromStr;
+use std::sync::Mutex;
 
 use anyhow::Context;
 use chrono::Utc;
@@ -12,7 +14,7 @@ use tracing_durations_export::{
     plot::PlotConfig, DurationsLayer, DurationsLayerBuilder, DurationsLayerDropGuard,
 };
 use tracing_subscriber::filter::Directive;
-use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::format::{FmtSpan, Writer};
 use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::registry::LookupSpan;
@@ -32,6 +34,16 @@ pub(crate) enum Level {
     ExtraVerbose,
 }
 
+/// The format of the log file written with `--log-file`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum LogFormat {
+    /// Write human-readable log lines, including the timestamp, level, and span of each message.
+    #[default]
+    Text,
+    /// Write newline-delimited JSON objects, one per message.
+    Json,
+}
+
 struct UvFormat {
     display_timestamp: bool,
     display_level: bool,
@@ -107,15 +119,80 @@ where
     }
 }
 
+/// The targets whose `TRACE` messages are written to the log file: uv's crates (which all share
+/// the `uv` prefix), the workspace crates that don't, and the HTTP client.
+const FILE_TARGETS: &[&str] = &[
+    "uv",
+    "cache_key",
+    "distribution_filename",
+    "distribution_types",
+    "install_wheel_rs",
+    "once_map",
+    "pep440_rs",
+    "pep508_rs",
+    "platform_tags",
+    "pypi_types",
+    "requirements_txt",
+    "reqwest",
+];
+
+/// Create a layer that writes the full `tracing` output to the given file, regardless of the
+/// console verbosity.
+///
+/// Includes all `TRACE` messages from uv's crates (such as resolver decisions and HTTP requests),
+/// along with the duration of each span as it closes.
+fn file_layer<S>(
+    log_file: Option<(&Path, LogFormat)>,
+) -> anyhow::Result<Option<Box<dyn Layer<S> + Send + Sync>>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let Some((path, format)) = log_file else {
+        return Ok(None);
+    };
+
+    if let Some(parent) = path.parent() {
+        fs_err::create_dir_all(parent)?;
+    }
+    let file = fs_err::File::create(path)?;
+    let filter = EnvFilter::new(
+        FILE_TARGETS
+            .iter()
+            .map(|target| format!("{target}=trace"))
+            .collect::<Vec<_>>()
+            .join(","),
+    );
+
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .with_span_events(FmtSpan::CLOSE);
+    Ok(Some(match format {
+        LogFormat::Text => layer
+            .event_format(UvFormat {
+                display_timestamp: true,
+                display_level: true,
+                show_spans: true,
+            })
+            .with_filter(filter)
+            .boxed(),
+        LogFormat::Json => layer.json().with_filter(filter).boxed(),
+    }))
+}
+
 /// Configure `tracing` based on the given [`Level`], taking into account the `RUST_LOG` environment
 /// variable.
 ///
 /// The [`Level`] is used to dictate the default filters (which can be overridden by the `RUST_LOG`
 /// environment variable) along with the formatting of the output. For example, [`Level::Verbose`]
 /// includes targets and timestamps, along with all `uv=debug` messages by default.
+///
+/// If a log file is provided, the full `tracing` output is additionally written to it, independent
+/// of the console filters.
 pub(crate) fn setup_logging(
     level: Level,
     duration: impl Layer<Registry> + Send + Sync,
+    log_file: Option<(&Path, LogFormat)>,
 ) -> anyhow::Result<()> {
     let default_directive = match level {
         Level::Default => {
@@ -128,6 +205,8 @@ pub(crate) fn setup_logging(
         }
     };
 
+    // The console filter applies to the console output alone, such that the log file (if any)
+    // captures the full output.
     let filter = EnvFilter::builder()
         .with_default_directive(default_directive)
         .from_env()
@@ -149,26 +228,28 @@ pub(crate) fn setup_logging(
             };
             tracing_subscriber::registry()
                 .with(duration)
-                .with(filter)
                 .with(
                     tracing_subscriber::fmt::layer()
                         .event_format(format)
                         .with_writer(std::io::stderr)
-                        .with_ansi(ansi),
+                        .with_ansi(ansi)
+                        .with_filter(filter),
                 )
+                .with(file_layer(log_file)?)
                 .init();
         }
         Level::ExtraVerbose => {
             // Regardless of the tracing level, include the uptime and target for each message.
             tracing_subscriber::registry()
                 .with(duration)
-                .with(filter)
                 .with(
                     HierarchicalLayer::default()
                         .with_targets(true)
                         .with_timer(Uptime::default())
-                        .with_writer(std::io::stderr),
+                        .with_writer(std::io::stderr)
+                        .with_filter(filter),
                 )
+                .with(file_layer(log_file)?)
                 .init();
         }
     }
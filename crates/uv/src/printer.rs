@@ -1,5 +1,6 @@
-use anstream::{eprint, print};
+use anstream::{eprint, eprintln, print};
 use indicatif::ProgressDrawTarget;
+use serde::Serialize;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Printer {
@@ -9,6 +10,12 @@ pub(crate) enum Printer {
     Quiet,
     /// A printer that prints all output, including debug messages.
     Verbose,
+    /// A printer that reports progress as newline-delimited JSON events on stderr, in lieu of
+    /// progress bars and other human-readable output.
+    Json,
+    /// A printer that writes to stdout, but suppresses human-readable output on stderr (e.g.,
+    /// progress bars and summaries), such that stderr is reserved for a structured error report.
+    NoStderr,
 }
 
 impl Printer {
@@ -20,6 +27,8 @@ impl Printer {
             // Confusingly, hide the progress bar when in verbose mode.
             // Otherwise, it gets interleaved with debug messages.
             Self::Verbose => ProgressDrawTarget::hidden(),
+            Self::Json => ProgressDrawTarget::hidden(),
+            Self::NoStderr => ProgressDrawTarget::hidden(),
         }
     }
 
@@ -29,6 +38,8 @@ impl Printer {
             Self::Default => Stdout::Enabled,
             Self::Quiet => Stdout::Disabled,
             Self::Verbose => Stdout::Enabled,
+            Self::Json => Stdout::Enabled,
+            Self::NoStderr => Stdout::Enabled,
         }
     }
 
@@ -38,6 +49,19 @@ impl Printer {
             Self::Default => Stderr::Enabled,
             Self::Quiet => Stderr::Disabled,
             Self::Verbose => Stderr::Enabled,
+            Self::Json => Stderr::Disabled,
+            Self::NoStderr => Stderr::Disabled,
+        }
+    }
+
+    /// Return the [`Events`] sink for this printer.
+    pub(crate) fn events(self) -> Events {
+        match self {
+            Self::Default => Events::Disabled,
+            Self::Quiet => Events::Disabled,
+            Self::Verbose => Events::Disabled,
+            Self::Json => Events::Enabled,
+            Self::NoStderr => Events::Disabled,
         }
     }
 }
@@ -85,3 +109,27 @@ impl std::fmt::Write for Stderr {
         Ok(())
     }
 }
+
+/// A sink for machine-readable progress events, written to stderr as newline-delimited JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Events {
+    Enabled,
+    Disabled,
+}
+
+impl Events {
+    /// Emit an event, if enabled.
+    pub(crate) fn emit(self, event: &impl Serialize) {
+        match self {
+            Self::Enabled => {
+                if let Ok(line) = serde_json::to_string(event) {
+                    #[allow(clippy::print_stderr, clippy::ignored_unit_patterns)]
+                    {
+                        eprintln!("{line}");
+                    }
+                }
+            }
+            Self::Disabled => {}
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+use toml_edit::{Array, DocumentMut, Item, RawString, Table, TomlError, Value};
+
+use pep508_rs::{Pep508Error, Requirement};
+use uv_normalize::{ExtraName, PackageName};
+
+/// The table of a `pyproject.toml` that holds a dependency.
+#[derive(Debug, Clone)]
+pub(crate) enum DependencyTarget {
+    /// `project.dependencies`
+    Project,
+    /// `project.optional-dependencies.<extra>`
+    Optional(ExtraName),
+    /// `dependency-groups.<group>`, as specified in PEP 735.
+    Group(String),
+}
+
+impl DependencyTarget {
+    /// Determine the target from the `--optional` and `--group` command-line arguments.
+    pub(crate) fn from_args(optional: Option<ExtraName>, group: Option<String>) -> Self {
+        match (optional, group) {
+            (Some(extra), _) => Self::Optional(extra),
+            (None, Some(group)) => Self::Group(group),
+            (None, None) => Self::Project,
+        }
+    }
+}
+
+impl fmt::Display for DependencyTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Project => write!(f, "`project.dependencies`"),
+            Self::Optional(extra) => write!(f, "`project.optional-dependencies.{extra}`"),
+            Self::Group(group) => write!(f, "`dependency-groups.{group}`"),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    #[error("Failed to parse `pyproject.toml`")]
+    Parse(#[from] Box<TomlError>),
+    #[error("Failed to parse dependency in `pyproject.toml`: `{0}`")]
+    Requirement(String, #[source] Box<Pep508Error>),
+    #[error("Expected {0} to be an array")]
+    MalformedDependencies(DependencyTarget),
+    #[error("Expected `{0}` to be a table")]
+    MalformedTable(&'static str),
+    #[error("`pyproject.toml` does not contain a `[project]` table")]
+    MissingProject,
+}
+
+/// A `pyproject.toml` that can be edited in-place, preserving its formatting and comments.
+pub(crate) struct PyProjectTomlMut {
+    doc: DocumentMut,
+}
+
+impl PyProjectTomlMut {
+    /// Parse a `pyproject.toml` from its contents.
+    pub(crate) fn from_toml(contents: &str) -> Result<Self, Error> {
+        Ok(Self {
+            doc: contents.parse().map_err(Box::new)?,
+        })
+    }
+
+    /// Add a dependency to the given target, replacing any existing entry for the same package.
+    pub(crate) fn add_dependency(
+        &mut self,
+        requirement: &Requirement,
+        target: &DependencyTarget,
+    ) -> Result<(), Error> {
+        let dependencies = self.dependencies_mut(target, true)?.expect("created");
+        let mut formatted = Value::from(requirement.to_string());
+
+        // Replace the existing entry in-place, retaining its surrounding whitespace and comments.
+        let mut existing = None;
+        for (index, value) in dependencies.iter().enumerate() {
+            if parse_requirement(value)?.is_some_and(|entry| entry.name == requirement.name) {
+                existing = Some(index);
+                break;
+            }
+        }
+        if let Some(index) = existing {
+            *formatted.decor_mut() = dependencies.get(index).expect("in bounds").decor().clone();
+            dependencies.replace(index, formatted);
+            return Ok(());
+        }
+
+        // Otherwise, append the entry, matching the layout of the existing array.
+        let multiline = dependencies.iter().any(|value| {
+            value
+                .decor()
+                .prefix()
+                .and_then(RawString::as_str)
+                .is_some_and(|prefix| prefix.contains('\n'))
+        });
+        dependencies.push(formatted);
+        if multiline {
+            let last = dependencies.len() - 1;
+            dependencies
+                .get_mut(last)
+                .expect("in bounds")
+                .decor_mut()
+                .set_prefix("\n    ");
+            dependencies.set_trailing_comma(true);
+            dependencies.set_trailing("\n");
+        }
+        Ok(())
+    }
+
+    /// Remove all entries for the given package from the given target, returning the removed
+    /// requirements.
+    pub(crate) fn remove_dependency(
+        &mut self,
+        name: &PackageName,
+        target: &DependencyTarget,
+    ) -> Result<Vec<Requirement>, Error> {
+        let Some(dependencies) = self.dependencies_mut(target, false)? else {
+            return Ok(Vec::new());
+        };
+
+        let mut removed = Vec::new();
+        let mut index = 0;
+        while index < dependencies.len() {
+            match parse_requirement(dependencies.get(index).expect("in bounds"))? {
+                Some(requirement) if requirement.name == *name => {
+                    dependencies.remove(index);
+                    removed.push(requirement);
+                }
+                _ => index += 1,
+            }
+        }
+
+        // Avoid leaving a dangling trailing comma in a single-line array.
+        if dependencies.is_empty() {
+            dependencies.set_trailing_comma(false);
+            dependencies.set_trailing("");
+        }
+
+        Ok(removed)
+    }
+
+    /// Return the dependency array for the given target, creating it if `create` is set.
+    fn dependencies_mut(
+        &mut self,
+        target: &DependencyTarget,
+        create: bool,
+    ) -> Result<Option<&mut Array>, Error> {
+        let (table, key) = match target {
+            DependencyTarget::Project => {
+                let project = self
+                    .doc
+                    .get_mut("project")
+                    .ok_or(Error::MissingProject)?
+                    .as_table_like_mut()
+                    .ok_or(Error::MalformedTable("project"))?;
+                (project, "dependencies")
+            }
+            DependencyTarget::Optional(extra) => {
+                let project = self
+                    .doc
+                    .get_mut("project")
+                    .ok_or(Error::MissingProject)?
+                    .as_table_like_mut()
+                    .ok_or(Error::MalformedTable("project"))?;
+                if project.get("optional-dependencies").is_none() {
+                    if !create {
+                        return Ok(None);
+                    }
+                    project.insert("optional-dependencies", Item::Table(Table::new()));
+                }
+                let optional = project
+                    .get_mut("optional-dependencies")
+                    .expect("inserted")
+                    .as_table_like_mut()
+                    .ok_or(Error::MalformedTable("project.optional-dependencies"))?;
+                (optional, extra.as_ref())
+            }
+            DependencyTarget::Group(group) => {
+                if self.doc.get("dependency-groups").is_none() {
+                    if !create {
+                        return Ok(None);
+                    }
+                    self.doc
+                        .insert("dependency-groups", Item::Table(Table::new()));
+                }
+                let groups = self
+                    .doc
+                    .get_mut("dependency-groups")
+                    .expect("inserted")
+                    .as_table_like_mut()
+                    .ok_or(Error::MalformedTable("dependency-groups"))?;
+                (groups, group.as_str())
+            }
+        };
+
+        if table.get(key).is_none() {
+            if !create {
+                return Ok(None);
+            }
+            table.insert(key, Item::Value(Value::Array(Array::new())));
+        }
+        table
+            .get_mut(key)
+            .expect("inserted")
+            .as_array_mut()
+            .map(Some)
+            .ok_or_else(|| Error::MalformedDependencies(target.clone()))
+    }
+}
+
+impl fmt::Display for PyProjectTomlMut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.doc)
+    }
+}
+
+/// Parse a dependency entry, returning `None` for non-string entries (e.g., PEP 735 group
+/// includes).
+fn parse_requirement(value: &Value) -> Result<Option<Requirement>, Error> {
+    let Some(requirement) = value.as_str() else {
+        return Ok(None);
+    };
+    Requirement::from_str(requirement)
+        .map(Some)
+        .map_err(|err| Error::Requirement(requirement.to_string(), Box::new(err)))
+}
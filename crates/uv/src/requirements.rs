@@ -1,5 +1,8 @@
 //! A standard interface for working with heterogeneous sources of requirements.
 
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
@@ -7,27 +10,40 @@ use anyhow::{Context, Result};
 use configparser::ini::Ini;
 use console::Term;
 use distribution_filename::{SourceDistFilename, WheelFilename};
+use futures::future::try_join_all;
+use futures::{try_join, TryStreamExt};
 use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde::Deserialize;
+use tokio::io::AsyncBufReadExt;
+use tokio_util::compat::FuturesAsyncReadCompatExt;
 use tracing::{debug, instrument, Level};
+use url::Url;
 
 use distribution_types::{FlatIndexLocation, IndexUrl, RemoteSource};
+use pep440_rs::{Operator, Version, VersionSpecifier, VersionSpecifiers};
 use pep508_rs::{
-    Requirement, RequirementsTxtRequirement, Scheme, UnnamedRequirement, VersionOrUrl,
+    MarkerEnvironment, Requirement, RequirementsTxtRequirement, Scheme, UnnamedRequirement,
+    VerbatimUrl, VersionOrUrl,
 };
 use pypi_types::Metadata10;
-use requirements_txt::{EditableRequirement, FindLink, RequirementsTxt};
-use uv_client::Connectivity;
+use requirements_txt::{
+    parse_via_annotations, EditableRequirement, FindLink, RequirementsTxt, Via,
+};
+use uv_cache::{Cache, CacheBucket, WheelCache};
+use uv_client::{BaseClientBuilder, CacheControl, CachedClient, CachedClientError, Connectivity};
 use uv_fs::Simplified;
 use uv_normalize::{ExtraName, PackageName};
-use uv_resolver::{Preference, PreferenceError};
+use uv_resolver::{is_pylock_path, Lock, Preference, PreferenceError, PyLock};
+use uv_traits::{BuildContext, BuildKind, NoBuild, RequiredHashes, SourceBuildTrait};
 use uv_warnings::warn_user;
 
 use crate::commands::Upgrade;
-use crate::confirm;
+use crate::confirm::{self, NoInput};
+use crate::source_format::SourceFormat;
+use crate::workspace::Workspace;
 
 #[derive(Debug)]
 pub(crate) enum RequirementsSource {
@@ -35,44 +51,128 @@ pub(crate) enum RequirementsSource {
     Package(String),
     /// An editable path was provided on the command line (e.g., `pip install -e ../flask`).
     Editable(String),
-    /// Dependencies were provided via a `requirements.txt` file (e.g., `pip install -r requirements.txt`).
-    RequirementsTxt(PathBuf),
-    /// Dependencies were provided via a `pyproject.toml` file (e.g., `pip-compile pyproject.toml`).
-    PyprojectToml(PathBuf),
+    /// Dependencies were provided via a file in the given format (e.g., `pip install -r
+    /// requirements.txt` or `pip-compile pyproject.toml`).
+    File(PathBuf, SourceFormat),
+    /// Dependencies were locked in a `uv.lock` file, to be installed into an environment with the
+    /// given markers (e.g., `uv sync`).
+    Lockfile(PathBuf, Box<MarkerEnvironment>),
 }
 
 impl RequirementsSource {
-    /// Parse a [`RequirementsSource`] from a [`PathBuf`].
-    pub(crate) fn from_path(path: PathBuf) -> Self {
-        if path.ends_with("pyproject.toml") {
-            Self::PyprojectToml(path)
-        } else {
-            Self::RequirementsTxt(path)
-        }
+    /// Parse a [`RequirementsSource`] from a [`PathBuf`], in the given format or, if no format is
+    /// provided, the format detected from the file name.
+    pub(crate) fn from_path(path: PathBuf, format: Option<SourceFormat>) -> Self {
+        let format = format.unwrap_or_else(|| SourceFormat::detect(&path));
+        Self::File(path, format)
+    }
+
+    /// Create a [`RequirementsSource`] for a `pyproject.toml` file.
+    pub(crate) fn pyproject_toml(path: PathBuf) -> Self {
+        Self::File(path, SourceFormat::PyprojectToml)
+    }
+
+    /// Returns `true` if the source is a `pyproject.toml` file.
+    pub(crate) fn is_pyproject_toml(&self) -> bool {
+        matches!(self, Self::File(_, SourceFormat::PyprojectToml))
     }
 
     /// Parse a [`RequirementsSource`] from a user-provided string, assumed to be a package.
     ///
-    /// If the user provided a value that appears to be a `requirements.txt` file or a local
-    /// directory, prompt them to correct it (if the terminal is interactive).
-    pub(crate) fn from_package(name: String) -> Self {
-        // If the user provided a `requirements.txt` file without `-r` (as in
-        // `uv pip install requirements.txt`), prompt them to correct it.
-        #[allow(clippy::case_sensitive_file_extension_comparisons)]
-        if (name.ends_with(".txt") || name.ends_with(".in")) && Path::new(&name).is_file() {
-            let term = Term::stderr();
-            if term.is_term() {
-                let prompt = format!(
-                    "`{name}` looks like a requirements file but was passed as a package name. Did you mean `-r {name}`?"
+    /// If the user provided a value that appears to be a `requirements.txt` file, a
+    /// `pyproject.toml` or `setup.py` file, or a local project directory, prompt them to correct
+    /// it (if the terminal is interactive). If interactive input is disabled (via `--no-input`),
+    /// either error with the suggested correction, or apply it, as configured.
+    pub(crate) fn from_package(name: String) -> Result<Self> {
+        let Some((kind, suggestion, source)) = Self::suggest(&name) else {
+            return Ok(Self::Package(name));
+        };
+
+        match NoInput::get() {
+            Some(NoInput::Error) => Err(anyhow::anyhow!(
+                "`{name}` looks like {kind} but was passed as a package name (hint: did you mean `{suggestion}`?)"
+            )),
+            Some(NoInput::Accept) => {
+                warn_user!(
+                    "`{name}` looks like {kind} but was passed as a package name; treating it as `{suggestion}`"
                 );
-                let confirmation = confirm::confirm(&prompt, &term, true).unwrap();
-                if confirmation {
-                    return Self::RequirementsTxt(name.into());
+                Ok(source)
+            }
+            Some(NoInput::Interactive) | None => {
+                let term = Term::stderr();
+                if term.is_term() {
+                    let prompt = format!(
+                        "`{name}` looks like {kind} but was passed as a package name. Did you mean `{suggestion}`?"
+                    );
+                    let confirmation = confirm::confirm(&prompt, &term, true)?;
+                    if confirmation {
+                        return Ok(source);
+                    }
                 }
+                Ok(Self::Package(name))
             }
         }
+    }
+
+    /// If a user-provided package name appears to refer to a local file or directory, return a
+    /// description of what it looks like, the suggested command-line argument, and the
+    /// corresponding [`RequirementsSource`].
+    fn suggest(name: &str) -> Option<(&'static str, String, Self)> {
+        let path = Path::new(name);
 
-        Self::Package(name)
+        // Ex) `uv pip install requirements.txt`
+        #[allow(clippy::case_sensitive_file_extension_comparisons)]
+        if (name.ends_with(".txt") || name.ends_with(".in")) && path.is_file() {
+            return Some((
+                "a requirements file",
+                format!("-r {name}"),
+                Self::File(path.to_path_buf(), SourceFormat::RequirementsTxt),
+            ));
+        }
+
+        // Ex) `uv pip install pyproject.toml`
+        if path.ends_with("pyproject.toml") && path.is_file() {
+            return Some((
+                "a `pyproject.toml` file",
+                format!("-r {name}"),
+                Self::pyproject_toml(path.to_path_buf()),
+            ));
+        }
+
+        // Ex) `uv pip install setup.py`
+        if path.ends_with("setup.py") && path.is_file() {
+            let directory = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.display().to_string(),
+                _ => ".".to_string(),
+            };
+            return Some((
+                "a `setup.py` file",
+                format!("-e {directory}"),
+                Self::Editable(directory),
+            ));
+        }
+
+        // Ex) `uv pip install "my project"`, where `my project` is a local project directory.
+        // Values that are unambiguously paths (e.g., `./my-project`) are already installed from
+        // the directory, so only bare names are considered. Bare names that are also valid
+        // requirements (e.g., `flask`, next to a `flask` checkout) are installed as requested.
+        if !name.contains(['/', '\\'])
+            && !name.starts_with('.')
+            && path.is_dir()
+            && (path.join("pyproject.toml").is_file() || path.join("setup.py").is_file())
+        {
+            if Requirement::from_str(name).is_ok() {
+                debug!("Treating `{name}` as a package name, despite a local project at: ./{name}");
+                return None;
+            }
+            return Some((
+                "a local project",
+                format!("./{name}"),
+                Self::Package(format!("./{name}")),
+            ));
+        }
+
+        None
     }
 }
 
@@ -80,9 +180,7 @@ impl std::fmt::Display for RequirementsSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Editable(path) => write!(f, "-e {path}"),
-            Self::RequirementsTxt(path) | Self::PyprojectToml(path) => {
-                write!(f, "{}", path.display())
-            }
+            Self::File(path, _) | Self::Lockfile(path, _) => write!(f, "{}", path.display()),
             Self::Package(package) => write!(f, "{package}"),
         }
     }
@@ -113,10 +211,23 @@ pub(crate) struct RequirementsSpecification {
     pub(crate) project: Option<PackageName>,
     /// The requirements for the project.
     pub(crate) requirements: Vec<RequirementsTxtRequirement>,
+    /// The hashes provided for the requirements (e.g., via `--hash` in a `requirements.txt`).
+    pub(crate) hashes: FxHashMap<RequirementsTxtRequirement, Vec<String>>,
     /// The constraints for the project.
     pub(crate) constraints: Vec<Requirement>,
+    /// The sources from which each constraint was read (e.g., `-c constraints.txt`), keyed by
+    /// package name, for use in annotations.
+    pub(crate) constraint_sources: FxHashMap<PackageName, Vec<String>>,
+    /// The hashes provided for the constraints (e.g., via `--hash` in a `constraints.txt`), keyed
+    /// by package name.
+    pub(crate) constraint_hashes: FxHashMap<PackageName, Vec<String>>,
+    /// The constraints without package names (e.g., local paths or URLs), along with the source
+    /// from which each was read. These are named when converting to [`NamedRequirements`].
+    pub(crate) unnamed_constraints: Vec<(UnnamedRequirement, String)>,
     /// The overrides for the project.
     pub(crate) overrides: Vec<Requirement>,
+    /// The constraints for the build environments used to build source distributions.
+    pub(crate) build_constraints: Vec<Requirement>,
     /// Package to install as editable installs
     pub(crate) editables: Vec<EditableRequirement>,
     /// The extras used to collect requirements.
@@ -129,6 +240,8 @@ pub(crate) struct RequirementsSpecification {
     pub(crate) no_index: bool,
     /// The `--find-links` locations to use for fetching packages.
     pub(crate) find_links: Vec<FlatIndexLocation>,
+    /// The packages that are pinned to a specific index.
+    pub(crate) pinned_indexes: FxHashMap<PackageName, IndexUrl>,
 }
 
 impl RequirementsSpecification {
@@ -146,153 +259,340 @@ impl RequirementsSpecification {
                 Self {
                     project: None,
                     requirements: vec![requirement],
+                    hashes: FxHashMap::default(),
                     constraints: vec![],
+                    constraint_sources: FxHashMap::default(),
+                    constraint_hashes: FxHashMap::default(),
+                    unnamed_constraints: vec![],
                     overrides: vec![],
+                    build_constraints: vec![],
                     editables: vec![],
                     extras: FxHashSet::default(),
                     index_url: None,
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    pinned_indexes: FxHashMap::default(),
                 }
             }
             RequirementsSource::Editable(name) => {
                 let requirement = EditableRequirement::parse(name, std::env::current_dir()?)
                     .with_context(|| format!("Failed to parse `{name}`"))?;
+
+                // If the editable is a workspace member, resolve any members that it depends on to
+                // their local sources, as editables, too.
+                let mut editables = vec![];
+                if requirement.path.join("pyproject.toml").is_file() {
+                    if let Some(workspace) = Workspace::discover(&requirement.path)? {
+                        let dependencies = workspace
+                            .members()
+                            .values()
+                            .find(|member| member.root() == requirement.path)
+                            .map(|member| member.requirements().cloned().collect::<Vec<_>>())
+                            .unwrap_or_default();
+                        editables.extend(
+                            workspace
+                                .editables(&dependencies)?
+                                .into_iter()
+                                .filter(|editable| editable.path != requirement.path),
+                        );
+                    }
+                }
+                editables.insert(0, requirement);
+
                 Self {
                     project: None,
                     requirements: vec![],
+                    hashes: FxHashMap::default(),
                     constraints: vec![],
+                    constraint_sources: FxHashMap::default(),
+                    constraint_hashes: FxHashMap::default(),
+                    unnamed_constraints: vec![],
                     overrides: vec![],
-                    editables: vec![requirement],
+                    build_constraints: vec![],
+                    editables,
                     extras: FxHashSet::default(),
                     index_url: None,
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    pinned_indexes: FxHashMap::default(),
                 }
             }
-            RequirementsSource::RequirementsTxt(path) => {
-                let requirements_txt =
-                    RequirementsTxt::parse(path, std::env::current_dir()?, connectivity).await?;
-                Self {
-                    project: None,
-                    requirements: requirements_txt
-                        .requirements
-                        .into_iter()
-                        .map(|entry| entry.requirement)
-                        .collect(),
-                    constraints: requirements_txt.constraints,
-                    editables: requirements_txt.editables,
-                    overrides: vec![],
-                    extras: FxHashSet::default(),
-                    index_url: requirements_txt.index_url.map(IndexUrl::from),
-                    extra_index_urls: requirements_txt
-                        .extra_index_urls
-                        .into_iter()
-                        .map(IndexUrl::from)
-                        .collect(),
-                    no_index: requirements_txt.no_index,
-                    find_links: requirements_txt
-                        .find_links
-                        .into_iter()
-                        .map(|link| match link {
-                            FindLink::Url(url) => FlatIndexLocation::Url(url),
-                            FindLink::Path(path) => FlatIndexLocation::Path(path),
-                        })
-                        .collect(),
-                }
+            RequirementsSource::File(path, format) => {
+                format.read(path, extras, connectivity).await?
             }
-            RequirementsSource::PyprojectToml(path) => {
-                let contents = uv_fs::read_to_string(path).await?;
-                let pyproject_toml = toml::from_str::<pyproject_toml::PyProjectToml>(&contents)
-                    .with_context(|| format!("Failed to parse `{}`", path.user_display()))?;
-                let mut used_extras = FxHashSet::default();
-                let mut requirements = Vec::new();
-                let mut project_name = None;
-
-                if let Some(project) = pyproject_toml.project {
-                    // Parse the project name.
-                    let parsed_project_name =
-                        PackageName::new(project.name).with_context(|| {
-                            format!("Invalid `project.name` in {}", path.user_display())
-                        })?;
-
-                    // Include the default dependencies.
-                    requirements.extend(project.dependencies.unwrap_or_default());
-
-                    // Include any optional dependencies specified in `extras`.
-                    if !matches!(extras, ExtrasSpecification::None) {
-                        if let Some(optional_dependencies) = project.optional_dependencies {
-                            for (extra_name, optional_requirements) in &optional_dependencies {
-                                // TODO(konstin): It's not ideal that pyproject-toml doesn't use
-                                // `ExtraName`
-                                let normalized_name = ExtraName::from_str(extra_name)?;
-                                if extras.contains(&normalized_name) {
-                                    used_extras.insert(normalized_name);
-                                    requirements.extend(flatten_extra(
-                                        &parsed_project_name,
-                                        optional_requirements,
-                                        &optional_dependencies,
-                                    )?);
-                                }
-                            }
-                        }
-                    }
+            RequirementsSource::Lockfile(path, markers) => Self::from_lockfile(path, markers)?,
+        })
+    }
 
-                    project_name = Some(parsed_project_name);
-                }
+    /// Read the locked requirements that apply to an environment with the given markers from a
+    /// `uv.lock` file, along with their locked hashes.
+    pub(crate) fn from_lockfile(path: &Path, markers: &MarkerEnvironment) -> Result<Self> {
+        let lock = Lock::from_toml(&fs_err::read_to_string(path)?)?;
+        let locked = lock.to_requirements(markers)?;
 
-                if requirements.is_empty()
-                    && pyproject_toml.build_system.is_some_and(|build_system| {
-                        build_system
-                            .requires
-                            .iter()
-                            .any(|v| v.name.as_dist_info_name().starts_with("poetry"))
-                    })
-                {
-                    warn_user!("`{}` does not contain any dependencies (hint: specify dependencies in the `project.dependencies` section; `tool.poetry.dependencies` is not currently supported)", path.user_display());
-                }
+        let working_dir = path.parent().unwrap_or(Path::new("."));
+        let editables = locked
+            .editables
+            .iter()
+            .map(|editable| {
+                EditableRequirement::parse(editable, working_dir)
+                    .with_context(|| format!("Failed to parse locked editable `{editable}`"))
+            })
+            .collect::<Result<Vec<_>>>()?;
 
-                Self {
-                    project: project_name,
-                    requirements: requirements
+        let requirements = locked
+            .requirements
+            .into_iter()
+            .map(RequirementsTxtRequirement::Pep508)
+            .collect::<Vec<_>>();
+
+        // Verify each locked distribution against its locked hashes, as with hashes in a
+        // constraints file, rather than enabling hash-checking mode, since the lockfile can also
+        // include editables and Git dependencies, which can't be hashed.
+        Ok(Self {
+            requirements,
+            constraint_hashes: locked.hashes,
+            editables,
+            ..Self::default()
+        })
+    }
+
+    /// Read the requirements, constraints, and index locations from a `requirements.txt` file.
+    pub(crate) async fn from_requirements_txt(
+        path: &Path,
+        connectivity: Connectivity,
+    ) -> Result<Self> {
+        let requirements_txt =
+            RequirementsTxt::parse(path, std::env::current_dir()?, connectivity).await?;
+        let hashes = requirements_txt
+            .requirements
+            .iter()
+            .filter(|entry| !entry.hashes.is_empty())
+            .map(|entry| (entry.requirement.clone(), entry.hashes.clone()))
+            .collect();
+        Ok(Self {
+            project: None,
+            requirements: requirements_txt
+                .requirements
+                .into_iter()
+                .map(|entry| entry.requirement)
+                .collect(),
+            hashes,
+            constraints: requirements_txt.constraints,
+            constraint_sources: requirements_txt
+                .constraint_sources
+                .into_iter()
+                .map(|(name, sources)| {
+                    let sources = sources
                         .into_iter()
-                        .map(RequirementsTxtRequirement::Pep508)
-                        .collect(),
-                    constraints: vec![],
-                    overrides: vec![],
-                    editables: vec![],
-                    extras: used_extras,
-                    index_url: None,
-                    extra_index_urls: vec![],
-                    no_index: false,
-                    find_links: vec![],
+                        .map(|source| format!("-c {source}"))
+                        .collect();
+                    (name, sources)
+                })
+                .collect(),
+            constraint_hashes: requirements_txt.constraint_hashes.into_iter().collect(),
+            unnamed_constraints: vec![],
+            editables: requirements_txt.editables,
+            overrides: vec![],
+            build_constraints: vec![],
+            extras: FxHashSet::default(),
+            index_url: requirements_txt.index_url.map(IndexUrl::from),
+            extra_index_urls: requirements_txt
+                .extra_index_urls
+                .into_iter()
+                .map(IndexUrl::from)
+                .collect(),
+            no_index: requirements_txt.no_index,
+            find_links: requirements_txt
+                .find_links
+                .into_iter()
+                .map(|link| match link {
+                    FindLink::Url(url) => FlatIndexLocation::Url(url),
+                    FindLink::Path(path) => FlatIndexLocation::Path(path),
+                })
+                .collect(),
+            pinned_indexes: FxHashMap::default(),
+        })
+    }
+
+    /// Read the requirements from a `pyproject.toml` file, including those for the requested
+    /// extras.
+    pub(crate) async fn from_pyproject_toml(
+        path: &Path,
+        extras: &ExtrasSpecification<'_>,
+    ) -> Result<Self> {
+        let contents = uv_fs::read_to_string(path).await?;
+        let pyproject_toml = toml::from_str::<pyproject_toml::PyProjectToml>(&contents)
+            .with_context(|| format!("Failed to parse `{}`", path.user_display()))?;
+        let mut used_extras = FxHashSet::default();
+        let mut selected_extras = Vec::new();
+        let mut requirements = Vec::new();
+        let mut project_name = None;
+
+        if let Some(project) = pyproject_toml.project {
+            // Parse the project name.
+            let parsed_project_name = PackageName::new(project.name)
+                .with_context(|| format!("Invalid `project.name` in {}", path.user_display()))?;
+
+            // Include the default dependencies.
+            requirements.extend(project.dependencies.unwrap_or_default());
+
+            // Include any optional dependencies specified in `extras`.
+            if !matches!(extras, ExtrasSpecification::None) {
+                if let Some(optional_dependencies) = project.optional_dependencies {
+                    for (extra_name, optional_requirements) in &optional_dependencies {
+                        // TODO(konstin): It's not ideal that pyproject-toml doesn't use
+                        // `ExtraName`
+                        let normalized_name = ExtraName::from_str(extra_name)?;
+                        if extras.contains(&normalized_name) {
+                            let extra_requirements = flatten_extra(
+                                &parsed_project_name,
+                                optional_requirements,
+                                &optional_dependencies,
+                            )?;
+                            requirements.extend(extra_requirements.iter().cloned());
+                            selected_extras.push((normalized_name.clone(), extra_requirements));
+                            used_extras.insert(normalized_name);
+                        }
+                    }
                 }
             }
+
+            // Reject any extras that can't be installed together (e.g., `cpu` and `gpu`
+            // variants that pin different builds of the same package).
+            if let Some((first, first_requirement, second, second_requirement)) =
+                find_conflicting_extras(&selected_extras)
+            {
+                return Err(anyhow::anyhow!(
+                    "Extras `{first}` and `{second}` of `{parsed_project_name}` are mutually exclusive: `{first}` requires `{first_requirement}`, but `{second}` requires `{second_requirement}`"
+                ));
+            }
+
+            project_name = Some(parsed_project_name);
+        }
+
+        if requirements.is_empty()
+            && pyproject_toml.build_system.is_some_and(|build_system| {
+                build_system
+                    .requires
+                    .iter()
+                    .any(|v| v.name.as_dist_info_name().starts_with("poetry"))
+            })
+        {
+            warn_user!("`{}` does not contain any dependencies (hint: specify dependencies in the `project.dependencies` section; `tool.poetry.dependencies` is not currently supported)", path.user_display());
+        }
+
+        let tool_uv = toml::from_str::<PyProjectToml>(&contents)
+            .with_context(|| format!("Failed to parse `{}`", path.user_display()))?
+            .tool
+            .and_then(|tool| tool.uv);
+
+        // If the project is part of a workspace, resolve any dependencies on other
+        // workspace members to their local sources, as editables. The requirements themselves
+        // are retained, such that their markers and version specifiers still apply.
+        let mut editables = vec![];
+        let project_dir = std::env::current_dir()?.join(path);
+        if let Some(workspace) = project_dir
+            .parent()
+            .map(Workspace::discover)
+            .transpose()?
+            .flatten()
+        {
+            editables = workspace.editables(&requirements)?;
+        }
+
+        // Include any overrides declared in `tool.uv.override-dependencies`.
+        let overrides = tool_uv
+            .as_ref()
+            .and_then(|uv| uv.override_dependencies.clone())
+            .unwrap_or_default();
+
+        // Include any named indexes declared in `tool.uv.index`, along with the packages
+        // pinned to them in `tool.uv.sources`.
+        let (extra_index_urls, pinned_indexes) = match tool_uv {
+            Some(tool_uv) => tool_uv.indexes(path)?,
+            None => (vec![], FxHashMap::default()),
+        };
+
+        Ok(Self {
+            project: project_name,
+            requirements: requirements
+                .into_iter()
+                .map(RequirementsTxtRequirement::Pep508)
+                .collect(),
+            hashes: FxHashMap::default(),
+            constraints: vec![],
+            constraint_sources: FxHashMap::default(),
+            constraint_hashes: FxHashMap::default(),
+            unnamed_constraints: vec![],
+            overrides,
+            build_constraints: vec![],
+            editables,
+            extras: used_extras,
+            index_url: None,
+            extra_index_urls,
+            no_index: false,
+            find_links: vec![],
+            pinned_indexes,
         })
     }
 
     /// Read the combined requirements and constraints from a set of sources.
-    pub(crate) async fn from_sources(
-        requirements: &[RequirementsSource],
-        constraints: &[RequirementsSource],
-        overrides: &[RequirementsSource],
+    pub(crate) async fn from_sources<'a>(
+        requirements: &'a [RequirementsSource],
+        constraints: &'a [RequirementsSource],
+        overrides: &'a [RequirementsSource],
+        build_constraints: &'a [RequirementsSource],
         extras: &ExtrasSpecification<'_>,
         connectivity: Connectivity,
     ) -> Result<Self> {
         let mut spec = Self::default();
 
+        // Read all sources concurrently, since each may involve fetching remote files (e.g., a
+        // `requirements.txt` that's hosted at a URL, or that includes other files via `-r`). The
+        // results are merged in order below.
+        let read_all = |sources: &'a [RequirementsSource]| {
+            try_join_all(
+                sources
+                    .iter()
+                    .map(|source| Self::from_source(source, extras, connectivity)),
+            )
+        };
+        let (requirement_specs, constraint_specs, override_specs, build_constraint_specs) = try_join!(
+            read_all(requirements),
+            read_all(constraints),
+            read_all(overrides),
+            read_all(build_constraints),
+        )?;
+
         // Read all requirements, and keep track of all requirements _and_ constraints.
         // A `requirements.txt` can contain a `-c constraints.txt` directive within it, so reading
         // a requirements file can also add constraints.
-        for source in requirements {
-            let source = Self::from_source(source, extras, connectivity).await?;
+        for source in requirement_specs {
             spec.requirements.extend(source.requirements);
+            for (requirement, hashes) in source.hashes {
+                spec.hashes.entry(requirement).or_default().extend(hashes);
+            }
             spec.constraints.extend(source.constraints);
+            spec.add_constraint_sources(source.constraint_sources);
+            spec.add_constraint_hashes(source.constraint_hashes);
             spec.overrides.extend(source.overrides);
             spec.extras.extend(source.extras);
-            spec.editables.extend(source.editables);
+            for editable in source.editables {
+                // Skip any editables that were already discovered (e.g., as a dependency of
+                // another workspace member), unless the editable was requested directly.
+                if let Some(existing) = spec
+                    .editables
+                    .iter_mut()
+                    .find(|existing| existing.path == editable.path)
+                {
+                    existing.dependency &= editable.dependency;
+                } else {
+                    spec.editables.push(editable);
+                }
+            }
 
             // Use the first project name discovered.
             if spec.project.is_none() {
@@ -310,24 +610,42 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            for (package_name, index) in source.pinned_indexes {
+                if let Some(existing) = spec.pinned_indexes.get(&package_name) {
+                    if *existing != index {
+                        return Err(anyhow::anyhow!(
+                            "Multiple indexes specified for `{package_name}`: `{existing}` vs. `{index}`",
+                        ));
+                    }
+                }
+                spec.pinned_indexes.insert(package_name, index);
+            }
         }
 
         // Read all constraints, treating _everything_ as a constraint.
-        for source in constraints {
-            let source = Self::from_source(source, extras, connectivity).await?;
+        for (mut source, origin) in constraint_specs.into_iter().zip(constraints) {
+            let origin = format!("-c {origin}");
             for requirement in source.requirements {
+                let hashes = source.hashes.remove(&requirement);
                 match requirement {
                     RequirementsTxtRequirement::Pep508(requirement) => {
+                        spec.add_constraint_sources([(
+                            requirement.name.clone(),
+                            vec![origin.clone()],
+                        )]);
+                        if let Some(hashes) = hashes {
+                            spec.add_constraint_hashes([(requirement.name.clone(), hashes)]);
+                        }
                         spec.constraints.push(requirement);
                     }
                     RequirementsTxtRequirement::Unnamed(requirement) => {
-                        return Err(anyhow::anyhow!(
-                            "Unnamed requirements are not allowed as constraints (found: `{requirement}`)"
-                        ));
+                        spec.unnamed_constraints.push((requirement, origin.clone()));
                     }
                 }
             }
             spec.constraints.extend(source.constraints);
+            spec.add_constraint_sources(source.constraint_sources);
+            spec.add_constraint_hashes(source.constraint_hashes);
             spec.constraints.extend(source.overrides);
 
             if let Some(url) = source.index_url {
@@ -341,11 +659,20 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            for (package_name, index) in source.pinned_indexes {
+                if let Some(existing) = spec.pinned_indexes.get(&package_name) {
+                    if *existing != index {
+                        return Err(anyhow::anyhow!(
+                            "Multiple indexes specified for `{package_name}`: `{existing}` vs. `{index}`",
+                        ));
+                    }
+                }
+                spec.pinned_indexes.insert(package_name, index);
+            }
         }
 
         // Read all overrides, treating both requirements _and_ constraints as overrides.
-        for source in overrides {
-            let source = Self::from_source(source, extras, connectivity).await?;
+        for source in override_specs {
             for requirement in source.requirements {
                 match requirement {
                     RequirementsTxtRequirement::Pep508(requirement) => {
@@ -372,6 +699,35 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            for (package_name, index) in source.pinned_indexes {
+                if let Some(existing) = spec.pinned_indexes.get(&package_name) {
+                    if *existing != index {
+                        return Err(anyhow::anyhow!(
+                            "Multiple indexes specified for `{package_name}`: `{existing}` vs. `{index}`",
+                        ));
+                    }
+                }
+                spec.pinned_indexes.insert(package_name, index);
+            }
+        }
+
+        // Read all build constraints, treating _everything_ as a build constraint. Index locations
+        // are ignored, since build constraints only apply to the build environments.
+        for source in build_constraint_specs {
+            for requirement in source.requirements {
+                match requirement {
+                    RequirementsTxtRequirement::Pep508(requirement) => {
+                        spec.build_constraints.push(requirement);
+                    }
+                    RequirementsTxtRequirement::Unnamed(requirement) => {
+                        return Err(anyhow::anyhow!(
+                            "Unnamed requirements are not allowed as build constraints (found: `{requirement}`)"
+                        ));
+                    }
+                }
+            }
+            spec.build_constraints.extend(source.constraints);
+            spec.build_constraints.extend(source.overrides);
         }
 
         Ok(spec)
@@ -386,11 +742,42 @@ impl RequirementsSpecification {
             requirements,
             &[],
             &[],
+            &[],
             &ExtrasSpecification::None,
             connectivity,
         )
         .await
     }
+
+    /// Record the sources from which the given constraints were read.
+    fn add_constraint_sources(
+        &mut self,
+        constraint_sources: impl IntoIterator<Item = (PackageName, Vec<String>)>,
+    ) {
+        for (name, sources) in constraint_sources {
+            let existing = self.constraint_sources.entry(name).or_default();
+            for source in sources {
+                if !existing.contains(&source) {
+                    existing.push(source);
+                }
+            }
+        }
+    }
+
+    /// Record the hashes provided for the given constraints.
+    fn add_constraint_hashes(
+        &mut self,
+        constraint_hashes: impl IntoIterator<Item = (PackageName, Vec<String>)>,
+    ) {
+        for (name, hashes) in constraint_hashes {
+            let existing = self.constraint_hashes.entry(name).or_default();
+            for hash in hashes {
+                if !existing.contains(&hash) {
+                    existing.push(hash);
+                }
+            }
+        }
+    }
 }
 
 /// Given an extra in a project that may contain references to the project
@@ -461,6 +848,60 @@ fn flatten_extra(
     )
 }
 
+/// Find a pair of extras that pin the same package to incompatible versions or URLs, such that
+/// they can't be installed together.
+///
+/// Only requirements with identical markers are compared, since requirements that apply to
+/// different environments never conflict.
+fn find_conflicting_extras<'a>(
+    extras: &'a [(ExtraName, Vec<Requirement>)],
+) -> Option<(
+    &'a ExtraName,
+    &'a Requirement,
+    &'a ExtraName,
+    &'a Requirement,
+)> {
+    /// Returns `true` if the two requirements can't be satisfied by the same distribution.
+    fn is_disjoint(first: &Requirement, second: &Requirement) -> bool {
+        match (&first.version_or_url, &second.version_or_url) {
+            (Some(VersionOrUrl::Url(first)), Some(VersionOrUrl::Url(second))) => first != second,
+            (
+                Some(VersionOrUrl::VersionSpecifier(first)),
+                Some(VersionOrUrl::VersionSpecifier(second)),
+            ) => {
+                pinned_version(first).is_some_and(|version| !second.contains(version))
+                    || pinned_version(second).is_some_and(|version| !first.contains(version))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the exact version pinned by the specifiers, if any.
+    fn pinned_version(specifiers: &VersionSpecifiers) -> Option<&Version> {
+        specifiers
+            .iter()
+            .find(|specifier| *specifier.operator() == Operator::Equal)
+            .map(VersionSpecifier::version)
+    }
+
+    for (index, (first, first_requirements)) in extras.iter().enumerate() {
+        for (second, second_requirements) in &extras[index + 1..] {
+            for first_requirement in first_requirements {
+                for second_requirement in second_requirements {
+                    if first_requirement.name == second_requirement.name
+                        && first_requirement.marker == second_requirement.marker
+                        && is_disjoint(first_requirement, second_requirement)
+                    {
+                        return Some((first, first_requirement, second, second_requirement));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
 /// Load the preferred requirements from an existing lockfile, applying the upgrade strategy.
 pub(crate) async fn read_lockfile(
     output_file: Option<&Path>,
@@ -474,16 +915,34 @@ pub(crate) async fn read_lockfile(
         return Ok(Vec::new());
     };
 
-    // Parse the requirements from the lockfile.
-    let requirements_txt =
-        RequirementsTxt::parse(output_file, std::env::current_dir()?, Connectivity::Offline)
-            .await?;
-    let preferences = requirements_txt
-        .requirements
-        .into_iter()
-        .filter(|entry| !entry.editable)
-        .map(Preference::from_entry)
-        .collect::<Result<Vec<_>, PreferenceError>>()?;
+    // Parse the pinned packages from the lockfile, along with the reasons that each package was
+    // included, if the lockfile was annotated by `pip-compile` (or `uv pip compile`).
+    let (preferences, annotations) = if is_pylock_path(output_file) {
+        (
+            PyLock::from_toml(&fs_err::read_to_string(output_file)?)?.to_preferences()?,
+            BTreeMap::default(),
+        )
+    } else if output_file
+        .file_name()
+        .is_some_and(|name| name == "uv.lock")
+    {
+        (
+            Lock::from_toml(&fs_err::read_to_string(output_file)?)?.to_preferences()?,
+            BTreeMap::default(),
+        )
+    } else {
+        let requirements_txt =
+            RequirementsTxt::parse(output_file, std::env::current_dir()?, Connectivity::Offline)
+                .await?;
+        let preferences = requirements_txt
+            .requirements
+            .into_iter()
+            .filter(|entry| !entry.editable)
+            .map(Preference::from_entry)
+            .collect::<Result<Vec<_>, PreferenceError>>()?;
+        let annotations = parse_via_annotations(&fs_err::read_to_string(output_file)?);
+        (preferences, annotations)
+    };
 
     // Apply the upgrade strategy to the requirements.
     Ok(match upgrade {
@@ -491,14 +950,53 @@ pub(crate) async fn read_lockfile(
         Upgrade::None => preferences,
         // Ignore all pinned versions from the existing lockfile.
         Upgrade::All => vec![],
-        // Ignore pinned versions for the specified packages.
-        Upgrade::Packages(packages) => preferences
-            .into_iter()
-            .filter(|preference| !packages.contains(preference.name()))
-            .collect(),
+        // Ignore pinned versions for the specified packages, along with any packages that were
+        // only pinned as dependencies of the specified packages.
+        Upgrade::Packages(packages) => {
+            let upgraded = upgraded_packages(packages.keys(), &annotations);
+            preferences
+                .into_iter()
+                .filter(|preference| !upgraded.contains(preference.name()))
+                .collect()
+        }
     })
 }
 
+/// Return the packages whose pinned versions should be ignored when upgrading the given packages:
+/// the packages themselves, along with any packages that (per their `# via` annotations) were only
+/// included as dependencies of upgraded packages, such that they can be upgraded (or dropped)
+/// alongside the packages that required them.
+///
+/// Packages without annotations, and packages that were requested directly (e.g., via
+/// `-r requirements.in`), are never upgraded implicitly.
+fn upgraded_packages<'a>(
+    packages: impl IntoIterator<Item = &'a PackageName>,
+    annotations: &'a BTreeMap<PackageName, Vec<Via>>,
+) -> FxHashSet<&'a PackageName> {
+    let mut upgraded = packages.into_iter().collect::<FxHashSet<_>>();
+
+    // Iterate to a fixed point, to capture transitive dependencies of the upgraded packages.
+    loop {
+        let mut changed = false;
+        for (name, via) in annotations {
+            if upgraded.contains(name) || via.is_empty() {
+                continue;
+            }
+            if via
+                .iter()
+                .all(|via| matches!(via, Via::Package(dependent) if upgraded.contains(dependent)))
+            {
+                debug!("Upgrading `{name}`, which is only required by upgraded packages");
+                upgraded.insert(name);
+                changed = true;
+            }
+        }
+        if !changed {
+            return upgraded;
+        }
+    }
+}
+
 /// Like [`RequirementsSpecification`], but with concrete names for all requirements.
 #[derive(Debug, Default)]
 pub(crate) struct NamedRequirements {
@@ -506,10 +1004,19 @@ pub(crate) struct NamedRequirements {
     pub(crate) project: Option<PackageName>,
     /// The requirements for the project.
     pub(crate) requirements: Vec<Requirement>,
+    /// The hashes provided for the requirements, by package name.
+    pub(crate) hashes: FxHashMap<PackageName, Vec<String>>,
     /// The constraints for the project.
     pub(crate) constraints: Vec<Requirement>,
+    /// The sources from which each constraint was read (e.g., `-c constraints.txt`), keyed by
+    /// package name, for use in annotations.
+    pub(crate) constraint_sources: FxHashMap<PackageName, Vec<String>>,
+    /// The hashes provided for the constraints, by package name.
+    pub(crate) constraint_hashes: FxHashMap<PackageName, Vec<String>>,
     /// The overrides for the project.
     pub(crate) overrides: Vec<Requirement>,
+    /// The constraints for the build environments used to build source distributions.
+    pub(crate) build_constraints: Vec<Requirement>,
     /// Package to install as editable installs
     pub(crate) editables: Vec<EditableRequirement>,
     /// The index URL to use for fetching packages.
@@ -520,35 +1027,334 @@ pub(crate) struct NamedRequirements {
     pub(crate) no_index: bool,
     /// The `--find-links` locations to use for fetching packages.
     pub(crate) find_links: Vec<FlatIndexLocation>,
+    /// The packages that are pinned to a specific index.
+    pub(crate) pinned_indexes: FxHashMap<PackageName, IndexUrl>,
+    /// The local directories whose package names couldn't be inferred from their static metadata,
+    /// to be named via [`NamedRequirements::name_with_build`].
+    pub(crate) unnamed: Vec<UnnamedRequirement>,
 }
 
 impl NamedRequirements {
     /// Convert a [`RequirementsSpecification`] into a [`NamedRequirements`].
-    pub(crate) fn from_spec(spec: RequirementsSpecification) -> Result<Self> {
+    ///
+    /// Remote archives that can't be named from their URLs alone are downloaded with a client
+    /// built from the given [`BaseClientBuilder`], and the inferred names are cached.
+    pub(crate) async fn from_spec(
+        mut spec: RequirementsSpecification,
+        client_builder: &BaseClientBuilder<'_>,
+        cache: &Cache,
+    ) -> Result<Self> {
+        let mut requirements = Vec::with_capacity(spec.requirements.len());
+        let mut hashes = FxHashMap::<PackageName, Vec<String>>::default();
+        let mut unnamed = Vec::new();
+        for requirement in spec.requirements {
+            let requirement_hashes = spec.hashes.remove(&requirement);
+            let requirement = match requirement {
+                RequirementsTxtRequirement::Pep508(requirement) => requirement,
+                RequirementsTxtRequirement::Unnamed(requirement) => {
+                    // Local directories without static metadata can only be named by invoking
+                    // the build backend, which requires a build environment.
+                    if unnamed_directory(&requirement)
+                        .is_some_and(|path| name_from_directory(&path).is_none())
+                    {
+                        debug!("Deferring name inference for: {requirement}");
+                        unnamed.push(requirement);
+                        continue;
+                    }
+                    Self::name_requirement(requirement, client_builder, cache).await?
+                }
+            };
+            if let Some(requirement_hashes) = requirement_hashes {
+                hashes
+                    .entry(requirement.name.clone())
+                    .or_default()
+                    .extend(requirement_hashes);
+            }
+            requirements.push(requirement);
+        }
+
+        // Install any local directories that the local requirements depend on (via paths in
+        // their `pyproject.toml`) from their local sources, rather than the index.
+        let path_dependencies = Self::path_dependencies(&requirements, &spec.editables)?;
+        requirements.extend(path_dependencies);
+
+        // Name any constraints that were provided as local paths or URLs.
+        let mut constraints = spec.constraints;
+        let mut constraint_sources = spec.constraint_sources;
+        for (constraint, origin) in spec.unnamed_constraints {
+            let constraint = Self::name_requirement(constraint, client_builder).await?;
+            let sources = constraint_sources
+                .entry(constraint.name.clone())
+                .or_default();
+            if !sources.contains(&origin) {
+                sources.push(origin);
+            }
+            constraints.push(constraint);
+        }
+
         Ok(Self {
             project: spec.project,
-            requirements: spec
-                .requirements
-                .into_iter()
-                .map(|requirement| match requirement {
-                    RequirementsTxtRequirement::Pep508(requirement) => Ok(requirement),
-                    RequirementsTxtRequirement::Unnamed(requirement) => {
-                        Self::name_requirement(requirement)
-                    }
-                })
-                .collect::<Result<_>>()?,
-            constraints: spec.constraints,
+            requirements,
+            hashes,
+            constraints,
+            constraint_sources,
+            constraint_hashes: spec.constraint_hashes,
             overrides: spec.overrides,
+            build_constraints: spec.build_constraints,
             editables: spec.editables,
             index_url: spec.index_url,
             extra_index_urls: spec.extra_index_urls,
             no_index: spec.no_index,
             find_links: spec.find_links,
+            pinned_indexes: spec.pinned_indexes,
+            unnamed,
         })
     }
 
+    /// Infer the package names for local directories without static metadata (e.g., those with a
+    /// dynamic `setup.py`) by invoking the build backend's `prepare_metadata_for_build_wheel`
+    /// hook in an isolated environment, or, if the backend lacks the hook, by building a wheel.
+    ///
+    /// Since this executes arbitrary code, it's disabled by `--no-build`.
+    pub(crate) async fn name_with_build(
+        unnamed: Vec<UnnamedRequirement>,
+        build_context: &impl BuildContext,
+    ) -> Result<Vec<Requirement>> {
+        let mut requirements = Vec::with_capacity(unnamed.len());
+        for requirement in unnamed {
+            // The package name is unknown, so builds must be enabled for all packages.
+            if !matches!(build_context.no_build(), NoBuild::None) {
+                return Err(anyhow::anyhow!(
+                    "Unable to infer package name for the unnamed requirement: {requirement} (the project lacks static metadata, and building is disabled)"
+                ));
+            }
+
+            let path = unnamed_directory(&requirement).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Expected a local directory for the unnamed requirement: {requirement}"
+                )
+            })?;
+            debug!(
+                "Invoking the build backend to infer the package name for: {}",
+                path.user_display()
+            );
+
+            let name = async {
+                let mut builder = build_context
+                    .setup_build(
+                        &path,
+                        None,
+                        &requirement.to_string(),
+                        None,
+                        BuildKind::Wheel,
+                    )
+                    .await?;
+                if let Some(metadata_directory) = builder.metadata().await? {
+                    let contents = fs_err::read(metadata_directory.join("METADATA"))?;
+                    Ok::<_, anyhow::Error>(Metadata10::parse_pkg_info(&contents)?.name)
+                } else {
+                    let wheel_dir = tempfile::tempdir()?;
+                    let filename = builder.wheel(wheel_dir.path()).await?;
+                    Ok(WheelFilename::from_str(&filename)?.name)
+                }
+            }
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to infer the package name for the unnamed requirement: {requirement}"
+                )
+            })?;
+            debug!("Inferred package name for {} ({name})", path.user_display());
+
+            requirements.push(Requirement {
+                name,
+                extras: requirement.extras,
+                version_or_url: Some(VersionOrUrl::Url(requirement.url)),
+                marker: requirement.marker,
+            });
+        }
+        Ok(requirements)
+    }
+
+    /// Recursively discover the local directories that the given local directory requirements
+    /// and editables depend on, as declared via paths in their `pyproject.toml` (e.g.,
+    /// `dependencies = ["bar @ file:../bar"]`, relative to the declaring project).
+    ///
+    /// Returns a requirement for each discovered directory that isn't already required, with its
+    /// path made absolute. Cycles between local projects are detected and skipped.
+    fn path_dependencies(
+        requirements: &[Requirement],
+        editables: &[EditableRequirement],
+    ) -> Result<Vec<Requirement>> {
+        let mut required = requirements
+            .iter()
+            .map(|requirement| requirement.name.clone())
+            .collect::<FxHashSet<_>>();
+        let mut visited = FxHashSet::default();
+        let mut discovered = Vec::new();
+
+        let roots = requirements
+            .iter()
+            .filter_map(local_directory)
+            .chain(editables.iter().map(|editable| editable.path.clone()));
+        for root in roots {
+            Self::visit_path_dependencies(
+                &root,
+                &mut vec![],
+                &mut visited,
+                &mut required,
+                &mut discovered,
+            )?;
+        }
+
+        Ok(discovered)
+    }
+
+    /// Visit the local path dependencies of the project in the given directory, depth-first.
+    ///
+    /// `stack` contains the directories on the current path from a root, and is used to detect
+    /// cycles.
+    fn visit_path_dependencies(
+        directory: &Path,
+        stack: &mut Vec<PathBuf>,
+        visited: &mut FxHashSet<PathBuf>,
+        required: &mut FxHashSet<PackageName>,
+        discovered: &mut Vec<Requirement>,
+    ) -> Result<()> {
+        let directory = directory
+            .canonicalize()
+            .unwrap_or_else(|_| directory.to_path_buf());
+        if stack.contains(&directory) {
+            debug!(
+                "Detected a cycle between local path dependencies: {}",
+                stack
+                    .iter()
+                    .chain(std::iter::once(&directory))
+                    .map(|path| path.user_display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            );
+            return Ok(());
+        }
+        if !visited.insert(directory.clone()) {
+            return Ok(());
+        }
+
+        stack.push(directory.clone());
+        for dependency in read_local_dependencies(&directory)? {
+            let Some(path) = local_directory(&dependency) else {
+                continue;
+            };
+            if required.insert(dependency.name.clone()) {
+                debug!(
+                    "Found local path dependency `{}` in {}",
+                    dependency.name,
+                    directory.user_display()
+                );
+                discovered.push(Requirement {
+                    version_or_url: Some(VersionOrUrl::Url(VerbatimUrl::from_path(&path))),
+                    ..dependency
+                });
+            }
+            Self::visit_path_dependencies(&path, stack, visited, required, discovered)?;
+        }
+        stack.pop();
+
+        Ok(())
+    }
+
+    /// Returns `true` if hash-checking mode is enabled.
+    ///
+    /// Like pip, hash-checking mode is enabled if any requirement includes a hash, or if
+    /// `require_hashes` is set. Hashes on constraints alone don't enable hash-checking mode.
+    pub(crate) fn hash_checking_mode(&self, require_hashes: bool) -> bool {
+        require_hashes || !self.hashes.is_empty()
+    }
+
+    /// Determine the hashes that distributions must match.
+    ///
+    /// Any requirement that includes a hash is verified against it. Constraints can also include
+    /// hashes, in which case any distribution selected for the constrained package (even if it's
+    /// only a transitive dependency) is verified against them; if both the requirement and the
+    /// constraint include hashes, the distribution must match both. In hash-checking mode (see
+    /// [`NamedRequirements::hash_checking_mode`]), every requirement must also be pinned to an
+    /// exact version (or URL) and include at least one hash, either directly or via a constraint.
+    pub(crate) fn required_hashes(&self, require_hashes: bool) -> Result<RequiredHashes> {
+        let hash_checking_mode = self.hash_checking_mode(require_hashes);
+        if !hash_checking_mode && self.constraint_hashes.is_empty() {
+            return Ok(RequiredHashes::default());
+        }
+
+        if hash_checking_mode {
+            self.validate_hash_checking_mode()?;
+        }
+
+        let mut required = FxHashMap::default();
+        for (name, hashes) in &self.hashes {
+            required.insert(name.clone(), normalize_hashes(name, hashes)?);
+        }
+        for (name, hashes) in &self.constraint_hashes {
+            let hashes = normalize_hashes(name, hashes)?;
+            match required.entry(name.clone()) {
+                Entry::Occupied(mut entry) => {
+                    entry.get_mut().retain(|hash| hashes.contains(hash));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(hashes);
+                }
+            }
+        }
+        Ok(RequiredHashes::new(required))
+    }
+
+    /// Validate the requirements for hash-checking mode, in which every requirement must be
+    /// pinned to an exact version (or URL) and include at least one hash.
+    fn validate_hash_checking_mode(&self) -> Result<()> {
+        if let Some(editable) = self.editables.first() {
+            return Err(anyhow::anyhow!(
+                "Editable requirements are not supported in hash-checking mode (found: `{editable}`)"
+            ));
+        }
+        if let Some(requirement) = self.unnamed.first() {
+            return Err(anyhow::anyhow!(
+                "In hash-checking mode, all requirements must have a hash, but none were provided for: `{requirement}`"
+            ));
+        }
+
+        for requirement in &self.requirements {
+            let pinned = match &requirement.version_or_url {
+                Some(VersionOrUrl::Url(_)) => true,
+                Some(VersionOrUrl::VersionSpecifier(specifiers)) => match &**specifiers {
+                    [specifier] => {
+                        matches!(specifier.operator(), Operator::Equal | Operator::ExactEqual)
+                    }
+                    _ => false,
+                },
+                None => false,
+            };
+            if !pinned {
+                return Err(anyhow::anyhow!(
+                    "In hash-checking mode, all requirements must be pinned to an exact version with `==` (found: `{requirement}`)"
+                ));
+            }
+            if !self.hashes.contains_key(&requirement.name)
+                && !self.constraint_hashes.contains_key(&requirement.name)
+            {
+                return Err(anyhow::anyhow!(
+                    "In hash-checking mode, all requirements must have a hash, but none were provided for: `{requirement}`"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Infer the package name for a given "unnamed" requirement.
-    fn name_requirement(requirement: UnnamedRequirement) -> Result<Requirement> {
+    async fn name_requirement(
+        requirement: UnnamedRequirement,
+        client_builder: &BaseClientBuilder<'_>,
+        cache: &Cache,
+    ) -> Result<Requirement> {
         // If the requirement is a wheel, extract the package name from the wheel filename.
         //
         // Ex) `anyio-4.3.0-py3-none-any.whl`
@@ -584,135 +1390,324 @@ impl NamedRequirements {
         }
 
         // Otherwise, download and/or extract the source archive.
-        if Scheme::parse(requirement.url.scheme()) == Some(Scheme::File) {
-            let path = requirement.url.to_file_path().map_err(|()| {
-                anyhow::anyhow!("Unable to convert file URL to path: {requirement}")
-            })?;
+        let name = match Scheme::parse(requirement.url.scheme()) {
+            Some(Scheme::File) => {
+                let path = requirement.url.to_file_path().map_err(|()| {
+                    anyhow::anyhow!("Unable to convert file URL to path: {requirement}")
+                })?;
 
-            if !path.exists() {
-                return Err(anyhow::anyhow!(
-                    "Unnamed requirement at {path} not found",
-                    path = path.simplified_display()
-                ));
+                if !path.exists() {
+                    return Err(anyhow::anyhow!(
+                        "Unnamed requirement at {path} not found",
+                        path = path.simplified_display()
+                    ));
+                }
+
+                if path.is_dir() {
+                    name_from_directory(&path)
+                } else {
+                    let file = fs_err::tokio::File::open(&path).await?;
+                    name_from_archive(tokio::io::BufReader::new(file))
+                        .await
+                        .with_context(|| {
+                            format!(
+                                "Failed to read the archive at: {}",
+                                path.simplified_display()
+                            )
+                        })?
+                }
+            }
+            Some(Scheme::Http | Scheme::Https) => {
+                name_from_remote_archive(&requirement.url.to_url(), client_builder, cache).await?
             }
+            _ => None,
+        };
 
-            // Attempt to read a `PKG-INFO` from the directory.
-            if let Some(metadata) = fs_err::read(path.join("PKG-INFO"))
-                .ok()
-                .and_then(|contents| Metadata10::parse_pkg_info(&contents).ok())
-            {
-                debug!(
-                    "Found PKG-INFO metadata for {path} ({name})",
-                    path = path.display(),
-                    name = metadata.name
-                );
-                return Ok(Requirement {
-                    name: metadata.name,
-                    extras: requirement.extras,
-                    version_or_url: Some(VersionOrUrl::Url(requirement.url)),
-                    marker: requirement.marker,
-                });
+        if let Some(name) = name {
+            return Ok(Requirement {
+                name,
+                extras: requirement.extras,
+                version_or_url: Some(VersionOrUrl::Url(requirement.url)),
+                marker: requirement.marker,
+            });
+        }
+
+        Err(anyhow::anyhow!(
+            "Unable to infer package name for the unnamed requirement: {requirement}"
+        ))
+    }
+}
+
+/// Normalize the hashes provided for a package to the `sha256:{digest}` form reported by the
+/// index, rejecting any unsupported algorithms.
+fn normalize_hashes(name: &PackageName, hashes: &[String]) -> Result<Vec<String>> {
+    hashes
+        .iter()
+        .map(|hash| match hash.split_once(':') {
+            Some((algorithm, digest)) if algorithm.eq_ignore_ascii_case("sha256") => {
+                Ok(format!("sha256:{}", digest.to_ascii_lowercase()))
             }
+            _ => Err(anyhow::anyhow!(
+                "Unsupported hash for `{name}`: `{hash}` (only `sha256` hashes are supported)"
+            )),
+        })
+        .collect()
+}
 
-            // Attempt to read a `pyproject.toml` file.
-            if let Some(pyproject) = fs_err::read_to_string(path.join("pyproject.toml"))
-                .ok()
-                .and_then(|contents| toml::from_str::<PyProjectToml>(&contents).ok())
-            {
-                // Read PEP 621 metadata from the `pyproject.toml`.
-                if let Some(project) = pyproject.project {
+/// Return the path to the local directory referenced by an unnamed requirement, if any.
+fn unnamed_directory(requirement: &UnnamedRequirement) -> Option<PathBuf> {
+    if !matches!(Scheme::parse(requirement.url.scheme()), Some(Scheme::File)) {
+        return None;
+    }
+    let path = requirement.url.to_file_path().ok()?;
+    path.is_dir().then_some(path)
+}
+
+/// Infer the package name of the project in a local directory from its static metadata (i.e., a
+/// `PKG-INFO`, `pyproject.toml`, `setup.cfg`, or `setup.py`).
+fn name_from_directory(path: &Path) -> Option<PackageName> {
+    // Attempt to read a `PKG-INFO` from the directory.
+    if let Some(metadata) = fs_err::read(path.join("PKG-INFO"))
+        .ok()
+        .and_then(|contents| Metadata10::parse_pkg_info(&contents).ok())
+    {
+        debug!(
+            "Found PKG-INFO metadata for {path} ({name})",
+            path = path.display(),
+            name = metadata.name
+        );
+        return Some(metadata.name);
+    }
+
+    // Attempt to read a `pyproject.toml` file.
+    if let Some(pyproject) = fs_err::read_to_string(path.join("pyproject.toml"))
+        .ok()
+        .and_then(|contents| toml::from_str::<PyProjectToml>(&contents).ok())
+    {
+        // Read PEP 621 metadata from the `pyproject.toml`.
+        if let Some(project) = pyproject.project {
+            debug!(
+                "Found PEP 621 metadata for {path} in `pyproject.toml` ({name})",
+                path = path.display(),
+                name = project.name
+            );
+            return Some(project.name);
+        }
+
+        // Read Poetry-specific metadata from the `pyproject.toml`.
+        if let Some(tool) = pyproject.tool {
+            if let Some(poetry) = tool.poetry {
+                if let Some(name) = poetry.name {
                     debug!(
-                        "Found PEP 621 metadata for {path} in `pyproject.toml` ({name})",
+                        "Found Poetry metadata for {path} in `pyproject.toml` ({name})",
                         path = path.display(),
-                        name = project.name
+                        name = name
                     );
-                    return Ok(Requirement {
-                        name: project.name,
-                        extras: requirement.extras,
-                        version_or_url: Some(VersionOrUrl::Url(requirement.url)),
-                        marker: requirement.marker,
-                    });
+                    return Some(name);
                 }
+            }
+        }
+    }
 
-                // Read Poetry-specific metadata from the `pyproject.toml`.
-                if let Some(tool) = pyproject.tool {
-                    if let Some(poetry) = tool.poetry {
-                        if let Some(name) = poetry.name {
-                            debug!(
-                                "Found Poetry metadata for {path} in `pyproject.toml` ({name})",
-                                path = path.display(),
-                                name = name
-                            );
-                            return Ok(Requirement {
-                                name,
-                                extras: requirement.extras,
-                                version_or_url: Some(VersionOrUrl::Url(requirement.url)),
-                                marker: requirement.marker,
-                            });
-                        }
-                    }
+    // Attempt to read a `setup.cfg` from the directory.
+    if let Some(setup_cfg) = fs_err::read_to_string(path.join("setup.cfg"))
+        .ok()
+        .and_then(|contents| {
+            let mut ini = Ini::new_cs();
+            ini.set_multiline(true);
+            ini.read(contents).ok()
+        })
+    {
+        if let Some(section) = setup_cfg.get("metadata") {
+            if let Some(Some(name)) = section.get("name") {
+                if let Ok(name) = PackageName::from_str(name) {
+                    debug!(
+                        "Found setuptools metadata for {path} in `setup.cfg` ({name})",
+                        path = path.display(),
+                        name = name
+                    );
+                    return Some(name);
                 }
             }
+        }
+    }
+
+    // Attempt to read a `setup.py` from the directory.
+    if let Ok(setup_py) = fs_err::read_to_string(path.join("setup.py")) {
+        static SETUP_PY_NAME: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r#"name\s*[=:]\s*['"](?P<name>[^'"]+)['"]"#).unwrap());
+
+        if let Some(name) = SETUP_PY_NAME
+            .captures(&setup_py)
+            .and_then(|captures| captures.name("name"))
+            .map(|name| name.as_str())
+        {
+            if let Ok(name) = PackageName::from_str(name) {
+                debug!(
+                    "Found setuptools metadata for {path} in `setup.py` ({name})",
+                    path = path.display(),
+                    name = name
+                );
+                return Some(name);
+            }
+        }
+    }
 
-            // Attempt to read a `setup.cfg` from the directory.
-            if let Some(setup_cfg) = fs_err::read_to_string(path.join("setup.cfg"))
-                .ok()
-                .and_then(|contents| {
-                    let mut ini = Ini::new_cs();
-                    ini.set_multiline(true);
-                    ini.read(contents).ok()
-                })
-            {
-                if let Some(section) = setup_cfg.get("metadata") {
-                    if let Some(Some(name)) = section.get("name") {
-                        if let Ok(name) = PackageName::from_str(name) {
-                            debug!(
-                                "Found setuptools metadata for {path} in `setup.cfg` ({name})",
-                                path = path.display(),
-                                name = name
-                            );
-                            return Ok(Requirement {
-                                name,
-                                extras: requirement.extras,
-                                version_or_url: Some(VersionOrUrl::Url(requirement.url)),
-                                marker: requirement.marker,
-                            });
-                        }
-                    }
-                }
+    None
+}
+
+/// Infer the package name of a remote wheel or source distribution by streaming the archive.
+///
+/// The inferred name is cached (subject to the server's caching headers), such that the archive
+/// is only downloaded for naming once.
+async fn name_from_remote_archive(
+    url: &Url,
+    client_builder: &BaseClientBuilder<'_>,
+    cache: &Cache,
+) -> Result<Option<PackageName>> {
+    let cache_entry = cache.entry(
+        CacheBucket::BuiltWheels,
+        WheelCache::Url(url).built_wheel_dir(""),
+        "name.msgpack",
+    );
+    let cache_control = CacheControl::from(cache.freshness(&cache_entry, None)?);
+
+    let client = CachedClient::new(client_builder.clone().build());
+    let req = client
+        .uncached()
+        .for_host(url)
+        .get(url.clone())
+        // Request an uncompressed response, as when downloading any other archive.
+        .header("accept-encoding", "identity")
+        .build()?;
+
+    // Unpack the archive as it's downloaded, rather than buffering it in memory.
+    debug!("Downloading unnamed requirement to infer its name: {url}");
+    client
+        .get_serde(req, &cache_entry, cache_control, |response| async {
+            let reader = response
+                .bytes_stream()
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
+                .into_async_read();
+            name_from_archive(tokio::io::BufReader::new(reader.compat()))
+                .await
+                .with_context(|| format!("Failed to read the archive at: {url}"))
+        })
+        .await
+        .map_err(|err| match err {
+            CachedClientError::Client(err) => {
+                anyhow::Error::from(err).context(format!("Failed to download: {url}"))
             }
+            CachedClientError::Callback(err) => err,
+        })
+}
+
+/// Infer the package name of a wheel or source distribution from the contents of the archive,
+/// for archives whose filenames don't follow the standard naming conventions.
+///
+/// The archive format is detected from its contents, rather than its filename, since the filename
+/// may not include an extension (e.g., `https://example.com/download?package=anyio`).
+async fn name_from_archive(
+    mut reader: impl tokio::io::AsyncBufRead + Unpin,
+) -> Result<Option<PackageName>> {
+    let temp_dir = tempfile::tempdir()?;
 
-            // Attempt to read a `setup.py` from the directory.
-            if let Ok(setup_py) = fs_err::read_to_string(path.join("setup.py")) {
-                static SETUP_PY_NAME: Lazy<Regex> =
-                    Lazy::new(|| Regex::new(r#"name\s*[=:]\s*['"](?P<name>[^'"]+)['"]"#).unwrap());
+    // Read the first few bytes, without consuming them, to detect the archive format.
+    let magic = reader
+        .fill_buf()
+        .await?
+        .iter()
+        .take(4)
+        .copied()
+        .collect::<Vec<_>>();
+    if magic.starts_with(b"PK\x03\x04") {
+        uv_extract::stream::unzip(reader, temp_dir.path()).await?;
 
-                if let Some(name) = SETUP_PY_NAME
-                    .captures(&setup_py)
-                    .and_then(|captures| captures.name("name"))
-                    .map(|name| name.as_str())
+        // A wheel contains a `.dist-info` directory at the top level.
+        for entry in fs_err::read_dir(temp_dir.path())? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("dist-info"))
+            {
+                if let Some(metadata) = fs_err::read(path.join("METADATA"))
+                    .ok()
+                    .and_then(|contents| Metadata10::parse_pkg_info(&contents).ok())
                 {
-                    if let Ok(name) = PackageName::from_str(name) {
-                        debug!(
-                            "Found setuptools metadata for {path} in `setup.py` ({name})",
-                            path = path.display(),
-                            name = name
-                        );
-                        return Ok(Requirement {
-                            name,
-                            extras: requirement.extras,
-                            version_or_url: Some(VersionOrUrl::Url(requirement.url)),
-                            marker: requirement.marker,
-                        });
-                    }
+                    debug!("Found wheel metadata in archive ({})", metadata.name);
+                    return Ok(Some(metadata.name));
                 }
             }
         }
+    } else if magic.starts_with(&[0x1f, 0x8b]) {
+        uv_extract::stream::untar(reader, temp_dir.path()).await?;
+    } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        uv_extract::stream::untar_zst(reader, temp_dir.path()).await?;
+    } else {
+        return Err(anyhow::anyhow!(
+            "Expected a `.zip`, `.tar.gz`, or `.tar.zst` archive"
+        ));
+    }
 
-        Err(anyhow::anyhow!(
-            "Unable to infer package name for the unnamed requirement: {requirement}"
-        ))
+    // A source distribution contains a single top-level directory.
+    let root = uv_extract::strip_component(temp_dir.path())?;
+    Ok(name_from_directory(&root))
+}
+
+/// Return the local directory to which a requirement points, if any (e.g., for
+/// `foo @ file:///path/to/foo`).
+fn local_directory(requirement: &Requirement) -> Option<PathBuf> {
+    let Some(VersionOrUrl::Url(url)) = &requirement.version_or_url else {
+        return None;
+    };
+    if url.scheme() != "file" {
+        return None;
+    }
+    let path = url.to_file_path().ok()?;
+    path.is_dir().then_some(path)
+}
+
+/// Read the dependencies declared in `project.dependencies` of the `pyproject.toml` in the given
+/// directory, resolving any relative paths against the directory itself.
+///
+/// Hatch's `{root:uri}` context variable (as in `bar @ {root:uri}/../bar`) is expanded to the
+/// URL of the directory.
+fn read_local_dependencies(directory: &Path) -> Result<Vec<Requirement>> {
+    let pyproject_toml = directory.join("pyproject.toml");
+    if !pyproject_toml.is_file() {
+        return Ok(vec![]);
     }
+
+    let contents = fs_err::read_to_string(&pyproject_toml)?;
+    let table = toml::from_str::<toml::Table>(&contents)
+        .with_context(|| format!("Failed to parse `{}`", pyproject_toml.user_display()))?;
+    let Some(dependencies) = table
+        .get("project")
+        .and_then(|project| project.get("dependencies"))
+        .and_then(toml::Value::as_array)
+    else {
+        return Ok(vec![]);
+    };
+
+    dependencies
+        .iter()
+        .filter_map(toml::Value::as_str)
+        .map(|dependency| {
+            let expanded = if dependency.contains("{root:uri}") {
+                let root = Url::from_directory_path(directory)
+                    .map_err(|()| anyhow::anyhow!("Invalid path: {}", directory.display()))?;
+                Cow::Owned(dependency.replace("{root:uri}", root.as_str().trim_end_matches('/')))
+            } else {
+                Cow::Borrowed(dependency)
+            };
+            Requirement::parse(&expanded, directory).with_context(|| {
+                format!(
+                    "Failed to parse dependency `{dependency}` in `{}`",
+                    pyproject_toml.user_display()
+                )
+            })
+        })
+        .collect()
 }
 
 /// A pyproject.toml as specified in PEP 517.
@@ -733,6 +1728,7 @@ struct Project {
 #[serde(rename_all = "kebab-case")]
 struct Tool {
     poetry: Option<ToolPoetry>,
+    uv: Option<ToolUv>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -740,3 +1736,77 @@ struct Tool {
 struct ToolPoetry {
     name: Option<PackageName>,
 }
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct ToolUv {
+    override_dependencies: Option<Vec<Requirement>>,
+    index: Option<Vec<ToolUvIndex>>,
+    sources: Option<BTreeMap<PackageName, ToolUvSource>>,
+}
+
+impl ToolUv {
+    /// Resolve the named indexes in `tool.uv.index` and the packages pinned to them in
+    /// `tool.uv.sources`.
+    ///
+    /// Returns the URLs of the non-explicit indexes, which should be searched for all packages,
+    /// along with the index to which each pinned package is restricted.
+    fn indexes(self, path: &Path) -> Result<(Vec<IndexUrl>, FxHashMap<PackageName, IndexUrl>)> {
+        let mut named = FxHashMap::default();
+        let mut extra_index_urls = Vec::new();
+        for index in self.index.unwrap_or_default() {
+            let url = IndexUrl::from_str(&index.url).with_context(|| {
+                format!(
+                    "Invalid URL for index `{}` in {}",
+                    index.name,
+                    path.user_display()
+                )
+            })?;
+            if !index.explicit {
+                extra_index_urls.push(url.clone());
+            }
+            if named.insert(index.name.clone(), url).is_some() {
+                return Err(anyhow::anyhow!(
+                    "Index `{}` is defined multiple times in {}",
+                    index.name,
+                    path.user_display()
+                ));
+            }
+        }
+
+        let mut pinned_indexes = FxHashMap::default();
+        for (package_name, source) in self.sources.unwrap_or_default() {
+            let Some(url) = named.get(&source.index) else {
+                return Err(anyhow::anyhow!(
+                    "Package `{package_name}` references an undefined index `{}` in {} (hint: define it in `tool.uv.index`)",
+                    source.index,
+                    path.user_display()
+                ));
+            };
+            pinned_indexes.insert(package_name, url.clone());
+        }
+
+        Ok((extra_index_urls, pinned_indexes))
+    }
+}
+
+/// A named index, as declared in `tool.uv.index`.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct ToolUvIndex {
+    /// The name of the index, as referenced in `tool.uv.sources`.
+    name: String,
+    /// The URL of the index.
+    url: String,
+    /// Whether the index should only be used for the packages that are explicitly pinned to it.
+    #[serde(default)]
+    explicit: bool,
+}
+
+/// The source for a package, as declared in `tool.uv.sources`.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct ToolUvSource {
+    /// The name of the index from which the package must be fetched.
+    index: String,
+}
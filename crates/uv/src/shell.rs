@@ -71,8 +71,8 @@ fn parse_shell_from_path(path: &Path) -> Option<Shell> {
         "bash" => Some(Shell::Bash),
         "zsh" => Some(Shell::Zsh),
         "fish" => Some(Shell::Fish),
-        "csh" => Some(Shell::Csh),
-        "powershell" | "powershell_ise" => Some(Shell::Powershell),
+        "csh" | "tcsh" => Some(Shell::Csh),
+        "powershell" | "powershell_ise" | "pwsh" => Some(Shell::Powershell),
         _ => None,
     }
 }
@@ -0,0 +1,58 @@
+//! The file formats from which requirements can be read (e.g., `requirements.txt` or
+//! `pyproject.toml`).
+//!
+//! A file's format is detected from its name, unless overridden with `--input-format` (e.g., for a
+//! `pyproject.toml` that isn't named `pyproject.toml`). Supporting a new format (e.g., a lockfile
+//! written by another tool) requires adding a variant, along with its detection and parsing.
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use uv_client::Connectivity;
+
+use crate::requirements::{ExtrasSpecification, RequirementsSpecification};
+
+/// A file format from which requirements can be read.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum SourceFormat {
+    /// A `requirements.txt`-style file, as accepted by pip (e.g., `requirements.in` or
+    /// `constraints.txt`).
+    #[value(name = "requirements.txt")]
+    RequirementsTxt,
+    /// A `pyproject.toml` file, from which the `project.dependencies` (and any requested
+    /// `project.optional-dependencies`) are read.
+    #[value(name = "pyproject.toml")]
+    PyprojectToml,
+}
+
+impl SourceFormat {
+    /// Detect the format of the file at the given path, falling back to `requirements.txt`.
+    ///
+    /// `requirements.txt` files are conventionally named anything (e.g., `requirements.in`,
+    /// `dev.txt`, or `-` for stdin), so that format is the fallback rather than detected.
+    pub(crate) fn detect(path: &Path) -> Self {
+        if path.ends_with("pyproject.toml") {
+            Self::PyprojectToml
+        } else {
+            Self::RequirementsTxt
+        }
+    }
+
+    /// Read the requirements, constraints, and index locations from a file in this format.
+    pub(crate) async fn read(
+        self,
+        path: &Path,
+        extras: &ExtrasSpecification<'_>,
+        connectivity: Connectivity,
+    ) -> Result<RequirementsSpecification> {
+        match self {
+            Self::RequirementsTxt => {
+                RequirementsSpecification::from_requirements_txt(path, connectivity).await
+            }
+            Self::PyprojectToml => {
+                RequirementsSpecification::from_pyproject_toml(path, extras).await
+            }
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Discovery of workspaces, as declared via `tool.uv.workspace` in a `pyproject.toml`.
+//!
+//! A workspace is a collection of projects that live in a single repository and are developed
+//! together. The workspace root declares its members as a set of globs (e.g., `packages/*`);
+//! dependencies between members are resolved to the members' local sources, rather than fetched
+//! from an index.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use tracing::debug;
+
+use pep508_rs::{Requirement, VersionOrUrl};
+use requirements_txt::EditableRequirement;
+use uv_fs::Simplified;
+use uv_normalize::{ExtraName, PackageName};
+
+/// A workspace member's `pyproject.toml`, limited to the fields that are relevant to the
+/// workspace.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct PyProjectToml {
+    project: Option<Project>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct Project {
+    name: PackageName,
+    dependencies: Option<Vec<Requirement>>,
+    optional_dependencies: Option<BTreeMap<ExtraName, Vec<Requirement>>>,
+}
+
+/// The workspace definition, as declared in `tool.uv.workspace`.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct ToolUvWorkspace {
+    /// Globs, relative to the workspace root, that match the member directories.
+    members: Option<Vec<String>>,
+    /// Globs, relative to the workspace root, that match directories to exclude from `members`.
+    exclude: Option<Vec<String>>,
+}
+
+/// A project that's part of a workspace.
+#[derive(Debug, Clone)]
+pub(crate) struct WorkspaceMember {
+    /// The directory containing the member's `pyproject.toml`.
+    root: PathBuf,
+    /// The member's dependencies, as declared in `project.dependencies`.
+    dependencies: Vec<Requirement>,
+    /// The member's optional dependencies, as declared in `project.optional-dependencies`.
+    optional_dependencies: BTreeMap<ExtraName, Vec<Requirement>>,
+}
+
+impl WorkspaceMember {
+    /// The directory containing the member's `pyproject.toml`.
+    pub(crate) fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The extras that the member declares in `project.optional-dependencies`.
+    pub(crate) fn extras(&self) -> impl Iterator<Item = &ExtraName> {
+        self.optional_dependencies.keys()
+    }
+
+    /// The member's dependencies, including those for all of its extras.
+    pub(crate) fn requirements(&self) -> impl Iterator<Item = &Requirement> {
+        self.dependencies
+            .iter()
+            .chain(self.optional_dependencies.values().flatten())
+    }
+}
+
+/// A workspace, consisting of a root directory and the projects that it contains.
+#[derive(Debug, Clone)]
+pub(crate) struct Workspace {
+    /// The directory containing the workspace's `pyproject.toml`.
+    root: PathBuf,
+    /// The members of the workspace, keyed by package name.
+    members: BTreeMap<PackageName, WorkspaceMember>,
+}
+
+impl Workspace {
+    /// Find the workspace that contains the project in the given directory, if any.
+    ///
+    /// Searches the directory and its ancestors for a `pyproject.toml` that declares a
+    /// `tool.uv.workspace`. The project is only considered part of the workspace if it's the
+    /// workspace root or one of its members.
+    pub(crate) fn discover(project: &Path) -> Result<Option<Self>> {
+        let project = fs_err::canonicalize(project)?;
+
+        for ancestor in project.ancestors() {
+            let pyproject_toml = ancestor.join("pyproject.toml");
+            if !pyproject_toml.is_file() {
+                continue;
+            }
+
+            // Ignore any `pyproject.toml` files that aren't valid TOML, or that don't declare a
+            // workspace; they may belong to unrelated projects higher up the tree.
+            let contents = fs_err::read_to_string(&pyproject_toml)?;
+            let Ok(table) = toml::from_str::<toml::Table>(&contents) else {
+                continue;
+            };
+            let Some(workspace) = table
+                .get("tool")
+                .and_then(|tool| tool.get("uv"))
+                .and_then(|uv| uv.get("workspace"))
+            else {
+                continue;
+            };
+            let workspace = ToolUvWorkspace::deserialize(workspace.clone()).with_context(|| {
+                format!(
+                    "Invalid `tool.uv.workspace` in `{}`",
+                    pyproject_toml.user_display()
+                )
+            })?;
+
+            let workspace = Self::from_definition(ancestor, &workspace)?;
+            if ancestor == project
+                || workspace
+                    .members
+                    .values()
+                    .any(|member| member.root == project)
+            {
+                debug!(
+                    "Found workspace at: {} ({} member{})",
+                    workspace.root.user_display(),
+                    workspace.members.len(),
+                    if workspace.members.len() == 1 {
+                        ""
+                    } else {
+                        "s"
+                    }
+                );
+                return Ok(Some(workspace));
+            }
+
+            debug!(
+                "Project at {} is not a member of the workspace at: {}",
+                project.user_display(),
+                ancestor.user_display()
+            );
+            return Ok(None);
+        }
+
+        Ok(None)
+    }
+
+    /// Find the root directory for the project in the given directory: the root of the
+    /// enclosing workspace, if any, or otherwise the directory itself.
+    pub(crate) fn project_root(project: &Path) -> Result<PathBuf> {
+        Ok(match Self::discover(project)? {
+            Some(workspace) => workspace.root,
+            None => project.to_path_buf(),
+        })
+    }
+
+    /// Expand the member globs in a `tool.uv.workspace` definition.
+    fn from_definition(root: &Path, workspace: &ToolUvWorkspace) -> Result<Self> {
+        let exclude = workspace
+            .exclude
+            .iter()
+            .flatten()
+            .map(|pattern| {
+                glob::Pattern::new(&root.join(pattern).to_string_lossy())
+                    .with_context(|| format!("Invalid `tool.uv.workspace.exclude` glob: {pattern}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut members = BTreeMap::new();
+        for pattern in workspace.members.iter().flatten() {
+            let paths = glob::glob(&root.join(pattern).to_string_lossy())
+                .with_context(|| format!("Invalid `tool.uv.workspace.members` glob: {pattern}"))?;
+            for path in paths {
+                let path = path?;
+                if !path.is_dir() || exclude.iter().any(|exclude| exclude.matches_path(&path)) {
+                    continue;
+                }
+
+                let pyproject_toml = path.join("pyproject.toml");
+                let contents = fs_err::read_to_string(&pyproject_toml).with_context(|| {
+                    format!(
+                        "Workspace member `{}` is missing a `pyproject.toml`",
+                        path.user_display()
+                    )
+                })?;
+                let pyproject = toml::from_str::<PyProjectToml>(&contents).with_context(|| {
+                    format!("Failed to parse `{}`", pyproject_toml.user_display())
+                })?;
+                let Some(project) = pyproject.project else {
+                    return Err(anyhow!(
+                        "Workspace member `{}` is missing a `project.name` in its `pyproject.toml`",
+                        path.user_display()
+                    ));
+                };
+
+                let member = WorkspaceMember {
+                    root: fs_err::canonicalize(&path)?,
+                    dependencies: project.dependencies.unwrap_or_default(),
+                    optional_dependencies: project.optional_dependencies.unwrap_or_default(),
+                };
+                if let Some(existing) = members.insert(project.name.clone(), member) {
+                    return Err(anyhow!(
+                        "Multiple workspace members are named `{}`: `{}` and `{}`",
+                        project.name,
+                        existing.root.user_display(),
+                        path.user_display()
+                    ));
+                }
+            }
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            members,
+        })
+    }
+
+    /// The directory containing the workspace's `pyproject.toml`.
+    pub(crate) fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The members of the workspace, keyed by package name.
+    pub(crate) fn members(&self) -> &BTreeMap<PackageName, WorkspaceMember> {
+        &self.members
+    }
+
+    /// Returns `true` if the requirement should be satisfied by a workspace member, i.e., if it
+    /// names a member and isn't pinned to a URL.
+    pub(crate) fn is_member_requirement(&self, requirement: &Requirement) -> bool {
+        self.members.contains_key(&requirement.name)
+            && !matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_)))
+    }
+
+    /// Return the editable requirements for the workspace members that are required by the given
+    /// requirements, including any members that those members depend on in turn (via their
+    /// dependencies or their optional dependencies).
+    ///
+    /// The editables are only the local sources for the members: the requirements themselves
+    /// (along with their markers and version specifiers) determine whether each member is
+    /// installed.
+    pub(crate) fn editables<'a>(
+        &self,
+        requirements: impl IntoIterator<Item = &'a Requirement>,
+    ) -> Result<Vec<EditableRequirement>> {
+        let mut queue = requirements
+            .into_iter()
+            .filter(|requirement| self.is_member_requirement(requirement))
+            .collect::<VecDeque<_>>();
+
+        // Walk the dependencies between members, visiting each member once (so as to terminate
+        // on cycles), but merging the extras requested by each dependent.
+        let mut editables: BTreeMap<&PackageName, EditableRequirement> = BTreeMap::new();
+        while let Some(requirement) = queue.pop_front() {
+            if let Some(editable) = editables.get_mut(&requirement.name) {
+                for extra in &requirement.extras {
+                    if !editable.extras.contains(extra) {
+                        editable.extras.push(extra.clone());
+                    }
+                }
+                continue;
+            }
+
+            let member = &self.members[&requirement.name];
+            debug!(
+                "Using workspace member `{}` from: {}",
+                requirement.name,
+                member.root.user_display()
+            );
+            let mut editable =
+                EditableRequirement::parse(&member.root.to_string_lossy(), &self.root)?;
+            editable.extras.clone_from(&requirement.extras);
+            editable.dependency = true;
+            editables.insert(&requirement.name, editable);
+
+            queue.extend(
+                member
+                    .requirements()
+                    .filter(|requirement| self.is_member_requirement(requirement)),
+            );
+        }
+
+        Ok(editables.into_values().collect())
+    }
+}
@@ -0,0 +1,188 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use common::{uv_snapshot, venv_to_interpreter, TestContext, EXCLUDE_NEWER};
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Create a `uv add` command with options shared across scenarios.
+fn add_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("add")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--python")
+        .arg(venv_to_interpreter(&context.venv))
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Create a `uv remove` command with options shared across scenarios.
+fn remove_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("remove")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Add a package without a version specifier, which should be resolved to a lower bound, while
+/// preserving the existing formatting.
+#[test]
+fn add_unpinned() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = [
+            # Web framework.
+            "flask==3.0.0",
+        ]
+    "#})?;
+
+    uv_snapshot!(context.filters(), add_command(&context).arg("iniconfig"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Added `iniconfig>=2.0.0` to `project.dependencies`
+    "###
+    );
+
+    insta::assert_snapshot!(fs_err::read_to_string(&pyproject_toml)?, @r###"
+    [project]
+    name = "project"
+    version = "0.1.0"
+    dependencies = [
+        # Web framework.
+        "flask==3.0.0",
+        "iniconfig>=2.0.0",
+    ]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Add packages to an extra and a dependency group, replacing an existing entry.
+#[test]
+fn add_optional_and_group() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = []
+
+        [project.optional-dependencies]
+        web = ["flask==2.0.0"]
+    "#})?;
+
+    uv_snapshot!(context.filters(), add_command(&context)
+        .arg("flask==3.0.0")
+        .arg("--optional")
+        .arg("web"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Added `flask==3.0.0` to `project.optional-dependencies.web`
+    "###
+    );
+
+    uv_snapshot!(context.filters(), add_command(&context)
+        .arg("pytest>=8")
+        .arg("--group")
+        .arg("test"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Added `pytest>=8` to `dependency-groups.test`
+    "###
+    );
+
+    insta::assert_snapshot!(fs_err::read_to_string(&pyproject_toml)?, @r###"
+    [project]
+    name = "project"
+    version = "0.1.0"
+    dependencies = []
+
+    [project.optional-dependencies]
+    web = ["flask==3.0.0"]
+
+    [dependency-groups]
+    test = ["pytest>=8"]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Remove a package from the project's dependencies.
+#[test]
+fn remove() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = [
+            "flask==3.0.0",
+            "iniconfig>=2.0.0",
+        ]
+    "#})?;
+
+    uv_snapshot!(context.filters(), remove_command(&context).arg("Flask"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Removed `flask==3.0.0` from `project.dependencies`
+    "###
+    );
+
+    insta::assert_snapshot!(fs_err::read_to_string(&pyproject_toml)?, @r###"
+    [project]
+    name = "project"
+    version = "0.1.0"
+    dependencies = [
+        "iniconfig>=2.0.0",
+    ]
+    "###
+    );
+
+    // Removing a package that isn't a dependency should fail.
+    uv_snapshot!(context.filters(), remove_command(&context).arg("flask"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `flask` is not a dependency in `project.dependencies`
+    "###
+    );
+
+    Ok(())
+}
@@ -289,6 +289,61 @@ pub fn create_venv(
     venv.to_path_buf()
 }
 
+/// Create a disposable copy of the base interpreter underlying the given virtual environment, in
+/// the given directory, returning the path to the copied executable.
+///
+/// Tests can install into the `site-packages` of the copy (e.g., to exercise installs outside of a
+/// virtual environment) without modifying the original installation. To keep things fast, only the
+/// executable is copied; the rest of the standard library is symlinked.
+#[cfg(unix)]
+pub fn copy_base_interpreter(venv: &Path, target: &Path) -> anyhow::Result<PathBuf> {
+    let output = std::process::Command::new(venv_to_interpreter(venv))
+        .arg("-c")
+        .arg("import sys, sysconfig; print(sys.base_prefix); print(sys._base_executable); print(sysconfig.get_python_version())")
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let mut lines = stdout.lines();
+    let (Some(prefix), Some(executable), Some(version)) =
+        (lines.next(), lines.next(), lines.next())
+    else {
+        anyhow::bail!(
+            "Failed to query the base interpreter for: {}",
+            venv.display()
+        );
+    };
+    let prefix = fs_err::canonicalize(prefix)?;
+    let executable = fs_err::canonicalize(executable)?;
+    let stdlib = format!("python{version}");
+
+    // Copy the executable, such that the prefix of the copy resolves to the target directory.
+    let bin = target.join("bin");
+    fs_err::create_dir_all(&bin)?;
+    let python = bin.join(
+        executable
+            .file_name()
+            .expect("Python executable must have a filename"),
+    );
+    fs_err::copy(&executable, &python)?;
+
+    // Symlink the rest of `lib`, except for `site-packages`, which starts out empty.
+    let lib = target.join("lib");
+    fs_err::create_dir_all(lib.join(&stdlib).join("site-packages"))?;
+    for entry in fs_err::read_dir(prefix.join("lib"))? {
+        let entry = entry?;
+        if entry.file_name().to_str() != Some(stdlib.as_str()) {
+            symlink_file(entry.path(), lib.join(entry.file_name()))?;
+        }
+    }
+    for entry in fs_err::read_dir(prefix.join("lib").join(&stdlib))? {
+        let entry = entry?;
+        if entry.file_name().to_str() != Some("site-packages") {
+            symlink_file(entry.path(), lib.join(&stdlib).join(entry.file_name()))?;
+        }
+    }
+
+    Ok(python)
+}
+
 /// Returns the uv binary that cargo built before launching the tests.
 ///
 /// <https://doc.rust-lang.org/cargo/reference/environment-variables.html#environment-variables-cargo-sets-for-crates>
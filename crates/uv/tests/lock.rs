@@ -0,0 +1,494 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use common::{uv_snapshot, TestContext, EXCLUDE_NEWER};
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Create a `uv lock` command with options shared across scenarios.
+fn lock_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("lock")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Create a `uv sync` command with options shared across scenarios.
+fn sync_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("sync")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Create a `uv export` command with options shared across scenarios.
+fn export_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command.arg("export").current_dir(&context.temp_dir);
+    command
+}
+
+/// Lock a project with a single dependency, then install it from the lockfile.
+#[test]
+fn lock_sync() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig"]
+    "#})?;
+
+    uv_snapshot!(context.filters(), lock_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock"))?;
+    insta::assert_snapshot!(lock, @r###"
+    version = 1
+
+    [[distribution]]
+    name = "iniconfig"
+    version = "2.0.0"
+    source = "registry+https://pypi.org/simple"
+    hashes = [
+        "sha256:2d91e135bf72d31a410b17c16da610a82cb55f6b0477d1a902134b24a455b8b3",
+        "sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374",
+    ]
+
+    [distribution.artifact]
+    filename = "iniconfig-2.0.0-py3-none-any.whl"
+    url = "https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl"
+    size = 5892
+    hashes = ["sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374"]
+    "###
+    );
+
+    uv_snapshot!(context.filters(), sync_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Lock a project with `--resolution lowest-direct`, which should select the lowest compatible
+/// version of each direct dependency.
+#[test]
+fn lock_lowest_direct() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig>=1.1.1"]
+    "#})?;
+
+    uv_snapshot!(context.filters(), lock_command(&context)
+        .arg("--resolution")
+        .arg("lowest-direct"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock"))?;
+    assert!(lock.contains(r#"version = "1.1.1""#), "{lock}");
+
+    Ok(())
+}
+
+/// Locking a project without a `pyproject.toml` should fail.
+#[test]
+fn lock_missing_pyproject() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), lock_command(&context), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No `pyproject.toml` found in the current directory
+    "###
+    );
+}
+
+/// Syncing a project without a `uv.lock` should fail.
+#[test]
+fn sync_missing_lock() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), sync_command(&context), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No `uv.lock` found in the current directory; run `uv lock` to create one
+    "###
+    );
+}
+
+/// Install from a lockfile whose locked hashes don't match the distribution, which should fail
+/// rather than installing an unverified distribution.
+#[test]
+fn sync_hash_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig"]
+    "#})?;
+    let lockfile = context.temp_dir.child("uv.lock");
+    lockfile.write_str(indoc! {r#"
+        version = 1
+
+        [[distribution]]
+        name = "iniconfig"
+        version = "2.0.0"
+        source = "registry+https://pypi.org/simple"
+        hashes = ["sha256:0000000000000000000000000000000000000000000000000000000000000000"]
+    "#})?;
+
+    uv_snapshot!(context.filters(), sync_command(&context), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Hash mismatch for `iniconfig==2.0.0`: expected `sha256:0000000000000000000000000000000000000000000000000000000000000000`, found `sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Re-lock and re-sync a project with `--refresh-package`, which should revalidate the cached
+/// data for the given package rather than reusing it.
+#[test]
+fn lock_sync_refresh_package() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig"]
+    "#})?;
+
+    uv_snapshot!(context.filters(), lock_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    uv_snapshot!(context.filters(), lock_command(&context)
+        .arg("--refresh-package")
+        .arg("iniconfig"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    uv_snapshot!(context.filters(), sync_command(&context)
+        .arg("--refresh"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Lock a workspace, which should lock every member (with all of its extras) as an editable,
+/// then install the members from the lockfile.
+#[test]
+fn lock_sync_workspace() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [tool.uv.workspace]
+        members = ["packages/*"]
+    "#})?;
+
+    let alpha = context.temp_dir.child("packages").child("alpha");
+    alpha.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "alpha"
+        version = "0.1.0"
+        dependencies = ["beta"]
+
+        [project.optional-dependencies]
+        test = ["iniconfig"]
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+    "#})?;
+
+    let beta = context.temp_dir.child("packages").child("beta");
+    beta.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "beta"
+        version = "0.1.0"
+        dependencies = []
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+    "#})?;
+
+    let filters = [(r"\(from file://.*\)", "(from [WORKSPACE_DIR])")]
+        .into_iter()
+        .chain(context.filters())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, lock_command(&context).current_dir(alpha.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 2 editables in [TIME]
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    uv_snapshot!(filters, export_command(&context)
+        .arg("--format")
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv export --format requirements.txt
+    -e [TEMP_DIR]/packages/alpha
+    -e [TEMP_DIR]/packages/beta
+    iniconfig==2.0.0
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(filters, sync_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 2 editables in [TIME]
+    Resolved 3 packages in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 3 packages in [TIME]
+     + alpha==0.1.0 (from [WORKSPACE_DIR])
+     + beta==0.1.0 (from [WORKSPACE_DIR])
+     + iniconfig==2.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Export a lockfile to `requirements.txt` (inferred from the output file), with hashes, and to
+/// `pylock.toml`.
+#[test]
+fn export_requirements_txt() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig"]
+    "#})?;
+
+    uv_snapshot!(context.filters(), lock_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    uv_snapshot!(context.filters(), export_command(&context)
+        .arg("--output-file")
+        .arg("requirements.txt")
+        .arg("--generate-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    "###
+    );
+
+    let requirements_txt = fs_err::read_to_string(context.temp_dir.join("requirements.txt"))?;
+    insta::assert_snapshot!(requirements_txt, @r###"
+    # This file was autogenerated by uv via the following command:
+    #    uv export --output-file requirements.txt --generate-hashes
+    iniconfig==2.0.0 \
+        --hash=sha256:2d91e135bf72d31a410b17c16da610a82cb55f6b0477d1a902134b24a455b8b3 \
+        --hash=sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374
+    "###
+    );
+
+    uv_snapshot!(context.filters(), export_command(&context)
+        .arg("--format")
+        .arg("pylock.toml")
+        .arg("--generate-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    lock-version = "1.0"
+    created-by = "uv"
+
+    [[packages]]
+    name = "iniconfig"
+    version = "2.0.0"
+    index = "https://pypi.org/simple"
+
+    [[packages.wheels]]
+    name = "iniconfig-2.0.0-py3-none-any.whl"
+    url = "https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl"
+    size = 5892
+
+    [packages.wheels.hashes]
+    sha256 = "b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374"
+
+    ----- stderr -----
+    "###
+    );
+
+    // A lockfile written without artifacts can't be exported to `pylock.toml`, which requires a
+    // source for every package.
+    context.temp_dir.child("uv.lock").write_str(indoc! {r#"
+        version = 1
+
+        [[distribution]]
+        name = "iniconfig"
+        version = "2.0.0"
+        source = "registry+https://pypi.org/simple"
+    "#})?;
+
+    uv_snapshot!(context.filters(), export_command(&context)
+        .arg("--format")
+        .arg("pylock.toml"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Locked distribution `iniconfig` is missing an artifact; re-run `uv lock` to update it
+    "###
+    );
+
+    Ok(())
+}
+
+/// Export a lockfile in the style of `pip-compile`, with `# via` annotations.
+#[test]
+fn export_pip_tools() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["requests"]
+    "#})?;
+
+    uv_snapshot!(context.filters(), lock_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 5 packages in [TIME]
+    "###
+    );
+
+    uv_snapshot!(context.filters(), export_command(&context)
+        .arg("--format")
+        .arg("pip-tools"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #
+    # This file is autogenerated by uv via the following command:
+    #
+    #    uv export --format pip-tools
+    #
+    certifi==2023.11.17
+        # via requests
+    charset-normalizer==3.3.2
+        # via requests
+    idna==3.4
+        # via requests
+    requests==2.31.0
+    urllib3==2.1.0
+        # via requests
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
@@ -191,6 +191,178 @@ dependencies = [
     Ok(())
 }
 
+/// Resolve a `pyproject.toml`-formatted file that isn't named `pyproject.toml`, by providing its
+/// format explicitly.
+#[test]
+fn compile_input_format() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let project_toml = context.temp_dir.child("project.toml");
+    project_toml.write_str(
+        r#"[build-system]
+requires = ["setuptools", "wheel"]
+
+[project]
+name = "project"
+dependencies = [
+    "anyio==3.7.0",
+]
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("project.toml")
+            .arg("--input-format")
+            .arg("pyproject.toml"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z project.toml --input-format pyproject.toml
+    anyio==3.7.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    uv_snapshot!(context.compile()
+            .arg("project.toml")
+            .arg("--input-format")
+            .arg("setup.cfg"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value 'setup.cfg' for '--input-format <INPUT_FORMAT>'
+      [possible values: requirements.txt, pyproject.toml]
+
+    For more information, try '--help'.
+    "###
+    );
+
+    // The format only applies to the given requirements files, not to any constraints files.
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("idna<3.4")?;
+
+    uv_snapshot!(context.compile()
+            .arg("project.toml")
+            .arg("--input-format")
+            .arg("pyproject.toml")
+            .arg("--constraint")
+            .arg("constraints.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z project.toml --input-format pyproject.toml --constraint constraints.txt
+    anyio==3.7.0
+    idna==3.3
+        # via
+        #   -c constraints.txt
+        #   anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compare a resolution against the existing output file with `--diff`, without modifying it.
+#[test]
+fn compile_diff() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    // If the output file doesn't exist, every package is reported as added.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--diff"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+     + anyio==3.7.0
+     + idna==3.4
+     + sniffio==1.3.0
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    `requirements.txt` is out of date (3 added, 0 removed, 0 changed)
+    "###
+    );
+    assert!(!context.temp_dir.child("requirements.txt").exists());
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--quiet"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    "###
+    );
+
+    // The output file is up to date.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--diff"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    `requirements.txt` is up to date
+    "###
+    );
+
+    // Changing the requirements (and pinning a stale package in the output file) produces a
+    // diff, but leaves the output file as-is.
+    requirements_in.write_str("anyio==4.0.0")?;
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    let existing = format!(
+        "{}tomli==2.0.1\n",
+        fs_err::read_to_string(&requirements_txt)?
+    );
+    requirements_txt.write_str(&existing)?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--diff"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+     ~ anyio==3.7.0 -> anyio==4.0.0
+     - tomli==2.0.1
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    `requirements.txt` is out of date (0 added, 1 removed, 1 changed)
+    "###
+    );
+    assert_eq!(fs_err::read_to_string(&requirements_txt)?, existing);
+
+    Ok(())
+}
+
 /// Resolve a package from a `requirements.in` file, with a `constraints.txt` file.
 #[test]
 fn compile_constraints_txt() -> Result<()> {
@@ -212,7 +384,9 @@ fn compile_constraints_txt() -> Result<()> {
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     anyio==3.7.0
     idna==3.3
-        # via anyio
+        # via
+        #   -c constraints.txt
+        #   anyio
     sniffio==1.3.0
         # via anyio
 
@@ -251,6 +425,46 @@ fn compile_constraints_inline() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a package from a `requirements.in` file, with a `-c constraints.txt` directive that
+/// itself includes a nested constraints file. The pin should be annotated with the file that
+/// contains the constraint.
+#[test]
+fn compile_constraints_nested() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0\n-c constraints.txt")?;
+
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("-c base.txt\nsniffio<1.4")?;
+
+    let base_txt = context.temp_dir.child("base.txt");
+    base_txt.write_str("idna<3.4")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    anyio==3.7.0
+    idna==3.3
+        # via
+        #   -c base.txt
+        #   anyio
+    sniffio==1.3.0
+        # via
+        #   -c constraints.txt
+        #   anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a package from a `requirements.in` file, with a `constraints.txt` file that
 /// uses markers.
 #[test]
@@ -278,7 +492,9 @@ fn compile_constraints_markers() -> Result<()> {
     idna==3.4
         # via anyio
     sniffio==1.3.0
-        # via anyio
+        # via
+        #   -c constraints.txt
+        #   anyio
 
     ----- stderr -----
     Resolved 3 packages in [TIME]
@@ -756,126 +972,361 @@ fn compile_python_dev_version() -> Result<()> {
     Ok(())
 }
 
-/// Test that we select the last 3.8 compatible numpy version instead of trying to compile an
-/// incompatible sdist <https://github.com/astral-sh/uv/issues/388>
+/// Resolve a package with a platform-specific dependency for a different target platform.
 #[test]
-fn compile_numpy_py38() -> Result<()> {
-    let context = TestContext::new("3.8");
-
+fn compile_python_platform() -> Result<()> {
+    let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("numpy")?;
+    requirements_in.write_str("tqdm==4.66.1")?;
 
     uv_snapshot!(context.compile()
             .arg("requirements.in")
-            .arg("--no-build"), @r###"
+            .arg("--python-platform")
+            .arg("windows"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-build
-    numpy==1.24.4
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --python-platform windows
+    colorama==0.4.6
+        # via tqdm
+    tqdm==4.66.1
 
     ----- stderr -----
-    Resolved 1 package in [TIME]
+    Resolved 2 packages in [TIME]
     "###
     );
 
     Ok(())
 }
 
-/// Resolve a specific Flask wheel via a URL dependency.
+/// Resolve against an unsupported target platform.
 #[test]
-fn compile_wheel_url_dependency() -> Result<()> {
+fn compile_python_invalid_platform() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl")?;
+    requirements_in.write_str("tqdm==4.66.1")?;
 
     uv_snapshot!(context.compile()
-            .arg("requirements.in"), @r###"
-    success: true
-    exit_code: 0
+            .arg("requirements.in")
+            .arg("--python-platform")
+            .arg("plan9"), @r###"
+    success: false
+    exit_code: 2
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
-    blinker==1.7.0
-        # via flask
-    click==8.1.7
-        # via flask
-    flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl
-    itsdangerous==2.1.2
-        # via flask
-    jinja2==3.1.2
-        # via flask
-    markupsafe==2.1.3
-        # via
-        #   jinja2
-        #   werkzeug
-    werkzeug==3.0.1
-        # via flask
 
     ----- stderr -----
-    Resolved 7 packages in [TIME]
+    error: invalid value 'plan9' for '--python-platform <PYTHON_PLATFORM>': Unsupported platform `plan9`; expected one of `linux`, `macos`, `windows`, or a wheel platform tag (e.g., `manylinux_2_17_x86_64`)
+
+    For more information, try '--help'.
     "###
     );
 
     Ok(())
 }
 
-/// Resolve a specific Flask source distribution via a URL dependency.
-///
-/// Exercises the `prepare_metadata_for_build_wheel` hooks.
+/// Resolve for multiple Python versions, folding the resolutions into a single output with
+/// `python_version` markers on the pins that differ.
 #[test]
-fn compile_sdist_url_dependency() -> Result<()> {
+fn compile_python_versions_matrix() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("flask @ https://files.pythonhosted.org/packages/d8/09/c1a7354d3925a3c6c8cfdebf4245bae67d633ffda1ba415add06ffc839c5/flask-3.0.0.tar.gz")?;
+    requirements_in.write_str("anyio==4.0.0")?;
 
     uv_snapshot!(context.compile()
-            .arg("requirements.in"), @r###"
+            .arg("requirements.in")
+            .arg("--python-versions")
+            .arg("3.10,3.12"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
-    blinker==1.7.0
-        # via flask
-    click==8.1.7
-        # via flask
-    flask @ https://files.pythonhosted.org/packages/d8/09/c1a7354d3925a3c6c8cfdebf4245bae67d633ffda1ba415add06ffc839c5/flask-3.0.0.tar.gz
-    itsdangerous==2.1.2
-        # via flask
-    jinja2==3.1.2
-        # via flask
-    markupsafe==2.1.3
-        # via
-        #   jinja2
-        #   werkzeug
-    werkzeug==3.0.1
-        # via flask
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --python-versions 3.10,3.12
+    anyio==4.0.0
+    exceptiongroup==1.1.3 ; python_version == '3.10'
+        # via anyio
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
 
     ----- stderr -----
-    Resolved 7 packages in [TIME]
+    Resolved 4 packages in [TIME]
     "###
     );
 
     Ok(())
 }
 
-/// Resolve a specific Flask source distribution via a Git HTTPS dependency.
+/// Test that we select the last 3.8 compatible numpy version instead of trying to compile an
+/// incompatible sdist <https://github.com/astral-sh/uv/issues/388>
 #[test]
-#[cfg(feature = "git")]
-fn compile_git_https_dependency() -> Result<()> {
-    let context = TestContext::new("3.12");
-    let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("flask @ git+https://github.com/pallets/flask.git")?;
+fn compile_numpy_py38() -> Result<()> {
+    let context = TestContext::new("3.8");
 
-    // In addition to the standard filters, remove the `main` commit, which will change frequently.
-    let filters: Vec<_> = [(r"@(\d|\w){40}", "@[COMMIT]")]
-        .into_iter()
-        .chain(INSTA_FILTERS.to_vec())
-        .collect();
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("numpy")?;
 
-    uv_snapshot!(filters, context.compile()
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-build"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-build
+    numpy==1.24.4
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Disable source distribution builds via the `UV_NO_BUILD` environment variable, as with
+/// `--no-build`.
+#[test]
+fn compile_no_build_env_var() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // `anyio` is requested as a source distribution, so resolution should fail.
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .env("UV_NO_BUILD", "true"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download and build: anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz
+      Caused by: Building source distributions is disabled
+    "###
+    );
+
+    Ok(())
+}
+
+/// Ignore package dependencies via the `UV_NO_DEPS` environment variable, as with `--no-deps`.
+#[test]
+fn compile_no_deps_env_var() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .env("UV_NO_DEPS", "true"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    anyio==3.7.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a specific Flask wheel via a URL dependency.
+#[test]
+fn compile_wheel_url_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.2
+        # via flask
+    markupsafe==2.1.3
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a specific Flask wheel via a URL dependency with `--generate-hashes`, which should emit
+/// the hash embedded in the URL fragment.
+#[test]
+fn compile_wheel_url_dependency_generate_hashes() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl#sha256=21128f47e4e3b9d597a3e8521a329bf56909b690fcc3fa3e477725aa81367638")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-deps")
+            .arg("--generate-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-deps --generate-hashes
+    flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl#sha256=21128f47e4e3b9d597a3e8521a329bf56909b690fcc3fa3e477725aa81367638 \
+        --hash=sha256:21128f47e4e3b9d597a3e8521a329bf56909b690fcc3fa3e477725aa81367638
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a specific Flask source distribution via a URL dependency.
+///
+/// Exercises the `prepare_metadata_for_build_wheel` hooks.
+#[test]
+fn compile_sdist_url_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask @ https://files.pythonhosted.org/packages/d8/09/c1a7354d3925a3c6c8cfdebf4245bae67d633ffda1ba415add06ffc839c5/flask-3.0.0.tar.gz")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask @ https://files.pythonhosted.org/packages/d8/09/c1a7354d3925a3c6c8cfdebf4245bae67d633ffda1ba415add06ffc839c5/flask-3.0.0.tar.gz
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.2
+        # via flask
+    markupsafe==2.1.3
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a specific Flask source distribution via a URL dependency, constraining the version
+/// of `flit-core` used to build it.
+#[test]
+fn compile_sdist_url_dependency_build_constraint() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask @ https://files.pythonhosted.org/packages/d8/09/c1a7354d3925a3c6c8cfdebf4245bae67d633ffda1ba415add06ffc839c5/flask-3.0.0.tar.gz")?;
+
+    let build_constraints_txt = context.temp_dir.child("build-constraints.txt");
+    build_constraints_txt.write_str("flit-core==3.8.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--build-constraint")
+            .arg("build-constraints.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --build-constraint build-constraints.txt
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask @ https://files.pythonhosted.org/packages/d8/09/c1a7354d3925a3c6c8cfdebf4245bae67d633ffda1ba415add06ffc839c5/flask-3.0.0.tar.gz
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.2
+        # via flask
+    markupsafe==2.1.3
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request an unnamed requirement as a build constraint.
+#[test]
+fn compile_build_constraint_unnamed() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    let build_constraints_txt = context.temp_dir.child("build-constraints.txt");
+    build_constraints_txt.write_str("https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--build-constraint")
+            .arg("build-constraints.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Unnamed requirements are not allowed as build constraints (found: `https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl`)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a specific Flask source distribution via a Git HTTPS dependency.
+#[test]
+#[cfg(feature = "git")]
+fn compile_git_https_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask @ git+https://github.com/pallets/flask.git")?;
+
+    // In addition to the standard filters, remove the `main` commit, which will change frequently.
+    let filters: Vec<_> = [(r"@(\d|\w){40}", "@[COMMIT]")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, context.compile()
             .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
@@ -1122,6 +1573,29 @@ fn compile_git_subdirectory_dependency() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a Git dependency with a subdirectory that doesn't exist in the repository.
+#[test]
+#[cfg(feature = "git")]
+fn compile_git_missing_subdirectory() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("example-pkg-a @ git+https://github.com/pypa/sample-namespace-packages.git@df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45#subdirectory=pkg_resources/pkg_z")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download and build: example-pkg-a @ git+https://github.com/pypa/sample-namespace-packages.git@df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45#subdirectory=pkg_resources/pkg_z
+      Caused by: Source distribution subdirectory not found: `pkg_resources/pkg_z`
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve two packages from a `requirements.in` file with the same Git HTTPS dependency.
 #[test]
 #[cfg(feature = "git")]
@@ -1237,21 +1711,50 @@ fn conflicting_direct_url_dependency() -> Result<()> {
     Ok(())
 }
 
-/// Request Werkzeug via both a version and a URL dependency at _the same_ version, which
-/// should prefer the direct URL dependency.
+/// With `--verbose`, a failed resolution should be followed by the full derivation tree.
 #[test]
-fn compatible_direct_url_dependency() -> Result<()> {
+fn conflict_derivation_tree_verbose() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("werkzeug==2.0.0\nwerkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
+    requirements_in.write_str("flask==3.0.0\nwerkzeug<3")?;
 
-    uv_snapshot!(context.compile()
-            .arg("requirements.in"), @r###"
-    success: true
-    exit_code: 0
-    ----- stdout -----
-    # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    let output = context
+        .compile()
+        .arg("requirements.in")
+        .arg("--verbose")
+        .output()?;
+    assert_eq!(output.status.code(), Some(1));
+
+    let stderr = String::from_utf8(output.stderr)?;
+    let (_, tree) = stderr
+        .split_once("Derivation tree:\n")
+        .context("missing derivation tree")?;
+    assert!(tree.contains("├── "), "{tree}");
+    assert!(tree.contains("└── "), "{tree}");
+
+    // Without `--verbose`, the tree is omitted.
+    let output = context.compile().arg("requirements.in").output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(!stderr.contains("Derivation tree:"), "{stderr}");
+
+    Ok(())
+}
+
+/// Request Werkzeug via both a version and a URL dependency at _the same_ version, which
+/// should prefer the direct URL dependency.
+#[test]
+fn compatible_direct_url_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("werkzeug==2.0.0\nwerkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     werkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl
 
     ----- stderr -----
@@ -1538,7 +2041,9 @@ fn allowed_transitive_url_dependency() -> Result<()> {
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     hatchling-editable @ ${HATCHLING}
     iniconfig @ git+https://github.com/pytest-dev/iniconfig@9cae43103df70bac6fde7b9f35ad11a9f1be0cb4
-        # via hatchling-editable
+        # via
+        #   -c constraints.txt
+        #   hatchling-editable
 
     ----- stderr -----
     Resolved 2 packages in [TIME]
@@ -1575,7 +2080,9 @@ fn allowed_transitive_canonical_url_dependency() -> Result<()> {
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     hatchling-editable @ ${HATCHLING}
     iniconfig @ git+https://github.com/pytest-dev/iniconfig.git@9cae43103df70bac6fde7b9f35ad11a9f1be0cb4
-        # via hatchling-editable
+        # via
+        #   -c constraints.txt
+        #   hatchling-editable
 
     ----- stderr -----
     Resolved 2 packages in [TIME]
@@ -1682,6 +2189,65 @@ optional-dependencies.bar = [
     Ok(())
 }
 
+/// Reject extras that pin the same package to incompatible versions, since they can't be
+/// installed together.
+#[test]
+fn compile_pyproject_toml_mutually_exclusive_extras() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[build-system]
+requires = ["setuptools", "wheel"]
+
+[project]
+name = "project"
+dependencies = ["iniconfig==1.1.1"]
+optional-dependencies.legacy = [
+    "anyio==3.7.0",
+]
+optional-dependencies.modern = [
+    "anyio==4.0.0",
+]
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml")
+            .arg("--all-extras"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Extras `legacy` and `modern` of `project` are mutually exclusive: `legacy` requires `anyio==3.7.0`, but `modern` requires `anyio==4.0.0`
+    "###
+    );
+
+    // Selecting either extra on its own is fine.
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml")
+            .arg("--extra")
+            .arg("modern"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml --extra modern
+    anyio==4.0.0
+    idna==3.4
+        # via anyio
+    iniconfig==1.1.1
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve packages from all optional dependency groups in a `pyproject.toml` file.
 #[test]
 fn compile_does_not_allow_both_extra_and_all_extras() -> Result<()> {
@@ -2149,6 +2715,7 @@ fn compile_yanked_version_direct() -> Result<()> {
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     attrs==21.1.0
+        # yanked (reason: "Installable but not importable on Python 3.4"), allowed explicitly
 
     ----- stderr -----
     Resolved 1 package in [TIME]
@@ -2188,6 +2755,63 @@ fn compile_yanked_version_indirect() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a yanked version of `attrs` indirectly by allowing yanked versions of `attrs`.
+#[test]
+fn compile_yanked_version_allow_yanked() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("attrs>20.3.0,<21.2.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--allow-yanked=attrs"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --allow-yanked=attrs
+    attrs==21.1.0
+        # yanked (reason: "Installable but not importable on Python 3.4"), allowed explicitly
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Retain a yanked version of `attrs` from an existing output file, with a warning.
+#[test]
+fn compile_yanked_version_preference() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("attrs>20.3.0")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("attrs==21.1.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
+    attrs==21.1.0
+        # yanked (reason: "Installable but not importable on Python 3.4"), kept from the existing pin
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    warning: The existing pin `attrs==21.1.0` is yanked (reason: "Installable but not importable on Python 3.4"). Pass `--upgrade-package attrs` to select a different version.
+    "###
+    );
+
+    Ok(())
+}
+
 /// Flask==3.0.0 depends on Werkzeug>=3.0.0. Demonstrate that we can override this
 /// requirement with an incompatible version.
 #[test]
@@ -2232,6 +2856,122 @@ fn override_dependency() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a `pyproject.toml` that declares overrides via `tool.uv.override-dependencies`.
+#[test]
+fn override_dependency_from_pyproject() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[build-system]
+requires = ["setuptools", "wheel"]
+
+[project]
+name = "project"
+dependencies = ["flask==3.0.0"]
+
+[tool.uv]
+override-dependencies = ["werkzeug==2.3.0"]
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask==3.0.0
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.2
+        # via flask
+    markupsafe==2.1.3
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==2.3.0
+        # via flask
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a `pyproject.toml` that pins a package to a named index via `tool.uv.sources`. The
+/// resolution should fail, since the package doesn't exist at the pinned index, and the pinned
+/// package shouldn't fall back to PyPI.
+#[test]
+fn index_pinned_from_pyproject() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "project"
+dependencies = ["anyio<4"]
+
+[[tool.uv.index]]
+name = "pytorch"
+url = "https://download.pytorch.org/whl"
+explicit = true
+
+[tool.uv.sources]
+anyio = { index = "pytorch" }
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because anyio<4 was not found in the package registry and you require
+          anyio<4, we can conclude that the requirements are unsatisfiable.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject a `pyproject.toml` that pins a package to an index that isn't defined.
+#[test]
+fn index_pinned_undefined() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "project"
+dependencies = ["anyio<4"]
+
+[tool.uv.sources]
+anyio = { index = "pytorch" }
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Package `anyio` references an undefined index `pytorch` in pyproject.toml (hint: define it in `tool.uv.index`)
+    "###
+    );
+
+    Ok(())
+}
+
 /// Black==23.10.1 depends on tomli>=1.1.0 for Python versions below 3.11. Demonstrate that we can
 /// override it with a multi-line override.
 #[test]
@@ -2766,7 +3506,7 @@ fn cache_errors_are_non_fatal() -> Result<()> {
     let interpreter_cache = context
         .cache_dir
         .path()
-        .join("interpreter-v0")
+        .join("interpreter-v2")
         .read_dir()?
         .next()
         .context("Expected a python interpreter cache file")??
@@ -3156,24 +3896,36 @@ fn find_links_directory() -> Result<()> {
     Ok(())
 }
 
-/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index.
+/// Compile using `--find-links` with a local directory tree, in which the distributions are nested
+/// in subdirectories.
 #[test]
-fn find_links_url() -> Result<()> {
+fn find_links_directory_nested() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("tqdm")?;
 
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheelhouse = context.temp_dir.child("wheelhouse").child("tqdm");
+    wheelhouse.create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        wheelhouse.join("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+
     uv_snapshot!(context.compile()
             .arg("requirements.in")
             .arg("--no-index")
             .arg("--find-links")
-            .arg("https://download.pytorch.org/whl/torch_stable.html"), @r###"
+            .arg("wheelhouse"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index
-    tqdm==4.64.1
+    tqdm==1000.0.0
 
     ----- stderr -----
     Resolved 1 package in [TIME]
@@ -3183,23 +3935,41 @@ fn find_links_url() -> Result<()> {
     Ok(())
 }
 
-/// Compile using `--find-links` with a URL passed via an environment variable.
+/// Compile using `--find-links` with a local directory tree that contains a symlink back to one of
+/// its ancestors, which is skipped rather than followed indefinitely.
 #[test]
-fn find_links_env_var() -> Result<()> {
+#[cfg(unix)]
+fn find_links_directory_symlink_loop() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("tqdm\n--find-links ${URL}")?;
+    requirements_in.write_str("tqdm")?;
+
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheelhouse = context.temp_dir.child("wheelhouse").child("tqdm");
+    wheelhouse.create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        wheelhouse.join("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+    fs_err::os::unix::fs::symlink(
+        context.temp_dir.child("wheelhouse").path(),
+        wheelhouse.join("loop"),
+    )?;
 
     uv_snapshot!(context.compile()
             .arg("requirements.in")
             .arg("--no-index")
-            .env("URL", "https://download.pytorch.org/whl/torch_stable.html"), @r###"
+            .arg("--find-links")
+            .arg("wheelhouse"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index
-    tqdm==4.64.1
+    tqdm==1000.0.0
 
     ----- stderr -----
     Resolved 1 package in [TIME]
@@ -3209,25 +3979,23 @@ fn find_links_env_var() -> Result<()> {
     Ok(())
 }
 
-/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index,
-/// with the URL itself provided in a `requirements.txt` file.
+/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index.
 #[test]
-fn find_links_requirements_txt() -> Result<()> {
+fn find_links_url() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("-f https://download.pytorch.org/whl/torch_stable.html\ntqdm")?;
+    requirements_in.write_str("tqdm")?;
 
     uv_snapshot!(context.compile()
             .arg("requirements.in")
             .arg("--no-index")
-            .arg("--emit-find-links"), @r###"
+            .arg("--find-links")
+            .arg("https://download.pytorch.org/whl/torch_stable.html"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --emit-find-links
-    --find-links https://download.pytorch.org/whl/torch_stable.html
-
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index
     tqdm==4.64.1
 
     ----- stderr -----
@@ -3238,15 +4006,250 @@ fn find_links_requirements_txt() -> Result<()> {
     Ok(())
 }
 
-/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
-/// Nothing should change.
+/// Compile using `--find-links` with a URL passed via an environment variable.
 #[test]
-fn upgrade_none() -> Result<()> {
+fn find_links_env_var() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("black==23.10.1")?;
+    requirements_in.write_str("tqdm\n--find-links ${URL}")?;
 
-    let requirements_txt = context.temp_dir.child("requirements.txt");
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .env("URL", "https://download.pytorch.org/whl/torch_stable.html"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index
+    tqdm==4.64.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile using `--no-index` and `--find-links` as provided in a `uv.toml` file.
+#[test]
+fn config_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let uv_toml = context.temp_dir.child("uv.toml");
+    uv_toml.write_str(indoc! {r#"
+        no-index = true
+        find-links = ["https://download.pytorch.org/whl/torch_stable.html"]
+    "#})?;
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==4.64.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // Command-line arguments take precedence over the configuration file.
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheelhouse = context.temp_dir.child("wheelhouse");
+    wheelhouse.create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        wheelhouse.join("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--find-links")
+            .arg("wheelhouse"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Ignore a `uv.toml` file with `--no-config`.
+#[test]
+fn config_file_disabled() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let uv_toml = context.temp_dir.child("uv.toml");
+    uv_toml.write_str("no-index = true")?;
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-config"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-config
+    anyio==3.7.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    // The same applies to `UV_NO_CONFIG`.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .env("UV_NO_CONFIG", "true"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    anyio==3.7.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile with a `--find-links` directory whose path contains a space, which must not be split
+/// into multiple locations.
+#[test]
+fn find_links_path_with_space() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheelhouse = context.temp_dir.child("my wheels");
+    wheelhouse.create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        wheelhouse.join("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg("my wheels"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --find-links my wheels
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// An invalid `uv.toml` file is an error.
+#[test]
+fn config_file_invalid() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let uv_toml = context.temp_dir.child("uv.toml");
+    uv_toml.write_str("resolution = \"newest\"")?;
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    let filters = [(r"`.*uv\.toml`", "`[TEMP_DIR]/uv.toml`")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to parse `[TEMP_DIR]/uv.toml`
+      Caused by: TOML parse error at line 1, column 14
+      |
+    1 | resolution = "newest"
+      |              ^^^^^^^^
+    invalid variant: newest
+
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index,
+/// with the URL itself provided in a `requirements.txt` file.
+#[test]
+fn find_links_requirements_txt() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("-f https://download.pytorch.org/whl/torch_stable.html\ntqdm")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--emit-find-links"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --emit-find-links
+    --find-links https://download.pytorch.org/whl/torch_stable.html
+
+    tqdm==4.64.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
+/// Nothing should change.
+#[test]
+fn upgrade_none() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
     requirements_txt.write_str(indoc! {r"
         black==23.10.1
         click==8.1.2
@@ -3269,9 +4272,281 @@ fn upgrade_none() -> Result<()> {
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
+    black==23.10.1
+    click==8.1.2
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.0
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Write the resolution to a `pylock.toml` file, respecting the versions pinned in an existing
+/// `pylock.toml` file.
+#[test]
+fn compile_pylock_toml() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let pylock_toml = context.temp_dir.child("pylock.toml");
+    pylock_toml.write_str(indoc! {r#"
+        lock-version = "1.0"
+        created-by = "uv"
+
+        [[packages]]
+        name = "click"
+        version = "8.1.2"
+        index = "https://pypi.org/simple"
+    "#})?;
+
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("pylock.toml")
+        .assert()
+        .success();
+
+    let lock: toml::Table = toml::from_str(&fs_err::read_to_string(pylock_toml.path())?)?;
+    assert_eq!(lock["lock-version"].as_str(), Some("1.0"));
+    assert_eq!(lock["created-by"].as_str(), Some("uv"));
+
+    let packages = lock["packages"].as_array().context("missing packages")?;
+    let versions = packages
+        .iter()
+        .map(|package| {
+            (
+                package["name"].as_str().unwrap(),
+                package["version"].as_str().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        versions,
+        vec![
+            ("black", "23.10.1"),
+            ("click", "8.1.2"),
+            ("mypy-extensions", "1.0.0"),
+            ("packaging", "23.2"),
+            ("pathspec", "0.11.2"),
+            ("platformdirs", "4.0.0"),
+        ]
+    );
+
+    // Every package should be pinned to a hashed artifact from the index.
+    for package in packages {
+        assert_eq!(package["index"].as_str(), Some("https://pypi.org/simple"));
+        let wheel = &package["wheels"][0];
+        assert!(wheel["url"].as_str().is_some());
+        assert!(wheel["hashes"]["sha256"].as_str().is_some());
+    }
+
+    Ok(())
+}
+
+/// Write the resolved graph as JSON, including the source, hashes, and dependencies of each
+/// package.
+#[test]
+fn compile_json_output() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let output = context
+        .compile()
+        .arg("requirements.in")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--generate-hashes")
+        .output()?;
+    assert!(output.status.success());
+
+    // The output should be valid JSON, without a comment header.
+    let graph: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(graph["version"], 1);
+
+    let packages = graph["packages"].as_array().context("missing packages")?;
+    let names = packages
+        .iter()
+        .map(|package| package["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            "black",
+            "click",
+            "mypy-extensions",
+            "packaging",
+            "pathspec",
+            "platformdirs"
+        ]
+    );
+
+    let black = &packages[0];
+    assert_eq!(black["version"], "23.10.1");
+    assert!(black["source"]
+        .as_str()
+        .is_some_and(|source| source.starts_with("https://files.pythonhosted.org/")));
+    assert!(black["hashes"]
+        .as_array()
+        .is_some_and(|hashes| !hashes.is_empty()));
+    assert_eq!(
+        black["dependencies"],
+        serde_json::json!([
+            "click",
+            "mypy-extensions",
+            "packaging",
+            "pathspec",
+            "platformdirs"
+        ])
+    );
+    assert_eq!(packages[1]["dependencies"], serde_json::json!([]));
+
+    Ok(())
+}
+
+/// Write the resolved graph as TOML.
+#[test]
+fn compile_toml_output() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--format")
+        .arg("toml")
+        .arg("--output-file")
+        .arg("graph.toml")
+        .assert()
+        .success();
+
+    let graph: toml::Table = toml::from_str(&fs_err::read_to_string(
+        context.temp_dir.child("graph.toml").path(),
+    )?)?;
+    let packages = graph["packages"].as_array().context("missing packages")?;
+    assert_eq!(packages.len(), 6);
+    assert_eq!(packages[0]["name"].as_str(), Some("black"));
+    assert_eq!(
+        packages[0]["dependencies"].as_array().map(Vec::len),
+        Some(5)
+    );
+
+    // Hashes are only included with `--generate-hashes`.
+    assert!(packages
+        .iter()
+        .all(|package| package.get("hashes").is_none()));
+
+    Ok(())
+}
+
+/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
+/// Both packages should be upgraded.
+#[test]
+fn upgrade_all() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        # This file was autogenerated by uv via the following command:
+        #    uv pip compile requirements.in --python-version 3.12 --cache-dir [CACHE_DIR]
+        black==23.10.1
+        click==8.1.2
+            # via black
+        mypy-extensions==1.0.0
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+    "})?;
+
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("requirements.txt")
+        .arg("--upgrade"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
+    black==23.10.1
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
+/// Only `click` should be upgraded.
+#[test]
+fn upgrade_package() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        # This file was autogenerated by uv via the following command:
+        #    uv pip compile requirements.in --python-version 3.12 --cache-dir [CACHE_DIR]
+        black==23.10.1
+        click==8.1.2
+            # via black
+        mypy-extensions==1.0.0
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--upgrade-package")
+            .arg("click"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt --upgrade-package click
     black==23.10.1
-    click==8.1.2
+    click==8.1.7
         # via black
     mypy-extensions==1.0.0
         # via black
@@ -3290,43 +4565,52 @@ fn upgrade_none() -> Result<()> {
     Ok(())
 }
 
-/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
-/// Both packages should be upgraded.
+/// Use an existing `pip-compile` resolution for `black==23.10.1`. Upgrading `black` should also
+/// upgrade the packages that were only pinned as dependencies of `black` (per their `# via`
+/// annotations), but not `click`, which was also requested directly.
 #[test]
-fn upgrade_all() -> Result<()> {
+fn upgrade_package_transitive() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("black==23.10.1")?;
+    requirements_in.write_str("black\nclick")?;
 
     let requirements_txt = context.temp_dir.child("requirements.txt");
     requirements_txt.write_str(indoc! {r"
-        # This file was autogenerated by uv via the following command:
-        #    uv pip compile requirements.in --python-version 3.12 --cache-dir [CACHE_DIR]
+        #
+        # This file is autogenerated by pip-compile with Python 3.12
+        # by the following command:
+        #
+        #    pip-compile requirements.in
+        #
         black==23.10.1
+            # via -r requirements.in
         click==8.1.2
-            # via black
+            # via
+            #   -r requirements.in
+            #   black
         mypy-extensions==1.0.0
             # via black
         packaging==23.2
             # via black
         pathspec==0.11.0
             # via black
-        platformdirs==4.0.0
+        platformdirs==3.11.0
             # via black
     "})?;
 
     uv_snapshot!(context.compile()
-        .arg("requirements.in")
-        .arg("--output-file")
-        .arg("requirements.txt")
-        .arg("--upgrade"), @r###"
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--upgrade-package")
+            .arg("black"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
-    black==23.10.1
-    click==8.1.7
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt --upgrade-package black
+    black==23.11.0
+    click==8.1.2
         # via black
     mypy-extensions==1.0.0
         # via black
@@ -3345,10 +4629,10 @@ fn upgrade_all() -> Result<()> {
     Ok(())
 }
 
-/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
-/// Only `click` should be upgraded.
+/// Use an existing resolution for `black==23.10.1`, with a stale version of `click`. Upgrading
+/// `click` with a version bound should upgrade it within that bound.
 #[test]
-fn upgrade_package() -> Result<()> {
+fn upgrade_package_with_specifier() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black==23.10.1")?;
@@ -3375,14 +4659,14 @@ fn upgrade_package() -> Result<()> {
             .arg("--output-file")
             .arg("requirements.txt")
             .arg("--upgrade-package")
-            .arg("click"), @r###"
+            .arg("click<8.1.7"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt --upgrade-package click
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt --upgrade-package click<8.1.7
     black==23.10.1
-    click==8.1.7
+    click==8.1.6
         # via black
     mypy-extensions==1.0.0
         # via black
@@ -3401,6 +4685,62 @@ fn upgrade_package() -> Result<()> {
     Ok(())
 }
 
+/// Resolve `anyio` with a per-package `--exclude-newer-package` date that predates `anyio==4.0.0`,
+/// while the remaining packages use the global `--exclude-newer` date.
+#[test]
+fn exclude_newer_package() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--exclude-newer-package")
+            .arg("anyio=2023-06-01"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --exclude-newer-package anyio=2023-06-01
+    anyio==3.7.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject a malformed `--exclude-newer-package` value.
+#[test]
+fn exclude_newer_package_invalid() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--exclude-newer-package")
+            .arg("anyio"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value 'anyio' for '--exclude-newer-package <EXCLUDE_NEWER_PACKAGE>': Expected `PACKAGE=DATE` (e.g., `flask=2024-03-01`), but found: `anyio`
+
+    For more information, try '--help'.
+    "###
+    );
+
+    Ok(())
+}
+
 /// Attempt to resolve a requirement at a path that doesn't exist.
 #[test]
 fn missing_path_requirement() -> Result<()> {
@@ -3501,7 +4841,67 @@ fn unnamed_requirement_with_package_name() -> Result<()> {
 }
 
 /// Attempt to resolve a URL requirement without a package name. The package name can't be extracted
-/// from the URL.
+/// from the URL, so it's read from the `pyproject.toml` within the downloaded archive.
+#[test]
+fn unnamed_requirement_archive() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("https://github.com/pallets/flask/archive/refs/tags/3.0.0.tar.gz")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask @ https://github.com/pallets/flask/archive/refs/tags/3.0.0.tar.gz
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.2
+        # via flask
+    markupsafe==2.1.3
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Attempt to resolve a local archive without a package name, where the archive doesn't exist.
+#[test]
+fn unnamed_requirement_not_found() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("./missing.tar.gz")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+            .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Unnamed requirement at [TEMP_DIR]/missing.tar.gz not found
+    "###
+    );
+
+    Ok(())
+}
+
+/// Attempt to resolve a URL requirement without a package name. The package name can't be extracted
+/// from the URL, and the URL doesn't point to an archive.
 #[test]
 fn unnamed_requirement_ambiguous() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -3515,7 +4915,102 @@ fn unnamed_requirement_ambiguous() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    error: Unable to infer package name for the unnamed requirement: https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0
+    error: Failed to download: https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0
+      Caused by: HTTP status client error (404 Not Found) for url (https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a local directory without a package name or any static metadata. The package name is
+/// inferred by invoking the build backend.
+#[test]
+fn unnamed_requirement_dynamic_metadata() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a project whose name is only known once `setup.py` is executed.
+    let example = context.temp_dir.child("example");
+    example.child("setup.py").write_str(indoc! {r#"
+        from setuptools import setup
+
+        NAME = "example" + "-pkg"
+
+        setup(name=NAME, version="0.1.0")
+    "#})?;
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("./example")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    example-pkg @ ./example
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // Inferring the name requires executing `setup.py`, which `--no-build` forbids.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-build"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Unable to infer package name for the unnamed requirement: ./example (the project lacks static metadata, and building is disabled)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Constrain a package to a URL that lacks a package name. The package name can be extracted from
+/// the URL.
+#[test]
+fn unnamed_constraint() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask")?;
+
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--constraint")
+            .arg("constraints.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl
+        # via -c constraints.txt
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.2
+        # via flask
+    markupsafe==2.1.3
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
     "###
     );
 
@@ -3552,6 +5047,37 @@ fn no_annotate() -> Result<()> {
     Ok(())
 }
 
+/// Include annotations in the output when `--annotate` follows `--no-annotate`, as in
+/// `pip-compile`.
+#[test]
+fn no_annotate_then_annotate() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-annotate")
+            .arg("--annotate"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-annotate --annotate
+    anyio==3.7.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Exclude header from the output.
 #[test]
 fn no_header() -> Result<()> {
@@ -3647,27 +5173,57 @@ fn custom_compile_command() -> Result<()> {
     Ok(())
 }
 
-/// Emit warnings when users pass redundant options from `pip-compile`.
+/// Without `--unsafe-package`, `--allow-unsafe` has no effect, since uv pins all packages.
+#[test]
+fn allow_unsafe() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("werkzeug==3.0.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--allow-unsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --allow-unsafe
+    markupsafe==2.1.3
+        # via werkzeug
+    werkzeug==3.0.1
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// With `--unsafe-package`, `--allow-unsafe` should pin the unsafe packages in a dedicated section.
 #[test]
-fn allow_unsafe() -> Result<()> {
+fn allow_unsafe_package() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("werkzeug==3.0.1")?;
 
     uv_snapshot!(context.compile()
             .arg("requirements.in")
+            .arg("--unsafe-package")
+            .arg("markupsafe")
             .arg("--allow-unsafe"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --allow-unsafe
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --unsafe-package markupsafe --allow-unsafe
+    werkzeug==3.0.1
+
+    # The following packages are considered to be unsafe in a requirements file:
     markupsafe==2.1.3
         # via werkzeug
-    werkzeug==3.0.1
 
     ----- stderr -----
-    warning: pip-compile's `--allow-unsafe` has no effect (uv can safely pin `pip` and other packages).
     Resolved 2 packages in [TIME]
     "###
     );
@@ -3779,6 +5335,47 @@ fn emit_find_links() -> Result<()> {
     Ok(())
 }
 
+/// Omit the index locations from both the output and the header by default, including when
+/// passed via short flags, and when `--emit-index-url` is overridden by `--no-emit-index-url`.
+#[test]
+fn no_emit_index_urls() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--emit-index-url")
+            .arg("--no-emit-index-url")
+            .arg("-i")
+            .arg("https://test.pypi.org/simple/")
+            .arg("--extra-index-url=https://pypi.org/simple")
+            .arg("-f./"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --emit-index-url --no-emit-index-url
+    black==23.10.1
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Respect the `--no-index` flag in a `requirements.txt` file.
 #[test]
 fn no_index_requirements_txt() -> Result<()> {
@@ -3938,6 +5535,95 @@ fn offline() -> Result<()> {
     Ok(())
 }
 
+/// Reuse a cached resolution when none of its inputs have changed, unless `--refresh` is provided.
+#[test]
+fn compile_cached_resolution() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    // Populate the cache.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    anyio==3.7.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    // Remove the cached index responses, such that the packages can only be resolved from the
+    // cached resolution.
+    fs::remove_dir_all(context.cache_dir.child("simple-v5").path())?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--offline"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --offline
+    anyio==3.7.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    // With `--refresh`, the cached resolution is ignored.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--offline")
+            .arg("--refresh"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because anyio==3.7.0 was not found in the cache and you require
+          anyio==3.7.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: Packages were unavailable because the network was disabled
+    "###
+    );
+
+    // If the requirements change, the cached resolution is ignored.
+    requirements_in.write_str("anyio==3.7.0\niniconfig==2.0.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--offline"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because anyio==3.7.0 was not found in the cache and you require
+          anyio==3.7.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: Packages were unavailable because the network was disabled
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve without network access via the `--offline` flag, using `--find-links` for an HTML
 /// registry.
 #[test]
@@ -4101,6 +5787,7 @@ fn compile_constraints_compatible_url() -> Result<()> {
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     anyio @ https://files.pythonhosted.org/packages/bf/cd/d6d9bb1dadf73e7af02d18225cbd2c93f8552e13130484f1c8dcfece292b/anyio-4.2.0-py3-none-any.whl
+        # via -c constraints.txt
     idna==3.4
         # via anyio
     sniffio==1.3.0
@@ -4135,6 +5822,7 @@ fn compile_constraints_compatible_url_version() -> Result<()> {
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     anyio @ https://files.pythonhosted.org/packages/bf/cd/d6d9bb1dadf73e7af02d18225cbd2c93f8552e13130484f1c8dcfece292b/anyio-4.2.0-py3-none-any.whl
+        # via -c constraints.txt
     idna==3.4
         # via anyio
     sniffio==1.3.0
@@ -4305,6 +5993,34 @@ fn no_deps_invalid_extra() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a source-only package with `--no-deps`, which shouldn't require building it.
+///
+/// `PyYAML==5.4.1` doesn't publish wheels for Python 3.12, and its source distribution fails to
+/// build against Cython 3, so the resolution would fail if its metadata were required.
+#[test]
+fn no_deps_source_distribution() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("pyyaml==5.4.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-deps"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-deps
+    pyyaml==5.4.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve an editable package with an invalid extra.
 #[test]
 fn editable_invalid_extra() -> Result<()> {
@@ -4381,6 +6097,48 @@ fn no_strip_extra() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a package with `--strip-extras`, which overrides an earlier `--no-strip-extras`.
+#[test]
+fn strip_extra() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask[dotenv]")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-strip-extras")
+            .arg("--strip-extras"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-strip-extras --strip-extras
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask==3.0.0
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.2
+        # via flask
+    markupsafe==2.1.3
+        # via
+        #   jinja2
+        #   werkzeug
+    python-dotenv==1.0.0
+        # via flask
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 8 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a package with `--no-strip-extras`.
 #[test]
 #[cfg(not(windows))]
@@ -4502,7 +6260,9 @@ fn compile_constraints_compatible_version() -> Result<()> {
     distlib==0.3.7
         # via virtualenv
     filelock==3.8.0
-        # via virtualenv
+        # via
+        #   -c constraints.txt
+        #   virtualenv
     platformdirs==3.11.0
         # via virtualenv
     virtualenv==20.21.1
@@ -4685,6 +6445,7 @@ fn override_with_compatible_constraint() -> Result<()> {
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt --override overrides.txt
     anyio==3.0.0
+        # via -c constraints.txt
     idna==3.4
         # via anyio
     sniffio==1.3.0
@@ -4906,6 +6667,7 @@ fn pre_release_constraint() -> Result<()> {
     click==8.1.7
         # via flask
     flask==2.0.0rc2
+        # via -c constraints.txt
     itsdangerous==2.1.2
         # via flask
     jinja2==3.1.2
@@ -5403,7 +7165,9 @@ fn no_stream() -> Result<()> {
     #    uv pip compile requirements.in -c constraints.in --cache-dir [CACHE_DIR]
     hashb-foxglove-protocolbuffers-python==25.3.0.1.20240226043130+465630478360
     protobuf==5.26.0
-        # via hashb-foxglove-protocolbuffers-python
+        # via
+        #   -c constraints.in
+        #   hashb-foxglove-protocolbuffers-python
 
     ----- stderr -----
     Resolved 2 packages in [TIME]
@@ -5815,3 +7579,114 @@ fn detect_package_name() -> Result<()> {
 
     Ok(())
 }
+
+/// Resolve a package with a conditional dependency using `--no-strip-markers`, which should retain
+/// the markers under which the dependency is required.
+#[test]
+fn compile_no_strip_markers() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==4.0.0")?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("--no-strip-markers")
+        .arg("--python-version")
+        .arg("3.8")
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z --no-strip-markers --python-version 3.8 requirements.in
+    anyio==4.0.0
+    exceptiongroup==1.1.3 ; python_version < '3.11'
+        # via anyio
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Resolve a package with a platform-specific dependency using `--universal`, which should include
+/// the dependency along with its environment markers.
+#[test]
+fn compile_universal() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm==4.66.1")?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("--universal")
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z --universal requirements.in
+    colorama==0.4.6 ; platform_system == 'Windows'
+        # via tqdm
+    tqdm==4.66.1
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Resolve a package that's pinned to different versions on different platforms, which requires
+/// forking the universal resolution.
+#[test]
+fn compile_universal_conflicting_markers() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        anyio==3.7.0 ; sys_platform == 'linux'
+        anyio==4.0.0 ; sys_platform == 'win32'
+    "})?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("--universal")
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z --universal requirements.in
+    anyio==3.7.0 ; sys_platform == 'linux'
+    anyio==4.0.0 ; sys_platform == 'win32'
+    idna==3.4 ; sys_platform == 'linux' or sys_platform == 'win32'
+        # via anyio
+    sniffio==1.3.0 ; sys_platform == 'linux' or sys_platform == 'win32'
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###);
+
+    // A `pylock.toml` can only represent a single resolution.
+    uv_snapshot!(context
+        .compile()
+        .arg("--universal")
+        .arg("--format")
+        .arg("pylock.toml")
+        .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: The resolution forked on conflicting markers, which is only supported when exporting to `requirements.txt`
+    "###);
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use anyhow::Result;
 use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
 use base64::{prelude::BASE64_STANDARD as base64, Engine};
-use indoc::indoc;
+use indoc::{formatdoc, indoc};
 use itertools::Itertools;
 use std::process::Command;
 use url::Url;
@@ -152,6 +152,114 @@ fn no_solution() {
     "###);
 }
 
+/// Report errors as structured JSON diagnostics, with `--error-format json`.
+#[test]
+fn error_format_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("flask>=3.0.0")
+        .arg("WerkZeug<1.0.0")
+        .arg("--error-format")
+        .arg("json"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    {"code":"UV_RESOLVE_CONFLICT","message":"Because only flask<=3.0.0 is available and flask==3.0.0 depends on werkzeug>=3.0.0, we can conclude that flask>=3.0.0 depends on werkzeug>=3.0.0.\nAnd because you require flask>=3.0.0 and you require werkzeug<1.0.0, we can conclude that the requirements are unsatisfiable.","causes":[],"context":"No solution found when resolving dependencies:"}
+    "###);
+
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--error-format")
+        .arg("json"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    {"code":"UV_IO","message":"failed to read from file `requirements.txt`","causes":["No such file or directory (os error 2)"]}
+    "###
+    );
+
+    // Errors that wrap a failed build are classified by their underlying cause.
+    uv_snapshot!(command(&context)
+        .arg("anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz")
+        .arg("--no-build")
+        .arg("--error-format")
+        .arg("json"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    {"code":"UV_BUILD_DISABLED","message":"Failed to download and build: anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz","causes":["Building source distributions is disabled"]}
+    "###
+    );
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("${URL}")?;
+
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.in")
+        .arg("--error-format")
+        .arg("json"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    {"code":"UV_INVALID_REQUIREMENT","message":"Couldn't parse requirement in `requirements.in` at position 0","causes":["Expected package name starting with an alphanumeric character, found '$'\n${URL}\n^"]}
+    "###
+    );
+
+    Ok(())
+}
+
+/// With `--error-format json`, warnings are included in the error report, rather than printed to
+/// stderr, and progress output is suppressed, such that stderr only contains the report.
+#[test]
+fn error_format_json_warnings() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("-r")
+        .arg("missing.txt")
+        .arg("--error-format")
+        .arg("json"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    {"code":"UV_IO","message":"failed to read from file `missing.txt`","causes":["No such file or directory (os error 2)"],"warnings":["Requirements file requirements.txt does not contain any dependencies"]}
+    "###
+    );
+
+    // On success, nothing is written to stderr.
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--error-format")
+        .arg("json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
 /// Install a package from the command line into a virtual environment.
 #[test]
 fn install_package() {
@@ -237,6 +345,163 @@ fn install_requirements_txt() -> Result<()> {
     Ok(())
 }
 
+/// Pass a `requirements.txt` file as a package name, with interactive input disabled.
+#[test]
+fn install_requirements_txt_as_package_no_input() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    // By default, exit with the suggested correction.
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--no-input"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `requirements.txt` looks like a requirements file but was passed as a package name (hint: did you mean `-r requirements.txt`?)
+    "###
+    );
+
+    // With `accept`, apply the suggested correction.
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .env("UV_NO_INPUT", "accept"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `requirements.txt` looks like a requirements file but was passed as a package name; treating it as `-r requirements.txt`
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    Ok(())
+}
+
+/// Re-enable interactive input with a falsey `UV_NO_INPUT`.
+#[test]
+fn install_no_input_disabled() {
+    let context = TestContext::new("3.12");
+
+    for value in ["0", "false"] {
+        uv_snapshot!(command(&context)
+            .arg("markupsafe==2.1.3")
+            .arg("--dry-run")
+            .env("UV_NO_INPUT", value), @r###"
+        success: true
+        exit_code: 0
+        ----- stdout -----
+
+        ----- stderr -----
+        Resolved 1 package in [TIME]
+        Would download 1 package ([SIZE])
+        Would install 1 package
+         + markupsafe==2.1.3
+        "###
+        );
+    }
+}
+
+/// Pass a `pyproject.toml`, a `setup.py`, or a project directory as a package name, with
+/// interactive input disabled.
+#[test]
+fn install_project_as_package_no_input() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project = context.temp_dir.child("project");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = []
+    "#})?;
+    project.child("setup.py").write_str(indoc! {r#"
+        from setuptools import setup
+
+        setup(name="project", version="0.1.0")
+    "#})?;
+
+    uv_snapshot!(command(&context)
+        .arg("project/pyproject.toml")
+        .arg("--no-input"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `project/pyproject.toml` looks like a `pyproject.toml` file but was passed as a package name (hint: did you mean `-r project/pyproject.toml`?)
+    "###
+    );
+
+    uv_snapshot!(command(&context)
+        .arg("project/setup.py")
+        .arg("--no-input"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `project/setup.py` looks like a `setup.py` file but was passed as a package name (hint: did you mean `-e project`?)
+    "###
+    );
+
+    // A directory name that isn't a valid requirement can only refer to the local project.
+    let project = context.temp_dir.child("my project");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = []
+    "#})?;
+
+    uv_snapshot!(command(&context)
+        .arg("my project")
+        .arg("--no-input"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `my project` looks like a local project but was passed as a package name (hint: did you mean `./my project`?)
+    "###
+    );
+
+    // A directory name that's also a valid requirement is installed from the index, as requested.
+    let project = context.temp_dir.child("iniconfig");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "iniconfig"
+        version = "0.1.0"
+        dependencies = []
+    "#})?;
+
+    uv_snapshot!(command(&context)
+        .arg("iniconfig")
+        .arg("--no-input")
+        .arg("--dry-run"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Would download 1 package ([SIZE])
+    Would install 1 package
+     + iniconfig==2.0.0
+    "###
+    );
+
+    Ok(())
+}
+
 /// Respect installed versions when resolving.
 #[test]
 fn respect_installed_and_reinstall() -> Result<()> {
@@ -945,6 +1210,39 @@ fn install_extra_index_url_has_priority() {
     context.assert_command("import flask").failure();
 }
 
+/// Install a package that's only available at the requested version on a lower-priority index,
+/// by searching across all indexes via `--index-strategy unsafe-best-match`.
+///
+/// `black` is on both test.pypi.org and pypi.org, but `black==24.2.0` is only on pypi.org. With
+/// the default `first-index` strategy, `uv` would stop at test.pypi.org and fail to resolve.
+#[test]
+fn install_index_strategy_unsafe_best_match() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command_without_exclude_newer(&context)
+        .arg("--index-url")
+        .arg("https://pypi.org/simple")
+        .arg("--extra-index-url")
+        .arg("https://test.pypi.org/simple")
+        .arg("--index-strategy")
+        .arg("unsafe-best-match")
+        .arg("black==24.2.0")
+        .arg("--no-deps")
+        .arg("--exclude-newer")
+        .arg("2024-03-09"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + black==24.2.0
+    "###
+    );
+}
+
 /// Install a package from a public GitHub repository
 #[test]
 #[cfg(feature = "git")]
@@ -970,6 +1268,74 @@ fn install_git_public_https() {
     context.assert_installed("uv_public_pypackage", "0.1.0");
 }
 
+/// Install a package from a Git repository whose version is derived from its tags (via
+/// `hatch-vcs`), which requires that the checkout include the tags and history of the commit.
+#[test]
+#[cfg(feature = "git")]
+#[cfg(unix)]
+fn install_git_version_from_tags() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a repository with a tagged release, followed by an untagged commit.
+    let repo = context.temp_dir.child("repo");
+    repo.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "example"
+        dynamic = ["version"]
+        requires-python = ">=3.8"
+
+        [build-system]
+        requires = ["hatchling", "hatch-vcs"]
+        build-backend = "hatchling.build"
+
+        [tool.hatch.version]
+        source = "vcs"
+        "#
+    })?;
+    repo.child("src/example/__init__.py").touch()?;
+
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(["-c", "user.name=uv", "-c", "user.email=uv@astral.sh"])
+            .args(["-c", "commit.gpgsign=false", "-c", "tag.gpgsign=false"])
+            .args(args)
+            .current_dir(&repo)
+            .assert()
+            .success();
+    };
+    git(&["init"]);
+    git(&["add", "."]);
+    git(&["commit", "-m", "Initial commit"]);
+    git(&["tag", "v1.0.0"]);
+    repo.child("README.md").touch()?;
+    git(&["add", "."]);
+    git(&["commit", "-m", "Add a README"]);
+
+    let url = Url::from_file_path(repo.path()).unwrap();
+
+    let mut filters = context.filters();
+    filters.push((r"[0-9a-f]{40}", "[COMMIT]"));
+    filters.push((r"\+g[0-9a-f]{7,}", "+g[COMMIT]"));
+
+    // The version should account for both the tag and the commit after it, rather than falling
+    // back to the default version for a checkout without any tags (`0.1.dev1`).
+    uv_snapshot!(filters, command(&context)
+        .arg(format!("example @ git+{url}")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==1.0.1.dev1+g[COMMIT] (from git+file://[TEMP_DIR]/repo@[COMMIT])
+    "###
+    );
+
+    Ok(())
+}
+
 /// Install a package from a public GitHub repository at a ref that does not exist
 #[test]
 #[cfg(feature = "git")]
@@ -1031,47 +1397,106 @@ fn install_git_public_https_missing_commit() {
     "###);
 }
 
-/// Install a package from a private GitHub repository using a PAT
+/// Install a package from a public GitHub repository as editable, checking it out to `src`.
 #[test]
-#[cfg(all(not(windows), feature = "git"))]
-fn install_git_private_https_pat() {
+#[cfg(feature = "git")]
+fn install_editable_git() {
     let context = TestContext::new("3.8");
-    let token = decode_token(READ_ONLY_GITHUB_TOKEN);
-
-    let mut filters = INSTA_FILTERS.to_vec();
-    filters.insert(0, (&token, "***"));
-
-    let mut command = command(&context);
-    command.arg(format!(
-        "uv-private-pypackage @ git+https://{token}@github.com/astral-test/uv-private-pypackage"
-    ));
 
-    uv_snapshot!(filters, command
-        , @r###"
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("-e")
+        .arg("git+https://github.com/astral-test/uv-public-pypackage#egg=uv-public-pypackage"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
+    Built 1 editable in [TIME]
     Resolved 1 package in [TIME]
-    Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
-     + uv-private-pypackage==0.1.0 (from git+https://***@github.com/astral-test/uv-private-pypackage@6c09ce9ae81f50670a60abd7d95f30dd416d00ac)
+     + uv-public-pypackage==0.1.0 (from file://[TEMP_DIR]/src/uv-public-pypackage)
     "###);
 
-    context.assert_installed("uv_private_pypackage", "0.1.0");
+    context.assert_installed("uv_public_pypackage", "0.1.0");
+
+    // The checkout is a regular Git repository, which tracks the remote.
+    let checkout = context.temp_dir.child("src").child("uv-public-pypackage");
+    assert!(checkout.child(".git").exists());
+    assert!(!checkout.child(".ok").exists());
+
+    // Reinstalling reuses the existing checkout, without overwriting local changes.
+    let marker = checkout.child("LOCAL_CHANGES");
+    marker.touch().unwrap();
+
+    command(&context)
+        .arg("-e")
+        .arg("git+https://github.com/astral-test/uv-public-pypackage#egg=uv-public-pypackage")
+        .arg("--reinstall")
+        .assert()
+        .success();
+
+    assert!(marker.exists());
 }
 
-/// Install a package from a private GitHub repository at a specific commit using a PAT
+/// Install a package from a public GitHub repository as editable, without a package name.
 #[test]
-#[cfg(feature = "git")]
-fn install_git_private_https_pat_at_ref() {
+fn install_editable_git_missing_name() {
     let context = TestContext::new("3.8");
-    let token = decode_token(READ_ONLY_GITHUB_TOKEN);
 
-    let mut filters = INSTA_FILTERS.to_vec();
-    filters.insert(0, (&token, "***"));
-    filters.push((r"git\+https://", ""));
+    uv_snapshot!(command(&context)
+        .arg("-e")
+        .arg("git+https://github.com/astral-test/uv-public-pypackage"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to parse `git+https://github.com/astral-test/uv-public-pypackage`
+      Caused by: Editable Git requirements must specify a package name (e.g., `#egg=<name>`): `git+https://github.com/astral-test/uv-public-pypackage`
+    "###);
+}
+
+/// Install a package from a private GitHub repository using a PAT
+#[test]
+#[cfg(all(not(windows), feature = "git"))]
+fn install_git_private_https_pat() {
+    let context = TestContext::new("3.8");
+    let token = decode_token(READ_ONLY_GITHUB_TOKEN);
+
+    let mut filters = INSTA_FILTERS.to_vec();
+    filters.insert(0, (&token, "***"));
+
+    let mut command = command(&context);
+    command.arg(format!(
+        "uv-private-pypackage @ git+https://{token}@github.com/astral-test/uv-private-pypackage"
+    ));
+
+    uv_snapshot!(filters, command
+        , @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + uv-private-pypackage==0.1.0 (from git+https://***@github.com/astral-test/uv-private-pypackage@6c09ce9ae81f50670a60abd7d95f30dd416d00ac)
+    "###);
+
+    context.assert_installed("uv_private_pypackage", "0.1.0");
+}
+
+/// Install a package from a private GitHub repository at a specific commit using a PAT
+#[test]
+#[cfg(feature = "git")]
+fn install_git_private_https_pat_at_ref() {
+    let context = TestContext::new("3.8");
+    let token = decode_token(READ_ONLY_GITHUB_TOKEN);
+
+    let mut filters = INSTA_FILTERS.to_vec();
+    filters.insert(0, (&token, "***"));
+    filters.push((r"git\+https://", ""));
 
     // A user is _required_ on Windows
     let user = if cfg!(windows) {
@@ -1248,6 +1673,63 @@ fn reinstall_no_binary() {
     context.assert_command("import anyio").success();
 }
 
+/// Install a package from source with `--no-binary`, while requiring pre-built wheels for all other
+/// packages with `--only-binary :all:`.
+#[test]
+fn only_binary_all_no_binary_package() {
+    let context = TestContext::new("3.12");
+
+    // Without the exemption, `anyio` would have no usable distributions.
+    uv_snapshot!(command(&context)
+        .arg("anyio==4.0.0")
+        .arg("--only-binary")
+        .arg(":all:")
+        .arg("--no-binary")
+        .arg("anyio")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==4.0.0
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    context.assert_command("import anyio").success();
+
+    // Packages may be provided as a comma-separated list.
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("anyio==4.0.0")
+        .arg("--only-binary")
+        .arg(":all:")
+        .arg("--no-binary")
+        .arg("idna,sniffio")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==4.0.0
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    context.assert_command("import anyio").success();
+}
+
 /// Install a package into a virtual environment, and ensuring that the executable permissions
 /// are retained.
 ///
@@ -1565,6 +2047,59 @@ fn install_constraints_remote() {
     ); // would yield typing-extensions==4.8.2 without constraint file
 }
 
+/// Verify the distribution selected for a constrained package against the hashes in the
+/// constraints file, even if the requirement itself (here, a transitive dependency) has none.
+#[test]
+fn install_constraints_hashes() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str(indoc! {r"
+        idna==3.6 \
+            --hash=sha256:9ecdbbd083b06798ae1e86adcbfe8ab1479cf864e4ee30fe4e46a003d12491ca
+    "})?;
+
+    uv_snapshot!(command(&context)
+            .arg("anyio==3.7.0")
+            .arg("-c")
+            .arg("constraints.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    error: Hash mismatch for `idna==3.6`: expected `sha256:9ecdbbd083b06798ae1e86adcbfe8ab1479cf864e4ee30fe4e46a003d12491ca`, found `sha256:c05567e9c24a6b9faaa835c4821bad0590fbb9d5779e7caa6e1cc4978e7eb24f`
+    "###
+    );
+
+    constraints_txt.write_str(indoc! {r"
+        idna==3.6 \
+            --hash=sha256:c05567e9c24a6b9faaa835c4821bad0590fbb9d5779e7caa6e1cc4978e7eb24f
+    "})?;
+
+    uv_snapshot!(command(&context)
+            .arg("anyio==3.7.0")
+            .arg("-c")
+            .arg("constraints.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+    Verified 1 package against the provided hashes
+     + anyio==3.7.0
+     + idna==3.6
+     + sniffio==1.3.0
+    "###
+    );
+
+    Ok(())
+}
+
 /// Install a package from a `requirements.txt` file, with an inline constraint, which points
 /// to a remote http server.
 #[test]
@@ -1921,6 +2456,27 @@ fn config_settings() -> Result<()> {
     Ok(())
 }
 
+/// Reject package-specific config settings that aren't scoped to a package.
+#[test]
+fn config_settings_package_invalid() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("iniconfig")
+        .arg("--config-settings-package")
+        .arg("editable_mode=compat"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value 'editable_mode=compat' for '--config-setting-package <CONFIG_SETTING_PACKAGE>': Invalid config setting: editable_mode=compat (expected `PACKAGE:KEY=VALUE`)
+
+    For more information, try '--help'.
+    "###
+    );
+}
+
 /// Reinstall a duplicate package in a virtual environment.
 #[test]
 #[cfg(unix)]
@@ -2375,6 +2931,97 @@ requires-python = "<=3.8"
     Ok(())
 }
 
+/// Forbid source distributions with `--no-sdist`, exempting individual packages with
+/// `--allow-sdist`.
+#[test]
+fn no_sdist() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz")?;
+
+    // `anyio` is only available as a source distribution, so resolution should fail.
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.in")
+        .arg("--no-sdist"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download and build: anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz
+      Caused by: Building source distributions is disabled
+    "###
+    );
+
+    // Allowing source distributions for `anyio` should succeed, while its dependencies are
+    // installed from wheels.
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.in")
+        .arg("--no-sdist")
+        .arg("--allow-sdist")
+        .arg("anyio"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==4.3.0 (from https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz)
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Editable requirements are exempt from `--no-sdist`, since they can't be installed without
+/// being built.
+#[test]
+fn no_sdist_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let current_dir = std::env::current_dir()?;
+    let workspace_dir = regex::escape(
+        Url::from_directory_path(current_dir.join("..").join("..").canonicalize()?)
+            .unwrap()
+            .as_str(),
+    );
+
+    let filters = [(workspace_dir.as_str(), "file://[WORKSPACE_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("-e")
+        .arg("../../scripts/editable-installs/poetry_editable")
+        .arg("--no-sdist")
+        .current_dir(&current_dir)
+        .env("CARGO_TARGET_DIR", "../../../target/target_install_editable"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Resolved 4 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 4 packages in [TIME]
+     + anyio==4.0.0
+     + idna==3.4
+     + poetry-editable==0.1.0 (from file://[WORKSPACE_DIR]/scripts/editable-installs/poetry_editable)
+     + sniffio==1.3.0
+    "###
+    );
+
+    Ok(())
+}
+
 /// Install with `--no-build-isolation`, to disable isolation during PEP 517 builds.
 #[test]
 fn no_build_isolation() -> Result<()> {
@@ -2446,33 +3093,91 @@ fn no_build_isolation() -> Result<()> {
     Ok(())
 }
 
-/// This tests that `uv` can read UTF-16LE encoded requirements.txt files.
-///
-/// Ref: <https://github.com/astral-sh/uv/issues/2276>
+/// Install with `--no-build-isolation-package`, to disable isolation during PEP 517 builds for a
+/// specific package.
 #[test]
-fn install_utf16le_requirements() -> Result<()> {
+fn no_build_isolation_package() -> Result<()> {
     let context = TestContext::new("3.12");
-    let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.touch()?;
-    requirements_txt.write_binary(&utf8_to_utf16_with_bom_le("tomli"))?;
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz")?;
 
-    uv_snapshot!(command_without_exclude_newer(&context)
+    // We expect the build to fail, because `setuptools` is not installed.
+    let filters = std::iter::once((r"exit code: 1", "exit status: 1"))
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+    uv_snapshot!(filters, command(&context)
         .arg("-r")
-        .arg("requirements.txt"), @r###"
-    success: true
-    exit_code: 0
+        .arg("requirements.in")
+        .arg("--no-build-isolation-package")
+        .arg("anyio"), @r###"
+    success: false
+    exit_code: 2
     ----- stdout -----
 
     ----- stderr -----
-    Resolved 1 package in [TIME]
-    Downloaded 1 package in [TIME]
-    Installed 1 package in [TIME]
-     + tomli==2.0.1
-    "###
-    );
-    Ok(())
-}
-
+    error: Failed to download and build: anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz
+      Caused by: Failed to build: anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz
+      Caused by: Build backend failed to determine metadata through `prepare_metadata_for_build_wheel` with exit status: 1
+    --- stdout:
+
+    --- stderr:
+    Traceback (most recent call last):
+      File "<string>", line 8, in <module>
+    ModuleNotFoundError: No module named 'setuptools'
+    ---
+    "###
+    );
+
+    // We expect the build to succeed, since `anyio` is still built in isolation.
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.in")
+        .arg("--no-build-isolation-package")
+        .arg("idna"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==0.0.0 (from https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz)
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// This tests that `uv` can read UTF-16LE encoded requirements.txt files.
+///
+/// Ref: <https://github.com/astral-sh/uv/issues/2276>
+#[test]
+fn install_utf16le_requirements() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_binary(&utf8_to_utf16_with_bom_le("tomli"))?;
+
+    uv_snapshot!(command_without_exclude_newer(&context)
+        .arg("-r")
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tomli==2.0.1
+    "###
+    );
+    Ok(())
+}
+
 /// This tests that `uv` can read UTF-16BE encoded requirements.txt files.
 ///
 /// Ref: <https://github.com/astral-sh/uv/issues/2276>
@@ -2538,7 +3243,7 @@ fn dry_run_install() -> std::result::Result<(), Box<dyn std::error::Error>> {
 
     ----- stderr -----
     Resolved 7 packages in [TIME]
-    Would download 7 packages
+    Would download 7 packages ([SIZE])
     Would install 7 packages
      + anyio==4.0.0
      + certifi==2023.11.17
@@ -2571,7 +3276,7 @@ fn dry_run_install_url_dependency() -> std::result::Result<(), Box<dyn std::erro
 
     ----- stderr -----
     Resolved 3 packages in [TIME]
-    Would download 3 packages
+    Would download 3 packages ([SIZE])
     Would install 3 packages
      + anyio @ https://files.pythonhosted.org/packages/2d/b8/7333d87d5f03247215d86a86362fd3e324111788c6cdd8d2e6196a6ba833/anyio-4.2.0.tar.gz
      + idna==3.4
@@ -2623,7 +3328,7 @@ fn dry_run_uninstall_url_dependency() -> std::result::Result<(), Box<dyn std::er
 
     ----- stderr -----
     Resolved 3 packages in [TIME]
-    Would download 1 package
+    Would download 1 package ([SIZE])
     Would uninstall 1 package
     Would install 1 package
      - anyio==4.2.0 (from https://files.pythonhosted.org/packages/2d/b8/7333d87d5f03247215d86a86362fd3e324111788c6cdd8d2e6196a6ba833/anyio-4.2.0.tar.gz)
@@ -2724,7 +3429,7 @@ fn dry_run_install_transitive_dependency_already_installed(
 
     ----- stderr -----
     Resolved 7 packages in [TIME]
-    Would download 4 packages
+    Would download 4 packages ([SIZE])
     Would install 4 packages
      + anyio==4.0.0
      + httpx==0.25.1
@@ -2778,7 +3483,7 @@ fn dry_run_install_then_upgrade() -> std::result::Result<(), Box<dyn std::error:
 
     ----- stderr -----
     Resolved 7 packages in [TIME]
-    Would download 1 package
+    Would download 1 package ([SIZE])
     Would uninstall 1 package
     Would install 1 package
      - httpx==0.25.0
@@ -2789,6 +3494,63 @@ fn dry_run_install_then_upgrade() -> std::result::Result<(), Box<dyn std::error:
     Ok(())
 }
 
+#[test]
+fn dry_run_install_json() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("anyio==3.7.0")?;
+
+    // Install the package
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==3.7.0
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    // Bump the version and write the plan as JSON
+    requirements_txt.write_str("anyio==4.0.0\nh11==0.14.0")?;
+    let filters = INSTA_FILTERS
+        .iter()
+        .copied()
+        .chain([(r#""size":\d+"#, r#""size":"[SIZE]""#)])
+        .collect::<Vec<_>>();
+    uv_snapshot!(filters, command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--dry-run")
+        .arg("--json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [{"name":"anyio","action":"upgrade","version":"4.0.0","installed_version":"3.7.0","size":"[SIZE]"},{"name":"h11","action":"install","version":"0.14.0","size":"[SIZE]"}]
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    Would download 2 packages ([SIZE])
+    Would uninstall 1 package
+    Would install 2 packages
+     - anyio==3.7.0
+     + anyio==4.0.0
+     + h11==0.14.0
+    "###
+    );
+
+    Ok(())
+}
+
 /// Raise an error when a direct URL's `Requires-Python` constraint is not met.
 #[test]
 fn requires_python_direct_url() -> Result<()> {
@@ -2827,6 +3589,45 @@ requires-python = "<=3.8"
     Ok(())
 }
 
+/// Install a package with `--progress json`, which reports progress as newline-delimited JSON
+/// events in lieu of progress bars and human-readable output.
+#[test]
+fn install_progress_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let output = command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--strict")
+        .arg("--progress")
+        .arg("json")
+        .output()?;
+    assert!(output.status.success());
+
+    // Every line on stderr is an event.
+    let events = String::from_utf8(output.stderr)?
+        .lines()
+        .map(|line| Ok(serde_json::from_str::<serde_json::Value>(line)?))
+        .collect::<Result<Vec<_>>>()?;
+    let names = events
+        .iter()
+        .map(|event| event["event"].as_str().unwrap_or_default())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names.first(), Some(&"resolve_started"));
+    assert!(names.contains(&"resolve_finished"), "{names:?}");
+    assert_eq!(names.last(), Some(&"install_finished"));
+    assert!(
+        events.iter().any(|event| event["event"] == "install_progress"
+            && event["package"] == "iniconfig"
+            && event["version"] == "2.0.0"),
+        "{names:?}"
+    );
+
+    context.assert_command("import iniconfig").success();
+
+    Ok(())
+}
+
 /// Install a package from an index that requires authentication
 #[test]
 fn install_package_basic_auth_from_url() {
@@ -2854,6 +3655,125 @@ fn install_package_basic_auth_from_url() {
     context.assert_command("import anyio").success();
 }
 
+/// Install a package from an index that requires authentication, with the credentials read from
+/// the netrc file at `NETRC`.
+#[test]
+fn install_package_basic_auth_from_netrc_env() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let netrc = context.temp_dir.child(".netrc");
+    netrc.write_str("machine pypi-proxy.fly.dev login public password heron")?;
+
+    uv_snapshot!(command(&context)
+        .arg("anyio")
+        .arg("--index-url")
+        .arg("https://pypi-proxy.fly.dev/basic-auth/simple")
+        .arg("--strict")
+        .env("NETRC", netrc.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==4.0.0
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    context.assert_command("import anyio").success();
+
+    Ok(())
+}
+
+/// An unreadable CA bundle passed via `--cert` is an error, rather than falling back to the
+/// default roots.
+#[test]
+fn install_invalid_cert() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("anyio")
+        .arg("--cert")
+        .arg("missing.pem"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to load CA bundle
+      Caused by: failed to read certificates from missing.pem: No such file or directory (os error 2)
+    "###
+    );
+}
+
+/// An invalid proxy passed via `--proxy` is an error, rather than connecting directly.
+#[test]
+fn install_invalid_proxy() {
+    let context = TestContext::new("3.12");
+
+    let output = command(&context)
+        .arg("anyio")
+        .arg("--proxy")
+        .arg("ftp://proxy.example.com")
+        .output()
+        .expect("Failed to run uv");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("error: Invalid proxy URL: `ftp://proxy.example.com/`"),
+        "{stderr}"
+    );
+}
+
+/// Requests that fail with a transient error are retried up to `--retries` times, with each retry
+/// reported to the user.
+#[test]
+fn install_retries() -> Result<()> {
+    use std::io::{Read, Write};
+
+    let context = TestContext::new("3.12");
+
+    // Serve a `503` for every request.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buffer = [0; 4096];
+            let _ = stream.read(&mut buffer);
+            let _ = stream.write_all(
+                b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            );
+        }
+    });
+
+    let output = command(&context)
+        .arg("anyio")
+        .arg("--index-url")
+        .arg(format!("http://{addr}/simple"))
+        .arg("--retries")
+        .arg("2")
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("warning: Retrying request after a transient failure (1/2)"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("warning: Retrying request after a transient failure (2/2)"),
+        "{stderr}"
+    );
+    assert!(!stderr.contains("(3/2)"), "{stderr}");
+
+    Ok(())
+}
+
 /// Install a package from an index that provides relative links
 #[test]
 fn install_index_with_relative_links() {
@@ -2952,3 +3872,759 @@ fn install_site_packages_mtime_updated() -> Result<()> {
 
     Ok(())
 }
+
+/// User installs aren't supported for interpreters within a virtual environment.
+#[test]
+fn install_user_virtualenv() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("anyio")
+        .arg("--user")
+        .arg("--python")
+        .arg(crate::common::venv_to_interpreter(&context.venv)), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: User site-packages are not available for the Python interpreter at `[VENV]/bin/python` (user installs are not supported within virtual environments)
+    "###
+    );
+}
+
+/// Install into the user site-packages of an interpreter outside of a virtual environment.
+#[test]
+#[cfg(unix)]
+fn install_user() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let base = context.temp_dir.child("base");
+    let python = crate::common::copy_base_interpreter(&context.venv, &base)?;
+    let user_base = context.temp_dir.child("user");
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--user")
+        .arg("--python")
+        .arg(&python)
+        .env("PYTHONUSERBASE", user_base.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // The package should be installed into the user site-packages, rather than the interpreter's.
+    user_base
+        .child("lib/python3.12/site-packages/iniconfig")
+        .assert(predicates::path::is_dir());
+    base.child("lib/python3.12/site-packages/iniconfig")
+        .assert(predicates::path::missing());
+
+    Command::new(&python)
+        .arg("-c")
+        .arg("import iniconfig")
+        .env("PYTHONUSERBASE", user_base.path())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+/// Install a package into the user site-packages when a different version is installed in the
+/// system site-packages, which should be shadowed rather than removed.
+#[test]
+#[cfg(unix)]
+fn install_user_shadows_system() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let base = context.temp_dir.child("base");
+    let python = crate::common::copy_base_interpreter(&context.venv, &base)?;
+    let user_base = context.temp_dir.child("user");
+
+    // Install an older version into the system site-packages.
+    command(&context)
+        .arg("iniconfig==1.1.1")
+        .arg("--python")
+        .arg(&python)
+        .env("PYTHONUSERBASE", user_base.path())
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--user")
+        .arg("--python")
+        .arg(&python)
+        .env("PYTHONUSERBASE", user_base.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // The system package should be left in place...
+    base.child("lib/python3.12/site-packages/iniconfig-1.1.1.dist-info")
+        .assert(predicates::path::is_dir());
+
+    // ...but shadowed by the user package.
+    Command::new(&python)
+        .arg("-c")
+        .arg("import importlib.metadata; print(importlib.metadata.version('iniconfig'), end='')")
+        .env("PYTHONUSERBASE", user_base.path())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success()
+        .stdout("2.0.0");
+
+    Ok(())
+}
+
+/// Install into a `.venv` discovered in a parent directory, without `VIRTUAL_ENV` set, and report
+/// the selected environment.
+#[test]
+fn install_discovered_venv() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project = context.temp_dir.child("project").child("src");
+    project.create_dir_all()?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("iniconfig==2.0.0")
+        .env_remove("VIRTUAL_ENV")
+        .env_remove("CONDA_PREFIX")
+        .current_dir(&project), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using virtual environment at: [TEMP_DIR]/.venv
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    context.assert_command("import iniconfig").success();
+
+    Ok(())
+}
+
+/// Install into an activated Conda environment, identified by `CONDA_PREFIX`.
+#[test]
+fn install_conda_prefix() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Any Python prefix can stand in for a Conda environment.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("iniconfig==2.0.0")
+        .env_remove("VIRTUAL_ENV")
+        .env("CONDA_PREFIX", context.venv.as_os_str())
+        .env("CONDA_DEFAULT_ENV", "example"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Conda environment at: .venv
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    context.assert_command("import iniconfig").success();
+
+    // The package is installed into the Conda environment's own `site-packages`, as reported by
+    // its interpreter (e.g., `Lib/site-packages` on Windows).
+    assert!(context.site_packages().join("iniconfig").is_dir());
+
+    // A Conda environment without Python can't be used.
+    let conda_prefix = context.temp_dir.child("conda");
+    conda_prefix.create_dir_all()?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("iniconfig==2.0.0")
+        .env_remove("VIRTUAL_ENV")
+        .env("CONDA_PREFIX", conda_prefix.as_os_str())
+        .env("CONDA_DEFAULT_ENV", "example"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: The Conda environment at `[TEMP_DIR]/conda` doesn't contain a Python interpreter. Install one with `conda install python`.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install into a target directory with `--target`, leaving the virtual environment untouched.
+#[test]
+fn install_target() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--target")
+        .arg("target"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    context
+        .temp_dir
+        .child("target")
+        .child("iniconfig")
+        .child("__init__.py")
+        .assert(predicates::path::exists());
+    context.assert_command("import iniconfig").failure();
+
+    Ok(())
+}
+
+/// Install wheels for another platform, Python version, implementation, and ABI into a target
+/// directory, refusing to build source distributions.
+#[test]
+fn install_target_cross_platform() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("markupsafe==2.1.5")
+        .arg("--target")
+        .arg("target")
+        .arg("--platform")
+        .arg("manylinux2014_x86_64")
+        .arg("--python-version")
+        .arg("3.10")
+        .arg("--implementation")
+        .arg("cp")
+        .arg("--abi")
+        .arg("cp310"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.5
+    "###
+    );
+
+    context
+        .temp_dir
+        .child("target")
+        .child("markupsafe")
+        .child("_speedups.cpython-310-x86_64-linux-gnu.so")
+        .assert(predicates::path::exists());
+
+    // Source distributions (here, a direct URL to `anyio`'s) can't be built for the target.
+    uv_snapshot!(command(&context)
+        .arg("anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz")
+        .arg("--target")
+        .arg("target")
+        .arg("--platform")
+        .arg("manylinux2014_x86_64")
+        .arg("--python-version")
+        .arg("3.10")
+        .arg("--implementation")
+        .arg("cp")
+        .arg("--abi")
+        .arg("cp310"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download and build: anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz
+      Caused by: Building source distributions is disabled
+    "###
+    );
+
+    // The same applies when only the Python version differs from the interpreter's.
+    uv_snapshot!(command(&context)
+        .arg("anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz")
+        .arg("--target")
+        .arg("target")
+        .arg("--python-version")
+        .arg("3.10"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download and build: anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz
+      Caused by: Building source distributions is disabled
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request a Python version that's satisfied by a `.venv` discovered in a parent directory, which
+/// should install into the virtual environment rather than a system interpreter.
+#[test]
+fn install_python_version_discovered_venv() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project = context.temp_dir.child("project");
+    project.create_dir_all()?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--python")
+        .arg("3.12")
+        .env_remove("VIRTUAL_ENV")
+        .env_remove("CONDA_PREFIX")
+        .current_dir(&project), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using virtual environment at: [TEMP_DIR]/.venv
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    context.assert_command("import iniconfig").success();
+
+    Ok(())
+}
+
+/// Write an installation report for the resolved distributions.
+#[test]
+fn install_report() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("anyio==3.7.0")
+        .arg("--report")
+        .arg("report.json")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==3.7.0
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(
+        context.temp_dir.child("report.json"),
+    )?)?;
+    assert_eq!(report["version"], "1");
+    assert_eq!(report["environment"]["implementation_name"], "cpython");
+
+    let install = report["install"].as_array().unwrap();
+    let entries = install
+        .iter()
+        .map(|item| {
+            (
+                item["metadata"]["name"].as_str().unwrap(),
+                item["metadata"]["version"].as_str().unwrap(),
+                item["requested"].as_bool().unwrap(),
+                item["is_direct"].as_bool().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        vec![
+            ("anyio", "3.7.0", true, false),
+            ("idna", "3.4", false, false),
+            ("sniffio", "1.3.0", false, false),
+        ]
+    );
+
+    // Each distribution should include its download URL and hash.
+    for item in install {
+        let url = item["download_info"]["url"].as_str().unwrap();
+        assert!(url.ends_with(".whl"), "{url}");
+        assert!(item["download_info"]["archive_info"]["hashes"]["sha256"].is_string());
+    }
+
+    Ok(())
+}
+
+/// Write a CycloneDX software bill of materials for the resolved distributions.
+#[test]
+fn install_sbom() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("anyio==3.7.0")
+        .arg("--sbom")
+        .arg("sbom.json")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==3.7.0
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    let sbom: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(
+        context.temp_dir.child("sbom.json"),
+    )?)?;
+    assert_eq!(sbom["bomFormat"], "CycloneDX");
+    assert_eq!(sbom["specVersion"], "1.5");
+
+    let components = sbom["components"].as_array().unwrap();
+    let purls = components
+        .iter()
+        .map(|component| component["purl"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        purls,
+        vec![
+            "pkg:pypi/anyio@3.7.0",
+            "pkg:pypi/idna@3.4",
+            "pkg:pypi/sniffio@1.3.0",
+        ]
+    );
+
+    // Each component should include the hash of the installed wheel, along with its license, as
+    // read from the installed distribution.
+    for component in components {
+        let hashes = component["hashes"].as_array().unwrap();
+        assert!(hashes.iter().any(|hash| hash["alg"] == "SHA-256"));
+        assert!(!component["licenses"].as_array().unwrap().is_empty());
+    }
+
+    // If the requirements are already satisfied, the SBOM should still be written, from the
+    // environment.
+    uv_snapshot!(command(&context)
+        .arg("anyio==3.7.0")
+        .arg("--sbom")
+        .arg("sbom.json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    let sbom: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(
+        context.temp_dir.child("sbom.json"),
+    )?)?;
+    let purls = sbom["components"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|component| component["purl"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        purls,
+        vec![
+            "pkg:pypi/anyio@3.7.0",
+            "pkg:pypi/idna@3.4",
+            "pkg:pypi/sniffio@1.3.0",
+        ]
+    );
+
+    Ok(())
+}
+
+/// Install a `setup.py`-only project as editable with `--legacy-setup-py`, which uses the PEP 660
+/// hooks of the default `setuptools` backend rather than invoking `setup.py` directly.
+#[test]
+fn install_editable_legacy_setup_py() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create an editable package with a `setup.py`, but no `pyproject.toml`.
+    let editable_dir = assert_fs::TempDir::new()?;
+    let setup_py = editable_dir.child("setup.py");
+    setup_py.write_str(indoc! {r#"
+        from setuptools import setup
+
+        setup(name="example", version="0.0.0")
+    "#})?;
+
+    let filters = [(r"\(from file://.*\)", "(from [WORKSPACE_DIR])")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("--editable")
+        .arg(editable_dir.path())
+        .arg("--legacy-setup-py"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.0.0 (from [WORKSPACE_DIR])
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install a workspace member as editable, which should also install the workspace members that
+/// it depends on as editables, rather than fetching them from the index.
+#[test]
+fn install_editable_workspace_member() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [tool.uv.workspace]
+        members = ["packages/*"]
+    "#})?;
+
+    let alpha = context.temp_dir.child("packages").child("alpha");
+    alpha.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "alpha"
+        version = "0.1.0"
+        dependencies = ["beta"]
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+    "#})?;
+
+    let beta = context.temp_dir.child("packages").child("beta");
+    beta.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "beta"
+        version = "0.1.0"
+        dependencies = []
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+    "#})?;
+
+    let filters = [(r"\(from file://.*\)", "(from [WORKSPACE_DIR])")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("--editable")
+        .arg(alpha.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 2 editables in [TIME]
+    Resolved 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + alpha==0.1.0 (from [WORKSPACE_DIR])
+     + beta==0.1.0 (from [WORKSPACE_DIR])
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install the dependencies of a workspace member, including those of an extra. Dependencies on
+/// other members should respect their markers, such that a member that's only required on other
+/// Python versions is built as a local source, but not installed.
+#[test]
+fn install_workspace_member_dependencies_markers() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [tool.uv.workspace]
+        members = ["packages/*"]
+    "#})?;
+
+    let alpha = context.temp_dir.child("packages").child("alpha");
+    alpha.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "alpha"
+        version = "0.1.0"
+        dependencies = ["beta ; python_version < '3.8'"]
+
+        [project.optional-dependencies]
+        test = ["gamma>=0.1.0"]
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+    "#})?;
+
+    for name in ["beta", "gamma"] {
+        let member = context.temp_dir.child("packages").child(name);
+        member.child("pyproject.toml").write_str(&formatdoc! {r#"
+            [project]
+            name = "{name}"
+            version = "0.1.0"
+            dependencies = []
+
+            [build-system]
+            requires = ["setuptools>=42"]
+            build-backend = "setuptools.build_meta"
+        "#})?;
+    }
+
+    let filters = [(r"\(from file://.*\)", "(from [WORKSPACE_DIR])")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("-r")
+        .arg(alpha.child("pyproject.toml").path())
+        .arg("--extra")
+        .arg("test"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 2 editables in [TIME]
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + gamma==0.1.0 (from [WORKSPACE_DIR])
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install a local directory that depends on other local directories via relative paths in its
+/// `pyproject.toml`, which should be discovered recursively and installed from their local
+/// sources.
+#[test]
+fn install_local_path_dependencies() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    for (name, dependencies) in [
+        ("alpha", r#"["beta @ {root:uri}/../beta"]"#),
+        ("beta", r#"["gamma @ {root:uri}/../gamma"]"#),
+        ("gamma", "[]"),
+    ] {
+        let project = context.temp_dir.child(name);
+        project.child("pyproject.toml").write_str(&formatdoc! {r#"
+            [project]
+            name = "{name}"
+            version = "0.1.0"
+            dependencies = {dependencies}
+
+            [tool.hatch.metadata]
+            allow-direct-references = true
+
+            [build-system]
+            requires = ["hatchling"]
+            build-backend = "hatchling.build"
+        "#})?;
+        project.child(name).child("__init__.py").touch()?;
+    }
+
+    let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg(context.temp_dir.child("alpha").path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + alpha==0.1.0 (from file://[TEMP_DIR]/alpha)
+     + beta==0.1.0 (from file://[TEMP_DIR]/beta)
+     + gamma==0.1.0 (from file://[TEMP_DIR]/gamma)
+    "###
+    );
+
+    context.assert_command("import alpha, beta, gamma").success();
+
+    Ok(())
+}
+
+/// Write the full `tracing` output to a log file with `--log-file`, independent of `--verbose`.
+#[test]
+fn log_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let log_txt = context.temp_dir.child("logs").child("log.txt");
+    command(&context)
+        .arg("MarkupSafe==2.1.3")
+        .arg("--log-file")
+        .arg(log_txt.path())
+        .assert()
+        .success();
+
+    log_txt.assert(predicates::str::contains("DEBUG"));
+    log_txt.assert(predicates::str::contains("Using Python 3.12"));
+    // The log includes the workspace crates outside of the `uv` prefix (e.g., the wheel
+    // installer).
+    log_txt.assert(predicates::str::contains("Writing record"));
+
+    let log_json = context.temp_dir.child("logs").child("log.json");
+    command(&context)
+        .arg("MarkupSafe==2.1.3")
+        .arg("--log-file")
+        .arg(log_json.path())
+        .arg("--log-format")
+        .arg("json")
+        .assert()
+        .success();
+
+    let contents = fs_err::read_to_string(log_json.path())?;
+    for line in contents.lines() {
+        serde_json::from_str::<serde_json::Value>(line)?;
+    }
+    assert!(contents.contains("Using Python 3.12"));
+
+    Ok(())
+}
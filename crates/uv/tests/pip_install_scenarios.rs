@@ -1373,6 +1373,8 @@ fn local_used_without_sdist() {
     ----- stderr -----
       × No solution found when resolving dependencies:
       ╰─▶ Because package-a==1.2.3 is unusable because no wheels are available with a matching Python ABI and you require package-a==1.2.3, we can conclude that the requirements are unsatisfiable.
+
+          hint: package-a==1.2.3 has no source distribution, and none of its wheels support Python 3.8 (try: pinning package-a to a version that publishes compatible wheels)
     "###);
 
     // The version '1.2.3+foo' satisfies the constraint '==1.2.3'.
@@ -1748,6 +1750,8 @@ fn local_transitive_confounding() {
       × No solution found when resolving dependencies:
       ╰─▶ Because package-b==2.0.0 is unusable because no wheels are available with a matching Python ABI and package-a==1.0.0 depends on package-b==2.0.0, we can conclude that package-a==1.0.0 cannot be used.
           And because only package-a==1.0.0 is available and you require package-a, we can conclude that the requirements are unsatisfiable.
+
+          hint: package-b==2.0.0 has no source distribution, and none of its wheels support Python 3.8 (try: pinning package-b to a version that publishes compatible wheels)
     "###);
 
     // The version '1.2.3+foo' satisfies the constraint '==1.2.3'.
@@ -4158,6 +4162,8 @@ fn no_sdist_no_wheels_with_matching_platform() {
       × No solution found when resolving dependencies:
       ╰─▶ Because only package-a==1.0.0 is available and package-a==1.0.0 is unusable because no wheels are available with a matching platform, we can conclude that all versions of package-a cannot be used.
           And because you require package-a, we can conclude that the requirements are unsatisfiable.
+
+          hint: package-a==1.0.0 has no source distribution, and none of its wheels support the current platform (try: pinning package-a to a version that publishes compatible wheels)
     "###);
 
     assert_not_installed(
@@ -4199,6 +4205,8 @@ fn no_sdist_no_wheels_with_matching_python() {
       × No solution found when resolving dependencies:
       ╰─▶ Because only package-a==1.0.0 is available and package-a==1.0.0 is unusable because no wheels are available with a matching Python implementation, we can conclude that all versions of package-a cannot be used.
           And because you require package-a, we can conclude that the requirements are unsatisfiable.
+
+          hint: package-a==1.0.0 has no source distribution, and none of its wheels support the current Python implementation (try: pinning package-a to a version that publishes compatible wheels)
     "###);
 
     assert_not_installed(
@@ -4240,6 +4248,8 @@ fn no_sdist_no_wheels_with_matching_abi() {
       × No solution found when resolving dependencies:
       ╰─▶ Because only package-a==1.0.0 is available and package-a==1.0.0 is unusable because no wheels are available with a matching Python ABI, we can conclude that all versions of package-a cannot be used.
           And because you require package-a, we can conclude that the requirements are unsatisfiable.
+
+          hint: package-a==1.0.0 has no source distribution, and none of its wheels support Python 3.8 (try: pinning package-a to a version that publishes compatible wheels)
     "###);
 
     assert_not_installed(
@@ -184,6 +184,165 @@ fn install() -> Result<()> {
     Ok(())
 }
 
+/// Install a package with `--progress json`, which reports progress as newline-delimited JSON
+/// events. Since the requirements are pinned, distributions are found without resolving.
+#[test]
+fn install_progress_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    let output = command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .arg("--progress")
+        .arg("json")
+        .output()?;
+    assert!(output.status.success());
+
+    // Every line on stderr is an event.
+    let events = String::from_utf8(output.stderr)?
+        .lines()
+        .map(|line| {
+            let event = serde_json::from_str::<serde_json::Value>(line)?;
+            Ok(event["event"].as_str().unwrap_or_default().to_string())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    assert_eq!(events.first().map(String::as_str), Some("find_started"));
+    assert!(events.contains(&"find_finished".to_string()), "{events:?}");
+    assert_eq!(events.last().map(String::as_str), Some("install_finished"));
+    assert!(
+        !events.iter().any(|event| event.starts_with("resolve_")),
+        "{events:?}"
+    );
+
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
+/// Sync the dependencies declared in a `pyproject.toml` file, including those in an optional
+/// dependency group, then remove the optional dependencies by syncing without the extra.
+#[test]
+fn install_pyproject_toml_extra() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "project"
+version = "0.1.0"
+dependencies = ["MarkupSafe==2.1.3"]
+
+[project.optional-dependencies]
+dev = ["iniconfig==2.0.0"]
+"#,
+    )?;
+
+    uv_snapshot!(command(&context)
+        .arg("pyproject.toml")
+        .arg("--extra")
+        .arg("dev")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + markupsafe==2.1.3
+    "###
+    );
+
+    context.assert_command("import iniconfig").success();
+
+    // Syncing without the extra should remove its dependencies.
+    command(&context)
+        .arg("pyproject.toml")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    context.assert_command("import markupsafe").success();
+    context.assert_command("import iniconfig").failure();
+
+    Ok(())
+}
+
+/// Sync a `pyproject.toml` file whose dependencies have dependencies of their own, which should be
+/// resolved (preferring the installed versions) rather than removed as extraneous.
+#[test]
+fn install_pyproject_toml_transitive() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("idna==3.6\nsniffio==1.3.1\niniconfig==2.0.0")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "project"
+version = "0.1.0"
+dependencies = ["anyio==4.3.0"]
+"#,
+    )?;
+
+    uv_snapshot!(command(&context)
+        .arg("pyproject.toml")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + anyio==4.3.0
+     - iniconfig==2.0.0
+    "###
+    );
+
+    context.assert_command("import anyio").success();
+
+    Ok(())
+}
+
+/// Extras can only be requested alongside a `pyproject.toml` file.
+#[test]
+fn install_requirements_txt_extra() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--all-extras"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Requesting extras requires a pyproject.toml input file.
+    "###
+    );
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment using copy semantics.
 #[test]
 fn install_copy() -> Result<()> {
@@ -256,6 +415,50 @@ fn install_hardlink() -> Result<()> {
     Ok(())
 }
 
+/// Install a package into a virtual environment using symlink semantics.
+#[test]
+#[cfg(unix)] // Windows does not allow symlinks by default
+fn install_symlink() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--link-mode")
+        .arg("symlink")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    context.assert_command("import markupsafe").success();
+
+    // The installed files should link into the cache.
+    assert!(context
+        .site_packages()
+        .join("markupsafe")
+        .join("__init__.py")
+        .is_symlink());
+
+    // Removing the cache _should_ invalidate the virtual environment.
+    fs::remove_dir_all(context.cache_dir.path())?;
+
+    context.assert_command("import markupsafe").failure();
+
+    Ok(())
+}
+
 /// Install multiple packages into a virtual environment.
 #[test]
 fn install_many() -> Result<()> {
@@ -320,6 +523,71 @@ fn noop() -> Result<()> {
     Ok(())
 }
 
+/// Print the planned changes without modifying the environment.
+#[test]
+fn dry_run() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--dry-run"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Would download 1 package ([SIZE])
+    Would uninstall 1 package
+    Would install 1 package
+     - markupsafe==2.1.3
+     + tomli==2.0.1
+    "###
+    );
+
+    let filters = INSTA_FILTERS
+        .iter()
+        .copied()
+        .chain([(r#""size":\d+"#, r#""size":"[SIZE]""#)])
+        .collect::<Vec<_>>();
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.txt")
+        .arg("--dry-run")
+        .arg("--json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [{"name":"markupsafe","action":"remove","installed_version":"2.1.3"},{"name":"tomli","action":"install","version":"2.0.1","size":"[SIZE]"}]
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Would download 1 package ([SIZE])
+    Would uninstall 1 package
+    Would install 1 package
+     - markupsafe==2.1.3
+     + tomli==2.0.1
+    "###
+    );
+
+    // The environment should be unchanged.
+    context.assert_command("import markupsafe").success();
+    context.assert_command("import tomli").failure();
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment, then install the same package into a different
 /// virtual environment.
 #[test]
@@ -426,6 +694,49 @@ fn add_remove() -> Result<()> {
     Ok(())
 }
 
+/// Install a package into a virtual environment, then sync a different requirements file while
+/// preserving the original package via `--keep`.
+#[test]
+fn keep() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--keep")
+        .arg("markupsafe")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tomli==2.0.1
+    "###
+    );
+
+    context.assert_command("import tomli").success();
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment, then install a second package into the same
 /// virtual environment.
 #[test]
@@ -2611,7 +2922,7 @@ fn offline() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    error: Network connectivity is disabled, but the requested data wasn't found in the cache for: `black`
+    error: Network connectivity is disabled, but the following packages weren't found in the cache: `black`
     "###
     );
 
@@ -2650,6 +2961,28 @@ fn offline() -> Result<()> {
     Ok(())
 }
 
+/// Report every package that's missing from the cache when running offline, via `UV_OFFLINE`.
+#[test]
+fn offline_missing_packages() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio==3.7.0\nidna==3.4\nsniffio==1.3.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .env("UV_OFFLINE", "1"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Network connectivity is disabled, but the following packages weren't found in the cache: `anyio`, `idna`, `sniffio`
+    "###
+    );
+
+    Ok(())
+}
+
 /// Sync with a repeated `anyio` requirement. The second requirement should be ignored.
 #[test]
 fn repeat_requirement() -> Result<()> {
@@ -2901,25 +3234,134 @@ fn compile() -> Result<()> {
     Ok(())
 }
 
-/// Test that the `PYC_INVALIDATION_MODE` option is recognized and that the error handling works.
+/// Install with bytecode compilation, and record the compiled files in the `RECORD`.
 #[test]
-fn compile_invalid_pyc_invalidation_mode() -> Result<()> {
+fn compile_bytecode_record() -> Result<()> {
     let context = TestContext::new("3.12");
 
     let requirements_txt = context.temp_dir.child("requirements.txt");
     requirements_txt.touch()?;
     requirements_txt.write_str("MarkupSafe==2.1.3")?;
 
-    let site_packages = regex::escape(
-        &context
-            .site_packages()
-            .canonicalize()
-            .unwrap()
-            .user_display()
-            .to_string(),
-    );
-    let filters: Vec<_> = [
-        (site_packages.as_str(), "[SITE-PACKAGES]"),
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--compile-bytecode")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+    Bytecode compiled 3 files in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    let record = fs::read_to_string(
+        context
+            .site_packages()
+            .join("MarkupSafe-2.1.3.dist-info")
+            .join("RECORD"),
+    )?;
+    assert!(record.contains("markupsafe/__pycache__/__init__.cpython-312.pyc,sha256="));
+    assert!(record.contains("markupsafe/__pycache__/_native.cpython-312.pyc,sha256="));
+
+    // Uninstalling the package removes the compiled files.
+    uv_snapshot!(uninstall_command(&context)
+        .arg("markupsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+     - markupsafe==2.1.3
+    "###
+    );
+
+    assert!(!context.site_packages().join("markupsafe").exists());
+
+    Ok(())
+}
+
+/// Install with bytecode compilation, and verify that only the `RECORD` files of the newly
+/// installed packages are modified.
+#[test]
+fn compile_bytecode_record_installed_only() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    let markupsafe_record = context
+        .site_packages()
+        .join("MarkupSafe-2.1.3.dist-info")
+        .join("RECORD");
+    let before = fs::read_to_string(&markupsafe_record)?;
+
+    requirements_txt.write_str("MarkupSafe==2.1.3\niniconfig==2.0.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--compile-bytecode")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+    Bytecode compiled 7 files in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // The bytecode for the newly installed package is recorded...
+    let record = fs::read_to_string(
+        context
+            .site_packages()
+            .join("iniconfig-2.0.0.dist-info")
+            .join("RECORD"),
+    )?;
+    assert!(record.contains("iniconfig/__pycache__/__init__.cpython-312.pyc,sha256="));
+
+    // ...but the `RECORD` of the existing package is left untouched.
+    assert_eq!(fs::read_to_string(&markupsafe_record)?, before);
+
+    Ok(())
+}
+
+/// Test that the `PYC_INVALIDATION_MODE` option is recognized and that the error handling works.
+#[test]
+fn compile_invalid_pyc_invalidation_mode() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    let site_packages = regex::escape(
+        &context
+            .site_packages()
+            .canonicalize()
+            .unwrap()
+            .user_display()
+            .to_string(),
+    );
+    let filters: Vec<_> = [
+        (site_packages.as_str(), "[SITE-PACKAGES]"),
         (r"\.venv/lib/python3.12/site-packages", "[SITE-PACKAGES]"),
         (r"\.venv/Lib/site-packages", "[SITE-PACKAGES]"),
         (
@@ -3070,3 +3512,298 @@ requires-python = "<=3.5"
 
     Ok(())
 }
+
+/// Verify the cached wheels against their `RECORD` files, then repair the cache by removing the
+/// corrupted wheel, such that it's re-downloaded on the next install.
+#[test]
+fn verify_cache() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let filters = [(r"archive-v0/[A-Za-z0-9_-]+", "archive-v0/[ARCHIVE]")]
+        .into_iter()
+        .chain(context.filters())
+        .collect::<Vec<_>>();
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tomli==2.0.1
+    "###
+    );
+
+    // Corrupt the unzipped wheel in the cache.
+    let archive = fs::read_dir(context.cache_dir.child("archive-v0"))?
+        .next()
+        .expect("Archive to exist")?
+        .path();
+    fs::remove_file(archive.join("tomli").join("_re.py"))?;
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("cache")
+        .arg("verify")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .current_dir(&context.temp_dir), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Verifying cache at: [CACHE_DIR]/
+    Corrupted: [CACHE_DIR]/archive-v0/[ARCHIVE]
+      - `tomli/_re.py` is missing
+    Found 1 corrupted wheel (out of 1 verified)
+    Run `uv cache verify --repair` to remove the corrupted entries, such that they're re-downloaded (or rebuilt) the next time they're needed
+    "###
+    );
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("cache")
+        .arg("verify")
+        .arg("--repair")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Verifying cache at: [CACHE_DIR]/
+    Corrupted: [CACHE_DIR]/archive-v0/[ARCHIVE]
+      - `tomli/_re.py` is missing
+    Found 1 corrupted wheel (out of 1 verified)
+    Removed 1 corrupted wheel ([SIZE])
+    "###
+    );
+
+    // Re-installing into a fresh environment should download the wheel again.
+    let parent = assert_fs::TempDir::new()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tomli==2.0.1
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install a package in hash-checking mode, with hashes that match the distribution.
+#[test]
+fn require_hashes() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        jinja2==3.1.2 \
+            --hash=sha256:31351a702a408a9e7595a8fc6150fc3f43bb6bf7e319770cbc0db9df9437e852 \
+            --hash=sha256:6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+    Verified 1 package against the provided hashes
+     + jinja2==3.1.2
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject a distribution that doesn't match any of the provided hashes.
+#[test]
+fn require_hashes_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        jinja2==3.1.2 \
+            --hash=sha256:31351a702a408a9e7595a8fc6150fc3f43bb6bf7e319770cbc0db9df9437e852
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Hash mismatch for `jinja2==3.1.2`: expected `sha256:31351a702a408a9e7595a8fc6150fc3f43bb6bf7e319770cbc0db9df9437e852`, found `sha256:6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject a cached distribution that doesn't match any of the provided hashes, even if the index
+/// (or URL) doesn't report a hash for it.
+#[test]
+fn verify_hashes_cached_url() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Populate the cache.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli @ https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tomli==2.0.1 (from https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl)
+    "###
+    );
+
+    // Reinstall from the cache, with a hash that doesn't match.
+    requirements_txt.write_str(indoc! {r"
+        tomli @ https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl \
+            --hash=sha256:de526c12914f0c550d15924c62d72abc48d6fe7364aa87328337a31007fe8a4f
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--reinstall"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Failed to download distributions
+      Caused by: Failed to fetch wheel: tomli @ https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl
+      Caused by: Hash mismatch for `tomli @ https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl`: expected `sha256:de526c12914f0c550d15924c62d72abc48d6fe7364aa87328337a31007fe8a4f`, found `sha256:939de3e7a6161af0c887ef91b7d41a53e7c5a1ca976325f429cb46ea9bc30ecc`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Like pip, any requirement that includes a hash enables hash-checking mode, in which case every
+/// requirement must include a hash.
+#[test]
+fn verify_hashes_partial() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        jinja2==3.1.2 \
+            --hash=sha256:6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61
+        tomli==2.0.1
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: In hash-checking mode, all requirements must have a hash, but none were provided for: `tomli==2.0.1`
+    "###
+    );
+
+    // Once every requirement includes a hash, each distribution is verified.
+    requirements_txt.write_str(indoc! {r"
+        jinja2==3.1.2 \
+            --hash=sha256:6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61
+        tomli==2.0.1 \
+            --hash=sha256:939de3e7a6161af0c887ef91b7d41a53e7c5a1ca976325f429cb46ea9bc30ecc
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+    Verified 2 packages against the provided hashes
+     + jinja2==3.1.2
+     + tomli==2.0.1
+    "###
+    );
+
+    Ok(())
+}
+
+/// In hash-checking mode, every requirement must include a hash.
+#[test]
+fn require_hashes_missing() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        jinja2==3.1.2 \
+            --hash=sha256:6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61
+        tomli==2.0.1
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: In hash-checking mode, all requirements must have a hash, but none were provided for: `tomli==2.0.1`
+    "###
+    );
+
+    // `--require-hashes` enables hash-checking mode even if no hashes are provided.
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: In hash-checking mode, all requirements must have a hash, but none were provided for: `tomli==2.0.1`
+    "###
+    );
+
+    Ok(())
+}
@@ -31,6 +31,26 @@ fn uninstall_command(context: &TestContext) -> Command {
     command
 }
 
+/// Create a `pip install` command with options shared across scenarios.
+fn install_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("install")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (8 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
 /// Create a `pip sync` command with options shared across scenarios.
 fn sync_command(context: &TestContext) -> Command {
     let mut command = Command::new(get_bin());
@@ -594,3 +614,78 @@ fn uninstall_duplicate() -> Result<()> {
 
     Ok(())
 }
+
+/// Uninstall a package from the user site-packages when it's also installed in the system
+/// site-packages, which should be left in place.
+#[test]
+#[cfg(unix)]
+fn uninstall_user() -> Result<()> {
+    use crate::common::copy_base_interpreter;
+
+    let context = TestContext::new("3.12");
+    let base = context.temp_dir.child("base");
+    let python = copy_base_interpreter(&context.venv, &base)?;
+    let user_base = context.temp_dir.child("user");
+
+    // Install one version into the system site-packages, and another into the user site-packages.
+    install_command(&context)
+        .arg("iniconfig==1.1.1")
+        .arg("--python")
+        .arg(&python)
+        .env("PYTHONUSERBASE", user_base.path())
+        .assert()
+        .success();
+    install_command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--user")
+        .arg("--python")
+        .arg(&python)
+        .env("PYTHONUSERBASE", user_base.path())
+        .assert()
+        .success();
+
+    // Only the user package should be uninstalled.
+    uv_snapshot!(uninstall_command(&context)
+        .arg("iniconfig")
+        .arg("--user")
+        .arg("--python")
+        .arg(&python)
+        .env("PYTHONUSERBASE", user_base.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+     - iniconfig==2.0.0
+    "###
+    );
+
+    user_base
+        .child("lib/python3.12/site-packages/iniconfig-2.0.0.dist-info")
+        .assert(predicates::path::missing());
+    base.child("lib/python3.12/site-packages/iniconfig-1.1.1.dist-info")
+        .assert(predicates::path::is_dir());
+
+    // The system package is visible, but isn't uninstalled from the user site.
+    uv_snapshot!(uninstall_command(&context)
+        .arg("iniconfig")
+        .arg("--user")
+        .arg("--python")
+        .arg(&python)
+        .env("PYTHONUSERBASE", user_base.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Skipping iniconfig as it is not installed in the user site.
+    warning: No packages to uninstall.
+    "###
+    );
+
+    base.child("lib/python3.12/site-packages/iniconfig-1.1.1.dist-info")
+        .assert(predicates::path::is_dir());
+
+    Ok(())
+}
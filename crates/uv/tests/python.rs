@@ -0,0 +1,72 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::fixture::PathChild;
+
+use common::uv_snapshot;
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Create a `uv python` command, using an isolated toolchain directory.
+fn command(temp_dir: &assert_fs::TempDir, subcommand: &str) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("python")
+        .arg(subcommand)
+        .env("UV_TOOLCHAIN_DIR", temp_dir.child("toolchains").path())
+        .current_dir(temp_dir);
+    command
+}
+
+/// List toolchains when none are installed.
+#[test]
+fn python_list_empty() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+
+    uv_snapshot!(command(&temp_dir, "list"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No Python toolchains installed
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request a Python version for which no build is available.
+#[test]
+fn python_install_unavailable() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+
+    uv_snapshot!(command(&temp_dir, "install")
+        .arg("2.0"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No download is available for Python 2.0 on this platform
+    "###
+    );
+
+    // An invalid version should be rejected by the parser.
+    uv_snapshot!(command(&temp_dir, "install")
+        .arg("python3.12"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value 'python3.12' for '<TARGETS>...': Invalid Python version: `python3.12`
+
+    For more information, try '--help'.
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+
+use common::{uv_snapshot, venv_to_interpreter, TestContext, EXCLUDE_NEWER};
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Create a `uv run` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("run")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--python")
+        .arg(venv_to_interpreter(&context.venv))
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .current_dir(&context.temp_dir);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (2 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// Run a command with an additional package, then re-use the cached environment.
+#[test]
+fn run_with() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("--with")
+        .arg("iniconfig==2.0.0")
+        .arg("--")
+        .arg("python")
+        .arg("-c")
+        .arg("import iniconfig; print('ok')"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ok
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // The environment should be reused on subsequent invocations.
+    uv_snapshot!(command(&context)
+        .arg("--with")
+        .arg("iniconfig==2.0.0")
+        .arg("--")
+        .arg("python")
+        .arg("-c")
+        .arg("import iniconfig; print('ok')"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ok
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    // The package should not be installed into the virtual environment itself.
+    context.assert_command("import iniconfig").failure();
+
+    Ok(())
+}
+
+/// The exit code of the command should be propagated.
+#[test]
+fn run_exit_code() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("--")
+        .arg("python")
+        .arg("-c")
+        .arg("import sys; sys.exit(3)"), @r###"
+    success: false
+    exit_code: 3
+    ----- stdout -----
+
+    ----- stderr -----
+    "###
+    );
+}
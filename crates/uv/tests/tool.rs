@@ -0,0 +1,181 @@
+#![cfg(all(feature = "python", feature = "pypi", unix))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use common::{uv_snapshot, venv_to_interpreter, TestContext, EXCLUDE_NEWER};
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Create a `uv tool` command with options shared across scenarios.
+///
+/// Tools are installed into, and linked from, the test context's temporary directory.
+fn command(context: &TestContext, subcommand: &str) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("tool")
+        .arg(subcommand)
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_TOOL_DIR", context.temp_dir.child("tools").path())
+        .env("UV_TOOL_BIN_DIR", context.temp_dir.child("bin").path())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Create a `uv tool install` command for the given package.
+fn install(context: &TestContext, package: &str) -> Command {
+    let mut command = command(context, "install");
+    command
+        .arg(package)
+        .arg("--python")
+        .arg(venv_to_interpreter(&context.venv))
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER);
+    command
+}
+
+/// Install a tool, and link its executables into the tool executable directory.
+#[test]
+fn tool_install() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), install(&context, "black==23.10.1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    Downloaded 6 packages in [TIME]
+    Installed 6 packages in [TIME]
+     + black==23.10.1
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==23.2
+     + pathspec==0.11.2
+     + platformdirs==4.0.0
+    Installed 2 executables for `black`: black, blackd
+    warning: `[TEMP_DIR]/bin` is not on your `PATH`; add it to run the installed executables
+    "###
+    );
+
+    context
+        .temp_dir
+        .child("bin")
+        .child("black")
+        .assert(predicates::path::exists());
+    context
+        .temp_dir
+        .child("bin")
+        .child("blackd")
+        .assert(predicates::path::exists());
+
+    // The tool should not be installed into the virtual environment itself.
+    context.assert_command("import black").failure();
+
+    uv_snapshot!(context.filters(), command(&context, "list"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black v23.10.1
+    - black
+    - blackd
+
+    ----- stderr -----
+    "###
+    );
+
+    // Installing the tool again should be a no-op.
+    uv_snapshot!(context.filters(), install(&context, "black==23.10.1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    `black` is already installed (from `black==23.10.1`); use `--force` to reinstall
+    "###
+    );
+
+    Ok(())
+}
+
+/// Upgrade a tool that's already at the latest compatible version.
+#[test]
+fn tool_upgrade() {
+    let context = TestContext::new("3.12");
+
+    install(&context, "black==23.10.1").assert().success();
+
+    uv_snapshot!(context.filters(), command(&context, "upgrade")
+        .arg("black")
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    Audited 6 packages in [TIME]
+    Upgraded `black`
+    "###
+    );
+
+    // Upgrading a tool that isn't installed should fail.
+    uv_snapshot!(context.filters(), command(&context, "upgrade")
+        .arg("flask"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `flask` is not installed
+    "###
+    );
+}
+
+/// Uninstall a tool, removing its environment and executables.
+#[test]
+fn tool_uninstall() {
+    let context = TestContext::new("3.12");
+
+    install(&context, "black==23.10.1").assert().success();
+
+    uv_snapshot!(context.filters(), command(&context, "uninstall")
+        .arg("black"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled `black` (black, blackd)
+    "###
+    );
+
+    context
+        .temp_dir
+        .child("bin")
+        .child("black")
+        .assert(predicates::path::missing());
+    context
+        .temp_dir
+        .child("tools")
+        .child("black")
+        .assert(predicates::path::missing());
+
+    uv_snapshot!(context.filters(), command(&context, "list"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No tools installed
+    "###
+    );
+}
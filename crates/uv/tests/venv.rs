@@ -5,6 +5,7 @@ use std::process::Command;
 use anyhow::Result;
 use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
+use predicates::prelude::PredicateBooleanExt;
 
 use uv_fs::Simplified;
 
@@ -139,6 +140,115 @@ fn create_venv_defaults_to_cwd() -> Result<()> {
     Ok(())
 }
 
+/// Default the prompt to the project name declared in `pyproject.toml`, unless `--prompt` is
+/// provided.
+#[test]
+fn create_venv_project_prompt() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    temp_dir
+        .child("pyproject.toml")
+        .write_str(indoc::indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.0.0"
+    "#})?;
+
+    Command::new(get_bin())
+        .arg("venv")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    venv.child("pyvenv.cfg")
+        .assert(predicates::str::contains("prompt = example"));
+
+    // An explicit prompt takes precedence.
+    Command::new(get_bin())
+        .arg("venv")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--prompt")
+        .arg("custom")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    venv.child("pyvenv.cfg")
+        .assert(predicates::str::contains("prompt = custom"));
+
+    Ok(())
+}
+
+/// Toggle access to the system site packages for an existing virtual environment.
+#[test]
+fn set_system_site_packages() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyvenv_cfg = context.venv.child("pyvenv.cfg");
+    pyvenv_cfg.assert(predicates::str::contains(
+        "include-system-site-packages = false",
+    ));
+
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("venv")
+        .arg(context.venv.as_os_str())
+        .arg("--set-system-site-packages")
+        .arg("true")
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Enabled system site packages for: .venv
+    "###
+    );
+
+    pyvenv_cfg.assert(predicates::str::contains(
+        "include-system-site-packages = true",
+    ));
+    pyvenv_cfg.assert(predicates::str::contains(format!(
+        "uv = {}",
+        env!("CARGO_PKG_VERSION")
+    )));
+
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("venv")
+        .arg(context.venv.as_os_str())
+        .arg("--set-system-site-packages")
+        .arg("false")
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Disabled system site packages for: .venv
+    "###
+    );
+
+    pyvenv_cfg.assert(predicates::str::contains(
+        "include-system-site-packages = false",
+    ));
+
+    Ok(())
+}
+
 #[test]
 fn seed() -> Result<()> {
     let temp_dir = assert_fs::TempDir::new()?;
@@ -186,6 +296,120 @@ fn seed() -> Result<()> {
     Ok(())
 }
 
+/// Create a relocatable virtual environment, in which the activation scripts and the scripts of
+/// installed packages don't embed the environment's absolute path.
+#[test]
+#[cfg(unix)]
+fn create_venv_relocatable() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--relocatable")
+        .arg("--seed")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    venv.child("pyvenv.cfg")
+        .assert(predicates::str::contains("relocatable = true"));
+
+    let venv_dir = venv.path().simplified_display().to_string();
+    for script in ["activate", "activate.fish", "activate.nu", "activate.bat"] {
+        venv.child("bin")
+            .child(script)
+            .assert(predicates::str::contains(venv_dir.as_str()).not());
+    }
+
+    // The `pip` entrypoint locates the interpreter relative to itself.
+    let pip = fs_err::read_to_string(venv.child("bin").child("pip"))?;
+    assert!(pip.starts_with(
+        "#!/bin/sh\n'''exec' \"$(CDPATH= cd -- \"$(dirname -- \"$0\")\" && pwd -P)\"/'python' \"$0\" \"$@\"\n' '''"
+    ));
+
+    // Once the environment is moved, sourcing the activation script and running the `pip`
+    // entrypoint both use the new location.
+    let moved = temp_dir.child("moved");
+    fs_err::rename(&venv, &moved)?;
+
+    let output = Command::new("bash")
+        .arg("-c")
+        .arg(r#". "$1/bin/activate" && printf '%s' "$VIRTUAL_ENV""#)
+        .arg("bash")
+        .arg(moved.as_os_str())
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        moved.path().simplified_display().to_string()
+    );
+
+    Command::new(moved.child("bin").child("pip"))
+        .arg("--version")
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+/// Every shell gets an activation script, and the PowerShell script uses the platform's `PATH`
+/// separator so that it works under PowerShell Core on Unix.
+#[test]
+#[cfg(unix)]
+fn create_venv_activation_scripts() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    for script in [
+        "activate",
+        "activate.csh",
+        "activate.fish",
+        "activate.nu",
+        "activate.ps1",
+        "activate.bat",
+        "deactivate.bat",
+    ] {
+        venv.child("bin")
+            .child(script)
+            .assert(predicates::path::is_file());
+    }
+
+    venv.child("bin")
+        .child("activate.ps1")
+        .assert(predicates::str::contains(
+            r#""$env:VIRTUAL_ENV/bin:" + $env:PATH"#,
+        ));
+
+    Ok(())
+}
+
 #[test]
 fn seed_older_python_version() -> Result<()> {
     let temp_dir = assert_fs::TempDir::new()?;
@@ -370,6 +594,52 @@ fn create_venv_python_patch() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn create_venv_python_specifiers() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.11", "3.12"])
+        .expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    let filter_venv = regex::escape(&venv.simplified_display().to_string());
+    let filter_prompt = r"Activate with: (?:.*)\\Scripts\\activate";
+    let filters = &[
+        (
+            r"Using Python 3\.11\.\d+ interpreter at: .+",
+            "Using Python 3.11.[X] interpreter at: [PATH]",
+        ),
+        (filter_prompt, "Activate with: source .venv/bin/activate"),
+        (&filter_venv, ".venv"),
+    ];
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--python")
+        .arg(">=3.11,<3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_NO_WRAP", "1")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.11.[X] interpreter at: [PATH]
+    Creating virtualenv at: .venv
+    Activate with: source .venv/bin/activate
+    "###
+    );
+
+    venv.assert(predicates::path::is_dir());
+
+    Ok(())
+}
+
 #[test]
 fn file_exists() -> Result<()> {
     let temp_dir = assert_fs::TempDir::new()?;